@@ -136,12 +136,41 @@ fn string_keys_subslice(c: &mut Criterion) {
     group.finish();
 }
 
+fn u64_keys_large(c: &mut Criterion) {
+    let mut group = c.benchmark_group("u64_keys_large");
+    let keys: Vec<_> = (0..1_000_000u64)
+        .map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+        .collect();
+    let kvs: Vec<_> = keys.iter().map(|&k| (k, k)).collect();
+
+    group.bench_function("FrozenMap", |b| {
+        let map = FrozenMap::from_iter(kvs.clone());
+        let mut i = 0;
+        b.iter(|| {
+            i = (i + 7919) % keys.len();
+            _ = black_box(map.get(&keys[i]));
+        });
+    });
+
+    let map = HashMap::<_, _>::from_iter(kvs.clone());
+    group.bench_function("HashMap", |b| {
+        let mut i = 0;
+        b.iter(|| {
+            i = (i + 7919) % keys.len();
+            _ = black_box(map.get(&keys[i]));
+        });
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     string_keys_length,
     string_keys_subslice,
     u32_keys,
     u32_keys_range,
-    i32_keys
+    i32_keys,
+    u64_keys_large
 );
 criterion_main!(benches);
@@ -1,6 +1,9 @@
-use frozen_collections::frozen_map;
+use frozen_collections::specialized_maps::{LengthMap, StaticMap};
 use frozen_collections::specialized_sets::{CommonSet, Set};
-use frozen_collections::FrozenMap;
+use frozen_collections::{
+    frozen_map, frozen_map_static, frozen_set, static_frozen_map, static_frozen_set, EnumKey,
+    FrozenEnumMap, FrozenKey, FrozenMap, KeyBytes, MapStrategy,
+};
 use std::collections::HashSet;
 
 fn main() {
@@ -9,9 +12,26 @@ fn main() {
 
     test_frozen_map();
     test_frozen_set();
+    test_key_bytes();
+    test_enum_key();
+    test_frozen_key();
 }
 
+static ROUTES: StaticMap<&str, u32> = frozen_map_static!(
+    "/": 1,
+    "/login": 2,
+    "/logout": 3,
+    "/settings": 4,
+    "/settings/profile": 5,
+);
+
+static_frozen_map!(static COLORS: &str => u32, "red": 1, "green": 2, "blue": 3, "yellow": 4);
+
+static_frozen_set!(static PRIMES: u32, 2, 3, 5, 7, 11, 13, 17, 19, 23, 29);
+
 fn test_frozen_map() {
+    const BASE: u32 = 100;
+
     let fm = frozen_map!(
         &str,
         "first_key": (1, "first_value"),
@@ -24,10 +44,114 @@ fn test_frozen_map() {
     );
 
     dbg!(fm);
+
+    let fm = frozen_map!(u16, 10: "ten", 200: "two hundred", 3000: "three thousand");
+    assert_eq!(fm.get(&200), Some(&"two hundred"));
+    assert_eq!(fm.get(&201), None);
+
+    let fm = frozen_map!("red": 1, "green": 2, "blue": 3, "yellow": 4);
+    assert_eq!(fm.get("green"), Some(&2));
+
+    let fm = frozen_map!(u32, BASE: "base", BASE + 1: "next", BASE * 2: "double");
+    assert_eq!(fm.get(&101), Some(&"next"));
+
+    assert_eq!(ROUTES.get("/logout"), Some(&3));
+    assert_eq!(ROUTES.get("/signup"), None);
+
+    assert_eq!(COLORS.get("blue"), Some(&3));
+    assert_eq!(COLORS.get("purple"), None);
 }
 
 fn test_frozen_set() {
-    let cs = CommonSet::<_, u8, _>::from([1, 2, 3]);
+    let cs = CommonSet::<_, _>::from([1, 2, 3]);
     let hs = HashSet::from([3, 4, 5]);
     let _u = cs.union(&hs);
+
+    let fs = frozen_set!(
+        &str,
+        "first_value",
+        "second_value",
+        "third_value",
+        "fourth_value"
+    );
+    assert!(fs.contains(&"third_value".to_string()));
+    assert!(!fs.contains(&"fifth_value".to_string()));
+
+    let fs = frozen_set!(u32, 10, 11, 12, 13);
+    assert!(fs.contains(&12));
+    assert!(!fs.contains(&14));
+
+    assert!(PRIMES.contains(&23));
+    assert!(!PRIMES.contains(&25));
+}
+
+#[derive(KeyBytes, PartialEq, Eq, Debug)]
+struct Route {
+    region: String,
+    service: String,
+    endpoint: String,
+}
+
+fn test_key_bytes() {
+    let route = |r: &str, s: &str, e: &str| Route {
+        region: r.to_string(),
+        service: s.to_string(),
+        endpoint: e.to_string(),
+    };
+
+    let lm = LengthMap::<_, _>::from_vec(vec![
+        (route("us", "auth", "login"), 1),
+        (route("eu", "billing", "invoice"), 2),
+        (route("ap", "search", "query"), 3),
+    ]);
+
+    assert_eq!(Some(&2), lm.get(&route("eu", "billing", "invoice")));
+}
+
+#[derive(EnumKey, PartialEq, Eq, Hash)]
+enum Command {
+    Get(u32),
+    Put(String),
+    Ping,
+    Move { x: i32, y: i32 },
+}
+
+fn test_enum_key() {
+    let map = FrozenEnumMap::from_vec(vec![
+        (Command::Get(1), "get one"),
+        (Command::Get(2), "get two"),
+        (Command::Put("x".to_string()), "put x"),
+        (Command::Ping, "ping"),
+        (Command::Move { x: 1, y: 2 }, "move"),
+    ]);
+
+    assert_eq!(Some(&"get two"), map.get(&Command::Get(2)));
+    assert_eq!(Some(&"put x"), map.get(&Command::Put("x".to_string())));
+    assert_eq!(Some(&"move"), map.get(&Command::Move { x: 1, y: 2 }));
+    assert_eq!(None, map.get(&Command::Get(3)));
+}
+
+#[derive(FrozenKey, PartialEq, Eq, Clone, Copy, Debug)]
+enum Status {
+    Ok = 200,
+    NotFound = 404,
+    Teapot = 418,
+    Unavailable = 503,
+}
+
+fn test_frozen_key() {
+    let statuses = [
+        Status::Ok,
+        Status::NotFound,
+        Status::Teapot,
+        Status::Unavailable,
+    ];
+    let map: FrozenMap<_, _> = statuses
+        .iter()
+        .copied()
+        .zip(["ok", "missing", "teapot", "down"])
+        .collect();
+
+    assert_eq!(MapStrategy::IntegerRange, map.strategy());
+    assert_eq!(Some(&"teapot"), map.get(&Status::Teapot));
 }
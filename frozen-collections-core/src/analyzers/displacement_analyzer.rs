@@ -0,0 +1,167 @@
+use crate::analyzers::perfect_hash_analyzer::slot_index;
+
+/// The average number of hash codes sharing a seed.
+const CODES_PER_BUCKET: usize = 4;
+
+/// The number of seeds tried for a bucket before settling for the best one found.
+const MAX_SEEDS: u32 = 64;
+
+/// How much longer than the chains of mixed hash codes the chains of the hash codes as given
+/// must be for the hash codes to be considered skewed.
+const SKEW_FACTOR: usize = 2;
+
+/// Looks for seeds which spread hash codes over `num_slots` slots more evenly than taking
+/// them modulo the number of slots does.
+///
+/// Hash codes with a pattern, like integers which are multiples of each other, can pile up
+/// in a few slots of a table, and a lookup of any key in those slots compares every key they
+/// hold. Such skew is detected by comparing the longest chain of hash codes sharing a slot
+/// with the longest chain left once the hash codes are mixed. When the former is much longer,
+/// the hash codes are grouped into small buckets, and for each bucket, starting with the
+/// largest ones, seeds are tried until one sends its hash codes to slots which aren't full
+/// yet. This is the hash-and-displace approach of perfect hashing, except that a few hash
+/// codes may share a slot, which keeps finding seeds quick.
+///
+/// Returns `None` if the hash codes aren't skewed, or if no seeds shorten the longest chain.
+/// Otherwise, the slot of a hash code is given by [`displaced_slot_index`].
+#[must_use]
+pub fn analyze_displacement(hash_codes: &[u64], num_slots: usize) -> Option<Box<[u32]>> {
+    if hash_codes.is_empty() || num_slots == 0 {
+        return None;
+    }
+
+    let natural = longest_chain(hash_codes.iter().map(|&c| modulo(c, num_slots)), num_slots);
+    let mixed = longest_chain(
+        hash_codes.iter().map(|&c| slot_index(c, 0, num_slots)),
+        num_slots,
+    );
+
+    if natural <= 2 || natural <= mixed * SKEW_FACTOR {
+        return None;
+    }
+
+    let num_buckets = hash_codes.len().div_ceil(CODES_PER_BUCKET);
+    let mut buckets = vec![Vec::new(); num_buckets];
+    for &code in hash_codes {
+        buckets[slot_index(code, 0, num_buckets)].push(code);
+    }
+
+    let mut order: Vec<_> = (0..num_buckets).collect();
+    order.sort_unstable_by_key(|&b| std::cmp::Reverse(buckets[b].len()));
+
+    // slots are filled up to the average load, plus one to leave room for seeds to be found
+    let limit = hash_codes.len().div_ceil(num_slots) + 1;
+
+    let mut seeds = vec![0; num_buckets].into_boxed_slice();
+    let mut counts = vec![0usize; num_slots];
+    let mut candidates = Vec::new();
+    for b in order {
+        let bucket = &buckets[b];
+        if bucket.is_empty() {
+            break;
+        }
+
+        let mut best = (usize::MAX, 1);
+        for seed in 1..=MAX_SEEDS {
+            candidates.clear();
+            candidates.extend(bucket.iter().map(|&c| slot_index(c, seed, num_slots)));
+
+            // the fullest slot the bucket would leave, counting its own codes
+            let fullest = candidates
+                .iter()
+                .map(|&slot| counts[slot] + candidates.iter().filter(|&&s| s == slot).count())
+                .max()
+                .unwrap_or(0);
+
+            if fullest < best.0 {
+                best = (fullest, seed);
+            }
+
+            if fullest <= limit {
+                break;
+            }
+        }
+
+        seeds[b] = best.1;
+        for &code in bucket {
+            counts[slot_index(code, best.1, num_slots)] += 1;
+        }
+    }
+
+    let displaced = counts.into_iter().max().unwrap_or(0);
+    (displaced < natural).then_some(seeds)
+}
+
+/// Returns the slot of a hash code in a table of `num_slots` slots, given the seeds found by
+/// [`analyze_displacement`].
+#[inline]
+#[must_use]
+pub fn displaced_slot_index(hash_code: u64, seeds: &[u32], num_slots: usize) -> usize {
+    let seed = seeds[slot_index(hash_code, 0, seeds.len())];
+    slot_index(hash_code, seed, num_slots)
+}
+
+/// Returns the largest number of slots which are the same.
+fn longest_chain<I>(slots: I, num_slots: usize) -> usize
+where
+    I: Iterator<Item = usize>,
+{
+    let mut counts = vec![0usize; num_slots];
+    slots.fold(0, |longest, slot| {
+        counts[slot] += 1;
+        longest.max(counts[slot])
+    })
+}
+
+/// Maps a hash code to a slot like a table without seeds does.
+#[inline]
+#[allow(clippy::cast_possible_truncation)]
+const fn modulo(hash_code: u64, num_slots: usize) -> usize {
+    // the result is less than num_slots, so narrowing it back can't truncate
+    (hash_code % num_slots as u64) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn skewed_codes_are_spread() {
+        let num_slots = 1009;
+        let codes: Vec<u64> = (0..1000).map(|i| i * 1009 * 7 + i % 5).collect();
+        assert_eq!(
+            200,
+            longest_chain(codes.iter().map(|&c| modulo(c, num_slots)), num_slots)
+        );
+
+        let seeds = analyze_displacement(&codes, num_slots).unwrap();
+        let longest = longest_chain(
+            codes
+                .iter()
+                .map(|&c| displaced_slot_index(c, &seeds, num_slots)),
+            num_slots,
+        );
+        assert!(longest <= 3, "{longest}");
+    }
+
+    #[test]
+    fn balanced_codes_are_left_alone() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let codes: Vec<u64> = (0..10_000).map(|_| rng.random()).collect();
+        assert!(analyze_displacement(&codes, 10_007).is_none());
+
+        let codes: Vec<u64> = (0..10_000).collect();
+        assert!(analyze_displacement(&codes, 10_000).is_none());
+        assert!(analyze_displacement(&[], 10).is_none());
+    }
+
+    #[test]
+    fn equal_codes_are_left_alone() {
+        // equal codes always share a slot, which no seed can change
+        let codes = vec![7u64; 100];
+        assert!(analyze_displacement(&codes, 13).is_none());
+    }
+}
@@ -0,0 +1,114 @@
+/// Finds the entries of a payload which share a key.
+///
+/// Returns one group per repeated key, holding the positions of all of the entries with that
+/// key in increasing order. Groups are sorted by the position of their second entry, so the
+/// first group holds the earliest repetition in the payload. `hash` must return equal codes for
+/// equal keys.
+pub fn find_duplicate_keys<K, V, F>(payload: &[(K, V)], hash: F) -> Vec<Vec<usize>>
+where
+    K: Eq,
+    F: Fn(&K) -> u64,
+{
+    let mut order: Vec<_> = payload
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| (hash(&entry.0), index))
+        .collect();
+
+    // equal keys have equal hash codes, so only keys within a run of equal codes need comparing
+    order.sort_unstable();
+
+    let mut groups = Vec::new();
+    for run in order.chunk_by(|x, y| x.0 == y.0) {
+        let mut seen = vec![false; run.len()];
+        for i in 0..run.len() {
+            if seen[i] {
+                continue;
+            }
+
+            let key = &payload[run[i].1].0;
+            let mut group = vec![run[i].1];
+            for j in i + 1..run.len() {
+                if !seen[j] && payload[run[j].1].0 == *key {
+                    seen[j] = true;
+                    group.push(run[j].1);
+                }
+            }
+
+            if group.len() > 1 {
+                groups.push(group);
+            }
+        }
+    }
+
+    groups.sort_unstable_by_key(|group| group[1]);
+    groups
+}
+
+/// Finds the earliest entry of a payload holding the key of each entry.
+///
+/// Returns one position per entry, so the entries which share a key share a position, that of
+/// the first of them. `hash` must return equal codes for equal keys.
+pub fn find_key_groups<K, V, F>(payload: &[(K, V)], hash: F) -> Vec<usize>
+where
+    K: Eq,
+    F: Fn(&K) -> u64,
+{
+    let mut order: Vec<_> = payload
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| (hash(&entry.0), index))
+        .collect();
+
+    // runs of equal codes are in increasing position, so the first entry of a key leads its group
+    order.sort_unstable();
+
+    let mut first: Vec<_> = (0..payload.len()).collect();
+    for run in order.chunk_by(|x, y| x.0 == y.0) {
+        for i in 0..run.len() {
+            let leader = run[i].1;
+            if first[leader] != leader {
+                continue;
+            }
+
+            for &(_, index) in &run[i + 1..] {
+                if first[index] == index && payload[index].0 == payload[leader].0 {
+                    first[index] = leader;
+                }
+            }
+        }
+    }
+
+    first
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_duplicate_keys() {
+        let payload = [(1, 'a'), (2, 'b'), (1, 'c'), (3, 'd'), (2, 'e'), (1, 'f')];
+        assert_eq!(
+            vec![vec![0, 2, 5], vec![1, 4]],
+            find_duplicate_keys(&payload, |k| *k)
+        );
+
+        // colliding hash codes don't make keys equal
+        assert_eq!(
+            vec![vec![0, 2, 5], vec![1, 4]],
+            find_duplicate_keys(&payload, |_| 0)
+        );
+
+        assert!(find_duplicate_keys(&[(1, ()), (2, ()), (3, ())], |_| 0).is_empty());
+        assert!(find_duplicate_keys::<u8, (), _>(&[], |_| 0).is_empty());
+    }
+
+    #[test]
+    fn test_find_key_groups() {
+        let payload = [(1, 'a'), (2, 'b'), (1, 'c'), (3, 'd'), (2, 'e'), (1, 'f')];
+        assert_eq!(vec![0, 1, 0, 3, 1, 0], find_key_groups(&payload, |k| *k));
+        assert_eq!(vec![0, 1, 0, 3, 1, 0], find_key_groups(&payload, |_| 0));
+        assert!(find_key_groups::<u8, (), _>(&[], |_| 0).is_empty());
+    }
+}
@@ -1,5 +1,15 @@
+use std::time::Instant;
+
 use bitvec::prelude::*;
 
+use crate::analyzers::displacement_analyzer::analyze_displacement;
+#[cfg(feature = "rayon")]
+use crate::analyzers::MIN_PARALLEL_LEN;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(feature = "rayon")]
+use std::sync::atomic::{AtomicU64, Ordering};
+
 /// Results of running an analyzers over a collection of hash code values.
 pub struct HashCodeAnalysisResult {
     /// The recommended hash table size. This is not necessarily optimal, but it's good enough.
@@ -7,11 +17,46 @@ pub struct HashCodeAnalysisResult {
 
     /// The number of collisions when using the recommended table size.
     pub _num_hash_collisions: usize,
+
+    /// Seeds which spread the hash codes over the slots of the table, when the hash codes
+    /// are skewed enough for them to shorten the longest chain of hash codes sharing a slot.
+    ///
+    /// See [`analyze_displacement`] for how the seeds are used.
+    pub displacements: Option<Box<[u32]>>,
+}
+
+/// Limits on the search [`analyze_hash_codes_with`] does for a hash table size.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HashCodeAnalysisOptions {
+    /// When set, the table gets the fewest slots which hold at most this many hash codes
+    /// each on average, rather than the size found to minimize collisions.
+    pub target_load_factor: Option<f64>,
+
+    /// When set, the search stops trying sizes once this instant has passed, keeping the
+    /// best size found so far.
+    pub deadline: Option<Instant>,
+
+    /// Whether the table also gets a filter over the hash codes of its keys, which settles
+    /// most misses before the slots are read.
+    pub prefilter: bool,
 }
 
 /// Given a collection of hash codes, figures out the best hash table size to use to minimize both table size snd collisions.
-#[allow(clippy::cast_possible_truncation)]
 pub fn analyze_hash_codes<I>(hash_codes: I) -> HashCodeAnalysisResult
+where
+    I: Iterator<Item = u64>,
+{
+    analyze_hash_codes_with(hash_codes, &HashCodeAnalysisOptions::default())
+}
+
+/// Figures out a hash table size like [`analyze_hash_codes`], within the given limits.
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_sign_loss)]
+#[allow(clippy::cast_precision_loss)]
+pub fn analyze_hash_codes_with<I>(
+    hash_codes: I,
+    options: &HashCodeAnalysisOptions,
+) -> HashCodeAnalysisResult
 where
     I: Iterator<Item = u64>,
 {
@@ -41,6 +86,17 @@ where
     ];
 
     let hash_codes: Vec<u64> = hash_codes.collect();
+
+    if let Some(load_factor) = options.target_load_factor.filter(|&f| f > 0.0) {
+        let size = ((hash_codes.len() as f64 / load_factor).ceil() as usize).max(1);
+        let mut use_table: BitVec = BitVec::repeat(false, size);
+        return HashCodeAnalysisResult {
+            num_hash_slots: size,
+            _num_hash_collisions: count_collisions(&hash_codes, size, &mut use_table, usize::MAX),
+            displacements: displace(&hash_codes, size, options),
+        };
+    }
+
     let mut acceptable_collisions = if hash_codes.len() < MEDIUM_INPUT_SIZE_THRESHOLD {
         // for small enough inputs, we try for perfection
         0
@@ -97,24 +153,14 @@ where
     for size in sizes {
         if size < min_size {
             continue;
-        } else if size > max_size {
+        } else if size > max_size
+            || (best_size != 0 && options.deadline.is_some_and(|d| Instant::now() >= d))
+        {
             break;
         }
 
-        use_table.fill(false);
-        let mut num_collisions = 0;
-
-        for code in &hash_codes {
-            let slot = (code % (size as u64)) as usize;
-            if use_table[slot] {
-                num_collisions += 1;
-                if num_collisions >= best_num_collisions {
-                    break;
-                }
-            } else {
-                use_table.set(slot, true);
-            }
-        }
+        let num_collisions =
+            count_collisions(&hash_codes, size, &mut use_table, best_num_collisions);
 
         if num_collisions < best_num_collisions {
             if best_size == 0 || num_collisions <= acceptable_collisions {
@@ -140,9 +186,77 @@ where
     HashCodeAnalysisResult {
         num_hash_slots: best_size,
         _num_hash_collisions: best_num_collisions,
+        displacements: displace(&hash_codes, best_size, options),
     }
 }
 
+/// Looks for seeds spreading skewed hash codes over the table, unless time is up.
+fn displace(
+    hash_codes: &[u64],
+    num_hash_slots: usize,
+    options: &HashCodeAnalysisOptions,
+) -> Option<Box<[u32]>> {
+    if options.deadline.is_some_and(|d| Instant::now() >= d) {
+        return None;
+    }
+
+    analyze_displacement(hash_codes, num_hash_slots)
+}
+
+/// Counts the hash codes which land in an already occupied slot of a table with `size` slots.
+///
+/// Counting stops once `limit` collisions have been found, since the caller has no use for a
+/// table size with that many collisions.
+#[allow(clippy::cast_possible_truncation)]
+fn count_collisions(
+    hash_codes: &[u64],
+    size: usize,
+    use_table: &mut BitVec,
+    limit: usize,
+) -> usize {
+    #[cfg(feature = "rayon")]
+    if hash_codes.len() >= MIN_PARALLEL_LEN {
+        return par_count_collisions(hash_codes, size);
+    }
+
+    use_table.fill(false);
+    let mut num_collisions = 0;
+
+    for code in hash_codes {
+        let slot = (code % (size as u64)) as usize;
+        if use_table[slot] {
+            num_collisions += 1;
+            if num_collisions >= limit {
+                break;
+            }
+        } else {
+            use_table.set(slot, true);
+        }
+    }
+
+    num_collisions
+}
+
+/// Counts collisions like [`count_collisions`], spreading the hash codes across threads.
+///
+/// Every code that finds its slot already taken counts as one collision, whichever thread got
+/// there first, so the total is the same as when counting sequentially. It isn't cut short
+/// at a limit, which doesn't change which table size the caller picks.
+#[cfg(feature = "rayon")]
+#[allow(clippy::cast_possible_truncation)]
+fn par_count_collisions(hash_codes: &[u64], size: usize) -> usize {
+    let use_table: Vec<AtomicU64> = (0..size.div_ceil(64)).map(|_| AtomicU64::new(0)).collect();
+
+    hash_codes
+        .par_iter()
+        .filter(|&&code| {
+            let slot = (code % (size as u64)) as usize;
+            let bit = 1 << (slot % 64);
+            use_table[slot / 64].fetch_or(bit, Ordering::Relaxed) & bit != 0
+        })
+        .count()
+}
+
 #[allow(clippy::cast_possible_truncation)]
 #[allow(clippy::cast_sign_loss)]
 #[allow(clippy::cast_precision_loss)]
@@ -239,4 +353,28 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    #[allow(clippy::used_underscore_binding)]
+    fn target_load_factor() {
+        let options = HashCodeAnalysisOptions {
+            target_load_factor: Some(0.5),
+            deadline: None,
+            prefilter: false,
+        };
+
+        let result = analyze_hash_codes_with(0..100, &options);
+        assert_eq!(200, result.num_hash_slots);
+        assert_eq!(0, result._num_hash_collisions);
+
+        let options = HashCodeAnalysisOptions {
+            target_load_factor: Some(4.0),
+            deadline: None,
+            prefilter: false,
+        };
+
+        let result = analyze_hash_codes_with(0..10, &options);
+        assert_eq!(3, result.num_hash_slots);
+        assert_eq!(7, result._num_hash_collisions);
+    }
 }
@@ -1,5 +1,17 @@
 use num_traits::PrimInt;
 
+/// The default smallest share of a key range, in percent, which must be occupied by keys for a
+/// direct-index table to be used rather than hashing.
+pub const DEFAULT_MIN_KEY_DENSITY_PERCENT: u8 = 25;
+
+/// The smallest share of a value range, in percent, which must be occupied by the values of a
+/// set for a bit set to be used rather than hashing.
+pub const MIN_BIT_SET_DENSITY_PERCENT: u8 = 10;
+
+/// The largest number of integer keys which are scanned rather than hashed, when they don't
+/// form a contiguous range.
+pub const MAX_INTEGER_SCANNING_KEYS: usize = 16;
+
 /// How to treat integer keys for best performance.
 #[derive(PartialEq, Eq, Debug)]
 pub enum IntKeyAnalysisResult {
@@ -8,29 +20,118 @@ pub enum IntKeyAnalysisResult {
 
     /// All keys are in a continuous range
     Range,
+
+    /// The keys are in a bounded range which they occupy densely enough for direct indexing
+    Dense,
 }
 
 /// Look for well-known patterns we can optimize for with integer map keys.
-pub fn analyze_int_keys<K, I>(keys: I) -> IntKeyAnalysisResult
+///
+/// Keys which don't form a continuous range are reported as dense when they occupy at least
+/// `min_density_percent` percent of the range between the smallest and largest key.
+///
+/// 128-bit keys which don't fit in 64 bits have both halves of their value folded into their
+/// hash codes, which then no longer follow one another, so such keys are always hashed.
+pub fn analyze_int_keys<K, I>(keys: I, min_density_percent: u8) -> IntKeyAnalysisResult
 where
     K: PrimInt,
     I: Iterator<Item = K>,
 {
+    let signed = K::min_value() < K::zero();
     let mut min = K::max_value();
     let mut max = K::min_value();
-    let mut count = K::zero();
+    let mut count = 0usize;
     for key in keys {
+        let fits = if signed {
+            key.to_i64().is_some()
+        } else {
+            key.to_u64().is_some()
+        };
+
+        if !fits {
+            return IntKeyAnalysisResult::Normal;
+        }
+
         min = min.min(key);
         max = max.max(key);
-        count = count + K::one();
+        count += 1;
+    }
+
+    if count == 0 {
+        return IntKeyAnalysisResult::Normal;
+    }
+
+    // the span between the keys can overflow the key type when it is signed
+    match max.checked_sub(&min).and_then(|span| span.to_u64()) {
+        Some(span) if span == count as u64 - 1 => IntKeyAnalysisResult::Range,
+        Some(span) if is_dense(count, span, min_density_percent) => IntKeyAnalysisResult::Dense,
+        _ => IntKeyAnalysisResult::Normal,
+    }
+}
+
+/// Returns `true` if `count` keys occupy enough of the range of `span + 1` integers.
+///
+/// A threshold of zero is treated as one percent, which keeps the range bounded.
+pub fn is_dense(count: usize, span: u64, min_density_percent: u8) -> bool {
+    count as u128 * 100 >= (u128::from(span) + 1) * u128::from(min_density_percent.max(1))
+}
+
+/// Looks for a continuous run of integer codes, which may wrap around from `u64::MAX` to zero.
+///
+/// Returns the first code of the run, or `None` if the codes aren't continuous or contain
+/// duplicates. The codes are sorted as a side effect.
+pub fn find_code_range(codes: &mut [u64]) -> Option<u64> {
+    codes.sort_unstable();
+
+    let (&first, &last) = (codes.first()?, codes.last()?);
+    let mut start = first;
+    let mut gaps = 0;
+    for pair in codes.windows(2) {
+        if pair[1] == pair[0] {
+            return None;
+        }
+
+        if pair[1] != pair[0] + 1 {
+            start = pair[1];
+            gaps += 1;
+        }
     }
 
-    if count == K::zero() {
-        IntKeyAnalysisResult::Normal
-    } else if max.sub(min) == count - K::one() {
-        IntKeyAnalysisResult::Range
+    match gaps {
+        0 => Some(first),
+        1 if first == 0 && last == u64::MAX => Some(start),
+        _ => None,
+    }
+}
+
+/// Looks for the smallest range of integer codes, which may wrap around from `u64::MAX` to zero,
+/// holding all the codes and occupied by them to at least `min_density_percent` percent.
+///
+/// Returns the first code of the range along with its length, or `None` if the codes aren't
+/// dense enough or contain duplicates. The codes are sorted as a side effect.
+pub fn find_dense_code_range(codes: &mut [u64], min_density_percent: u8) -> Option<(u64, u64)> {
+    codes.sort_unstable();
+
+    let (&first, &last) = (codes.first()?, codes.last()?);
+    let mut start = first;
+    let mut span = last - first;
+    for pair in codes.windows(2) {
+        if pair[1] == pair[0] {
+            return None;
+        }
+
+        // skipping the gap between these codes, the range wraps around from the second one
+        let wrapped_span = pair[0].wrapping_sub(pair[1]);
+        if wrapped_span < span {
+            start = pair[1];
+            span = wrapped_span;
+        }
+    }
+
+    if is_dense(codes.len(), span, min_density_percent) {
+        Some((start, span + 1))
     } else {
-        IntKeyAnalysisResult::Normal
+        None
     }
 }
 
@@ -40,29 +141,121 @@ mod tests {
 
     #[test]
     fn test_analyze_int_keys_normal() {
-        let keys = vec![1, 3, 5, 7, 9];
-        let result = analyze_int_keys(keys.into_iter());
+        let keys = vec![1, 30, 50, 70, 90];
+        let result = analyze_int_keys(keys.into_iter(), DEFAULT_MIN_KEY_DENSITY_PERCENT);
         assert_eq!(result, IntKeyAnalysisResult::Normal);
     }
 
+    #[test]
+    fn test_analyze_int_keys_dense() {
+        let keys = vec![1, 3, 5, 7, 9];
+        assert_eq!(
+            analyze_int_keys(keys.into_iter(), 50),
+            IntKeyAnalysisResult::Dense
+        );
+
+        let keys = vec![0, 100, 200, 399];
+        assert_eq!(
+            analyze_int_keys(keys.into_iter(), 1),
+            IntKeyAnalysisResult::Dense
+        );
+        assert_eq!(
+            analyze_int_keys(i64::MIN..i64::MIN + 2, 100),
+            IntKeyAnalysisResult::Range
+        );
+    }
+
+    #[test]
+    fn test_find_dense_code_range() {
+        assert_eq!(find_dense_code_range(&mut [9, 1, 5], 25), Some((1, 9)));
+        assert_eq!(find_dense_code_range(&mut [9, 1, 5], 50), None);
+        assert_eq!(
+            find_dense_code_range(&mut [2, u64::MAX - 1, 0], 50),
+            Some((u64::MAX - 1, 5))
+        );
+        assert_eq!(find_dense_code_range(&mut [0, 1000], 0), None);
+        assert_eq!(find_dense_code_range(&mut [1, 1, 2], 1), None);
+        assert_eq!(find_dense_code_range(&mut [], 1), None);
+    }
+
     #[test]
     fn test_analyze_int_keys_range() {
         let keys = vec![1, 2, 3, 4, 5];
-        let result = analyze_int_keys(keys.into_iter());
+        let result = analyze_int_keys(keys.into_iter(), DEFAULT_MIN_KEY_DENSITY_PERCENT);
         assert_eq!(result, IntKeyAnalysisResult::Range);
     }
 
+    #[test]
+    fn test_analyze_int_keys_extremes() {
+        let keys: Vec<i8> = vec![-100, 0, 100];
+        assert_eq!(
+            analyze_int_keys(keys.into_iter(), DEFAULT_MIN_KEY_DENSITY_PERCENT),
+            IntKeyAnalysisResult::Normal
+        );
+
+        assert_eq!(
+            analyze_int_keys(0..=u8::MAX, DEFAULT_MIN_KEY_DENSITY_PERCENT),
+            IntKeyAnalysisResult::Range
+        );
+        assert_eq!(
+            analyze_int_keys(i8::MIN..=i8::MAX, DEFAULT_MIN_KEY_DENSITY_PERCENT),
+            IntKeyAnalysisResult::Normal
+        );
+    }
+
+    #[test]
+    fn test_find_code_range() {
+        assert_eq!(find_code_range(&mut [7, 5, 6]), Some(5));
+        assert_eq!(
+            find_code_range(&mut [1, u64::MAX, 0, u64::MAX - 1]),
+            Some(u64::MAX - 1)
+        );
+        assert_eq!(find_code_range(&mut [1, 3]), None);
+        assert_eq!(find_code_range(&mut [1, 1, 2]), None);
+        assert_eq!(find_code_range(&mut [0, 2, u64::MAX]), None);
+        assert_eq!(find_code_range(&mut []), None);
+    }
+
+    #[test]
+    fn test_analyze_int_keys_wide() {
+        assert_eq!(
+            analyze_int_keys(-2..2i128, DEFAULT_MIN_KEY_DENSITY_PERCENT),
+            IntKeyAnalysisResult::Range
+        );
+        assert_eq!(
+            analyze_int_keys(
+                (u128::from(u64::MAX) - 1)..=u128::from(u64::MAX),
+                DEFAULT_MIN_KEY_DENSITY_PERCENT
+            ),
+            IntKeyAnalysisResult::Range
+        );
+        assert_eq!(
+            analyze_int_keys(
+                u128::from(u64::MAX)..=u128::from(u64::MAX) + 1,
+                DEFAULT_MIN_KEY_DENSITY_PERCENT
+            ),
+            IntKeyAnalysisResult::Normal
+        );
+        assert_eq!(
+            analyze_int_keys(
+                i128::from(i64::MAX)..=i128::from(i64::MAX) + 1,
+                DEFAULT_MIN_KEY_DENSITY_PERCENT
+            ),
+            IntKeyAnalysisResult::Normal
+        );
+    }
+
     #[test]
     fn test_analyze_int_keys_empty() {
         let keys: Vec<i32> = vec![];
-        let result = analyze_int_keys(keys.into_iter());
+        let result = analyze_int_keys(keys.into_iter(), DEFAULT_MIN_KEY_DENSITY_PERCENT);
         assert_eq!(result, IntKeyAnalysisResult::Normal);
     }
 
     #[test]
     fn test_analyze_int_keys_single() {
         let keys = vec![1];
-        let result = analyze_int_keys(keys.into_iter());
+        let result = analyze_int_keys(keys.into_iter(), DEFAULT_MIN_KEY_DENSITY_PERCENT);
         assert_eq!(result, IntKeyAnalysisResult::Range);
     }
 }
@@ -1,3 +1,13 @@
+pub mod displacement_analyzer;
+pub mod duplicate_key_analyzer;
 pub mod hash_code_analyzer;
 pub mod int_key_analyzer;
+pub mod prefix_key_analyzer;
+pub mod perfect_hash_analyzer;
 pub mod slice_key_analyzer;
+
+/// The number of items from which the analyzers and hash tables split their work across
+/// threads, when the `rayon` feature is enabled. Below this, the overhead of coordinating
+/// threads outweighs the gains.
+#[cfg(feature = "rayon")]
+pub const MIN_PARALLEL_LEN: usize = 64 * 1024;
@@ -0,0 +1,175 @@
+/// The average number of keys sharing a seed in the first layout tried.
+const KEYS_PER_BUCKET: usize = 4;
+
+/// The number of seeds tried for each bucket, per slot of the table, before giving up on a
+/// layout.
+const ATTEMPTS_PER_SLOT: usize = 32;
+
+/// How to place a set of keys in a minimal perfect hash table.
+pub struct PerfectHashLayout {
+    /// The seed of each bucket of keys.
+    pub seeds: Box<[u32]>,
+
+    /// The slot of each key, in the order in which the hash codes were given.
+    pub slots: Vec<usize>,
+}
+
+/// Looks for a minimal perfect hash function for the given hash codes.
+///
+/// This uses the hash-and-displace approach: keys are grouped into small buckets, and for
+/// each bucket, starting with the largest ones, seeds are tried until one sends every key of
+/// the bucket to a distinct free slot. Every slot ends up holding exactly one key.
+///
+/// When a bucket runs out of seeds to try, the layout is started over with smaller buckets,
+/// down to a single key per bucket, for which a free slot is all but certain to be found.
+///
+/// Returns `None` if two hash codes are equal, since no seed can tell them apart, or if no
+/// layout could be found with any bucket size.
+#[must_use]
+pub fn analyze_perfect_hash(hash_codes: &[u64]) -> Option<PerfectHashLayout> {
+    let mut sorted = hash_codes.to_vec();
+    sorted.sort_unstable();
+    if sorted.windows(2).any(|pair| pair[0] == pair[1]) {
+        return None;
+    }
+
+    let mut keys_per_bucket = KEYS_PER_BUCKET;
+    loop {
+        let num_buckets = hash_codes.len().div_ceil(keys_per_bucket).max(1);
+        if let Some(layout) = displace(hash_codes, num_buckets) {
+            return Some(layout);
+        }
+
+        if keys_per_bucket == 1 {
+            return None;
+        }
+
+        keys_per_bucket /= 2;
+    }
+}
+
+/// Looks for a seed for each of `num_buckets` buckets, giving up as soon as one bucket can't
+/// be placed.
+fn displace(hash_codes: &[u64], num_buckets: usize) -> Option<PerfectHashLayout> {
+    let len = hash_codes.len();
+
+    let mut buckets = vec![Vec::new(); num_buckets];
+    for (index, &code) in hash_codes.iter().enumerate() {
+        buckets[bucket_index(code, num_buckets)].push(index);
+    }
+
+    let mut order: Vec<_> = (0..num_buckets).collect();
+    order.sort_unstable_by_key(|&b| std::cmp::Reverse(buckets[b].len()));
+
+    let mut seeds = vec![0; num_buckets].into_boxed_slice();
+    let mut slots = vec![0; len];
+    let mut taken = vec![false; len];
+    let mut candidates = Vec::new();
+    let attempts = len.saturating_mul(ATTEMPTS_PER_SLOT);
+
+    for b in order {
+        let bucket = &buckets[b];
+        if bucket.is_empty() {
+            break;
+        }
+
+        let mut seed = 0u32;
+        loop {
+            if seed as usize >= attempts {
+                return None;
+            }
+
+            candidates.clear();
+            let fits = bucket.iter().all(|&index| {
+                let slot = slot_index(hash_codes[index], seed, len);
+                let free = !taken[slot] && !candidates.contains(&slot);
+                candidates.push(slot);
+                free
+            });
+
+            if fits {
+                break;
+            }
+
+            seed = seed.checked_add(1)?;
+        }
+
+        seeds[b] = seed;
+        for (&index, &slot) in bucket.iter().zip(&candidates) {
+            slots[index] = slot;
+            taken[slot] = true;
+        }
+    }
+
+    Some(PerfectHashLayout { seeds, slots })
+}
+
+/// Returns the bucket, and therefore the seed, used for a hash code.
+#[inline]
+#[allow(clippy::cast_possible_truncation)]
+pub const fn bucket_index(hash_code: u64, num_buckets: usize) -> usize {
+    // the result is less than num_buckets, so narrowing it back can't truncate
+    (hash_code % num_buckets as u64) as usize
+}
+
+/// Returns the slot of a hash code in a table of `len` slots, given the seed of its bucket.
+#[inline]
+#[allow(clippy::cast_possible_truncation)]
+pub const fn slot_index(hash_code: u64, seed: u32, len: usize) -> usize {
+    let mut x = hash_code ^ (seed as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+
+    // the murmur3 finalizer, so every bit of the code and seed affects the slot
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    x ^= x >> 33;
+
+    (x % len as u64) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn test_analyze_perfect_hash() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for len in [1, 2, 10, 1000, 20_000] {
+            let codes: Vec<u64> = (0..len).map(|_| rng.random()).collect();
+            let layout = analyze_perfect_hash(&codes).unwrap();
+
+            let mut used = vec![false; codes.len()];
+            for (code, &slot) in codes.iter().zip(&layout.slots) {
+                let seed = layout.seeds[bucket_index(*code, layout.seeds.len())];
+                assert_eq!(slot, slot_index(*code, seed, codes.len()));
+                assert!(!used[slot]);
+                used[slot] = true;
+            }
+        }
+    }
+
+    #[test]
+    fn test_analyze_perfect_hash_small_payloads() {
+        let mut rng = StdRng::seed_from_u64(7);
+
+        for len in [2, 3, 5, 10, 50, 100] {
+            for _ in 0..2000 {
+                let codes: Vec<u64> = (0..len).map(|_| rng.random()).collect();
+                assert!(
+                    analyze_perfect_hash(&codes).is_some(),
+                    "no layout for {codes:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_analyze_perfect_hash_duplicate_codes() {
+        assert!(analyze_perfect_hash(&[1, 2, 3, 2]).is_none());
+    }
+}
@@ -0,0 +1,101 @@
+/// The number of keys from which a trie is preferred over binary searching the sorted keys.
+const MIN_TRIE_KEYS: usize = 64;
+
+/// The number of keys nested as prefixes of one another from which a trie is preferred,
+/// since each of them may have to be checked after binary searching the sorted keys.
+const MIN_TRIE_NESTING: usize = 8;
+
+/// How to lay out keys for longest-prefix lookups.
+#[derive(PartialEq, Eq, Debug)]
+pub enum PrefixKeyAnalysisResult {
+    /// Binary search the sorted keys, then walk up the keys which are prefixes of the closest one
+    SortedArray,
+
+    /// Walk down a compressed trie, following the bytes of the query
+    Trie,
+}
+
+/// Finds, for each of the sorted keys, the index of its longest proper prefix among the keys.
+///
+/// The keys must be sorted and free of duplicates.
+pub fn find_parent_prefixes<'a, I>(sorted_keys: I) -> Vec<Option<usize>>
+where
+    I: Iterator<Item = &'a [u8]>,
+{
+    // the keys which are prefixes of the current key, shortest first
+    let mut stack: Vec<(usize, &[u8])> = Vec::new();
+    let mut parents = Vec::new();
+
+    for (index, key) in sorted_keys.enumerate() {
+        while stack.last().is_some_and(|top| !key.starts_with(top.1)) {
+            stack.pop();
+        }
+
+        parents.push(stack.last().map(|top| top.0));
+        stack.push((index, key));
+    }
+
+    parents
+}
+
+/// Picks how to lay out keys for longest-prefix lookups, given the longest proper prefix of
+/// each key as found by [`find_parent_prefixes`].
+#[must_use]
+pub fn analyze_prefix_keys(parents: &[Option<usize>]) -> PrefixKeyAnalysisResult {
+    if parents.len() >= MIN_TRIE_KEYS {
+        return PrefixKeyAnalysisResult::Trie;
+    }
+
+    // parents come before their children, so their nesting is known by the time it's needed
+    let mut nesting = vec![0; parents.len()];
+    for (index, parent) in parents.iter().enumerate() {
+        nesting[index] = parent.map_or(1, |p| nesting[p] + 1);
+        if nesting[index] >= MIN_TRIE_NESTING {
+            return PrefixKeyAnalysisResult::Trie;
+        }
+    }
+
+    PrefixKeyAnalysisResult::SortedArray
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parents(keys: &[&str]) -> Vec<Option<usize>> {
+        find_parent_prefixes(keys.iter().map(|k| k.as_bytes()))
+    }
+
+    #[test]
+    fn test_find_parent_prefixes() {
+        assert_eq!(
+            parents(&["", "a", "a/b", "a/b/c", "a/c", "b", "ba"]),
+            vec![None, Some(0), Some(1), Some(2), Some(1), Some(0), Some(5)]
+        );
+        assert_eq!(parents(&["ab", "b", "bc"]), vec![None, None, Some(1)]);
+        assert_eq!(parents(&[]), Vec::<Option<usize>>::new());
+    }
+
+    #[test]
+    fn test_analyze_prefix_keys() {
+        assert_eq!(
+            analyze_prefix_keys(&parents(&["a", "b", "c"])),
+            PrefixKeyAnalysisResult::SortedArray
+        );
+
+        let nested = [
+            "a", "aa", "aaa", "aaaa", "aaaaa", "aaaaaa", "aaaaaaa", "aaaaaaaa",
+        ];
+        assert_eq!(
+            analyze_prefix_keys(&parents(&nested)),
+            PrefixKeyAnalysisResult::Trie
+        );
+
+        let many: Vec<_> = (0..MIN_TRIE_KEYS).map(|i| format!("{i:03}")).collect();
+        let many: Vec<_> = many.iter().map(String::as_str).collect();
+        assert_eq!(
+            analyze_prefix_keys(&parents(&many)),
+            PrefixKeyAnalysisResult::Trie
+        );
+    }
+}
@@ -1,6 +1,27 @@
 use std::cmp::{max, min};
 use std::collections::{HashMap, HashSet};
-use std::hash::{BuildHasher, Hash};
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::time::Instant;
+
+#[cfg(feature = "rayon")]
+use crate::analyzers::MIN_PARALLEL_LEN;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// The longest subslice considered, which constrains the amount of work done by the analysis.
+const MAX_SUBSLICE_LENGTH_LIMIT: usize = 16;
+
+/// The share of subslices which may be duplicates while still being considered unique.
+const ACCEPTABLE_DUPLICATE_PERCENT: f64 = 0.05;
+
+/// The longest region considered on either side of a split subslice.
+const MAX_SPLIT_REGION_LEN: usize = 4;
+
+/// How far from its own end of the keys a region of a split subslice may start.
+const MAX_SPLIT_REGION_INDEX: usize = 8;
+
+/// The longest subslice considered alongside the length of keys.
+const MAX_LENGTH_SUBSLICE_LEN: usize = 4;
 
 /// How to treat keys which are slices for best performance.
 #[derive(PartialEq, Eq, Debug)]
@@ -20,10 +41,52 @@ pub enum SliceKeyAnalysisResult {
         subslice_len: usize,
     },
 
+    /// Hash a left-justified subslice together with a right-justified subslice
+    SplitSubslice {
+        left_index: usize,
+        left_len: usize,
+        right_index: usize,
+        right_len: usize,
+    },
+
+    /// Hash a left-justified subslice together with the length of the slices
+    LengthAndSubslice {
+        subslice_index: usize,
+        subslice_len: usize,
+    },
+
     /// Use the length of the slices as hash codes, instead of hashing the slices
     Length,
 }
 
+/// Limits on the work [`analyze_slice_keys_with`] does.
+#[derive(Clone, Copy, Debug)]
+pub struct SliceKeyAnalysisOptions {
+    /// Whether slice lengths may be used as hash codes.
+    pub lengths: bool,
+
+    /// Whether subslices may be hashed instead of whole slices.
+    pub subslices: bool,
+
+    /// When set, subslices are searched for across a random sample of this many slices, and
+    /// the subslice found is then verified across all of them.
+    pub sample_size: Option<usize>,
+
+    /// When set, the search for subslices gives up once this instant has passed.
+    pub deadline: Option<Instant>,
+}
+
+impl Default for SliceKeyAnalysisOptions {
+    fn default() -> Self {
+        Self {
+            lengths: true,
+            subslices: true,
+            sample_size: None,
+            deadline: None,
+        }
+    }
+}
+
 /// Look for well-known patterns we can optimize for map keys.
 ///
 /// The idea here is to find the shortest subslice across all the input slices which are maximally unique. A corresponding
@@ -34,36 +97,172 @@ pub enum SliceKeyAnalysisResult {
 /// enough uniqueness factor. We look at all the slices both left-justified and right-justified as this maximizes
 /// the opportunities to find unique subslices, especially in the case of many slices with the same prefix or suffix.
 ///
+/// When no single subslice is unique enough, we look for a short subslice which is unique enough
+/// once combined with the length of the slices. Failing that, we look for a pair of short subslices, one left-justified
+/// and one right-justified, which are unique enough together. This catches structured identifiers
+/// which vary at both ends, like `us-east-1-i-0abc123`.
+///
 /// We also analyze the length of the input slices. If the length of the slices are sufficiently unique,
 /// we can totally skip hashing and just use their lengths as hash codes.
-pub fn analyze_slice_keys<'a, K, I, BH>(keys: I, bh: &BH) -> SliceKeyAnalysisResult
+pub fn analyze_slice_keys<'a, I, BH>(keys: I, bh: &BH) -> SliceKeyAnalysisResult
+where
+    I: Iterator<Item = &'a [u8]>,
+    BH: BuildHasher,
+{
+    analyze_slice_keys_with(keys, bh, &SliceKeyAnalysisOptions::default())
+}
+
+/// Look for well-known patterns like [`analyze_slice_keys`], within the given limits.
+pub fn analyze_slice_keys_with<'a, I, BH>(
+    keys: I,
+    bh: &BH,
+    options: &SliceKeyAnalysisOptions,
+) -> SliceKeyAnalysisResult
 where
-    K: Hash + 'a,
-    I: Iterator<Item = &'a [K]>,
+    I: Iterator<Item = &'a [u8]>,
     BH: BuildHasher,
 {
-    let keys = keys.collect();
+    let keys: Vec<&[u8]> = keys.collect();
 
     // first, see if we can just use slice lengths as hash codes
-    let result = analyze_lengths(&keys);
+    if options.lengths {
+        let result = analyze_lengths(&keys);
+        if result != SliceKeyAnalysisResult::Normal {
+            return result;
+        }
+    }
 
-    if result == SliceKeyAnalysisResult::Normal {
-        // if we can't use slice lengths, look for suitable subslices
-        analyze_subslices(&keys, bh)
-    } else {
-        result
+    if !options.subslices {
+        return SliceKeyAnalysisResult::Normal;
+    }
+
+    // if we can't use slice lengths, look for suitable subslices
+    let mut sample_size = options
+        .sample_size
+        .filter(|&sample_size| sample_size > 0)
+        .unwrap_or(keys.len());
+    while sample_size < keys.len() {
+        let sample = reservoir_sample(&keys, sample_size, bh);
+        let result = analyze_subslices(&sample, bh, options.deadline);
+
+        // when no subslice tells the sample apart, none tells all the keys apart either
+        if result == SliceKeyAnalysisResult::Normal {
+            return result;
+        }
+
+        // the sample may hide duplicates, so the subslice must hold up across all the keys
+        if verify_subslice(&keys, &result, bh) {
+            return result;
+        }
+
+        if options
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return SliceKeyAnalysisResult::Normal;
+        }
+
+        sample_size = sample_size.saturating_mul(2);
+    }
+
+    analyze_subslices(&keys, bh, options.deadline)
+}
+
+/// Checks that the result of an earlier analysis still suits a set of keys, which is much
+/// cheaper than analyzing them again.
+pub fn verify_slice_keys<'a, I, BH>(
+    keys: I,
+    result: &SliceKeyAnalysisResult,
+    bh: &BH,
+) -> bool
+where
+    I: Iterator<Item = &'a [u8]>,
+    BH: BuildHasher,
+{
+    let keys: Vec<&[u8]> = keys.collect();
+    match result {
+        SliceKeyAnalysisResult::Normal => true,
+        SliceKeyAnalysisResult::Length => analyze_lengths(&keys) == SliceKeyAnalysisResult::Length,
+        _ => verify_subslice(&keys, result, bh),
+    }
+}
+
+/// Picks `sample_size` keys at random, each key being equally likely to be picked.
+///
+/// The hasher stands in for a random number generator, which keeps the sample reproducible
+/// for seeded hashers.
+#[allow(clippy::cast_possible_truncation)]
+fn reservoir_sample<'a, BH>(keys: &[&'a [u8]], sample_size: usize, bh: &BH) -> Vec<&'a [u8]>
+where
+    BH: BuildHasher,
+{
+    let mut sample = keys[..sample_size].to_vec();
+    for (i, key) in keys.iter().enumerate().skip(sample_size) {
+        let j = (bh.hash_one(i) % (i as u64 + 1)) as usize;
+        if j < sample_size {
+            sample[j] = key;
+        }
+    }
+
+    sample
+}
+
+/// Checks that a subslice found across a sample of the keys tells apart all of them.
+fn verify_subslice<BH>(keys: &Vec<&[u8]>, result: &SliceKeyAnalysisResult, bh: &BH) -> bool
+where
+    BH: BuildHasher,
+{
+    let regions = match *result {
+        SliceKeyAnalysisResult::LeftHandSubslice {
+            subslice_index,
+            subslice_len,
+        } => vec![Region::left(subslice_index, subslice_len)],
+        SliceKeyAnalysisResult::RightHandSubslice {
+            subslice_index,
+            subslice_len,
+        } => vec![Region::right(subslice_index, subslice_len)],
+        SliceKeyAnalysisResult::SplitSubslice {
+            left_index,
+            left_len,
+            right_index,
+            right_len,
+        } => vec![
+            Region::left(left_index, left_len),
+            Region::right(right_index, right_len),
+        ],
+        SliceKeyAnalysisResult::LengthAndSubslice {
+            subslice_index,
+            subslice_len,
+        } => vec![Region::left(subslice_index, subslice_len), Region::Length],
+        _ => return false,
+    };
+
+    // keys outside the sample may be too short for the subslice
+    if keys.iter().any(|s| regions.iter().any(|r| !r.fits(s))) {
+        return false;
     }
+
+    let mut set = HashSet::with_capacity(keys.len());
+    is_sufficiently_unique(
+        keys,
+        &regions,
+        &mut set,
+        acceptable_duplicates(keys.len()),
+        bh,
+    )
+}
+
+/// Returns how many duplicate subslices are tolerated across this many keys.
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_sign_loss)]
+#[allow(clippy::cast_precision_loss)]
+fn acceptable_duplicates(len: usize) -> usize {
+    ((len as f64) * ACCEPTABLE_DUPLICATE_PERCENT) as usize
 }
 
 /// See if we can use slice lengths instead of hashing
 fn analyze_lengths<T>(keys: &Vec<&[T]>) -> SliceKeyAnalysisResult {
     const MAX_IDENTICAL_LENGTHS: usize = 3;
-    const MAX_SLICES: usize = 255;
-
-    if keys.len() > MAX_SLICES {
-        // if there are a lof of slices, assume we'll get too many length collisions
-        return SliceKeyAnalysisResult::Normal;
-    }
 
     let mut lengths = HashMap::new();
     for s in keys {
@@ -83,18 +282,14 @@ fn analyze_lengths<T>(keys: &Vec<&[T]>) -> SliceKeyAnalysisResult {
 }
 
 /// See if we can use subslices to reduce the time spent hashing
-#[allow(clippy::cast_possible_truncation)]
-#[allow(clippy::cast_sign_loss)]
-#[allow(clippy::cast_precision_loss)]
-fn analyze_subslices<T, BH>(keys: &Vec<&[T]>, bh: &BH) -> SliceKeyAnalysisResult
+fn analyze_subslices<BH>(
+    keys: &Vec<&[u8]>,
+    bh: &BH,
+    deadline: Option<Instant>,
+) -> SliceKeyAnalysisResult
 where
-    T: Hash,
     BH: BuildHasher,
 {
-    // constrain the amount of work we do in this code
-    const MAX_SUBSLICE_LENGTH_LIMIT: usize = 16;
-    const ACCEPTABLE_DUPLICATE_PERCENT: f64 = 0.05;
-
     let mut min_len = usize::MAX;
     let mut max_len = 0;
     for s in keys {
@@ -103,7 +298,7 @@ where
     }
 
     // tolerate a certain amount of duplicate subslices
-    let acceptable_duplicates = ((keys.len() as f64) * ACCEPTABLE_DUPLICATE_PERCENT) as usize;
+    let acceptable_duplicates = acceptable_duplicates(keys.len());
 
     // this set is reused for each call to is_sufficiently_unique
     let mut set = HashSet::with_capacity(keys.len());
@@ -113,15 +308,17 @@ where
 
     let mut subslice_len = 1;
     while subslice_len <= max_subslice_len {
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            break;
+        }
+
         // For each index, get a uniqueness factor for the left-justified subslices.
         // If any is above our threshold, we're done.
         let mut subslice_index = 0;
         while subslice_index <= min_len - subslice_len {
             if is_sufficiently_unique(
                 keys,
-                subslice_index,
-                subslice_len,
-                true,
+                &[Region::left(subslice_index, subslice_len)],
                 &mut set,
                 acceptable_duplicates,
                 bh,
@@ -150,9 +347,7 @@ where
             while subslice_index <= min_len - subslice_len {
                 if is_sufficiently_unique(
                     keys,
-                    subslice_index,
-                    subslice_len,
-                    false,
+                    &[Region::right(subslice_index, subslice_len)],
                     &mut set,
                     acceptable_duplicates,
                     bh,
@@ -170,35 +365,209 @@ where
         subslice_len += 1;
     }
 
-    // could not find a subslice that was good enough.
+    // could not find a single subslice that was good enough, so see whether lengths help
+    if min_len != max_len {
+        let result =
+            analyze_length_subslices(keys, min_len, &mut set, acceptable_duplicates, bh, deadline);
+
+        if result != SliceKeyAnalysisResult::Normal {
+            return result;
+        }
+    }
+
+    // and otherwise try pairs of subslices
+    analyze_split_subslices(
+        keys,
+        min_len,
+        max_len,
+        &mut set,
+        acceptable_duplicates,
+        bh,
+        deadline,
+    )
+}
+
+/// See if a short left-justified subslice combined with the length of the keys is unique
+/// enough, preferring the shortest subslice.
+fn analyze_length_subslices<BH>(
+    keys: &Vec<&[u8]>,
+    min_len: usize,
+    set: &mut HashSet<u64>,
+    acceptable_duplicates: usize,
+    bh: &BH,
+    deadline: Option<Instant>,
+) -> SliceKeyAnalysisResult
+where
+    BH: BuildHasher,
+{
+    for subslice_len in 1..=min(min_len, MAX_LENGTH_SUBSLICE_LEN) {
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            break;
+        }
+
+        for subslice_index in 0..=min_len - subslice_len {
+            if is_sufficiently_unique(
+                keys,
+                &[Region::left(subslice_index, subslice_len), Region::Length],
+                set,
+                acceptable_duplicates,
+                bh,
+            ) {
+                return SliceKeyAnalysisResult::LengthAndSubslice {
+                    subslice_index,
+                    subslice_len,
+                };
+            }
+        }
+    }
+
     SliceKeyAnalysisResult::Normal
 }
 
-fn is_sufficiently_unique<T, BH>(
-    keys: &Vec<&[T]>,
-    subslice_index: usize,
-    subslice_len: usize,
-    left_justified: bool,
+/// See if a left-justified subslice combined with a right-justified one is unique enough,
+/// preferring the shortest combination.
+///
+/// Both regions are kept short and close to their end of the keys, which bounds the number of
+/// combinations checked.
+fn analyze_split_subslices<BH>(
+    keys: &Vec<&[u8]>,
+    min_len: usize,
+    max_len: usize,
+    set: &mut HashSet<u64>,
+    acceptable_duplicates: usize,
+    bh: &BH,
+    deadline: Option<Instant>,
+) -> SliceKeyAnalysisResult
+where
+    BH: BuildHasher,
+{
+    // subslices covering the longest keys in full are no cheaper than hashing keys in full
+    let max_total_len = min(
+        min(min_len, max_len.saturating_sub(1)),
+        2 * MAX_SPLIT_REGION_LEN,
+    );
+    for total_len in 2..=max_total_len {
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            break;
+        }
+
+        for left_len in 1..total_len {
+            let right_len = total_len - left_len;
+            if left_len > MAX_SPLIT_REGION_LEN || right_len > MAX_SPLIT_REGION_LEN {
+                continue;
+            }
+
+            for left_index in 0..=min(min_len - left_len, MAX_SPLIT_REGION_INDEX) {
+                for right_index in 0..=min(min_len - right_len, MAX_SPLIT_REGION_INDEX) {
+                    if is_sufficiently_unique(
+                        keys,
+                        &[
+                            Region::left(left_index, left_len),
+                            Region::right(right_index, right_len),
+                        ],
+                        set,
+                        acceptable_duplicates,
+                        bh,
+                    ) {
+                        return SliceKeyAnalysisResult::SplitSubslice {
+                            left_index,
+                            left_len,
+                            right_index,
+                            right_len,
+                        };
+                    }
+                }
+            }
+        }
+    }
+
+    SliceKeyAnalysisResult::Normal
+}
+
+/// A part of a key which helps tell it apart from other keys.
+#[derive(Clone, Copy)]
+enum Region {
+    /// A run of bytes counted from the start of the key.
+    Left { index: usize, len: usize },
+
+    /// A run of bytes counted back from the end of the key.
+    Right { index: usize, len: usize },
+
+    /// The length of the key.
+    Length,
+}
+
+impl Region {
+    const fn left(index: usize, len: usize) -> Self {
+        Self::Left { index, len }
+    }
+
+    const fn right(index: usize, len: usize) -> Self {
+        Self::Right { index, len }
+    }
+
+    /// Returns whether a key is long enough to hold the region.
+    const fn fits(&self, s: &[u8]) -> bool {
+        match *self {
+            Self::Left { index, len } | Self::Right { index, len } => index + len <= s.len(),
+            Self::Length => true,
+        }
+    }
+
+    /// Feeds the region of a key to a hasher.
+    fn hash<H: Hasher>(&self, s: &[u8], h: &mut H) {
+        match *self {
+            Self::Left { index, len } => subslice(s, index, len, true).hash(h),
+            Self::Right { index, len } => subslice(s, index, len, false).hash(h),
+            Self::Length => s.len().hash(h),
+        }
+    }
+
+    /// Copies the region of a key into `buffer` at `offset`, returning the offset past it.
+    #[cfg(feature = "rayon")]
+    fn pack(&self, s: &[u8], buffer: &mut [u8], offset: usize) -> usize {
+        let bytes = match *self {
+            Self::Left { index, len } => subslice(s, index, len, true),
+            Self::Right { index, len } => subslice(s, index, len, false),
+            Self::Length => {
+                let len = (s.len() as u64).to_le_bytes();
+                buffer[offset..offset + len.len()].copy_from_slice(&len);
+                return offset + len.len();
+            }
+        };
+
+        buffer[offset..offset + bytes.len()].copy_from_slice(bytes);
+        offset + bytes.len()
+    }
+}
+
+/// Checks whether the bytes of the given regions tell the keys apart, with no more than the
+/// acceptable number of duplicates.
+fn is_sufficiently_unique<BH>(
+    keys: &Vec<&[u8]>,
+    regions: &[Region],
     set: &mut HashSet<u64>,
     acceptable_duplicates: usize,
     bh: &BH,
 ) -> bool
 where
-    T: Hash,
     BH: BuildHasher,
 {
+    #[cfg(feature = "rayon")]
+    if keys.len() >= MIN_PARALLEL_LEN {
+        return par_is_sufficiently_unique(keys, regions, acceptable_duplicates);
+    }
+
     set.clear();
 
     let mut acceptable_duplicates = acceptable_duplicates;
     for s in keys {
-        let sub = if left_justified {
-            &s[subslice_index..subslice_index + subslice_len]
-        } else {
-            let start = s.len() - subslice_index - 1;
-            &s[start..start + subslice_len]
-        };
+        let mut h = bh.build_hasher();
+        for region in regions {
+            region.hash(s, &mut h);
+        }
 
-        if !set.insert(bh.hash_one(sub)) {
+        if !set.insert(h.finish()) {
             if acceptable_duplicates == 0 {
                 return false;
             }
@@ -210,11 +579,53 @@ where
     true
 }
 
+/// Checks uniqueness like [`is_sufficiently_unique`], spreading the work across threads.
+///
+/// The regions checked add up to at most 16 bytes, lengths taking 8 of them, so their bytes are
+/// packed into a `u128` without losing information. Sorting these brings duplicates next to each other, which finds
+/// them without hashing and so without sharing the hasher between threads.
+#[cfg(feature = "rayon")]
+fn par_is_sufficiently_unique(
+    keys: &Vec<&[u8]>,
+    regions: &[Region],
+    acceptable_duplicates: usize,
+) -> bool {
+    let mut packed: Vec<u128> = keys
+        .par_iter()
+        .map(|s| {
+            let mut buffer = [0; MAX_SUBSLICE_LENGTH_LIMIT];
+            let mut offset = 0;
+            for region in regions {
+                offset = region.pack(s, &mut buffer, offset);
+            }
+
+            u128::from_le_bytes(buffer)
+        })
+        .collect();
+
+    packed.par_sort_unstable();
+    let duplicates = packed.par_windows(2).filter(|w| w[0] == w[1]).count();
+
+    duplicates <= acceptable_duplicates
+}
+
+/// Returns the subslice of a key at the given position, counting from the start of the key
+/// when left-justified and from its end otherwise.
+fn subslice(s: &[u8], subslice_index: usize, subslice_len: usize, left_justified: bool) -> &[u8] {
+    if left_justified {
+        &s[subslice_index..subslice_index + subslice_len]
+    } else {
+        let start = s.len() - subslice_index - subslice_len;
+        &s[start..start + subslice_len]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::hash::RandomState;
 
     use super::*;
+    use crate::hashers::SeededState;
 
     struct AnalysisTestCase<'a> {
         slices: &'a [&'a str],
@@ -223,7 +634,7 @@ mod tests {
 
     #[test]
     fn analyze_string_keys_test() {
-        const ANALYSIS_TEST_CASES: [AnalysisTestCase; 9] = [
+        const ANALYSIS_TEST_CASES: [AnalysisTestCase; 10] = [
             AnalysisTestCase {
                 slices: &[
                     "AAA", "ABB", "ACC", "ADD", "AEE", "AFF", "AGG", "AHH", "AII", "AJJ", "AKK",
@@ -266,6 +677,15 @@ mod tests {
                     subslice_len: 1,
                 },
             },
+            AnalysisTestCase {
+                slices: &[
+                    "x-10", "xx-11", "x-12", "xx-13", "x-21", "xx-22", "x-23", "xx-24",
+                ],
+                expected: SliceKeyAnalysisResult::RightHandSubslice {
+                    subslice_index: 0,
+                    subslice_len: 2,
+                },
+            },
             AnalysisTestCase {
                 slices: &["ABC", "DEFG", "HIJKL", "MNOPQR", "STUVWX", "YZ"],
                 expected: SliceKeyAnalysisResult::Length,
@@ -292,4 +712,201 @@ mod tests {
             assert_eq!(case.expected, analyze_slice_keys(keys, &RandomState::new()));
         }
     }
+    #[test]
+    fn analysis_options() {
+        let keys: Vec<String> = (0..1000).map(|i| format!("{i:04}")).collect();
+        let analyze = |options: &SliceKeyAnalysisOptions| {
+            analyze_slice_keys_with(
+                keys.iter().map(String::as_bytes),
+                &RandomState::new(),
+                options,
+            )
+        };
+
+        assert_eq!(
+            SliceKeyAnalysisResult::LeftHandSubslice {
+                subslice_index: 1,
+                subslice_len: 3,
+            },
+            analyze(&SliceKeyAnalysisOptions::default())
+        );
+
+        // a small sample can be told apart by fewer digits, which verification rejects
+        let sampled = SliceKeyAnalysisOptions {
+            sample_size: Some(10),
+            ..SliceKeyAnalysisOptions::default()
+        };
+        assert_eq!(
+            SliceKeyAnalysisResult::LeftHandSubslice {
+                subslice_index: 1,
+                subslice_len: 3,
+            },
+            analyze(&sampled)
+        );
+
+        let no_subslices = SliceKeyAnalysisOptions {
+            subslices: false,
+            ..SliceKeyAnalysisOptions::default()
+        };
+        assert_eq!(SliceKeyAnalysisResult::Normal, analyze(&no_subslices));
+
+        let expired = SliceKeyAnalysisOptions {
+            deadline: Some(Instant::now()),
+            ..SliceKeyAnalysisOptions::default()
+        };
+        assert_eq!(SliceKeyAnalysisResult::Normal, analyze(&expired));
+    }
+
+    #[test]
+    fn split_subslices() {
+        // the keys vary at both ends, too far apart for a single subslice to span
+        let keys: Vec<String> = ["us", "eu", "ap", "ca"]
+            .iter()
+            .flat_map(|region| {
+                (0..50).map(move |i| format!("{region}-1/instances/reserved/{i:04}"))
+            })
+            .collect();
+
+        assert_eq!(
+            SliceKeyAnalysisResult::SplitSubslice {
+                left_index: 0,
+                left_len: 1,
+                right_index: 0,
+                right_len: 2,
+            },
+            analyze_slice_keys(keys.iter().map(String::as_bytes), &RandomState::new())
+        );
+
+        let sampled = SliceKeyAnalysisOptions {
+            sample_size: Some(20),
+            ..SliceKeyAnalysisOptions::default()
+        };
+        assert_eq!(
+            SliceKeyAnalysisResult::SplitSubslice {
+                left_index: 0,
+                left_len: 1,
+                right_index: 0,
+                right_len: 2,
+            },
+            analyze_slice_keys_with(
+                keys.iter().map(String::as_bytes),
+                &RandomState::new(),
+                &sampled
+            )
+        );
+    }
+
+    #[test]
+    fn many_lengths() {
+        let keys: Vec<String> = (1..=1000).map(|i| "x".repeat(i)).collect();
+        assert_eq!(
+            SliceKeyAnalysisResult::Length,
+            analyze_slice_keys(keys.iter().map(String::as_bytes), &RandomState::new())
+        );
+    }
+
+    #[test]
+    fn length_and_subslice() {
+        // only the first byte and the length vary, neither enough to tell the keys apart
+        let keys: Vec<String> = ('a'..='t')
+            .flat_map(|c| (0..10).map(move |n| format!("{c}{}", "-".repeat(n + 5))))
+            .collect();
+
+        assert_eq!(
+            SliceKeyAnalysisResult::LengthAndSubslice {
+                subslice_index: 0,
+                subslice_len: 1,
+            },
+            analyze_slice_keys(keys.iter().map(String::as_bytes), &RandomState::new())
+        );
+    }
+
+    #[test]
+    fn reservoir_sampling() {
+        let keys: Vec<String> = (0..1000).map(|i| format!("{i:04}")).collect();
+        let keys: Vec<&[u8]> = keys.iter().map(String::as_bytes).collect();
+        let bh = SeededState::new(42);
+
+        let sample = reservoir_sample(&keys, 50, &bh);
+        assert_eq!(50, sample.len());
+        assert_eq!(sample, reservoir_sample(&keys, 50, &bh));
+        assert_ne!(sample, keys[..50]);
+
+        assert!(verify_subslice(
+            &keys,
+            &SliceKeyAnalysisResult::RightHandSubslice {
+                subslice_index: 0,
+                subslice_len: 3,
+            },
+            &bh
+        ));
+        assert!(!verify_subslice(
+            &keys,
+            &SliceKeyAnalysisResult::RightHandSubslice {
+                subslice_index: 0,
+                subslice_len: 2,
+            },
+            &bh
+        ));
+        assert!(!verify_subslice(
+            &keys,
+            &SliceKeyAnalysisResult::LeftHandSubslice {
+                subslice_index: 2,
+                subslice_len: 3,
+            },
+            &bh
+        ));
+    }
+
+    #[test]
+    fn verify_slice_keys_test() {
+        let bh = RandomState::new();
+        let lengths = ["a", "bb", "ccc", "dddd"];
+        assert!(verify_slice_keys(
+            lengths.iter().map(|s| s.as_bytes()),
+            &SliceKeyAnalysisResult::Length,
+            &bh
+        ));
+
+        let lengths = ["a", "b", "c", "d", "e"];
+        assert!(!verify_slice_keys(
+            lengths.iter().map(|s| s.as_bytes()),
+            &SliceKeyAnalysisResult::Length,
+            &bh
+        ));
+
+        let keys = ["id-1", "id-2", "id-3"];
+        let result = SliceKeyAnalysisResult::RightHandSubslice {
+            subslice_index: 0,
+            subslice_len: 1,
+        };
+        assert!(verify_slice_keys(
+            keys.iter().map(|s| s.as_bytes()),
+            &result,
+            &bh
+        ));
+        assert!(!verify_slice_keys(
+            ["id-1", "id-2", "di-2"].iter().map(|s| s.as_bytes()),
+            &result,
+            &bh
+        ));
+        assert!(verify_slice_keys(
+            keys.iter().map(|s| s.as_bytes()),
+            &SliceKeyAnalysisResult::Normal,
+            &bh
+        ));
+    }
+
+    #[test]
+    fn analyze_many_slice_keys() {
+        // enough keys to take the parallel path when the rayon feature is enabled
+        let keys: Vec<_> = (0..100_000).map(|i| format!("item-{i:06}")).collect();
+        assert_eq!(
+            SliceKeyAnalysisResult::LeftHandSubslice {
+                subslice_index: 6,
+                subslice_len: 5,
+            },
+            analyze_slice_keys(keys.iter().map(String::as_bytes), &RandomState::new())
+        );
+    }
 }
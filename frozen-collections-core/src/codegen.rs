@@ -0,0 +1,477 @@
+//! Generation of Rust source code declaring maps whose layout is computed ahead of time.
+//!
+//! Creating a frozen map analyzes its keys and distributes them over hash slots, which takes
+//! time at startup. When the payload is known at build time, a build script can run that
+//! work once and emit the resulting layout as a [`StaticMap`](crate::specialized_maps::StaticMap)
+//! declared as a `static`, which the program then uses without any construction cost.
+//!
+//! # Examples
+//!
+//! In `build.rs`:
+//!
+//! ```no_run
+//! use frozen_collections_core::codegen::emit_static_map;
+//!
+//! let payload = vec![("red", 1), ("green", 2), ("blue", 3)];
+//! let source = emit_static_map("COLORS", "&str", "u32", payload).unwrap();
+//!
+//! let out_dir = std::env::var("OUT_DIR").unwrap();
+//! std::fs::write(format!("{out_dir}/colors.rs"), source).unwrap();
+//! ```
+//!
+//! And in the crate itself:
+//!
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/colors.rs"));
+//!
+//! assert_eq!(COLORS.get("green"), Some(&2));
+//! ```
+
+use std::borrow::Cow;
+use std::fmt::Write;
+use std::hash::Hash;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::errors::FrozenError;
+use crate::specialized_maps::hash_table::HashTable;
+use crate::specialized_maps::static_map::{static_table, static_table_with};
+use crate::specialized_maps::StaticKeyCode;
+
+/// The path through which the emitted code refers to the static map types.
+const TYPES_PATH: &str = "::frozen_collections::specialized_maps";
+
+/// Emits Rust source declaring a `static` [`StaticMap`](crate::specialized_maps::StaticMap)
+/// named `name` which holds the payload.
+///
+/// The keys are analyzed the same way [`FrozenMap`](crate::facades::FrozenMap) analyzes them,
+/// to pick how hash codes are derived from keys, and the entries are then laid out in hash
+/// slots. `key_type` and `value_type` are the types spelled out in the declaration, such as
+/// `&str` for a payload of `String` keys.
+///
+/// Keys and values are written out as constant expressions through [`RustLiteral`], which is
+/// implemented for integers, floats, `bool`, `char`, strings, slices, and arrays, options, and
+/// tuples of these. Owned strings and slices are written as references, so they suit a
+/// declared type of `&str` or `&[T]`.
+///
+/// Hash codes come from a hashing algorithm owned by this crate, so the emitted code can be
+/// compiled by another toolchain, or for another platform, than the one which ran this
+/// function.
+///
+/// # Errors
+///
+/// Fails if the payload holds duplicate keys, or more entries than fit in a `u32`.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::codegen::emit_static_map;
+///
+/// let source = emit_static_map("PRIMES", "u32", "&str", vec![(2, "two"), (3, "three")]).unwrap();
+/// assert!(source.starts_with("static PRIMES: "));
+/// ```
+#[allow(clippy::module_name_repetitions)]
+pub fn emit_static_map<K, V>(
+    name: &str,
+    key_type: &str,
+    value_type: &str,
+    payload: Vec<(K, V)>,
+) -> Result<String, FrozenError>
+where
+    K: Hash + Eq + RustLiteral,
+    V: RustLiteral,
+{
+    let (key_code, table) = static_table(payload)?;
+    Ok(write_static_map(
+        name, key_type, value_type, &key_code, &table,
+    ))
+}
+
+/// Emits Rust source declaring a `static` [`StaticMap`](crate::specialized_maps::StaticMap)
+/// named `name` which holds the payload, with hash codes derived as `key_code` specifies and
+/// the types spelled out by [`RustLiteral`].
+pub(crate) fn emit_static_map_with<K, V>(
+    name: &str,
+    key_code: &StaticKeyCode,
+    payload: Vec<(K, V)>,
+) -> String
+where
+    K: Hash + RustLiteral,
+    V: RustLiteral,
+{
+    let mut key_type = String::new();
+    K::write_type(&mut key_type);
+    let mut value_type = String::new();
+    V::write_type(&mut value_type);
+
+    let table = static_table_with(payload, key_code);
+    write_static_map(name, &key_type, &value_type, key_code, &table)
+}
+
+/// Writes the declaration of a static map holding the entries laid out in `table`.
+fn write_static_map<K, V>(
+    name: &str,
+    key_type: &str,
+    value_type: &str,
+    key_code: &StaticKeyCode,
+    table: &HashTable<K, V>,
+) -> String
+where
+    K: RustLiteral,
+    V: RustLiteral,
+{
+    let mut source = String::new();
+    _ = writeln!(
+        source,
+        "static {name}: {TYPES_PATH}::StaticMap<{key_type}, {value_type}> = {TYPES_PATH}::StaticMap::from_raw_parts("
+    );
+    _ = writeln!(source, "    {TYPES_PATH}::StaticKeyCode::{key_code:?},");
+
+    source.push_str("    &[");
+    for (index, range) in table.slot_ranges().enumerate() {
+        if index > 0 {
+            source.push_str(", ");
+        }
+
+        _ = write!(source, "({}, {})", range.start, range.end);
+    }
+    source.push_str("],\n");
+
+    source.push_str("    &[\n");
+    for (k, v) in &table.entries {
+        source.push_str("        (");
+        k.write_literal(&mut source);
+        source.push_str(", ");
+        v.write_literal(&mut source);
+        source.push_str("),\n");
+    }
+    source.push_str("    ],\n);\n");
+
+    source
+}
+
+/// A value which can be written out as a Rust constant expression, for the source code emitted
+/// by this module.
+///
+/// The expressions are valid in a `static`. Owned strings, such as `String` or `Box<str>`, are
+/// written as string literals, and owned slices, such as `Vec<T>` or `Box<[T]>`, as references
+/// to arrays, so they suit a declared type of `&str` or `&[T]`.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::codegen::RustLiteral;
+///
+/// let mut source = String::new();
+/// (vec![1u8, 2], "a\"b", Some('c')).write_literal(&mut source);
+///
+/// assert_eq!(source, r#"(&[1, 2], "a\"b", Some('c'))"#);
+/// ```
+pub trait RustLiteral {
+    /// Appends the value to `out`, as a constant expression.
+    fn write_literal(&self, out: &mut String);
+
+    /// Appends the type of the expressions written by [`Self::write_literal`] to `out`.
+    fn write_type(out: &mut String);
+}
+
+macro_rules! debug_literal {
+    ($($t:ty),*) => {
+        $(
+            impl RustLiteral for $t {
+                fn write_literal(&self, out: &mut String) {
+                    _ = write!(out, "{self:?}");
+                }
+
+                fn write_type(out: &mut String) {
+                    out.push_str(stringify!($t));
+                }
+            }
+        )*
+    };
+}
+
+debug_literal!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize
+);
+
+macro_rules! float_literal {
+    ($($t:ident),*) => {
+        $(
+            impl RustLiteral for $t {
+                fn write_literal(&self, out: &mut String) {
+                    if self.is_nan() {
+                        out.push_str(concat!(stringify!($t), "::NAN"));
+                    } else if self.is_infinite() {
+                        out.push_str(if *self > 0.0 {
+                            concat!(stringify!($t), "::INFINITY")
+                        } else {
+                            concat!(stringify!($t), "::NEG_INFINITY")
+                        });
+                    } else {
+                        _ = write!(out, "{self:?}");
+                    }
+                }
+
+                fn write_type(out: &mut String) {
+                    out.push_str(stringify!($t));
+                }
+            }
+        )*
+    };
+}
+
+float_literal!(f32, f64);
+
+macro_rules! str_literal {
+    ($($t:ty),*) => {
+        $(
+            impl RustLiteral for $t {
+                fn write_literal(&self, out: &mut String) {
+                    _ = write!(out, "{:?}", AsRef::<str>::as_ref(self));
+                }
+
+                fn write_type(out: &mut String) {
+                    out.push_str("&str");
+                }
+            }
+        )*
+    };
+}
+
+str_literal!(str, String, Box<str>, Rc<str>, Arc<str>, Cow<'_, str>);
+
+impl<T: ?Sized + RustLiteral> RustLiteral for &T {
+    fn write_literal(&self, out: &mut String) {
+        (**self).write_literal(out);
+    }
+
+    fn write_type(out: &mut String) {
+        T::write_type(out);
+    }
+}
+
+impl<T: RustLiteral> RustLiteral for [T] {
+    fn write_literal(&self, out: &mut String) {
+        out.push('&');
+        write_items(self, out);
+    }
+
+    fn write_type(out: &mut String) {
+        out.push_str("&[");
+        T::write_type(out);
+        out.push(']');
+    }
+}
+
+impl<T: RustLiteral> RustLiteral for Vec<T> {
+    fn write_literal(&self, out: &mut String) {
+        self.as_slice().write_literal(out);
+    }
+
+    fn write_type(out: &mut String) {
+        <[T]>::write_type(out);
+    }
+}
+
+impl<T: RustLiteral> RustLiteral for Box<[T]> {
+    fn write_literal(&self, out: &mut String) {
+        (**self).write_literal(out);
+    }
+
+    fn write_type(out: &mut String) {
+        <[T]>::write_type(out);
+    }
+}
+
+impl<T: RustLiteral, const N: usize> RustLiteral for [T; N] {
+    fn write_literal(&self, out: &mut String) {
+        write_items(self, out);
+    }
+
+    fn write_type(out: &mut String) {
+        out.push('[');
+        T::write_type(out);
+        _ = write!(out, "; {N}]");
+    }
+}
+
+impl<T: RustLiteral> RustLiteral for Option<T> {
+    fn write_literal(&self, out: &mut String) {
+        match self {
+            Some(value) => {
+                out.push_str("Some(");
+                value.write_literal(out);
+                out.push(')');
+            }
+            None => out.push_str("None"),
+        }
+    }
+
+    fn write_type(out: &mut String) {
+        out.push_str("Option<");
+        T::write_type(out);
+        out.push('>');
+    }
+}
+
+impl<A: RustLiteral, B: RustLiteral> RustLiteral for (A, B) {
+    fn write_literal(&self, out: &mut String) {
+        out.push('(');
+        self.0.write_literal(out);
+        out.push_str(", ");
+        self.1.write_literal(out);
+        out.push(')');
+    }
+
+    fn write_type(out: &mut String) {
+        out.push('(');
+        A::write_type(out);
+        out.push_str(", ");
+        B::write_type(out);
+        out.push(')');
+    }
+}
+
+impl<A: RustLiteral, B: RustLiteral, C: RustLiteral> RustLiteral for (A, B, C) {
+    fn write_literal(&self, out: &mut String) {
+        out.push('(');
+        self.0.write_literal(out);
+        out.push_str(", ");
+        self.1.write_literal(out);
+        out.push_str(", ");
+        self.2.write_literal(out);
+        out.push(')');
+    }
+
+    fn write_type(out: &mut String) {
+        out.push('(');
+        A::write_type(out);
+        out.push_str(", ");
+        B::write_type(out);
+        out.push_str(", ");
+        C::write_type(out);
+        out.push(')');
+    }
+}
+
+/// Writes items as an array expression.
+fn write_items<T: RustLiteral>(items: &[T], out: &mut String) {
+    out.push('[');
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            out.push_str(", ");
+        }
+
+        item.write_literal(out);
+    }
+    out.push(']');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn emit_string_keys() {
+        let payload = vec![
+            (String::from("red"), 1),
+            (String::from("blue"), 2),
+            (String::from("green"), 3),
+            (String::from("purple"), 4),
+        ];
+
+        let source = emit_static_map("COLORS", "&str", "u32", payload).unwrap();
+        assert_eq!(
+            source,
+            "static COLORS: ::frozen_collections::specialized_maps::StaticMap<&str, u32> = ::frozen_collections::specialized_maps::StaticMap::from_raw_parts(
+    ::frozen_collections::specialized_maps::StaticKeyCode::Length,
+    &[(0, 1), (1, 2), (2, 3), (3, 4)],
+    &[
+        (\"blue\", 2),
+        (\"green\", 3),
+        (\"purple\", 4),
+        (\"red\", 1),
+    ],
+);
+"
+        );
+    }
+
+    #[test]
+    fn emit_byte_keys() {
+        let payload = vec![(vec![1u8, 2], 'a'), (vec![3u8], 'b')];
+
+        let source = emit_static_map("BYTES", "&[u8]", "char", payload).unwrap();
+        assert!(source.contains("(&[1, 2], 'a')"));
+        assert!(source.contains("(&[3], 'b')"));
+    }
+
+    #[test]
+    fn literals() {
+        fn literal<T: RustLiteral + ?Sized>(value: &T) -> String {
+            let mut out = String::new();
+            value.write_literal(&mut out);
+            out
+        }
+
+        assert_eq!("-3", literal(&-3i64));
+        assert_eq!("'x'", literal(&'x'));
+        assert_eq!("\"a\\nb\"", literal(&String::from("a\nb")));
+        assert_eq!("\"rc\"", literal(&Rc::<str>::from("rc")));
+        assert_eq!("&[1, 2]", literal(&Box::<[u8]>::from([1, 2])));
+        assert_eq!("&[]", literal(&Vec::<u8>::new()));
+        assert_eq!(
+            "[(1, true), (2, false)]",
+            literal(&[(1u8, true), (2, false)])
+        );
+        assert_eq!(
+            "(Some(1.5), None, ())",
+            literal(&(Some(1.5f64), None::<u8>, ()))
+        );
+        assert_eq!("f32::NAN", literal(&f32::NAN));
+        assert_eq!("f64::NEG_INFINITY", literal(&f64::NEG_INFINITY));
+    }
+
+    #[test]
+    fn literal_types() {
+        fn type_of<T: RustLiteral + ?Sized>() -> String {
+            let mut out = String::new();
+            T::write_type(&mut out);
+            out
+        }
+
+        assert_eq!("u16", type_of::<u16>());
+        assert_eq!("&str", type_of::<String>());
+        assert_eq!("&str", type_of::<&str>());
+        assert_eq!("&[u8]", type_of::<Vec<u8>>());
+        assert_eq!("&[u8]", type_of::<&[u8]>());
+        assert_eq!("[char; 3]", type_of::<[char; 3]>());
+        assert_eq!(
+            "(Option<f32>, bool, ())",
+            type_of::<(Option<f32>, bool, ())>()
+        );
+    }
+
+    #[test]
+    fn emit_rejects_bad_payloads() {
+        assert_eq!(
+            emit_static_map("MAP", "u32", "u32", vec![(1, 1), (2, 2), (1, 3)]),
+            Err(FrozenError::DuplicateKey { index: 2 })
+        );
+    }
+}
@@ -0,0 +1,72 @@
+//! Errors reported when a frozen collection can't be created from a payload.
+
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result};
+
+/// Describes why a frozen collection couldn't be created.
+///
+/// This is returned by the `try_` constructors, which let callers handle bad input instead of
+/// panicking like the regular constructors do.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+#[allow(clippy::module_name_repetitions)]
+pub enum FrozenError {
+    /// The payload holds more entries than the collection's index type can address.
+    CapacityExceeded {
+        /// The number of entries in the payload.
+        len: usize,
+
+        /// The largest number of entries the collection can hold.
+        max: usize,
+    },
+
+    /// The payload holds the same key more than once.
+    DuplicateKey {
+        /// The position in the payload of the first entry which repeats the key of an earlier
+        /// entry.
+        index: usize,
+    },
+
+    /// The keys of an integer range collection don't form a continuous range.
+    NonContiguousRange,
+
+    /// The keys of a dense integer collection are too spread out over the range they cover.
+    SparseRange,
+
+    /// No minimal perfect hash function could be found for the keys of a perfect hash
+    /// collection.
+    NoPerfectHash,
+
+    /// The keys can't be handled by the implementation requested for the collection, such as
+    /// string keys for an integer map.
+    IncompatibleStrategy,
+}
+
+impl Display for FrozenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::CapacityExceeded { len, max } => write!(
+                f,
+                "the payload has {len} entries, but the collection can hold at most {max}"
+            ),
+            Self::DuplicateKey { index } => write!(
+                f,
+                "the payload entry at index {index} repeats the key of an earlier entry"
+            ),
+            Self::NonContiguousRange => {
+                write!(f, "the keys don't form a continuous range")
+            }
+            Self::SparseRange => {
+                write!(f, "the keys are too sparse for direct indexing")
+            }
+            Self::NoPerfectHash => {
+                write!(f, "no perfect hash function could be found for the keys")
+            }
+            Self::IncompatibleStrategy => {
+                write!(f, "the keys don't suit the requested implementation")
+            }
+        }
+    }
+}
+
+impl Error for FrozenError {}
@@ -0,0 +1,236 @@
+use std::time::{Duration, Instant};
+
+use crate::analyzers::hash_code_analyzer::HashCodeAnalysisOptions;
+use crate::analyzers::int_key_analyzer::DEFAULT_MIN_KEY_DENSITY_PERCENT;
+use crate::analyzers::slice_key_analyzer::SliceKeyAnalysisOptions;
+
+/// Controls the analysis a [`FrozenMap`](crate::facades::FrozenMap) performs on its keys,
+/// trading construction time against lookup speed.
+///
+/// By default, every specialization is considered and the analysis takes as long as it
+/// needs. Bounding the analysis, or ruling out specializations, makes maps quicker to build
+/// but possibly slower to probe.
+///
+/// # Examples
+///
+/// ```
+/// use std::hash::RandomState;
+/// use std::time::Duration;
+///
+/// use frozen_collections_core::facades::{AnalysisOptions, FrozenMap, MapStrategy};
+///
+/// let options = AnalysisOptions::new()
+///     .max_duration(Duration::from_millis(10))
+///     .slice_maps(false)
+///     .target_load_factor(0.5);
+///
+/// let payload: Vec<_> = (0..100).map(|i| (format!("key{i:03}"), i)).collect();
+/// let map = FrozenMap::from_vec_with_options(payload, RandomState::new(), options);
+///
+/// assert_eq!(map.strategy(), MapStrategy::Common);
+/// assert_eq!(map.bucket_count(), 200);
+/// assert_eq!(map.get("key042"), Some(&42));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct AnalysisOptions {
+    max_duration: Option<Duration>,
+    slice_sample_size: Option<usize>,
+    target_load_factor: Option<f64>,
+    min_key_density: u8,
+    integer_maps: bool,
+    dense_integer_maps: bool,
+    slice_maps: bool,
+    length_maps: bool,
+    perfect_hash_maps: bool,
+    prefilter: bool,
+    prehashed_keys: bool,
+}
+
+impl AnalysisOptions {
+    /// Creates options which consider every specialization, without bounding the analysis.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            max_duration: None,
+            slice_sample_size: None,
+            target_load_factor: None,
+            min_key_density: DEFAULT_MIN_KEY_DENSITY_PERCENT,
+            integer_maps: true,
+            dense_integer_maps: true,
+            slice_maps: true,
+            length_maps: true,
+            perfect_hash_maps: true,
+            prefilter: false,
+            prehashed_keys: false,
+        }
+    }
+
+    /// Bounds the time spent looking for subslices which tell keys apart and for a hash table
+    /// size which keeps collisions down.
+    ///
+    /// Once the time is up, keys are hashed in full and the best table size found so far is
+    /// used. Perfect hashing isn't attempted either, since finding a perfect hash function
+    /// can't be cut short.
+    ///
+    /// Where the analysis stops then depends on the speed and load of the machine, so the same
+    /// payload can be given a different layout from one run to the next. This defeats the
+    /// purpose of a [`SeededState`](crate::hashers::SeededState), which is to make layouts
+    /// reproducible, so maps built with one shouldn't bound their analysis.
+    #[must_use]
+    pub const fn max_duration(mut self, duration: Duration) -> Self {
+        self.max_duration = Some(duration);
+        self
+    }
+
+    /// Searches for subslices which tell keys apart across a random sample of this many keys,
+    /// rather than across all of them.
+    ///
+    /// The subslice found is then checked once across the full payload. When it falls short,
+    /// the search is repeated over a sample twice the size, so large payloads with keys which
+    /// are easy to tell apart are analyzed in a fraction of the time.
+    #[must_use]
+    pub const fn slice_sample_size(mut self, keys: usize) -> Self {
+        self.slice_sample_size = Some(keys);
+        self
+    }
+
+    /// Sizes hash tables to hold this many entries per slot on average, rather than searching
+    /// for the size which keeps collisions down.
+    ///
+    /// Lower factors spend memory to reduce collisions.
+    #[must_use]
+    pub const fn target_load_factor(mut self, load_factor: f64) -> Self {
+        self.target_load_factor = Some(load_factor);
+        self
+    }
+
+    /// Sets the smallest share of their range, in percent, which integer keys must occupy to
+    /// be indexed directly instead of hashed. Defaults to 25 percent.
+    #[must_use]
+    pub const fn min_key_density(mut self, percent: u8) -> Self {
+        self.min_key_density = percent;
+        self
+    }
+
+    /// Sets whether keys which write a single integer to a hasher may be treated as integers,
+    /// rather than hashed like other keys.
+    #[must_use]
+    pub const fn integer_maps(mut self, enabled: bool) -> Self {
+        self.integer_maps = enabled;
+        self
+    }
+
+    /// Sets whether integer keys which fill most of their range may be indexed directly.
+    #[must_use]
+    pub const fn dense_integer_maps(mut self, enabled: bool) -> Self {
+        self.dense_integer_maps = enabled;
+        self
+    }
+
+    /// Sets whether keys may be hashed by a subslice which tells them apart.
+    #[must_use]
+    pub const fn slice_maps(mut self, enabled: bool) -> Self {
+        self.slice_maps = enabled;
+        self
+    }
+
+    /// Sets whether the lengths of keys may be used as their hash codes.
+    #[must_use]
+    pub const fn length_maps(mut self, enabled: bool) -> Self {
+        self.length_maps = enabled;
+        self
+    }
+
+    /// Sets whether large payloads may be placed with a perfect hash function.
+    #[must_use]
+    pub const fn perfect_hash_maps(mut self, enabled: bool) -> Self {
+        self.perfect_hash_maps = enabled;
+        self
+    }
+
+    /// Sets whether maps which hash their keys also get a compact filter over the hash codes
+    /// of their keys.
+    ///
+    /// The filter takes about 10 bits per key, and settles most lookups of missing keys with a
+    /// single cache line read, before the map's own layout is touched. This pays off for large
+    /// maps which are mostly probed with keys they don't hold, and only slows down the other
+    /// lookups.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::{AnalysisOptions, FrozenMap};
+    /// use std::hash::RandomState;
+    ///
+    /// let payload: Vec<_> = (0..10_000).map(|i| (format!("user-{i}"), i)).collect();
+    /// let options = AnalysisOptions::new().prefilter(true);
+    /// let map = FrozenMap::from_vec_with_options(payload, RandomState::new(), options);
+    ///
+    /// assert_eq!(map.get("user-42"), Some(&42));
+    /// assert_eq!(map.get("guest-42"), None);
+    /// ```
+    #[must_use]
+    pub const fn prefilter(mut self, enabled: bool) -> Self {
+        self.prefilter = enabled;
+        self
+    }
+
+    /// Treats the keys as hashes already, which the hash builder passes through.
+    pub(crate) const fn prehashed_keys(mut self) -> Self {
+        self.prehashed_keys = true;
+        self
+    }
+
+    pub(crate) const fn has_prehashed_keys(&self) -> bool {
+        self.prehashed_keys
+    }
+
+    pub(crate) const fn allows_integer_maps(&self) -> bool {
+        self.integer_maps
+    }
+
+    pub(crate) const fn allows_dense_integer_maps(&self) -> bool {
+        self.dense_integer_maps
+    }
+
+    pub(crate) const fn allows_perfect_hash_maps(&self) -> bool {
+        self.perfect_hash_maps
+    }
+
+    pub(crate) const fn wants_prefilter(&self) -> bool {
+        self.prefilter
+    }
+
+    pub(crate) const fn key_density(&self) -> u8 {
+        self.min_key_density
+    }
+
+    /// Returns when the analysis must be over, for an analysis which started at `start`.
+    pub(crate) fn deadline(&self, start: Instant) -> Option<Instant> {
+        self.max_duration.and_then(|d| start.checked_add(d))
+    }
+
+    pub(crate) const fn slice_options(&self, deadline: Option<Instant>) -> SliceKeyAnalysisOptions {
+        SliceKeyAnalysisOptions {
+            lengths: self.length_maps,
+            subslices: self.slice_maps,
+            sample_size: self.slice_sample_size,
+            deadline,
+        }
+    }
+
+    pub(crate) const fn sizing(&self, deadline: Option<Instant>) -> HashCodeAnalysisOptions {
+        HashCodeAnalysisOptions {
+            target_load_factor: self.target_load_factor,
+            deadline,
+            prefilter: self.prefilter,
+        }
+    }
+}
+
+impl Default for AnalysisOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
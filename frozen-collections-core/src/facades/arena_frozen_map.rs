@@ -0,0 +1,213 @@
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::BuildHasher;
+use std::hash::RandomState;
+use std::mem::ManuallyDrop;
+use std::ptr::NonNull;
+
+use crate::facades::FrozenMap;
+use crate::traits::len::Len;
+
+/// A frozen map from strings which copies all its keys into a single buffer.
+///
+/// A map of [`String`] keys holds one heap allocation per key, scattered around memory,
+/// and each key takes 24 bytes in the entries on top of its text. This map instead copies
+/// the text of every key into one contiguous arena when it is created, and its entries only
+/// refer to a span of the arena. This saves an allocation and a third of the per-key
+/// overhead, and keeps the bytes read when hashing keys, or slices of them, close together.
+///
+/// Lookups are otherwise identical to those of a [`FrozenMap`] of strings, including the
+/// analysis of the keys picking the fastest way to tell them apart.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::ArenaFrozenMap;
+///
+/// let map = ArenaFrozenMap::from_vec(vec![
+///     ("red".to_string(), 1),
+///     ("green".to_string(), 2),
+///     ("blue".to_string(), 3),
+/// ]);
+///
+/// assert_eq!(map.get("green"), Some(&2));
+/// assert_eq!(map.get_key_value("blue"), Some(("blue", &3)));
+/// assert_eq!(map.get("yellow"), None);
+/// ```
+#[allow(clippy::module_name_repetitions)]
+pub struct ArenaFrozenMap<V, BH = RandomState> {
+    /// Keys referring to the arena, which are dropped before the arena is freed.
+    ///
+    /// The keys are never handed out with a longer lifetime than a borrow of the map.
+    map: ManuallyDrop<FrozenMap<&'static str, V, BH>>,
+
+    /// The text of every key, one after the other, allocated as a `Box<str>`.
+    ///
+    /// The arena is held through a pointer rather than a box, since moving a box asserts
+    /// unique access to its contents, which the keys share. It's never modified or
+    /// reallocated, so the keys stay valid when the map moves.
+    arena: NonNull<str>,
+}
+
+// SAFETY: the arena is owned by the map and never modified, so it's as safe to send and share
+// as the keys and values of the map are
+unsafe impl<V: Send, BH: Send> Send for ArenaFrozenMap<V, BH> {}
+unsafe impl<V: Sync, BH: Sync> Sync for ArenaFrozenMap<V, BH> {}
+
+impl<V, BH> ArenaFrozenMap<V, BH>
+where
+    BH: BuildHasher,
+{
+    /// Creates a frozen map which will use the given hash builder to hash keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::ArenaFrozenMap;
+    /// use std::hash::RandomState;
+    ///
+    /// let map = ArenaFrozenMap::from_vec_with_hasher(vec![("a", 1)], RandomState::new());
+    /// assert_eq!(map.get("a"), Some(&1));
+    /// ```
+    #[must_use]
+    pub fn from_vec_with_hasher<K>(payload: Vec<(K, V)>, bh: BH) -> Self
+    where
+        K: AsRef<str>,
+    {
+        let mut text = String::with_capacity(payload.iter().map(|x| x.0.as_ref().len()).sum());
+        let mut spans = Vec::with_capacity(payload.len());
+        let mut values = Vec::with_capacity(payload.len());
+        for (k, v) in payload {
+            let start = text.len();
+            text.push_str(k.as_ref());
+            spans.push(start..text.len());
+            values.push(v);
+        }
+
+        let arena = NonNull::from(Box::leak(text.into_boxed_str()));
+
+        // SAFETY: the arena lives on the heap until the map holding the keys is dropped, and
+        // the keys are only handed out tied to a borrow of the map
+        let text: &'static str = unsafe { arena.as_ref() };
+        let keys = spans.into_iter().map(|span| &text[span]);
+
+        Self {
+            map: ManuallyDrop::new(FrozenMap::from_vec_with_hasher(
+                keys.zip(values).collect(),
+                bh,
+            )),
+            arena,
+        }
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self.map.get(key)
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    #[inline]
+    #[must_use]
+    pub fn get_key_value(&self, key: &str) -> Option<(&str, &V)> {
+        self.map.get_key_value(key).map(|(k, v)| (*k, v))
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    #[inline]
+    #[must_use]
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut V> {
+        self.map.get_mut(key)
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    #[inline]
+    #[must_use]
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Returns the number of elements in the map.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns the number of bytes held by the arena, which is the total length of the keys.
+    #[must_use]
+    pub const fn arena_len(&self) -> usize {
+        // SAFETY: the arena is only freed when the map is dropped
+        unsafe { self.arena.as_ref() }.len()
+    }
+
+    /// An iterator visiting all key-value pairs in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &V)> {
+        self.map.iter().map(|(k, v)| (*k, v))
+    }
+
+    /// An iterator visiting all keys in arbitrary order.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.map.keys().copied()
+    }
+
+    /// An iterator visiting all values in arbitrary order.
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.map.values()
+    }
+}
+
+impl<V> ArenaFrozenMap<V, RandomState> {
+    /// Creates a frozen map.
+    #[must_use]
+    pub fn from_vec<K>(payload: Vec<(K, V)>) -> Self
+    where
+        K: AsRef<str>,
+    {
+        Self::from_vec_with_hasher(payload, RandomState::new())
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for ArenaFrozenMap<V, RandomState>
+where
+    K: AsRef<str>,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self::from_vec(Vec::from_iter(iter))
+    }
+}
+
+impl<V, BH> Drop for ArenaFrozenMap<V, BH> {
+    fn drop(&mut self) {
+        // SAFETY: the keys are dropped before the arena they refer to is freed, the arena was
+        // leaked from a box, and neither is used again
+        unsafe {
+            ManuallyDrop::drop(&mut self.map);
+            drop(Box::from_raw(self.arena.as_ptr()));
+        }
+    }
+}
+
+impl<V, BH> Len for ArenaFrozenMap<V, BH>
+where
+    BH: BuildHasher,
+{
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+}
+
+impl<V, BH> Debug for ArenaFrozenMap<V, BH>
+where
+    V: Debug,
+    BH: BuildHasher,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
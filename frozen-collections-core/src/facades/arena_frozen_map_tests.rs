@@ -0,0 +1,83 @@
+use crate::facades::{ArenaFrozenMap, FrozenMapBuilder};
+use crate::traits::len::Len;
+
+#[test]
+fn keys_share_the_arena() {
+    let payloads = [
+        vec![],
+        vec!["a".to_string(), "bb".to_string()],
+        (0..10).map(|i| format!("Key-{i:03}")).collect(),
+        (0..300).map(|i| format!("Key-{i:03}")).collect(),
+        (0..300).map(|i| format!("{i:03}-Key")).collect(),
+        (0..2000).map(|i| format!("{}", i * 7919)).collect(),
+    ];
+
+    for keys in payloads {
+        let mut map: ArenaFrozenMap<_> = keys.iter().zip(0..).collect();
+        assert_eq!(keys.len(), map.len());
+        assert_eq!(keys.len(), Len::len(&map));
+        assert_eq!(keys.iter().map(String::len).sum::<usize>(), map.arena_len());
+
+        for (i, key) in keys.iter().enumerate() {
+            assert_eq!(Some(&i), map.get(key));
+            assert_eq!(Some((key.as_str(), &i)), map.get_key_value(key));
+            assert!(map.contains_key(key));
+            *map.get_mut(key).unwrap() += 1;
+            assert_eq!(Some(&(i + 1)), map.get(key));
+        }
+
+        assert_eq!(None, map.get("missing"));
+        assert!(!map.contains_key(""));
+
+        let mut found: Vec<_> = map.keys().map(str::to_string).collect();
+        found.sort();
+        let mut expected = keys.clone();
+        expected.sort();
+        assert_eq!(expected, found);
+        assert_eq!(map.iter().count(), map.values().count());
+    }
+}
+
+#[test]
+fn outlives_its_payload() {
+    let map = {
+        let payload: Vec<_> = (0..100).map(|i| (format!("k{i}"), i)).collect();
+        ArenaFrozenMap::from_vec(payload)
+    };
+
+    // moving the map doesn't move the arena
+    let moved = Box::new(map);
+    assert_eq!(Some(&42), moved.get("k42"));
+    assert_eq!(
+        r#"{"k1": 1}"#,
+        format!("{:?}", ArenaFrozenMap::from_vec(vec![("k1", 1)]))
+    );
+}
+
+#[test]
+fn builder() {
+    let mut builder = FrozenMapBuilder::new();
+    builder.push("first", 1).push("second", 2).push("third", 3);
+
+    let map = builder.build_arena();
+    assert_eq!(Some(&3), map.get("third"));
+    assert_eq!(16, map.arena_len());
+}
+
+#[test]
+fn sent_across_threads() {
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+    let map = ArenaFrozenMap::from_vec((0..50).map(|i| (format!("k{i}"), i.to_string())).collect());
+    assert_send_sync(&map);
+
+    let map = std::thread::spawn(move || {
+        assert_eq!(Some(&"7".to_string()), map.get("k7"));
+        map
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(50, map.iter().count());
+    drop(map);
+}
@@ -0,0 +1,258 @@
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::RandomState;
+use std::hash::{BuildHasher, Hash, Hasher};
+
+use crate::facades::FrozenMap;
+use crate::specialized_maps::Values;
+use crate::traits::len::Len;
+
+/// A string slice which compares and hashes without regard to ASCII case.
+///
+/// The hash folds ASCII letters to lower case before feeding the bytes to the hasher, so the
+/// slice analysis done by [`FrozenMap`] sees the folded bytes and picks its layout from them.
+/// Non-ASCII characters are compared exactly.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::CaseInsensitiveStr;
+///
+/// assert_eq!(CaseInsensitiveStr::new("Content-Type"), CaseInsensitiveStr::new("content-type"));
+/// assert_ne!(CaseInsensitiveStr::new("Content-Type"), CaseInsensitiveStr::new("content-length"));
+/// ```
+#[repr(transparent)]
+pub struct CaseInsensitiveStr(str);
+
+impl CaseInsensitiveStr {
+    /// Wraps a string slice without copying it.
+    #[must_use]
+    pub const fn new(s: &str) -> &Self {
+        // SAFETY: the type is a transparent wrapper around `str`, so both have the same layout
+        unsafe { &*(std::ptr::from_ref::<str>(s) as *const Self) }
+    }
+
+    /// Returns the wrapped string slice, with its original case.
+    #[must_use]
+    pub const fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for Box<CaseInsensitiveStr> {
+    fn from(s: String) -> Self {
+        // SAFETY: the type is a transparent wrapper around `str`, so both have the same layout
+        unsafe { Self::from_raw(Box::into_raw(s.into_boxed_str()) as *mut CaseInsensitiveStr) }
+    }
+}
+
+impl Clone for Box<CaseInsensitiveStr> {
+    fn clone(&self) -> Self {
+        Self::from(self.0.to_string())
+    }
+}
+
+impl PartialEq for CaseInsensitiveStr {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+impl Eq for CaseInsensitiveStr {}
+
+impl Hash for CaseInsensitiveStr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // fold on the stack, a chunk at a time, so hashing never allocates
+        const CHUNK_LEN: usize = 64;
+
+        let bytes = self.0.as_bytes();
+        let mut buffer = [0; CHUNK_LEN];
+        let mut start = 0;
+        loop {
+            let end = bytes.len().min(start + CHUNK_LEN);
+            let chunk = &mut buffer[..end - start];
+            chunk.copy_from_slice(&bytes[start..end]);
+            chunk.make_ascii_lowercase();
+            state.write(chunk);
+
+            if end == bytes.len() {
+                break;
+            }
+
+            start = end;
+        }
+
+        // same terminator as `str`, so prefixes don't collide
+        state.write_u8(0xff);
+    }
+}
+
+impl Debug for CaseInsensitiveStr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.0.fmt(f)
+    }
+}
+
+/// A frozen map with string keys which are looked up without regard to ASCII case.
+///
+/// This suits protocol identifiers such as HTTP header names, which are ASCII and case
+/// insensitive. Keys keep the case they were given when iterating over the map, while lookups
+/// fold case on the fly, without allocating or requiring callers to normalize their queries.
+///
+/// Keys which only differ by ASCII case are duplicates, which is a logic error just like
+/// duplicate keys in a [`FrozenMap`].
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::CaseInsensitiveFrozenMap;
+///
+/// let map = CaseInsensitiveFrozenMap::from_vec(vec![("Content-Type", 1), ("Accept", 2)]);
+///
+/// assert_eq!(map.get("Content-Type"), Some(&1));
+/// assert_eq!(map.get("content-type"), Some(&1));
+/// assert_eq!(map.get("ACCEPT"), Some(&2));
+/// assert_eq!(map.get("Host"), None);
+/// ```
+#[derive(Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct CaseInsensitiveFrozenMap<V, BH = RandomState> {
+    map: FrozenMap<Box<CaseInsensitiveStr>, V, BH>,
+}
+
+impl<V, BH> CaseInsensitiveFrozenMap<V, BH>
+where
+    BH: BuildHasher,
+{
+    /// Creates a case-insensitive frozen map which will use the given hash builder to hash keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::CaseInsensitiveFrozenMap;
+    /// use std::hash::RandomState;
+    ///
+    /// let map = CaseInsensitiveFrozenMap::from_vec_with_hasher(vec![("Host", 1)], RandomState::new());
+    /// assert_eq!(map.get("HOST"), Some(&1));
+    /// ```
+    #[must_use]
+    pub fn from_vec_with_hasher<K>(payload: Vec<(K, V)>, bh: BH) -> Self
+    where
+        K: Into<String>,
+    {
+        let payload = payload
+            .into_iter()
+            .map(|(k, v)| (Box::from(k.into()), v))
+            .collect();
+
+        Self {
+            map: FrozenMap::from_vec_with_hasher(payload, bh),
+        }
+    }
+
+    /// Returns a reference to the value corresponding to the key, ignoring ASCII case.
+    #[inline]
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self.map.get(CaseInsensitiveStr::new(key))
+    }
+
+    /// Returns the key-value pair corresponding to the key, ignoring ASCII case.
+    ///
+    /// The returned key has the case it was given when the map was created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::CaseInsensitiveFrozenMap;
+    ///
+    /// let map = CaseInsensitiveFrozenMap::from_vec(vec![("Content-Type", 1)]);
+    /// assert_eq!(map.get_key_value("CONTENT-TYPE"), Some(("Content-Type", &1)));
+    /// ```
+    #[inline]
+    pub fn get_key_value(&self, key: &str) -> Option<(&str, &V)> {
+        self.map
+            .get_key_value(CaseInsensitiveStr::new(key))
+            .map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key, ignoring ASCII case.
+    #[inline]
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut V> {
+        self.map.get_mut(CaseInsensitiveStr::new(key))
+    }
+
+    /// Returns `true` if the map contains a value for the key, ignoring ASCII case.
+    #[inline]
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.map.contains_key(CaseInsensitiveStr::new(key))
+    }
+
+    /// An iterator visiting all key-value pairs in arbitrary order, with keys in their original case.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &V)> {
+        self.map.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// An iterator visiting all keys in arbitrary order, in their original case.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.map.keys().map(|k| k.as_str())
+    }
+
+    /// An iterator visiting all values in arbitrary order.
+    pub const fn values(&self) -> Values<'_, Box<CaseInsensitiveStr>, V> {
+        self.map.values()
+    }
+}
+
+impl<V, BH> CaseInsensitiveFrozenMap<V, BH> {
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+impl<V> CaseInsensitiveFrozenMap<V, RandomState> {
+    /// Creates a case-insensitive frozen map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::CaseInsensitiveFrozenMap;
+    ///
+    /// let map = CaseInsensitiveFrozenMap::from_vec(vec![("Host".to_string(), 1)]);
+    /// ```
+    #[must_use]
+    pub fn from_vec<K>(payload: Vec<(K, V)>) -> Self
+    where
+        K: Into<String>,
+    {
+        Self::from_vec_with_hasher(payload, RandomState::new())
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for CaseInsensitiveFrozenMap<V, RandomState>
+where
+    K: Into<String>,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self::from_vec(Vec::from_iter(iter))
+    }
+}
+
+impl<V, BH> Len for CaseInsensitiveFrozenMap<V, BH> {
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+}
+
+impl<V, BH> Debug for CaseInsensitiveFrozenMap<V, BH>
+where
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.map.fmt(f)
+    }
+}
@@ -0,0 +1,54 @@
+use crate::facades::{CaseInsensitiveFrozenMap, CaseInsensitiveStr};
+
+#[test]
+fn test_get() {
+    let payload: Vec<_> = (0..300).map(|i| (format!("X-Header-{i:03}"), i)).collect();
+    let mut m = CaseInsensitiveFrozenMap::from_vec(payload);
+
+    assert_eq!(300, m.len());
+    assert_eq!(Some(&42), m.get("X-Header-042"));
+    assert_eq!(Some(&42), m.get("x-header-042"));
+    assert_eq!(Some(&42), m.get("X-HEADER-042"));
+    assert_eq!(Some(("X-Header-007", &7)), m.get_key_value("x-HEADER-007"));
+    assert!(m.contains_key("x-header-299"));
+    assert!(!m.contains_key("x-header-300"));
+    assert!(!m.contains_key("x_header_001"));
+
+    *m.get_mut("X-HEADER-001").unwrap() = 100;
+    assert_eq!(Some(&100), m.get("x-header-001"));
+
+    assert!(m.keys().all(|k| k.starts_with("X-Header-")));
+}
+
+#[test]
+fn test_small_and_long_keys() {
+    let long = "Long-".repeat(40);
+    let m = CaseInsensitiveFrozenMap::from_vec(vec![("", 0), ("Accept", 1), (long.as_str(), 2)]);
+
+    assert_eq!(Some(&0), m.get(""));
+    assert_eq!(Some(&1), m.get("aCCEPT"));
+    assert_eq!(Some(&2), m.get(&long.to_uppercase()));
+    assert_eq!(None, m.get(&long[1..]));
+}
+
+#[test]
+fn test_non_ascii() {
+    let m: CaseInsensitiveFrozenMap<_> = [("Ünïcode", 1), ("Straße", 2)].into_iter().collect();
+
+    assert_eq!(Some(&1), m.get("ÜNïCODE"));
+    assert_eq!(None, m.get("üNïCODE"));
+    assert_eq!(Some(&2), m.get("STRAße"));
+}
+
+#[test]
+fn test_str_equality() {
+    assert_eq!(
+        CaseInsensitiveStr::new("abc"),
+        CaseInsensitiveStr::new("ABC")
+    );
+    assert_ne!(
+        CaseInsensitiveStr::new("abc"),
+        CaseInsensitiveStr::new("abcd")
+    );
+    assert_eq!("AbC", CaseInsensitiveStr::new("AbC").as_str());
+}
@@ -0,0 +1,373 @@
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::fmt::{Debug, Formatter, Result};
+use std::iter::FusedIterator;
+
+use bitvec::macros::internal::funty::Fundamental;
+
+use crate::errors::FrozenError;
+use crate::facades::frozen_prefix_map::sort_entries;
+use crate::traits::len::Len;
+
+/// Marks nodes which don't end a key.
+const NO_ENTRY: u32 = u32::MAX;
+
+/// A frozen map from strings which stores its keys in a radix trie, sharing the bytes of
+/// common prefixes between keys.
+///
+/// Keys are not kept as-is, which cuts down memory usage for large maps of keys with much
+/// in common, such as paths, URLs, or dictionary words, at the cost of slower lookups than
+/// a [`FrozenMap`](crate::facades::FrozenMap) which hashes them. Iterating through the map
+/// rebuilds its keys, in sorted order.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::CompactFrozenMap;
+///
+/// let map = CompactFrozenMap::from_vec(vec![
+///     ("/usr/bin", 1),
+///     ("/usr/lib", 2),
+///     ("/usr/local/bin", 3),
+/// ]);
+///
+/// assert_eq!(map.get("/usr/lib"), Some(&2));
+/// assert_eq!(map.get("/usr"), None);
+/// assert_eq!(map.keys().collect::<Vec<_>>(), ["/usr/bin", "/usr/lib", "/usr/local/bin"]);
+/// ```
+#[derive(Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct CompactFrozenMap<V> {
+    /// The bytes of the edge leading to each node, laid out in node order.
+    labels: Box<[u8]>,
+
+    /// Where the label of each node starts in `labels`, followed by the length of `labels`.
+    label_starts: Box<[u32]>,
+
+    /// Where the children of each node start, followed by the number of nodes.
+    ///
+    /// Nodes are numbered breadth first, with the root first, so the children of a node are
+    /// contiguous and sorted by the first byte of their label.
+    child_starts: Box<[u32]>,
+
+    /// The index in `values` of the key ending at each node, or [`NO_ENTRY`].
+    entries: Box<[u32]>,
+
+    /// The values, in key order.
+    values: Box<[V]>,
+}
+
+impl<V> CompactFrozenMap<V> {
+    /// Creates a compact frozen map.
+    ///
+    /// When several entries hold the same key, the last one is kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the keys hold 4 GiB or more of bytes.
+    #[must_use]
+    pub fn from_vec<K>(payload: Vec<(K, V)>) -> Self
+    where
+        K: AsRef<str>,
+    {
+        Self::new(sort_entries(payload, key_bytes).0)
+            .expect("The keys must hold less than 4 GiB of bytes")
+    }
+
+    /// Creates a compact frozen map, returning an error instead of accepting duplicate keys.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys, or if the keys hold 4 GiB or more of bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::errors::FrozenError;
+    /// use frozen_collections_core::facades::CompactFrozenMap;
+    ///
+    /// let map = CompactFrozenMap::try_from_vec(vec![("a", 1), ("b", 2), ("a", 3)]);
+    /// assert_eq!(map.err(), Some(FrozenError::DuplicateKey { index: 2 }));
+    /// ```
+    pub fn try_from_vec<K>(payload: Vec<(K, V)>) -> std::result::Result<Self, FrozenError>
+    where
+        K: AsRef<str>,
+    {
+        match sort_entries(payload, key_bytes) {
+            (_, Some(index)) => Err(FrozenError::DuplicateKey { index }),
+            (entries, None) => Self::new(entries),
+        }
+    }
+
+    /// Creates a compact frozen map from entries sorted by key, without duplicate keys.
+    fn new<K>(entries: Vec<(K, V)>) -> std::result::Result<Self, FrozenError>
+    where
+        K: AsRef<str>,
+    {
+        let len: usize = entries.iter().map(|x| x.0.as_ref().len()).sum();
+        let max = u32::MAX.as_usize() - 1;
+        if len > max {
+            return Err(FrozenError::CapacityExceeded { len, max });
+        }
+
+        let key = |index: usize| entries[index].0.as_ref().as_bytes();
+
+        let mut labels = Vec::new();
+        let mut label_starts = vec![0];
+        let mut child_starts = Vec::new();
+        let mut node_entries = Vec::new();
+
+        // ranges of keys sharing the prefix of a node, along with the length of the prefix,
+        // in node order
+        let mut pending = VecDeque::from([(0..entries.len(), 0)]);
+        while let Some((range, depth)) = pending.pop_front() {
+            child_starts.push(label_starts.len());
+
+            let mut start = range.start;
+            if start < range.end && key(start).len() == depth {
+                node_entries.push(start);
+                start += 1;
+            } else {
+                node_entries.push(NO_ENTRY.as_usize());
+            }
+
+            while start < range.end {
+                let byte = key(start)[depth];
+                let end = start
+                    + (start..range.end)
+                        .map(key)
+                        .take_while(|k| k[depth] == byte)
+                        .count();
+
+                // the child extends to the longest prefix shared by its keys, which is
+                // that of the first and last of them
+                let child_depth = common_prefix_len(key(start), key(end - 1));
+                label_starts.push(labels.len());
+                labels.extend_from_slice(&key(start)[depth..child_depth]);
+                pending.push_back((start..end, child_depth));
+                start = end;
+            }
+        }
+
+        child_starts.push(label_starts.len());
+        label_starts.push(labels.len());
+
+        Ok(Self {
+            labels: labels.into_boxed_slice(),
+            label_starts: narrow(label_starts),
+            child_starts: narrow(child_starts),
+            entries: narrow(node_entries),
+            values: entries.into_iter().map(|x| x.1).collect(),
+        })
+    }
+
+    /// Returns the label of a node.
+    fn label(&self, node: usize) -> &[u8] {
+        &self.labels[self.label_starts[node].as_usize()..self.label_starts[node + 1].as_usize()]
+    }
+
+    /// Returns the child of a node whose label starts with the byte.
+    fn find_child(&self, node: usize, byte: u8) -> Option<usize> {
+        let mut low = self.child_starts[node].as_usize();
+        let mut high = self.child_starts[node + 1].as_usize();
+
+        // labels of nodes other than the root are never empty
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match self.labels[self.label_starts[mid].as_usize()].cmp(&byte) {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return Some(mid),
+            }
+        }
+
+        None
+    }
+
+    /// Returns the index in `values` of the key.
+    fn find(&self, key: &[u8]) -> Option<usize> {
+        let mut node = 0;
+        let mut rest = key;
+
+        while let Some(&byte) = rest.first() {
+            node = self.find_child(node, byte)?;
+            rest = rest.strip_prefix(self.label(node))?;
+        }
+
+        match self.entries[node] {
+            NO_ENTRY => None,
+            index => Some(index.as_usize()),
+        }
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::CompactFrozenMap;
+    ///
+    /// let map = CompactFrozenMap::from_vec(vec![("apple", 1), ("apricot", 2)]);
+    /// assert_eq!(map.get("apricot"), Some(&2));
+    /// assert_eq!(map.get("ap"), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: ?Sized + AsRef<str>,
+    {
+        let index = self.find(key.as_ref().as_bytes())?;
+        Some(&self.values[index])
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    #[inline]
+    #[must_use]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        Q: ?Sized + AsRef<str>,
+    {
+        let index = self.find(key.as_ref().as_bytes())?;
+        Some(&mut self.values[index])
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    #[inline]
+    #[must_use]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + AsRef<str>,
+    {
+        self.find(key.as_ref().as_bytes()).is_some()
+    }
+
+    /// An iterator visiting all key-value pairs in key order, rebuilding each key.
+    #[must_use]
+    pub fn iter(&self) -> CompactIter<'_, V> {
+        CompactIter {
+            map: self,
+            key: Vec::new(),
+            pending: vec![(0, 0)],
+        }
+    }
+
+    /// An iterator visiting all keys in order, rebuilding each of them.
+    pub fn keys(&self) -> impl Iterator<Item = String> + '_ {
+        self.iter().map(|x| x.0)
+    }
+
+    /// An iterator visiting all values in key order.
+    pub fn values(&self) -> std::slice::Iter<'_, V> {
+        self.values.iter()
+    }
+
+    /// Returns the number of elements in the map.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// An iterator over the entries of a [`CompactFrozenMap`], in key order.
+#[derive(Clone)]
+pub struct CompactIter<'a, V> {
+    map: &'a CompactFrozenMap<V>,
+
+    /// The key of the last node visited.
+    key: Vec<u8>,
+
+    /// The nodes left to visit, along with the length of their parent's key, in reverse order.
+    pending: Vec<(usize, usize)>,
+}
+
+impl<'a, V> Iterator for CompactIter<'a, V> {
+    type Item = (String, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, depth)) = self.pending.pop() {
+            self.key.truncate(depth);
+            self.key.extend_from_slice(self.map.label(node));
+
+            let children =
+                self.map.child_starts[node].as_usize()..self.map.child_starts[node + 1].as_usize();
+            let depth = self.key.len();
+            self.pending
+                .extend(children.rev().map(|child| (child, depth)));
+
+            if let Some(index) = self.map.entries.get(node).filter(|&&x| x != NO_ENTRY) {
+                // keys only ever end at boundaries between characters
+                let key = String::from_utf8(self.key.clone()).expect("keys are valid UTF-8");
+                return Some((key, &self.map.values[index.as_usize()]));
+            }
+        }
+
+        None
+    }
+}
+
+impl<V> FusedIterator for CompactIter<'_, V> {}
+
+/// Returns the bytes of a key.
+fn key_bytes<K: AsRef<str>>(key: &K) -> &[u8] {
+    key.as_ref().as_bytes()
+}
+
+/// Returns the length of the longest common prefix of two slices.
+fn common_prefix_len(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(x, y)| x == y).count()
+}
+
+/// Converts offsets known to fit in 32 bits.
+#[allow(clippy::cast_possible_truncation)]
+fn narrow(values: Vec<usize>) -> Box<[u32]> {
+    values.into_iter().map(|x| x as u32).collect()
+}
+
+impl<V> Len for CompactFrozenMap<V> {
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+}
+
+impl<V> Debug for CompactFrozenMap<V>
+where
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, V> IntoIterator for &'a CompactFrozenMap<V> {
+    type Item = (String, &'a V);
+    type IntoIter = CompactIter<'a, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V, const N: usize> From<[(K, V); N]> for CompactFrozenMap<V>
+where
+    K: AsRef<str>,
+{
+    fn from(payload: [(K, V); N]) -> Self {
+        Self::from_vec(Vec::from_iter(payload))
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for CompactFrozenMap<V>
+where
+    K: AsRef<str>,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self::from_vec(Vec::from_iter(iter))
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::BTreeMap;
+
+use crate::errors::FrozenError;
+use crate::facades::{CompactFrozenMap, FrozenMapBuilder};
+use crate::traits::len::Len;
+
+fn check(payload: &[(String, usize)]) {
+    let expected: BTreeMap<_, _> = payload.iter().cloned().collect();
+    let mut map = CompactFrozenMap::from_vec(payload.to_vec());
+    assert_eq!(expected.len(), map.len());
+    assert_eq!(expected.len(), Len::len(&map));
+
+    for (k, v) in &expected {
+        assert_eq!(Some(v), map.get(k));
+        assert!(map.contains_key(k));
+
+        // truncating and extending keys mostly leads to keys missing from the map
+        let mut truncated = k.clone();
+        truncated.pop();
+        for probe in [&truncated, &format!("{k}~")] {
+            assert_eq!(expected.get(probe), map.get(probe));
+        }
+    }
+
+    assert!(map
+        .iter()
+        .map(|(k, v)| (k, *v))
+        .eq(expected.clone().into_iter()));
+    assert!(map.values().eq(expected.values()));
+
+    if let Some((k, _)) = expected.iter().next() {
+        *map.get_mut(k).unwrap() += 1;
+        assert_eq!(Some(&(expected[k] + 1)), map.get(k));
+    }
+}
+
+#[test]
+fn paths() {
+    let payload: Vec<_> = (0..5000)
+        .map(|i| (format!("/srv/{}/{}/file{}", i % 3, i % 17, i), i))
+        .collect();
+    check(&payload);
+}
+
+#[test]
+fn nested_keys() {
+    let words = ["", "a", "ab", "abc", "abd", "b", "ba", "bab", "c"];
+    let payload: Vec<_> = words.iter().map(|w| (w.to_string(), w.len())).collect();
+    check(&payload);
+    check(&payload[1..]);
+    check(&payload[..1]);
+    check(&[]);
+}
+
+#[test]
+fn multibyte_keys() {
+    // the keys share a leading byte within their first character, so labels split it
+    let words = ["é", "éa", "ê", "日本", "日本語", "日曜"];
+    let payload: Vec<_> = words
+        .iter()
+        .enumerate()
+        .map(|(i, w)| (w.to_string(), i))
+        .collect();
+    check(&payload);
+}
+
+#[test]
+fn duplicates() {
+    let map = CompactFrozenMap::from_vec(vec![("a", 1), ("b", 2), ("a", 3)]);
+    assert_eq!(2, map.len());
+    assert_eq!(Some(&3), map.get("a"));
+
+    assert_eq!(
+        Some(FrozenError::DuplicateKey { index: 3 }),
+        CompactFrozenMap::try_from_vec(vec![("a", 1), ("b", 2), ("c", 3), ("b", 4)]).err()
+    );
+}
+
+#[test]
+fn builder() {
+    let mut builder = FrozenMapBuilder::new();
+    builder.push("xy".to_string(), 1).push("x".to_string(), 2);
+
+    let map = builder.build_compact();
+    assert_eq!(Some(&2), map.get("x"));
+    assert_eq!(r#"{"x": 2, "xy": 1}"#, format!("{map:?}"));
+}
@@ -0,0 +1,116 @@
+use std::fmt::{Debug, Formatter, Result};
+
+use crate::errors::FrozenError;
+use crate::facades::CompactFrozenMap;
+use crate::traits::len::Len;
+
+/// A frozen set of strings which stores its values in a radix trie, sharing the bytes of
+/// common prefixes between values.
+///
+/// This trades lookup speed for memory, like [`CompactFrozenMap`] does.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::CompactFrozenSet;
+///
+/// let words = CompactFrozenSet::from_vec(vec!["car", "cart", "carton", "cat"]);
+///
+/// assert!(words.contains("cart"));
+/// assert!(!words.contains("ca"));
+/// assert_eq!(words.iter().collect::<Vec<_>>(), ["car", "cart", "carton", "cat"]);
+/// ```
+#[derive(Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct CompactFrozenSet {
+    map: CompactFrozenMap<()>,
+}
+
+impl CompactFrozenSet {
+    /// Creates a compact frozen set, ignoring duplicate values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the values hold 4 GiB or more of bytes.
+    #[must_use]
+    pub fn from_vec<T>(payload: Vec<T>) -> Self
+    where
+        T: AsRef<str>,
+    {
+        Self {
+            map: CompactFrozenMap::from_vec(payload.into_iter().map(|x| (x, ())).collect()),
+        }
+    }
+
+    /// Creates a compact frozen set, returning an error instead of accepting duplicate values.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate values, or if the values hold 4 GiB or more of
+    /// bytes.
+    pub fn try_from_vec<T>(payload: Vec<T>) -> std::result::Result<Self, FrozenError>
+    where
+        T: AsRef<str>,
+    {
+        Ok(Self {
+            map: CompactFrozenMap::try_from_vec(payload.into_iter().map(|x| (x, ())).collect())?,
+        })
+    }
+
+    /// Returns `true` if the set contains the value.
+    #[inline]
+    #[must_use]
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        Q: ?Sized + AsRef<str>,
+    {
+        self.map.contains_key(value)
+    }
+
+    /// An iterator visiting all values in order, rebuilding each of them.
+    pub fn iter(&self) -> impl Iterator<Item = String> + '_ {
+        self.map.keys()
+    }
+
+    /// Returns the number of elements in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the set contains no elements.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+impl Len for CompactFrozenSet {
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+}
+
+impl Debug for CompactFrozenSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for CompactFrozenSet
+where
+    T: AsRef<str>,
+{
+    fn from(payload: [T; N]) -> Self {
+        Self::from_vec(Vec::from_iter(payload))
+    }
+}
+
+impl<T> FromIterator<T> for CompactFrozenSet
+where
+    T: AsRef<str>,
+{
+    fn from_iter<U: IntoIterator<Item = T>>(iter: U) -> Self {
+        Self::from_vec(Vec::from_iter(iter))
+    }
+}
@@ -0,0 +1,43 @@
+use crate::errors::FrozenError;
+use crate::facades::CompactFrozenSet;
+use crate::traits::len::Len;
+
+#[test]
+fn basic() {
+    let words: Vec<_> = (0..1000).map(|i| format!("word{}", i * 7)).collect();
+    let set = CompactFrozenSet::from_vec(words.clone());
+    assert_eq!(1000, set.len());
+    assert_eq!(1000, Len::len(&set));
+    assert!(!set.is_empty());
+
+    for w in &words {
+        assert!(set.contains(w));
+    }
+
+    assert!(!set.contains("word"));
+    assert!(!set.contains("word1"));
+    assert!(!set.contains("word00"));
+
+    let mut sorted = words;
+    sorted.sort();
+    assert!(set.iter().eq(sorted));
+}
+
+#[test]
+fn duplicates() {
+    let set = CompactFrozenSet::from(["b", "a", "b"]);
+    assert_eq!(2, set.len());
+    assert_eq!(r#"{"a", "b"}"#, format!("{set:?}"));
+
+    assert_eq!(
+        Some(FrozenError::DuplicateKey { index: 2 }),
+        CompactFrozenSet::try_from_vec(vec!["b", "a", "b"]).err()
+    );
+}
+
+#[test]
+fn empty() {
+    let set: CompactFrozenSet = Vec::<String>::new().into_iter().collect();
+    assert!(set.is_empty());
+    assert!(!set.contains(""));
+}
@@ -0,0 +1,225 @@
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::panic::resume_unwind;
+use std::sync::{Arc, OnceLock};
+use std::thread::{self, JoinHandle};
+
+use crate::facades::{DuplicatePolicy, FrozenMap};
+use crate::specialized_maps::hash_table::HashTable;
+use crate::traits::equivalent::Equivalent;
+use crate::traits::len::Len;
+
+/// A frozen map which is usable right away, while its keys are analyzed on a background thread.
+///
+/// Analyzing a large payload can take a while, which delays the startup of processes that
+/// can't serve anything until their maps exist. This map is created from a plain hash table
+/// of the keys, which takes no analysis, and hands a copy of the keys to a background thread
+/// which freezes them. Lookups go through the hash table until the frozen map is ready, and
+/// through the frozen map afterwards. The values are stored once, and both only lead to their
+/// position.
+///
+/// The hash table is released by [`wait`](Self::wait) and [`into_frozen`](Self::into_frozen),
+/// since lookups may still be using it until then.
+///
+/// When the payload holds the same key more than once, the last entry holding it is kept, as
+/// with [`DuplicatePolicy::KeepLast`], so lookups give the same answers before and after the
+/// frozen map is ready.
+///
+/// Created with [`FrozenMap::freeze_deferred`] or [`FrozenMap::freeze_deferred_with_hasher`].
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::FrozenMap;
+///
+/// let map = FrozenMap::freeze_deferred((0..10_000).map(|i| (i.to_string(), i)).collect());
+///
+/// // lookups work while the keys are being analyzed
+/// assert_eq!(map.get("42"), Some(&42));
+///
+/// let frozen = map.into_frozen();
+/// assert_eq!(frozen.get("42"), Some(&42));
+/// ```
+#[allow(clippy::module_name_repetitions)]
+pub struct DeferredFrozenMap<K, V, BH = RandomState> {
+    values: Box<[V]>,
+    shim: HashTable<K, usize>,
+    bh: BH,
+    optimized: Arc<OnceLock<FrozenMap<K, usize, BH>>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl<K, V, BH> DeferredFrozenMap<K, V, BH>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    BH: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Creates a map answering lookups through a hash map, and starts freezing the keys in the
+    /// background.
+    pub(crate) fn new(payload: Vec<(K, V)>, bh: BH) -> Self {
+        let payload = DuplicatePolicy::KeepLast
+            .apply(payload, |k| bh.hash_one(k))
+            .expect("Keeping the last duplicate never fails");
+
+        let mut keys = Vec::with_capacity(payload.len());
+        let mut values = Vec::with_capacity(payload.len());
+        for (k, v) in payload {
+            keys.push(k);
+            values.push(v);
+        }
+
+        let shim = HashTable::new(
+            keys.iter().cloned().zip(0..).collect(),
+            keys.len().max(1),
+            |k| bh.hash_one(k),
+        );
+
+        let optimized = Arc::new(OnceLock::new());
+        let worker = {
+            let optimized = Arc::clone(&optimized);
+            let bh = bh.clone();
+            thread::spawn(move || {
+                _ = optimized.set(FrozenMap::from_iter_with_hasher(
+                    keys.into_iter().zip(0..),
+                    bh,
+                ));
+            })
+        };
+
+        Self {
+            values: values.into_boxed_slice(),
+            shim,
+            bh,
+            optimized,
+            worker: Some(worker),
+        }
+    }
+}
+
+impl<K, V, BH> DeferredFrozenMap<K, V, BH>
+where
+    K: Hash + Eq,
+    BH: BuildHasher,
+{
+    /// Returns a reference to the value corresponding to the key.
+    #[inline]
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let index = self.optimized.get().map_or_else(
+            || {
+                let entry = self.shim.find(self.bh.hash_one(key), key)?;
+                Some(&entry.1)
+            },
+            |map| map.get(key),
+        );
+
+        index.map(|&index| &self.values[index])
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    #[inline]
+    #[must_use]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Returns `true` once lookups go through the frozen map.
+    #[must_use]
+    pub fn is_optimized(&self) -> bool {
+        self.optimized.get().is_some()
+    }
+
+    /// Waits for the keys to be frozen, and returns the resulting frozen map.
+    ///
+    /// # Panics
+    ///
+    /// Resumes the panic of the background thread, if freezing the keys panicked.
+    #[must_use]
+    pub fn into_frozen(mut self) -> FrozenMap<K, V, BH> {
+        self.wait();
+
+        let Self {
+            values, optimized, ..
+        } = self;
+
+        let optimized = Arc::into_inner(optimized)
+            .and_then(OnceLock::into_inner)
+            .expect("The background thread must have frozen the keys");
+
+        let mut values: Vec<_> = values.into_vec().into_iter().map(Some).collect();
+        optimized.map_values(|index| {
+            values[index]
+                .take()
+                .expect("Every key must lead to a distinct value")
+        })
+    }
+}
+
+impl<K, V, BH> DeferredFrozenMap<K, V, BH> {
+    /// Waits for the keys to be frozen, and releases the hash table which answered lookups
+    /// until then.
+    ///
+    /// # Panics
+    ///
+    /// Resumes the panic of the background thread, if freezing the keys panicked.
+    pub fn wait(&mut self) {
+        if let Some(worker) = self.worker.take() {
+            if let Err(panic) = worker.join() {
+                resume_unwind(panic);
+            }
+        }
+
+        self.shim = HashTable::empty();
+    }
+
+    /// Returns the number of elements in the map.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// An iterator visiting all key-value pairs in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        let optimized = self.optimized.get();
+        let shim = if optimized.is_some() {
+            &[]
+        } else {
+            &*self.shim.entries
+        };
+
+        optimized
+            .into_iter()
+            .flat_map(FrozenMap::iter)
+            .chain(shim.iter().map(|(k, index)| (k, index)))
+            .map(|(k, &index)| (k, &self.values[index]))
+    }
+}
+
+impl<K, V, BH> Len for DeferredFrozenMap<K, V, BH> {
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+}
+
+impl<K, V, BH> Debug for DeferredFrozenMap<K, V, BH>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,83 @@
+use std::hash::RandomState;
+
+use crate::facades::FrozenMap;
+use crate::traits::len::Len;
+
+#[test]
+fn lookups_before_and_after_freezing() {
+    for count in [0, 1, 3, 10, 300, 20_000] {
+        let payload: Vec<_> = (0..count).map(|i| (format!("Key-{i:05}"), i)).collect();
+        let map = FrozenMap::freeze_deferred(payload.clone());
+        assert_eq!(count, map.len());
+        assert_eq!(count, Len::len(&map));
+        assert_eq!(count == 0, map.is_empty());
+
+        for (k, v) in &payload {
+            assert_eq!(Some(v), map.get(k.as_str()));
+            assert!(map.contains_key(k));
+        }
+
+        assert_eq!(None, map.get("missing"));
+        assert_eq!(count, map.iter().count());
+
+        let frozen = map.into_frozen();
+        assert_eq!(count, frozen.len());
+        for (k, v) in &payload {
+            assert_eq!(Some(v), frozen.get(k.as_str()));
+        }
+    }
+}
+
+#[test]
+fn switches_to_the_frozen_map() {
+    let map = FrozenMap::freeze_deferred_with_hasher(
+        (0..1000).map(|i| (i, i.to_string())).collect(),
+        RandomState::new(),
+    );
+
+    while !map.is_optimized() {
+        std::thread::yield_now();
+    }
+
+    assert_eq!(Some("42"), map.get(&42).map(String::as_str));
+    assert_eq!(None, map.get(&1000));
+    assert_eq!(
+        r#"{1: "1"}"#,
+        format!("{:?}", FrozenMap::freeze_deferred(vec![(1, "1")]))
+    );
+}
+
+#[test]
+fn duplicate_keys_keep_the_last_entry() {
+    let payload = vec![("a", 1), ("b", 2), ("a", 3), ("c", 4), ("b", 5)];
+    let mut map = FrozenMap::freeze_deferred(payload);
+    assert_eq!(3, map.len());
+    assert_eq!(Some(&3), map.get("a"));
+    assert_eq!(Some(&5), map.get("b"));
+
+    map.wait();
+    assert!(map.is_optimized());
+    assert_eq!(Some(&3), map.get("a"));
+    assert_eq!(Some(&5), map.get("b"));
+    assert_eq!(Some(&4), map.get("c"));
+
+    let frozen = map.into_frozen();
+    assert_eq!(3, frozen.len());
+    assert_eq!(Some(&3), frozen.get("a"));
+    assert_eq!(Some(&5), frozen.get("b"));
+}
+
+#[test]
+fn iterates_the_frozen_map_once_ready() {
+    let mut map = FrozenMap::freeze_deferred((0..300).map(|i| (i.to_string(), i)).collect());
+    map.wait();
+
+    let mut entries: Vec<_> = map.iter().map(|(k, &v)| (k.clone(), v)).collect();
+    entries.sort_by_key(|entry| entry.1);
+    assert_eq!(300, entries.len());
+    assert_eq!(("42".to_string(), 42), entries[42]);
+
+    // equivalent keys are looked up without building a key
+    assert_eq!(Some(&42), map.get("42"));
+    assert!(!map.contains_key("300"));
+}
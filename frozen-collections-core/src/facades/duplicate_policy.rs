@@ -0,0 +1,75 @@
+use crate::analyzers::duplicate_key_analyzer::find_duplicate_keys;
+use crate::errors::FrozenError;
+
+/// What to do when the payload of a frozen collection holds the same key more than once.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::errors::FrozenError;
+/// use frozen_collections_core::facades::{DuplicatePolicy, FrozenMap};
+///
+/// let payload = vec![("a", 1), ("b", 2), ("a", 3)];
+///
+/// let map = FrozenMap::from_vec_with_policy(payload.clone(), DuplicatePolicy::KeepFirst).unwrap();
+/// assert_eq!(map.get("a"), Some(&1));
+///
+/// let map = FrozenMap::from_vec_with_policy(payload.clone(), DuplicatePolicy::KeepLast).unwrap();
+/// assert_eq!(map.get("a"), Some(&3));
+///
+/// let err = FrozenMap::from_vec_with_policy(payload, DuplicatePolicy::Error).err();
+/// assert_eq!(err, Some(FrozenError::DuplicateKey { index: 2 }));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DuplicatePolicy {
+    /// Keeps the earliest entry holding a key and drops the later ones.
+    KeepFirst,
+
+    /// Keeps the latest entry holding a key and drops the earlier ones, as if the entries were
+    /// inserted one after the other into a [`HashMap`](std::collections::HashMap).
+    KeepLast,
+
+    /// Rejects the payload with [`FrozenError::DuplicateKey`], identifying the first entry
+    /// which repeats a key.
+    #[default]
+    Error,
+}
+
+impl DuplicatePolicy {
+    /// Removes the entries holding duplicate keys from the payload according to the policy.
+    ///
+    /// `hash` must return equal codes for equal keys.
+    pub(crate) fn apply<K, V, F>(
+        self,
+        payload: Vec<(K, V)>,
+        hash: F,
+    ) -> Result<Vec<(K, V)>, FrozenError>
+    where
+        K: Eq,
+        F: Fn(&K) -> u64,
+    {
+        let groups = find_duplicate_keys(&payload, hash);
+        if groups.is_empty() {
+            return Ok(payload);
+        }
+
+        let mut keep = vec![true; payload.len()];
+        for group in &groups {
+            let dropped = match self {
+                Self::KeepFirst => &group[1..],
+                Self::KeepLast => &group[..group.len() - 1],
+                Self::Error => return Err(FrozenError::DuplicateKey { index: group[1] }),
+            };
+
+            for &index in dropped {
+                keep[index] = false;
+            }
+        }
+
+        Ok(payload
+            .into_iter()
+            .zip(keep)
+            .filter_map(|(entry, keep)| keep.then_some(entry))
+            .collect())
+    }
+}
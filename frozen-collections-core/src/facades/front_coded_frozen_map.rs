@@ -0,0 +1,427 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter, Result};
+use std::iter::FusedIterator;
+
+use bitvec::macros::internal::funty::Fundamental;
+
+use crate::errors::FrozenError;
+use crate::facades::frozen_prefix_map::sort_entries;
+use crate::traits::len::Len;
+
+/// The number of keys in each block, whose first key is stored in full.
+///
+/// Lookups binary search the first keys of the blocks, and then scan through a single block,
+/// so longer blocks save the space of full keys at the cost of longer scans.
+const BLOCK_LEN: usize = 16;
+
+/// A frozen map from strings which stores its keys sorted and front coded, each key only
+/// holding the bytes which follow the prefix it shares with the key before it.
+///
+/// Sorted keys with much in common, such as URL paths or file names, mostly repeat the key
+/// before them, so storing only the length of the shared prefix and the remaining bytes cuts
+/// the memory taken by keys down to a fraction of their length. Every few keys, one is stored
+/// in full, which lets lookups binary search these keys before decoding a handful of others.
+/// Lookups are slower than in a [`FrozenMap`](crate::facades::FrozenMap) which hashes keys,
+/// and iterating through the map rebuilds its keys, in sorted order.
+///
+/// Compared to a [`CompactFrozenMap`](crate::facades::CompactFrozenMap), which shares
+/// prefixes through a trie, this map has no per-node overhead, and its lookups read a few
+/// runs of contiguous bytes rather than following nodes.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::FrontCodedFrozenMap;
+///
+/// let map = FrontCodedFrozenMap::from_vec(vec![
+///     ("/api/v1/users", 1),
+///     ("/api/v1/users/me", 2),
+///     ("/api/v2/users", 3),
+/// ]);
+///
+/// assert_eq!(map.get("/api/v1/users/me"), Some(&2));
+/// assert_eq!(map.get("/api/v1"), None);
+/// assert_eq!(
+///     map.keys().collect::<Vec<_>>(),
+///     ["/api/v1/users", "/api/v1/users/me", "/api/v2/users"]
+/// );
+/// ```
+#[derive(Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct FrontCodedFrozenMap<V> {
+    /// The keys in order, each as the length of the prefix it shares with the key before it,
+    /// the length of the rest of the key, and the rest of the key's bytes.
+    ///
+    /// Lengths are stored as variable length integers, and the first key of each block shares
+    /// no prefix.
+    bytes: Box<[u8]>,
+
+    /// Where the first key of each block starts in `bytes`.
+    block_starts: Box<[u32]>,
+
+    /// The values, in key order.
+    values: Box<[V]>,
+}
+
+impl<V> FrontCodedFrozenMap<V> {
+    /// Creates a front coded frozen map.
+    ///
+    /// When several entries hold the same key, the last one is kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the encoded keys take 4 GiB or more.
+    #[must_use]
+    pub fn from_vec<K>(payload: Vec<(K, V)>) -> Self
+    where
+        K: AsRef<str>,
+    {
+        Self::new(sort_entries(payload, key_bytes).0)
+            .expect("The encoded keys must take less than 4 GiB")
+    }
+
+    /// Creates a front coded frozen map, returning an error instead of accepting duplicate
+    /// keys.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys, or if the encoded keys take 4 GiB or more.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::errors::FrozenError;
+    /// use frozen_collections_core::facades::FrontCodedFrozenMap;
+    ///
+    /// let map = FrontCodedFrozenMap::try_from_vec(vec![("a", 1), ("b", 2), ("a", 3)]);
+    /// assert_eq!(map.err(), Some(FrozenError::DuplicateKey { index: 2 }));
+    /// ```
+    pub fn try_from_vec<K>(payload: Vec<(K, V)>) -> std::result::Result<Self, FrozenError>
+    where
+        K: AsRef<str>,
+    {
+        match sort_entries(payload, key_bytes) {
+            (_, Some(index)) => Err(FrozenError::DuplicateKey { index }),
+            (entries, None) => Self::new(entries),
+        }
+    }
+
+    /// Creates a front coded frozen map from entries sorted by key, without duplicate keys.
+    fn new<K>(entries: Vec<(K, V)>) -> std::result::Result<Self, FrozenError>
+    where
+        K: AsRef<str>,
+    {
+        let mut bytes = Vec::new();
+        let mut block_starts = Vec::with_capacity(entries.len().div_ceil(BLOCK_LEN));
+        let mut previous: &[u8] = &[];
+
+        for (index, entry) in entries.iter().enumerate() {
+            let key = key_bytes(&entry.0);
+            let shared = if index % BLOCK_LEN == 0 {
+                block_starts.push(bytes.len());
+                0
+            } else {
+                common_prefix_len(previous, key)
+            };
+
+            write_len(&mut bytes, shared);
+            write_len(&mut bytes, key.len() - shared);
+            bytes.extend_from_slice(&key[shared..]);
+            previous = key;
+        }
+
+        let max = u32::MAX.as_usize();
+        if bytes.len() > max {
+            return Err(FrozenError::CapacityExceeded {
+                len: bytes.len(),
+                max,
+            });
+        }
+
+        // every block start is within the bytes, which were just checked to fit
+        #[allow(clippy::cast_possible_truncation)]
+        let block_starts = block_starts.into_iter().map(|x| x as u32).collect();
+
+        Ok(Self {
+            bytes: bytes.into_boxed_slice(),
+            block_starts,
+            values: entries.into_iter().map(|x| x.1).collect(),
+        })
+    }
+
+    /// Decodes the key starting at `offset`, returning the length of the prefix it shares with
+    /// the key before it, the rest of its bytes, and where the next key starts.
+    fn entry(&self, offset: usize) -> (usize, &[u8], usize) {
+        let (shared, offset) = read_len(&self.bytes, offset);
+        let (len, offset) = read_len(&self.bytes, offset);
+        (shared, &self.bytes[offset..offset + len], offset + len)
+    }
+
+    /// Returns the index in `values` of the key.
+    fn find(&self, key: &[u8]) -> Option<usize> {
+        // the first keys of the blocks are stored in full, so they can be compared directly
+        let block = self
+            .block_starts
+            .partition_point(|&start| self.entry(start.as_usize()).1 <= key)
+            .checked_sub(1)?;
+
+        let first = block * BLOCK_LEN;
+        let last = self.values.len().min(first + BLOCK_LEN);
+        let mut offset = self.block_starts[block].as_usize();
+
+        // the length of the prefix the current key shares with the searched key, which the
+        // current key is less than or equal to
+        let mut matched = 0;
+        for index in first..last {
+            let (shared, rest, next) = self.entry(offset);
+            offset = next;
+
+            match shared.cmp(&matched) {
+                // the key differs from the searched key where the previous key did, and so is
+                // still less than it
+                Ordering::Greater => continue,
+
+                // the key differs from the previous key where the previous key matched the
+                // searched key, and so is greater than the searched key
+                Ordering::Less => return None,
+
+                Ordering::Equal => {}
+            }
+
+            let remaining = &key[matched..];
+            let common = common_prefix_len(rest, remaining);
+            matched += common;
+
+            match (rest.get(common), remaining.get(common)) {
+                (None, None) => return Some(index),
+
+                // the key is greater than the searched key, and so are the keys after it
+                (Some(_), None) => return None,
+                (Some(x), Some(y)) if x > y => return None,
+
+                // the key is less than the searched key
+                _ => {}
+            }
+        }
+
+        None
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrontCodedFrozenMap;
+    ///
+    /// let map = FrontCodedFrozenMap::from_vec(vec![("apple", 1), ("apricot", 2)]);
+    /// assert_eq!(map.get("apricot"), Some(&2));
+    /// assert_eq!(map.get("ap"), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: ?Sized + AsRef<str>,
+    {
+        let index = self.find(key.as_ref().as_bytes())?;
+        Some(&self.values[index])
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    #[inline]
+    #[must_use]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        Q: ?Sized + AsRef<str>,
+    {
+        let index = self.find(key.as_ref().as_bytes())?;
+        Some(&mut self.values[index])
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    #[inline]
+    #[must_use]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + AsRef<str>,
+    {
+        self.find(key.as_ref().as_bytes()).is_some()
+    }
+
+    /// An iterator visiting all key-value pairs in key order, rebuilding each key.
+    #[must_use]
+    pub const fn iter(&self) -> FrontCodedIter<'_, V> {
+        FrontCodedIter {
+            map: self,
+            key: Vec::new(),
+            offset: 0,
+            index: 0,
+        }
+    }
+
+    /// An iterator visiting all keys in order, rebuilding each of them.
+    pub fn keys(&self) -> impl Iterator<Item = String> + '_ {
+        self.iter().map(|x| x.0)
+    }
+
+    /// An iterator visiting all values in key order.
+    pub fn values(&self) -> std::slice::Iter<'_, V> {
+        self.values.iter()
+    }
+
+    /// Returns the number of elements in the map.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the number of bytes taken by the encoded keys and the starts of their blocks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrontCodedFrozenMap;
+    ///
+    /// let keys: Vec<_> = (0..1000).map(|i| format!("/static/images/icon-{i:04}.png")).collect();
+    /// let map = FrontCodedFrozenMap::from_iter(keys.iter().map(|k| (k, ())));
+    ///
+    /// let plain: usize = keys.iter().map(String::len).sum();
+    /// assert!(map.key_bytes() * 3 < plain);
+    /// ```
+    #[must_use]
+    pub fn key_bytes(&self) -> usize {
+        self.bytes.len() + size_of_val(&*self.block_starts)
+    }
+}
+
+/// An iterator over the entries of a [`FrontCodedFrozenMap`], in key order.
+#[derive(Clone)]
+pub struct FrontCodedIter<'a, V> {
+    map: &'a FrontCodedFrozenMap<V>,
+
+    /// The key of the last entry visited.
+    key: Vec<u8>,
+
+    /// Where the next key starts.
+    offset: usize,
+
+    /// The index of the next entry.
+    index: usize,
+}
+
+impl<'a, V> Iterator for FrontCodedIter<'a, V> {
+    type Item = (String, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.map.values.get(self.index)?;
+        let (shared, rest, next) = self.map.entry(self.offset);
+        self.key.truncate(shared);
+        self.key.extend_from_slice(rest);
+        self.offset = next;
+        self.index += 1;
+
+        // keys are sorted by their bytes, so complete keys are valid UTF-8
+        let key = String::from_utf8(self.key.clone()).expect("keys are valid UTF-8");
+        Some((key, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.map.values.len() - self.index;
+        (len, Some(len))
+    }
+}
+
+impl<V> ExactSizeIterator for FrontCodedIter<'_, V> {}
+
+impl<V> FusedIterator for FrontCodedIter<'_, V> {}
+
+/// Returns the bytes of a key.
+fn key_bytes<K: AsRef<str>>(key: &K) -> &[u8] {
+    key.as_ref().as_bytes()
+}
+
+/// Returns the length of the longest common prefix of two slices.
+fn common_prefix_len(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(x, y)| x == y).count()
+}
+
+/// Appends a length, seven bits at a time from the lowest, setting the top bit of every byte
+/// but the last.
+fn write_len(bytes: &mut Vec<u8>, mut len: usize) {
+    while len >= 0x80 {
+        // the top bit marks the continuation, so only the low seven bits are kept
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.push((len as u8) | 0x80);
+        len >>= 7;
+    }
+
+    // the loop leaves less than 0x80
+    #[allow(clippy::cast_possible_truncation)]
+    bytes.push(len as u8);
+}
+
+/// Reads a length written by [`write_len`], returning it along with where the next item
+/// starts.
+fn read_len(bytes: &[u8], mut offset: usize) -> (usize, usize) {
+    let mut len = 0;
+    let mut shift = 0;
+    loop {
+        let byte = bytes[offset];
+        offset += 1;
+        len |= usize::from(byte & 0x7f) << shift;
+        if byte < 0x80 {
+            return (len, offset);
+        }
+
+        shift += 7;
+    }
+}
+
+impl<V> Len for FrontCodedFrozenMap<V> {
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+}
+
+impl<V> Debug for FrontCodedFrozenMap<V>
+where
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, V> IntoIterator for &'a FrontCodedFrozenMap<V> {
+    type Item = (String, &'a V);
+    type IntoIter = FrontCodedIter<'a, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V, const N: usize> From<[(K, V); N]> for FrontCodedFrozenMap<V>
+where
+    K: AsRef<str>,
+{
+    fn from(payload: [(K, V); N]) -> Self {
+        Self::from_vec(Vec::from_iter(payload))
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for FrontCodedFrozenMap<V>
+where
+    K: AsRef<str>,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self::from_vec(Vec::from_iter(iter))
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+
+use crate::errors::FrozenError;
+use crate::facades::{FrontCodedFrozenMap, FrozenMapBuilder};
+use crate::traits::len::Len;
+
+fn check(payload: &[(String, usize)]) {
+    let expected: BTreeMap<_, _> = payload.iter().cloned().collect();
+    let mut map = FrontCodedFrozenMap::from_vec(payload.to_vec());
+    assert_eq!(expected.len(), map.len());
+    assert_eq!(expected.len(), Len::len(&map));
+
+    for (k, v) in &expected {
+        assert_eq!(Some(v), map.get(k));
+        assert!(map.contains_key(k));
+
+        // truncating and extending keys mostly leads to keys missing from the map
+        let mut truncated = k.clone();
+        truncated.pop();
+        for probe in [
+            &truncated,
+            &format!("{k}~"),
+            &format!("{k}\0"),
+            &format!("!{k}"),
+        ] {
+            assert_eq!(expected.get(probe), map.get(probe));
+        }
+    }
+
+    assert!(map
+        .iter()
+        .map(|(k, v)| (k, *v))
+        .eq(expected.clone().into_iter()));
+    assert!(map.values().eq(expected.values()));
+    assert_eq!(expected.len(), map.iter().len());
+
+    if let Some((k, _)) = expected.iter().next() {
+        *map.get_mut(k).unwrap() += 1;
+        assert_eq!(Some(&(expected[k] + 1)), map.get(k));
+    }
+}
+
+#[test]
+fn paths() {
+    let payload: Vec<_> = (0..5000)
+        .map(|i| (format!("/srv/{}/{}/file{}", i % 3, i % 17, i), i))
+        .collect();
+    check(&payload);
+
+    let map = FrontCodedFrozenMap::from_vec(payload.clone());
+    let plain: usize = payload.iter().map(|x| x.0.len()).sum();
+    assert!(map.key_bytes() * 2 < plain, "{} {plain}", map.key_bytes());
+}
+
+#[test]
+fn nested_keys() {
+    let words = ["", "a", "ab", "abc", "abd", "b", "ba", "bab", "c"];
+    let payload: Vec<_> = words.iter().map(|w| (w.to_string(), w.len())).collect();
+    check(&payload);
+    check(&payload[1..]);
+    check(&payload[..1]);
+    check(&[]);
+}
+
+#[test]
+fn block_boundaries() {
+    // chains of keys extending each other, which cross the boundaries between blocks
+    for len in [1, 15, 16, 17, 31, 32, 33, 100] {
+        let payload: Vec<_> = (0..len).map(|i| ("x".repeat(i + 1), i)).collect();
+        check(&payload);
+
+        let payload: Vec<_> = (0..len).map(|i| (format!("k{i:03}"), i)).collect();
+        check(&payload);
+    }
+}
+
+#[test]
+fn long_keys() {
+    // lengths which take several bytes to encode
+    let payload: Vec<_> = (0..40)
+        .map(|i| (format!("{}{i:02}", "p".repeat(200 + i * 100)), i))
+        .collect();
+    check(&payload);
+}
+
+#[test]
+fn multibyte_keys() {
+    let words = ["é", "éa", "ê", "日本", "日本語", "日曜"];
+    let payload: Vec<_> = words
+        .iter()
+        .enumerate()
+        .map(|(i, w)| (w.to_string(), i))
+        .collect();
+    check(&payload);
+}
+
+#[test]
+fn duplicates() {
+    let map = FrontCodedFrozenMap::from_vec(vec![("a", 1), ("b", 2), ("a", 3)]);
+    assert_eq!(2, map.len());
+    assert_eq!(Some(&3), map.get("a"));
+
+    assert_eq!(
+        Some(FrozenError::DuplicateKey { index: 3 }),
+        FrontCodedFrozenMap::try_from_vec(vec![("a", 1), ("b", 2), ("c", 3), ("b", 4)]).err()
+    );
+}
+
+#[test]
+fn builder() {
+    let mut builder = FrozenMapBuilder::new();
+    builder.push("xy".to_string(), 1).push("x".to_string(), 2);
+
+    let map = builder.build_front_coded();
+    assert_eq!(Some(&2), map.get("x"));
+    assert_eq!(r#"{"x": 2, "xy": 1}"#, format!("{map:?}"));
+}
+
+#[test]
+fn random_keys() {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(7);
+    let word = |rng: &mut StdRng| -> String {
+        let len = rng.random_range(0..8);
+        (0..len).map(|_| rng.random_range('a'..='d')).collect()
+    };
+
+    for _ in 0..20 {
+        let payload: Vec<_> = (0..rng.random_range(0..300))
+            .map(|i| (word(&mut rng), i))
+            .collect();
+        let expected: BTreeMap<_, _> = payload.iter().cloned().collect();
+        let map = FrontCodedFrozenMap::from_vec(payload);
+
+        for _ in 0..500 {
+            let probe = word(&mut rng);
+            assert_eq!(expected.get(&probe), map.get(&probe), "{probe}");
+        }
+    }
+}
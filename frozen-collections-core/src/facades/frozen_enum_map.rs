@@ -0,0 +1,118 @@
+use std::fmt::{Debug, Formatter, Result};
+
+use crate::traits::enum_key::{EnumKey, EnumKeyMap};
+use crate::traits::len::Len;
+
+/// A frozen map keyed by an enum whose variants carry data.
+///
+/// Lookups happen in two stages: the key's variant is matched first, and the variant's payload
+/// is then looked up in a frozen map dedicated to that variant. Keys such as
+/// `Command::Get(u32)` and `Command::Put(String)` thus end up in an integer map and a
+/// string map respectively, each of which gets analyzed and laid out on its own, rather than
+/// having the whole enum go through the fully generic hashing path.
+///
+/// The key type must implement [`EnumKey`], which is normally done with `#[derive(EnumKey)]`:
+///
+/// ```text
+/// #[derive(EnumKey)]
+/// enum Command {
+///     Get(u32),
+///     Put(String),
+///     Ping,
+/// }
+///
+/// let map = FrozenEnumMap::from_vec(vec![
+///     (Command::Get(1), "get one"),
+///     (Command::Put("x".to_string()), "put x"),
+///     (Command::Ping, "ping"),
+/// ]);
+///
+/// assert_eq!(map.get(&Command::Get(1)), Some(&"get one"));
+/// assert_eq!(map.get(&Command::Get(2)), None);
+/// ```
+#[allow(clippy::module_name_repetitions)]
+pub struct FrozenEnumMap<K, V>
+where
+    K: EnumKey,
+{
+    map: K::Map<V>,
+}
+
+impl<K, V> FrozenEnumMap<K, V>
+where
+    K: EnumKey,
+{
+    /// Creates a frozen enum map.
+    #[must_use]
+    pub fn from_vec(payload: Vec<(K, V)>) -> Self {
+        Self {
+            map: K::Map::from_vec(payload),
+        }
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    #[inline]
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.map.get(key)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    #[inline]
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.map.get_mut(key)
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    #[inline]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.map.get(key).is_some()
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+impl<K, V> Len for FrozenEnumMap<K, V>
+where
+    K: EnumKey,
+{
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+}
+
+impl<K, V> Debug for FrozenEnumMap<K, V>
+where
+    K: EnumKey,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("FrozenEnumMap")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<K, V, const N: usize> From<[(K, V); N]> for FrozenEnumMap<K, V>
+where
+    K: EnumKey,
+{
+    fn from(payload: [(K, V); N]) -> Self {
+        Self::from_vec(Vec::from_iter(payload))
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for FrozenEnumMap<K, V>
+where
+    K: EnumKey,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self::from_vec(Vec::from_iter(iter))
+    }
+}
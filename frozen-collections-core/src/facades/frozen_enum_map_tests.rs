@@ -0,0 +1,107 @@
+use crate::facades::frozen_enum_map::FrozenEnumMap;
+use crate::facades::FrozenMap;
+use crate::traits::enum_key::{EnumKey, EnumKeyMap};
+use crate::traits::len::Len;
+
+#[derive(PartialEq, Eq, Hash, Debug)]
+enum Command {
+    Get(u32),
+    Put(String),
+    Ping,
+    Move { x: i32, y: i32 },
+}
+
+// Mirrors what `#[derive(EnumKey)]` generates for `Command`.
+struct CommandMap<V> {
+    v0: FrozenMap<u32, V>,
+    v1: FrozenMap<String, V>,
+    v2: Option<V>,
+    v3: FrozenMap<Command, V>,
+}
+
+impl<V> EnumKeyMap<Command, V> for CommandMap<V> {
+    fn from_vec(payload: Vec<(Command, V)>) -> Self {
+        let mut v0 = Vec::new();
+        let mut v1 = Vec::new();
+        let mut v2 = None;
+        let mut v3 = Vec::new();
+
+        for (k, v) in payload {
+            match k {
+                Command::Get(p) => v0.push((p, v)),
+                Command::Put(p) => v1.push((p, v)),
+                Command::Ping => v2 = Some(v),
+                k @ Command::Move { .. } => v3.push((k, v)),
+            }
+        }
+
+        Self {
+            v0: FrozenMap::from_vec(v0),
+            v1: FrozenMap::from_vec(v1),
+            v2,
+            v3: FrozenMap::from_vec(v3),
+        }
+    }
+
+    fn get(&self, key: &Command) -> Option<&V> {
+        match key {
+            Command::Get(p) => self.v0.get(p),
+            Command::Put(p) => self.v1.get(p),
+            Command::Ping => self.v2.as_ref(),
+            Command::Move { .. } => self.v3.get(key),
+        }
+    }
+
+    fn get_mut(&mut self, key: &Command) -> Option<&mut V> {
+        match key {
+            Command::Get(p) => self.v0.get_mut(p),
+            Command::Put(p) => self.v1.get_mut(p),
+            Command::Ping => self.v2.as_mut(),
+            Command::Move { .. } => self.v3.get_mut(key),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.v0.len() + self.v1.len() + usize::from(self.v2.is_some()) + self.v3.len()
+    }
+}
+
+impl EnumKey for Command {
+    type Map<V> = CommandMap<V>;
+}
+
+#[test]
+fn two_stage_lookup() {
+    let mut payload: Vec<_> = (0..300).map(|i| (Command::Get(i), i)).collect();
+    payload.push((Command::Put("Key-001".to_string()), 1000));
+    payload.push((Command::Put("Key-002".to_string()), 1001));
+    payload.push((Command::Ping, 2000));
+    payload.push((Command::Move { x: 1, y: 2 }, 3000));
+
+    let mut map = FrozenEnumMap::from_vec(payload);
+    assert_eq!(304, map.len());
+    assert_eq!(304, Len::len(&map));
+    assert!(!map.is_empty());
+
+    assert_eq!(Some(&42), map.get(&Command::Get(42)));
+    assert_eq!(None, map.get(&Command::Get(300)));
+    assert_eq!(Some(&1001), map.get(&Command::Put("Key-002".to_string())));
+    assert_eq!(None, map.get(&Command::Put("Key-003".to_string())));
+    assert_eq!(Some(&2000), map.get(&Command::Ping));
+    assert_eq!(Some(&3000), map.get(&Command::Move { x: 1, y: 2 }));
+    assert!(!map.contains_key(&Command::Move { x: 2, y: 1 }));
+
+    *map.get_mut(&Command::Ping).unwrap() += 1;
+    assert_eq!(Some(&2001), map.get(&Command::Ping));
+}
+
+#[test]
+fn missing_variants() {
+    let map = FrozenEnumMap::from([(Command::Get(1), 'a')]);
+    assert_eq!(1, map.len());
+    assert_eq!(None, map.get(&Command::Ping));
+    assert_eq!(None, map.get(&Command::Put(String::new())));
+
+    let map: FrozenEnumMap<Command, char> = FrozenEnumMap::from_vec(Vec::new());
+    assert!(map.is_empty());
+}
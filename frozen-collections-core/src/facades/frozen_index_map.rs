@@ -0,0 +1,327 @@
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::ops::Index;
+
+use crate::errors::FrozenError;
+use crate::facades::{DuplicatePolicy, FrozenMap};
+use crate::traits::equivalent::Equivalent;
+use crate::traits::len::Len;
+
+/// A frozen map which remembers the order in which its entries were given.
+///
+/// Every entry has a stable index, its position in the payload, which can be used to reach the
+/// entry without hashing its key. Iteration visits the entries in that order, like
+/// [`IndexMap`](https://docs.rs/indexmap) does. Lookups by key cost the same as in a regular
+/// [`FrozenMap`], plus one indirection to reach the value.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::FrozenIndexMap;
+///
+/// let map = FrozenIndexMap::from_vec(vec![("c", 3), ("a", 1), ("b", 2)]);
+///
+/// assert_eq!(map.get("a"), Some(&1));
+/// assert_eq!(map.get_index_of("a"), Some(1));
+/// assert_eq!(map.get_index(2), Some((&"b", &2)));
+/// assert_eq!(map.keys().collect::<Vec<_>>(), vec![&"c", &"a", &"b"]);
+/// ```
+#[derive(Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct FrozenIndexMap<K, V, BH = RandomState> {
+    /// Maps each key to its index.
+    indices: FrozenMap<K, usize, BH>,
+
+    /// The values, by index.
+    values: Box<[V]>,
+
+    /// The position of each key within the entries of `indices`, by index.
+    positions: Box<[usize]>,
+}
+
+impl<K, V, BH> FrozenIndexMap<K, V, BH>
+where
+    K: Hash + Eq,
+    BH: BuildHasher,
+{
+    /// Creates a frozen map which will use the given hash builder to hash keys.
+    ///
+    /// When several entries hold the same key, the last one is kept, at its own position among
+    /// the other entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenIndexMap;
+    /// use std::hash::RandomState;
+    ///
+    /// let map = FrozenIndexMap::from_vec_with_hasher(vec![(1, 2)], RandomState::new());
+    /// assert_eq!(map.get(&1), Some(&2));
+    /// ```
+    #[must_use]
+    pub fn from_vec_with_hasher(payload: Vec<(K, V)>, bh: BH) -> Self {
+        let Ok(payload) = DuplicatePolicy::KeepLast.apply(payload, |k| bh.hash_one(k)) else {
+            unreachable!("keeping the last duplicate never fails");
+        };
+
+        Self::new(payload, bh)
+    }
+
+    /// Creates a frozen map which will use the given hash builder to hash keys, handling
+    /// duplicate keys according to `policy`.
+    ///
+    /// The entries which are kept retain their relative order.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys and the policy is [`DuplicatePolicy::Error`].
+    pub fn from_vec_with_hasher_and_policy(
+        payload: Vec<(K, V)>,
+        bh: BH,
+        policy: DuplicatePolicy,
+    ) -> std::result::Result<Self, FrozenError> {
+        let payload = policy.apply(payload, |k| bh.hash_one(k))?;
+        Ok(Self::new(payload, bh))
+    }
+
+    /// Creates a frozen map from a payload without duplicate keys.
+    fn new(payload: Vec<(K, V)>, bh: BH) -> Self {
+        let mut values = Vec::with_capacity(payload.len());
+        let keys = payload
+            .into_iter()
+            .enumerate()
+            .map(|(index, (k, v))| {
+                values.push(v);
+                (k, index)
+            })
+            .collect();
+
+        let indices = FrozenMap::from_vec_with_hasher(keys, bh);
+
+        let mut positions = vec![0; values.len()].into_boxed_slice();
+        for (position, (_, &index)) in indices.iter().enumerate() {
+            positions[index] = position;
+        }
+
+        Self {
+            indices,
+            values: values.into_boxed_slice(),
+            positions,
+        }
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    #[inline]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        Some(&self.values[*self.indices.get(key)?])
+    }
+
+    /// Returns the key-value pair corresponding to the key.
+    #[inline]
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let (k, &index) = self.indices.get_key_value(key)?;
+        Some((k, &self.values[index]))
+    }
+
+    /// Returns the index of the key along with its key-value pair.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenIndexMap;
+    ///
+    /// let map = FrozenIndexMap::from_vec(vec![("a", 1), ("b", 2)]);
+    /// assert_eq!(map.get_full("b"), Some((1, &"b", &2)));
+    /// ```
+    #[inline]
+    pub fn get_full<Q>(&self, key: &Q) -> Option<(usize, &K, &V)>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let (k, &index) = self.indices.get_key_value(key)?;
+        Some((index, k, &self.values[index]))
+    }
+
+    /// Returns the index of the key, which is its position in the payload the map was
+    /// created from.
+    #[inline]
+    pub fn get_index_of<Q>(&self, key: &Q) -> Option<usize>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.indices.get(key).copied()
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    #[inline]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        Some(&mut self.values[*self.indices.get(key)?])
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    #[inline]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.indices.contains_key(key)
+    }
+
+    /// Returns the key-value pair at the given index, without hashing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenIndexMap;
+    ///
+    /// let map = FrozenIndexMap::from_vec(vec![("a", 1), ("b", 2)]);
+    /// assert_eq!(map.get_index(0), Some((&"a", &1)));
+    /// assert_eq!(map.get_index(2), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+        let position = *self.positions.get(index)?;
+        Some((&self.keys_by_position()[position].0, &self.values[index]))
+    }
+
+    /// Returns the key and a mutable reference to the value at the given index.
+    #[inline]
+    pub fn get_index_mut(&mut self, index: usize) -> Option<(&K, &mut V)> {
+        let position = *self.positions.get(index)?;
+        let key = &self.indices.iter().entries()[position].0;
+        Some((key, &mut self.values[index]))
+    }
+
+    /// Returns the first entry, in index order.
+    #[must_use]
+    pub fn first(&self) -> Option<(&K, &V)> {
+        self.get_index(0)
+    }
+
+    /// Returns the last entry, in index order.
+    #[must_use]
+    pub fn last(&self) -> Option<(&K, &V)> {
+        self.get_index(self.len().checked_sub(1)?)
+    }
+
+    /// An iterator visiting all key-value pairs in index order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> + ExactSizeIterator {
+        let entries = self.keys_by_position();
+        self.positions
+            .iter()
+            .zip(self.values.iter())
+            .map(move |(&position, v)| (&entries[position].0, v))
+    }
+
+    /// An iterator visiting all keys in index order.
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> + ExactSizeIterator {
+        let entries = self.keys_by_position();
+        self.positions
+            .iter()
+            .map(move |&position| &entries[position].0)
+    }
+
+    /// An iterator visiting all values in index order.
+    pub fn values(&self) -> std::slice::Iter<'_, V> {
+        self.values.iter()
+    }
+
+    /// An iterator visiting all values mutably in index order.
+    pub fn values_mut(&mut self) -> std::slice::IterMut<'_, V> {
+        self.values.iter_mut()
+    }
+
+    /// Returns the number of elements in the map.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the entries of the key map, which hold the keys in hash order.
+    const fn keys_by_position(&self) -> &[(K, usize)] {
+        self.indices.iter().entries()
+    }
+}
+
+impl<K, V> FrozenIndexMap<K, V, RandomState>
+where
+    K: Hash + Eq,
+{
+    /// Creates a frozen map.
+    ///
+    /// When several entries hold the same key, the last one is kept, at its own position among
+    /// the other entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenIndexMap;
+    ///
+    /// let map = FrozenIndexMap::from_vec(vec![("a", 1), ("b", 2), ("a", 3)]);
+    /// assert_eq!(map.get_index(1), Some((&"a", &3)));
+    /// ```
+    #[must_use]
+    pub fn from_vec(payload: Vec<(K, V)>) -> Self {
+        Self::from_vec_with_hasher(payload, RandomState::new())
+    }
+
+    /// Creates a frozen map, returning an error instead of accepting duplicate keys.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys.
+    pub fn try_from_vec(payload: Vec<(K, V)>) -> std::result::Result<Self, FrozenError> {
+        Self::from_vec_with_hasher_and_policy(payload, RandomState::new(), DuplicatePolicy::Error)
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for FrozenIndexMap<K, V, RandomState>
+where
+    K: Hash + Eq,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self::from_vec(Vec::from_iter(iter))
+    }
+}
+
+impl<K, V, BH> Index<usize> for FrozenIndexMap<K, V, BH> {
+    type Output = V;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.values[index]
+    }
+}
+
+impl<K, V, BH> Len for FrozenIndexMap<K, V, BH> {
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+}
+
+impl<K, V, BH> Debug for FrozenIndexMap<K, V, BH>
+where
+    K: Hash + Eq + Debug,
+    V: Debug,
+    BH: BuildHasher,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,65 @@
+use crate::errors::FrozenError;
+use crate::facades::FrozenIndexMap;
+use crate::traits::len::Len;
+
+#[test]
+fn preserves_order() {
+    let payload: Vec<_> = (0..1000).rev().map(|i| (format!("key{i}"), i)).collect();
+    let mut map = FrozenIndexMap::from_vec(payload.clone());
+    assert_eq!(1000, map.len());
+    assert_eq!(1000, Len::len(&map));
+
+    for (index, (k, v)) in payload.iter().enumerate() {
+        assert_eq!(Some(index), map.get_index_of(k.as_str()));
+        assert_eq!(Some((k, v)), map.get_index(index));
+        assert_eq!(Some((index, k, v)), map.get_full(k.as_str()));
+        assert_eq!(Some(v), map.get(k.as_str()));
+        assert_eq!(*v, map[index]);
+    }
+
+    assert!(map
+        .iter()
+        .map(|(k, v)| (k.clone(), *v))
+        .eq(payload.iter().cloned()));
+    assert!(map.keys().eq(payload.iter().map(|x| &x.0)));
+    assert!(map.values().eq(payload.iter().map(|x| &x.1)));
+    assert_eq!(Some((&"key0".to_string(), &0)), map.iter().next_back());
+    assert_eq!(Some((&"key999".to_string(), &999)), map.first());
+    assert_eq!(Some((&"key0".to_string(), &0)), map.last());
+
+    assert_eq!(None, map.get_index(1000));
+    assert_eq!(None, map.get_index_of("key1000"));
+    assert!(!map.contains_key("key1000"));
+
+    *map.get_mut("key7").unwrap() = 70;
+    *map.get_index_mut(0).unwrap().1 = 9990;
+    assert_eq!(Some(&70), map.get("key7"));
+    assert_eq!(Some(&9990), map.get("key999"));
+}
+
+#[test]
+fn duplicates() {
+    let payload = vec![(1, 'a'), (2, 'b'), (1, 'c'), (3, 'd')];
+
+    let map = FrozenIndexMap::from_vec(payload.clone());
+    assert_eq!(3, map.len());
+    assert_eq!(
+        vec![(&2, &'b'), (&1, &'c'), (&3, &'d')],
+        map.iter().collect::<Vec<_>>()
+    );
+
+    assert_eq!(
+        Some(FrozenError::DuplicateKey { index: 2 }),
+        FrozenIndexMap::try_from_vec(payload).err()
+    );
+}
+
+#[test]
+fn empty() {
+    let map = FrozenIndexMap::<i32, i32>::from_vec(vec![]);
+    assert!(map.is_empty());
+    assert_eq!(None, map.first());
+    assert_eq!(None, map.last());
+    assert_eq!(None, map.get_index(0));
+    assert_eq!("{}", format!("{map:?}"));
+}
@@ -0,0 +1,245 @@
+use std::collections::HashSet;
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::BuildHasher;
+use std::hash::RandomState;
+use std::mem::ManuallyDrop;
+use std::ptr::NonNull;
+
+use crate::facades::FrozenMap;
+use crate::traits::len::Len;
+
+/// A handle to a string held by a [`FrozenInterner`].
+///
+/// Symbols are numbered from zero in the order their strings were first given to the
+/// interner, so interning the same strings in the same order always produces the same
+/// symbols, and a symbol can be stored as a `u32` and turned back into a symbol later.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// Creates a symbol from its number.
+    #[must_use]
+    pub const fn from_u32(value: u32) -> Self {
+        Self(value)
+    }
+
+    /// Returns the number of the symbol.
+    #[must_use]
+    pub const fn as_u32(self) -> u32 {
+        self.0
+    }
+}
+
+impl From<Symbol> for u32 {
+    fn from(symbol: Symbol) -> Self {
+        symbol.0
+    }
+}
+
+/// A fixed set of strings, each identified by a [`Symbol`].
+///
+/// The text of all the strings is copied into a single buffer when the interner is created.
+/// Resolving a symbol to its string is an array index, while looking up the symbol of a
+/// string goes through a [`FrozenMap`], so it benefits from the same analysis of the strings
+/// picking the fastest way to tell them apart.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::FrozenInterner;
+///
+/// let interner = FrozenInterner::from_vec(vec!["let", "fn", "match", "fn"]);
+///
+/// let sym = interner.lookup("match").unwrap();
+/// assert_eq!(sym.as_u32(), 2);
+/// assert_eq!(interner.resolve(sym), "match");
+/// assert_eq!(interner.lookup("loop"), None);
+/// assert_eq!(interner.len(), 3);
+/// ```
+#[allow(clippy::module_name_repetitions)]
+pub struct FrozenInterner<BH = RandomState> {
+    /// Strings referring to the arena, which are dropped before the arena is freed.
+    ///
+    /// The strings are never handed out with a longer lifetime than a borrow of the interner.
+    map: ManuallyDrop<FrozenMap<&'static str, Symbol, BH>>,
+
+    /// The strings, indexed by their symbol.
+    strings: ManuallyDrop<Box<[&'static str]>>,
+
+    /// The text of every string, one after the other, allocated as a `Box<str>`.
+    ///
+    /// The arena is held through a pointer rather than a box, since moving a box asserts
+    /// unique access to its contents, which the strings share. It's never modified or
+    /// reallocated, so the strings stay valid when the interner moves.
+    arena: NonNull<str>,
+}
+
+// SAFETY: the arena is owned by the interner and never modified, so it's as safe to send and
+// share as the strings referring to it are
+unsafe impl<BH: Send> Send for FrozenInterner<BH> {}
+unsafe impl<BH: Sync> Sync for FrozenInterner<BH> {}
+
+impl<BH> FrozenInterner<BH>
+where
+    BH: BuildHasher,
+{
+    /// Creates an interner which will use the given hash builder to hash strings.
+    ///
+    /// Repeated strings are interned once, keeping the symbol of their first occurrence.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than `u32::MAX` distinct strings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenInterner;
+    /// use std::hash::RandomState;
+    ///
+    /// let interner = FrozenInterner::from_vec_with_hasher(vec!["a", "b"], RandomState::new());
+    /// assert_eq!(interner.lookup("b").map(|s| s.as_u32()), Some(1));
+    /// ```
+    #[must_use]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn from_vec_with_hasher<S>(payload: Vec<S>, bh: BH) -> Self
+    where
+        S: AsRef<str>,
+    {
+        let mut seen = HashSet::with_capacity(payload.len());
+        let mut text = String::new();
+        let mut spans = Vec::with_capacity(payload.len());
+        for s in &payload {
+            let s = s.as_ref();
+            if seen.insert(s) {
+                let start = text.len();
+                text.push_str(s);
+                spans.push(start..text.len());
+            }
+        }
+
+        let arena = NonNull::from(Box::leak(text.into_boxed_str()));
+
+        // SAFETY: the arena lives on the heap until the interner holding the strings is
+        // dropped, and the strings are only handed out tied to a borrow of the interner
+        let text: &'static str = unsafe { arena.as_ref() };
+        let strings: Box<[&'static str]> = spans.into_iter().map(|span| &text[span]).collect();
+
+        let entries = strings
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                let symbol = u32::try_from(i).expect("An interner holds at most u32::MAX strings");
+                (*s, Symbol(symbol))
+            })
+            .collect();
+
+        Self {
+            map: ManuallyDrop::new(FrozenMap::from_vec_with_hasher(entries, bh)),
+            strings: ManuallyDrop::new(strings),
+            arena,
+        }
+    }
+
+    /// Returns the symbol of a string, if the interner holds it.
+    #[inline]
+    #[must_use]
+    pub fn lookup(&self, s: &str) -> Option<Symbol> {
+        self.map.get(s).copied()
+    }
+
+    /// Returns `true` if the interner holds the string.
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, s: &str) -> bool {
+        self.map.contains_key(s)
+    }
+}
+
+impl<BH> FrozenInterner<BH> {
+    /// Returns the string of a symbol.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the symbol wasn't produced by this interner.
+    #[inline]
+    #[must_use]
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        self.strings[symbol.0 as usize]
+    }
+
+    /// Returns the string of a symbol, or `None` if the symbol wasn't produced by this interner.
+    #[inline]
+    #[must_use]
+    pub fn try_resolve(&self, symbol: Symbol) -> Option<&str> {
+        self.strings.get(symbol.0 as usize).copied()
+    }
+
+    /// Returns the number of strings in the interner.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns `true` if the interner holds no strings.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Returns the number of bytes held by the arena, which is the total length of the strings.
+    #[must_use]
+    pub const fn arena_len(&self) -> usize {
+        // SAFETY: the arena is only freed when the interner is dropped
+        unsafe { self.arena.as_ref() }.len()
+    }
+
+    /// An iterator visiting all symbols and their strings, in symbol order.
+    pub fn iter(&self) -> impl Iterator<Item = (Symbol, &str)> {
+        (0..).map(Symbol).zip(self.strings.iter().copied())
+    }
+}
+
+impl FrozenInterner<RandomState> {
+    /// Creates an interner.
+    #[must_use]
+    pub fn from_vec<S>(payload: Vec<S>) -> Self
+    where
+        S: AsRef<str>,
+    {
+        Self::from_vec_with_hasher(payload, RandomState::new())
+    }
+}
+
+impl<S> FromIterator<S> for FrozenInterner<RandomState>
+where
+    S: AsRef<str>,
+{
+    fn from_iter<T: IntoIterator<Item = S>>(iter: T) -> Self {
+        Self::from_vec(Vec::from_iter(iter))
+    }
+}
+
+impl<BH> Drop for FrozenInterner<BH> {
+    fn drop(&mut self) {
+        // SAFETY: the strings are dropped before the arena they refer to is freed, the arena
+        // was leaked from a box, and none of them is used again
+        unsafe {
+            ManuallyDrop::drop(&mut self.map);
+            ManuallyDrop::drop(&mut self.strings);
+            drop(Box::from_raw(self.arena.as_ptr()));
+        }
+    }
+}
+
+impl<BH> Len for FrozenInterner<BH> {
+    fn len(&self) -> usize {
+        self.strings.len()
+    }
+}
+
+impl<BH> Debug for FrozenInterner<BH> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list().entries(self.strings.iter()).finish()
+    }
+}
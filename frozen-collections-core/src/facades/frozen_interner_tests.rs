@@ -0,0 +1,85 @@
+use crate::facades::{FrozenInterner, Symbol};
+use crate::traits::len::Len;
+
+#[test]
+fn symbols_round_trip() {
+    let payloads = [
+        vec![],
+        vec!["a".to_string(), "bb".to_string()],
+        (0..10).map(|i| format!("Sym-{i:03}")).collect(),
+        (0..300).map(|i| format!("Sym-{i:03}")).collect(),
+        (0..2000).map(|i| format!("{}", i * 7919)).collect(),
+    ];
+
+    for strings in payloads {
+        let interner: FrozenInterner = strings.iter().collect();
+        assert_eq!(strings.len(), interner.len());
+        assert_eq!(strings.len(), Len::len(&interner));
+        assert_eq!(
+            strings.iter().map(String::len).sum::<usize>(),
+            interner.arena_len()
+        );
+
+        for (i, s) in strings.iter().enumerate() {
+            let sym = interner.lookup(s).unwrap();
+            assert_eq!(i, sym.as_u32() as usize);
+            assert_eq!(s, interner.resolve(sym));
+            assert!(interner.contains(s));
+        }
+
+        assert_eq!(None, interner.lookup("missing"));
+        assert!(!interner.contains(""));
+        assert!(interner
+            .iter()
+            .map(|(_, s)| s)
+            .eq(strings.iter().map(String::as_str)));
+    }
+}
+
+#[test]
+fn duplicates_keep_their_first_symbol() {
+    let interner = FrozenInterner::from_vec(vec!["x", "y", "x", "z", "y"]);
+    assert_eq!(3, interner.len());
+    assert_eq!(Some(Symbol::from_u32(0)), interner.lookup("x"));
+    assert_eq!(Some(Symbol::from_u32(2)), interner.lookup("z"));
+    assert_eq!(3, interner.arena_len());
+    assert_eq!(r#"["x", "y", "z"]"#, format!("{interner:?}"));
+}
+
+#[test]
+fn foreign_symbols() {
+    let interner = {
+        let payload: Vec<_> = (0..100).map(|i| format!("s{i}")).collect();
+        FrozenInterner::from_vec(payload)
+    };
+
+    // moving the interner doesn't move the arena
+    let moved = Box::new(interner);
+    assert_eq!(Some("s42"), moved.try_resolve(Symbol::from_u32(42)));
+    assert_eq!(None, moved.try_resolve(Symbol::from_u32(100)));
+    assert_eq!(42, u32::from(moved.lookup("s42").unwrap()));
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn resolve_foreign_symbol() {
+    let interner = FrozenInterner::from_vec(vec!["a"]);
+    _ = interner.resolve(Symbol::from_u32(1));
+}
+
+#[test]
+fn sent_across_threads() {
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+    let interner = FrozenInterner::from_vec((0..50).map(|i| format!("s{i}")).collect());
+    assert_send_sync(&interner);
+
+    let interner = std::thread::spawn(move || {
+        assert_eq!(Some(Symbol::from_u32(7)), interner.lookup("s7"));
+        interner
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!("s49", interner.resolve(Symbol::from_u32(49)));
+}
@@ -0,0 +1,264 @@
+use std::collections::HashSet;
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::RandomState;
+use std::hash::{BuildHasher, Hash};
+
+use crate::analyzers::hash_code_analyzer::analyze_hash_codes;
+use crate::errors::FrozenError;
+use crate::specialized_maps::hash_table::HashTable;
+use crate::traits::equivalent::Equivalent;
+use crate::traits::len::Len;
+
+/// A frozen set of values which are looked up by a key borrowed from each value.
+///
+/// Freezing a collection of records into a [`FrozenMap`](crate::facades::FrozenMap) means
+/// pulling the key out of every record and storing it next to the record, which holds the key
+/// twice when it's also a field of the record. This set stores the values once, in the order
+/// they were given, along with a hash table of their positions, and takes a function picking
+/// the key out of a value when it is created. The keys are picked out of the values again
+/// whenever they're compared, so they're never copied.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::FrozenKeyedSet;
+///
+/// struct Route {
+///     path: String,
+///     handler: u32,
+/// }
+///
+/// let routes = vec![
+///     Route { path: "/".to_string(), handler: 1 },
+///     Route { path: "/login".to_string(), handler: 2 },
+///     Route { path: "/logout".to_string(), handler: 3 },
+/// ];
+///
+/// let set = FrozenKeyedSet::from_vec(routes, |r: &Route| &r.path);
+///
+/// assert_eq!(set.get("/login").map(|r| r.handler), Some(2));
+/// assert!(set.contains_key("/logout"));
+/// assert!(!set.contains_key("/admin"));
+/// ```
+#[allow(clippy::module_name_repetitions)]
+pub struct FrozenKeyedSet<K: ?Sized, V, BH = RandomState> {
+    /// The positions of the values, laid out by the hash codes of their keys.
+    table: HashTable<usize, ()>,
+
+    /// The values, in the order they were given.
+    values: Box<[V]>,
+
+    key_fn: fn(&V) -> &K,
+    bh: BH,
+}
+
+impl<K, V, BH> FrozenKeyedSet<K, V, BH>
+where
+    K: ?Sized + Hash + Eq,
+    BH: BuildHasher,
+{
+    /// Creates a frozen set which will use the given hash builder to hash keys.
+    ///
+    /// The keys picked out of the values are expected to be distinct.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenKeyedSet;
+    /// use std::hash::RandomState;
+    ///
+    /// let set = FrozenKeyedSet::from_vec_with_hasher(
+    ///     vec![(1, 'a'), (2, 'b')],
+    ///     |v: &(u32, char)| &v.0,
+    ///     RandomState::new(),
+    /// );
+    ///
+    /// assert_eq!(set.get(&2), Some(&(2, 'b')));
+    /// ```
+    #[must_use]
+    pub fn from_vec_with_hasher(payload: Vec<V>, key_fn: fn(&V) -> &K, bh: BH) -> Self {
+        let values = payload.into_boxed_slice();
+        let hash = |&index: &usize| bh.hash_one(key_fn(&values[index]));
+
+        let code_analysis = analyze_hash_codes((0..values.len()).map(|index| hash(&index)));
+        let positions = (0..values.len()).map(|index| (index, ())).collect();
+        let table = HashTable::from_analysis(positions, code_analysis, false, hash);
+
+        Self {
+            table,
+            values,
+            key_fn,
+            bh,
+        }
+    }
+
+    /// Creates a frozen set which will use the given hash builder to hash keys, returning an
+    /// error instead of accepting values with duplicate keys.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the keys of two values are equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::errors::FrozenError;
+    /// use frozen_collections_core::facades::FrozenKeyedSet;
+    /// use std::hash::RandomState;
+    ///
+    /// let set = FrozenKeyedSet::try_from_vec_with_hasher(
+    ///     vec![(1, 'a'), (1, 'b')],
+    ///     |v: &(u32, char)| &v.0,
+    ///     RandomState::new(),
+    /// );
+    ///
+    /// assert_eq!(set.err(), Some(FrozenError::DuplicateKey { index: 1 }));
+    /// ```
+    pub fn try_from_vec_with_hasher(
+        payload: Vec<V>,
+        key_fn: fn(&V) -> &K,
+        bh: BH,
+    ) -> std::result::Result<Self, FrozenError> {
+        let mut seen = HashSet::with_capacity(payload.len());
+        if let Some(index) = payload.iter().position(|v| !seen.insert(key_fn(v))) {
+            return Err(FrozenError::DuplicateKey { index });
+        }
+
+        drop(seen);
+        Ok(Self::from_vec_with_hasher(payload, key_fn, bh))
+    }
+
+    /// Returns a reference to the value whose key matches the given one.
+    ///
+    /// The key may be any borrowed form of the set's key type, as with a
+    /// [`FrozenMap`](crate::facades::FrozenMap).
+    #[inline]
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let probe = Probe {
+            key,
+            values: &self.values,
+            key_fn: self.key_fn,
+        };
+
+        let (index, ()) = self.table.find(self.bh.hash_one(key), &probe)?;
+        Some(&self.values[*index])
+    }
+
+    /// Returns the key and the value whose key matches the given one.
+    #[inline]
+    #[must_use]
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let v = self.get(key)?;
+        Some(((self.key_fn)(v), v))
+    }
+
+    /// Returns `true` if the set holds a value whose key matches the given one.
+    #[inline]
+    #[must_use]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.get(key).is_some()
+    }
+}
+
+impl<K, V, BH> FrozenKeyedSet<K, V, BH>
+where
+    K: ?Sized,
+{
+    /// Returns the number of values in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the set holds no values.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the values, in the order they were given.
+    #[must_use]
+    pub fn as_slice(&self) -> &[V] {
+        &self.values
+    }
+
+    /// An iterator visiting all values, in the order they were given.
+    pub fn iter(&self) -> std::slice::Iter<'_, V> {
+        self.values.iter()
+    }
+
+    /// An iterator visiting the keys of the values, in the order the values were given.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.values.iter().map(self.key_fn)
+    }
+}
+
+impl<K, V> FrozenKeyedSet<K, V, RandomState>
+where
+    K: ?Sized + Hash + Eq,
+{
+    /// Creates a frozen set.
+    #[must_use]
+    pub fn from_vec(payload: Vec<V>, key_fn: fn(&V) -> &K) -> Self {
+        Self::from_vec_with_hasher(payload, key_fn, RandomState::new())
+    }
+}
+
+impl<'a, K, V, BH> IntoIterator for &'a FrozenKeyedSet<K, V, BH>
+where
+    K: ?Sized,
+{
+    type Item = &'a V;
+    type IntoIter = std::slice::Iter<'a, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V, BH> Len for FrozenKeyedSet<K, V, BH>
+where
+    K: ?Sized,
+{
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+}
+
+impl<K, V, BH> Debug for FrozenKeyedSet<K, V, BH>
+where
+    K: ?Sized,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_set().entries(self.values.iter()).finish()
+    }
+}
+
+/// A key being looked up, which compares it to the key of the value at a position.
+struct Probe<'a, Q: ?Sized, K: ?Sized, V> {
+    key: &'a Q,
+    values: &'a [V],
+    key_fn: fn(&V) -> &K,
+}
+
+impl<Q, K, V> Equivalent<usize> for Probe<'_, Q, K, V>
+where
+    Q: ?Sized + Equivalent<K>,
+    K: ?Sized,
+{
+    #[inline]
+    fn equivalent(&self, index: &usize) -> bool {
+        self.key.equivalent((self.key_fn)(&self.values[*index]))
+    }
+}
@@ -0,0 +1,110 @@
+use crate::errors::FrozenError;
+use crate::facades::FrozenKeyedSet;
+use crate::traits::len::Len;
+
+#[derive(Debug, PartialEq)]
+struct Route {
+    path: String,
+    handler: usize,
+}
+
+fn routes(count: usize) -> Vec<Route> {
+    (0..count)
+        .map(|i| Route {
+            path: format!("/route/{i:04}"),
+            handler: i,
+        })
+        .collect()
+}
+
+#[test]
+fn lookup_by_field() {
+    for count in [0, 1, 3, 10, 300, 2000] {
+        let set = FrozenKeyedSet::from_vec(routes(count), |r: &Route| &r.path);
+        assert_eq!(count, set.len());
+        assert_eq!(count, Len::len(&set));
+        assert_eq!(count == 0, set.is_empty());
+
+        for i in 0..count {
+            let path = format!("/route/{i:04}");
+            assert_eq!(Some(i), set.get(path.as_str()).map(|r| r.handler));
+            assert_eq!(Some(i), set.get(&path).map(|r| r.handler));
+            assert_eq!(
+                Some(path.as_str()),
+                set.get_key_value(&path).map(|(k, _)| k.as_str())
+            );
+            assert!(set.contains_key(path.as_str()));
+        }
+
+        assert_eq!(None, set.get("/missing"));
+        assert!(!set.contains_key(""));
+        assert_eq!(count, set.keys().count());
+        assert!(set.iter().map(|r| r.handler).eq(0..count));
+        assert!((&set).into_iter().eq(set.as_slice()));
+    }
+}
+
+#[test]
+fn unsized_and_integer_keys() {
+    let set = FrozenKeyedSet::from_vec(routes(100), |r: &Route| r.path.as_str());
+    assert_eq!(Some(42), set.get("/route/0042").map(|r| r.handler));
+
+    let set = FrozenKeyedSet::from_vec(routes(100), |r: &Route| &r.handler);
+    assert_eq!(Some("/route/0042"), set.get(&42).map(|r| r.path.as_str()));
+    assert_eq!(None, set.get(&100));
+}
+
+#[test]
+fn outlives_its_payload() {
+    let set = FrozenKeyedSet::from_vec(routes(100), |r: &Route| &r.path);
+
+    // moving the set doesn't move the values
+    let moved = Box::new(set);
+    assert_eq!(Some(7), moved.get("/route/0007").map(|r| r.handler));
+    assert_eq!(
+        r#"{Route { path: "/route/0000", handler: 0 }}"#,
+        format!(
+            "{:?}",
+            FrozenKeyedSet::from_vec(routes(1), |r: &Route| &r.path)
+        )
+    );
+}
+
+#[test]
+fn duplicate_keys() {
+    let mut payload = routes(10);
+    payload[7].path = "/route/0003".to_string();
+
+    let set = FrozenKeyedSet::try_from_vec_with_hasher(
+        payload,
+        |r: &Route| &r.path,
+        std::hash::RandomState::new(),
+    );
+    assert_eq!(Some(FrozenError::DuplicateKey { index: 7 }), set.err());
+}
+
+#[test]
+fn borrowed_records() {
+    struct Employee<'a> {
+        name: &'a str,
+        team: &'a str,
+    }
+
+    let text = String::from("ada:compilers,grace:languages,alan:theory");
+    let employees: Vec<_> = text
+        .split(',')
+        .map(|line| {
+            let (name, team) = line.split_once(':').unwrap();
+            Employee { name, team }
+        })
+        .collect();
+
+    let set = FrozenKeyedSet::from_vec(employees, |e: &Employee<'_>| e.name);
+    assert_eq!(Some("languages"), set.get("grace").map(|e| e.team));
+    assert_eq!(
+        Some(("alan", "theory")),
+        set.get_key_value("alan").map(|(k, e)| (k, e.team))
+    );
+    assert!(!set.contains_key("edsger"));
+    assert!(set.keys().eq(["ada", "grace", "alan"]));
+}
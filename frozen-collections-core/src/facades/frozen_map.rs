@@ -1,41 +1,81 @@
-use std::any::type_name;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::{Debug, Formatter, Result};
 use std::hash::RandomState;
-use std::hash::{BuildHasher, Hash};
-use std::mem::transmute;
-use std::mem::MaybeUninit;
+use std::hash::{BuildHasher, Hash, Hasher};
 use std::ops::Index;
 use std::ops::IndexMut;
+use std::time::Instant;
 
 use bitvec::macros::internal::funty::Fundamental;
 
-use crate::analyzers::int_key_analyzer::{analyze_int_keys, IntKeyAnalysisResult};
-use crate::analyzers::slice_key_analyzer::{analyze_slice_keys, SliceKeyAnalysisResult};
+use crate::analyzers::duplicate_key_analyzer::find_key_groups;
+use crate::analyzers::hash_code_analyzer::HashCodeAnalysisOptions;
+use crate::analyzers::int_key_analyzer::{
+    find_code_range, find_dense_code_range, MAX_INTEGER_SCANNING_KEYS,
+};
+use crate::analyzers::perfect_hash_analyzer::analyze_perfect_hash;
+use crate::analyzers::slice_key_analyzer::{
+    analyze_slice_keys_with, verify_slice_keys, SliceKeyAnalysisResult,
+};
+use crate::codegen::{emit_static_map_with, RustLiteral};
+use crate::errors::FrozenError;
+use crate::facades::lookup_stream::Probe;
+use crate::facades::{
+    AnalysisOptions, DeferredFrozenMap, DuplicatePolicy, KeySuggester, LookupStream, MapStrategy,
+    SecondaryIndex,
+};
+use crate::hashers::{hash_unordered, PassThroughHasher, SeededState};
+use crate::specialized_maps::hash_table::sort_entries_by;
+use crate::specialized_maps::key_codes::{capture_bytes, int_code, probe_int};
 use crate::specialized_maps::*;
+use crate::traits::equivalent::Equivalent;
+use crate::traits::heap_size::{entries_heap_bytes, HeapSize};
 use crate::traits::len::Len;
+use crate::traits::map::Map;
 
-// TODO: make this type implement Len
+#[cfg(feature = "deepsize")]
+use deepsize::DeepSizeOf;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// The number of string keys from which a perfect hash map is preferred over a common map.
+const MIN_PERFECT_HASH_KEYS: usize = 1024;
+
+/// The share of a map's keys, in percent, which may be added or removed by a refreeze while
+/// still reusing the map's analysis.
+const MAX_REFREEZE_CHANGE_PERCENT: usize = 25;
 
 /// The different implementations available for use, depending on the type and content of the payload.
+///
+/// The integer and slice implementations aren't restricted to primitive integers and strings:
+/// they are picked based on what the keys' [`Hash`] implementation writes to a hasher, so they
+/// work just as well for newtypes, fieldless enums, `&str`, `Cow<str>`, byte vectors, and so on.
 #[derive(Clone)]
 enum MapTypes<K, V, BH> {
     Scanning(ScanningMap<K, V>),
 
-    CommonSmall(CommonMap<K, V, u8, BH>),
-    CommonLarge(CommonMap<K, V, usize, BH>),
+    Common(CommonMap<K, V, BH>),
+
+    PerfectHash(PerfectHashMap<K, V, BH>),
 
-    U32Small(IntegerMap<u32, V, u8>),
-    U32Large(IntegerMap<u32, V, usize>),
+    Integer(IntegerMap<K, V>),
 
-    U32Range(IntegerRangeMap<u32, V>),
+    IntegerRange(IntegerRangeMap<K, V>),
+    Dense(DenseIntegerMap<K, V>),
+    IntegerScanning(IntegerScanningMap<K, V>),
 
-    LeftStringSliceSmall(LeftSliceMap<String, V, u8, BH>),
-    LeftStringSliceLarge(LeftSliceMap<String, V, usize, BH>),
+    LeftSlice(LeftSliceMap<K, V, BH>),
 
-    RightStringSliceSmall(RightSliceMap<String, V, u8, BH>),
-    RightStringSliceLarge(RightSliceMap<String, V, usize, BH>),
+    LengthSlice(LengthSliceMap<K, V, BH>),
 
-    StringLengthSmall(LengthMap<String, V, u8>),
+    RightSlice(RightSliceMap<K, V, BH>),
+
+    SplitSlice(SplitSliceMap<K, V, BH>),
+
+    Length(LengthMap<K, V>),
 }
 
 /// A map optimized for fast read access.
@@ -65,6 +105,10 @@ enum MapTypes<K, V, BH> {
 /// In other words, if two keys are equal, their hashes must be equal.
 /// Violating this property is a logic error.
 ///
+/// Keys which implement [`Ord`] but not [`Hash`] can be stored in a
+/// [`FrozenOrderedMap`](crate::facades::FrozenOrderedMap) instead, and a few keys which only
+/// implement [`Eq`] can be stored in a map created with [`FrozenMap::scanning`].
+///
 /// It is also a logic error for a key to be modified in such a way that the key's
 /// hash, as determined by the [`Hash`] trait, or its equality, as determined by
 /// the [`Eq`] trait, changes while it is in the map. This is normally only
@@ -155,538 +199,2215 @@ pub struct FrozenMap<K, V, BH = RandomState> {
     map_impl: MapTypes<K, V, BH>,
 }
 
-impl<K, V, BH> FrozenMap<K, V, BH>
-where
-    K: Hash + Eq,
-    BH: BuildHasher,
-{
-    /// Creates a frozen map which will use the given hash builder to hash
-    /// keys.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use frozen_collections_core::facades::FrozenMap;
-    /// use std::hash::RandomState;
-    ///
-    /// let map = FrozenMap::from_vec_with_hasher(vec![(1, 2), (3, 4)], RandomState::new());
-    /// ```
-    #[must_use]
-    pub fn from_vec_with_hasher(payload: Vec<(K, V)>, bh: BH) -> Self {
-        Self::new(payload, bh)
-    }
-
-    /// Creates a frozen map which will use the given hash builder to hash
-    /// keys.
-    ///
-    /// # Examples
+impl<K, V, BH> FrozenMap<K, V, BH> {
+    /// Creates an empty map.
     ///
-    /// ```
-    /// use frozen_collections_core::facades::FrozenMap;
-    /// use std::hash::RandomState;
+    /// This neither allocates nor sets up a hasher, which makes it cheap to create
+    /// placeholders for optional tables which are usually left empty.
     ///
-    /// let v = vec![(1, 2), (3, 4)];
-    /// let map = FrozenMap::from_iter_with_hasher(v, RandomState::new());
-    /// ```
-    #[must_use]
-    pub fn from_iter_with_hasher<T: IntoIterator<Item = (K, V)>>(iter: T, bh: BH) -> Self {
-        Self::new(Vec::from_iter(iter), bh)
-    }
-
-    /// Creates a frozen map which will use the given hash builder to hash
-    /// keys.
+    /// This isn't a `const fn`, since the boxed slices frozen collections are made of can't
+    /// be created in constant expressions. Use
+    /// [`StaticMap::empty`](crate::specialized_maps::StaticMap::empty) to initialize a
+    /// `static` item instead.
     ///
     /// # Examples
     ///
     /// ```
     /// use frozen_collections_core::facades::FrozenMap;
-    /// use std::hash::RandomState;
     ///
-    /// let map = FrozenMap::with_hasher([(0, 1), (2, 3)], RandomState::new());
+    /// let map = FrozenMap::<String, u32>::empty();
+    /// assert!(map.is_empty());
+    /// assert_eq!(map.get(&"a".to_string()), None);
     /// ```
     #[must_use]
-    pub fn with_hasher<const N: usize>(payload: [(K, V); N], bh: BH) -> Self {
-        Self::from_iter_with_hasher(payload, bh)
-    }
-
-    fn new(payload: Vec<(K, V)>, bh: BH) -> Self {
+    pub fn empty() -> Self {
         Self {
-            map_impl: if payload.len() < 4 {
-                MapTypes::Scanning(ScanningMap::from_vec(payload))
-            } else if type_name::<K>() == type_name::<u32>() {
-                Self::new_u32_map(payload)
-            } else if type_name::<K>() == type_name::<String>() {
-                Self::new_string_map(payload, bh)
-            } else {
-                Self::new_common_map(payload, bh)
-            },
-        }
-    }
-
-    #[allow(clippy::transmute_undefined_repr)]
-    fn new_u32_map(payload: Vec<(K, V)>) -> MapTypes<K, V, BH> {
-        let payload: Vec<(u32, V)> = unsafe { transmute(payload) };
-
-        let key_analysis = analyze_int_keys(payload.iter().map(|x| x.0));
-
-        match key_analysis {
-            IntKeyAnalysisResult::Range => MapTypes::U32Range(IntegerRangeMap::from_vec(payload)),
-            IntKeyAnalysisResult::Normal => {
-                if payload.len() <= u8::MAX.as_usize() {
-                    MapTypes::U32Small(IntegerMap::from_vec(payload))
-                } else {
-                    MapTypes::U32Large(IntegerMap::from_vec(payload))
-                }
-            }
-        }
-    }
-
-    #[allow(clippy::transmute_undefined_repr)]
-    fn new_string_map(payload: Vec<(K, V)>, bh: BH) -> MapTypes<K, V, BH> {
-        let payload: Vec<(String, V)> = unsafe { transmute(payload) };
-
-        let key_analysis = analyze_slice_keys(payload.iter().map(|x| x.0.as_bytes()), &bh);
-
-        if payload.len() <= u8::MAX.as_usize() {
-            match key_analysis {
-                SliceKeyAnalysisResult::Normal => MapTypes::CommonSmall(
-                    CommonMap::from_vec_with_hasher(unsafe { transmute(payload) }, bh),
-                ),
-
-                SliceKeyAnalysisResult::LeftHandSubslice {
-                    subslice_index,
-                    subslice_len,
-                } => MapTypes::LeftStringSliceSmall(LeftSliceMap::from_vec_with_hasher(
-                    payload,
-                    subslice_index..subslice_index + subslice_len,
-                    bh,
-                )),
-
-                SliceKeyAnalysisResult::RightHandSubslice {
-                    subslice_index,
-                    subslice_len,
-                } => MapTypes::RightStringSliceSmall(RightSliceMap::from_vec_with_hasher(
-                    payload,
-                    subslice_index..subslice_index + subslice_len,
-                    bh,
-                )),
-
-                SliceKeyAnalysisResult::Length => {
-                    MapTypes::StringLengthSmall(LengthMap::from_vec(payload))
-                }
-            }
-        } else {
-            match key_analysis {
-                SliceKeyAnalysisResult::Length | SliceKeyAnalysisResult::Normal => {
-                    MapTypes::CommonLarge(CommonMap::from_vec_with_hasher(
-                        unsafe { transmute(payload) },
-                        bh,
-                    ))
-                }
-
-                SliceKeyAnalysisResult::LeftHandSubslice {
-                    subslice_index,
-                    subslice_len,
-                } => MapTypes::LeftStringSliceLarge(LeftSliceMap::from_vec_with_hasher(
-                    payload,
-                    subslice_index..subslice_index + subslice_len,
-                    bh,
-                )),
-
-                SliceKeyAnalysisResult::RightHandSubslice {
-                    subslice_index,
-                    subslice_len,
-                } => MapTypes::RightStringSliceLarge(RightSliceMap::from_vec_with_hasher(
-                    payload,
-                    subslice_index..subslice_index + subslice_len,
-                    bh,
-                )),
-            }
-        }
-    }
-
-    fn new_common_map(payload: Vec<(K, V)>, bh: BH) -> MapTypes<K, V, BH> {
-        if payload.len() <= u8::MAX.as_usize() {
-            MapTypes::CommonSmall(CommonMap::from_vec_with_hasher(payload, bh))
-        } else {
-            MapTypes::CommonLarge(CommonMap::from_vec_with_hasher(payload, bh))
+            map_impl: MapTypes::Scanning(ScanningMap::empty()),
         }
     }
 
-    /// Returns a reference to the value corresponding to the key.
+    /// Returns the number of elements in the map.
     ///
     /// # Examples
     ///
     /// ```
     /// use frozen_collections_core::facades::FrozenMap;
     ///
-    /// let map = FrozenMap::from([(1, "a".to_string())]);
-    /// assert_eq!(map.get(&1), Some(&"a".to_string()));
-    /// assert_eq!(map.get(&2), None);
+    /// let a = FrozenMap::from([(1, 2)]);
+    /// assert_eq!(a.len(), 1);
     /// ```
-    #[inline]
-    pub fn get(&self, key: &K) -> Option<&V> {
+    pub fn len(&self) -> usize {
         match &self.map_impl {
-            MapTypes::Scanning(m) => m.get(key),
-            MapTypes::CommonSmall(m) => m.get(key),
-            MapTypes::CommonLarge(m) => m.get(key),
-            MapTypes::U32Small(m) => m.get(unsafe { transmute(key) }),
-            MapTypes::U32Large(m) => m.get(unsafe { transmute(key) }),
-            MapTypes::U32Range(m) => m.get(unsafe { transmute(key) }),
-            MapTypes::LeftStringSliceSmall(m) => {
-                let k: &String = unsafe { transmute(key) };
-                m.get(k)
-            }
-            MapTypes::LeftStringSliceLarge(m) => {
-                let k: &String = unsafe { transmute(key) };
-                m.get(k)
-            }
-            MapTypes::RightStringSliceSmall(m) => {
-                let k: &String = unsafe { transmute(key) };
-                m.get(k)
-            }
-            MapTypes::RightStringSliceLarge(m) => {
-                let k: &String = unsafe { transmute(key) };
-                m.get(k)
-            }
-            MapTypes::StringLengthSmall(m) => {
-                let k: &String = unsafe { transmute(key) };
-                m.get(k)
-            }
+            MapTypes::Scanning(m) => m.len(),
+            MapTypes::Common(m) => m.len(),
+            MapTypes::PerfectHash(m) => m.len(),
+            MapTypes::Integer(m) => m.len(),
+            MapTypes::IntegerRange(m) => m.len(),
+            MapTypes::Dense(m) => m.len(),
+            MapTypes::IntegerScanning(m) => m.len(),
+            MapTypes::LeftSlice(m) => m.len(),
+            MapTypes::LengthSlice(m) => m.len(),
+            MapTypes::RightSlice(m) => m.len(),
+            MapTypes::SplitSlice(m) => m.len(),
+            MapTypes::Length(m) => m.len(),
         }
     }
 
-    /// Returns the key-value pair corresponding to the supplied key.
+    /// Returns the implementation picked for the map's payload.
     ///
     /// # Examples
     ///
     /// ```
-    /// use frozen_collections_core::facades::FrozenMap;
+    /// use frozen_collections_core::facades::{FrozenMap, MapStrategy};
     ///
-    /// let map = FrozenMap::from([(1, "a".to_string())]);
-    /// assert_eq!(map.get_key_value(&1), Some((&1, &"a".to_string())));
-    /// assert_eq!(map.get_key_value(&2), None);
+    /// let map = FrozenMap::from([("a", 1), ("b", 2)]);
+    /// assert_eq!(map.strategy(), MapStrategy::Scanning);
     /// ```
-    #[inline]
-    pub fn get_key_value(&self, key: &K) -> Option<(&K, &V)> {
+    #[must_use]
+    pub fn strategy(&self) -> MapStrategy {
         match &self.map_impl {
-            MapTypes::Scanning(m) => m.get_key_value(key),
-            MapTypes::CommonSmall(m) => m.get_key_value(key),
-            MapTypes::CommonLarge(m) => m.get_key_value(key),
-            MapTypes::U32Small(m) => unsafe { transmute(m.get_key_value(transmute(key))) },
-            MapTypes::U32Large(m) => unsafe { transmute(m.get_key_value(transmute(key))) },
-            MapTypes::U32Range(m) => unsafe { transmute(m.get_key_value(transmute(key))) },
-            MapTypes::LeftStringSliceSmall(m) => unsafe {
-                let k: &String = transmute(key);
-                transmute(m.get_key_value(k))
-            },
-            MapTypes::LeftStringSliceLarge(m) => unsafe {
-                let k: &String = transmute(key);
-                transmute(m.get_key_value(k))
+            MapTypes::Scanning(_) => MapStrategy::Scanning,
+            MapTypes::Common(_) => MapStrategy::Common,
+            MapTypes::PerfectHash(_) => MapStrategy::PerfectHash,
+            MapTypes::Integer(_) => MapStrategy::Integer,
+            MapTypes::IntegerRange(_) => MapStrategy::IntegerRange,
+            MapTypes::Dense(_) => MapStrategy::DenseInteger,
+            MapTypes::IntegerScanning(_) => MapStrategy::IntegerScanning,
+            MapTypes::LeftSlice(m) => MapStrategy::LeftSlice {
+                range: m.range.clone(),
             },
-            MapTypes::RightStringSliceSmall(m) => unsafe {
-                let k: &String = transmute(key);
-                transmute(m.get_key_value(k))
+            MapTypes::LengthSlice(m) => MapStrategy::LengthSlice {
+                range: m.range.clone(),
             },
-            MapTypes::RightStringSliceLarge(m) => unsafe {
-                let k: &String = transmute(key);
-                transmute(m.get_key_value(k))
+            MapTypes::RightSlice(m) => MapStrategy::RightSlice {
+                range: m.range.clone(),
             },
-            MapTypes::StringLengthSmall(m) => unsafe {
-                let k: &String = transmute(key);
-                transmute(m.get_key_value(k))
+            MapTypes::SplitSlice(m) => MapStrategy::SplitSlice {
+                left: m.left.clone(),
+                right: m.right.clone(),
             },
+            MapTypes::Length(_) => MapStrategy::Length,
         }
     }
 
-    /// Returns a mutable reference to the value corresponding to the key.
+    /// Returns the number of buckets the hash codes of keys are distributed over.
+    ///
+    /// Maps which don't hash their keys, because they scan or index them directly, have no
+    /// buckets.
     ///
     /// # Examples
     ///
     /// ```
     /// use frozen_collections_core::facades::FrozenMap;
     ///
-    /// let mut map = FrozenMap::from([(1, "a".to_string())]);
-    /// assert_eq!(map.get_mut(&1), Some(&mut "a".to_string()));
-    /// assert_eq!(map.get_mut(&2), None);
+    /// let map = FrozenMap::from_iter((0..100).map(|i| (i * 7, i)));
+    /// assert!(map.bucket_count() >= map.len());
+    ///
+    /// let map = FrozenMap::from([(1, "a"), (2, "b")]);
+    /// assert_eq!(map.bucket_count(), 0);
     /// ```
-    #[inline]
-    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
-        match &mut self.map_impl {
-            MapTypes::Scanning(m) => m.get_mut(key),
-            MapTypes::CommonSmall(m) => m.get_mut(key),
-            MapTypes::CommonLarge(m) => m.get_mut(key),
-            MapTypes::U32Small(m) => m.get_mut(unsafe { transmute(key) }),
-            MapTypes::U32Large(m) => m.get_mut(unsafe { transmute(key) }),
-            MapTypes::U32Range(m) => {
-                let k = unsafe { transmute(key) };
-                m.get_mut(k)
-            }
-            MapTypes::LeftStringSliceSmall(m) => {
-                let k: &String = unsafe { transmute(key) };
-                m.get_mut(k)
-            }
-            MapTypes::LeftStringSliceLarge(m) => {
-                let k: &String = unsafe { transmute(key) };
-                m.get_mut(k)
-            }
-            MapTypes::RightStringSliceSmall(m) => {
-                let k: &String = unsafe { transmute(key) };
-                m.get_mut(k)
-            }
-            MapTypes::RightStringSliceLarge(m) => {
-                let k: &String = unsafe { transmute(key) };
-                m.get_mut(k)
-            }
-            MapTypes::StringLengthSmall(m) => {
-                let k: &String = unsafe { transmute(key) };
-                m.get_mut(k)
-            }
+    #[must_use]
+    pub fn bucket_count(&self) -> usize {
+        match &self.map_impl {
+            MapTypes::Scanning(_)
+            | MapTypes::IntegerRange(_)
+            | MapTypes::Dense(_)
+            | MapTypes::IntegerScanning(_) => 0,
+            MapTypes::Common(m) => m.table.num_slots(),
+            MapTypes::PerfectHash(m) => m.entries.len(),
+            MapTypes::Integer(m) => m.table.num_slots(),
+            MapTypes::LeftSlice(m) => m.table.num_slots(),
+            MapTypes::LengthSlice(m) => m.table.num_slots(),
+            MapTypes::RightSlice(m) => m.table.num_slots(),
+            MapTypes::SplitSlice(m) => m.table.num_slots(),
+            MapTypes::Length(m) => m.table.num_slots(),
         }
     }
 
-    /// Attempts to get mutable references to `N` values in the map at once.
+    /// Returns `true` if the map was built with a prefilter ruling out most absent keys, as
+    /// requested with [`AnalysisOptions::prefilter`].
     ///
-    /// Returns an array of length `N` with the results of each query. For soundness, at most one
-    /// mutable reference will be returned to any value. `None` will be returned if any of the
-    /// keys are duplicates or missing.
+    /// Maps which don't hash their keys never hold a prefilter.
     ///
     /// # Examples
     ///
     /// ```
-    /// use frozen_collections_core::facades::FrozenMap;
-    ///
-    /// let mut libraries = FrozenMap::from([
-    ///     ("Bodleian Library".to_string(), 1602),
-    ///     ("Athenæum".to_string(), 1807),
-    ///     ("Herzogin-Anna-Amalia-Bibliothek".to_string(), 1691),
-    ///     ("Library of Congress".to_string(), 1800)
-    /// ]);
-    ///
-    /// let got = libraries.get_many_mut([
-    ///     &"Athenæum".to_string(),
-    ///     &"Library of Congress".to_string(),
-    /// ]);
-    /// assert_eq!(
-    ///     got,
-    ///     Some([
-    ///         &mut 1807,
-    ///         &mut 1800,
-    ///     ]),
-    /// );
+    /// use frozen_collections_core::facades::{AnalysisOptions, FrozenMap};
+    /// use std::hash::RandomState;
     ///
-    /// // Missing keys result in None
-    /// let got = libraries.get_many_mut([
-    ///     &"Athenæum".to_string(),
-    ///     &"New York Public Library".to_string(),
-    /// ]);
-    /// assert_eq!(got, None);
+    /// let payload: Vec<_> = (0..1000).map(|i| (format!("key{i}"), i)).collect();
+    /// let options = AnalysisOptions::new().prefilter(true);
+    /// let map = FrozenMap::from_vec_with_options(payload, RandomState::new(), options);
     ///
-    /// // Duplicate keys result in None
-    /// let got = libraries.get_many_mut([
-    ///     &"Athenæum".to_string(),
-    ///     &"Athenæum".to_string(),
-    /// ]);
-    /// assert_eq!(got, None);
+    /// assert!(map.has_prefilter());
+    /// assert!(!FrozenMap::from([(1, "a"), (2, "b")]).has_prefilter());
     /// ```
-    #[allow(mutable_transmutes)]
-    pub fn get_many_mut<const N: usize>(&mut self, keys: [&K; N]) -> Option<[&mut V; N]> {
-        // ensure key uniqueness (assumes "keys" is a relatively small array)
-        for i in 0..keys.len() {
-            for j in 0..i {
-                if keys[j].eq(keys[i]) {
-                    return None;
-                }
-            }
-        }
-
-        unsafe {
-            let mut result: MaybeUninit<[&mut V; N]> = MaybeUninit::uninit();
-            let p = result.as_mut_ptr();
-
-            for (i, key) in keys.iter().enumerate() {
-                *(*p).get_unchecked_mut(i) = transmute(self.get(key)?);
-            }
-
-            Some(result.assume_init())
+    #[must_use]
+    pub fn has_prefilter(&self) -> bool {
+        match &self.map_impl {
+            MapTypes::Scanning(_)
+            | MapTypes::IntegerRange(_)
+            | MapTypes::Dense(_)
+            | MapTypes::IntegerScanning(_) => false,
+            MapTypes::Common(m) => m.table.has_prefilter(),
+            MapTypes::PerfectHash(m) => m.has_prefilter(),
+            MapTypes::Integer(m) => m.table.has_prefilter(),
+            MapTypes::LeftSlice(m) => m.table.has_prefilter(),
+            MapTypes::LengthSlice(m) => m.table.has_prefilter(),
+            MapTypes::RightSlice(m) => m.table.has_prefilter(),
+            MapTypes::SplitSlice(m) => m.table.has_prefilter(),
+            MapTypes::Length(m) => m.table.has_prefilter(),
         }
     }
 
-    /// Returns `true` if the map contains a value for the specified key.
+    /// Returns the largest number of keys sharing a bucket, which bounds the number of keys
+    /// a lookup compares.
+    ///
+    /// Maps without buckets report zero, as described for [`Self::bucket_count`].
     ///
     /// # Examples
     ///
     /// ```
     /// use frozen_collections_core::facades::FrozenMap;
     ///
-    /// let map = FrozenMap::from([(1, "a".to_string())]);
-    ///
-    /// assert_eq!(map.contains_key(&1), true);
-    /// assert_eq!(map.contains_key(&2), false);
+    /// let map = FrozenMap::from_iter((0..100).map(|i| (format!("key{i}"), i)));
+    /// assert!(map.max_collisions() >= 1);
     /// ```
-    pub fn contains_key(&self, key: &K) -> bool {
-        self.get(key).is_some()
+    #[must_use]
+    pub fn max_collisions(&self) -> usize {
+        match &self.map_impl {
+            MapTypes::Scanning(_)
+            | MapTypes::IntegerRange(_)
+            | MapTypes::Dense(_)
+            | MapTypes::IntegerScanning(_) => 0,
+            MapTypes::Common(m) => m.table.max_collisions(),
+            MapTypes::PerfectHash(m) => usize::from(!m.entries.is_empty()),
+            MapTypes::Integer(m) => m.table.max_collisions(),
+            MapTypes::LeftSlice(m) => m.table.max_collisions(),
+            MapTypes::LengthSlice(m) => m.table.max_collisions(),
+            MapTypes::RightSlice(m) => m.table.max_collisions(),
+            MapTypes::SplitSlice(m) => m.table.max_collisions(),
+            MapTypes::Length(m) => m.table.max_collisions(),
+        }
     }
 
-    /// Returns the number of elements in the map.
+    /// Describes the layout of the map: its slots, how crowded they are, and the memory taken
+    /// by its keys, values and slots.
     ///
     /// # Examples
     ///
     /// ```
     /// use frozen_collections_core::facades::FrozenMap;
     ///
-    /// let a = FrozenMap::from([(1, 2)]);
-    /// assert_eq!(a.len(), 1);
+    /// let map = FrozenMap::from_iter((0..100).map(|i| (format!("key{i}"), i)));
+    /// let stats = map.stats();
+    ///
+    /// assert_eq!(stats.num_slots, map.bucket_count());
+    /// assert_eq!(stats.longest_probe_chain, map.max_collisions());
+    /// assert!(stats.heap_bytes() > stats.key_bytes + stats.value_bytes);
     /// ```
-    pub fn len(&self) -> usize {
+    #[must_use]
+    pub fn stats(&self) -> MapStats {
         match &self.map_impl {
-            MapTypes::Scanning(m) => m.len(),
-            MapTypes::CommonSmall(m) => m.len(),
-            MapTypes::CommonLarge(m) => m.len(),
-            MapTypes::U32Small(m) => m.len(),
-            MapTypes::U32Large(m) => m.len(),
-            MapTypes::U32Range(m) => m.len(),
-            MapTypes::LeftStringSliceSmall(m) => m.len(),
-            MapTypes::LeftStringSliceLarge(m) => m.len(),
-            MapTypes::RightStringSliceSmall(m) => m.len(),
-            MapTypes::RightStringSliceLarge(m) => m.len(),
-            MapTypes::StringLengthSmall(m) => m.len(),
+            MapTypes::Scanning(m) => m.stats(),
+            MapTypes::Common(m) => m.stats(),
+            MapTypes::PerfectHash(m) => m.stats(),
+            MapTypes::Integer(m) => m.stats(),
+            MapTypes::IntegerRange(m) => m.stats(),
+            MapTypes::Dense(m) => m.stats(),
+            MapTypes::IntegerScanning(m) => m.stats(),
+            MapTypes::LeftSlice(m) => m.stats(),
+            MapTypes::LengthSlice(m) => m.stats(),
+            MapTypes::RightSlice(m) => m.stats(),
+            MapTypes::SplitSlice(m) => m.stats(),
+            MapTypes::Length(m) => m.stats(),
         }
     }
+}
 
-    /// Returns `true` if the map contains no elements.
+impl<K, V, BH> FrozenMap<K, V, BH>
+where
+    K: Hash + Eq,
+    BH: BuildHasher,
+{
+    /// Creates a frozen map which will use the given hash builder to hash
+    /// keys.
     ///
     /// # Examples
     ///
     /// ```
     /// use frozen_collections_core::facades::FrozenMap;
+    /// use std::hash::RandomState;
     ///
-    /// let a = FrozenMap::from([(0, 1)]);
-    /// assert!(!a.is_empty());
+    /// let map = FrozenMap::from_vec_with_hasher(vec![(1, 2), (3, 4)], RandomState::new());
     /// ```
-    pub fn is_empty(&self) -> bool {
-        self.len() == 0
+    #[must_use]
+    pub fn from_vec_with_hasher(payload: Vec<(K, V)>, bh: BH) -> Self {
+        Self::new(payload, bh)
     }
 
-    /// An iterator visiting all key-value pairs in arbitrary order.
-    /// The iterator element type is `(&'a K, &'a V)`.
+    /// Creates a frozen map which will use the given hash builder to hash keys, returning an
+    /// error instead of accepting an invalid payload.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys.
     ///
     /// # Examples
     ///
     /// ```
+    /// use frozen_collections_core::errors::FrozenError;
     /// use frozen_collections_core::facades::FrozenMap;
+    /// use std::hash::RandomState;
     ///
-    /// let map = FrozenMap::from([
-    ///     ("a", 1),
-    ///     ("b", 2),
-    ///     ("c", 3),
-    /// ]);
+    /// let map = FrozenMap::try_from_vec_with_hasher(vec![(1, 2), (3, 4)], RandomState::new());
+    /// assert_eq!(map.unwrap().get(&3), Some(&4));
     ///
-    /// for (key, val) in map.iter() {
-    ///     println!("key: {key} val: {val}");
-    /// }
+    /// let map = FrozenMap::try_from_vec_with_hasher(vec![(1, 2), (1, 4)], RandomState::new());
+    /// assert_eq!(map.err(), Some(FrozenError::DuplicateKey { index: 1 }));
     /// ```
-    pub const fn iter(&self) -> Iter<K, V> {
-        match &self.map_impl {
-            MapTypes::Scanning(m) => m.iter(),
-            MapTypes::CommonSmall(m) => m.iter(),
-            MapTypes::CommonLarge(m) => m.iter(),
-            MapTypes::U32Small(m) => unsafe { transmute(m.iter()) },
-            MapTypes::U32Large(m) => unsafe { transmute(m.iter()) },
-            MapTypes::U32Range(m) => unsafe { transmute(m.iter()) },
-            MapTypes::LeftStringSliceSmall(m) => unsafe { transmute(m.iter()) },
-            MapTypes::LeftStringSliceLarge(m) => unsafe { transmute(m.iter()) },
-            MapTypes::RightStringSliceSmall(m) => unsafe { transmute(m.iter()) },
-            MapTypes::RightStringSliceLarge(m) => unsafe { transmute(m.iter()) },
-            MapTypes::StringLengthSmall(m) => unsafe { transmute(m.iter()) },
-        }
+    pub fn try_from_vec_with_hasher(
+        payload: Vec<(K, V)>,
+        bh: BH,
+    ) -> std::result::Result<Self, FrozenError> {
+        Self::from_vec_with_hasher_and_policy(payload, bh, DuplicatePolicy::Error)
     }
 
-    /// An iterator visiting all keys in arbitrary order.
-    /// The iterator element type is `&'a K`.
+    /// Creates a frozen map which will use the given hash builder to hash keys, handling
+    /// duplicate keys according to `policy`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys and the policy is [`DuplicatePolicy::Error`].
     ///
     /// # Examples
     ///
     /// ```
-    /// use frozen_collections_core::facades::FrozenMap;
+    /// use frozen_collections_core::facades::{DuplicatePolicy, FrozenMap};
+    /// use std::hash::RandomState;
     ///
-    /// let map = FrozenMap::from([
-    ///     ("a", 1),
-    ///     ("b", 2),
-    ///     ("c", 3),
-    /// ]);
+    /// let payload = vec![(1, "a"), (2, "b"), (1, "c")];
+    /// let map = FrozenMap::from_vec_with_hasher_and_policy(
+    ///     payload,
+    ///     RandomState::new(),
+    ///     DuplicatePolicy::KeepLast,
+    /// );
     ///
-    /// for key in map.keys() {
-    ///     println!("{key}");
-    /// }
+    /// assert_eq!(map.unwrap().get(&1), Some(&"c"));
     /// ```
-    pub const fn keys(&self) -> Keys<K, V> {
-        match &self.map_impl {
-            MapTypes::Scanning(m) => m.keys(),
-            MapTypes::CommonSmall(m) => m.keys(),
-            MapTypes::CommonLarge(m) => m.keys(),
-            MapTypes::U32Small(m) => unsafe { transmute(m.keys()) },
-            MapTypes::U32Large(m) => unsafe { transmute(m.keys()) },
-            MapTypes::U32Range(m) => unsafe { transmute(m.keys()) },
-            MapTypes::LeftStringSliceSmall(m) => unsafe { transmute(m.keys()) },
-            MapTypes::LeftStringSliceLarge(m) => unsafe { transmute(m.keys()) },
-            MapTypes::RightStringSliceSmall(m) => unsafe { transmute(m.keys()) },
-            MapTypes::RightStringSliceLarge(m) => unsafe { transmute(m.keys()) },
-            MapTypes::StringLengthSmall(m) => unsafe { transmute(m.keys()) },
-        }
+    pub fn from_vec_with_hasher_and_policy(
+        payload: Vec<(K, V)>,
+        bh: BH,
+        policy: DuplicatePolicy,
+    ) -> std::result::Result<Self, FrozenError> {
+        let payload = policy.apply(payload, |k| bh.hash_one(k))?;
+        Ok(Self::new(payload, bh))
     }
 
-    /// An iterator visiting all values in arbitrary order.
-    /// The iterator element type is `&'a V`.
+    /// Creates a frozen map which will use the given hash builder to hash
+    /// keys.
     ///
     /// # Examples
     ///
     /// ```
     /// use frozen_collections_core::facades::FrozenMap;
+    /// use std::hash::RandomState;
     ///
-    /// let map = FrozenMap::from([
-    ///     ("a", 1),
-    ///     ("b", 2),
-    ///     ("c", 3),
-    /// ]);
-    ///
-    /// for val in map.values() {
-    ///     println!("{val}");
-    /// }
+    /// let v = vec![(1, 2), (3, 4)];
+    /// let map = FrozenMap::from_iter_with_hasher(v, RandomState::new());
     /// ```
-    pub const fn values(&self) -> Values<K, V> {
+    #[must_use]
+    pub fn from_iter_with_hasher<T: IntoIterator<Item = (K, V)>>(iter: T, bh: BH) -> Self {
+        Self::new(Vec::from_iter(iter), bh)
+    }
+
+    /// Creates a map which answers lookups right away, while the keys are analyzed on a
+    /// background thread, using the given hash builder to hash keys.
+    ///
+    /// See [`DeferredFrozenMap`] for details.
+    #[must_use]
+    pub fn freeze_deferred_with_hasher(payload: Vec<(K, V)>, bh: BH) -> DeferredFrozenMap<K, V, BH>
+    where
+        K: Clone + Send + Sync + 'static,
+        BH: Clone + Send + Sync + 'static,
+    {
+        DeferredFrozenMap::new(payload, bh)
+    }
+
+    /// Creates a frozen map which will use the given hash builder to hash
+    /// keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    /// use std::hash::RandomState;
+    ///
+    /// let map = FrozenMap::with_hasher([(0, 1), (2, 3)], RandomState::new());
+    /// ```
+    #[must_use]
+    pub fn with_hasher<const N: usize>(payload: [(K, V); N], bh: BH) -> Self {
+        Self::from_iter_with_hasher(payload, bh)
+    }
+
+    /// Creates a frozen map with the given implementation, bypassing the analysis of its keys.
+    ///
+    /// This is for when more is known about the keys than the analysis can find out, such
+    /// as a suffix which tells apart keys to come as well as those in the payload. Slice
+    /// ranges are measured as reported by [`Self::strategy`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys, or if the keys don't suit the strategy:
+    /// integer strategies need keys which write a single integer to a hasher, slice and
+    /// length strategies need keys which write a run of bytes, and so on.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::errors::FrozenError;
+    /// use frozen_collections_core::facades::{FrozenMap, MapStrategy};
+    /// use std::hash::RandomState;
+    ///
+    /// let payload = vec![("user-0001", 1), ("user-0002", 2), ("user-0003", 3)];
+    /// let strategy = MapStrategy::RightSlice { range: 0..4 };
+    ///
+    /// let map = FrozenMap::try_from_vec_with_strategy(payload, strategy.clone(), RandomState::new());
+    /// let map = map.unwrap();
+    /// assert_eq!(map.strategy(), strategy);
+    /// assert_eq!(map.get("user-0002"), Some(&2));
+    ///
+    /// let payload = vec![("a", 1), ("b", 2)];
+    /// let map = FrozenMap::try_from_vec_with_strategy(payload, MapStrategy::Integer, RandomState::new());
+    /// assert_eq!(map.err(), Some(FrozenError::IncompatibleStrategy));
+    /// ```
+    pub fn try_from_vec_with_strategy(
+        payload: Vec<(K, V)>,
+        strategy: MapStrategy,
+        bh: BH,
+    ) -> std::result::Result<Self, FrozenError> {
+        let payload = DuplicatePolicy::Error.apply(payload, |k| bh.hash_one(k))?;
+        let sizing = HashCodeAnalysisOptions::default();
+
+        let integer_keys = |payload: &[(K, V)]| {
+            if payload.iter().all(|entry| probe_int(&entry.0).is_some()) {
+                Ok(())
+            } else {
+                Err(FrozenError::IncompatibleStrategy)
+            }
+        };
+
+        let slice_keys = |payload: &[(K, V)]| {
+            let mut bytes = Vec::new();
+            if payload
+                .iter()
+                .all(|entry| capture_bytes(&entry.0, &mut bytes))
+            {
+                Ok(())
+            } else {
+                Err(FrozenError::IncompatibleStrategy)
+            }
+        };
+
+        let map_impl = match strategy {
+            MapStrategy::Scanning => MapTypes::Scanning(ScanningMap::from_vec(payload)),
+            MapStrategy::Common => MapTypes::Common(CommonMap::from_vec_with_hasher(payload, bh)),
+            MapStrategy::PerfectHash => {
+                MapTypes::PerfectHash(PerfectHashMap::try_from_vec_with_hasher(payload, bh)?)
+            }
+            MapStrategy::Integer => {
+                integer_keys(&payload)?;
+                MapTypes::Integer(IntegerMap::from_vec(payload))
+            }
+            MapStrategy::IntegerRange => {
+                integer_keys(&payload)?;
+                MapTypes::IntegerRange(IntegerRangeMap::try_from_vec(payload)?)
+            }
+            MapStrategy::DenseInteger => {
+                integer_keys(&payload)?;
+                MapTypes::Dense(DenseIntegerMap::try_from_vec(payload)?)
+            }
+            MapStrategy::IntegerScanning => {
+                integer_keys(&payload)?;
+                MapTypes::IntegerScanning(IntegerScanningMap::from_vec(payload))
+            }
+            MapStrategy::LeftSlice { range } => {
+                slice_keys(&payload)?;
+                let analysis = SliceKeyAnalysisResult::LeftHandSubslice {
+                    subslice_index: range.start,
+                    subslice_len: range.len(),
+                };
+                Self::new_analyzed_map(payload, &analysis, bh, &sizing)
+            }
+            MapStrategy::LengthSlice { range } => {
+                slice_keys(&payload)?;
+                let analysis = SliceKeyAnalysisResult::LengthAndSubslice {
+                    subslice_index: range.start,
+                    subslice_len: range.len(),
+                };
+                Self::new_analyzed_map(payload, &analysis, bh, &sizing)
+            }
+            MapStrategy::RightSlice { range } => {
+                slice_keys(&payload)?;
+                let analysis = SliceKeyAnalysisResult::RightHandSubslice {
+                    subslice_index: range.start,
+                    subslice_len: range.len(),
+                };
+                Self::new_analyzed_map(payload, &analysis, bh, &sizing)
+            }
+            MapStrategy::SplitSlice { left, right } => {
+                slice_keys(&payload)?;
+                let analysis = SliceKeyAnalysisResult::SplitSubslice {
+                    left_index: left.start,
+                    left_len: left.len(),
+                    right_index: right.start,
+                    right_len: right.len(),
+                };
+                Self::new_analyzed_map(payload, &analysis, bh, &sizing)
+            }
+            MapStrategy::Length => {
+                slice_keys(&payload)?;
+                Self::new_analyzed_map(payload, &SliceKeyAnalysisResult::Length, bh, &sizing)
+            }
+        };
+
+        Ok(Self { map_impl })
+    }
+
+    /// Creates a frozen map which will use the given hash builder to hash keys, analyzing the
+    /// keys within the bounds set by `options`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::{AnalysisOptions, FrozenMap, MapStrategy};
+    /// use std::hash::RandomState;
+    ///
+    /// let options = AnalysisOptions::new().integer_maps(false);
+    /// let map = FrozenMap::from_vec_with_options(vec![(1, 2), (3, 4)], RandomState::new(), options);
+    ///
+    /// assert_eq!(map.get(&3), Some(&4));
+    /// ```
+    #[must_use]
+    pub fn from_vec_with_options(payload: Vec<(K, V)>, bh: BH, options: AnalysisOptions) -> Self {
+        Self::new_with_options(payload, bh, &options)
+    }
+
+    /// Creates a frozen map holding this map's entries, with some entries added and others
+    /// removed, using the given hash builder to hash keys.
+    ///
+    /// Additions replace the entries of the keys already in the map, and removals of keys which
+    /// aren't in the map are ignored. As long as the changes touch a modest share of the keys,
+    /// the new map keeps the implementation and load factor picked for this one, and only
+    /// checks that they still suit the keys, rather than analyzing the keys from scratch. Larger
+    /// changes, or keys which no longer suit the implementation, lead to a full analysis.
+    /// Either way, the new map holds a prefilter if this one does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    /// use std::hash::RandomState;
+    ///
+    /// let map = FrozenMap::from_vec_with_hasher(
+    ///     (0..100).map(|i| (format!("sku-{i:04}"), i)).collect(),
+    ///     RandomState::new(),
+    /// );
+    /// let strategy = map.strategy();
+    ///
+    /// let map = map.refreeze_with_hasher(
+    ///     vec![("sku-0100".to_string(), 100), ("sku-0001".to_string(), 1000)],
+    ///     ["sku-0002"],
+    ///     RandomState::new(),
+    /// );
+    ///
+    /// assert_eq!(map.strategy(), strategy);
+    /// assert_eq!(map.get("sku-0100"), Some(&100));
+    /// assert_eq!(map.get("sku-0001"), Some(&1000));
+    /// assert_eq!(map.get("sku-0002"), None);
+    /// assert_eq!(map.len(), 100);
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn refreeze_with_hasher<'q, Q, I>(self, additions: Vec<(K, V)>, removals: I, bh: BH) -> Self
+    where
+        Q: ?Sized + Hash + Equivalent<K> + 'q,
+        I: IntoIterator<Item = &'q Q>,
+    {
+        let len = self.len();
+        let mut removed = vec![false; len];
+        let mut changes = additions.len();
+        for key in removals {
+            if let Some(index) = self.entry_index(key) {
+                changes += usize::from(!removed[index]);
+                removed[index] = true;
+            }
+        }
+
+        let strategy = self.strategy();
+        let bucket_count = self.bucket_count();
+        let options = AnalysisOptions::new().prefilter(self.has_prefilter());
+
+        let mut payload: Vec<_> = self
+            .into_entries()
+            .into_vec()
+            .into_iter()
+            .zip(removed)
+            .filter_map(|(entry, removed)| (!removed).then_some(entry))
+            .collect();
+        payload.extend(additions);
+
+        let Ok(payload) = DuplicatePolicy::KeepLast.apply(payload, |k| bh.hash_one(k)) else {
+            unreachable!("Keeping the last entry of a key never fails");
+        };
+
+        if changes.saturating_mul(100) > len.saturating_mul(MAX_REFREEZE_CHANGE_PERCENT) {
+            return Self::new_with_options(payload, bh, &options);
+        }
+
+        // carry the load factor over, rather than searching for a table size again
+        let sizing = HashCodeAnalysisOptions {
+            target_load_factor: (bucket_count > 0).then(|| len as f64 / bucket_count as f64),
+            deadline: None,
+            prefilter: options.wants_prefilter(),
+        };
+
+        let map_impl = match strategy {
+            MapStrategy::Common => {
+                MapTypes::Common(CommonMap::from_vec_with_sizing(payload, bh, &sizing))
+            }
+            MapStrategy::Integer if payload.iter().all(|entry| probe_int(&entry.0).is_some()) => {
+                MapTypes::Integer(IntegerMap::from_vec_with_sizing(payload, &sizing))
+            }
+            strategy => {
+                let Some(analysis) = slice_analysis(strategy) else {
+                    return Self::new_with_options(payload, bh, &options);
+                };
+
+                let mut bytes = Vec::new();
+                let mut bounds = Vec::with_capacity(payload.len());
+                for entry in &payload {
+                    let start = bytes.len();
+                    if !capture_bytes(&entry.0, &mut bytes) {
+                        return Self::new_with_options(payload, bh, &options);
+                    }
+
+                    bounds.push(start..bytes.len());
+                }
+
+                if !verify_slice_keys(bounds.into_iter().map(|r| &bytes[r]), &analysis, &bh) {
+                    return Self::new_with_options(payload, bh, &options);
+                }
+
+                Self::new_analyzed_map(payload, &analysis, bh, &sizing)
+            }
+        };
+
+        Self { map_impl }
+    }
+
+    fn new(payload: Vec<(K, V)>, bh: BH) -> Self {
+        Self::new_with_options(payload, bh, &AnalysisOptions::new())
+    }
+
+    /// Creates a frozen map, picking its implementation within the bounds set by `options`.
+    pub(crate) fn new_with_options(
+        payload: Vec<(K, V)>,
+        bh: BH,
+        options: &AnalysisOptions,
+    ) -> Self {
+        let deadline = options.deadline(Instant::now());
+        let int_codes: Option<Vec<_>> = if options.allows_integer_maps() {
+            payload.iter().map(|x| probe_int(&x.0)).collect()
+        } else {
+            None
+        };
+
+        Self {
+            map_impl: match int_codes {
+                Some(codes) if !codes.is_empty() => {
+                    Self::new_integer_map(payload, codes, options, deadline)
+                }
+                _ if payload.len() < 4 => MapTypes::Scanning(ScanningMap::from_vec(payload)),
+                _ if options.has_prehashed_keys() => {
+                    Self::new_prehashed_map(payload, bh, options, deadline)
+                }
+                _ => Self::new_slice_map(payload, bh, options, deadline),
+            },
+        }
+    }
+
+    /// Creates a map for keys which each write a single integer to a hasher.
+    fn new_integer_map(
+        payload: Vec<(K, V)>,
+        mut codes: Vec<u64>,
+        options: &AnalysisOptions,
+        deadline: Option<Instant>,
+    ) -> MapTypes<K, V, BH> {
+        let min_density_percent = options.key_density();
+        if find_code_range(&mut codes).is_some() {
+            MapTypes::IntegerRange(IntegerRangeMap::from_vec(payload))
+        } else if payload.len() <= MAX_INTEGER_SCANNING_KEYS {
+            MapTypes::IntegerScanning(IntegerScanningMap::from_vec(payload))
+        } else if options.allows_dense_integer_maps()
+            && find_dense_code_range(&mut codes, min_density_percent).is_some()
+        {
+            MapTypes::Dense(DenseIntegerMap::from_vec_with_density(
+                payload,
+                min_density_percent,
+            ))
+        } else {
+            drop(codes);
+            MapTypes::Integer(IntegerMap::from_vec_with_sizing(
+                payload,
+                &options.sizing(deadline),
+            ))
+        }
+    }
+
+    /// Creates a map for keys which each write a run of bytes to a hasher, falling back to a
+    /// common map for any other keys.
+    fn new_slice_map(
+        payload: Vec<(K, V)>,
+        bh: BH,
+        options: &AnalysisOptions,
+        deadline: Option<Instant>,
+    ) -> MapTypes<K, V, BH> {
+        let mut bytes = Vec::new();
+        let mut bounds = Vec::with_capacity(payload.len());
+        for entry in &payload {
+            let start = bytes.len();
+            if !capture_bytes(&entry.0, &mut bytes) {
+                return Self::new_common_map(payload, bh, options, deadline);
+            }
+
+            bounds.push(start..bytes.len());
+        }
+
+        let key_analysis = analyze_slice_keys_with(
+            bounds.into_iter().map(|r| &bytes[r]),
+            &bh,
+            &options.slice_options(deadline),
+        );
+
+        // release the copies of the keys before the map is laid out
+        drop(bytes);
+
+        if key_analysis == SliceKeyAnalysisResult::Normal && payload.len() > u8::MAX.as_usize() {
+            return Self::new_large_common_map(payload, bh, options, deadline);
+        }
+
+        Self::new_analyzed_map(payload, &key_analysis, bh, &options.sizing(deadline))
+    }
+
+    /// Creates a map which hashes keys, or the parts of them picked by `analysis`.
+    fn new_analyzed_map(
+        payload: Vec<(K, V)>,
+        analysis: &SliceKeyAnalysisResult,
+        bh: BH,
+        sizing: &HashCodeAnalysisOptions,
+    ) -> MapTypes<K, V, BH> {
+        match *analysis {
+            SliceKeyAnalysisResult::Normal => {
+                MapTypes::Common(CommonMap::from_vec_with_sizing(payload, bh, sizing))
+            }
+
+            SliceKeyAnalysisResult::LeftHandSubslice {
+                subslice_index,
+                subslice_len,
+            } => {
+                let range = subslice_index..subslice_index + subslice_len;
+                MapTypes::LeftSlice(LeftSliceMap::from_hashed_vec(payload, range, bh, sizing))
+            }
+
+            SliceKeyAnalysisResult::RightHandSubslice {
+                subslice_index,
+                subslice_len,
+            } => {
+                let range = subslice_index..subslice_index + subslice_len;
+                MapTypes::RightSlice(RightSliceMap::from_hashed_vec(payload, range, bh, sizing))
+            }
+
+            SliceKeyAnalysisResult::LengthAndSubslice {
+                subslice_index,
+                subslice_len,
+            } => {
+                let range = subslice_index..subslice_index + subslice_len;
+                MapTypes::LengthSlice(LengthSliceMap::from_hashed_vec(payload, range, bh, sizing))
+            }
+
+            SliceKeyAnalysisResult::SplitSubslice {
+                left_index,
+                left_len,
+                right_index,
+                right_len,
+            } => {
+                let left = left_index..left_index + left_len;
+                let right = right_index..right_index + right_len;
+                MapTypes::SplitSlice(SplitSliceMap::from_hashed_vec(
+                    payload, left, right, bh, sizing,
+                ))
+            }
+
+            SliceKeyAnalysisResult::Length => {
+                MapTypes::Length(LengthMap::from_hashed_vec(payload, sizing))
+            }
+        }
+    }
+
+    /// Creates a map for keys which are already hashes, which the hash builder passes through.
+    ///
+    /// Hashing subslices of such keys would only be slower than using their bits directly.
+    fn new_prehashed_map(
+        payload: Vec<(K, V)>,
+        bh: BH,
+        options: &AnalysisOptions,
+        deadline: Option<Instant>,
+    ) -> MapTypes<K, V, BH> {
+        if payload.len() <= u8::MAX.as_usize() {
+            Self::new_common_map(payload, bh, options, deadline)
+        } else {
+            Self::new_large_common_map(payload, bh, options, deadline)
+        }
+    }
+
+    /// Creates a map for a large payload of keys with no exploitable pattern, switching to a
+    /// perfect hash map once there are enough keys for a single key comparison per lookup to
+    /// pay for the slower construction.
+    fn new_large_common_map(
+        payload: Vec<(K, V)>,
+        bh: BH,
+        options: &AnalysisOptions,
+        deadline: Option<Instant>,
+    ) -> MapTypes<K, V, BH> {
+        let in_time = deadline.is_none_or(|deadline| Instant::now() < deadline);
+        if payload.len() >= MIN_PERFECT_HASH_KEYS && options.allows_perfect_hash_maps() && in_time {
+            let codes: Vec<_> = payload.iter().map(|entry| bh.hash_one(&entry.0)).collect();
+            if let Some(layout) = analyze_perfect_hash(&codes) {
+                let mut map = PerfectHashMap::from_layout(payload, layout, bh);
+                if options.wants_prefilter() {
+                    map = map.with_prefilter(&codes);
+                }
+
+                return MapTypes::PerfectHash(map);
+            }
+        }
+
+        Self::new_common_map(payload, bh, options, deadline)
+    }
+
+    fn new_common_map(
+        payload: Vec<(K, V)>,
+        bh: BH,
+        options: &AnalysisOptions,
+        deadline: Option<Instant>,
+    ) -> MapTypes<K, V, BH> {
+        let sizing = options.sizing(deadline);
+        MapTypes::Common(CommonMap::from_vec_with_sizing(payload, bh, &sizing))
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, so a map with `String` keys
+    /// can be probed with a `&str`, or any type implementing [`Equivalent`] for it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from([(1, "a".to_string())]);
+    /// assert_eq!(map.get(&1), Some(&"a".to_string()));
+    /// assert_eq!(map.get(&2), None);
+    ///
+    /// let map = FrozenMap::from([("a".to_string(), 1)]);
+    /// assert_eq!(map.get("a"), Some(&1));
+    /// ```
+    #[inline]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
         match &self.map_impl {
-            MapTypes::Scanning(m) => m.values(),
-            MapTypes::CommonSmall(m) => m.values(),
-            MapTypes::CommonLarge(m) => m.values(),
-            MapTypes::U32Small(m) => unsafe { transmute(m.values()) },
-            MapTypes::U32Large(m) => unsafe { transmute(m.values()) },
-            MapTypes::U32Range(m) => unsafe { transmute(m.values()) },
-            MapTypes::LeftStringSliceSmall(m) => unsafe { transmute(m.values()) },
-            MapTypes::LeftStringSliceLarge(m) => unsafe { transmute(m.values()) },
-            MapTypes::RightStringSliceSmall(m) => unsafe { transmute(m.values()) },
-            MapTypes::RightStringSliceLarge(m) => unsafe { transmute(m.values()) },
-            MapTypes::StringLengthSmall(m) => unsafe { transmute(m.values()) },
+            MapTypes::Scanning(m) => m.get(key),
+            MapTypes::Common(m) => m.get(key),
+            MapTypes::PerfectHash(m) => m.get(key),
+            MapTypes::Integer(m) => m.get(key),
+            MapTypes::IntegerRange(m) => m.get(key),
+            MapTypes::Dense(m) => m.get(key),
+            MapTypes::IntegerScanning(m) => m.get(key),
+            MapTypes::LeftSlice(m) => Some(&m.find(key)?.1),
+            MapTypes::LengthSlice(m) => Some(&m.find(key)?.1),
+            MapTypes::RightSlice(m) => Some(&m.find(key)?.1),
+            MapTypes::SplitSlice(m) => Some(&m.find(key)?.1),
+            MapTypes::Length(m) => Some(&m.find(key)?.1),
+        }
+    }
+
+    /// Returns a reference to the value corresponding to a key known to be in the map.
+    ///
+    /// This skips the check for a missing key, and implementations which can reach a key's
+    /// entry without comparing keys, such as integer ranges and perfect hashes, skip the
+    /// comparison as well.
+    ///
+    /// # Safety
+    ///
+    /// Calling this method with a key which isn't in the map is undefined behavior.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from([(1, "a"), (2, "b")]);
+    ///
+    /// if map.contains_key(&2) {
+    ///     assert_eq!(unsafe { map.get_unchecked(&2) }, &"b");
+    /// }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub unsafe fn get_unchecked<Q>(&self, key: &Q) -> &V
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        unsafe {
+            match &self.map_impl {
+                MapTypes::PerfectHash(m) => m.get_unchecked(key),
+                MapTypes::IntegerRange(m) => m.get_unchecked(key),
+                MapTypes::Dense(m) => m.get_unchecked(key),
+                _ => self.get(key).unwrap_unchecked(),
+            }
+        }
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from([(1, "a".to_string())]);
+    /// assert_eq!(map.get_key_value(&1), Some((&1, &"a".to_string())));
+    /// assert_eq!(map.get_key_value(&2), None);
+    /// ```
+    #[inline]
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        match &self.map_impl {
+            MapTypes::Scanning(m) => m.get_key_value(key),
+            MapTypes::Common(m) => m.get_key_value(key),
+            MapTypes::PerfectHash(m) => m.get_key_value(key),
+            MapTypes::Integer(m) => m.get_key_value(key),
+            MapTypes::IntegerRange(m) => m.get_key_value(key),
+            MapTypes::Dense(m) => m.get_key_value(key),
+            MapTypes::IntegerScanning(m) => m.get_key_value(key),
+            MapTypes::LeftSlice(m) => m.find(key).map(|(k, v)| (k, v)),
+            MapTypes::LengthSlice(m) => m.find(key).map(|(k, v)| (k, v)),
+            MapTypes::RightSlice(m) => m.find(key).map(|(k, v)| (k, v)),
+            MapTypes::SplitSlice(m) => m.find(key).map(|(k, v)| (k, v)),
+            MapTypes::Length(m) => m.find(key).map(|(k, v)| (k, v)),
+        }
+    }
+
+    /// Returns a reference to the map's own copy of the supplied key.
+    ///
+    /// This is useful to deduplicate keys against the ones stored in the map
+    /// without needing to look at the associated value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from([("a".to_string(), 1)]);
+    /// assert_eq!(map.get_key(&"a".to_string()), Some(&"a".to_string()));
+    /// assert_eq!(map.get_key(&"b".to_string()), None);
+    /// ```
+    #[inline]
+    pub fn get_key<Q>(&self, key: &Q) -> Option<&K>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        Some(self.get_key_value(key)?.0)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let mut map = FrozenMap::from([(1, "a".to_string())]);
+    /// assert_eq!(map.get_mut(&1), Some(&mut "a".to_string()));
+    /// assert_eq!(map.get_mut(&2), None);
+    /// ```
+    #[inline]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        match &mut self.map_impl {
+            MapTypes::Scanning(m) => m.get_mut(key),
+            MapTypes::Common(m) => m.get_mut(key),
+            MapTypes::PerfectHash(m) => m.get_mut(key),
+            MapTypes::Integer(m) => m.get_mut(key),
+            MapTypes::IntegerRange(m) => m.get_mut(key),
+            MapTypes::Dense(m) => m.get_mut(key),
+            MapTypes::IntegerScanning(m) => m.get_mut(key),
+            MapTypes::LeftSlice(m) => Some(&mut m.find_mut(key)?.1),
+            MapTypes::LengthSlice(m) => Some(&mut m.find_mut(key)?.1),
+            MapTypes::RightSlice(m) => Some(&mut m.find_mut(key)?.1),
+            MapTypes::SplitSlice(m) => Some(&mut m.find_mut(key)?.1),
+            MapTypes::Length(m) => Some(&mut m.find_mut(key)?.1),
+        }
+    }
+
+    /// Returns references to the values corresponding to `N` keys at once.
+    ///
+    /// The hash codes of all the keys are computed before any of the map's entries are
+    /// examined, which lets the memory accesses of the individual lookups overlap. This makes
+    /// batches of lookups into large maps faster than calling [`Self::get`] repeatedly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from([("a".to_string(), 1), ("b".to_string(), 2)]);
+    /// assert_eq!(map.get_many(["a", "c", "b"]), [Some(&1), None, Some(&2)]);
+    /// ```
+    #[inline]
+    pub fn get_many<Q, const N: usize>(&self, keys: [&Q; N]) -> [Option<&V>; N]
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        match &self.map_impl {
+            MapTypes::Scanning(m) => m.get_many(keys),
+            MapTypes::Common(m) => m.get_many(keys),
+            MapTypes::PerfectHash(m) => m.get_many(keys),
+            MapTypes::Integer(m) => m.get_many(keys),
+            MapTypes::IntegerRange(m) => m.get_many(keys),
+            MapTypes::Dense(m) => m.get_many(keys),
+            MapTypes::IntegerScanning(m) => m.get_many(keys),
+            MapTypes::LeftSlice(m) => entry_values(m.find_many(keys)),
+            MapTypes::LengthSlice(m) => entry_values(m.find_many(keys)),
+            MapTypes::RightSlice(m) => entry_values(m.find_many(keys)),
+            MapTypes::SplitSlice(m) => entry_values(m.find_many(keys)),
+            MapTypes::Length(m) => entry_values(m.find_many(keys)),
+        }
+    }
+
+    /// Looks up a stream of keys, producing the value of each key in turn.
+    ///
+    /// Every key is hashed and its entries prefetched as soon as it's read from `keys`, while
+    /// its value is only produced once a few more keys were read. This hides most of the
+    /// memory latency of lookups into maps too large for the processor's caches, where
+    /// calling [`Self::get`] for each key would wait on memory for every lookup in turn.
+    ///
+    /// Values are produced in the order of the keys, with `None` for keys the map doesn't
+    /// hold.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from_iter((0..10_000).map(|i| (format!("user-{i}"), i)));
+    /// let keys = ["user-7", "guest-3", "user-9000"];
+    ///
+    /// let values: Vec<_> = map.lookup_stream(keys).collect();
+    /// assert_eq!(values, [Some(&7), None, Some(&9000)]);
+    /// ```
+    pub fn lookup_stream<'a, 'q, Q, I>(
+        &'a self,
+        keys: I,
+    ) -> LookupStream<'a, 'q, K, V, Q, I::IntoIter, BH>
+    where
+        Q: ?Sized + Hash + Equivalent<K> + 'q,
+        I: IntoIterator<Item = &'q Q>,
+    {
+        LookupStream::new(self, keys.into_iter())
+    }
+
+    /// Starts looking a key up, prefetching the memory which holds its entries.
+    #[inline]
+    pub(crate) fn locate<Q>(&self, key: &Q) -> Probe
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let (hash_code, range) = match &self.map_impl {
+            MapTypes::Scanning(_)
+            | MapTypes::IntegerRange(_)
+            | MapTypes::Dense(_)
+            | MapTypes::IntegerScanning(_) => return Probe::Direct,
+            MapTypes::PerfectHash(m) => return Probe::Slot(m.locate(key)),
+            MapTypes::Common(m) => {
+                let hash_code = m.hasher().hash_one(key);
+                (hash_code, m.table.locate(hash_code))
+            }
+            MapTypes::Integer(m) => {
+                let hash_code = int_code(key);
+                (hash_code, m.table.locate(hash_code))
+            }
+            MapTypes::LeftSlice(m) => m.locate(key),
+            MapTypes::LengthSlice(m) => m.locate(key),
+            MapTypes::RightSlice(m) => m.locate(key),
+            MapTypes::SplitSlice(m) => m.locate(key),
+            MapTypes::Length(m) => m.locate(key),
+        };
+
+        Probe::Table { hash_code, range }
+    }
+
+    /// Finishes looking a key up, given what [`Self::locate`] returned for it.
+    #[inline]
+    pub(crate) fn find_located<Q>(&self, probe: Probe, key: &Q) -> Option<&V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let entry = match (probe, &self.map_impl) {
+            (Probe::Table { hash_code, range }, MapTypes::Common(m)) => {
+                m.table.find_located(range, hash_code, key)
+            }
+            (Probe::Table { hash_code, range }, MapTypes::Integer(m)) => {
+                m.table.find_located(range, hash_code, key)
+            }
+            (Probe::Table { hash_code, range }, MapTypes::LeftSlice(m)) => {
+                m.table.find_located(range, hash_code, key)
+            }
+            (Probe::Table { hash_code, range }, MapTypes::LengthSlice(m)) => {
+                m.table.find_located(range, hash_code, key)
+            }
+            (Probe::Table { hash_code, range }, MapTypes::RightSlice(m)) => {
+                m.table.find_located(range, hash_code, key)
+            }
+            (Probe::Table { hash_code, range }, MapTypes::SplitSlice(m)) => {
+                m.table.find_located(range, hash_code, key)
+            }
+            (Probe::Table { hash_code, range }, MapTypes::Length(m)) => {
+                m.table.find_located(range, hash_code, key)
+            }
+            (Probe::Slot(slot), MapTypes::PerfectHash(m)) => m.find_located(slot, key),
+            _ => return self.get(key),
+        };
+
+        Some(&entry?.1)
+    }
+
+    /// Attempts to get mutable references to `N` values in the map at once.
+    ///
+    /// Returns an array of length `N` with the results of each query. For soundness, at most one
+    /// mutable reference will be returned to any value. `None` will be returned if any of the
+    /// keys are duplicates or missing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let mut libraries = FrozenMap::from([
+    ///     ("Bodleian Library".to_string(), 1602),
+    ///     ("Athenæum".to_string(), 1807),
+    ///     ("Herzogin-Anna-Amalia-Bibliothek".to_string(), 1691),
+    ///     ("Library of Congress".to_string(), 1800)
+    /// ]);
+    ///
+    /// let got = libraries.get_many_mut([
+    ///     &"Athenæum".to_string(),
+    ///     &"Library of Congress".to_string(),
+    /// ]);
+    /// assert_eq!(
+    ///     got,
+    ///     Some([
+    ///         &mut 1807,
+    ///         &mut 1800,
+    ///     ]),
+    /// );
+    ///
+    /// // Missing keys result in None
+    /// let got = libraries.get_many_mut([
+    ///     &"Athenæum".to_string(),
+    ///     &"New York Public Library".to_string(),
+    /// ]);
+    /// assert_eq!(got, None);
+    ///
+    /// // Duplicate keys result in None
+    /// let got = libraries.get_many_mut([
+    ///     &"Athenæum".to_string(),
+    ///     &"Athenæum".to_string(),
+    /// ]);
+    /// assert_eq!(got, None);
+    /// ```
+    pub fn get_many_mut<const N: usize>(&mut self, keys: [&K; N]) -> Option<[&mut V; N]> {
+        let mut indices = [0; N];
+        for (index, key) in indices.iter_mut().zip(keys) {
+            *index = self.entry_index(key)?;
+        }
+
+        // duplicate keys land on the same entry, which makes this fail
+        let entries = self.entries_mut().get_disjoint_mut(indices).ok()?;
+        Some(entries.map(|entry| &mut entry.1))
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from([(1, "a".to_string())]);
+    ///
+    /// assert_eq!(map.contains_key(&1), true);
+    /// assert_eq!(map.contains_key(&2), false);
+    /// ```
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Returns `true` if the map contains a value for every one of the keys.
+    ///
+    /// This stops at the first missing key, and is faster than calling
+    /// [`contains_key`](Self::contains_key) for every key, since the map's implementation is
+    /// selected once for the whole batch rather than once per key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from([("a".to_string(), 1), ("b".to_string(), 2)]);
+    ///
+    /// assert!(map.contains_all_keys(["a", "b"]));
+    /// assert!(!map.contains_all_keys(["a", "c"]));
+    /// ```
+    pub fn contains_all_keys<'q, Q, I>(&self, keys: I) -> bool
+    where
+        Q: ?Sized + Hash + Equivalent<K> + 'q,
+        I: IntoIterator<Item = &'q Q>,
+    {
+        self.all_keys_found(keys, true)
+    }
+
+    /// Returns `true` if the map contains a value for at least one of the keys.
+    ///
+    /// This stops at the first key found. As with [`contains_all_keys`](Self::contains_all_keys),
+    /// the map's implementation is selected once for the whole batch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from([(1, "a"), (2, "b")]);
+    ///
+    /// assert!(map.contains_any_key(&[3, 2]));
+    /// assert!(!map.contains_any_key(&[3, 4]));
+    /// ```
+    pub fn contains_any_key<'q, Q, I>(&self, keys: I) -> bool
+    where
+        Q: ?Sized + Hash + Equivalent<K> + 'q,
+        I: IntoIterator<Item = &'q Q>,
+    {
+        !self.all_keys_found(keys, false)
+    }
+
+    /// Returns whether every key's presence in the map matches `found`, stopping at the first
+    /// key which doesn't.
+    fn all_keys_found<'q, Q, I>(&self, keys: I, found: bool) -> bool
+    where
+        Q: ?Sized + Hash + Equivalent<K> + 'q,
+        I: IntoIterator<Item = &'q Q>,
+    {
+        let mut keys = keys.into_iter();
+        match &self.map_impl {
+            MapTypes::Scanning(m) => keys.all(|key| m.get(key).is_some() == found),
+            MapTypes::Common(m) => keys.all(|key| m.get(key).is_some() == found),
+            MapTypes::PerfectHash(m) => keys.all(|key| m.get(key).is_some() == found),
+            MapTypes::Integer(m) => keys.all(|key| m.get(key).is_some() == found),
+            MapTypes::IntegerRange(m) => keys.all(|key| m.get(key).is_some() == found),
+            MapTypes::Dense(m) => keys.all(|key| m.get(key).is_some() == found),
+            MapTypes::IntegerScanning(m) => keys.all(|key| m.get(key).is_some() == found),
+            MapTypes::LeftSlice(m) => keys.all(|key| m.find(key).is_some() == found),
+            MapTypes::LengthSlice(m) => keys.all(|key| m.find(key).is_some() == found),
+            MapTypes::RightSlice(m) => keys.all(|key| m.find(key).is_some() == found),
+            MapTypes::SplitSlice(m) => keys.all(|key| m.find(key).is_some() == found),
+            MapTypes::Length(m) => keys.all(|key| m.find(key).is_some() == found),
+        }
+    }
+
+}
+
+impl<K, V, BH> FrozenMap<K, V, BH> {
+    /// Returns a reference to the value corresponding to the key, without hashing it.
+    ///
+    /// This is how maps created with [`scanning`](FrozenMap::scanning) are queried, since their
+    /// keys may not implement [`Hash`]. On other maps it compares the key with every entry, so
+    /// [`get`](Self::get) should be preferred there.
+    #[must_use]
+    pub fn get_by_scan<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: ?Sized + Equivalent<K>,
+    {
+        match &self.map_impl {
+            MapTypes::Scanning(m) => m.get(key),
+            _ => self.iter().find_map(|(k, v)| key.equivalent(k).then_some(v)),
+        }
+    }
+
+    /// Returns `true` if the map contains a value for the specified key, without hashing it.
+    ///
+    /// See [`get_by_scan`](Self::get_by_scan) for when this is useful.
+    #[must_use]
+    pub fn contains_key_by_scan<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + Equivalent<K>,
+    {
+        self.get_by_scan(key).is_some()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let a = FrozenMap::from([(0, 1)]);
+    /// assert!(!a.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// An iterator visiting all key-value pairs in arbitrary order.
+    /// The iterator element type is `(&'a K, &'a V)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from([
+    ///     ("a", 1),
+    ///     ("b", 2),
+    ///     ("c", 3),
+    /// ]);
+    ///
+    /// for (key, val) in map.iter() {
+    ///     println!("key: {key} val: {val}");
+    /// }
+    /// ```
+    pub const fn iter(&self) -> Iter<K, V> {
+        match &self.map_impl {
+            MapTypes::Scanning(m) => m.iter(),
+            MapTypes::Common(m) => m.iter(),
+            MapTypes::PerfectHash(m) => m.iter(),
+            MapTypes::Integer(m) => m.iter(),
+            MapTypes::IntegerRange(m) => m.iter(),
+            MapTypes::Dense(m) => m.iter(),
+            MapTypes::IntegerScanning(m) => m.iter(),
+            MapTypes::LeftSlice(m) => m.iter(),
+            MapTypes::LengthSlice(m) => m.iter(),
+            MapTypes::RightSlice(m) => m.iter(),
+            MapTypes::SplitSlice(m) => m.iter(),
+            MapTypes::Length(m) => m.iter(),
+        }
+    }
+
+    /// An iterator visiting key-value pairs in the same order as [`iter`](Self::iter), starting at
+    /// a position obtained from [`Iter::cursor`].
+    ///
+    /// This makes it possible to paginate through a large map, resuming each page where the
+    /// previous one left off without skipping over the entries which were already visited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    /// use frozen_collections_core::specialized_maps::Cursor;
+    ///
+    /// let map = FrozenMap::from_iter((0..10).map(|i| (i, i * 10)));
+    ///
+    /// let mut page = map.iter();
+    /// let first: Vec<_> = page.by_ref().take(4).collect();
+    /// let token = page.cursor().position();
+    ///
+    /// let second: Vec<_> = map.iter_at(Cursor::new(token)).take(4).collect();
+    /// assert_eq!(map.iter().skip(4).take(4).collect::<Vec<_>>(), second);
+    /// ```
+    pub const fn iter_at(&self, cursor: Cursor) -> Iter<'_, K, V> {
+        Iter::at(self.iter().entries(), cursor)
+    }
+
+    /// Returns the key-value pair at a position among the entries, as visited by
+    /// [`iter`](Self::iter).
+    ///
+    /// The positions are those held by a [`SecondaryIndex`] built over the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from([("a", 1), ("b", 2)]);
+    ///
+    /// assert_eq!(map.entry_at(1), map.iter().nth(1));
+    /// assert_eq!(map.entry_at(2), None);
+    /// ```
+    #[must_use]
+    pub fn entry_at(&self, index: usize) -> Option<(&K, &V)> {
+        self.iter().entries().get(index).map(|(k, v)| (k, v))
+    }
+
+    /// Returns the key-value pair at a position among the entries, as visited by
+    /// [`iter`](Self::iter), without checking that the position is in bounds.
+    ///
+    /// Positions can be resolved once with [`position`](Self::position), and then used to
+    /// reach entries without any lookup.
+    ///
+    /// # Safety
+    ///
+    /// Calling this method with a position which isn't less than the map's length is
+    /// undefined behavior.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from([("add", 1), ("sub", 2)]);
+    /// let sub = map.position("sub").unwrap();
+    ///
+    /// assert_eq!(unsafe { map.index_unchecked(sub) }, (&"sub", &2));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub unsafe fn index_unchecked(&self, index: usize) -> (&K, &V) {
+        let (k, v) = unsafe { self.iter().entries().get_unchecked(index) };
+        (k, v)
+    }
+
+    /// Builds an index over the values of the map, from the key `f` derives from each value to
+    /// the positions of the entries holding it.
+    ///
+    /// Several entries may derive the same key. The index only holds positions, so the values
+    /// aren't copied, and [`SecondaryIndex::entries`] gets back to them through the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from([(1, "us-east"), (2, "eu-west"), (3, "us-east")]);
+    /// let by_region = map.index_by(|region| *region);
+    ///
+    /// let mut ids: Vec<_> = by_region.entries("us-east", &map).map(|(id, _)| *id).collect();
+    /// ids.sort();
+    /// assert_eq!(ids, vec![1, 3]);
+    /// ```
+    #[must_use]
+    pub fn index_by<K2, F>(&self, f: F) -> SecondaryIndex<K2>
+    where
+        K2: Hash + Eq,
+        F: Fn(&V) -> K2,
+    {
+        self.index_by_with_hasher(f, RandomState::new())
+    }
+
+    /// Builds an index over the values of the map, which will use the given hash builder to
+    /// hash the keys derived from the values.
+    ///
+    /// See [`index_by`](Self::index_by) for details.
+    #[must_use]
+    pub fn index_by_with_hasher<K2, F, BH2>(&self, f: F, bh: BH2) -> SecondaryIndex<K2, BH2>
+    where
+        K2: Hash + Eq,
+        F: Fn(&V) -> K2,
+        BH2: BuildHasher,
+    {
+        SecondaryIndex::new(self.values().map(f), bh)
+    }
+
+    /// An iterator visiting all keys in arbitrary order.
+    /// The iterator element type is `&'a K`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from([
+    ///     ("a", 1),
+    ///     ("b", 2),
+    ///     ("c", 3),
+    /// ]);
+    ///
+    /// for key in map.keys() {
+    ///     println!("{key}");
+    /// }
+    /// ```
+    pub const fn keys(&self) -> Keys<K, V> {
+        match &self.map_impl {
+            MapTypes::Scanning(m) => m.keys(),
+            MapTypes::Common(m) => m.keys(),
+            MapTypes::PerfectHash(m) => m.keys(),
+            MapTypes::Integer(m) => m.keys(),
+            MapTypes::IntegerRange(m) => m.keys(),
+            MapTypes::Dense(m) => m.keys(),
+            MapTypes::IntegerScanning(m) => m.keys(),
+            MapTypes::LeftSlice(m) => m.keys(),
+            MapTypes::LengthSlice(m) => m.keys(),
+            MapTypes::RightSlice(m) => m.keys(),
+            MapTypes::SplitSlice(m) => m.keys(),
+            MapTypes::Length(m) => m.keys(),
+        }
+    }
+
+    /// An iterator visiting all values in arbitrary order.
+    /// The iterator element type is `&'a V`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from([
+    ///     ("a", 1),
+    ///     ("b", 2),
+    ///     ("c", 3),
+    /// ]);
+    ///
+    /// for val in map.values() {
+    ///     println!("{val}");
+    /// }
+    /// ```
+    pub const fn values(&self) -> Values<K, V> {
+        match &self.map_impl {
+            MapTypes::Scanning(m) => m.values(),
+            MapTypes::Common(m) => m.values(),
+            MapTypes::PerfectHash(m) => m.values(),
+            MapTypes::Integer(m) => m.values(),
+            MapTypes::IntegerRange(m) => m.values(),
+            MapTypes::Dense(m) => m.values(),
+            MapTypes::IntegerScanning(m) => m.values(),
+            MapTypes::LeftSlice(m) => m.values(),
+            MapTypes::LengthSlice(m) => m.values(),
+            MapTypes::RightSlice(m) => m.values(),
+            MapTypes::SplitSlice(m) => m.values(),
+            MapTypes::Length(m) => m.values(),
+        }
+    }
+
+    /// Creates a consuming iterator visiting all the keys in arbitrary order.
+    /// The map cannot be used after calling this. The iterator element type is `K`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from([("a".to_string(), 1), ("b".to_string(), 2)]);
+    ///
+    /// let mut keys: Vec<String> = map.into_keys().collect();
+    /// keys.sort();
+    /// assert_eq!(keys, ["a", "b"]);
+    /// ```
+    #[must_use]
+    pub fn into_keys(self) -> IntoKeys<K, V> {
+        IntoKeys::new(self.into_entries())
+    }
+
+    /// Creates a consuming iterator visiting all the values in arbitrary order.
+    /// The map cannot be used after calling this. The iterator element type is `V`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from([("a", vec![1]), ("b", vec![2, 3])]);
+    ///
+    /// let mut values: Vec<Vec<i32>> = map.into_values().collect();
+    /// values.sort();
+    /// assert_eq!(values, [vec![1], vec![2, 3]]);
+    /// ```
+    #[must_use]
+    pub fn into_values(self) -> IntoValues<K, V> {
+        IntoValues::new(self.into_entries())
+    }
+
+    /// An iterator visiting all key-value pairs in arbitrary order, with mutable references to
+    /// the values. The iterator element type is `(&'a K, &'a mut V)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let mut map = FrozenMap::from([("a", 1), ("b", 2)]);
+    ///
+    /// for (key, val) in map.iter_mut() {
+    ///     if *key == "b" {
+    ///         *val *= 10;
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(map.get("b"), Some(&20));
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut::new(self.entries_mut())
+    }
+
+    /// An iterator visiting all values mutably in arbitrary order.
+    /// The iterator element type is `&'a mut V`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let mut map = FrozenMap::from([("a", 1), ("b", 2)]);
+    ///
+    /// for val in map.values_mut() {
+    ///     *val += 10;
+    /// }
+    ///
+    /// assert_eq!(map.get("a"), Some(&11));
+    /// assert_eq!(map.get("b"), Some(&12));
+    /// ```
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut::new(self.entries_mut())
+    }
+
+    /// Returns the map's entries, in the order in which [`Self::iter`] visits them.
+    fn entries_mut(&mut self) -> &mut [(K, V)] {
+        match &mut self.map_impl {
+            MapTypes::Scanning(m) => &mut m.entries,
+            MapTypes::Common(m) => &mut m.table.entries,
+            MapTypes::PerfectHash(m) => &mut m.entries,
+            MapTypes::Integer(m) => &mut m.table.entries,
+            MapTypes::IntegerRange(m) => &mut m.entries,
+            MapTypes::Dense(m) => &mut m.entries,
+            MapTypes::IntegerScanning(m) => &mut m.entries,
+            MapTypes::LeftSlice(m) => &mut m.table.entries,
+            MapTypes::LengthSlice(m) => &mut m.table.entries,
+            MapTypes::RightSlice(m) => &mut m.table.entries,
+            MapTypes::SplitSlice(m) => &mut m.table.entries,
+            MapTypes::Length(m) => &mut m.table.entries,
+        }
+    }
+
+    /// Transforms every value in the map without reanalyzing the keys.
+    ///
+    /// The function is called on the values in the same order in which [`Self::iter`] visits them.
+    pub(crate) fn map_values<V2, F>(self, f: F) -> FrozenMap<K, V2, BH>
+    where
+        F: FnMut(V) -> V2,
+    {
+        FrozenMap {
+            map_impl: match self.map_impl {
+                MapTypes::Scanning(m) => MapTypes::Scanning(m.map_values(f)),
+                MapTypes::Common(m) => MapTypes::Common(m.map_values(f)),
+                MapTypes::PerfectHash(m) => MapTypes::PerfectHash(m.map_values(f)),
+                MapTypes::Integer(m) => MapTypes::Integer(m.map_values(f)),
+                MapTypes::IntegerRange(m) => MapTypes::IntegerRange(m.map_values(f)),
+                MapTypes::Dense(m) => MapTypes::Dense(m.map_values(f)),
+                MapTypes::IntegerScanning(m) => MapTypes::IntegerScanning(m.map_values(f)),
+                MapTypes::LeftSlice(m) => MapTypes::LeftSlice(m.map_values(f)),
+                MapTypes::LengthSlice(m) => MapTypes::LengthSlice(m.map_values(f)),
+                MapTypes::RightSlice(m) => MapTypes::RightSlice(m.map_values(f)),
+                MapTypes::SplitSlice(m) => MapTypes::SplitSlice(m.map_values(f)),
+                MapTypes::Length(m) => MapTypes::Length(m.map_values(f)),
+            },
+        }
+    }
+
+    /// Consumes the map and returns its entries, in the order in which [`Self::iter`] visits them.
+    pub(crate) fn into_entries(self) -> Box<[(K, V)]> {
+        match self.map_impl {
+            MapTypes::Scanning(m) => m.entries,
+            MapTypes::Common(m) => m.table.entries,
+            MapTypes::PerfectHash(m) => m.entries,
+            MapTypes::Integer(m) => m.table.entries,
+            MapTypes::IntegerRange(m) => m.entries,
+            MapTypes::Dense(m) => m.entries,
+            MapTypes::IntegerScanning(m) => m.entries,
+            MapTypes::LeftSlice(m) => m.table.entries,
+            MapTypes::LengthSlice(m) => m.table.entries,
+            MapTypes::RightSlice(m) => m.table.entries,
+            MapTypes::SplitSlice(m) => m.table.entries,
+            MapTypes::Length(m) => m.table.entries,
+        }
+    }
+}
+
+impl<K, V, BH> FrozenMap<K, V, BH>
+where
+    K: Hash + Eq,
+    BH: BuildHasher,
+{
+    /// An iterator visiting key-value pairs in the same order as [`iter`](Self::iter), starting
+    /// with the given key.
+    ///
+    /// Returns `None` if the key isn't in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from([("a", 1), ("b", 2), ("c", 3)]);
+    ///
+    /// let all: Vec<_> = map.iter().collect();
+    /// let rest: Vec<_> = map.iter_from(all[1].0).unwrap().collect();
+    /// assert_eq!(rest, all[1..]);
+    ///
+    /// assert!(map.iter_from(&"d").is_none());
+    /// ```
+    pub fn iter_from(&self, key: &K) -> Option<Iter<'_, K, V>> {
+        let index = self.entry_index(key)?;
+        Some(Iter::at(self.iter().entries(), Cursor::new(index)))
+    }
+
+    /// Returns the position of the key's entry among the entries, as visited by
+    /// [`iter`](Self::iter).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from([("a", 1), ("b", 2)]);
+    ///
+    /// let position = map.position("b").unwrap();
+    /// assert_eq!(map.entry_at(position), Some((&"b", &2)));
+    /// assert_eq!(map.position("c"), None);
+    /// ```
+    #[must_use]
+    pub fn position<Q>(&self, key: &Q) -> Option<usize>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.entry_index(key)
+    }
+
+    /// Returns the position of the key within the map's entries, as visited by [`Self::iter`].
+    pub(crate) fn entry_index<Q>(&self, key: &Q) -> Option<usize>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let (k, _) = self.get_key_value(key)?;
+        let base = self.iter().entries().as_ptr().addr();
+
+        // the key lives inside its entry, so its offset from the start of the
+        // entries identifies the entry regardless of the tuple's field layout
+        Some(
+            (std::ptr::from_ref(k).addr() - base)
+                .checked_div(size_of::<(K, V)>())
+                .unwrap_or(0),
+        )
+    }
+
+    /// Copies the map's entries into a vector, in the order in which [`Self::iter`] visits them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from([("a", 1), ("b", 2)]);
+    /// let mut entries = map.to_vec();
+    /// entries.sort();
+    ///
+    /// assert_eq!(entries, vec![("a", 1), ("b", 2)]);
+    /// ```
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<(K, V)>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        self.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+    }
+
+    /// Consumes the map and returns its entries sorted by key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from([(3, 'c'), (1, 'a'), (2, 'b')]);
+    /// assert_eq!(map.into_sorted_vec(), vec![(1, 'a'), (2, 'b'), (3, 'c')]);
+    /// ```
+    #[must_use]
+    pub fn into_sorted_vec(self) -> Vec<(K, V)>
+    where
+        K: Ord,
+    {
+        self.into_sorted_by(|x, y| x.0.cmp(&y.0))
+    }
+
+    /// Consumes the map and returns its entries sorted with a comparator function.
+    ///
+    /// Entries which compare equal end up in an unspecified order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from([("a", 3), ("b", 1), ("c", 2)]);
+    /// let entries = map.into_sorted_by(|x, y| x.1.cmp(&y.1));
+    ///
+    /// assert_eq!(entries, vec![("b", 1), ("c", 2), ("a", 3)]);
+    /// ```
+    #[must_use]
+    pub fn into_sorted_by<F>(self, compare: F) -> Vec<(K, V)>
+    where
+        F: FnMut(&(K, V), &(K, V)) -> Ordering,
+    {
+        let mut entries = self.into_entries().into_vec();
+        sort_entries_by(&mut entries, compare);
+        entries
+    }
+
+    /// Renders the map as Rust source code declaring a `static` [`StaticMap`] named `name`.
+    ///
+    /// The static map derives hash codes from its keys the way this map was analyzed to, from
+    /// the integers written by integer keys, or from the subslice or length picked for the
+    /// keys, and its entries are laid out in hash slots by this function. Pasting the output
+    /// into a crate, or writing it out from a `build.rs` script, gives a map which needs no
+    /// construction at startup. See [`codegen`](crate::codegen) for a way to emit a static
+    /// map straight from a payload.
+    ///
+    /// Keys and values are written out through [`RustLiteral`], and the declared types are
+    /// the types of the expressions written, such as `&str` for `String` keys.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map holds more entries than fit in a `u32`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from([("a", 2)]);
+    /// let code = map.emit_rust("MY_MAP");
+    ///
+    /// assert!(code.starts_with("static MY_MAP: ::frozen_collections::specialized_maps::StaticMap<&str, i32> = "));
+    /// assert!(code.contains("(\"a\", 2),"));
+    /// ```
+    #[must_use]
+    pub fn emit_rust(&self, name: &str) -> String
+    where
+        K: RustLiteral,
+        V: RustLiteral,
+    {
+        assert!(
+            u32::try_from(self.len()).is_ok(),
+            "Too many entries for a static map"
+        );
+
+        let key_code = match self.strategy() {
+            MapStrategy::Integer
+            | MapStrategy::IntegerRange
+            | MapStrategy::DenseInteger
+            | MapStrategy::IntegerScanning => StaticKeyCode::Integer,
+            MapStrategy::LeftSlice { range } => StaticKeyCode::LeftSlice(range),
+            MapStrategy::RightSlice { range } => StaticKeyCode::RightSlice(range),
+            MapStrategy::SplitSlice { left, right } => StaticKeyCode::SplitSlice(left, right),
+            MapStrategy::LengthSlice { range } => StaticKeyCode::LengthSlice(range),
+            MapStrategy::Length => StaticKeyCode::Length,
+            MapStrategy::Scanning | MapStrategy::Common | MapStrategy::PerfectHash => {
+                StaticKeyCode::Hashed
+            }
+        };
+
+        emit_static_map_with(name, &key_code, self.iter().collect())
+    }
+}
+
+impl<K, V, BH> FrozenMap<K, V, BH>
+where
+    K: Hash + Eq + AsRef<str>,
+    BH: BuildHasher,
+{
+    /// Returns the keys which are within `max_distance` edits of `query`, closest first.
+    ///
+    /// Distances are measured in characters using the Levenshtein metric, and at most
+    /// `limit` keys are returned. Keys at the same distance are ordered lexicographically.
+    /// This is meant to produce "did you mean ...?" suggestions after a lookup misses.
+    ///
+    /// Keys whose length differs from the query by more than `max_distance` are skipped
+    /// without computing a distance, and the prefix and suffix shared with the query are
+    /// trimmed before the remainder is compared. Each call still measures the length of every
+    /// key, so use a [`KeySuggester`] to answer many queries against the same map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from([("build", 1), ("bench", 2), ("check", 3), ("clean", 4)]);
+    ///
+    /// assert_eq!(map.closest_keys("biuld", 2, 3), vec![&"build"]);
+    /// assert_eq!(map.closest_keys("chek", 1, 3), vec![&"check"]);
+    /// assert!(map.closest_keys("publish", 2, 3).is_empty());
+    /// ```
+    #[must_use]
+    pub fn closest_keys(&self, query: &str, max_distance: usize, limit: usize) -> Vec<&K> {
+        let query: Vec<char> = query.chars().collect();
+        let candidates = self
+            .keys()
+            .filter(|key| key.as_ref().chars().count().abs_diff(query.len()) <= max_distance);
+
+        KeySuggester::rank(&query, candidates, max_distance, limit)
+    }
+
+    /// Builds an index over the keys of the map which finds the keys closest to a query
+    /// without visiting every key.
+    ///
+    /// See [`KeySuggester`] for details.
+    #[must_use]
+    pub fn key_suggester(&self) -> KeySuggester<'_, K> {
+        KeySuggester::new(self.keys())
+    }
+}
+
+/// Returns the analysis of slice keys which leads to the given strategy, if any.
+fn slice_analysis(strategy: MapStrategy) -> Option<SliceKeyAnalysisResult> {
+    Some(match strategy {
+        MapStrategy::LeftSlice { range } => SliceKeyAnalysisResult::LeftHandSubslice {
+            subslice_index: range.start,
+            subslice_len: range.len(),
+        },
+        MapStrategy::LengthSlice { range } => SliceKeyAnalysisResult::LengthAndSubslice {
+            subslice_index: range.start,
+            subslice_len: range.len(),
+        },
+        MapStrategy::RightSlice { range } => SliceKeyAnalysisResult::RightHandSubslice {
+            subslice_index: range.start,
+            subslice_len: range.len(),
+        },
+        MapStrategy::SplitSlice { left, right } => SliceKeyAnalysisResult::SplitSubslice {
+            left_index: left.start,
+            left_len: left.len(),
+            right_index: right.start,
+            right_len: right.len(),
+        },
+        MapStrategy::Length => SliceKeyAnalysisResult::Length,
+        _ => return None,
+    })
+}
+
+/// Splits a reference to an entry into references to its key and value.
+#[cfg(feature = "rayon")]
+const fn entry_refs<K, V>(entry: &(K, V)) -> (&K, &V) {
+    (&entry.0, &entry.1)
+}
+
+/// Projects the entries found by a batched lookup onto their values.
+fn entry_values<K, V, const N: usize>(entries: [Option<&(K, V)>; N]) -> [Option<&V>; N] {
+    entries.map(|entry| entry.map(|(_, v)| v))
+}
+
+impl<K, V, BH> FrozenMap<K, V, BH>
+where
+    K: Hash + Eq,
+    BH: PassThroughHasher,
+{
+    /// Creates a frozen map of keys which are already hashes, using a hash builder which passes
+    /// them through.
+    ///
+    /// Keys which write a single integer are handled as by [`Self::from_vec_with_hasher`], and
+    /// other keys go straight into a hash table indexed by the bits they write, rather than
+    /// being analyzed for subslices which tell them apart.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::{FrozenMap, MapStrategy};
+    /// use frozen_collections_core::hashers::NoHashBuilder;
+    ///
+    /// let digests: Vec<[u8; 16]> = (0..300u128)
+    ///     .map(|i| (i * 0x9e37_79b9_7f4a_7c15).to_le_bytes())
+    ///     .collect();
+    ///
+    /// let payload = digests.iter().copied().zip(0..).collect();
+    /// let map = FrozenMap::from_vec_with_pass_through_hasher(payload, NoHashBuilder);
+    ///
+    /// assert_eq!(map.strategy(), MapStrategy::Common);
+    /// assert_eq!(map.get(&digests[3]), Some(&3));
+    /// ```
+    #[must_use]
+    pub fn from_vec_with_pass_through_hasher(payload: Vec<(K, V)>, bh: BH) -> Self {
+        Self::new_with_options(payload, bh, &AnalysisOptions::new().prehashed_keys())
+    }
+}
+
+impl<K, V> FrozenMap<K, V, RandomState>
+where
+    K: Hash + Eq,
+{
+    /// Creates a frozen map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from_vec(vec![(1, 2)]);
+    /// ```
+    #[must_use]
+    pub fn from_vec(payload: Vec<(K, V)>) -> Self {
+        Self::new(payload, RandomState::new())
+    }
+
+    /// Creates a frozen map holding this map's entries, with some entries added and others
+    /// removed.
+    ///
+    /// See [`refreeze_with_hasher`](Self::refreeze_with_hasher) for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::from_vec(vec![(1, "a"), (2, "b"), (3, "c")]);
+    /// let map = map.refreeze_with(vec![(4, "d")], [&1]);
+    ///
+    /// assert_eq!(map.get(&1), None);
+    /// assert_eq!(map.get(&4), Some(&"d"));
+    /// ```
+    #[must_use]
+    pub fn refreeze_with<'q, Q, I>(self, additions: Vec<(K, V)>, removals: I) -> Self
+    where
+        Q: ?Sized + Hash + Equivalent<K> + 'q,
+        I: IntoIterator<Item = &'q Q>,
+    {
+        self.refreeze_with_hasher(additions, removals, RandomState::new())
+    }
+
+    /// Creates a map which answers lookups right away, while the keys are analyzed on a
+    /// background thread.
+    ///
+    /// Lookups go through a plain hash map until the analysis completes, so this trades the
+    /// speed of the first lookups for a shorter startup.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::freeze_deferred(vec![("a", 1), ("b", 2)]);
+    /// assert_eq!(map.get("b"), Some(&2));
+    /// ```
+    #[must_use]
+    pub fn freeze_deferred(payload: Vec<(K, V)>) -> DeferredFrozenMap<K, V>
+    where
+        K: Clone + Send + Sync + 'static,
+    {
+        DeferredFrozenMap::new(payload, RandomState::new())
+    }
+
+    /// Creates a frozen map, returning an error instead of accepting an invalid payload.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// assert!(FrozenMap::try_from_vec(vec![(1, 2)]).is_ok());
+    /// assert!(FrozenMap::try_from_vec(vec![("a", 1), ("a", 2)]).is_err());
+    /// ```
+    pub fn try_from_vec(payload: Vec<(K, V)>) -> std::result::Result<Self, FrozenError> {
+        Self::try_from_vec_with_hasher(payload, RandomState::new())
+    }
+
+    /// Creates a frozen map, handling duplicate keys according to `policy`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys and the policy is [`DuplicatePolicy::Error`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::{DuplicatePolicy, FrozenMap};
+    ///
+    /// let payload = vec![(1, "a"), (2, "b"), (1, "c")];
+    /// let map = FrozenMap::from_vec_with_policy(payload, DuplicatePolicy::KeepFirst).unwrap();
+    ///
+    /// assert_eq!(map.len(), 2);
+    /// assert_eq!(map.get(&1), Some(&"a"));
+    /// ```
+    pub fn from_vec_with_policy(
+        payload: Vec<(K, V)>,
+        policy: DuplicatePolicy,
+    ) -> std::result::Result<Self, FrozenError> {
+        Self::from_vec_with_hasher_and_policy(payload, RandomState::new(), policy)
+    }
+}
+
+impl<K, V, BH> FrozenMap<K, Box<[V]>, BH>
+where
+    K: Hash + Eq,
+    BH: BuildHasher,
+{
+    /// Creates a frozen map from entries which may repeat keys, gathering the values of each
+    /// key into a slice, and using the given hash builder to hash keys.
+    ///
+    /// The values of a key keep the order they were produced in. The entries are grouped in
+    /// place, without first building a map of growable vectors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    /// use std::hash::RandomState;
+    ///
+    /// let entries = vec![("a", 1), ("b", 2), ("a", 3)];
+    /// let map = FrozenMap::group_from_iter_with_hasher(entries, RandomState::new());
+    ///
+    /// assert_eq!(map.get("a").map(|v| &v[..]), Some(&[1, 3][..]));
+    /// assert_eq!(map.get("b").map(|v| &v[..]), Some(&[2][..]));
+    /// ```
+    #[must_use]
+    pub fn group_from_iter_with_hasher<T: IntoIterator<Item = (K, V)>>(iter: T, bh: BH) -> Self {
+        let payload = Vec::from_iter(iter);
+        let first = find_key_groups(&payload, |k| bh.hash_one(k));
+
+        let mut counts = vec![0; payload.len()];
+        for &leader in &first {
+            counts[leader] += 1;
+        }
+
+        // a stable sort keeps the values of each key in order
+        let mut entries: Vec<_> = first.into_iter().zip(payload).collect();
+        entries.sort_by_key(|x| x.0);
+
+        let mut groups = Vec::with_capacity(counts.iter().filter(|&&c| c > 0).count());
+        let mut entries = entries.into_iter();
+        while let Some((leader, (k, v))) = entries.next() {
+            let mut values = Vec::with_capacity(counts[leader]);
+            values.push(v);
+            values.extend(entries.by_ref().take(counts[leader] - 1).map(|x| x.1 .1));
+            groups.push((k, values.into_boxed_slice()));
         }
+
+        Self::new(groups, bh)
+    }
+}
+
+impl<K, V> FrozenMap<K, Box<[V]>, RandomState>
+where
+    K: Hash + Eq,
+{
+    /// Creates a frozen map from entries which may repeat keys, gathering the values of each
+    /// key into a slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// let map = FrozenMap::group_from_iter([(1, 'a'), (2, 'b'), (1, 'c'), (1, 'd')]);
+    ///
+    /// assert_eq!(map.len(), 2);
+    /// assert_eq!(map.get(&1).map(|v| &v[..]), Some(&['a', 'c', 'd'][..]));
+    /// ```
+    #[must_use]
+    pub fn group_from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self::group_from_iter_with_hasher(iter, RandomState::new())
     }
 }
 
 impl<K, V> FrozenMap<K, V, RandomState>
+where
+    K: Eq,
+{
+    /// Creates a frozen map which scans its entries, for keys which can be compared for
+    /// equality but not hashed.
+    ///
+    /// Scanning beats hashing for a handful of entries, but every lookup compares the query
+    /// with each entry, so this is only a good fit for small payloads. Since the keys don't
+    /// implement [`Hash`], the map is queried with [`get_by_scan`](Self::get_by_scan) and
+    /// [`contains_key_by_scan`](Self::contains_key_by_scan).
+    ///
+    /// When several entries hold the same key, the last one is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    ///
+    /// #[derive(PartialEq, Eq)]
+    /// enum Axis {
+    ///     X,
+    ///     Y,
+    ///     Z,
+    /// }
+    ///
+    /// let map = FrozenMap::scanning(vec![(Axis::X, 1), (Axis::Y, 2), (Axis::X, 3)]);
+    ///
+    /// assert_eq!(map.len(), 2);
+    /// assert_eq!(map.get_by_scan(&Axis::X), Some(&3));
+    /// assert!(!map.contains_key_by_scan(&Axis::Z));
+    /// ```
+    #[must_use]
+    pub fn scanning(payload: Vec<(K, V)>) -> Self {
+        let mut entries: Vec<(K, V)> = Vec::with_capacity(payload.len());
+        for entry in payload {
+            match entries.iter_mut().find(|x| x.0 == entry.0) {
+                Some(existing) => *existing = entry,
+                None => entries.push(entry),
+            }
+        }
+
+        Self {
+            map_impl: MapTypes::Scanning(ScanningMap::from_vec(entries)),
+        }
+    }
+}
+
+impl<K, V> FrozenMap<K, V, SeededState>
 where
     K: Hash + Eq,
 {
-    /// Creates a frozen map.
+    /// Creates a frozen map whose layout, and so iteration order, only depends on the payload
+    /// and the seed, making it identical every time the program runs.
+    ///
+    /// This is meant for reproducible output and snapshot tests: the keys' hash codes are
+    /// predictable, so the map shouldn't hold keys from untrusted sources. The analysis of the
+    /// keys is never bounded in time, since where a bounded analysis stops depends on the speed
+    /// of the machine; see [`AnalysisOptions::max_duration`].
     ///
     /// # Examples
     ///
     /// ```
     /// use frozen_collections_core::facades::FrozenMap;
     ///
-    /// let map = FrozenMap::from_vec(vec![(1, 2)]);
+    /// let map = FrozenMap::from_vec_with_seed(vec![("a", 1), ("b", 2)], 42);
+    /// assert_eq!(map.get("b"), Some(&2));
     /// ```
     #[must_use]
-    pub fn from_vec(payload: Vec<(K, V)>) -> Self {
-        Self::new(payload, RandomState::new())
+    pub fn from_vec_with_seed(payload: Vec<(K, V)>, seed: u64) -> Self {
+        Self::new(payload, SeededState::new(seed))
     }
 }
 
@@ -708,6 +2429,83 @@ where
     }
 }
 
+/// Freezes a hash map, keeping its hash builder.
+///
+/// The keys of a hash map are already unique, so this skips the duplicate check done by
+/// [`FrozenMap::try_from_vec`].
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use frozen_collections_core::facades::FrozenMap;
+///
+/// let mut map = HashMap::new();
+/// map.insert("a", 1);
+/// map.insert("b", 2);
+///
+/// let map = FrozenMap::from(map);
+/// assert_eq!(map.get("b"), Some(&2));
+/// ```
+impl<K, V, S> From<HashMap<K, V, S>> for FrozenMap<K, V, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher + Clone,
+{
+    fn from(map: HashMap<K, V, S>) -> Self {
+        let bh = map.hasher().clone();
+        Self::new(Vec::from_iter(map), bh)
+    }
+}
+
+/// Freezes a B-tree map.
+///
+/// The keys of a B-tree map are already unique, so this skips the duplicate check done by
+/// [`FrozenMap::try_from_vec`].
+impl<K, V> From<BTreeMap<K, V>> for FrozenMap<K, V, RandomState>
+where
+    K: Hash + Eq,
+{
+    fn from(map: BTreeMap<K, V>) -> Self {
+        Self::new(Vec::from_iter(map), RandomState::new())
+    }
+}
+
+/// Thaws a frozen map into a hash map, moving its entries without cloning them.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use frozen_collections_core::facades::FrozenMap;
+///
+/// let map = FrozenMap::from([("a", 1), ("b", 2)]);
+///
+/// let mut map = HashMap::from(map);
+/// map.insert("c", 3);
+/// assert_eq!(map.len(), 3);
+/// ```
+impl<K, V, BH> From<FrozenMap<K, V, BH>> for HashMap<K, V, RandomState>
+where
+    K: Hash + Eq,
+    BH: BuildHasher,
+{
+    fn from(map: FrozenMap<K, V, BH>) -> Self {
+        Self::from_iter(map.into_entries().into_vec())
+    }
+}
+
+/// Thaws a frozen map into a B-tree map, moving its entries without cloning them.
+impl<K, V, BH> From<FrozenMap<K, V, BH>> for BTreeMap<K, V>
+where
+    K: Hash + Ord,
+    BH: BuildHasher,
+{
+    fn from(map: FrozenMap<K, V, BH>) -> Self {
+        Self::from_iter(map.into_entries().into_vec())
+    }
+}
+
 impl<K, V, BH> Index<K> for FrozenMap<K, V, BH>
 where
     K: Hash + Eq,
@@ -730,15 +2528,76 @@ where
     }
 }
 
-impl<K, V, BH> Default for FrozenMap<K, V, BH>
+impl<K, V, BH> Default for FrozenMap<K, V, BH> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<K, V, BH> Len for FrozenMap<K, V, BH> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V, BH> HeapSize for FrozenMap<K, V, BH>
+where
+    K: HeapSize,
+    V: HeapSize,
+{
+    fn heap_bytes(&self) -> usize {
+        self.stats().heap_bytes() + entries_heap_bytes(self.iter())
+    }
+}
+
+impl<K, V, BH> Map<K, V> for FrozenMap<K, V, BH>
 where
     K: Hash + Eq,
-    BH: BuildHasher + Default,
+    BH: BuildHasher,
 {
-    fn default() -> Self {
-        Self {
-            map_impl: MapTypes::Scanning(ScanningMap::<K, V>::from_vec(vec![])),
-        }
+    type Iterator<'a>
+        = Iter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+        BH: 'a;
+
+    type KeyIterator<'a>
+        = Keys<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+        BH: 'a;
+
+    type ValueIterator<'a>
+        = Values<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+        BH: 'a;
+
+    fn iter(&self) -> Iter<'_, K, V> {
+        self.iter()
+    }
+
+    fn keys(&self) -> Keys<'_, K, V> {
+        self.keys()
+    }
+
+    fn values(&self) -> Values<'_, K, V> {
+        self.values()
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    unsafe fn get_unchecked(&self, key: &K) -> &V {
+        unsafe { self.get_unchecked(key) }
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.contains_key(key)
     }
 }
 
@@ -750,16 +2609,17 @@ where
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match &self.map_impl {
             MapTypes::Scanning(m) => m.fmt(f),
-            MapTypes::CommonSmall(m) => m.fmt(f),
-            MapTypes::CommonLarge(m) => m.fmt(f),
-            MapTypes::U32Small(m) => m.fmt(f),
-            MapTypes::U32Large(m) => m.fmt(f),
-            MapTypes::U32Range(m) => m.fmt(f),
-            MapTypes::LeftStringSliceSmall(m) => m.fmt(f),
-            MapTypes::LeftStringSliceLarge(m) => m.fmt(f),
-            MapTypes::RightStringSliceSmall(m) => m.fmt(f),
-            MapTypes::RightStringSliceLarge(m) => m.fmt(f),
-            MapTypes::StringLengthSmall(m) => m.fmt(f),
+            MapTypes::Common(m) => m.fmt(f),
+            MapTypes::PerfectHash(m) => m.fmt(f),
+            MapTypes::Integer(m) => m.fmt(f),
+            MapTypes::IntegerRange(m) => m.fmt(f),
+            MapTypes::Dense(m) => m.fmt(f),
+            MapTypes::IntegerScanning(m) => m.fmt(f),
+            MapTypes::LeftSlice(m) => m.fmt(f),
+            MapTypes::LengthSlice(m) => m.fmt(f),
+            MapTypes::RightSlice(m) => m.fmt(f),
+            MapTypes::SplitSlice(m) => m.fmt(f),
+            MapTypes::Length(m) => m.fmt(f),
         }
     }
 }
@@ -788,11 +2648,87 @@ where
 {
 }
 
-impl<'a, K, V, BH> IntoIterator for &'a FrozenMap<K, V, BH>
+/// Hashes the entries of the map regardless of their order, so maps which compare equal hash
+/// the same even when they were built with different hash builders or strategies.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use frozen_collections_core::facades::FrozenMap;
+///
+/// let a = FrozenMap::from([("a", 1), ("b", 2)]);
+/// let b = FrozenMap::from([("b", 2), ("a", 1)]);
+///
+/// let mut cache = HashMap::new();
+/// cache.insert(a, "config");
+/// assert_eq!(cache.get(&b), Some(&"config"));
+/// ```
+impl<K, V, BH> Hash for FrozenMap<K, V, BH>
+where
+    K: Hash + Eq,
+    V: Hash,
+    BH: BuildHasher,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self, state);
+    }
+}
+
+impl<K, V, BH, S> PartialEq<HashMap<K, V, S>> for FrozenMap<K, V, BH>
+where
+    K: Hash + Eq,
+    V: PartialEq,
+    BH: BuildHasher,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &HashMap<K, V, S>) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+
+        // probe the frozen map, which is the faster of the two
+        other
+            .iter()
+            .all(|(key, value)| self.get(key).is_some_and(|v| *v == *value))
+    }
+}
+
+impl<K, V, BH, S> PartialEq<FrozenMap<K, V, BH>> for HashMap<K, V, S>
 where
     K: Hash + Eq,
+    V: PartialEq,
     BH: BuildHasher,
+    S: BuildHasher,
 {
+    fn eq(&self, other: &FrozenMap<K, V, BH>) -> bool {
+        other == self
+    }
+}
+
+/// Moves the entries out of the map, in arbitrary order.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::FrozenMap;
+///
+/// let map = FrozenMap::from([("a", "x".to_string()), ("b", "y".to_string())]);
+///
+/// let mut entries: Vec<(&str, String)> = map.into_iter().collect();
+/// entries.sort();
+/// assert_eq!(entries, [("a", "x".to_string()), ("b", "y".to_string())]);
+/// ```
+impl<K, V, BH> IntoIterator for FrozenMap<K, V, BH> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.into_entries())
+    }
+}
+
+impl<'a, K, V, BH> IntoIterator for &'a FrozenMap<K, V, BH> {
     type Item = (&'a K, &'a V);
     type IntoIter = Iter<'a, K, V>;
 
@@ -800,3 +2736,170 @@ where
         self.iter()
     }
 }
+
+impl<'a, K, V, BH> IntoIterator for &'a mut FrozenMap<K, V, BH> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<K, V, BH> FrozenMap<K, V, BH>
+where
+    K: Hash + Eq + Sync,
+    V: Sync,
+    BH: BuildHasher,
+{
+    /// A parallel iterator visiting all key-value pairs.
+    /// The iterator element type is `(&'a K, &'a V)`.
+    ///
+    /// The entries of a frozen map are stored contiguously, so they split evenly across threads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    /// use rayon::prelude::*;
+    ///
+    /// let map = FrozenMap::from_iter((0..1000).map(|i| (i, i * 2)));
+    ///
+    /// let sum: i32 = map.par_iter().map(|(k, v)| k + v).sum();
+    /// assert_eq!(sum, 1_498_500);
+    /// ```
+    pub fn par_iter(&self) -> impl IndexedParallelIterator<Item = (&K, &V)> {
+        self.into_par_iter()
+    }
+
+    /// A parallel iterator visiting all keys.
+    /// The iterator element type is `&'a K`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    /// use rayon::prelude::*;
+    ///
+    /// let map = FrozenMap::from([("a", 1), ("bb", 2), ("ccc", 3)]);
+    /// assert_eq!(map.par_keys().map(|k| k.len()).sum::<usize>(), 6);
+    /// ```
+    pub fn par_keys(&self) -> impl IndexedParallelIterator<Item = &K> {
+        self.par_iter().map(|(k, _)| k)
+    }
+
+    /// A parallel iterator visiting all values.
+    /// The iterator element type is `&'a V`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMap;
+    /// use rayon::prelude::*;
+    ///
+    /// let map = FrozenMap::from([("a", 1), ("b", 2), ("c", 3)]);
+    /// assert_eq!(map.par_values().max(), Some(&3));
+    /// ```
+    pub fn par_values(&self) -> impl IndexedParallelIterator<Item = &V> {
+        self.par_iter().map(|(_, v)| v)
+    }
+}
+
+#[cfg(feature = "rayon")]
+type ParIter<'a, K, V> =
+    rayon::iter::Map<rayon::slice::Iter<'a, (K, V)>, fn(&'a (K, V)) -> (&'a K, &'a V)>;
+
+#[cfg(feature = "rayon")]
+impl<'a, K, V, BH> IntoParallelIterator for &'a FrozenMap<K, V, BH>
+where
+    K: Hash + Eq + Sync,
+    V: Sync,
+    BH: BuildHasher,
+{
+    type Item = (&'a K, &'a V);
+    type Iter = ParIter<'a, K, V>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.iter().entries().par_iter().map(entry_refs)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<K, V, BH> IntoParallelIterator for FrozenMap<K, V, BH>
+where
+    K: Hash + Eq + Send,
+    V: Send,
+    BH: BuildHasher,
+{
+    type Item = (K, V);
+    type Iter = rayon::vec::IntoIter<(K, V)>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.into_entries().into_vec().into_par_iter()
+    }
+}
+
+/// Wipes the keys, values and hash table metadata of the map, leaving it empty.
+///
+/// To have the map wiped automatically when it's dropped, use a [`ZeroizingFrozenMap`](crate::facades::ZeroizingFrozenMap).
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::FrozenMap;
+/// use zeroize::Zeroize;
+///
+/// let mut map = FrozenMap::from([("token-1".to_string(), 1), ("token-2".to_string(), 2)]);
+/// map.zeroize();
+///
+/// assert!(map.is_empty());
+/// assert_eq!(map.get(&"token-1".to_string()), None);
+/// ```
+#[cfg(feature = "zeroize")]
+impl<K, V, BH> Zeroize for FrozenMap<K, V, BH>
+where
+    K: Zeroize,
+    V: Zeroize,
+{
+    fn zeroize(&mut self) {
+        match &mut self.map_impl {
+            MapTypes::Scanning(m) => m.zeroize(),
+            MapTypes::Common(m) => m.zeroize(),
+            MapTypes::PerfectHash(m) => m.zeroize(),
+            MapTypes::Integer(m) => m.zeroize(),
+            MapTypes::IntegerRange(m) => m.zeroize(),
+            MapTypes::Dense(m) => m.zeroize(),
+            MapTypes::IntegerScanning(m) => m.zeroize(),
+            MapTypes::LeftSlice(m) => m.zeroize(),
+            MapTypes::LengthSlice(m) => m.zeroize(),
+            MapTypes::RightSlice(m) => m.zeroize(),
+            MapTypes::SplitSlice(m) => m.zeroize(),
+            MapTypes::Length(m) => m.zeroize(),
+        }
+    }
+}
+
+/// Accounts for the map in memory reports built with the `deepsize` crate.
+///
+/// # Examples
+///
+/// ```
+/// use deepsize::DeepSizeOf;
+/// use frozen_collections_core::facades::FrozenMap;
+///
+/// let map = FrozenMap::from([("a".to_string(), vec![1u64; 100])]);
+/// assert!(map.deep_size_of() > map.stats().heap_bytes() + 800);
+/// ```
+#[cfg(feature = "deepsize")]
+impl<K, V, BH> DeepSizeOf for FrozenMap<K, V, BH>
+where
+    K: DeepSizeOf,
+    V: DeepSizeOf,
+{
+    fn deep_size_of_children(&self, context: &mut deepsize::Context) -> usize {
+        self.iter().fold(self.stats().heap_bytes(), |bytes, (k, v)| {
+            bytes + k.deep_size_of_children(context) + v.deep_size_of_children(context)
+        })
+    }
+}
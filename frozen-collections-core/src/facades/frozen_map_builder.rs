@@ -0,0 +1,329 @@
+use std::hash::RandomState;
+use std::hash::{BuildHasher, Hash};
+
+use crate::analyzers::int_key_analyzer::DEFAULT_MIN_KEY_DENSITY_PERCENT;
+use crate::errors::FrozenError;
+use crate::facades::indirect_frozen_map::DEFAULT_INDIRECT_THRESHOLD;
+use crate::facades::{
+    AnalysisOptions, ArenaFrozenMap, CompactFrozenMap, DuplicatePolicy, FrontCodedFrozenMap,
+    FrozenMap, IndirectFrozenMap, NormalizedFrozenMap, PackedFrozenMap,
+};
+use crate::traits::packed_value::PackedValue;
+
+/// Accumulates entries and options for a frozen map, then builds it.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::FrozenMapBuilder;
+///
+/// let mut builder = FrozenMapBuilder::new();
+/// builder.push("a", 1).push("b", 2);
+/// builder.extend([("c", 3)]);
+///
+/// let map = builder.build();
+/// assert_eq!(map.get(&"c"), Some(&3));
+/// ```
+#[derive(Clone, Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct FrozenMapBuilder<K, V, BH = RandomState> {
+    payload: Vec<(K, V)>,
+    bh: BH,
+    value_bits: Option<u32>,
+    indirect_threshold: usize,
+    min_key_density: u8,
+    duplicate_policy: Option<DuplicatePolicy>,
+}
+
+impl<K, V, BH> FrozenMapBuilder<K, V, BH> {
+    /// Creates a builder for maps which will use the given hash builder to hash keys.
+    #[must_use]
+    pub const fn with_hasher(bh: BH) -> Self {
+        Self {
+            payload: Vec::new(),
+            bh,
+            value_bits: None,
+            indirect_threshold: DEFAULT_INDIRECT_THRESHOLD,
+            min_key_density: DEFAULT_MIN_KEY_DENSITY_PERCENT,
+            duplicate_policy: None,
+        }
+    }
+
+    /// Adds an entry to the map being built.
+    pub fn push(&mut self, key: K, value: V) -> &mut Self {
+        self.payload.push((key, value));
+        self
+    }
+
+    /// Sets the number of bits used to store each value of a map created with
+    /// [`build_packed`](Self::build_packed).
+    ///
+    /// By default, the narrowest width able to represent every value is used. Forcing a
+    /// width is useful when the set of values the map may have to represent is known to be
+    /// wider than the values actually present.
+    pub const fn value_bits(&mut self, bits: u32) -> &mut Self {
+        self.value_bits = Some(bits);
+        self
+    }
+
+    /// Sets the size in bytes above which a map created with
+    /// [`build_indirect`](Self::build_indirect) stores values apart from the keys.
+    ///
+    /// Defaults to [`DEFAULT_INDIRECT_THRESHOLD`].
+    pub const fn indirect_threshold(&mut self, bytes: usize) -> &mut Self {
+        self.indirect_threshold = bytes;
+        self
+    }
+
+    /// Sets the smallest share of their range, in percent, which integer keys must occupy for
+    /// [`build`](Self::build) and [`try_build`](Self::try_build) to index them directly
+    /// instead of hashing them.
+    ///
+    /// Direct indexing spends four bytes for every integer between the smallest and largest
+    /// key, so raising the threshold trades lookup speed for memory. Defaults to 25 percent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMapBuilder;
+    ///
+    /// let mut builder = FrozenMapBuilder::new();
+    /// builder.extend((0..100).map(|i| (i * 10, i)));
+    /// builder.min_key_density(10);
+    ///
+    /// let map = builder.build();
+    /// assert_eq!(map.get(&990), Some(&99));
+    /// ```
+    pub const fn min_key_density(&mut self, percent: u8) -> &mut Self {
+        self.min_key_density = percent;
+        self
+    }
+
+    /// Sets how [`build`](Self::build) and [`try_build`](Self::try_build) handle entries which
+    /// hold the same key.
+    ///
+    /// By default, no policy is enforced and the payload is used as is.
+    pub const fn duplicate_policy(&mut self, policy: DuplicatePolicy) -> &mut Self {
+        self.duplicate_policy = Some(policy);
+        self
+    }
+}
+
+impl<K, V, BH> FrozenMapBuilder<K, V, BH>
+where
+    K: Hash + Eq,
+    BH: BuildHasher,
+{
+    /// Creates a frozen map from the accumulated entries.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entries hold duplicate keys and the policy set with
+    /// [`duplicate_policy`](Self::duplicate_policy) is [`DuplicatePolicy::Error`].
+    #[must_use]
+    pub fn build(self) -> FrozenMap<K, V, BH> {
+        self.try_build()
+            .expect("The payload must not hold duplicate keys")
+    }
+
+    /// Creates a frozen map from the accumulated entries, reporting duplicate keys instead of
+    /// panicking.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the entries hold duplicate keys and the policy set with
+    /// [`duplicate_policy`](Self::duplicate_policy) is [`DuplicatePolicy::Error`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::errors::FrozenError;
+    /// use frozen_collections_core::facades::{DuplicatePolicy, FrozenMapBuilder};
+    ///
+    /// let mut builder = FrozenMapBuilder::new();
+    /// builder.push("a", 1).push("b", 2).push("a", 3);
+    ///
+    /// let mut keep_last = builder.clone();
+    /// keep_last.duplicate_policy(DuplicatePolicy::KeepLast);
+    /// assert_eq!(keep_last.try_build().unwrap().get("a"), Some(&3));
+    ///
+    /// builder.duplicate_policy(DuplicatePolicy::Error);
+    /// assert_eq!(builder.try_build().err(), Some(FrozenError::DuplicateKey { index: 2 }));
+    /// ```
+    pub fn try_build(self) -> Result<FrozenMap<K, V, BH>, FrozenError> {
+        let payload = match self.duplicate_policy {
+            Some(policy) => policy.apply(self.payload, |k| self.bh.hash_one(k))?,
+            None => self.payload,
+        };
+
+        let options = AnalysisOptions::new().min_key_density(self.min_key_density);
+        Ok(FrozenMap::new_with_options(payload, self.bh, &options))
+    }
+
+    /// Creates a frozen map whose values are bit-packed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the width set with [`value_bits`](Self::value_bits) is larger than 64 or too
+    /// small to represent one of the values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMapBuilder;
+    ///
+    /// let mut builder = FrozenMapBuilder::new();
+    /// builder.push(1, 1u8).push(2, 2).value_bits(4);
+    ///
+    /// let map = builder.build_packed();
+    /// assert_eq!(map.value_bits(), 4);
+    /// assert_eq!(map.get(&2), Some(2));
+    /// ```
+    #[must_use]
+    pub fn build_packed(self) -> PackedFrozenMap<K, V, BH>
+    where
+        V: PackedValue,
+    {
+        PackedFrozenMap::new(self.payload, self.bh, self.value_bits)
+    }
+
+    /// Creates a frozen map which stores values apart from the keys if they are larger than
+    /// the threshold set with [`indirect_threshold`](Self::indirect_threshold).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMapBuilder;
+    ///
+    /// let mut builder = FrozenMapBuilder::new();
+    /// builder.push(1, [0u8; 64]).push(2, [1u8; 64]).indirect_threshold(32);
+    ///
+    /// let map = builder.build_indirect();
+    /// assert!(map.is_indirect());
+    /// assert_eq!(map.get(&2).map(|v| v[0]), Some(1));
+    /// ```
+    #[must_use]
+    pub fn build_indirect(self) -> IndirectFrozenMap<K, V, BH> {
+        IndirectFrozenMap::new(self.payload, self.bh, self.indirect_threshold)
+    }
+
+    /// Creates a frozen map which applies `normalize` to its keys and to every lookup.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMapBuilder;
+    ///
+    /// let mut builder = FrozenMapBuilder::new();
+    /// builder.push("GET".to_string(), 1).push("Post".to_string(), 2);
+    ///
+    /// let map = builder.build_normalized(|k: &String| k.to_uppercase());
+    /// assert_eq!(map.get(&"post".to_string()), Some(&2));
+    /// ```
+    #[must_use]
+    pub fn build_normalized<N>(self, normalize: N) -> NormalizedFrozenMap<K, V, N, BH>
+    where
+        N: Fn(&K) -> K,
+    {
+        NormalizedFrozenMap::from_vec_with_hasher(self.payload, normalize, self.bh)
+    }
+
+    /// Creates a frozen map which copies the text of all its string keys into a single buffer,
+    /// rather than keeping a separate allocation per key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMapBuilder;
+    ///
+    /// let mut builder = FrozenMapBuilder::new();
+    /// builder.push("alpha".to_string(), 1).push("beta".to_string(), 2);
+    ///
+    /// let map = builder.build_arena();
+    /// assert_eq!(map.arena_len(), 9);
+    /// assert_eq!(map.get("beta"), Some(&2));
+    /// ```
+    #[must_use]
+    pub fn build_arena(self) -> ArenaFrozenMap<V, BH>
+    where
+        K: AsRef<str>,
+    {
+        ArenaFrozenMap::from_vec_with_hasher(self.payload, self.bh)
+    }
+
+    /// Creates a frozen map which shares the bytes of common prefixes between its string keys,
+    /// for when memory usage matters more than lookup speed.
+    ///
+    /// When several entries hold the same key, the last one is kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the keys hold 4 GiB or more of bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMapBuilder;
+    ///
+    /// let mut builder = FrozenMapBuilder::new();
+    /// builder.push("/usr/bin", 1).push("/usr/lib", 2);
+    ///
+    /// let map = builder.build_compact();
+    /// assert_eq!(map.get("/usr/lib"), Some(&2));
+    /// ```
+    #[must_use]
+    pub fn build_compact(self) -> CompactFrozenMap<V>
+    where
+        K: AsRef<str>,
+    {
+        CompactFrozenMap::from_vec(self.payload)
+    }
+
+    /// Creates a frozen map which stores its string keys sorted, each as the bytes following
+    /// the prefix it shares with the key before it.
+    ///
+    /// When several entries hold the same key, the last one is kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the encoded keys take 4 GiB or more.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenMapBuilder;
+    ///
+    /// let mut builder = FrozenMapBuilder::new();
+    /// builder.push("/usr/bin", 1).push("/usr/lib", 2);
+    ///
+    /// let map = builder.build_front_coded();
+    /// assert_eq!(map.get("/usr/lib"), Some(&2));
+    /// ```
+    #[must_use]
+    pub fn build_front_coded(self) -> FrontCodedFrozenMap<V>
+    where
+        K: AsRef<str>,
+    {
+        FrontCodedFrozenMap::from_vec(self.payload)
+    }
+}
+
+impl<K, V> FrozenMapBuilder<K, V, RandomState> {
+    /// Creates a builder.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<K, V> Default for FrozenMapBuilder<K, V, RandomState> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, BH> Extend<(K, V)> for FrozenMapBuilder<K, V, BH> {
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        self.payload.extend(iter);
+    }
+}
@@ -0,0 +1,126 @@
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+
+use crate::facades::FrozenMap;
+
+/// A shared slot holding the current version of a frozen map, which can be replaced while
+/// readers use it.
+///
+/// Services commonly refresh a lookup table every few minutes: a background thread builds a
+/// new frozen map from fresh data, and the threads serving requests should pick it up without
+/// ever waiting on the builder. Reading the handle never takes a lock, and publishing a new map
+/// swaps it in atomically. Readers which loaded the previous map keep using it until they're
+/// done, at which point it's dropped.
+///
+/// This type is only available with the `arc-swap` feature.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::{FrozenMap, FrozenMapHandle};
+///
+/// let handle = FrozenMapHandle::new(FrozenMap::from([("timeout", 30)]));
+/// let before = handle.load();
+///
+/// handle.rebuild_from([("timeout", 60), ("retries", 3)]);
+///
+/// assert_eq!(handle.with(|m| m.get("timeout").copied()), Some(60));
+/// assert_eq!(before.get("timeout"), Some(&30));
+/// ```
+#[allow(clippy::module_name_repetitions)]
+pub struct FrozenMapHandle<K, V, BH = RandomState> {
+    current: ArcSwap<FrozenMap<K, V, BH>>,
+}
+
+impl<K, V, BH> FrozenMapHandle<K, V, BH> {
+    /// Creates a handle holding the given map.
+    #[must_use]
+    pub fn new(map: FrozenMap<K, V, BH>) -> Self {
+        Self {
+            current: ArcSwap::from_pointee(map),
+        }
+    }
+
+    /// Returns the current map, which stays valid for as long as it's held, even after a new map
+    /// is published.
+    #[must_use]
+    pub fn load(&self) -> Arc<FrozenMap<K, V, BH>> {
+        self.current.load_full()
+    }
+
+    /// Calls a function with the current map.
+    ///
+    /// This is cheaper than [`load`](Self::load) for short lookups, as it doesn't touch the
+    /// reference count of the map.
+    pub fn with<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&FrozenMap<K, V, BH>) -> R,
+    {
+        f(&self.current.load())
+    }
+
+    /// Replaces the current map, returning the previous one.
+    pub fn publish(&self, map: FrozenMap<K, V, BH>) -> Arc<FrozenMap<K, V, BH>> {
+        self.current.swap(Arc::new(map))
+    }
+}
+
+impl<K, V, BH> FrozenMapHandle<K, V, BH>
+where
+    K: Hash + Eq,
+    BH: BuildHasher,
+{
+    /// Builds a new map from the given entries, using the given hash builder to hash keys, and
+    /// publishes it, returning the previous map.
+    ///
+    /// The map is built before the current one is replaced, so readers keep using the previous
+    /// map until the new one is ready.
+    pub fn rebuild_from_with_hasher<T>(&self, iter: T, bh: BH) -> Arc<FrozenMap<K, V, BH>>
+    where
+        T: IntoIterator<Item = (K, V)>,
+    {
+        self.publish(FrozenMap::from_iter_with_hasher(iter, bh))
+    }
+}
+
+impl<K, V> FrozenMapHandle<K, V, RandomState>
+where
+    K: Hash + Eq,
+{
+    /// Builds a new map from the given entries and publishes it, returning the previous map.
+    ///
+    /// The map is built before the current one is replaced, so readers keep using the previous
+    /// map until the new one is ready.
+    pub fn rebuild_from<T>(&self, iter: T) -> Arc<FrozenMap<K, V, RandomState>>
+    where
+        T: IntoIterator<Item = (K, V)>,
+    {
+        self.rebuild_from_with_hasher(iter, RandomState::new())
+    }
+}
+
+impl<K, V, BH> Default for FrozenMapHandle<K, V, BH> {
+    fn default() -> Self {
+        Self::new(FrozenMap::empty())
+    }
+}
+
+impl<K, V, BH> From<FrozenMap<K, V, BH>> for FrozenMapHandle<K, V, BH> {
+    fn from(map: FrozenMap<K, V, BH>) -> Self {
+        Self::new(map)
+    }
+}
+
+impl<K, V, BH> Debug for FrozenMapHandle<K, V, BH>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.with(|map| map.fmt(f))
+    }
+}
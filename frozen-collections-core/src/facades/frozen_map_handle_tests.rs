@@ -0,0 +1,58 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use crate::facades::{FrozenMap, FrozenMapHandle};
+
+#[test]
+fn publish_and_load() {
+    let handle = FrozenMapHandle::default();
+    assert!(handle.load().is_empty());
+
+    let previous = handle.publish((0..100).map(|i| (i, i)).collect());
+    assert!(previous.is_empty());
+    assert_eq!(Some(42), handle.with(|m| m.get(&42).copied()));
+
+    let held = handle.load();
+    let previous = handle.rebuild_from((0..200).map(|i| (i, i * 2)));
+    assert!(Arc::ptr_eq(&held, &previous));
+    assert_eq!(Some(&42), held.get(&42));
+    assert_eq!(Some(84), handle.with(|m| m.get(&42).copied()));
+    assert_eq!(200, handle.load().len());
+
+    let handle: FrozenMapHandle<_, _> = FrozenMap::from([(1, 'a')]).into();
+    assert_eq!("{1: 'a'}", format!("{handle:?}"));
+}
+
+#[test]
+fn readers_see_whole_maps() {
+    let handle = Arc::new(FrozenMapHandle::new((0..100).map(|i| (i, 0)).collect()));
+    let done = Arc::new(AtomicBool::new(false));
+
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let handle = Arc::clone(&handle);
+            let done = Arc::clone(&done);
+            thread::spawn(move || {
+                while !done.load(Ordering::Relaxed) {
+                    // every value of a map comes from the same generation
+                    handle.with(|m| {
+                        let generation = m.get(&0).copied();
+                        assert!(m.values().all(|v| Some(*v) == generation));
+                    });
+                }
+            })
+        })
+        .collect();
+
+    for generation in 1..50 {
+        _ = handle.rebuild_from((0..100).map(|i| (i, generation)));
+    }
+
+    done.store(true, Ordering::Relaxed);
+    for reader in readers {
+        reader.join().unwrap();
+    }
+
+    assert_eq!(Some(49), handle.with(|m| m.get(&99).copied()));
+}
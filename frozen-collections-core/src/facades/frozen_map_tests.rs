@@ -1,6 +1,14 @@
 use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hash, Hasher, RandomState};
+use std::time::Duration;
 
+use crate::errors::FrozenError;
 use crate::facades::frozen_map::FrozenMap;
+use crate::facades::{AnalysisOptions, MapStrategy};
+use crate::hashers::{NoHashBuilder, PassThroughHasher, SeededState};
+use crate::specialized_maps::{Cursor, DenseIntegerMap, IntegerRangeMap, MapStats, PerfectHashMap};
+use crate::traits::heap_size::HeapSize;
+use crate::traits::map::Map;
 
 #[test]
 fn test_empty_map() {
@@ -65,6 +73,20 @@ fn basic_u32_map() {
     }
 }
 
+#[test]
+fn test_get_key() {
+    let keys = ["A", "BB", "CCC", "DDDD", "EEEEE"].map(String::from);
+    let m = keys.iter().cloned().zip(0..).collect::<FrozenMap<_, _>>();
+
+    for key in &keys {
+        let canonical = m.get_key(key).unwrap();
+        assert_eq!(key, canonical);
+        assert!(!std::ptr::eq(key, canonical));
+    }
+
+    assert_eq!(None, m.get_key(&"F".to_string()));
+}
+
 #[test]
 fn test_iter() {
     let mut m = HashMap::new();
@@ -79,3 +101,1603 @@ fn test_iter() {
     iter.next();
     println!("{iter:?}");
 }
+
+#[test]
+fn test_emit_rust() {
+    let m = FrozenMap::from([(1u32, 10), (2, 20), (3, 30), (4, 40), (5, 50)]);
+    let code = m.emit_rust("M");
+    assert!(code.starts_with(
+        "static M: ::frozen_collections::specialized_maps::StaticMap<u32, i32> = ::frozen_collections::specialized_maps::StaticMap::from_raw_parts(\n    ::frozen_collections::specialized_maps::StaticKeyCode::Integer,\n"
+    ));
+    assert!(code.contains("        (3, 30),\n"));
+    assert!(code.ends_with("    ],\n);\n"));
+
+    let m = FrozenMap::from([
+        ("A00".to_string(), 1),
+        ("B00".to_string(), 2),
+        ("C00".to_string(), 3),
+        ("D00".to_string(), 4),
+    ]);
+    let code = m.emit_rust("M");
+    assert!(code.contains("StaticMap<&str, i32>"));
+    assert!(code.contains("StaticKeyCode::LeftSlice(0..1),\n"));
+    assert!(code.contains("        (\"C00\", 3),\n"));
+
+    // the slots follow the hash codes of the static map rather than those of this map
+    let m: FrozenMap<_, _> = (0..300).map(|i| (format!("{i:03}-suffix"), i)).collect();
+    let code = m.emit_rust("M");
+    assert!(code.contains("StaticKeyCode::LeftSlice(0..3),\n"));
+    assert!(code.contains("        (\"042-suffix\", 42),\n"));
+
+    let m: FrozenMap<_, _> = (0..2000).map(|i| (i.to_string(), i)).collect();
+    assert!(m.emit_rust("M").contains("StaticKeyCode::Hashed,\n"));
+}
+
+#[test]
+fn min_key_density() {
+    use crate::facades::FrozenMapBuilder;
+
+    let mut builder = FrozenMapBuilder::new();
+    builder.extend((0..20).map(|i| (i * 4, i)));
+
+    let m = builder.clone().build();
+    assert_eq!(MapStrategy::DenseInteger, m.strategy());
+    assert_eq!(Some(&19), m.get(&76));
+    assert_eq!(None, m.get(&75));
+
+    builder.min_key_density(50);
+    let m = builder.build();
+    assert_eq!(MapStrategy::Integer, m.strategy());
+    assert_eq!(Some(&19), m.get(&76));
+}
+
+#[test]
+fn test_closest_keys() {
+    let m = FrozenMap::from([
+        ("install".to_string(), 1),
+        ("uninstall".to_string(), 2),
+        ("update".to_string(), 3),
+        ("upgrade".to_string(), 4),
+        ("list".to_string(), 5),
+    ]);
+
+    assert_eq!(vec!["install"], m.closest_keys("instal", 2, 5));
+    assert_eq!(vec!["update", "upgrade"], m.closest_keys("updade", 3, 5));
+    assert_eq!(vec!["update"], m.closest_keys("updade", 3, 1));
+    assert_eq!(vec!["list"], m.closest_keys("list", 0, 5));
+    assert!(m.closest_keys("remove", 1, 5).is_empty());
+    assert!(m.closest_keys("install", 2, 0).is_empty());
+}
+
+#[test]
+fn test_pagination() {
+    for len in [0, 3, 10, 300, 1000] {
+        let m = (0..len)
+            .map(|i: u32| (format!("KEY{i:04}"), i))
+            .collect::<FrozenMap<_, _>>();
+
+        let mut seen = Vec::new();
+        let mut cursor = Cursor::default();
+        loop {
+            let mut page = m.iter_at(cursor);
+            let before = seen.len();
+            seen.extend(page.by_ref().take(7).map(|(_, v)| *v));
+            if seen.len() == before {
+                break;
+            }
+
+            cursor = Cursor::new(page.cursor().position());
+        }
+
+        assert_eq!(m.values().copied().collect::<Vec<_>>(), seen);
+
+        if let Some((k, _)) = m.iter().nth(2) {
+            assert_eq!(
+                m.iter().skip(2).collect::<Vec<_>>(),
+                m.iter_from(k).unwrap().collect::<Vec<_>>()
+            );
+        }
+
+        assert!(m.iter_from(&"MISSING".to_string()).is_none());
+    }
+}
+
+#[test]
+fn sorted_exports() {
+    for size in [3u32, 10, 300] {
+        // evens avoid the range map, while the second map is a contiguous range
+        let sparse: Vec<_> = (0..size).rev().map(|i| (i * 2, i)).collect();
+        let dense: Vec<_> = (0..size).rev().map(|i| (i, i)).collect();
+
+        for payload in [sparse, dense] {
+            let m = FrozenMap::from_vec(payload.clone());
+
+            let mut copied = m.to_vec();
+            copied.sort_unstable();
+            let mut expected = payload.clone();
+            expected.sort_unstable();
+            assert_eq!(expected, copied);
+
+            assert_eq!(expected, m.clone().into_sorted_vec());
+            assert_eq!(payload, m.into_sorted_by(|x, y| y.1.cmp(&x.1)));
+        }
+    }
+
+    let payload: Vec<_> = (0..300).map(|i| (format!("Key-{i:03}"), i)).collect();
+    let m = FrozenMap::from_vec(payload.clone());
+    assert_eq!(payload, m.into_sorted_vec());
+
+    let m = FrozenMap::from_vec(vec![
+        ((1, 2), 'a'),
+        ((0, 5), 'b'),
+        ((1, 0), 'c'),
+        ((3, 3), 'd'),
+    ]);
+    let keys: Vec<_> = m.into_sorted_vec().into_iter().map(|x| x.0).collect();
+    assert_eq!(vec![(0, 5), (1, 0), (1, 2), (3, 3)], keys);
+}
+
+#[test]
+#[cfg(feature = "zeroize")]
+fn zeroize() {
+    use zeroize::Zeroize;
+
+    let mut maps = vec![
+        FrozenMap::from_vec((0..3).map(|i| (format!("Key-{i:03}"), i)).collect()),
+        FrozenMap::from_vec((0..300).map(|i| (format!("Key-{i:03}"), i)).collect()),
+        FrozenMap::from_vec((0..300).map(|i| (format!("{i}"), i)).collect()),
+    ];
+
+    for m in &mut maps {
+        m.zeroize();
+        assert!(m.is_empty());
+        assert_eq!(0, m.iter().count());
+        assert_eq!(None, m.get(&"Key-001".to_string()));
+        assert_eq!(None, m.get(&String::new()));
+    }
+
+    for payload in [
+        (0..300u32).map(|i| (i * 2, i)).collect::<Vec<_>>(),
+        (0..300u32).map(|i| (i, i)).collect::<Vec<_>>(),
+    ] {
+        let mut m = FrozenMap::from_vec(payload);
+        m.zeroize();
+        assert!(m.is_empty());
+        assert_eq!(None, m.get(&0));
+        assert_eq!(None, m.get(&2));
+    }
+}
+
+#[test]
+fn empty() {
+    let m = FrozenMap::<String, i32>::empty();
+    assert!(m.is_empty());
+    assert_eq!(None, m.get(&String::new()));
+    assert_eq!(0, m.iter().count());
+
+    let m: FrozenMap<u32, i32> = FrozenMap::default();
+    assert_eq!(None, m.get(&0));
+    assert_eq!(m, FrozenMap::from_vec(Vec::new()));
+}
+
+#[test]
+fn borrowed_lookups() {
+    let payloads = [
+        vec!["a".to_string(), "b".to_string()],
+        vec![
+            "a".to_string(),
+            "bb".to_string(),
+            "ccc".to_string(),
+            "dddd".to_string(),
+        ],
+        (0..10).map(|i| format!("Key-{i:03}")).collect(),
+        (0..300).map(|i| format!("Key-{i:03}")).collect(),
+        (0..300).map(|i| format!("{i:03}-Key")).collect(),
+    ];
+
+    for keys in payloads {
+        let mut m: FrozenMap<_, _> = keys.iter().cloned().zip(0..).collect();
+
+        for (i, key) in keys.iter().enumerate() {
+            let k: &str = key;
+            assert_eq!(Some(&i), m.get(k));
+            assert_eq!(Some(&i), m.get(key));
+            assert_eq!(Some((key, &i)), m.get_key_value(k));
+            assert_eq!(Some(key), m.get_key(k));
+            assert!(m.contains_key(k));
+            *m.get_mut(k).unwrap() += 1;
+            assert_eq!(Some(&(i + 1)), m.get(k));
+        }
+
+        assert_eq!(None, m.get("missing"));
+        assert!(!m.contains_key(""));
+    }
+
+    let m: FrozenMap<_, _> = (0..300u32).map(|i| (i, i)).collect();
+    assert_eq!(Some(&7), m.get(&7));
+    assert_eq!(None, m.get(&300));
+}
+
+#[test]
+fn integer_keys() {
+    fn check<K>(keys: impl IntoIterator<Item = K>, expected: &MapStrategy)
+    where
+        K: Copy + std::hash::Hash + Eq + std::fmt::Debug,
+    {
+        let keys: Vec<K> = keys.into_iter().collect();
+        let mut m: FrozenMap<_, _> = keys.iter().copied().zip(0..).collect();
+        assert_eq!(*expected, m.strategy());
+        assert_eq!(keys.len(), m.len());
+
+        for (i, key) in keys.iter().enumerate() {
+            assert_eq!(Some(&i), m.get(key));
+            assert_eq!(Some((key, &i)), m.get_key_value(key));
+            *m.get_mut(key).unwrap() += 1;
+            assert_eq!(Some(&(i + 1)), m.get(key));
+        }
+
+        let entries = m.into_sorted_by(|x, y| x.1.cmp(&y.1));
+        assert_eq!(keys, entries.iter().map(|x| x.0).collect::<Vec<_>>());
+    }
+
+    check(0..10u8, &MapStrategy::IntegerRange);
+    check(0..=u8::MAX, &MapStrategy::IntegerRange);
+    check(-5..5i8, &MapStrategy::IntegerRange);
+    check([-100i8, -1, 0, 100], &MapStrategy::IntegerScanning);
+    check((0..10u16).map(|i| i * 7), &MapStrategy::IntegerScanning);
+    check((0..20u16).map(|i| i * 7), &MapStrategy::Integer);
+    check((-300..300i16).map(|i| i * 3), &MapStrategy::DenseInteger);
+    check((-300..300i16).map(|i| i * 5), &MapStrategy::Integer);
+    check(0..300u32, &MapStrategy::IntegerRange);
+    check((0..300i32).map(|i| i * -11), &MapStrategy::Integer);
+    check((0..10u64).map(|i| i << 40), &MapStrategy::IntegerScanning);
+    check((0..20u64).map(|i| i << 40), &MapStrategy::Integer);
+    check([i64::MIN, -1, 0, i64::MAX], &MapStrategy::IntegerScanning);
+    check([u128::MAX, 1, 2, 3], &MapStrategy::IntegerScanning);
+    check(
+        std::iter::once(u128::MAX).chain(1..=20),
+        &MapStrategy::Integer,
+    );
+    check(
+        std::iter::once(u64::MAX.into()).chain(1..=20u128),
+        &MapStrategy::DenseInteger,
+    );
+    check([u128::MAX, 1, 2, 1 << 40], &MapStrategy::IntegerScanning);
+    check([0, 1 << 64, 2, 3u128], &MapStrategy::IntegerScanning);
+    check((0..20u128).map(|i| i << 60), &MapStrategy::Integer);
+    check((0..20u128).map(|i| i << 64), &MapStrategy::Integer);
+    check(-2..2i128, &MapStrategy::IntegerRange);
+    check(0..10usize, &MapStrategy::IntegerRange);
+    check(
+        [isize::MIN, 0, 5, isize::MAX],
+        &MapStrategy::IntegerScanning,
+    );
+
+    let m: FrozenMap<_, _> = (0..10i64).map(|i| (i, i)).collect();
+    assert_eq!(None, m.get(&-1));
+    assert_eq!(None, m.get(&10));
+}
+
+#[test]
+fn custom_keys() {
+    #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+    struct UserId(u32);
+
+    #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+    enum Color {
+        Red,
+        Green,
+        Blue,
+        Yellow,
+    }
+
+    let m: FrozenMap<_, _> = (0..300).map(|i| (UserId(i * 3), i)).collect();
+    assert_eq!(MapStrategy::DenseInteger, m.strategy());
+    assert_eq!(Some(&7), m.get(&UserId(21)));
+    assert_eq!(None, m.get(&UserId(22)));
+
+    let m: FrozenMap<_, _> = (0..300).map(|i| (UserId(i), i)).collect();
+    assert_eq!(MapStrategy::IntegerRange, m.strategy());
+    assert_eq!(Some(&299), m.get(&UserId(299)));
+    assert_eq!(None, m.get(&UserId(300)));
+
+    let colors = [Color::Red, Color::Green, Color::Blue, Color::Yellow];
+    let m: FrozenMap<_, _> = colors.iter().copied().zip(0..).collect();
+    assert_eq!(MapStrategy::IntegerRange, m.strategy());
+    for (i, color) in colors.iter().enumerate() {
+        assert_eq!(Some(&i), m.get(color));
+    }
+
+    let m = FrozenMap::from([("A00", 1), ("B00", 2), ("C00", 3), ("D00", 4)]);
+    assert_eq!(MapStrategy::LeftSlice { range: 0..1 }, m.strategy());
+    assert_eq!(Some(&3), m.get("C00"));
+    assert_eq!(None, m.get("E00"));
+
+    let keys: Vec<Vec<u8>> = (0..10u8).map(|i| vec![b'x'; usize::from(i) + 1]).collect();
+    let m: FrozenMap<_, _> = keys.iter().cloned().zip(0..).collect();
+    assert_eq!(MapStrategy::Length, m.strategy());
+    for (i, key) in keys.iter().enumerate() {
+        assert_eq!(Some(&i), m.get(key.as_slice()));
+    }
+    assert_eq!(None, m.get(&b"y"[..]));
+
+    // variable-length keys which only differ near their end
+    let keys: Vec<String> = (0..100)
+        .map(|i| format!("{}-{i:02}", "x".repeat(i % 3 + 1)))
+        .collect();
+    let m: FrozenMap<_, _> = keys.iter().cloned().zip(0..).collect();
+    assert!(matches!(
+        m.strategy(),
+        MapStrategy::LeftSlice { .. }
+            | MapStrategy::RightSlice { .. }
+            | MapStrategy::SplitSlice { .. }
+            | MapStrategy::LengthSlice { .. }
+    ));
+    for (i, key) in keys.iter().enumerate() {
+        assert_eq!(Some(&i), m.get(key.as_str()));
+    }
+    assert_eq!(None, m.get("x"));
+    assert_eq!(None, m.get("x-100"));
+
+    // signed ranges which cross zero wrap around in the key codes
+    let m: FrozenMap<_, _> = (-150..150i64).map(|i| (i, i)).collect();
+    assert_eq!(MapStrategy::IntegerRange, m.strategy());
+    assert_eq!(Some(&-150), m.get(&-150));
+    assert_eq!(Some(&149), m.get(&149));
+    assert_eq!(None, m.get(&150));
+    assert_eq!(None, m.get(&-151));
+}
+
+#[test]
+fn seeded_layout() {
+    fn check(payload: &[(String, usize)]) {
+        let a = FrozenMap::from_vec_with_seed(payload.to_vec(), 42);
+        let b = FrozenMap::from_vec_with_seed(payload.to_vec(), 42);
+        assert_eq!(a.emit_rust("m"), b.emit_rust("m"));
+        assert!(a.iter().eq(b.iter()));
+        assert_eq!(Some(&0), a.get(&payload[0].0));
+    }
+
+    // keys telling themselves apart by a subslice, keys with no pattern, and enough keys
+    // for a perfect hash
+    check(
+        &(0..100)
+            .map(|i| (format!("key{i:03}"), i))
+            .collect::<Vec<_>>(),
+    );
+    check(
+        &(0..100)
+            .map(|i| ("x".repeat(i % 7) + &i.to_string(), i))
+            .collect::<Vec<_>>(),
+    );
+    check(
+        &(0..2000)
+            .map(|i| (i.to_string().repeat(3), i))
+            .collect::<Vec<_>>(),
+    );
+
+    let payload: Vec<_> = (0..2000).map(|i| (i.to_string(), i)).collect();
+    let a = FrozenMap::from_vec_with_hasher(payload.clone(), SeededState::new(1));
+    let b = FrozenMap::from_vec_with_hasher(payload, SeededState::new(2));
+    assert!(!a.keys().eq(b.keys()));
+}
+
+#[test]
+fn prehashed_keys() {
+    // a stand-in for the hasher of the nohash-hasher crate, which only accepts a single integer
+    #[derive(Default)]
+    struct NoHashHasher(Option<u64>);
+
+    impl Hasher for NoHashHasher {
+        fn finish(&self) -> u64 {
+            self.0.unwrap()
+        }
+
+        fn write(&mut self, _: &[u8]) {
+            panic!("only integers can be hashed");
+        }
+
+        fn write_u64(&mut self, i: u64) {
+            assert!(self.0.replace(i).is_none());
+        }
+    }
+
+    impl PassThroughHasher for BuildHasherDefault<NoHashHasher> {}
+
+    // digests which share their first bytes, and so have a subslice telling them apart
+    let digests: Vec<[u8; 32]> = (0..300u64)
+        .map(|i| {
+            let mut digest = [7; 32];
+            digest[24..].copy_from_slice(&i.wrapping_mul(0x9e37_79b9_7f4a_7c15).to_le_bytes());
+            digest
+        })
+        .collect();
+
+    let m: FrozenMap<_, _, NoHashBuilder> = FrozenMap::from_vec_with_pass_through_hasher(
+        digests.iter().copied().zip(0..).collect(),
+        NoHashBuilder,
+    );
+    assert_eq!(MapStrategy::Common, m.strategy());
+    for (i, digest) in digests.iter().enumerate() {
+        assert_eq!(Some(&i), m.get(digest));
+    }
+    assert_eq!(None, m.get(&[7; 32]));
+
+    // other constructors spend time on hashing the subslice instead
+    let m = FrozenMap::from_vec_with_hasher(
+        digests.iter().copied().take(10).zip(0..).collect(),
+        NoHashBuilder,
+    );
+    assert!(matches!(
+        m.strategy(),
+        MapStrategy::LeftSlice { .. }
+            | MapStrategy::RightSlice { .. }
+            | MapStrategy::SplitSlice { .. }
+            | MapStrategy::LengthSlice { .. }
+    ));
+
+    let m = FrozenMap::from_vec_with_pass_through_hasher(
+        (0..100u64)
+            .map(|i| (i.wrapping_mul(0x9e37_79b9_7f4a_7c15), i))
+            .collect(),
+        BuildHasherDefault::<NoHashHasher>::default(),
+    );
+    assert_eq!(MapStrategy::Integer, m.strategy());
+    assert_eq!(Some(&3), m.get(&0x9e37_79b9_7f4a_7c15u64.wrapping_mul(3)));
+}
+
+#[test]
+fn try_from_vec() {
+    let payloads = [
+        (vec![(1, 1), (2, 2), (1, 3)], 2),
+        ((0..300).chain([150]).map(|i| (i, i)).collect(), 300),
+        ((0..300).chain([7, 9]).map(|i| (i * 3, i)).collect(), 300),
+    ];
+
+    for (payload, index) in payloads {
+        assert_eq!(
+            Some(FrozenError::DuplicateKey { index }),
+            FrozenMap::try_from_vec(payload).err()
+        );
+    }
+
+    let keys: Vec<_> = (0..300).map(|i| format!("Key-{i:03}")).collect();
+    let m = FrozenMap::try_from_vec(keys.iter().cloned().zip(0..).collect()).unwrap();
+    assert_eq!(Some(&7), m.get("Key-007"));
+
+    let payload = keys.iter().chain([&keys[42]]).cloned().zip(0..).collect();
+    assert_eq!(
+        Some(FrozenError::DuplicateKey { index: 300 }),
+        FrozenMap::<String, i32>::try_from_vec(payload).err()
+    );
+}
+
+#[test]
+fn duplicate_policy() {
+    use crate::facades::DuplicatePolicy;
+
+    let payload: Vec<_> = (0..300)
+        .map(|i| (format!("Key-{:03}", i % 100), i))
+        .collect();
+
+    let m = FrozenMap::from_vec_with_policy(payload.clone(), DuplicatePolicy::KeepFirst).unwrap();
+    assert_eq!(100, m.len());
+    assert_eq!(Some(&42), m.get("Key-042"));
+
+    let m = FrozenMap::from_vec_with_policy(payload.clone(), DuplicatePolicy::KeepLast).unwrap();
+    assert_eq!(100, m.len());
+    assert_eq!(Some(&242), m.get("Key-042"));
+
+    assert_eq!(
+        Some(crate::errors::FrozenError::DuplicateKey { index: 100 }),
+        FrozenMap::from_vec_with_policy(payload, DuplicatePolicy::Error).err()
+    );
+
+    let m = FrozenMap::from_vec_with_policy(vec![(1, 1), (2, 2)], DuplicatePolicy::Error).unwrap();
+    assert_eq!(2, m.len());
+}
+
+#[test]
+fn group_from_iter() {
+    #[derive(Default)]
+    struct Constant;
+
+    impl Hasher for Constant {
+        fn finish(&self) -> u64 {
+            0
+        }
+
+        fn write(&mut self, _: &[u8]) {}
+    }
+
+    let entries = (0..300).map(|i| (format!("Key-{:03}", i % 100), i));
+    let m = FrozenMap::group_from_iter(entries);
+    assert_eq!(100, m.len());
+    assert_eq!(Some(&[42, 142, 242][..]), m.get("Key-042").map(|v| &v[..]));
+    assert_eq!(None, m.get("Key-100"));
+
+    // colliding hash codes don't merge the values of different keys
+    let entries = [(1, 'a'), (2, 'b'), (1, 'c'), (3, 'd'), (2, 'e'), (1, 'f')];
+    let m =
+        FrozenMap::group_from_iter_with_hasher(entries, BuildHasherDefault::<Constant>::default());
+    assert_eq!(3, m.len());
+    assert_eq!(Some(&['a', 'c', 'f'][..]), m.get(&1).map(|v| &v[..]));
+    assert_eq!(Some(&['b', 'e'][..]), m.get(&2).map(|v| &v[..]));
+    assert_eq!(Some(&['d'][..]), m.get(&3).map(|v| &v[..]));
+
+    let m = FrozenMap::<u8, Box<[u8]>>::group_from_iter([]);
+    assert!(m.is_empty());
+}
+
+#[test]
+fn refreeze_with() {
+    // small changes keep the strategy
+    let m: FrozenMap<_, _> = (0..100).map(|i| (format!("{i:03}-suffix"), i)).collect();
+    let strategy = m.strategy();
+    let m = m.refreeze_with(
+        vec![
+            ("100-suffix".to_string(), 100),
+            ("005-suffix".to_string(), 500),
+        ],
+        ["007-suffix", "missing", "007-suffix"],
+    );
+    assert_eq!(strategy, m.strategy());
+    assert_eq!(100, m.len());
+    assert_eq!(Some(&100), m.get("100-suffix"));
+    assert_eq!(Some(&500), m.get("005-suffix"));
+    assert_eq!(None, m.get("007-suffix"));
+    assert_eq!(Some(&8), m.get("008-suffix"));
+
+    let m: FrozenMap<_, _> = (0..20u64).map(|i| (i << 40, i)).collect();
+    let m = m.refreeze_with(vec![(20 << 40, 20)], &[0]);
+    assert_eq!(MapStrategy::Integer, m.strategy());
+    assert_eq!(20, m.len());
+    assert_eq!(Some(&20), m.get(&(20 << 40)));
+    assert_eq!(None, m.get(&0));
+
+    // keys which no longer suit the strategy lead to a new analysis
+    let m: FrozenMap<_, _> = (1..=20).map(|i| ("x".repeat(i), i)).collect();
+    assert_eq!(MapStrategy::Length, m.strategy());
+    let additions = ["www", "yyy", "zzz"].map(|k| (k.to_string(), 0));
+    let m = m.refreeze_with(additions.to_vec(), [""]);
+    assert_ne!(MapStrategy::Length, m.strategy());
+    assert_eq!(23, m.len());
+    assert_eq!(Some(&0), m.get("zzz"));
+    assert_eq!(Some(&3), m.get("xxx"));
+
+    // large changes lead to a new analysis
+    let m: FrozenMap<_, _> = (0..100).map(|i| (format!("{i:03}-suffix"), i)).collect();
+    let m = m.refreeze_with(
+        (0..100).map(|i| (format!("prefix-{i:03}"), i)).collect(),
+        ["000-suffix"],
+    );
+    assert_ne!(MapStrategy::LeftSlice { range: 1..3 }, m.strategy());
+    assert_eq!(199, m.len());
+    assert_eq!(Some(&99), m.get("prefix-099"));
+
+    let m = FrozenMap::<String, i32>::default().refreeze_with(vec![("a".to_string(), 1)], [""]);
+    assert_eq!(Some(&1), m.get("a"));
+}
+
+#[test]
+fn perfect_hash() {
+    let m: FrozenMap<_, _> = (0..2000).map(|i| (i.to_string(), i)).collect();
+    assert_eq!(MapStrategy::PerfectHash, m.strategy());
+    assert_eq!(2000, m.len());
+
+    for i in 0..2000 {
+        assert_eq!(Some(&i), m.get(i.to_string().as_str()));
+    }
+
+    assert_eq!(None, m.get("2000"));
+
+    let m: FrozenMap<_, _> = (0..1000).map(|i| (i.to_string(), i)).collect();
+    assert_eq!(MapStrategy::Common, m.strategy());
+}
+
+#[test]
+fn strategy() {
+    let m = FrozenMap::from([("a", 1), ("b", 2)]);
+    assert_eq!(MapStrategy::Scanning, m.strategy());
+    assert_eq!(0, m.bucket_count());
+    assert_eq!(0, m.max_collisions());
+
+    let m: FrozenMap<_, _> = (0..10u32).map(|i| (i, i)).collect();
+    assert_eq!(MapStrategy::IntegerRange, m.strategy());
+
+    let m: FrozenMap<_, _> = (0..20u64).map(|i| (i << 40, i)).collect();
+    assert_eq!(MapStrategy::Integer, m.strategy());
+    assert!(m.bucket_count() >= m.len());
+    assert!(m.max_collisions() >= 1);
+
+    let m: FrozenMap<_, _> = (0..100).map(|i| (format!("{i:03}-suffix"), i)).collect();
+    assert_eq!(MapStrategy::LeftSlice { range: 1..3 }, m.strategy());
+
+    let m: FrozenMap<_, _> = (0..100)
+        .map(|i| (format!("{}{i:03}", "-".repeat(i % 7)), i))
+        .collect();
+    assert_eq!(MapStrategy::RightSlice { range: 0..2 }, m.strategy());
+    assert!(m.max_collisions() <= m.len());
+
+    let m: FrozenMap<_, _> = ["us", "eu", "ap", "ca"]
+        .iter()
+        .flat_map(|region| (0..50).map(move |i| format!("{region}-1/instances/reserved/{i:04}")))
+        .zip(0..)
+        .collect();
+    assert_eq!(
+        MapStrategy::SplitSlice {
+            left: 0..1,
+            right: 0..2
+        },
+        m.strategy()
+    );
+    assert_eq!(Some(&51), m.get("eu-1/instances/reserved/0001"));
+    assert_eq!(None, m.get("eu-1/instances/reserved/0050"));
+    assert_eq!(None, m.get("e"));
+    assert!(m
+        .emit_rust("M")
+        .contains("StaticKeyCode::SplitSlice(0..1, 0..2),\n"));
+
+    let m: FrozenMap<_, _> = ('a'..='t')
+        .flat_map(|c| (0..10).map(move |n| format!("{c}{}", "-".repeat(n + 5))))
+        .zip(0..)
+        .collect();
+    assert_eq!(MapStrategy::LengthSlice { range: 0..1 }, m.strategy());
+    assert_eq!(Some(&12), m.get("b-------"));
+    assert_eq!(None, m.get("b----"));
+    assert_eq!(None, m.get("u------"));
+    assert!(m
+        .emit_rust("M")
+        .contains("StaticKeyCode::LengthSlice(0..1),\n"));
+
+    let m: FrozenMap<_, _> = (1..=300).map(|i| ("x".repeat(i), i)).collect();
+    assert_eq!(MapStrategy::Length, m.strategy());
+    assert_eq!(Some(&300), m.get(&"x".repeat(300)));
+    assert_eq!(None, m.get(&"x".repeat(301)));
+    assert!(m.emit_rust("M").contains("StaticKeyCode::Length,\n"));
+
+    let m: FrozenMap<_, _> = (0..2000).map(|i| (i.to_string(), i)).collect();
+    assert_eq!(MapStrategy::PerfectHash, m.strategy());
+    assert_eq!(2000, m.bucket_count());
+    assert_eq!(1, m.max_collisions());
+}
+
+#[test]
+fn unchecked_lookups() {
+    fn check<K: Hash + Eq + Clone, M: Map<K, usize>>(map: &M, keys: &[K]) {
+        for (i, k) in keys.iter().enumerate() {
+            assert_eq!(&i, unsafe { map.get_unchecked(k) });
+        }
+    }
+
+    let maps: Vec<FrozenMap<String, usize>> = vec![
+        (0..3).map(|i| (format!("k{i}"), i)).collect(),
+        (0..100).map(|i| (format!("{i:03}-suffix"), i)).collect(),
+        (1..=300).map(|i| ("x".repeat(i), i - 1)).collect(),
+        (0..2000).map(|i| (i.to_string(), i)).collect(),
+    ];
+
+    for m in &maps {
+        let mut keys: Vec<_> = m.keys().cloned().collect();
+        keys.sort_by_key(|k| m[k.clone()]);
+        check(m, &keys);
+
+        for k in &keys {
+            assert_eq!(
+                m.get(k.as_str()),
+                Some(unsafe { m.get_unchecked(k.as_str()) })
+            );
+
+            let position = m.position(k.as_str()).unwrap();
+            assert_eq!(
+                m.entry_at(position),
+                Some(unsafe { m.index_unchecked(position) })
+            );
+        }
+    }
+
+    let maps: Vec<FrozenMap<u64, usize>> = vec![
+        (0..10u64).map(|i| i + 5).zip(0..).collect(),
+        (0..20u64).map(|i| i << 40).zip(0..).collect(),
+        (0..2000u64).map(|i| i * 3).zip(0..).collect(),
+        (0..300u64).map(|i| i * 2).zip(0..).collect(),
+    ];
+
+    for m in &maps {
+        let mut keys: Vec<_> = m.keys().copied().collect();
+        keys.sort_by_key(|k| m[*k]);
+        check(m, &keys);
+    }
+
+    let keys: Vec<u64> = (5..15).collect();
+    let m = IntegerRangeMap::from_vec(keys.iter().copied().zip(0..).collect());
+    check(&m, &keys);
+
+    let keys: Vec<u64> = (0..300).map(|i| i * 2).collect();
+    let m = DenseIntegerMap::from_vec(keys.iter().copied().zip(0..).collect());
+    check(&m, &keys);
+
+    let keys: Vec<String> = (0..2000).map(|i| i.to_string()).collect();
+    let m = PerfectHashMap::<_, _>::from_vec(keys.iter().cloned().zip(0..).collect());
+    check(&m, &keys);
+}
+
+#[test]
+fn prefilter() {
+    let payloads: Vec<Vec<(String, usize)>> = vec![
+        (0..500).map(|i| (format!("user-{i}"), i)).collect(),
+        (0..5000).map(|i| (format!("user-{i}"), i)).collect(),
+    ];
+
+    for payload in payloads {
+        let plain = FrozenMap::from_vec_with_hasher(payload.clone(), SeededState::new(7));
+        let options = AnalysisOptions::new().prefilter(true);
+        let filtered =
+            FrozenMap::from_vec_with_options(payload.clone(), SeededState::new(7), options);
+
+        assert!(!plain.has_prefilter());
+        assert!(filtered.has_prefilter());
+        assert_eq!(plain.strategy(), filtered.strategy());
+        assert!(filtered.stats().slot_bytes > plain.stats().slot_bytes);
+
+        for (k, v) in &payload {
+            assert_eq!(filtered.get(k.as_str()), Some(v));
+        }
+
+        for i in 0..payload.len() {
+            assert_eq!(filtered.get(format!("guest-{i}").as_str()), None);
+        }
+
+        let filtered = filtered.refreeze_with_hasher(
+            vec![("guest-1".to_string(), 1)],
+            ["user-1"],
+            SeededState::new(7),
+        );
+        assert!(filtered.has_prefilter());
+        assert_eq!(filtered.get("guest-1"), Some(&1));
+        assert_eq!(filtered.get("user-1"), None);
+    }
+
+    let m = FrozenMap::from_vec_with_options(
+        (0..1000u64).map(|i| (i * 7919, i)).collect(),
+        RandomState::new(),
+        AnalysisOptions::new().prefilter(true),
+    );
+    assert!(m.has_prefilter());
+    assert_eq!(m.get(&(3 * 7919)), Some(&3));
+    assert_eq!(m.get(&1), None);
+}
+
+#[test]
+fn skewed_hash_codes() {
+    // with 1000 slots, the keys land in only 5 of them unless they're displaced
+    let payload: Vec<_> = (0..1000u64).map(|i| (i * 1000 + i % 5, i)).collect();
+    let options = AnalysisOptions::new().target_load_factor(1.0);
+    let m = FrozenMap::from_vec_with_options(payload.clone(), RandomState::new(), options);
+
+    assert_eq!(MapStrategy::Integer, m.strategy());
+    assert_eq!(1000, m.bucket_count());
+    assert!(m.max_collisions() <= 3, "{}", m.max_collisions());
+
+    for (k, v) in &payload {
+        assert_eq!(Some(v), m.get(k));
+        assert_eq!(None, m.get(&(k + 5)));
+    }
+}
+
+#[test]
+fn stats() {
+    let m = FrozenMap::from([(1u64, 1u32), (5, 2)]);
+    let stats = m.stats();
+    assert_eq!(2, stats.len);
+    assert_eq!(0, stats.num_slots);
+    assert_eq!(2, stats.longest_probe_chain);
+    assert_eq!(16, stats.key_bytes);
+    assert_eq!(8, stats.value_bytes);
+    assert!(stats.load_factor().abs() < f64::EPSILON);
+
+    let m: FrozenMap<_, _> = (0..300u32).map(|i| (i, i)).collect();
+    let stats = m.stats();
+    assert_eq!(1, stats.longest_probe_chain);
+    assert_eq!(0, stats.slot_bytes);
+    assert_eq!(2400, stats.heap_bytes());
+
+    let m: FrozenMap<_, _> = (0..200).map(|i| (format!("key{i}"), i)).collect();
+    let stats = m.stats();
+    assert_eq!(m.bucket_count(), stats.num_slots);
+    assert_eq!(m.max_collisions(), stats.longest_probe_chain);
+    assert!(stats.load_factor() > 0.0 && stats.load_factor() <= 1.0);
+    assert!(stats.slot_bytes >= stats.num_slots * 2);
+
+    let m: FrozenMap<_, _> = (0..2000).map(|i| (i.to_string(), i)).collect();
+    let stats = m.stats();
+    assert!((stats.load_factor() - 1.0).abs() < f64::EPSILON);
+    assert_eq!(1, stats.longest_probe_chain);
+
+    assert_eq!(MapStats::default(), FrozenMap::<u32, u32>::empty().stats());
+}
+
+#[test]
+fn heap_bytes() {
+    let m: FrozenMap<_, _> = (0..300u32).map(|i| (i, i)).collect();
+    assert_eq!(m.stats().heap_bytes(), m.heap_bytes());
+
+    // keys of every shape, so that each kind of map accounts for its strings
+    for len in [3, 20, 500] {
+        for key in [
+            |i: usize| format!("{i}"),
+            |i: usize| format!("{i}-suffix"),
+            |i: usize| format!("prefix-{i}"),
+            |i: usize| "x".repeat(i + 1),
+        ] {
+            let payload: Vec<_> = (0..len).map(|i| (key(i), vec![i; i % 3])).collect();
+            let owned: usize = payload
+                .iter()
+                .map(|(k, v)| k.capacity() + v.capacity() * size_of::<usize>())
+                .sum();
+
+            let m = FrozenMap::from_vec(payload);
+            assert_eq!(m.stats().heap_bytes() + owned, m.heap_bytes());
+            assert_eq!(size_of_val(&m) + m.heap_bytes(), m.deep_size_of());
+        }
+    }
+
+    assert_eq!(0, FrozenMap::<String, String>::empty().heap_bytes());
+}
+
+#[test]
+fn analysis_options() {
+    let build = |payload: Vec<(String, usize)>, options| {
+        let m = FrozenMap::from_vec_with_options(payload.clone(), RandomState::new(), options);
+        for (key, value) in &payload {
+            assert_eq!(Some(value), m.get(key));
+        }
+
+        m.strategy()
+    };
+
+    let payload: Vec<_> = (0..100).map(|i| (format!("{i:03}-suffix"), i)).collect();
+    assert_eq!(
+        MapStrategy::LeftSlice { range: 1..3 },
+        build(payload.clone(), AnalysisOptions::new())
+    );
+    assert_eq!(
+        MapStrategy::Common,
+        build(payload.clone(), AnalysisOptions::new().slice_maps(false))
+    );
+    assert_eq!(
+        MapStrategy::Common,
+        build(payload, AnalysisOptions::new().max_duration(Duration::ZERO))
+    );
+
+    let payload: Vec<_> = ('a'..='e')
+        .enumerate()
+        .map(|(i, c)| (c.to_string().repeat(i + 1), i))
+        .collect();
+    assert_eq!(
+        MapStrategy::Length,
+        build(payload.clone(), AnalysisOptions::new())
+    );
+    assert_eq!(
+        MapStrategy::LeftSlice { range: 0..1 },
+        build(payload, AnalysisOptions::new().length_maps(false))
+    );
+
+    let payload: Vec<_> = (0..2000).map(|i| (i.to_string(), i)).collect();
+    assert_eq!(
+        MapStrategy::Common,
+        build(payload, AnalysisOptions::new().perfect_hash_maps(false))
+    );
+
+    let options = AnalysisOptions::new().integer_maps(false);
+    let m = FrozenMap::from_vec_with_options(
+        vec![(1, 1), (2, 2), (3, 3), (4, 4)],
+        RandomState::new(),
+        options,
+    );
+    assert_eq!(MapStrategy::Common, m.strategy());
+    assert_eq!(Some(&3), m.get(&3));
+
+    let options = AnalysisOptions::new().dense_integer_maps(false);
+    let payload: Vec<_> = (0..100).map(|i| (i * 2, i)).collect();
+    let m = FrozenMap::from_vec_with_options(payload, RandomState::new(), options);
+    assert_eq!(MapStrategy::Integer, m.strategy());
+
+    let options = AnalysisOptions::new().target_load_factor(2.0);
+    let payload: Vec<_> = (0..100).map(|i| (i * 1000, i)).collect();
+    let m = FrozenMap::from_vec_with_options(payload, RandomState::new(), options);
+    assert_eq!(50, m.bucket_count());
+    assert_eq!(Some(&7), m.get(&7000));
+}
+
+#[test]
+fn forced_strategy() {
+    let build = |payload: Vec<(String, usize)>, strategy: MapStrategy| {
+        let m = FrozenMap::try_from_vec_with_strategy(
+            payload.clone(),
+            strategy.clone(),
+            RandomState::new(),
+        )?;
+        assert_eq!(strategy, m.strategy());
+        for (key, value) in &payload {
+            assert_eq!(Some(value), m.get(key));
+        }
+
+        assert_eq!(None, m.get("missing"));
+        Ok::<_, FrozenError>(())
+    };
+
+    let payload: Vec<_> = (0..100).map(|i| (format!("id-{i:04}"), i)).collect();
+    for strategy in [
+        MapStrategy::Scanning,
+        MapStrategy::Common,
+        MapStrategy::PerfectHash,
+        MapStrategy::LeftSlice { range: 3..7 },
+        MapStrategy::LeftSlice { range: 0..2 },
+        MapStrategy::RightSlice { range: 0..4 },
+        MapStrategy::RightSlice { range: 10..20 },
+        MapStrategy::SplitSlice {
+            left: 0..2,
+            right: 0..4,
+        },
+        MapStrategy::SplitSlice {
+            left: 0..1,
+            right: 5..10,
+        },
+        MapStrategy::LengthSlice { range: 0..3 },
+        MapStrategy::LengthSlice { range: 6..12 },
+        MapStrategy::Length,
+    ] {
+        build(payload.clone(), strategy).unwrap();
+    }
+
+    let payload: Vec<_> = (0..300).map(|i| (i.to_string(), i)).collect();
+    build(payload.clone(), MapStrategy::RightSlice { range: 0..1 }).unwrap();
+    build(payload, MapStrategy::Length).unwrap();
+
+    assert_eq!(
+        Err(FrozenError::IncompatibleStrategy),
+        build(vec![("a".to_string(), 1)], MapStrategy::IntegerScanning)
+    );
+    assert_eq!(
+        Err(FrozenError::DuplicateKey { index: 1 }),
+        build(
+            vec![("a".to_string(), 1), ("a".to_string(), 2)],
+            MapStrategy::Common
+        )
+    );
+
+    let ints = |keys: &[u32], strategy: MapStrategy| {
+        let payload = keys.iter().map(|&k| (k, k)).collect();
+        let m = FrozenMap::try_from_vec_with_strategy(payload, strategy, RandomState::new())?;
+        for k in keys {
+            assert_eq!(Some(k), m.get(k));
+        }
+
+        Ok::<_, FrozenError>(m.strategy())
+    };
+
+    let keys: Vec<u32> = (10..20).collect();
+    for strategy in [
+        MapStrategy::Integer,
+        MapStrategy::IntegerRange,
+        MapStrategy::DenseInteger,
+        MapStrategy::IntegerScanning,
+        MapStrategy::Common,
+    ] {
+        assert_eq!(Ok(strategy.clone()), ints(&keys, strategy));
+    }
+
+    assert_eq!(
+        Err(FrozenError::NonContiguousRange),
+        ints(&[1, 3], MapStrategy::IntegerRange)
+    );
+    assert_eq!(
+        Err(FrozenError::IncompatibleStrategy),
+        ints(&[1, 2], MapStrategy::Length)
+    );
+}
+
+#[test]
+fn map_trait() {
+    use std::collections::{BTreeMap, HashMap};
+
+    use crate::specialized_maps::{OrderedMap, ScanningMap};
+    use crate::traits::map::Map;
+
+    fn check<M: Map<i32, i32>>(m: &M) {
+        assert_eq!(3, m.len());
+        assert_eq!(Some(&20), m.get(&2));
+        assert_eq!(None, m.get(&4));
+        assert!(m.contains_key(&3));
+        assert!(!m.contains_key(&0));
+        assert_eq!(6, m.keys().sum::<i32>());
+        assert_eq!(60, m.values().sum::<i32>());
+        assert_eq!(3, m.iter().filter(|(k, v)| **k * 10 == **v).count());
+    }
+
+    let payload = [(1, 10), (2, 20), (3, 30)];
+    check(&FrozenMap::from(payload));
+    check(&ScanningMap::from(payload));
+    check(&OrderedMap::from(payload));
+    check(&HashMap::from(payload));
+    check(&BTreeMap::from(payload));
+}
+
+#[test]
+fn std_conversions() {
+    use std::collections::BTreeMap;
+
+    let hashed: HashMap<_, _> = (0..100).map(|i| (format!("Key-{i:03}"), i)).collect();
+    let m = FrozenMap::from(hashed.clone());
+    assert_eq!(100, m.len());
+    assert_eq!(Some(&42), m.get("Key-042"));
+    assert_eq!(hashed, HashMap::from(m));
+
+    let ordered: BTreeMap<_, _> = (0..100).map(|i| (i, i * 2)).collect();
+    let m = FrozenMap::from(ordered.clone());
+    assert_eq!(100, m.len());
+    assert_eq!(Some(&84), m.get(&42));
+    assert_eq!(ordered, BTreeMap::from(m));
+}
+
+#[test]
+fn std_equality() {
+    let hashed: HashMap<_, _> = (0..100).map(|i| (format!("Key-{i:03}"), i)).collect();
+    let m = FrozenMap::from(hashed.clone());
+    assert_eq!(m, hashed);
+    assert_eq!(hashed, m);
+
+    let mut changed = hashed.clone();
+    changed.insert("Key-042".to_string(), 0);
+    assert_ne!(m, changed);
+    assert_ne!(changed, m);
+
+    changed.insert("Key-042".to_string(), 42);
+    changed.insert("Key-100".to_string(), 100);
+    assert_ne!(m, changed);
+
+    let seeded: HashMap<_, _, SeededState> = hashed.into_iter().collect();
+    assert_eq!(m, seeded);
+}
+
+#[test]
+fn hash_matches_equality() {
+    use std::hash::BuildHasher;
+
+    let payload: Vec<_> = (0..300).map(|i| (format!("Key-{i:03}"), i)).collect();
+    let a = FrozenMap::from_vec(payload.clone());
+    let b = FrozenMap::from_vec_with_seed(payload.iter().rev().cloned().collect(), 7);
+    let c = FrozenMap::from_vec_with_options(
+        payload.clone(),
+        RandomState::new(),
+        AnalysisOptions::new().slice_maps(false).length_maps(false),
+    );
+
+    let hash = |m: &FrozenMap<String, i32>| SeededState::new(0).hash_one(m);
+    assert_eq!(hash(&a), SeededState::new(0).hash_one(&b));
+    assert_eq!(hash(&a), hash(&c));
+
+    let mut changed = payload;
+    changed[42].1 = 0;
+    assert_ne!(hash(&a), hash(&FrozenMap::from_vec(changed)));
+
+    let mut cache = HashMap::new();
+    cache.insert(a, "config");
+    assert_eq!(Some(&"config"), cache.get(&c));
+}
+
+#[test]
+fn byte_keys() {
+    use crate::specialized_maps::{LeftSliceMap, LengthMap, RightSliceMap};
+
+    let keys: Vec<Vec<u8>> = (0..100u8).map(|i| vec![i, b'-', b'x']).collect();
+    let m: FrozenMap<_, _> = keys.iter().cloned().zip(0..).collect();
+    let code = m.emit_rust("M");
+    assert!(matches!(m.strategy(), MapStrategy::LeftSlice { .. }));
+    assert!(code.contains("StaticMap<&[u8], i32>"));
+    assert!(code.contains("        (&[42, 45, 120], 42),\n"));
+    assert_eq!(Some(&42), m.get(&[42, b'-', b'x'][..]));
+    assert_eq!(None, m.get(&[42, b'-', b'y'][..]));
+
+    let keys: Vec<Box<[u8]>> = (0..100u8)
+        .map(|i| {
+            [vec![b'x'; usize::from(i % 3) + 1], vec![i]]
+                .concat()
+                .into()
+        })
+        .collect();
+    let m: FrozenMap<_, _> = keys.iter().cloned().zip(0..).collect();
+    let code = m.emit_rust("M");
+    assert!(matches!(m.strategy(), MapStrategy::RightSlice { .. }));
+    assert!(code.contains("StaticMap<&[u8], i32>"));
+    assert!(code.contains("        (&[120, 42], 42),\n"));
+    assert_eq!(Some(&43), m.get(&[b'x', b'x', 43][..]));
+    assert_eq!(None, m.get(&[b'x', 43][..]));
+
+    let keys: Vec<&[u8]> = vec![b"a", b"bb", b"ccc", b"dddd"];
+    let m: FrozenMap<_, _> = keys.iter().copied().zip(0..).collect();
+    let code = m.emit_rust("M");
+    assert_eq!(MapStrategy::Length, m.strategy());
+    assert!(code.contains("StaticMap<&[u8], i32>"));
+    assert!(code.contains("        (&[98, 98], 1),\n"));
+    assert_eq!(Some(&2), m.get(&b"ccc"[..]));
+    assert_eq!(None, m.get(&b"eee"[..]));
+
+    // the specialized maps can also be built directly from byte keys
+    let m = LeftSliceMap::<Vec<u8>, _>::from_vec(vec![(vec![1, 2], 1), (vec![2, 2], 2)], 0..1);
+    assert_eq!(Some(&2), m.get(&vec![2, 2]));
+    let m = RightSliceMap::<Box<[u8]>, _>::from_vec(vec![(Box::from(&[1, 2][..]), 1)], 0..1);
+    assert_eq!(Some(&1), m.get(&Box::from(&[1, 2][..])));
+    let m = LengthMap::<&[u8], _>::from_vec(vec![(&[1][..], 1), (&[1, 2][..], 2)]);
+    assert_eq!(Some(&2), m.get(&&[1, 2][..]));
+}
+
+#[test]
+fn shared_string_keys() {
+    use crate::specialized_maps::{LeftSliceMap, RightSliceMap};
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    let keys: Vec<Arc<str>> = (0..100).map(|i| Arc::from(format!("{i:02}-x"))).collect();
+    let m: FrozenMap<_, _> = keys.iter().cloned().zip(0..).collect();
+    let code = m.emit_rust("M");
+    assert!(matches!(m.strategy(), MapStrategy::LeftSlice { .. }));
+    assert!(code.contains("StaticMap<&str, i32>"));
+    assert!(code.contains("        (\"42-x\", 42),\n"));
+    assert_eq!(Some(&42), m.get("42-x"));
+    assert_eq!(None, m.get("42-y"));
+
+    let keys: Vec<Rc<str>> = (0..100)
+        .map(|i| Rc::from(format!("{}{i:02}", "x".repeat(i % 3 + 1))))
+        .collect();
+    let m: FrozenMap<_, _> = keys.iter().cloned().zip(0..).collect();
+    let code = m.emit_rust("M");
+    assert!(matches!(m.strategy(), MapStrategy::RightSlice { .. }));
+    assert!(code.contains("        (\"x42\", 42),\n"));
+    assert_eq!(Some(&43), m.get("xx43"));
+    assert_eq!(None, m.get("x43"));
+
+    let keys: Vec<Box<str>> = ["a", "bb", "ccc", "dddd"].map(Box::from).to_vec();
+    let m: FrozenMap<_, _> = keys.iter().cloned().zip(0..).collect();
+    let code = m.emit_rust("M");
+    assert_eq!(MapStrategy::Length, m.strategy());
+    assert!(code.contains("        (\"bb\", 1),\n"));
+    assert_eq!(Some(&2), m.get("ccc"));
+
+    // the specialized maps can also be built directly from shared keys
+    let m = LeftSliceMap::<Arc<str>, _>::from_vec(vec![(Arc::from("ab"), 1)], 0..1);
+    assert_eq!(Some(&1), m.get(&Arc::from("ab")));
+    let m = RightSliceMap::<Rc<str>, _>::from_vec(vec![(Rc::from("ab"), 1)], 0..1);
+    assert_eq!(Some(&1), m.get(&Rc::from("ab")));
+}
+
+#[test]
+fn cow_str_keys() {
+    use crate::specialized_maps::{LeftSliceMap, LengthMap};
+    use std::borrow::Cow;
+
+    // mostly borrowed keys, with a few computed ones mixed in
+    let keys: Vec<Cow<str>> = (0..100)
+        .map(|i| {
+            if i % 10 == 0 {
+                Cow::Owned(format!("{i:02}-x"))
+            } else {
+                Cow::Borrowed(&*String::leak(format!("{i:02}-x")))
+            }
+        })
+        .collect();
+    let m: FrozenMap<_, _> = keys.iter().cloned().zip(0..).collect();
+    let code = m.emit_rust("M");
+    assert!(matches!(m.strategy(), MapStrategy::LeftSlice { .. }));
+    assert!(code.contains("        (\"40-x\", 40),\n"));
+    assert!(code.contains("        (\"42-x\", 42),\n"));
+    assert_eq!(Some(&42), m.get("42-x"));
+    assert_eq!(Some(&40), m.get("40-x"));
+    assert_eq!(Some(&40), m.get(&Cow::Borrowed("40-x")));
+    assert_eq!(None, m.get("42-y"));
+
+    let m: FrozenMap<Cow<str>, _> = [
+        Cow::Borrowed("a"),
+        Cow::Owned("bb".to_string()),
+        Cow::Borrowed("ccc"),
+        Cow::Borrowed("dddd"),
+    ]
+    .into_iter()
+    .zip(0..)
+    .collect();
+    assert_eq!(MapStrategy::Length, m.strategy());
+    assert_eq!(Some(&1), m.get("bb"));
+    assert_eq!(None, m.get("ee"));
+
+    // the specialized maps can also be built directly from cow keys
+    let m = LeftSliceMap::<Cow<str>, _>::from_vec(vec![(Cow::Borrowed("ab"), 1)], 0..1);
+    assert_eq!(Some(&1), m.get(&Cow::Owned("ab".to_string())));
+    let m = LengthMap::<Cow<str>, _>::from_vec(vec![(Cow::Borrowed("a"), 1)]);
+    assert_eq!(Some(&1), m.get(&Cow::Borrowed("a")));
+}
+
+#[test]
+fn borrowed_str_keys() {
+    static WORDS: [&str; 6] = ["alpha", "bravo", "charlie", "delta", "echo", "foxtrot"];
+
+    // the keys found in the map live as long as the table, not just as long as the map
+    fn find<'a>(m: &FrozenMap<&'a str, usize>, key: &str) -> Option<&'a str> {
+        m.get_key(key).copied()
+    }
+
+    let m: FrozenMap<&'static str, usize> = WORDS.iter().copied().zip(0..).collect();
+    assert_eq!(MapStrategy::Length, m.strategy());
+
+    for (i, word) in WORDS.iter().enumerate() {
+        assert_eq!(Some(&i), m.get(*word));
+        assert_eq!(Some(&i), m.get(word));
+        assert_eq!(Some(*word), find(&m, word));
+    }
+    assert_eq!(None, m.get("golf"));
+
+    let m: FrozenMap<&'static str, usize> = (0..100)
+        .map(|i| &*String::leak(format!("{i:02}-x")))
+        .zip(0..)
+        .collect();
+    assert!(matches!(m.strategy(), MapStrategy::LeftSlice { .. }));
+    assert_eq!(Some(&42), m.get("42-x"));
+    assert_eq!(None, m.get("42-y"));
+
+    let m: FrozenMap<&'static str, usize> = (0..100)
+        .map(|i| &*String::leak(format!("{}{i:02}", "x".repeat(i % 3 + 1))))
+        .zip(0..)
+        .collect();
+    assert!(matches!(m.strategy(), MapStrategy::RightSlice { .. }));
+    assert_eq!(Some(&43), m.get("xx43"));
+    assert_eq!(None, m.get("x43"));
+
+    let keys: Vec<&'static str> = {
+        let m: FrozenMap<_, _> = WORDS.iter().map(|w| (*w, w.len())).collect();
+        let mut keys: Vec<_> = m.keys().copied().collect();
+        keys.sort_unstable();
+        assert_eq!(
+            keys,
+            m.into_sorted_vec()
+                .into_iter()
+                .map(|x| x.0)
+                .collect::<Vec<_>>()
+        );
+        keys
+    };
+    assert_eq!(WORDS.to_vec(), keys);
+}
+
+#[test]
+fn equivalent_keys() {
+    use crate::traits::equivalent::Equivalent;
+
+    #[derive(Hash, PartialEq, Eq)]
+    struct Query<'a>(&'a str, u32);
+
+    impl Equivalent<(String, u32)> for Query<'_> {
+        fn equivalent(&self, key: &(String, u32)) -> bool {
+            self.0 == key.0 && self.1 == key.1
+        }
+    }
+
+    for len in [2, 10, 300, 2000] {
+        let mut m: FrozenMap<_, _> = (0..len).map(|i| ((format!("k{i}"), i), i)).collect();
+        for i in 0..len {
+            let name = format!("k{i}");
+            assert_eq!(Some(&i), m.get(&Query(&name, i)));
+            assert_eq!(
+                Some((&(name.clone(), i), &i)),
+                m.get_key_value(&Query(&name, i))
+            );
+            assert!(m.contains_key(&Query(&name, i)));
+            assert!(!m.contains_key(&Query(&name, i + 1)));
+            *m.get_mut(&Query(&name, i)).unwrap() += 1;
+        }
+
+        assert_eq!(
+            [Some(&1), None],
+            m.get_many([&Query("k0", 0), &Query("k0", 1)])
+        );
+    }
+}
+
+#[test]
+fn get_many() {
+    use std::borrow::Borrow;
+    use std::hash::Hash;
+
+    fn check<K, Q>(m: &FrozenMap<K, usize>, keys: [&Q; 4])
+    where
+        K: Hash + Eq + Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        assert_eq!(keys.map(|key| m.get(key)), m.get_many(keys));
+    }
+
+    // covers scanning, slice, length, common and perfect hash maps
+    for len in [3, 10, 100, 500, 2000] {
+        let m: FrozenMap<_, _> = (0..len).map(|i| (format!("k{i:04}"), i)).collect();
+        check(&m, ["k0000", "k0002", "missing", "k0001"]);
+
+        let m: FrozenMap<_, _> = (0..len).map(|i| ("x".repeat(i + 1), i)).collect();
+        check(&m, ["x", "xxx", "", "xx"]);
+
+        let m: FrozenMap<_, _> = (0..len).map(|i| (i * 7, i)).collect();
+        check(&m, [&0, &7, &8, &14]);
+
+        let m: FrozenMap<_, _> = (0..len).map(|i| (i, i)).collect();
+        check(&m, [&0, &2, &len, &1]);
+    }
+
+    let m = FrozenMap::<String, usize>::default();
+    check(&m, ["a", "b", "c", "d"]);
+}
+
+#[test]
+fn contains_all_and_any_keys() {
+    // covers scanning, slice, length, common, perfect hash and integer maps
+    for len in [3, 10, 100, 500, 2000] {
+        let m: FrozenMap<_, _> = (0..len).map(|i| (format!("k{i:04}"), i)).collect();
+        assert!(m.contains_all_keys(["k0000", "k0002", "k0001"]));
+        assert!(!m.contains_all_keys(["k0000", "missing"]));
+        assert!(m.contains_any_key(["missing", "k0001"]));
+        assert!(!m.contains_any_key(["missing", "k"]));
+
+        let m: FrozenMap<_, _> = (0..len).map(|i| ("x".repeat(i + 1), i)).collect();
+        assert!(m.contains_all_keys(["x", "xxx"]));
+        assert!(!m.contains_any_key(["", "y"]));
+
+        let m: FrozenMap<_, _> = (0..len).map(|i| (i * 7, i)).collect();
+        assert!(m.contains_all_keys(&[0, 7, 14]));
+        assert!(!m.contains_all_keys(&[0, 8]));
+        assert!(m.contains_any_key(&[8, 14]));
+
+        let m: FrozenMap<_, _> = (0..len).map(|i| (i, i)).collect();
+        assert!(m.contains_all_keys(&[0, 2, 1]));
+        assert!(!m.contains_any_key(&[len, len + 1]));
+    }
+
+    let m = FrozenMap::<String, usize>::default();
+    assert!(m.contains_all_keys(Vec::<&str>::new()));
+    assert!(!m.contains_any_key(["a"]));
+}
+
+#[test]
+fn scanning_without_hash() {
+    #[derive(Debug, PartialEq, Eq)]
+    struct Celsius(i32);
+
+    let m = FrozenMap::scanning(vec![(Celsius(0), "freezing"), (Celsius(100), "boiling")]);
+    assert_eq!(2, m.len());
+    assert_eq!(Some(&"freezing"), m.get_by_scan(&Celsius(0)));
+    assert_eq!(Some(&"boiling"), m.get_by_scan(&Celsius(100)));
+    assert_eq!(None, m.get_by_scan(&Celsius(37)));
+    assert!(m.contains_key_by_scan(&Celsius(100)));
+    assert!(!m.contains_key_by_scan(&Celsius(-40)));
+    assert_eq!(
+        vec![(&Celsius(0), &"freezing"), (&Celsius(100), &"boiling")],
+        m.iter().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        "{Celsius(0): \"freezing\", Celsius(100): \"boiling\"}",
+        format!("{m:?}")
+    );
+
+    let m = FrozenMap::scanning(vec![(Celsius(1), 1), (Celsius(2), 2), (Celsius(1), 3)]);
+    assert_eq!(2, m.len());
+    assert_eq!(Some(&3), m.get_by_scan(&Celsius(1)));
+
+    let m = FrozenMap::<Celsius, i32>::scanning(vec![]);
+    assert!(m.is_empty());
+    assert_eq!(None, m.get_by_scan(&Celsius(0)));
+    assert_eq!(0, m.into_iter().count());
+
+    // hashable keys can be looked up without hashing as well
+    let m = FrozenMap::from_vec((0..100).map(|i| (i, i * 2)).collect());
+    for i in 0..100 {
+        assert_eq!(m.get(&i), m.get_by_scan(&i));
+    }
+    assert_eq!(None, m.get_by_scan(&100));
+}
+
+#[test]
+fn into_iter() {
+    use std::fmt::Debug;
+    use std::hash::Hash;
+
+    fn check<K: Hash + Eq + Ord + Clone + Debug>(m: &FrozenMap<K, String>) {
+        let mut expected: Vec<_> = m.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+        expected.sort();
+
+        let mut entries: Vec<_> = m.clone().into_iter().collect();
+        entries.sort();
+        assert_eq!(expected, entries);
+
+        let mut keys: Vec<_> = m.clone().into_keys().collect();
+        keys.sort();
+        assert!(keys.iter().eq(expected.iter().map(|x| &x.0)));
+
+        let mut values: Vec<_> = m.clone().into_values().collect();
+        values.sort();
+        let mut expected_values: Vec<_> = expected.into_iter().map(|x| x.1).collect();
+        expected_values.sort();
+        assert_eq!(expected_values, values);
+    }
+
+    for len in [0, 3, 10, 100, 2000] {
+        check(&(0..len).map(|i| (i, i.to_string())).collect());
+        check(&(0..len).map(|i| (i * 7, i.to_string())).collect());
+        check(
+            &(0..len)
+                .map(|i| (format!("k{i:04}"), i.to_string()))
+                .collect(),
+        );
+        check(
+            &(0..len)
+                .map(|i| ("x".repeat(i + 1), i.to_string()))
+                .collect(),
+        );
+    }
+}
+
+#[test]
+fn iter_mut() {
+    use std::hash::Hash;
+
+    fn check<K: Hash + Eq + Clone>(mut m: FrozenMap<K, usize>) {
+        let expected: Vec<_> = m.iter().map(|(k, v)| (k.clone(), v * 2)).collect();
+
+        for (_, v) in &mut m {
+            *v += 1;
+        }
+
+        for v in m.values_mut() {
+            *v = (*v - 1) * 2;
+        }
+
+        for (k, v) in &expected {
+            assert_eq!(Some(v), m.get(k));
+        }
+    }
+
+    for len in [0, 3, 10, 100, 2000] {
+        check((0..len).map(|i| (i, i)).collect());
+        check((0..len).map(|i| (i * 7, i)).collect());
+        check((0..len).map(|i| (format!("k{i:04}"), i)).collect());
+        check((0..len).map(|i| ("x".repeat(i + 1), i)).collect());
+    }
+}
+
+#[test]
+fn double_ended_iter() {
+    use std::fmt::Debug;
+    use std::hash::Hash;
+
+    fn check<K: Hash + Eq + Clone + Debug>(mut m: FrozenMap<K, usize>) {
+        let forward: Vec<_> = m.iter().map(|(k, v)| (k.clone(), *v)).collect();
+        let mut backward: Vec<_> = m.iter().rev().map(|(k, v)| (k.clone(), *v)).collect();
+        backward.reverse();
+        assert_eq!(forward, backward);
+
+        assert_eq!(
+            forward.last(),
+            m.iter().last().map(|(k, v)| (k.clone(), *v)).as_ref()
+        );
+        assert_eq!(forward.get(2).map(|x| &x.0), m.keys().nth(2));
+        assert_eq!(
+            forward.iter().rev().nth(2).map(|x| &x.1),
+            m.values().nth_back(2)
+        );
+        assert!(m.keys().rev().eq(forward.iter().rev().map(|x| &x.0)));
+        assert_eq!(
+            forward.iter().map(|x| x.1).rev().collect::<Vec<_>>(),
+            m.values().rfold(Vec::new(), |mut acc, v| {
+                acc.push(*v);
+                acc
+            })
+        );
+
+        // consuming from both ends meets in the middle without repeating entries
+        let mut iter = m.iter();
+        let mut seen = 0;
+        while iter.next().is_some() {
+            seen += 1;
+            if iter.next_back().is_some() {
+                seen += 1;
+            }
+            assert_eq!(forward.len() - seen, iter.len());
+        }
+        assert_eq!(forward.len(), seen);
+
+        let mut iter = m.iter();
+        assert_eq!(None, iter.nth(forward.len()));
+        assert_eq!(None, iter.next_back());
+
+        for v in m.values_mut().rev() {
+            *v += 1;
+        }
+        assert!(m
+            .iter_mut()
+            .rev()
+            .map(|(_, v)| *v)
+            .eq(forward.iter().rev().map(|x| x.1 + 1)));
+
+        let into_keys: Vec<_> = m.clone().into_keys().rev().collect();
+        assert!(into_keys.iter().eq(forward.iter().rev().map(|x| &x.0)));
+        assert_eq!(
+            forward.last().map(|x| x.1 + 1),
+            m.clone().into_values().next_back()
+        );
+        assert_eq!(
+            forward.first().map(|x| x.0.clone()),
+            m.into_iter().rev().last().map(|x| x.0)
+        );
+    }
+
+    for len in [0, 3, 10, 100, 2000] {
+        check((0..len).map(|i| (i, i)).collect());
+        check((0..len).map(|i| (i * 7, i)).collect());
+        check((0..len).map(|i| (format!("k{i:04}"), i)).collect());
+        check((0..len).map(|i| ("x".repeat(i + 1), i)).collect());
+    }
+}
+
+#[test]
+fn len_through_wrappers() {
+    use std::borrow::Cow;
+    use std::rc::Rc;
+
+    use crate::specialized_maps::{CommonMap, IntegerMap};
+    use crate::traits::len::Len;
+
+    fn len_of(collection: impl Len) -> (usize, bool) {
+        (collection.len(), collection.is_empty())
+    }
+
+    for len in [0, 3, 300] {
+        let mut m: FrozenMap<_, _> = (0..len).map(|i| (i * 7, i)).collect();
+        let expected = (len, len == 0);
+
+        assert_eq!(expected, len_of(&m));
+        assert_eq!(expected, len_of(&mut m));
+        assert_eq!(expected, len_of(Cow::Borrowed(&m)));
+        assert_eq!(expected, len_of(Cow::<FrozenMap<_, _>>::Owned(m.clone())));
+        assert_eq!(expected, len_of(Box::new(m.clone())));
+        assert_eq!(expected, len_of(Rc::new(m.clone())));
+
+        let payload: Vec<_> = m.into_iter().collect();
+        assert_eq!(
+            expected,
+            len_of(CommonMap::<_, _>::from_vec(payload.clone()))
+        );
+        assert_eq!(expected, len_of(IntegerMap::from_vec(payload)));
+    }
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn par_iter() {
+    use rayon::prelude::*;
+
+    for len in [0, 3, 100, 2000] {
+        let m: FrozenMap<_, _> = (0..len).map(|i| (format!("k{i}"), i)).collect();
+
+        let mut entries: Vec<_> = m.par_iter().collect();
+        let mut expected: Vec<_> = m.iter().collect();
+        entries.sort();
+        expected.sort();
+        assert_eq!(expected, entries);
+
+        assert_eq!(m.keys().count(), m.par_keys().count());
+        assert_eq!(m.values().sum::<usize>(), m.par_values().sum());
+        assert_eq!(m.len(), (&m).into_par_iter().count());
+
+        let mut entries: Vec<_> = m.clone().into_par_iter().collect();
+        entries.sort();
+        assert_eq!(
+            expected,
+            entries.iter().map(|(k, v)| (k, v)).collect::<Vec<_>>()
+        );
+    }
+}
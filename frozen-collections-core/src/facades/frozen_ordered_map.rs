@@ -0,0 +1,397 @@
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::{Hash, Hasher};
+use std::ops::Index;
+
+use crate::errors::FrozenError;
+use crate::hashers::hash_unordered;
+use crate::specialized_maps::hash_table::sort_entries_by;
+use crate::specialized_maps::{
+    IntoIter, Iter, IterMut, Keys, OrderedMap, ScanningMap, Values, ValuesMut,
+};
+use crate::traits::equivalent::Comparable;
+use crate::traits::len::Len;
+use crate::traits::map::Map;
+
+/// The number of keys from which binary search is preferred over scanning the entries.
+const MIN_BINARY_SEARCH_KEYS: usize = 4;
+
+/// A frozen map for keys which can be ordered but not hashed.
+///
+/// [`FrozenMap`](crate::facades::FrozenMap) picks its implementation based on what the keys
+/// write to a hasher, so its keys must implement [`Hash`]. Keys such as decimal numbers or
+/// structs deriving only [`Ord`] can be stored in this map instead, which keeps its entries
+/// sorted by key. Lookups compare the query with a few of the entries while binary searching
+/// them, or with every entry when there are too few for a search to pay off.
+///
+/// Entries are visited in key order by all the iterators.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::FrozenOrderedMap;
+///
+/// #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// struct Version(u16, u16);
+///
+/// let releases = FrozenOrderedMap::from_vec(vec![
+///     (Version(1, 2), "stable"),
+///     (Version(1, 0), "legacy"),
+///     (Version(2, 0), "beta"),
+/// ]);
+///
+/// assert_eq!(releases.get(&Version(1, 2)), Some(&"stable"));
+/// assert_eq!(releases.get(&Version(1, 1)), None);
+/// assert_eq!(releases.values().collect::<Vec<_>>(), [&"legacy", &"stable", &"beta"]);
+/// ```
+#[derive(Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct FrozenOrderedMap<K, V> {
+    map_impl: OrderedTypes<K, V>,
+}
+
+/// The different implementations available for use, depending on the size of the payload.
+///
+/// Both keep their entries sorted by key.
+#[derive(Clone)]
+enum OrderedTypes<K, V> {
+    Scanning(ScanningMap<K, V>),
+    Ordered(OrderedMap<K, V>),
+}
+
+impl<K, V> FrozenOrderedMap<K, V>
+where
+    K: Ord,
+{
+    /// Creates a frozen map.
+    ///
+    /// When several entries hold the same key, the last one is kept.
+    #[must_use]
+    pub fn from_vec(mut payload: Vec<(K, V)>) -> Self {
+        // a stable sort keeps the entries holding the same key in payload order
+        sort_entries_by(&mut payload, |x, y| x.0.cmp(&y.0));
+
+        let mut entries: Vec<(K, V)> = Vec::with_capacity(payload.len());
+        for entry in payload {
+            match entries.last_mut() {
+                Some(last) if last.0 == entry.0 => *last = entry,
+                _ => entries.push(entry),
+            }
+        }
+
+        Self::new(entries)
+    }
+
+    /// Creates a frozen map, returning an error instead of accepting duplicate keys.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::errors::FrozenError;
+    /// use frozen_collections_core::facades::FrozenOrderedMap;
+    ///
+    /// let map = FrozenOrderedMap::try_from_vec(vec![(1, 'a'), (2, 'b'), (1, 'c')]);
+    /// assert_eq!(map.err(), Some(FrozenError::DuplicateKey { index: 2 }));
+    /// ```
+    pub fn try_from_vec(payload: Vec<(K, V)>) -> std::result::Result<Self, FrozenError> {
+        let map = OrderedMap::try_from_vec(payload)?;
+        Ok(Self::new(map.entries.into_vec()))
+    }
+
+    /// Creates a frozen map from entries sorted by key, without duplicate keys.
+    fn new(entries: Vec<(K, V)>) -> Self {
+        let map_impl = if entries.len() < MIN_BINARY_SEARCH_KEYS {
+            OrderedTypes::Scanning(ScanningMap::from_vec(entries))
+        } else {
+            OrderedTypes::Ordered(OrderedMap::from_vec(entries))
+        };
+
+        Self { map_impl }
+    }
+}
+
+impl<K, V> FrozenOrderedMap<K, V> {
+    /// Creates an empty map.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            map_impl: OrderedTypes::Scanning(ScanningMap::empty()),
+        }
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any type implementing
+    /// [`Comparable`] for it.
+    #[inline]
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: ?Sized + Comparable<K>,
+    {
+        match &self.map_impl {
+            OrderedTypes::Scanning(m) => m.get(key),
+            OrderedTypes::Ordered(m) => m.get(key),
+        }
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    #[inline]
+    #[must_use]
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        Q: ?Sized + Comparable<K>,
+    {
+        match &self.map_impl {
+            OrderedTypes::Scanning(m) => m.get_key_value(key),
+            OrderedTypes::Ordered(m) => m.get_key_value(key),
+        }
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    #[inline]
+    #[must_use]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        Q: ?Sized + Comparable<K>,
+    {
+        match &mut self.map_impl {
+            OrderedTypes::Scanning(m) => m.get_mut(key),
+            OrderedTypes::Ordered(m) => m.get_mut(key),
+        }
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    #[inline]
+    #[must_use]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + Comparable<K>,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Returns the number of elements in the map.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// An iterator visiting all key-value pairs in key order.
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(self.entries())
+    }
+
+    /// An iterator visiting all keys in key order.
+    #[must_use]
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys::new(self.entries())
+    }
+
+    /// An iterator visiting all values in the order of their keys.
+    #[must_use]
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values::new(self.entries())
+    }
+
+    /// An iterator visiting all key-value pairs in key order, with mutable references to the
+    /// values.
+    #[must_use]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        match &mut self.map_impl {
+            OrderedTypes::Scanning(m) => m.iter_mut(),
+            OrderedTypes::Ordered(m) => m.iter_mut(),
+        }
+    }
+
+    /// An iterator visiting all values mutably, in the order of their keys.
+    #[must_use]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        match &mut self.map_impl {
+            OrderedTypes::Scanning(m) => m.values_mut(),
+            OrderedTypes::Ordered(m) => m.values_mut(),
+        }
+    }
+
+    fn entries(&self) -> &[(K, V)] {
+        match &self.map_impl {
+            OrderedTypes::Scanning(m) => &m.entries,
+            OrderedTypes::Ordered(m) => &m.entries,
+        }
+    }
+}
+
+impl<K, V, const N: usize> From<[(K, V); N]> for FrozenOrderedMap<K, V>
+where
+    K: Ord,
+{
+    fn from(payload: [(K, V); N]) -> Self {
+        Self::from_vec(Vec::from_iter(payload))
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for FrozenOrderedMap<K, V>
+where
+    K: Ord,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self::from_vec(Vec::from_iter(iter))
+    }
+}
+
+impl<K, V> From<BTreeMap<K, V>> for FrozenOrderedMap<K, V>
+where
+    K: Ord,
+{
+    fn from(map: BTreeMap<K, V>) -> Self {
+        // the entries are already sorted and unique
+        Self::new(map.into_iter().collect())
+    }
+}
+
+impl<Q, K, V> Index<&Q> for FrozenOrderedMap<K, V>
+where
+    Q: ?Sized + Comparable<K>,
+{
+    type Output = V;
+
+    fn index(&self, index: &Q) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<K, V> Default for FrozenOrderedMap<K, V> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<K, V> Len for FrozenOrderedMap<K, V> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V> Map<K, V> for FrozenOrderedMap<K, V>
+where
+    K: Ord,
+{
+    type Iterator<'a>
+        = Iter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    type KeyIterator<'a>
+        = Keys<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    type ValueIterator<'a>
+        = Values<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    fn iter(&self) -> Iter<'_, K, V> {
+        self.iter()
+    }
+
+    fn keys(&self) -> Keys<'_, K, V> {
+        self.keys()
+    }
+
+    fn values(&self) -> Values<'_, K, V> {
+        self.values()
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+}
+
+impl<K, V> Debug for FrozenOrderedMap<K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V> PartialEq<Self> for FrozenOrderedMap<K, V>
+where
+    K: Ord,
+    V: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.entries()
+            .iter()
+            .map(|x| (&x.0, &x.1))
+            .eq(other.entries().iter().map(|x| (&x.0, &x.1)))
+    }
+}
+
+impl<K, V> Eq for FrozenOrderedMap<K, V>
+where
+    K: Ord,
+    V: Eq,
+{
+}
+
+impl<K, V> Hash for FrozenOrderedMap<K, V>
+where
+    K: Hash,
+    V: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self.iter(), state);
+    }
+}
+
+impl<K, V> IntoIterator for FrozenOrderedMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match self.map_impl {
+            OrderedTypes::Scanning(m) => m.into_iter(),
+            OrderedTypes::Ordered(m) => m.into_iter(),
+        }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a FrozenOrderedMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut FrozenOrderedMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
@@ -0,0 +1,103 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use crate::errors::FrozenError;
+use crate::facades::FrozenOrderedMap;
+use crate::traits::len::Len;
+use crate::traits::map::Map;
+
+/// A key which can be ordered but not hashed, like a decimal number.
+#[derive(Clone, Copy, Debug)]
+struct Fixed(f64);
+
+impl PartialEq for Fixed {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Fixed {}
+
+impl PartialOrd for Fixed {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Fixed {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+#[test]
+fn keys_without_hash() {
+    // covers both scanning and binary search
+    for len in [0, 1, 3, 4, 10, 1000] {
+        let payload: Vec<_> = (0..len).map(|i| (Fixed(f64::from(i) / 4.0), i)).collect();
+        let mut map = FrozenOrderedMap::from_vec(payload.iter().rev().copied().collect());
+        assert_eq!(payload.len(), map.len());
+        assert_eq!(payload.len(), Len::len(&map));
+        assert_eq!(len == 0, map.is_empty());
+
+        for (key, value) in &payload {
+            assert_eq!(Some(value), map.get(key));
+            assert_eq!(Some((key, value)), map.get_key_value(key));
+            assert!(map.contains_key(key));
+            assert_eq!(Some(value), Map::get(&map, key));
+            assert_eq!(*value, map[key]);
+        }
+
+        assert_eq!(None, map.get(&Fixed(-1.0)));
+        assert_eq!(None, map.get(&Fixed(0.1)));
+        assert!(!map.contains_key(&Fixed(f64::from(len))));
+
+        // iteration is in key order
+        let entries: Vec<_> = map.iter().map(|(k, v)| (*k, *v)).collect();
+        assert_eq!(payload, entries);
+        assert!(map.keys().copied().eq(payload.iter().map(|x| x.0)));
+        assert!(map.values().copied().eq(payload.iter().map(|x| x.1)));
+
+        for value in map.values_mut() {
+            *value += 1;
+        }
+        for (key, value) in &payload {
+            *map.get_mut(key).unwrap() -= 1;
+            assert_eq!(Some(value), map.get(key));
+        }
+
+        assert_eq!(payload, map.into_iter().collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn duplicates() {
+    let map = FrozenOrderedMap::from_vec(vec![(1, 'a'), (2, 'b'), (1, 'c'), (1, 'd')]);
+    assert_eq!(2, map.len());
+    assert_eq!(Some(&'d'), map.get(&1));
+
+    assert_eq!(
+        Some(FrozenError::DuplicateKey { index: 2 }),
+        FrozenOrderedMap::try_from_vec(vec![(1, 'a'), (2, 'b'), (1, 'c')]).err()
+    );
+    assert!(FrozenOrderedMap::try_from_vec(vec![(1, 'a'), (2, 'b')]).is_ok());
+}
+
+#[test]
+fn conversions_and_equality() {
+    let ordered: BTreeMap<_, _> = (0..100).map(|i| (format!("Key-{i:03}"), i)).collect();
+    let a = FrozenOrderedMap::from(ordered.clone());
+    let b: FrozenOrderedMap<_, _> = ordered.into_iter().rev().collect();
+    assert_eq!(a, b);
+    assert_eq!(Some(&42), a.get("Key-042"));
+    assert_ne!(a, FrozenOrderedMap::from([("Key-000".to_string(), 0)]));
+    assert_eq!(
+        FrozenOrderedMap::<String, i32>::default(),
+        FrozenOrderedMap::empty()
+    );
+
+    assert_eq!(
+        r#"{1: "a", 2: "b"}"#,
+        format!("{:?}", FrozenOrderedMap::from([(2, "b"), (1, "a")]))
+    );
+}
@@ -0,0 +1,424 @@
+use std::fmt::{Debug, Formatter, Result};
+use std::ops::Range;
+
+use crate::analyzers::prefix_key_analyzer::{
+    analyze_prefix_keys, find_parent_prefixes, PrefixKeyAnalysisResult,
+};
+use crate::errors::FrozenError;
+use crate::specialized_maps::{Iter, Keys, Values};
+use crate::traits::len::Len;
+
+/// A frozen map which finds the longest key that is a prefix of a query.
+///
+/// This is the lookup performed by routers and path-based dispatch tables: given the keys
+/// `/api` and `/api/users`, the query `/api/users/42` matches `/api/users`, while `/api/orders`
+/// matches `/api`. Keys and queries are compared as bytes, so any key which can be viewed as a
+/// byte slice works, such as strings, byte strings, or IP addresses given as their octets,
+/// like `[10]` for `10.0.0.0/8` or `[192, 168, 1]` for `192.168.1.0/24`.
+///
+/// When the map is created, its keys are analyzed to pick between binary searching the sorted
+/// keys, which is compact and fast for few keys, and walking down a compressed trie, which
+/// is faster for many keys or for keys deeply nested within one another.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::FrozenPrefixMap;
+///
+/// let routes = FrozenPrefixMap::from_vec(vec![
+///     ("/", "root"),
+///     ("/api", "api"),
+///     ("/api/users", "users"),
+/// ]);
+///
+/// assert_eq!(routes.get_longest_prefix("/api/users/42"), Some(&"users"));
+/// assert_eq!(routes.get_longest_prefix("/api/orders"), Some(&"api"));
+/// assert_eq!(routes.get_longest_prefix("/index.html"), Some(&"root"));
+/// assert_eq!(routes.get_longest_prefix("api"), None);
+/// ```
+#[derive(Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct FrozenPrefixMap<K, V> {
+    /// The entries, sorted by key.
+    entries: Box<[(K, V)]>,
+
+    prefix_impl: PrefixTypes,
+}
+
+/// The different layouts available for use, depending on the keys.
+#[derive(Clone)]
+enum PrefixTypes {
+    /// The index of the longest proper prefix of each key, by entry.
+    SortedArray(Box<[Option<usize>]>),
+
+    Trie(Trie),
+}
+
+/// A trie whose chains of nodes with a single child and no entry are collapsed into edges
+/// spanning several bytes.
+#[derive(Clone)]
+struct Trie {
+    /// The nodes, starting with the root.
+    nodes: Box<[TrieNode]>,
+
+    /// The first byte of the edge to each child of a node, along with the child, sorted by
+    /// byte within each node.
+    edges: Box<[(u8, usize)]>,
+}
+
+#[derive(Clone)]
+struct TrieNode {
+    /// The length of the prefix spelled by the path from the root to the node.
+    depth: usize,
+
+    /// An entry whose key starts with the node's prefix, which holds the bytes of the edge
+    /// leading to the node.
+    sample: usize,
+
+    /// The entry whose key is the node's prefix, if any.
+    entry: Option<usize>,
+
+    /// The range of `edges` leading to the node's children.
+    children: Range<usize>,
+}
+
+impl<K, V> FrozenPrefixMap<K, V>
+where
+    K: AsRef<[u8]>,
+{
+    /// Creates a frozen prefix map.
+    ///
+    /// When several entries hold the same key, the last one is kept.
+    #[must_use]
+    pub fn from_vec(payload: Vec<(K, V)>) -> Self {
+        Self::new(sort_entries(payload, K::as_ref).0)
+    }
+
+    /// Creates a frozen prefix map, returning an error instead of accepting duplicate keys.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::errors::FrozenError;
+    /// use frozen_collections_core::facades::FrozenPrefixMap;
+    ///
+    /// let map = FrozenPrefixMap::try_from_vec(vec![("a", 1), ("b", 2), ("a", 3)]);
+    /// assert_eq!(map.err(), Some(FrozenError::DuplicateKey { index: 2 }));
+    /// ```
+    pub fn try_from_vec(payload: Vec<(K, V)>) -> std::result::Result<Self, FrozenError> {
+        match sort_entries(payload, K::as_ref) {
+            (_, Some(index)) => Err(FrozenError::DuplicateKey { index }),
+            (entries, None) => Ok(Self::new(entries)),
+        }
+    }
+
+    /// Creates a frozen prefix map from entries sorted by key, without duplicate keys.
+    fn new(entries: Vec<(K, V)>) -> Self {
+        let parents = find_parent_prefixes(entries.iter().map(|x| x.0.as_ref()));
+        let prefix_impl = match analyze_prefix_keys(&parents) {
+            PrefixKeyAnalysisResult::SortedArray => {
+                PrefixTypes::SortedArray(parents.into_boxed_slice())
+            }
+            PrefixKeyAnalysisResult::Trie => PrefixTypes::Trie(Trie::new(&entries)),
+        };
+
+        Self {
+            entries: entries.into_boxed_slice(),
+            prefix_impl,
+        }
+    }
+
+    /// Returns the index of the entry holding the longest key which is a prefix of the query.
+    fn find_longest_prefix(&self, query: &[u8]) -> Option<usize> {
+        match &self.prefix_impl {
+            PrefixTypes::SortedArray(parents) => {
+                // every key which is a prefix of the query sorts between that key and the
+                // query, so it is a prefix of the closest key which doesn't sort after the query
+                let closest = self
+                    .entries
+                    .partition_point(|x| x.0.as_ref() <= query)
+                    .checked_sub(1)?;
+
+                let mut candidate = Some(closest);
+                while let Some(index) = candidate {
+                    if query.starts_with(self.entries[index].0.as_ref()) {
+                        return Some(index);
+                    }
+
+                    candidate = parents[index];
+                }
+
+                None
+            }
+
+            PrefixTypes::Trie(trie) => trie.find_longest_prefix(&self.entries, query),
+        }
+    }
+
+    /// Returns the value of the longest key which is a prefix of the query.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenPrefixMap;
+    ///
+    /// let networks = FrozenPrefixMap::from_vec(vec![
+    ///     (vec![10u8], "private"),
+    ///     (vec![10, 1], "lab"),
+    ///     (vec![192, 168], "home"),
+    /// ]);
+    ///
+    /// assert_eq!(networks.get_longest_prefix(&[10, 1, 2, 3]), Some(&"lab"));
+    /// assert_eq!(networks.get_longest_prefix(&[10, 2, 0, 1]), Some(&"private"));
+    /// assert_eq!(networks.get_longest_prefix(&[8, 8, 8, 8]), None);
+    /// ```
+    #[inline]
+    pub fn get_longest_prefix<Q>(&self, query: &Q) -> Option<&V>
+    where
+        Q: ?Sized + AsRef<[u8]>,
+    {
+        let index = self.find_longest_prefix(query.as_ref())?;
+        Some(&self.entries[index].1)
+    }
+
+    /// Returns the longest key which is a prefix of the query, along with its value.
+    #[inline]
+    pub fn get_longest_prefix_key_value<Q>(&self, query: &Q) -> Option<(&K, &V)>
+    where
+        Q: ?Sized + AsRef<[u8]>,
+    {
+        let index = self.find_longest_prefix(query.as_ref())?;
+        Some((&self.entries[index].0, &self.entries[index].1))
+    }
+
+    /// Returns the value of the key equal to the query, ignoring keys which are only prefixes
+    /// of it.
+    #[inline]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: ?Sized + AsRef<[u8]>,
+    {
+        let index = self
+            .entries
+            .binary_search_by(|x| x.0.as_ref().cmp(key.as_ref()))
+            .ok()?;
+
+        Some(&self.entries[index].1)
+    }
+
+    /// Returns `true` if the map holds a key equal to the query.
+    #[inline]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + AsRef<[u8]>,
+    {
+        self.get(key).is_some()
+    }
+}
+
+impl<K, V> FrozenPrefixMap<K, V> {
+    /// An iterator visiting all key-value pairs in key order.
+    #[must_use]
+    pub const fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(&self.entries)
+    }
+
+    /// An iterator visiting all keys in order.
+    #[must_use]
+    pub const fn keys(&self) -> Keys<'_, K, V> {
+        Keys::new(&self.entries)
+    }
+
+    /// An iterator visiting all values in key order.
+    #[must_use]
+    pub const fn values(&self) -> Values<'_, K, V> {
+        Values::new(&self.entries)
+    }
+
+    /// Returns the number of elements in the map.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns `true` if lookups walk down a trie rather than binary searching the keys.
+    #[must_use]
+    pub const fn is_trie(&self) -> bool {
+        matches!(self.prefix_impl, PrefixTypes::Trie(_))
+    }
+}
+
+/// Sorts the entries by the bytes of their keys, keeping the last of the entries which hold
+/// the same key.
+///
+/// Also returns the position in the payload of the first entry which repeats the key of an
+/// earlier entry, if any.
+pub fn sort_entries<K, V>(
+    payload: Vec<(K, V)>,
+    bytes: fn(&K) -> &[u8],
+) -> (Vec<(K, V)>, Option<usize>) {
+    let mut indexed: Vec<_> = payload.into_iter().enumerate().collect();
+
+    // the sort is stable, so entries holding the same key stay in payload order
+    indexed.sort_by(|x, y| bytes(&x.1 .0).cmp(bytes(&y.1 .0)));
+
+    let mut duplicate: Option<usize> = None;
+    let mut entries: Vec<(K, V)> = Vec::with_capacity(indexed.len());
+    for (index, entry) in indexed {
+        match entries.last_mut() {
+            Some(last) if bytes(&last.0) == bytes(&entry.0) => {
+                duplicate = Some(duplicate.map_or(index, |d| d.min(index)));
+                *last = entry;
+            }
+            _ => entries.push(entry),
+        }
+    }
+
+    (entries, duplicate)
+}
+
+impl Trie {
+    fn new<K, V>(entries: &[(K, V)]) -> Self
+    where
+        K: AsRef<[u8]>,
+    {
+        let key = |index: usize| entries[index].0.as_ref();
+
+        let mut nodes = Vec::new();
+        let mut edges: Vec<(u8, usize)> = Vec::new();
+
+        // ranges of entries sharing a prefix, along with the edge leading to their node
+        let mut pending = vec![(0..entries.len(), None::<usize>)];
+        while let Some((range, edge)) = pending.pop() {
+            // the root stays at depth zero, while other nodes extend to the longest prefix
+            // shared by their keys, which is that of the first and last of them
+            let depth = match (edge, range.is_empty()) {
+                (Some(_), false) => common_prefix_len(key(range.start), key(range.end - 1)),
+                _ => 0,
+            };
+
+            let mut start = range.start;
+            let entry = if start < range.end && key(start).len() == depth {
+                start += 1;
+                Some(range.start)
+            } else {
+                None
+            };
+
+            let first_edge = edges.len();
+            while start < range.end {
+                let byte = key(start)[depth];
+                let end = start
+                    + (start..range.end)
+                        .map(key)
+                        .take_while(|k| k[depth] == byte)
+                        .count();
+
+                edges.push((byte, 0));
+                pending.push((start..end, Some(edges.len() - 1)));
+                start = end;
+            }
+
+            if let Some(edge) = edge {
+                edges[edge].1 = nodes.len();
+            }
+
+            nodes.push(TrieNode {
+                depth,
+                sample: range.start,
+                entry,
+                children: first_edge..edges.len(),
+            });
+        }
+
+        Self {
+            nodes: nodes.into_boxed_slice(),
+            edges: edges.into_boxed_slice(),
+        }
+    }
+
+    fn find_longest_prefix<K, V>(&self, entries: &[(K, V)], query: &[u8]) -> Option<usize>
+    where
+        K: AsRef<[u8]>,
+    {
+        let mut node = &self.nodes[0];
+        let mut found = node.entry;
+
+        while let Some(&byte) = query.get(node.depth) {
+            let edges = &self.edges[node.children.clone()];
+            let Ok(edge) = edges.binary_search_by_key(&byte, |e| e.0) else {
+                break;
+            };
+
+            let child = &self.nodes[edges[edge].1];
+            let span = node.depth..child.depth;
+            if query.get(span.clone()) != entries[child.sample].0.as_ref().get(span) {
+                break;
+            }
+
+            node = child;
+            found = node.entry.or(found);
+        }
+
+        found
+    }
+}
+
+/// Returns the length of the longest common prefix of two slices.
+fn common_prefix_len(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(x, y)| x == y).count()
+}
+
+impl<K, V> Len for FrozenPrefixMap<K, V> {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl<K, V> Debug for FrozenPrefixMap<K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let pairs = self.entries.iter().map(|x| (&x.0, &x.1));
+        f.debug_map().entries(pairs).finish()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a FrozenPrefixMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V, const N: usize> From<[(K, V); N]> for FrozenPrefixMap<K, V>
+where
+    K: AsRef<[u8]>,
+{
+    fn from(payload: [(K, V); N]) -> Self {
+        Self::from_vec(Vec::from_iter(payload))
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for FrozenPrefixMap<K, V>
+where
+    K: AsRef<[u8]>,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self::from_vec(Vec::from_iter(iter))
+    }
+}
@@ -0,0 +1,149 @@
+use crate::errors::FrozenError;
+use crate::facades::FrozenPrefixMap;
+use crate::traits::len::Len;
+
+/// Finds the longest prefix the slow way, to check the maps against.
+fn expected<'a>(keys: &[&'a str], query: &str) -> Option<&'a str> {
+    // the prefixes of a query are prefixes of one another, so the longest also sorts last
+    keys.iter().copied().filter(|k| query.starts_with(k)).max()
+}
+
+fn check(keys: &[&str], queries: &[&str], trie: bool) {
+    let map = FrozenPrefixMap::from_vec(keys.iter().map(|k| (*k, k.len())).collect());
+    assert_eq!(trie, map.is_trie());
+    assert_eq!(keys.len(), map.len());
+    assert_eq!(keys.len(), Len::len(&map));
+
+    for query in keys.iter().chain(queries) {
+        let found = map.get_longest_prefix_key_value(query);
+        assert_eq!(expected(keys, query), found.map(|x| *x.0), "{query}");
+        assert_eq!(found.map(|x| x.1), map.get_longest_prefix(query));
+    }
+
+    for key in keys {
+        assert_eq!(Some(&key.len()), map.get(key));
+        assert!(map.contains_key(key));
+    }
+}
+
+#[test]
+fn sorted_array() {
+    let keys = [
+        "/",
+        "/api",
+        "/api/users",
+        "/api/users/admin",
+        "/static",
+        "/z",
+    ];
+    let queries = [
+        "",
+        "api",
+        "/api/user",
+        "/api/users/42",
+        "/api/usersx",
+        "/api/orders",
+        "/static/app.js",
+        "/stat",
+        "/y",
+        "/zz",
+    ];
+
+    check(&keys, &queries, false);
+
+    let map = FrozenPrefixMap::from_vec(keys.iter().map(|k| (*k, ())).collect());
+    assert!(!map.contains_key("/api/users/42"));
+    assert_eq!(None, map.get("/ap"));
+}
+
+#[test]
+fn trie() {
+    let nested = [
+        "a", "ab", "abc", "abcd", "abcde", "abcdef", "abcdefg", "abcdefgh", "abd", "b",
+    ];
+    let queries = ["", "abcdefghij", "abcdx", "abdc", "ac", "ba", "c"];
+    check(&nested, &queries, true);
+
+    let words: Vec<_> = (0..200).map(|i| format!("{}/{}", i % 7, i * 13)).collect();
+    let mut keys: Vec<_> = words.iter().map(String::as_str).collect();
+    keys.push("3");
+    keys.push("3/1");
+    let queries = ["3/13", "3/130/x", "5/", "6/1001", "7", "3/"];
+    check(&keys, &queries, true);
+}
+
+#[test]
+fn empty_key() {
+    let map = FrozenPrefixMap::from([("", 0), ("x", 1)]);
+    assert_eq!(Some(&0), map.get_longest_prefix(""));
+    assert_eq!(Some(&0), map.get_longest_prefix("y"));
+    assert_eq!(Some(&1), map.get_longest_prefix("xy"));
+
+    let keys: Vec<_> = (0..100).map(|i| format!("k{i}")).collect();
+    let map: FrozenPrefixMap<_, _> = keys
+        .iter()
+        .map(|k| (k.as_str(), 1))
+        .chain([("", 0)])
+        .collect();
+    assert!(map.is_trie());
+    assert_eq!(Some(&0), map.get_longest_prefix("y"));
+    assert_eq!(Some(&1), map.get_longest_prefix("k99x"));
+    assert_eq!(Some(&0), map.get_longest_prefix("k"));
+}
+
+#[test]
+fn empty() {
+    let map = FrozenPrefixMap::<&str, i32>::from_vec(vec![]);
+    assert!(map.is_empty());
+    assert_eq!(None, map.get_longest_prefix("abc"));
+    assert_eq!(None, map.get(""));
+}
+
+#[test]
+fn ip_prefixes() {
+    let map = FrozenPrefixMap::from_vec(vec![
+        (vec![10u8], "10.0.0.0/8"),
+        (vec![10, 20], "10.20.0.0/16"),
+        (vec![10, 20, 30], "10.20.30.0/24"),
+        (vec![192, 168], "192.168.0.0/16"),
+    ]);
+
+    assert_eq!(
+        Some(&"10.20.30.0/24"),
+        map.get_longest_prefix(&[10, 20, 30, 40])
+    );
+    assert_eq!(
+        Some(&"10.20.0.0/16"),
+        map.get_longest_prefix(&[10, 20, 31, 40])
+    );
+    assert_eq!(
+        Some(&"10.0.0.0/8"),
+        map.get_longest_prefix(&[10, 21, 30, 40])
+    );
+    assert_eq!(
+        Some(&"192.168.0.0/16"),
+        map.get_longest_prefix(&[192, 168, 1, 1])
+    );
+    assert_eq!(None, map.get_longest_prefix(&[192, 169, 1, 1]));
+}
+
+#[test]
+fn duplicates() {
+    let map = FrozenPrefixMap::from_vec(vec![("a", 1), ("b", 2), ("a", 3)]);
+    assert_eq!(2, map.len());
+    assert_eq!(Some(&3), map.get("a"));
+
+    assert_eq!(
+        Some(FrozenError::DuplicateKey { index: 3 }),
+        FrozenPrefixMap::try_from_vec(vec![("a", 1), ("b", 2), ("c", 3), ("b", 4), ("a", 5)]).err()
+    );
+}
+
+#[test]
+fn iteration() {
+    let map = FrozenPrefixMap::from([("b", 2), ("a", 1), ("ab", 3)]);
+    assert!(map.keys().eq(["a", "ab", "b"].iter()));
+    assert!(map.values().eq([1, 3, 2].iter()));
+    assert_eq!(3, (&map).into_iter().count());
+    assert_eq!(r#"{"a": 1, "ab": 3, "b": 2}"#, format!("{map:?}"));
+}
@@ -1,41 +1,28 @@
 use std::any::type_name;
-use std::collections::HashSet;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashSet};
 use std::hash::RandomState;
-use std::hash::{BuildHasher, Hash};
-use std::intrinsics::transmute;
+use std::hash::{BuildHasher, Hash, Hasher};
 use std::ops::{BitAnd, BitOr, BitXor, Sub};
 
-use bitvec::macros::internal::funty::Fundamental;
-
-use crate::analyzers::int_key_analyzer::{analyze_int_keys, IntKeyAnalysisResult};
-use crate::analyzers::slice_key_analyzer::{analyze_slice_keys, SliceKeyAnalysisResult};
-use crate::specialized_sets::{
-    CommonSet, IntegerRangeSet, IntegerSet, Iter, LeftSliceSet, LengthSet, RightSliceSet,
-    ScanningSet, Set,
+use crate::analyzers::int_key_analyzer::{
+    find_code_range, find_dense_code_range, is_dense, MIN_BIT_SET_DENSITY_PERCENT,
 };
+use crate::errors::FrozenError;
+use crate::facades::{AnalysisOptions, DuplicatePolicy, FrozenMap};
+use crate::hashers::{hash_unordered, PassThroughHasher, SeededState};
+use crate::specialized_maps::key_codes::int_code;
+use crate::specialized_sets::{BitSet, Iter, Set};
+use crate::traits::heap_size::HeapSize;
 use crate::traits::len::Len;
 
-/// The different implementations available for use, depending on the type and content of the payload.
-#[derive(Clone)]
-enum SetTypes<T, BH> {
-    Scanning(ScanningSet<T>),
-
-    CommonSmall(CommonSet<T, u8, BH>),
-    CommonLarge(CommonSet<T, usize, BH>),
-
-    U32Small(IntegerSet<u32, u8>),
-    U32Large(IntegerSet<u32, usize>),
-
-    U32Range(IntegerRangeSet<u32>),
-
-    LeftStringSliceSmall(LeftSliceSet<String, u8, BH>),
-    LeftStringSliceLarge(LeftSliceSet<String, usize, BH>),
-
-    RightStringSliceSmall(RightSliceSet<String, u8, BH>),
-    RightStringSliceLarge(RightSliceSet<String, usize, BH>),
-
-    StringLengthSmall(LengthSet<String, u8>),
-}
+#[cfg(feature = "deepsize")]
+use deepsize::DeepSizeOf;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
 
 /// A set optimized for fast read access.
 ///
@@ -133,6 +120,47 @@ pub struct FrozenSet<T, BH = RandomState> {
     set_impl: SetTypes<T, BH>,
 }
 
+/// The different implementations available for use, depending on the type and content of the payload.
+#[derive(Clone)]
+enum SetTypes<T, BH> {
+    // the map picks the specialized implementation, the set just ignores the values
+    Hashed(FrozenMap<T, (), BH>),
+
+    Bits(BitSet<T>),
+}
+
+/// The value types for which a [`BitSet`] can be used, which are those whose [`Hash`]
+/// implementation writes an integer identifying the value.
+const BIT_SET_TYPES: [&str; 10] = [
+    "u8", "i8", "u16", "i16", "u32", "i32", "u64", "i64", "usize", "isize",
+];
+
+impl<T, BH> FrozenSet<T, BH> {
+    /// Creates an empty set.
+    ///
+    /// This neither allocates nor sets up a hasher, which makes it cheap to create
+    /// placeholders for optional tables which are usually left empty.
+    ///
+    /// This isn't a `const fn`, since the boxed slices frozen collections are made of can't
+    /// be created in constant expressions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenSet;
+    ///
+    /// let set = FrozenSet::<String>::empty();
+    /// assert!(set.is_empty());
+    /// assert_eq!(set.contains(&"a".to_string()), false);
+    /// ```
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            set_impl: SetTypes::Hashed(FrozenMap::empty()),
+        }
+    }
+}
+
 impl<T, BH> FrozenSet<T, BH>
 where
     T: Hash + Eq,
@@ -157,6 +185,125 @@ where
         Self::new(payload, bh)
     }
 
+    /// Creates a new frozen set which will use the given hasher to hash values, analyzing the
+    /// values within the bounds set by `options`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::{AnalysisOptions, FrozenSet};
+    /// use std::hash::RandomState;
+    ///
+    /// let payload: Vec<_> = (0..1000).map(|i| format!("host-{i}")).collect();
+    /// let options = AnalysisOptions::new().prefilter(true);
+    /// let set = FrozenSet::from_vec_with_options(payload, RandomState::new(), options);
+    ///
+    /// assert!(set.contains(&"host-7".to_string()));
+    /// assert!(!set.contains(&"guest-7".to_string()));
+    /// ```
+    #[must_use]
+    pub fn from_vec_with_options(payload: Vec<T>, bh: BH, options: AnalysisOptions) -> Self {
+        Self {
+            set_impl: SetTypes::Hashed(FrozenMap::from_vec_with_options(
+                payload.into_iter().map(|v| (v, ())).collect(),
+                bh,
+                options,
+            )),
+        }
+    }
+
+    /// Creates a new frozen set of values which are already hashes, using a hash builder which
+    /// passes them through, as [`FrozenMap::from_vec_with_pass_through_hasher`] does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenSet;
+    /// use frozen_collections_core::hashers::NoHashBuilder;
+    ///
+    /// let digests: Vec<[u8; 16]> = (0..10u128).map(|i| (i * 0x9e37_79b9).to_le_bytes()).collect();
+    /// let set = FrozenSet::from_vec_with_pass_through_hasher(digests.clone(), NoHashBuilder);
+    ///
+    /// assert!(set.contains(&digests[7]));
+    /// assert!(!set.contains(&[0xFF; 16]));
+    /// ```
+    #[must_use]
+    pub fn from_vec_with_pass_through_hasher(payload: Vec<T>, bh: BH) -> Self
+    where
+        BH: PassThroughHasher,
+    {
+        Self {
+            set_impl: SetTypes::Hashed(FrozenMap::from_vec_with_pass_through_hasher(
+                payload.into_iter().map(|v| (v, ())).collect(),
+                bh,
+            )),
+        }
+    }
+
+    /// Creates a new frozen set which will use the given hasher to hash values, returning an
+    /// error instead of accepting an invalid payload.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::errors::FrozenError;
+    /// use frozen_collections_core::facades::FrozenSet;
+    /// use std::hash::RandomState;
+    ///
+    /// let set = FrozenSet::try_from_vec_with_hasher(vec![1, 2, 3], RandomState::new());
+    /// assert!(set.unwrap().contains(&1));
+    ///
+    /// let set = FrozenSet::try_from_vec_with_hasher(vec![1, 2, 1], RandomState::new());
+    /// assert_eq!(set.err(), Some(FrozenError::DuplicateKey { index: 2 }));
+    /// ```
+    pub fn try_from_vec_with_hasher(
+        payload: Vec<T>,
+        bh: BH,
+    ) -> std::result::Result<Self, FrozenError> {
+        Self::from_vec_with_hasher_and_policy(payload, bh, DuplicatePolicy::Error)
+    }
+
+    /// Creates a new frozen set which will use the given hasher to hash values, handling
+    /// duplicate values according to `policy`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate values and the policy is [`DuplicatePolicy::Error`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::{DuplicatePolicy, FrozenSet};
+    /// use frozen_collections_core::traits::len::Len;
+    /// use std::hash::RandomState;
+    ///
+    /// let set = FrozenSet::from_vec_with_hasher_and_policy(
+    ///     vec![1, 2, 1],
+    ///     RandomState::new(),
+    ///     DuplicatePolicy::KeepFirst,
+    /// );
+    ///
+    /// assert_eq!(set.unwrap().len(), 2);
+    /// ```
+    pub fn from_vec_with_hasher_and_policy(
+        payload: Vec<T>,
+        bh: BH,
+        policy: DuplicatePolicy,
+    ) -> std::result::Result<Self, FrozenError> {
+        let payload = policy.apply(payload.into_iter().map(|v| (v, ())).collect(), |k| {
+            bh.hash_one(k)
+        })?;
+
+        Ok(Self::new(
+            payload.into_iter().map(|(v, ())| v).collect(),
+            bh,
+        ))
+    }
+
     /// Creates a new frozen set which will use the given hasher to hash
     /// keys.
     ///
@@ -195,148 +342,219 @@ where
     }
 
     fn new(payload: Vec<T>, bh: BH) -> Self {
+        if payload.len() >= 4 && BIT_SET_TYPES.contains(&type_name::<T>()) {
+            // a continuous range is best served by the map's range implementation
+            let mut codes: Vec<_> = payload.iter().map(int_code).collect();
+            if find_code_range(&mut codes).is_none()
+                && find_dense_code_range(&mut codes, MIN_BIT_SET_DENSITY_PERCENT).is_some()
+            {
+                return Self {
+                    set_impl: SetTypes::Bits(BitSet::from_vec(payload)),
+                };
+            }
+        }
+
         Self {
-            set_impl: if payload.len() < 4 {
-                SetTypes::Scanning(ScanningSet::from_vec(payload))
-            } else if type_name::<T>() == type_name::<u32>() {
-                Self::new_u32_set(payload)
-            } else if type_name::<T>() == type_name::<String>() {
-                Self::new_string_set(payload, bh)
-            } else {
-                Self::new_common_set(payload, bh)
-            },
+            set_impl: SetTypes::Hashed(FrozenMap::from_iter_with_hasher(
+                payload.into_iter().map(|v| (v, ())),
+                bh,
+            )),
         }
     }
 
-    #[allow(clippy::transmute_undefined_repr)]
-    fn new_u32_set(payload: Vec<T>) -> SetTypes<T, BH> {
-        let payload: Vec<u32> = unsafe { transmute(payload) };
-
-        let key_analysis = analyze_int_keys(payload.iter().copied());
-
-        match key_analysis {
-            IntKeyAnalysisResult::Range => SetTypes::U32Range(IntegerRangeSet::from_vec(payload)),
-            IntKeyAnalysisResult::Normal => {
-                if payload.len() <= u8::MAX.as_usize() {
-                    SetTypes::U32Small(IntegerSet::from_vec(payload))
-                } else {
-                    SetTypes::U32Large(IntegerSet::from_vec(payload))
-                }
-            }
+    /// Returns `true` if the set contains a value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenSet;
+    ///
+    /// let set = FrozenSet::from([1, 2, 3]);
+    /// assert!(set.contains(&1));
+    /// assert!(!set.contains(&4));
+    /// ```
+    pub fn contains(&self, value: &T) -> bool {
+        match &self.set_impl {
+            SetTypes::Hashed(m) => m.contains_key(value),
+            SetTypes::Bits(s) => s.contains(value),
         }
     }
 
-    #[allow(clippy::transmute_undefined_repr)]
-    fn new_string_set(payload: Vec<T>, bh: BH) -> SetTypes<T, BH> {
-        let payload: Vec<String> = unsafe { transmute(payload) };
-
-        let key_analysis = analyze_slice_keys(payload.iter().map(String::as_bytes), &bh);
-
-        if payload.len() <= u8::MAX.as_usize() {
-            match key_analysis {
-                SliceKeyAnalysisResult::Normal => SetTypes::CommonSmall(
-                    CommonSet::from_vec_with_hasher(unsafe { transmute(payload) }, bh),
-                ),
-
-                SliceKeyAnalysisResult::LeftHandSubslice {
-                    subslice_index,
-                    subslice_len,
-                } => SetTypes::LeftStringSliceSmall(LeftSliceSet::from_vec_with_hasher(
-                    payload,
-                    subslice_index..subslice_index + subslice_len,
-                    bh,
-                )),
-
-                SliceKeyAnalysisResult::RightHandSubslice {
-                    subslice_index,
-                    subslice_len,
-                } => SetTypes::RightStringSliceSmall(RightSliceSet::from_vec_with_hasher(
-                    payload,
-                    subslice_index..subslice_index + subslice_len,
-                    bh,
-                )),
-
-                SliceKeyAnalysisResult::Length => {
-                    SetTypes::StringLengthSmall(LengthSet::from_vec(payload))
-                }
-            }
-        } else {
-            match key_analysis {
-                SliceKeyAnalysisResult::Length | SliceKeyAnalysisResult::Normal => {
-                    SetTypes::CommonLarge(CommonSet::from_vec_with_hasher(
-                        unsafe { transmute(payload) },
-                        bh,
-                    ))
-                }
-
-                SliceKeyAnalysisResult::LeftHandSubslice {
-                    subslice_index,
-                    subslice_len,
-                } => SetTypes::LeftStringSliceLarge(LeftSliceSet::from_vec_with_hasher(
-                    payload,
-                    subslice_index..subslice_index + subslice_len,
-                    bh,
-                )),
-
-                SliceKeyAnalysisResult::RightHandSubslice {
-                    subslice_index,
-                    subslice_len,
-                } => SetTypes::RightStringSliceLarge(RightSliceSet::from_vec_with_hasher(
-                    payload,
-                    subslice_index..subslice_index + subslice_len,
-                    bh,
-                )),
-            }
+    /// Returns `true` if the set contains every one of the values.
+    ///
+    /// This stops at the first missing value, and is faster than calling
+    /// [`contains`](Self::contains) for every value, since the set's implementation is selected
+    /// once for the whole batch rather than once per value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenSet;
+    ///
+    /// let set = FrozenSet::from([1, 2, 3]);
+    /// assert!(set.contains_all(&[3, 1]));
+    /// assert!(!set.contains_all(&[1, 4]));
+    /// ```
+    pub fn contains_all<I>(&self, values: I) -> bool
+    where
+        I: IntoIterator,
+        I::Item: Borrow<T>,
+    {
+        let mut values = values.into_iter();
+        match &self.set_impl {
+            SetTypes::Hashed(m) => values.all(|v| m.contains_key(v.borrow())),
+            SetTypes::Bits(s) => values.all(|v| s.contains(v.borrow())),
         }
     }
 
-    fn new_common_set(payload: Vec<T>, bh: BH) -> SetTypes<T, BH> {
-        if payload.len() <= u8::MAX.as_usize() {
-            SetTypes::CommonSmall(CommonSet::from_vec_with_hasher(payload, bh))
-        } else {
-            SetTypes::CommonLarge(CommonSet::from_vec_with_hasher(payload, bh))
+    /// Returns `true` if the set contains at least one of the values.
+    ///
+    /// This stops at the first value found. As with [`contains_all`](Self::contains_all), the
+    /// set's implementation is selected once for the whole batch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenSet;
+    ///
+    /// let set = FrozenSet::from(["a".to_string(), "b".to_string()]);
+    /// assert!(set.contains_any(["c".to_string(), "a".to_string()]));
+    /// assert!(!set.contains_any(Vec::<String>::new()));
+    /// ```
+    pub fn contains_any<I>(&self, values: I) -> bool
+    where
+        I: IntoIterator,
+        I::Item: Borrow<T>,
+    {
+        let mut values = values.into_iter();
+        match &self.set_impl {
+            SetTypes::Hashed(m) => values.any(|v| m.contains_key(v.borrow())),
+            SetTypes::Bits(s) => values.any(|v| s.contains(v.borrow())),
         }
     }
 
-    /// Returns `true` if the set contains a value.
+    /// Splits a batch of items into those which are in the set and those which aren't.
+    ///
+    /// The relative order of the items is preserved in both halves. The set's implementation is
+    /// selected once for the whole batch, and hashed sets look the items up as a stream, as
+    /// [`FrozenMap::lookup_stream`] does, so that the memory latency of the lookups overlaps.
+    /// On sets too large for the processor's caches, this is faster than calling
+    /// [`contains`](Self::contains) for every item.
     ///
     /// # Examples
     ///
     /// ```
     /// use frozen_collections_core::facades::FrozenSet;
     ///
-    /// let set = FrozenSet::from([1, 2, 3]);
-    /// assert!(set.contains(&1));
-    /// assert!(!set.contains(&4));
+    /// let allowed = FrozenSet::from([1, 3, 5]);
+    /// let (yes, no) = allowed.partition_slice(&[1, 2, 3, 4]);
+    ///
+    /// assert_eq!(yes, vec![&1, &3]);
+    /// assert_eq!(no, vec![&2, &4]);
     /// ```
-    pub fn contains(&self, value: &T) -> bool {
+    pub fn partition_slice<'a>(&self, items: &'a [T]) -> (Vec<&'a T>, Vec<&'a T>) {
         match &self.set_impl {
-            SetTypes::Scanning(s) => s.contains(value),
-            SetTypes::CommonSmall(s) => s.contains(value),
-            SetTypes::CommonLarge(s) => s.contains(value),
-            SetTypes::U32Small(s) => s.contains(unsafe { transmute(value) }),
-            SetTypes::U32Large(s) => s.contains(unsafe { transmute(value) }),
-            SetTypes::U32Range(s) => s.contains(unsafe { transmute(value) }),
-            SetTypes::LeftStringSliceSmall(s) => {
-                let v: &String = unsafe { transmute(value) };
-                s.contains(v)
-            }
-            SetTypes::LeftStringSliceLarge(s) => {
-                let v: &String = unsafe { transmute(value) };
-                s.contains(v)
-            }
-            SetTypes::RightStringSliceSmall(s) => {
-                let v: &String = unsafe { transmute(value) };
-                s.contains(v)
-            }
-            SetTypes::RightStringSliceLarge(s) => {
-                let v: &String = unsafe { transmute(value) };
-                s.contains(v)
-            }
-            SetTypes::StringLengthSmall(s) => {
-                let v: &String = unsafe { transmute(value) };
-                s.contains(v)
+            SetTypes::Hashed(m) => {
+                let mut found = m.lookup_stream(items);
+                partition(items, |_| found.next().flatten().is_some())
             }
+            SetTypes::Bits(s) => partition(items, |v| s.contains(v)),
+        }
+    }
+
+    /// Wraps an iterator so that it only yields the items which are in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenSet;
+    ///
+    /// let allowed = FrozenSet::from(["a".to_string(), "c".to_string()]);
+    /// let incoming = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+    ///
+    /// let kept: Vec<_> = allowed.filter_contained(incoming).collect();
+    /// assert_eq!(kept, vec!["a".to_string(), "c".to_string()]);
+    /// ```
+    pub fn filter_contained<'a, I>(&'a self, iter: I) -> impl Iterator<Item = I::Item> + 'a
+    where
+        I: IntoIterator,
+        I::Item: Borrow<T>,
+        I::IntoIter: 'a,
+    {
+        iter.into_iter().filter(|item| self.contains(item.borrow()))
+    }
+
+    /// Copies the set's values into a vector, in the order in which [`Self::iter`] visits them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenSet;
+    ///
+    /// let set = FrozenSet::from(["a", "b"]);
+    /// let mut values = set.to_vec();
+    /// values.sort();
+    ///
+    /// assert_eq!(values, vec!["a", "b"]);
+    /// ```
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+
+    /// Consumes the set and returns its values in sorted order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenSet;
+    ///
+    /// let set = FrozenSet::from([3, 1, 2]);
+    /// assert_eq!(set.into_sorted_vec(), vec![1, 2, 3]);
+    /// ```
+    #[must_use]
+    pub fn into_sorted_vec(self) -> Vec<T>
+    where
+        T: Ord,
+    {
+        self.into_sorted_by(Ord::cmp)
+    }
+
+    /// Consumes the set and returns its values sorted with a comparator function.
+    ///
+    /// Values which compare equal end up in an unspecified order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenSet;
+    ///
+    /// let set = FrozenSet::from([3, 1, 2]);
+    /// assert_eq!(set.into_sorted_by(|x, y| y.cmp(x)), vec![3, 2, 1]);
+    /// ```
+    #[must_use]
+    pub fn into_sorted_by<F>(self, compare: F) -> Vec<T>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut values = self.into_vec();
+        values.sort_unstable_by(compare);
+        values
+    }
+
+    /// Consumes the set and returns its values, in the order in which [`Self::iter`] visits them.
+    fn into_vec(self) -> Vec<T> {
+        match self.set_impl {
+            SetTypes::Hashed(m) => m
+                .into_entries()
+                .into_vec()
+                .into_iter()
+                .map(|(v, ())| v)
+                .collect(),
+            SetTypes::Bits(s) => s.into_iter().collect(),
         }
     }
 
@@ -369,19 +587,15 @@ where
     ///     println!("{x}");
     /// }
     /// ```
-    pub fn iter(&self) -> Iter<T> {
+    pub const fn iter(&self) -> Iter<T> {
+        Iter::new(self.entries())
+    }
+
+    /// Returns the entries of the underlying implementation, whose values are all `()`.
+    const fn entries(&self) -> &[(T, ())] {
         match &self.set_impl {
-            SetTypes::Scanning(s) => s.iter(),
-            SetTypes::CommonSmall(s) => s.iter(),
-            SetTypes::CommonLarge(s) => s.iter(),
-            SetTypes::U32Small(s) => unsafe { transmute(s.iter()) },
-            SetTypes::U32Large(s) => unsafe { transmute(s.iter()) },
-            SetTypes::U32Range(s) => unsafe { transmute(s.iter()) },
-            SetTypes::LeftStringSliceSmall(s) => unsafe { transmute(s.iter()) },
-            SetTypes::LeftStringSliceLarge(s) => unsafe { transmute(s.iter()) },
-            SetTypes::RightStringSliceSmall(s) => unsafe { transmute(s.iter()) },
-            SetTypes::RightStringSliceLarge(s) => unsafe { transmute(s.iter()) },
-            SetTypes::StringLengthSmall(s) => unsafe { transmute(s.iter()) },
+            SetTypes::Hashed(m) => m.iter().entries(),
+            SetTypes::Bits(s) => s.entries(),
         }
     }
 
@@ -398,36 +612,29 @@ where
     /// ```
     pub fn get(&self, value: &T) -> Option<&T> {
         match &self.set_impl {
-            SetTypes::Scanning(s) => s.get(value),
-            SetTypes::CommonSmall(s) => s.get(value),
-            SetTypes::CommonLarge(s) => s.get(value),
-            SetTypes::U32Small(s) => unsafe { transmute(s.get(transmute(value))) },
-            SetTypes::U32Large(s) => unsafe { transmute(s.get(transmute(value))) },
-            SetTypes::U32Range(s) => unsafe { transmute(s.get(transmute(value))) },
-            SetTypes::LeftStringSliceSmall(s) => unsafe {
-                let v: &String = transmute(value);
-                transmute(s.get(v))
-            },
-            SetTypes::LeftStringSliceLarge(s) => unsafe {
-                let v: &String = transmute(value);
-                transmute(s.get(v))
-            },
-            SetTypes::RightStringSliceSmall(s) => unsafe {
-                let v: &String = transmute(value);
-                transmute(s.get(v))
-            },
-            SetTypes::RightStringSliceLarge(s) => unsafe {
-                let v: &String = transmute(value);
-                transmute(s.get(v))
-            },
-            SetTypes::StringLengthSmall(s) => unsafe {
-                let v: &String = transmute(value);
-                transmute(s.get(v))
-            },
+            SetTypes::Hashed(m) => m.get_key(value),
+            SetTypes::Bits(s) => s.get(value),
         }
     }
 }
 
+fn partition<T, F>(items: &[T], mut contains: F) -> (Vec<&T>, Vec<&T>)
+where
+    F: FnMut(&T) -> bool,
+{
+    let mut contained = Vec::with_capacity(items.len());
+    let mut not_contained = Vec::new();
+    for item in items {
+        if contains(item) {
+            contained.push(item);
+        } else {
+            not_contained.push(item);
+        }
+    }
+
+    (contained, not_contained)
+}
+
 impl<T> FrozenSet<T, RandomState>
 where
     T: Hash + Eq,
@@ -446,6 +653,72 @@ where
     pub fn from_vec(payload: Vec<T>) -> Self {
         Self::new(payload, RandomState::new())
     }
+
+    /// Creates a new frozen set, returning an error instead of accepting an invalid payload.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenSet;
+    ///
+    /// assert!(FrozenSet::try_from_vec(vec![1, 2, 3]).is_ok());
+    /// assert!(FrozenSet::try_from_vec(vec!["a", "a"]).is_err());
+    /// ```
+    pub fn try_from_vec(payload: Vec<T>) -> std::result::Result<Self, FrozenError> {
+        Self::try_from_vec_with_hasher(payload, RandomState::new())
+    }
+
+    /// Creates a new frozen set, handling duplicate values according to `policy`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate values and the policy is [`DuplicatePolicy::Error`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::errors::FrozenError;
+    /// use frozen_collections_core::facades::{DuplicatePolicy, FrozenSet};
+    ///
+    /// let set = FrozenSet::from_vec_with_policy(vec!["a", "b", "a"], DuplicatePolicy::Error);
+    /// assert_eq!(set.err(), Some(FrozenError::DuplicateKey { index: 2 }));
+    /// ```
+    pub fn from_vec_with_policy(
+        payload: Vec<T>,
+        policy: DuplicatePolicy,
+    ) -> std::result::Result<Self, FrozenError> {
+        Self::from_vec_with_hasher_and_policy(payload, RandomState::new(), policy)
+    }
+}
+
+impl<T> FrozenSet<T, SeededState>
+where
+    T: Hash + Eq,
+{
+    /// Creates a frozen set whose layout, and so iteration order, only depends on the payload
+    /// and the seed, making it identical every time the program runs.
+    ///
+    /// This is meant for reproducible output and snapshot tests: the values' hash codes are
+    /// predictable, so the set shouldn't hold values from untrusted sources. The analysis of
+    /// the values is never bounded in time, since where a bounded analysis stops depends on
+    /// the speed of the machine; see [`AnalysisOptions::max_duration`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenSet;
+    ///
+    /// let set = FrozenSet::from_vec_with_seed(vec!["a", "b", "c"], 42);
+    /// assert!(set.contains(&"b"));
+    /// ```
+    #[must_use]
+    pub fn from_vec_with_seed(payload: Vec<T>, seed: u64) -> Self {
+        Self::new(payload, SeededState::new(seed))
+    }
 }
 
 impl<T, const N: usize> From<[T; N]> for FrozenSet<T, RandomState>
@@ -466,15 +739,86 @@ where
     }
 }
 
-impl<T, BH> Default for FrozenSet<T, BH>
+/// Freezes a hash set, keeping its hash builder.
+///
+/// The values of a hash set are already unique, so this skips the duplicate check done by
+/// [`FrozenSet::try_from_vec`].
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashSet;
+/// use frozen_collections_core::facades::FrozenSet;
+///
+/// let mut set = HashSet::new();
+/// set.insert("a");
+/// set.insert("b");
+///
+/// let set = FrozenSet::from(set);
+/// assert!(set.contains(&"b"));
+/// ```
+impl<T, S> From<HashSet<T, S>> for FrozenSet<T, S>
 where
     T: Hash + Eq,
-    BH: BuildHasher + Default,
+    S: BuildHasher + Clone,
+{
+    fn from(set: HashSet<T, S>) -> Self {
+        let bh = set.hasher().clone();
+        Self::new(Vec::from_iter(set), bh)
+    }
+}
+
+/// Freezes a B-tree set.
+///
+/// The values of a B-tree set are already unique, so this skips the duplicate check done by
+/// [`FrozenSet::try_from_vec`].
+impl<T> From<BTreeSet<T>> for FrozenSet<T, RandomState>
+where
+    T: Hash + Eq,
+{
+    fn from(set: BTreeSet<T>) -> Self {
+        Self::new(Vec::from_iter(set), RandomState::new())
+    }
+}
+
+/// Thaws a frozen set into a hash set, moving its values without cloning them.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashSet;
+/// use frozen_collections_core::facades::FrozenSet;
+///
+/// let set = FrozenSet::from(["a", "b"]);
+///
+/// let mut set = HashSet::from(set);
+/// set.insert("c");
+/// assert_eq!(set.len(), 3);
+/// ```
+impl<T, BH> From<FrozenSet<T, BH>> for HashSet<T, RandomState>
+where
+    T: Hash + Eq,
+    BH: BuildHasher,
+{
+    fn from(set: FrozenSet<T, BH>) -> Self {
+        Self::from_iter(set.into_vec())
+    }
+}
+
+/// Thaws a frozen set into a B-tree set, moving its values without cloning them.
+impl<T, BH> From<FrozenSet<T, BH>> for BTreeSet<T>
+where
+    T: Hash + Ord,
+    BH: BuildHasher,
 {
+    fn from(set: FrozenSet<T, BH>) -> Self {
+        Self::from_iter(set.into_vec())
+    }
+}
+
+impl<T, BH> Default for FrozenSet<T, BH> {
     fn default() -> Self {
-        Self {
-            set_impl: SetTypes::Scanning(ScanningSet::<T>::from_vec(vec![])),
-        }
+        Self::empty()
     }
 }
 
@@ -484,19 +828,7 @@ where
     T: Debug,
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        match &self.set_impl {
-            SetTypes::Scanning(s) => s.fmt(f),
-            SetTypes::CommonSmall(s) => s.fmt(f),
-            SetTypes::CommonLarge(s) => s.fmt(f),
-            SetTypes::U32Small(s) => s.fmt(f),
-            SetTypes::U32Large(s) => s.fmt(f),
-            SetTypes::U32Range(s) => s.fmt(f),
-            SetTypes::LeftStringSliceSmall(s) => s.fmt(f),
-            SetTypes::LeftStringSliceLarge(s) => s.fmt(f),
-            SetTypes::RightStringSliceSmall(s) => s.fmt(f),
-            SetTypes::RightStringSliceLarge(s) => s.fmt(f),
-            SetTypes::StringLengthSmall(s) => s.fmt(f),
-        }
+        f.debug_set().entries(self.iter()).finish()
     }
 }
 */
@@ -522,6 +854,173 @@ where
 {
 }
 
+/// Hashes the values of the set regardless of their order, so sets which compare equal hash
+/// the same however their values are stored.
+impl<T, BH> Hash for FrozenSet<T, BH>
+where
+    T: Hash + Eq,
+    BH: BuildHasher,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self, state);
+    }
+}
+
+impl<T, BH, S> PartialEq<HashSet<T, S>> for FrozenSet<T, BH>
+where
+    T: Hash + Eq,
+    BH: BuildHasher,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &HashSet<T, S>) -> bool {
+        // probe the frozen set, which is the faster of the two
+        self.len() == other.len() && self.contains_all(other)
+    }
+}
+
+impl<T, BH, S> PartialEq<FrozenSet<T, BH>> for HashSet<T, S>
+where
+    T: Hash + Eq,
+    BH: BuildHasher,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &FrozenSet<T, BH>) -> bool {
+        other == self
+    }
+}
+
+impl<T, BH> PartialEq<BTreeSet<T>> for FrozenSet<T, BH>
+where
+    T: Hash + Eq,
+    BH: BuildHasher,
+{
+    fn eq(&self, other: &BTreeSet<T>) -> bool {
+        self.len() == other.len() && self.contains_all(other)
+    }
+}
+
+impl<T, BH> PartialEq<FrozenSet<T, BH>> for BTreeSet<T>
+where
+    T: Hash + Eq,
+    BH: BuildHasher,
+{
+    fn eq(&self, other: &FrozenSet<T, BH>) -> bool {
+        other == self
+    }
+}
+
+/// Set operations whose results are frozen again, instead of being collected into a [`HashSet`]
+/// like the results of the `|`, `&`, `^`, and `-` operators.
+///
+/// The combined values are analyzed from scratch, so the result gets whichever implementation
+/// best suits it, regardless of how the operands were laid out. When both operands store their
+/// values as bit vectors, the vectors are combined a word at a time rather than probing one set
+/// for every value of the other.
+impl<T, BH> FrozenSet<T, BH>
+where
+    T: Hash + Eq + Clone,
+    BH: BuildHasher + Default,
+{
+    /// Returns a frozen set holding the values which are in `self`, in `other`, or in both.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenSet;
+    ///
+    /// let a = FrozenSet::from([1, 2, 3]);
+    /// let b = FrozenSet::from([3, 4]);
+    ///
+    /// assert!(a.union_frozen(&b) == FrozenSet::from([1, 2, 3, 4]));
+    /// ```
+    #[must_use]
+    pub fn union_frozen<ST: Set<T>>(&self, other: &ST) -> Self {
+        self.combine_bits(other, |x, y| x | y).unwrap_or_else(|| {
+            Self::from_vec_with_hasher(self.union(other).cloned().collect(), BH::default())
+        })
+    }
+
+    /// Returns a frozen set holding the values which are in both `self` and `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenSet;
+    ///
+    /// let a = FrozenSet::from([1, 2, 3]);
+    /// let b = FrozenSet::from([3, 4]);
+    ///
+    /// assert!(a.intersection_frozen(&b) == FrozenSet::from([3]));
+    /// ```
+    #[must_use]
+    pub fn intersection_frozen<ST: Set<T>>(&self, other: &ST) -> Self {
+        self.combine_bits(other, |x, y| x & y).unwrap_or_else(|| {
+            Self::from_vec_with_hasher(self.intersection(other).cloned().collect(), BH::default())
+        })
+    }
+
+    /// Returns a frozen set holding the values which are in `self` but not in `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenSet;
+    ///
+    /// let a = FrozenSet::from([1, 2, 3]);
+    /// let b = FrozenSet::from([3, 4]);
+    ///
+    /// assert!(a.difference_frozen(&b) == FrozenSet::from([1, 2]));
+    /// ```
+    #[must_use]
+    pub fn difference_frozen<ST: Set<T>>(&self, other: &ST) -> Self {
+        self.combine_bits(other, |x, y| x & !y).unwrap_or_else(|| {
+            Self::from_vec_with_hasher(self.difference(other).cloned().collect(), BH::default())
+        })
+    }
+
+    /// Returns a frozen set holding the values which are in exactly one of `self` and `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenSet;
+    ///
+    /// let a = FrozenSet::from([1, 2, 3]);
+    /// let b = FrozenSet::from([3, 4]);
+    ///
+    /// assert!(a.symmetric_difference_frozen(&b) == FrozenSet::from([1, 2, 4]));
+    /// ```
+    #[must_use]
+    pub fn symmetric_difference_frozen<ST: Set<T>>(&self, other: &ST) -> Self {
+        self.combine_bits(other, |x, y| x ^ y).unwrap_or_else(|| {
+            Self::from_vec_with_hasher(
+                self.symmetric_difference(other).cloned().collect(),
+                BH::default(),
+            )
+        })
+    }
+
+    /// Combines the bit vectors of two sets a word at a time, or returns `None` if either set
+    /// isn't stored as a bit vector or the two lie too far apart to be combined into one.
+    fn combine_bits<ST>(&self, other: &ST, op: fn(usize, usize) -> usize) -> Option<Self>
+    where
+        ST: Set<T>,
+    {
+        let bits = self.as_bit_set()?.merge(other.as_bit_set()?, op)?;
+
+        // the combined values still get whichever implementation suits them best
+        let len = bits.len();
+        let span = bits.span();
+        if len >= 4 && span + 1 != len as u64 && is_dense(len, span, MIN_BIT_SET_DENSITY_PERCENT) {
+            return Some(Self {
+                set_impl: SetTypes::Bits(bits),
+            });
+        }
+
+        Some(Self::new(bits.into_iter().collect(), BH::default()))
+    }
+}
+
 impl<T, ST, BH> BitOr<&ST> for &FrozenSet<T, BH>
 where
     T: Hash + Eq + Clone,
@@ -587,20 +1086,83 @@ where
     }
 }
 
+#[cfg(feature = "rayon")]
+impl<T, BH> FrozenSet<T, BH>
+where
+    T: Hash + Eq + Sync,
+    BH: BuildHasher,
+{
+    /// A parallel iterator visiting all elements.
+    /// The iterator element type is `&'a T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenSet;
+    /// use rayon::prelude::*;
+    ///
+    /// let set = FrozenSet::from_iter(1..=100);
+    /// assert_eq!(set.par_iter().sum::<i32>(), 5050);
+    /// ```
+    pub fn par_iter(&self) -> impl IndexedParallelIterator<Item = &T> {
+        self.into_par_iter()
+    }
+}
+
+#[cfg(feature = "rayon")]
+type ParIter<'a, T> = rayon::iter::Map<rayon::slice::Iter<'a, (T, ())>, fn(&'a (T, ())) -> &'a T>;
+
+#[cfg(feature = "rayon")]
+impl<'a, T, BH> IntoParallelIterator for &'a FrozenSet<T, BH>
+where
+    T: Hash + Eq + Sync,
+    BH: BuildHasher,
+{
+    type Item = &'a T;
+    type Iter = ParIter<'a, T>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.entries().par_iter().map(entry_value)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T, BH> IntoParallelIterator for FrozenSet<T, BH>
+where
+    T: Hash + Eq + Send,
+    BH: BuildHasher,
+{
+    type Item = T;
+    type Iter = rayon::vec::IntoIter<T>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.into_vec().into_par_iter()
+    }
+}
+
+/// Returns the value held by the entry of a set's underlying map.
+#[cfg(feature = "rayon")]
+const fn entry_value<T>(entry: &(T, ())) -> &T {
+    &entry.0
+}
+
 impl<T, BH> Len for FrozenSet<T, BH> {
     fn len(&self) -> usize {
         match &self.set_impl {
-            SetTypes::Scanning(s) => Len::len(s),
-            SetTypes::CommonSmall(s) => Len::len(s),
-            SetTypes::CommonLarge(s) => Len::len(s),
-            SetTypes::U32Small(s) => Len::len(s),
-            SetTypes::U32Large(s) => Len::len(s),
-            SetTypes::U32Range(s) => Len::len(s),
-            SetTypes::LeftStringSliceSmall(s) => Len::len(s),
-            SetTypes::LeftStringSliceLarge(s) => Len::len(s),
-            SetTypes::RightStringSliceSmall(s) => Len::len(s),
-            SetTypes::RightStringSliceLarge(s) => Len::len(s),
-            SetTypes::StringLengthSmall(s) => Len::len(s),
+            SetTypes::Hashed(m) => m.len(),
+            SetTypes::Bits(s) => s.len(),
+        }
+    }
+}
+
+impl<T, BH> HeapSize for FrozenSet<T, BH>
+where
+    T: HeapSize,
+{
+    fn heap_bytes(&self) -> usize {
+        match &self.set_impl {
+            SetTypes::Hashed(m) => m.heap_bytes(),
+            SetTypes::Bits(s) => s.heap_bytes(),
         }
     }
 }
@@ -610,7 +1172,8 @@ where
     T: Hash + Eq,
     BH: BuildHasher,
 {
-    type Iterator<'a> = Iter<'a, T>
+    type Iterator<'a>
+        = Iter<'a, T>
     where
         T: 'a,
         BH: 'a;
@@ -622,4 +1185,56 @@ where
     fn contains(&self, value: &T) -> bool {
         self.contains(value)
     }
+
+    fn as_bit_set(&self) -> Option<&BitSet<T>> {
+        match &self.set_impl {
+            SetTypes::Hashed(_) => None,
+            SetTypes::Bits(s) => Some(s),
+        }
+    }
+}
+
+/// Wipes the values and hash table metadata of the set, leaving it empty.
+///
+/// To have the set wiped automatically when it's dropped, use a [`ZeroizingFrozenSet`](crate::facades::ZeroizingFrozenSet).
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::FrozenSet;
+/// use zeroize::Zeroize;
+///
+/// let mut set = FrozenSet::from(["token-1".to_string(), "token-2".to_string()]);
+/// set.zeroize();
+///
+/// assert!(set.is_empty());
+/// assert!(!set.contains(&"token-1".to_string()));
+/// ```
+#[cfg(feature = "zeroize")]
+impl<T, BH> Zeroize for FrozenSet<T, BH>
+where
+    T: Zeroize,
+{
+    fn zeroize(&mut self) {
+        match &mut self.set_impl {
+            SetTypes::Hashed(m) => m.zeroize(),
+            SetTypes::Bits(s) => s.zeroize(),
+        }
+    }
+}
+
+/// Accounts for the set in memory reports built with the `deepsize` crate.
+#[cfg(feature = "deepsize")]
+impl<T, BH> DeepSizeOf for FrozenSet<T, BH>
+where
+    T: DeepSizeOf,
+{
+    fn deep_size_of_children(&self, context: &mut deepsize::Context) -> usize {
+        match &self.set_impl {
+            SetTypes::Hashed(m) => m.deep_size_of_children(context),
+            SetTypes::Bits(s) => s.iter().fold(s.table_bytes(), |bytes, value| {
+                bytes + value.deep_size_of_children(context)
+            }),
+        }
+    }
 }
@@ -1,6 +1,7 @@
 use std::collections::HashSet;
 
 use crate::facades::frozen_set::FrozenSet;
+use crate::traits::heap_size::HeapSize;
 use crate::traits::len::Len;
 
 #[test]
@@ -32,3 +33,346 @@ fn misc() {
         }
     }
 }
+
+#[test]
+fn dense_integers() {
+    let values: Vec<u32> = (0..1000).map(|i| 5000 + i * 7).collect();
+    let s = FrozenSet::from_vec(values.clone());
+    assert_eq!(1000, s.len());
+
+    for v in 4990..12010 {
+        let expected = (5000..12000).contains(&v) && (v - 5000) % 7 == 0;
+        assert_eq!(expected, s.contains(&v), "{v}");
+        assert_eq!(expected, s.get(&v) == Some(&v), "{v}");
+    }
+
+    assert_eq!(values, s.clone().into_sorted_vec());
+    assert!(s == values.iter().rev().copied().collect::<FrozenSet<_>>());
+
+    let s = FrozenSet::from_vec_with_policy(
+        vec![-3i64, -1, 1, 3, -3, 5],
+        crate::facades::DuplicatePolicy::KeepFirst,
+    )
+    .unwrap();
+    assert_eq!(5, s.len());
+    assert!(s.contains(&-3));
+    assert!(!s.contains(&-2));
+    assert_eq!(vec![-3, -1, 1, 3, 5], s.into_sorted_vec());
+}
+
+#[test]
+fn partition_slice() {
+    let s = FrozenSet::from_vec((0..100u32).filter(|x| x % 3 == 0).collect());
+    let items: Vec<u32> = (0..20).collect();
+
+    let (contained, not_contained) = s.partition_slice(&items);
+    assert_eq!(vec![&0, &3, &6, &9, &12, &15, &18], contained);
+    assert_eq!(13, not_contained.len());
+    assert!(not_contained.iter().all(|x| !s.contains(x)));
+
+    let s = FrozenSet::from_vec(vec!["Key-001".to_string(), "Key-002".to_string()]);
+    let items = vec!["Key-002".to_string(), "Key-003".to_string()];
+    let (contained, not_contained) = s.partition_slice(&items);
+    assert_eq!(vec![&items[0]], contained);
+    assert_eq!(vec![&items[1]], not_contained);
+
+    // enough items for the lookups of a hashed set to be streamed
+    let s = FrozenSet::from_vec((0..1000u64).map(|x| x * 1_000_003).collect());
+    let items: Vec<u64> = (0..2000).map(|x| x * 1_000_003 + x % 2).collect();
+    let (contained, not_contained) = s.partition_slice(&items);
+    let expected: Vec<_> = items.iter().step_by(2).take(500).collect();
+    assert_eq!(expected, contained);
+    assert_eq!(1500, not_contained.len());
+    assert!(not_contained.iter().all(|x| !s.contains(x)));
+}
+
+#[test]
+fn filter_contained() {
+    let s = FrozenSet::from([1, 2, 3]);
+
+    let by_ref: Vec<_> = s.filter_contained(&[0, 1, 3, 5]).collect();
+    assert_eq!(vec![&1, &3], by_ref);
+
+    let by_value: Vec<_> = s.filter_contained(vec![3, 4, 2]).collect();
+    assert_eq!(vec![3, 2], by_value);
+}
+
+#[test]
+fn contains_all_and_any() {
+    // covers both bit sets and hashed sets
+    for s in [
+        FrozenSet::from_vec((0..300u32).map(|x| x * 3).collect()),
+        FrozenSet::from_vec((0..300u32).map(|x| x * 3000).collect()),
+    ] {
+        let present: Vec<_> = s.iter().copied().step_by(7).collect();
+        assert!(s.contains_all(&present));
+        assert!(s.contains_all(present.iter().rev()));
+        assert!(!s.contains_all([0, 1]));
+        assert!(s.contains_all(Vec::<u32>::new()));
+
+        assert!(s.contains_any(vec![1, 2, 0]));
+        assert!(!s.contains_any([1, 2, 4]));
+        assert!(!s.contains_any([0u32; 0]));
+    }
+}
+
+#[test]
+fn sorted_exports() {
+    let values: Vec<u32> = (0..300).rev().collect();
+    let s = FrozenSet::from_vec(values.clone());
+
+    let mut copied = s.to_vec();
+    copied.sort_unstable();
+    assert_eq!((0..300).collect::<Vec<_>>(), copied);
+    assert_eq!((0..300).collect::<Vec<_>>(), s.clone().into_sorted_vec());
+    assert_eq!(values, s.into_sorted_by(|x, y| y.cmp(x)));
+
+    let s = FrozenSet::from_vec(vec!["Key-002".to_string(), "Key-001".to_string()]);
+    assert_eq!(vec!["Key-001", "Key-002"], s.into_sorted_vec());
+}
+
+#[test]
+#[cfg(feature = "zeroize")]
+fn zeroize() {
+    use zeroize::{Zeroize, Zeroizing};
+
+    let mut s = FrozenSet::from_vec((0..300).map(|i| format!("Token-{i:03}")).collect());
+    s.zeroize();
+    assert!(s.is_empty());
+    assert!(!s.contains(&"Token-001".to_string()));
+    assert!(!s.contains(&String::new()));
+
+    let s = Zeroizing::new(FrozenSet::from_vec(vec![1u64, 2, 3, 4, 5]));
+    assert!(s.contains(&3));
+
+    let mut s = FrozenSet::from_vec(vec![1u64, 3, 5, 7, 9]);
+    s.zeroize();
+    assert!(s.is_empty());
+    assert!(!s.contains(&3));
+}
+
+#[test]
+fn empty() {
+    let s = FrozenSet::<String>::empty();
+    assert!(s.is_empty());
+    assert!(!s.contains(&String::new()));
+
+    let s: FrozenSet<u32> = FrozenSet::default();
+    assert!(!s.contains(&0));
+    assert_eq!(0, s.iter().count());
+}
+
+#[test]
+fn custom_values() {
+    #[derive(Debug, Hash, PartialEq, Eq)]
+    struct Port(u16);
+
+    let s: FrozenSet<_> = (0..100).map(|i| Port(i * 2)).collect();
+    assert!(s.contains(&Port(198)));
+    assert!(!s.contains(&Port(199)));
+    assert_eq!(Some(&Port(4)), s.get(&Port(4)));
+
+    let s: FrozenSet<_> = (0..300)
+        .map(|i| format!("{}-{i}", "x".repeat(i % 5)))
+        .collect();
+    assert_eq!(300, s.len());
+    assert!(s.contains(&"xx-2".to_string()));
+    assert!(!s.contains(&"xx-3".to_string()));
+}
+
+#[test]
+fn std_conversions() {
+    use std::collections::BTreeSet;
+
+    let hashed: HashSet<_> = (0..100).map(|i| format!("Value-{i:03}")).collect();
+    let s = FrozenSet::from(hashed.clone());
+    assert_eq!(100, s.len());
+    assert!(s.contains(&"Value-042".to_string()));
+    assert_eq!(hashed, HashSet::from(s));
+
+    let ordered: BTreeSet<_> = (0..100).collect();
+    let s = FrozenSet::from(ordered.clone());
+    assert_eq!(100, s.len());
+    assert!(s.contains(&42));
+    assert_eq!(ordered, BTreeSet::from(s));
+}
+
+#[test]
+fn std_equality() {
+    use std::collections::BTreeSet;
+
+    // covers both bit sets and hashed sets
+    for step in [3, 3000] {
+        let hashed: HashSet<u32> = (0..100).map(|i| i * step).collect();
+        let s = FrozenSet::from(hashed.clone());
+        assert!(s == hashed);
+        assert!(hashed == s);
+
+        let ordered: BTreeSet<u32> = hashed.iter().copied().collect();
+        assert!(s == ordered);
+        assert!(ordered == s);
+
+        let mut changed = hashed.clone();
+        changed.remove(&0);
+        assert!(s != changed);
+        changed.insert(1);
+        assert!(s != changed);
+        assert!(changed != s);
+
+        let ordered: BTreeSet<u32> = changed.into_iter().collect();
+        assert!(s != ordered);
+        assert!(ordered != s);
+    }
+}
+
+#[test]
+fn hash_matches_equality() {
+    use std::hash::BuildHasher;
+
+    use crate::hashers::SeededState;
+
+    // covers both bit sets and hashed sets
+    for step in [3, 3000] {
+        let values: Vec<u32> = (0..100).map(|i| i * step).collect();
+        let a = FrozenSet::from_vec(values.clone());
+        let b = FrozenSet::from_vec(values.iter().rev().copied().collect());
+        let c = FrozenSet::from_vec(values[1..].to_vec());
+
+        let hash = |s: &FrozenSet<u32>| SeededState::new(0).hash_one(s);
+        assert_eq!(hash(&a), hash(&b));
+        assert_ne!(hash(&a), hash(&c));
+    }
+}
+
+#[test]
+fn seeded_layout() {
+    let values: Vec<_> = (0..500).map(|i| format!("value{i}")).collect();
+    let a = FrozenSet::from_vec_with_seed(values.clone(), 7);
+    let b = FrozenSet::from_vec_with_seed(values, 7);
+    assert!(a.iter().eq(b.iter()));
+    assert!(a.contains(&"value42".to_string()));
+}
+
+#[test]
+fn frozen_set_ops() {
+    fn check<T: std::hash::Hash + Eq + Clone + std::fmt::Debug>(a: &[T], b: &[T]) {
+        let fa = FrozenSet::from_vec(a.to_vec());
+        let fb = FrozenSet::from_vec(b.to_vec());
+        let ha: HashSet<_> = a.iter().cloned().collect();
+        let hb: HashSet<_> = b.iter().cloned().collect();
+
+        let check_op = |frozen: FrozenSet<T>, expected: HashSet<T>| {
+            assert_eq!(expected.len(), frozen.len());
+            assert!(expected.iter().all(|v| frozen.contains(v)));
+        };
+
+        check_op(fa.union_frozen(&fb), &ha | &hb);
+        check_op(fa.intersection_frozen(&fb), &ha & &hb);
+        check_op(fa.difference_frozen(&fb), &ha - &hb);
+        check_op(fa.symmetric_difference_frozen(&fb), &ha ^ &hb);
+
+        // the operand needn't be frozen itself
+        check_op(fa.union_frozen(&hb), &ha | &hb);
+        assert!(fa.union_frozen(&fb) == &fa | &fb);
+    }
+
+    let evens: Vec<u32> = (0..300).map(|i| i * 2).collect();
+    let threes: Vec<u32> = (0..300).map(|i| i * 3).collect();
+    check(&evens, &threes);
+    check(&evens, &[]);
+    check(&[], &threes);
+
+    // results which are a continuous range, or too sparse for a bit vector
+    let odds: Vec<u32> = (0..300).map(|i| i * 2 + 1).collect();
+    let far: Vec<u32> = (0..300).map(|i| 10_000_000 + i * 2).collect();
+    check(&evens, &odds);
+    check(&evens, &far);
+    check(&evens, &evens[..3]);
+
+    {
+        use crate::specialized_sets::Set;
+
+        let a = FrozenSet::from_vec(evens);
+        let b = FrozenSet::from_vec(threes);
+        assert!(a.union_frozen(&b).as_bit_set().is_some());
+        assert!(a.union_frozen(&FrozenSet::from_vec(odds)).as_bit_set().is_none());
+        assert!(a.union_frozen(&FrozenSet::from_vec(far)).as_bit_set().is_none());
+    }
+
+    let a: Vec<_> = (0..100).map(|i| format!("Key-{i:03}")).collect();
+    let b: Vec<_> = (50..150).map(|i| format!("Key-{i:03}")).collect();
+    check(&a, &b);
+    check(&a, &a);
+}
+
+#[test]
+fn subsets_and_disjointness() {
+    use crate::specialized_sets::Set;
+
+    let evens: FrozenSet<u32> = (0..300).map(|i| i * 2).collect();
+    let fours: FrozenSet<u32> = (0..100).map(|i| i * 4).collect();
+    let odds: FrozenSet<u32> = (0..300).map(|i| i * 2 + 1).collect();
+    let hashed: HashSet<u32> = (0..100).map(|i| i * 4).collect();
+
+    assert!(fours.is_subset(&evens));
+    assert!(!evens.is_subset(&fours));
+    assert!(evens.is_superset(&fours));
+    assert!(evens.is_disjoint(&odds));
+    assert!(!evens.is_disjoint(&fours));
+
+    assert!(Set::is_subset(&hashed, &evens));
+    assert!(evens.is_superset(&hashed));
+    assert!(odds.is_disjoint(&hashed));
+}
+
+#[test]
+fn double_ended_iter() {
+    for len in [0, 3, 100, 2000] {
+        let s: FrozenSet<_> = (0..len).map(|i| i * 3).collect();
+        let forward: Vec<_> = s.iter().copied().collect();
+
+        assert!(s.iter().rev().eq(forward.iter().rev()));
+        assert_eq!(forward.last(), s.iter().last());
+        assert_eq!(forward.get(5), s.iter().nth(5));
+        assert_eq!(forward.iter().rev().nth(5), s.iter().nth_back(5));
+        assert_eq!(forward.len(), s.iter().count());
+        assert_eq!(
+            forward.iter().sum::<i32>(),
+            s.iter().rfold(0, |acc, x| acc + x)
+        );
+
+        let mut iter = s.iter();
+        iter.next_back();
+        assert_eq!(forward.len().saturating_sub(1), iter.count());
+    }
+}
+
+#[test]
+fn heap_bytes() {
+    // a bit set, with a bit per integer of the range next to the values
+    let s: FrozenSet<_> = (0..1000u32).map(|i| i * 3).collect();
+    assert!(s.heap_bytes() >= 1000 * size_of::<u32>() + 3000 / 8);
+    assert!(s.heap_bytes() < 2 * (1000 * size_of::<u32>() + 3000 / 8));
+
+    let values: Vec<_> = (0..100).map(|i| format!("value{i}")).collect();
+    let owned: usize = values.iter().map(String::capacity).sum();
+    let s = FrozenSet::from_vec(values);
+    assert!(s.heap_bytes() >= 100 * size_of::<String>() + owned);
+    assert_eq!(size_of_val(&s) + s.heap_bytes(), s.deep_size_of());
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn par_iter() {
+    use rayon::prelude::*;
+
+    for len in [0, 3, 100, 2000] {
+        let s: FrozenSet<_> = (0..len).collect();
+        assert_eq!(s.iter().sum::<i32>(), s.par_iter().sum::<i32>());
+        assert_eq!(s.len(), (&s).into_par_iter().count());
+
+        let mut values: Vec<_> = s.clone().into_par_iter().collect();
+        values.sort_unstable();
+        assert_eq!((0..len).collect::<Vec<_>>(), values);
+    }
+}
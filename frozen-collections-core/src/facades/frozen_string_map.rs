@@ -0,0 +1,175 @@
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::BuildHasher;
+use std::hash::RandomState;
+use std::ops::Index;
+
+use crate::facades::ArenaFrozenMap;
+use crate::traits::len::Len;
+
+/// A frozen map from strings which owns the text of its keys.
+///
+/// A [`FrozenMap`](crate::facades::FrozenMap) of `&str` keys needs the strings to outlive the
+/// map, while one of [`String`] keys allocates each key separately and makes lookups from
+/// anything other than a `&str` awkward. This map takes its keys as anything that can be seen
+/// as a `&str`, copies their text into storage of its own, and hands them back out as `&str`.
+/// Lookups likewise accept any `AsRef<str>`, such as `&str`, `String`, or `&String`.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::FrozenStringMap;
+///
+/// let map = FrozenStringMap::from([("red", 1), ("green", 2), ("blue", 3)]);
+///
+/// let key = String::from("green");
+/// assert_eq!(map.get(&key), Some(&2));
+/// assert_eq!(map.get("blue"), Some(&3));
+/// assert_eq!(map["red"], 1);
+/// assert!(!map.contains_key("yellow"));
+/// ```
+#[allow(clippy::module_name_repetitions)]
+pub struct FrozenStringMap<V, BH = RandomState> {
+    map: ArenaFrozenMap<V, BH>,
+}
+
+impl<V, BH> FrozenStringMap<V, BH>
+where
+    BH: BuildHasher,
+{
+    /// Creates a frozen map which will use the given hash builder to hash keys.
+    #[must_use]
+    pub fn from_vec_with_hasher<K>(payload: Vec<(K, V)>, bh: BH) -> Self
+    where
+        K: AsRef<str>,
+    {
+        Self {
+            map: ArenaFrozenMap::from_vec_with_hasher(payload, bh),
+        }
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    #[inline]
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: ?Sized + AsRef<str>,
+    {
+        self.map.get(key.as_ref())
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    #[inline]
+    #[must_use]
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&str, &V)>
+    where
+        Q: ?Sized + AsRef<str>,
+    {
+        self.map.get_key_value(key.as_ref())
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    #[inline]
+    #[must_use]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        Q: ?Sized + AsRef<str>,
+    {
+        self.map.get_mut(key.as_ref())
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    #[inline]
+    #[must_use]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + AsRef<str>,
+    {
+        self.map.contains_key(key.as_ref())
+    }
+
+    /// Returns the number of elements in the map.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// An iterator visiting all key-value pairs in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &V)> {
+        self.map.iter()
+    }
+
+    /// An iterator visiting all keys in arbitrary order.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.map.keys()
+    }
+
+    /// An iterator visiting all values in arbitrary order.
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.map.values()
+    }
+}
+
+impl<V> FrozenStringMap<V, RandomState> {
+    /// Creates a frozen map.
+    #[must_use]
+    pub fn from_vec<K>(payload: Vec<(K, V)>) -> Self
+    where
+        K: AsRef<str>,
+    {
+        Self::from_vec_with_hasher(payload, RandomState::new())
+    }
+}
+
+impl<K, V, const N: usize> From<[(K, V); N]> for FrozenStringMap<V, RandomState>
+where
+    K: AsRef<str>,
+{
+    fn from(payload: [(K, V); N]) -> Self {
+        Self::from_vec(Vec::from_iter(payload))
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for FrozenStringMap<V, RandomState>
+where
+    K: AsRef<str>,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self::from_vec(Vec::from_iter(iter))
+    }
+}
+
+impl<V, BH> Index<&str> for FrozenStringMap<V, BH>
+where
+    BH: BuildHasher,
+{
+    type Output = V;
+
+    fn index(&self, index: &str) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<V, BH> Len for FrozenStringMap<V, BH>
+where
+    BH: BuildHasher,
+{
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+}
+
+impl<V, BH> Debug for FrozenStringMap<V, BH>
+where
+    V: Debug,
+    BH: BuildHasher,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.map.fmt(f)
+    }
+}
@@ -0,0 +1,55 @@
+use crate::facades::FrozenStringMap;
+use crate::traits::len::Len;
+
+#[test]
+fn lookups_accept_any_string() {
+    let keys: Vec<String> = (0..200).map(|i| format!("Key-{i}")).collect();
+    let mut map: FrozenStringMap<_> = keys.iter().zip(0..).collect();
+    assert_eq!(keys.len(), map.len());
+    assert_eq!(keys.len(), Len::len(&map));
+    assert!(!map.is_empty());
+
+    for (i, key) in keys.iter().enumerate() {
+        assert_eq!(Some(&i), map.get(key));
+        assert_eq!(Some(&i), map.get(key.as_str()));
+        assert_eq!(Some(&i), map.get(&key.clone()));
+        assert_eq!(Some((key.as_str(), &i)), map.get_key_value(key));
+        assert!(map.contains_key(key));
+        assert_eq!(i, map[key.as_str()]);
+
+        *map.get_mut(key).unwrap() *= 2;
+        assert_eq!(Some(&(i * 2)), map.get(key));
+    }
+
+    assert_eq!(None, map.get("Key-200"));
+    assert!(!map.contains_key(&String::new()));
+    assert_eq!(keys.len(), map.keys().count());
+    assert_eq!(keys.len(), map.values().count());
+    assert_eq!(keys.len(), map.iter().count());
+}
+
+#[test]
+fn keys_outlive_their_source() {
+    let map = {
+        let key = String::from("temporary");
+        FrozenStringMap::from([(key, 1)])
+    };
+
+    assert_eq!(vec![("temporary", &1)], map.iter().collect::<Vec<_>>());
+    assert_eq!(r#"{"temporary": 1}"#, format!("{map:?}"));
+}
+
+#[test]
+fn empty() {
+    let map = FrozenStringMap::<i32>::from_vec(Vec::<(&str, i32)>::new());
+    assert!(map.is_empty());
+    assert_eq!(None, map.get("a"));
+    assert_eq!("{}", format!("{map:?}"));
+}
+
+#[test]
+#[should_panic(expected = "called `Option::unwrap()` on a `None` value")]
+fn index_missing() {
+    let map = FrozenStringMap::from([("a", 1)]);
+    _ = map["b"];
+}
@@ -0,0 +1,294 @@
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::RandomState;
+use std::hash::{BuildHasher, Hash};
+
+use crate::errors::FrozenError;
+use crate::facades::{FrozenMap, SecondaryIndex};
+use crate::traits::equivalent::Equivalent;
+use crate::traits::len::Len;
+
+/// A fixed list of records which can be looked up through several indexes.
+///
+/// Looking records up by more than one of their fields normally takes one map per field, each
+/// with its own copy of the records. A table stores the records once, and its indexes only map
+/// keys derived from the records to the positions of the records. Indexes are either unique,
+/// built with [`unique_index_by`](Self::unique_index_by), where every record has a distinct key,
+/// or not, built with [`index_by`](Self::index_by), where several records may share a key.
+///
+/// An index refers to the records of the table it was built from, and means nothing for any
+/// other table.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::FrozenTable;
+///
+/// struct Country {
+///     code: &'static str,
+///     id: u16,
+///     name: &'static str,
+///     continent: &'static str,
+/// }
+///
+/// let table = FrozenTable::from_vec(vec![
+///     Country { code: "FR", id: 250, name: "France", continent: "Europe" },
+///     Country { code: "JP", id: 392, name: "Japan", continent: "Asia" },
+///     Country { code: "DE", id: 276, name: "Germany", continent: "Europe" },
+/// ]);
+///
+/// let by_code = table.unique_index_by(|c| c.code).unwrap();
+/// let by_id = table.unique_index_by(|c| c.id).unwrap();
+/// let by_continent = table.index_by(|c| c.continent);
+///
+/// assert_eq!(table.find(&by_code, "JP").map(|c| c.name), Some("Japan"));
+/// assert_eq!(table.find(&by_id, &276).map(|c| c.name), Some("Germany"));
+/// assert_eq!(table.find_all(&by_continent, "Europe").count(), 2);
+/// ```
+#[derive(Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct FrozenTable<R> {
+    records: Box<[R]>,
+}
+
+impl<R> FrozenTable<R> {
+    /// Creates a table holding the given records, in order.
+    #[must_use]
+    pub fn from_vec(records: Vec<R>) -> Self {
+        Self {
+            records: records.into_boxed_slice(),
+        }
+    }
+
+    /// Builds an index over the records where every record has a distinct key.
+    ///
+    /// # Errors
+    ///
+    /// Fails if two records have the same key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::errors::FrozenError;
+    /// use frozen_collections_core::facades::FrozenTable;
+    ///
+    /// let table = FrozenTable::from_vec(vec![("a", 1), ("b", 2), ("a", 3)]);
+    ///
+    /// assert!(table.unique_index_by(|r| r.1).is_ok());
+    /// assert_eq!(
+    ///     table.unique_index_by(|r| r.0).err(),
+    ///     Some(FrozenError::DuplicateKey { index: 2 })
+    /// );
+    /// ```
+    pub fn unique_index_by<K, F>(&self, f: F) -> std::result::Result<UniqueIndex<K>, FrozenError>
+    where
+        K: Hash + Eq,
+        F: Fn(&R) -> K,
+    {
+        self.unique_index_by_with_hasher(f, RandomState::new())
+    }
+
+    /// Builds an index over the records where every record has a distinct key, which will use
+    /// the given hash builder to hash keys.
+    ///
+    /// # Errors
+    ///
+    /// Fails if two records have the same key.
+    pub fn unique_index_by_with_hasher<K, F, BH>(
+        &self,
+        f: F,
+        bh: BH,
+    ) -> std::result::Result<UniqueIndex<K, BH>, FrozenError>
+    where
+        K: Hash + Eq,
+        F: Fn(&R) -> K,
+        BH: BuildHasher,
+    {
+        let payload = self.records.iter().map(f).zip(0..).collect();
+        Ok(UniqueIndex {
+            positions: FrozenMap::try_from_vec_with_hasher(payload, bh)?,
+        })
+    }
+
+    /// Builds an index over the records where several records may have the same key.
+    #[must_use]
+    pub fn index_by<K, F>(&self, f: F) -> SecondaryIndex<K>
+    where
+        K: Hash + Eq,
+        F: Fn(&R) -> K,
+    {
+        self.index_by_with_hasher(f, RandomState::new())
+    }
+
+    /// Builds an index over the records where several records may have the same key, which
+    /// will use the given hash builder to hash keys.
+    #[must_use]
+    pub fn index_by_with_hasher<K, F, BH>(&self, f: F, bh: BH) -> SecondaryIndex<K, BH>
+    where
+        K: Hash + Eq,
+        F: Fn(&R) -> K,
+        BH: BuildHasher,
+    {
+        SecondaryIndex::new(self.records.iter().map(f), bh)
+    }
+
+    /// Returns the record with the given key in a unique index.
+    ///
+    /// # Panics
+    ///
+    /// May panic if the index wasn't built from this table.
+    #[must_use]
+    pub fn find<K, Q, BH>(&self, index: &UniqueIndex<K, BH>, key: &Q) -> Option<&R>
+    where
+        K: Hash + Eq,
+        Q: ?Sized + Hash + Equivalent<K>,
+        BH: BuildHasher,
+    {
+        index.get(key).map(|position| &self.records[position])
+    }
+
+    /// An iterator visiting the records with the given key in an index, in table order.
+    ///
+    /// # Panics
+    ///
+    /// May panic if the index wasn't built from this table.
+    pub fn find_all<'a, 'i, K, Q, BH>(
+        &'a self,
+        index: &'i SecondaryIndex<K, BH>,
+        key: &Q,
+    ) -> impl Iterator<Item = &'a R> + use<'a, 'i, K, Q, BH, R>
+    where
+        K: Hash + Eq,
+        Q: ?Sized + Hash + Equivalent<K>,
+        BH: BuildHasher,
+    {
+        index
+            .get(key)
+            .iter()
+            .map(|&position| &self.records[position])
+    }
+
+    /// Returns the record at a position, as held by the table's indexes.
+    #[must_use]
+    pub fn get(&self, position: usize) -> Option<&R> {
+        self.records.get(position)
+    }
+
+    /// Returns the records, in the order they were given.
+    #[must_use]
+    pub fn records(&self) -> &[R] {
+        &self.records
+    }
+
+    /// Returns the number of records in the table.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns `true` if the table holds no records.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// An iterator visiting all records, in the order they were given.
+    pub fn iter(&self) -> std::slice::Iter<'_, R> {
+        self.records.iter()
+    }
+}
+
+impl<R> FromIterator<R> for FrozenTable<R> {
+    fn from_iter<T: IntoIterator<Item = R>>(iter: T) -> Self {
+        Self::from_vec(Vec::from_iter(iter))
+    }
+}
+
+impl<'a, R> IntoIterator for &'a FrozenTable<R> {
+    type Item = &'a R;
+    type IntoIter = std::slice::Iter<'a, R>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<R> Len for FrozenTable<R> {
+    fn len(&self) -> usize {
+        self.records.len()
+    }
+}
+
+impl<R> Debug for FrozenTable<R>
+where
+    R: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list().entries(self.records.iter()).finish()
+    }
+}
+
+/// An index of a [`FrozenTable`] where every record has a distinct key, created with
+/// [`FrozenTable::unique_index_by`].
+#[derive(Clone)]
+pub struct UniqueIndex<K, BH = RandomState> {
+    positions: FrozenMap<K, usize, BH>,
+}
+
+impl<K, BH> UniqueIndex<K, BH>
+where
+    K: Hash + Eq,
+    BH: BuildHasher,
+{
+    /// Returns the position of the record with the given key.
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> Option<usize>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.positions.get(key).copied()
+    }
+
+    /// Returns `true` if a record has the given key.
+    #[must_use]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.positions.contains_key(key)
+    }
+}
+
+impl<K, BH> UniqueIndex<K, BH> {
+    /// Returns the number of keys in the index.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns `true` if the index holds no keys.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// An iterator visiting all keys and the positions of the records with them, in arbitrary
+    /// order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, usize)> {
+        self.positions.iter().map(|(k, p)| (k, *p))
+    }
+}
+
+impl<K, BH> Len for UniqueIndex<K, BH> {
+    fn len(&self) -> usize {
+        self.positions.len()
+    }
+}
+
+impl<K, BH> Debug for UniqueIndex<K, BH>
+where
+    K: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,86 @@
+use crate::errors::FrozenError;
+use crate::facades::FrozenTable;
+use crate::traits::len::Len;
+
+#[derive(Debug, PartialEq)]
+struct Country {
+    code: String,
+    id: u32,
+    continent: u32,
+}
+
+fn countries(count: u32) -> FrozenTable<Country> {
+    (0..count)
+        .map(|i| Country {
+            code: format!("C{i:03}"),
+            id: i * 4 + 2,
+            continent: i % 5,
+        })
+        .collect()
+}
+
+#[test]
+fn several_indexes() {
+    for count in [0, 1, 3, 10, 300, 2000] {
+        let table = countries(count);
+        assert_eq!(count as usize, table.len());
+        assert_eq!(count as usize, Len::len(&table));
+        assert_eq!(count == 0, table.is_empty());
+
+        let by_code = table.unique_index_by(|c| c.code.clone()).unwrap();
+        let by_id = table.unique_index_by(|c| c.id).unwrap();
+        let by_continent = table.index_by(|c| c.continent);
+        assert_eq!(table.len(), by_code.len());
+        assert_eq!(table.len(), Len::len(&by_id));
+
+        for (position, country) in table.iter().enumerate() {
+            assert_eq!(Some(country), table.find(&by_code, country.code.as_str()));
+            assert_eq!(Some(country), table.find(&by_id, &country.id));
+            assert_eq!(Some(position), by_id.get(&country.id));
+            assert_eq!(Some(country), table.get(position));
+            assert!(by_code.contains_key(country.code.as_str()));
+            assert!(table
+                .find_all(&by_continent, &country.continent)
+                .any(|c| c == country));
+        }
+
+        assert_eq!(None, table.find(&by_code, "X000"));
+        assert_eq!(None, table.find(&by_id, &1));
+        assert_eq!(None, table.get(table.len()));
+        assert_eq!(0, table.find_all(&by_continent, &5).count());
+        assert_eq!(
+            table.len(),
+            (0..5)
+                .map(|c| table.find_all(&by_continent, &c).count())
+                .sum::<usize>()
+        );
+    }
+}
+
+#[test]
+fn duplicate_unique_keys() {
+    let table = countries(10);
+    assert_eq!(
+        Some(FrozenError::DuplicateKey { index: 5 }),
+        table.unique_index_by(|c| c.continent).err()
+    );
+}
+
+#[test]
+fn records_in_order() {
+    let table = countries(3);
+    assert!(table.records().iter().map(|c| c.id).eq([2, 6, 10]));
+    assert!((&table).into_iter().eq(table.iter()));
+
+    let by_id = table.unique_index_by(|c| c.id).unwrap();
+    let one = countries(1);
+    assert_eq!(
+        "{2: 0}",
+        format!("{:?}", one.unique_index_by(|c| c.id).unwrap())
+    );
+    assert_eq!(3, by_id.iter().count());
+    assert_eq!(
+        r#"[Country { code: "C000", id: 2, continent: 0 }]"#,
+        format!("{:?}", countries(1))
+    );
+}
@@ -0,0 +1,206 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::{Hash, Hasher};
+
+use crate::specialized_maps::IntegerMap;
+use crate::traits::len::Len;
+
+type Entry = (TypeId, Box<dyn Any + Send + Sync>);
+
+/// A map optimized for fast read access which holds at most one value of any given type.
+///
+/// Values are keyed by their [`TypeId`] and retrieved with a typed [`get`](Self::get) call.
+/// Type identifiers are reduced to 64-bit integers so that lookups go through the same
+/// integer hash table used by [`IntegerMap`], without hashing the key at runtime.
+///
+/// This is a good fit for registries of plugins, services, or extensions which are populated
+/// once at startup and then only read.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::FrozenTypeMap;
+///
+/// struct Config {
+///     verbose: bool,
+/// }
+///
+/// let map = FrozenTypeMap::from_vec(vec![Box::new(Config { verbose: true }), Box::new(42u32)]);
+///
+/// assert!(map.get::<Config>().unwrap().verbose);
+/// assert_eq!(map.get::<u32>(), Some(&42));
+/// assert_eq!(map.get::<String>(), None);
+/// ```
+#[allow(clippy::module_name_repetitions)]
+pub struct FrozenTypeMap {
+    map: IntegerMap<u64, Entry>,
+}
+
+impl FrozenTypeMap {
+    /// Creates a frozen type map.
+    ///
+    /// If several values have the same type, the last one is kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if two distinct types reduce to the same 64-bit identifier, which is not expected to
+    /// happen in practice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenTypeMap;
+    ///
+    /// let map = FrozenTypeMap::from_vec(vec![Box::new(1u8), Box::new(2u8)]);
+    /// assert_eq!(map.get::<u8>(), Some(&2));
+    /// ```
+    #[must_use]
+    pub fn from_vec(payload: Vec<Box<dyn Any + Send + Sync>>) -> Self {
+        let mut by_code: HashMap<u64, Entry> = HashMap::with_capacity(payload.len());
+        for value in payload {
+            let type_id = (*value).type_id();
+            let code = type_code(type_id);
+            if let Some(existing) = by_code.insert(code, (type_id, value)) {
+                assert!(existing.0 == type_id, "type identifier collision");
+            }
+        }
+
+        Self {
+            map: IntegerMap::from_vec(by_code.into_iter().collect()),
+        }
+    }
+
+    /// Returns a reference to the value of type `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenTypeMap;
+    ///
+    /// let map = FrozenTypeMap::from_vec(vec![Box::new("hello")]);
+    /// assert_eq!(map.get::<&str>(), Some(&"hello"));
+    /// assert_eq!(map.get::<i32>(), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        let (type_id, value) = self.map.get(&type_code(TypeId::of::<T>()))?;
+        if *type_id == TypeId::of::<T>() {
+            value.downcast_ref()
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the value of type `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenTypeMap;
+    ///
+    /// let mut map = FrozenTypeMap::from_vec(vec![Box::new(1)]);
+    /// if let Some(x) = map.get_mut::<i32>() {
+    ///     *x = 2;
+    /// }
+    /// assert_eq!(map.get::<i32>(), Some(&2));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn get_mut<T: Any>(&mut self) -> Option<&mut T> {
+        let (type_id, value) = self.map.get_mut(&type_code(TypeId::of::<T>()))?;
+        if *type_id == TypeId::of::<T>() {
+            value.downcast_mut()
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the map contains a value of type `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::FrozenTypeMap;
+    ///
+    /// let map = FrozenTypeMap::from_vec(vec![Box::new(1)]);
+    /// assert!(map.contains::<i32>());
+    /// assert!(!map.contains::<u32>());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn contains<T: Any>(&self) -> bool {
+        self.get::<T>().is_some()
+    }
+
+    /// Returns the number of values in the map.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the map contains no values.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// An iterator visiting the type identifiers of all values in arbitrary order.
+    pub fn type_ids(&self) -> impl Iterator<Item = &TypeId> {
+        self.map.values().map(|entry| &entry.0)
+    }
+}
+
+impl Len for FrozenTypeMap {
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+}
+
+impl Default for FrozenTypeMap {
+    fn default() -> Self {
+        Self::from_vec(vec![])
+    }
+}
+
+impl FromIterator<Box<dyn Any + Send + Sync>> for FrozenTypeMap {
+    fn from_iter<T: IntoIterator<Item = Box<dyn Any + Send + Sync>>>(iter: T) -> Self {
+        Self::from_vec(Vec::from_iter(iter))
+    }
+}
+
+impl Debug for FrozenTypeMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_set().entries(self.type_ids()).finish()
+    }
+}
+
+/// Reduces a type identifier to a 64-bit integer without running a general purpose hash function.
+fn type_code(type_id: TypeId) -> u64 {
+    let mut h = FoldHasher(0);
+    type_id.hash(&mut h);
+    h.0
+}
+
+/// Folds whatever a `TypeId` writes into a single `u64`. Type identifiers are already
+/// hashes, so there is no point in mixing them any further.
+struct FoldHasher(u64);
+
+impl Hasher for FoldHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut buf = [0; 8];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            self.write_u64(u64::from_le_bytes(buf));
+        }
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.0 = self.0.rotate_left(17) ^ i;
+    }
+}
@@ -0,0 +1,50 @@
+use std::any::{Any, TypeId};
+
+use crate::facades::FrozenTypeMap;
+
+#[derive(Debug, PartialEq)]
+struct Logger(&'static str);
+
+#[derive(Debug, PartialEq)]
+struct Metrics(u32);
+
+#[test]
+fn test_get() {
+    let mut payload: Vec<Box<dyn Any + Send + Sync>> = vec![
+        Box::new(Logger("stderr")),
+        Box::new(Metrics(7)),
+        Box::new(String::from("name")),
+    ];
+    payload.extend((0..300u16).map(|_| Box::new(0u64) as Box<dyn Any + Send + Sync>));
+
+    let mut m = FrozenTypeMap::from_vec(payload);
+    assert_eq!(4, m.len());
+    assert_eq!(Some(&Logger("stderr")), m.get::<Logger>());
+    assert_eq!(Some(&Metrics(7)), m.get::<Metrics>());
+    assert_eq!(Some(&"name".to_string()), m.get::<String>());
+    assert_eq!(Some(&0), m.get::<u64>());
+    assert_eq!(None, m.get::<u32>());
+    assert!(m.contains::<Logger>());
+    assert!(!m.contains::<Box<Logger>>());
+
+    m.get_mut::<Metrics>().unwrap().0 = 8;
+    assert_eq!(Some(&Metrics(8)), m.get::<Metrics>());
+
+    let mut ids: Vec<_> = m.type_ids().copied().collect();
+    ids.sort();
+    let mut expected = vec![
+        TypeId::of::<Logger>(),
+        TypeId::of::<Metrics>(),
+        TypeId::of::<String>(),
+        TypeId::of::<u64>(),
+    ];
+    expected.sort();
+    assert_eq!(expected, ids);
+}
+
+#[test]
+fn test_empty() {
+    let m = FrozenTypeMap::default();
+    assert!(m.is_empty());
+    assert_eq!(None, m.get::<i32>());
+}
@@ -0,0 +1,243 @@
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::mem::size_of;
+
+use crate::facades::FrozenMap;
+use crate::traits::len::Len;
+
+/// The size in bytes above which values are stored out of line by default.
+pub const DEFAULT_INDIRECT_THRESHOLD: usize = 128;
+
+/// A frozen map which stores large values apart from the keys.
+///
+/// Lookups probe an array of entries, and every entry holds a value next to its key. When
+/// values are large, probing touches a lot of memory which has nothing to do with the key
+/// being looked up, and few entries fit in a cache line. This map checks the size of the
+/// value type when it is created: values larger than a threshold are moved to a separate
+/// array so the probed entries only hold keys, while smaller values are kept inline as in a
+/// regular [`FrozenMap`].
+///
+/// The threshold is [`DEFAULT_INDIRECT_THRESHOLD`] unless set with
+/// [`FrozenMapBuilder::indirect_threshold`](crate::facades::FrozenMapBuilder::indirect_threshold).
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::IndirectFrozenMap;
+///
+/// let map = IndirectFrozenMap::from_vec(vec![(1, [0u8; 1024]), (2, [1u8; 1024])]);
+/// assert!(map.is_indirect());
+/// assert_eq!(map.get(&2).map(|v| v[0]), Some(1));
+///
+/// let map = IndirectFrozenMap::from_vec(vec![(1, 'a'), (2, 'b')]);
+/// assert!(!map.is_indirect());
+/// assert_eq!(map.get(&2), Some(&'b'));
+/// ```
+#[derive(Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct IndirectFrozenMap<K, V, BH = RandomState> {
+    storage: Storage<K, V, BH>,
+}
+
+#[derive(Clone)]
+enum Storage<K, V, BH> {
+    Inline(FrozenMap<K, V, BH>),
+    Indirect {
+        keys: FrozenMap<K, (), BH>,
+        values: Box<[V]>,
+    },
+}
+
+impl<K, V, BH> IndirectFrozenMap<K, V, BH>
+where
+    K: Hash + Eq,
+    BH: BuildHasher,
+{
+    /// Creates a frozen map which will use the given hash builder to hash keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::IndirectFrozenMap;
+    /// use std::hash::RandomState;
+    ///
+    /// let map = IndirectFrozenMap::from_vec_with_hasher(vec![(1, 2)], RandomState::new());
+    /// assert_eq!(map.get(&1), Some(&2));
+    /// ```
+    #[must_use]
+    pub fn from_vec_with_hasher(payload: Vec<(K, V)>, bh: BH) -> Self {
+        Self::new(payload, bh, DEFAULT_INDIRECT_THRESHOLD)
+    }
+
+    /// Creates a frozen map which stores values apart from the keys if they are larger than
+    /// `threshold` bytes.
+    pub(crate) fn new(payload: Vec<(K, V)>, bh: BH, threshold: usize) -> Self {
+        let map = FrozenMap::from_vec_with_hasher(payload, bh);
+
+        if size_of::<V>() <= threshold {
+            return Self {
+                storage: Storage::Inline(map),
+            };
+        }
+
+        let mut values = Vec::with_capacity(map.len());
+        let keys = map.map_values(|v| values.push(v));
+
+        Self {
+            storage: Storage::Indirect {
+                keys,
+                values: values.into_boxed_slice(),
+            },
+        }
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::IndirectFrozenMap;
+    ///
+    /// let map = IndirectFrozenMap::from_vec(vec![(1, "a".to_string())]);
+    /// assert_eq!(map.get(&1), Some(&"a".to_string()));
+    /// assert_eq!(map.get(&2), None);
+    /// ```
+    #[inline]
+    pub fn get(&self, key: &K) -> Option<&V> {
+        match &self.storage {
+            Storage::Inline(map) => map.get(key),
+            Storage::Indirect { keys, values } => Some(&values[keys.entry_index(key)?]),
+        }
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    #[inline]
+    pub fn get_key_value(&self, key: &K) -> Option<(&K, &V)> {
+        match &self.storage {
+            Storage::Inline(map) => map.get_key_value(key),
+            Storage::Indirect { keys, values } => {
+                let index = keys.entry_index(key)?;
+                Some((keys.get_key_value(key)?.0, &values[index]))
+            }
+        }
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    #[inline]
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        match &mut self.storage {
+            Storage::Inline(map) => map.get_mut(key),
+            Storage::Indirect { keys, values } => Some(&mut values[keys.entry_index(key)?]),
+        }
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    #[inline]
+    pub fn contains_key(&self, key: &K) -> bool {
+        match &self.storage {
+            Storage::Inline(map) => map.contains_key(key),
+            Storage::Indirect { keys, .. } => keys.contains_key(key),
+        }
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        match &self.storage {
+            Storage::Inline(map) => map.len(),
+            Storage::Indirect { values, .. } => values.len(),
+        }
+    }
+
+    /// Returns `true` if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the values are stored apart from the keys.
+    #[must_use]
+    pub const fn is_indirect(&self) -> bool {
+        matches!(self.storage, Storage::Indirect { .. })
+    }
+
+    /// An iterator visiting all key-value pairs in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::IndirectFrozenMap;
+    ///
+    /// let map = IndirectFrozenMap::from_vec(vec![(1, [2u64; 64]), (3, [4u64; 64])]);
+    /// let mut pairs: Vec<_> = map.iter().map(|(k, v)| (*k, v[0])).collect();
+    /// pairs.sort();
+    /// assert_eq!(pairs, vec![(1, 2), (3, 4)]);
+    /// ```
+    pub fn iter(&self) -> Box<dyn Iterator<Item = (&K, &V)> + '_> {
+        match &self.storage {
+            Storage::Inline(map) => Box::new(map.iter()),
+            Storage::Indirect { keys, values } => Box::new(keys.keys().zip(values.iter())),
+        }
+    }
+}
+
+impl<K, V> IndirectFrozenMap<K, V, RandomState>
+where
+    K: Hash + Eq,
+{
+    /// Creates a frozen map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::IndirectFrozenMap;
+    ///
+    /// let map = IndirectFrozenMap::from_vec(vec![(1, 2)]);
+    /// ```
+    #[must_use]
+    pub fn from_vec(payload: Vec<(K, V)>) -> Self {
+        Self::from_vec_with_hasher(payload, RandomState::new())
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for IndirectFrozenMap<K, V, RandomState>
+where
+    K: Hash + Eq,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self::from_vec(Vec::from_iter(iter))
+    }
+}
+
+impl<K, V, BH> Len for IndirectFrozenMap<K, V, BH>
+where
+    K: Hash + Eq,
+    BH: BuildHasher,
+{
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<'a, K, V, BH> IntoIterator for &'a IndirectFrozenMap<K, V, BH>
+where
+    K: Hash + Eq,
+    BH: BuildHasher,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Box<dyn Iterator<Item = (&'a K, &'a V)> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V, BH> Debug for IndirectFrozenMap<K, V, BH>
+where
+    K: Hash + Eq + Debug,
+    V: Debug,
+    BH: BuildHasher,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,73 @@
+use crate::facades::{FrozenMapBuilder, IndirectFrozenMap};
+use crate::traits::len::Len;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Large {
+    id: usize,
+    padding: [u64; 192],
+}
+
+impl Large {
+    const fn new(id: usize) -> Self {
+        Self {
+            id,
+            padding: [0; 192],
+        }
+    }
+}
+
+#[test]
+fn large_values_are_indirect() {
+    let mut map: IndirectFrozenMap<_, _> = (0..1000).map(|i| (i, Large::new(i * 2))).collect();
+    assert!(map.is_indirect());
+    assert_eq!(1000, map.len());
+    assert_eq!(1000, Len::len(&map));
+
+    for i in 0..1000 {
+        assert_eq!(Some(i * 2), map.get(&i).map(|v| v.id));
+        assert_eq!(Some(&i), map.get_key_value(&i).map(|x| x.0));
+    }
+
+    assert!(!map.contains_key(&1000));
+    assert_eq!(None, map.get(&1000));
+
+    map.get_mut(&7).unwrap().id = 1;
+    assert_eq!(1, map.get(&7).unwrap().id);
+
+    let mut ids: Vec<_> = map.iter().map(|(k, v)| (*k, v.id)).collect();
+    ids.sort_unstable();
+    assert_eq!((0, 0), ids[0]);
+    assert_eq!((7, 1), ids[7]);
+    assert_eq!((999, 1998), ids[999]);
+}
+
+#[test]
+fn small_values_are_inline() {
+    let map = IndirectFrozenMap::from_vec(vec![("a", 1u64), ("b", 2)]);
+    assert!(!map.is_indirect());
+    assert_eq!(Some(&2), map.get(&"b"));
+    assert_eq!(2, map.iter().count());
+}
+
+#[test]
+fn builder_threshold() {
+    let mut builder = FrozenMapBuilder::new();
+    builder.push("a", 1u64).push("b", 2).indirect_threshold(4);
+
+    let map = builder.clone().build_indirect();
+    assert!(map.is_indirect());
+    assert_eq!(Some(&1), map.get(&"a"));
+    assert_eq!(2, map.len());
+
+    builder.indirect_threshold(8);
+    let map = builder.build_indirect();
+    assert!(!map.is_indirect());
+}
+
+#[test]
+fn empty() {
+    let map = IndirectFrozenMap::<u32, Large>::from_vec(Vec::new());
+    assert!(map.is_empty());
+    assert_eq!(None, map.get(&1));
+    assert_eq!("{}", format!("{map:?}"));
+}
@@ -0,0 +1,150 @@
+use std::fmt::{Debug, Formatter, Result};
+
+/// An index over the string keys of a [`FrozenMap`](crate::facades::FrozenMap) which suggests
+/// the keys closest to a query.
+///
+/// The keys are ordered by their length in characters when the index is built, so a query
+/// only computes the edit distance to keys whose length is within the allowed distance of its
+/// own, rather than visiting every key. Build one with
+/// [`FrozenMap::key_suggester`](crate::facades::FrozenMap::key_suggester) when answering many
+/// "did you mean ...?" queries against the same map.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::FrozenMap;
+///
+/// let map = FrozenMap::from([("build", 1), ("bench", 2), ("check", 3), ("clean", 4)]);
+/// let suggester = map.key_suggester();
+///
+/// assert_eq!(suggester.closest_keys("biuld", 2, 3), vec![&"build"]);
+/// assert_eq!(suggester.closest_keys("chek", 1, 3), vec![&"check"]);
+/// assert!(suggester.closest_keys("publish", 2, 3).is_empty());
+/// ```
+pub struct KeySuggester<'m, K> {
+    /// The keys along with their length in characters, shortest first.
+    keys: Box<[(usize, &'m K)]>,
+}
+
+impl<'m, K> KeySuggester<'m, K>
+where
+    K: AsRef<str>,
+{
+    /// Creates an index over the given keys.
+    pub(crate) fn new<I>(keys: I) -> Self
+    where
+        I: Iterator<Item = &'m K>,
+    {
+        let mut keys: Box<[_]> = keys.map(|k| (k.as_ref().chars().count(), k)).collect();
+        keys.sort_unstable_by_key(|entry| entry.0);
+        Self { keys }
+    }
+
+    /// Returns the keys which are within `max_distance` edits of `query`, closest first.
+    ///
+    /// This gives the same results as
+    /// [`FrozenMap::closest_keys`](crate::facades::FrozenMap::closest_keys).
+    #[must_use]
+    pub fn closest_keys(&self, query: &str, max_distance: usize, limit: usize) -> Vec<&'m K> {
+        let query: Vec<char> = query.chars().collect();
+        let min_len = query.len().saturating_sub(max_distance);
+        let max_len = query.len().saturating_add(max_distance);
+
+        let start = self.keys.partition_point(|entry| entry.0 < min_len);
+        let end = self.keys.partition_point(|entry| entry.0 <= max_len);
+        let candidates = self.keys[start..end].iter().map(|entry| entry.1);
+
+        Self::rank(&query, candidates, max_distance, limit)
+    }
+
+    /// Returns the candidates which are within `max_distance` edits of `query`, closest first
+    /// and then in lexicographic order, keeping at most `limit` of them.
+    pub(crate) fn rank<I>(
+        query: &[char],
+        candidates: I,
+        max_distance: usize,
+        limit: usize,
+    ) -> Vec<&'m K>
+    where
+        I: Iterator<Item = &'m K>,
+    {
+        let mut ranked = Vec::new();
+        let mut buf = Vec::new();
+
+        for key in candidates {
+            buf.clear();
+            buf.extend(key.as_ref().chars());
+            if let Some(distance) = bounded_edit_distance(query, &buf, max_distance) {
+                ranked.push((distance, key));
+            }
+        }
+
+        ranked.sort_unstable_by(|x, y| x.0.cmp(&y.0).then_with(|| x.1.as_ref().cmp(y.1.as_ref())));
+        ranked.into_iter().take(limit).map(|x| x.1).collect()
+    }
+}
+
+impl<K> KeySuggester<'_, K> {
+    /// Returns the number of keys in the index.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if the index holds no keys.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+impl<K> Debug for KeySuggester<'_, K>
+where
+    K: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list()
+            .entries(self.keys.iter().map(|entry| entry.1))
+            .finish()
+    }
+}
+
+/// Computes the Levenshtein distance between two strings, giving up once it exceeds `max`.
+fn bounded_edit_distance(a: &[char], b: &[char], max: usize) -> Option<usize> {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let (a, b) = (&a[prefix..], &b[prefix..]);
+    let suffix = a
+        .iter()
+        .rev()
+        .zip(b.iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (a, b) = (&a[..a.len() - suffix], &b[..b.len() - suffix]);
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        let mut row_min = curr[0];
+
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+            row_min = row_min.min(curr[j + 1]);
+        }
+
+        if row_min > max {
+            return None;
+        }
+
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    let distance = prev[b.len()];
+    if distance <= max {
+        Some(distance)
+    } else {
+        None
+    }
+}
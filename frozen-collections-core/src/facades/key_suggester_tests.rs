@@ -0,0 +1,50 @@
+use crate::facades::FrozenMap;
+
+#[test]
+fn matches_closest_keys() {
+    let m = FrozenMap::from([
+        ("install".to_string(), 1),
+        ("uninstall".to_string(), 2),
+        ("update".to_string(), 3),
+        ("upgrade".to_string(), 4),
+        ("list".to_string(), 5),
+        ("ls".to_string(), 6),
+        ("überprüfen".to_string(), 7),
+    ]);
+
+    let s = m.key_suggester();
+    assert_eq!(7, s.len());
+    assert!(!s.is_empty());
+
+    for (query, max_distance, limit) in [
+        ("instal", 2, 5),
+        ("updade", 3, 5),
+        ("updade", 3, 1),
+        ("list", 0, 5),
+        ("lst", 1, 5),
+        ("l", 1, 5),
+        ("", 2, 5),
+        ("remove", 1, 5),
+        ("install", 2, 0),
+        ("uberprufen", 2, 5),
+        ("install", usize::MAX, 10),
+    ] {
+        assert_eq!(
+            m.closest_keys(query, max_distance, limit),
+            s.closest_keys(query, max_distance, limit),
+            "{query}"
+        );
+    }
+
+    assert_eq!(vec!["überprüfen"], s.closest_keys("uberprufen", 2, 5));
+    assert_eq!(vec!["list", "ls"], s.closest_keys("lst", 1, 5));
+}
+
+#[test]
+fn empty() {
+    let m = FrozenMap::<&str, i32>::empty();
+    let s = m.key_suggester();
+    assert!(s.is_empty());
+    assert!(s.closest_keys("a", 3, 5).is_empty());
+    assert_eq!("[]", format!("{s:?}"));
+}
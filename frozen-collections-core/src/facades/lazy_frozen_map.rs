@@ -0,0 +1,190 @@
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::sync::OnceLock;
+
+use crate::facades::FrozenMap;
+use crate::traits::equivalent::Equivalent;
+use crate::traits::len::Len;
+
+/// A frozen map whose values are computed on first access.
+///
+/// A lazy frozen map combines the read-optimized key layout of a [`FrozenMap`] with deferred
+/// value computation. Each key is associated with a seed value which is handed to an initializer
+/// function the first time the key is looked up. The resulting value is then cached for the
+/// lifetime of the map, so the initializer runs at most once per key.
+///
+/// This is useful when values are expensive to derive and only a small fraction of the keys
+/// are ever probed during the lifetime of a process.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::LazyFrozenMap;
+///
+/// let map = LazyFrozenMap::from_vec(
+///     vec![("one".to_string(), 1), ("two".to_string(), 2)],
+///     |key: &String, seed: &i32| format!("{key}={}", seed * 100),
+/// );
+///
+/// assert!(!map.is_initialized("one"));
+/// assert_eq!(map.get("one"), Some(&"one=100".to_string()));
+/// assert!(map.is_initialized("one"));
+/// assert_eq!(map.get("three"), None);
+/// ```
+#[allow(clippy::module_name_repetitions)]
+pub struct LazyFrozenMap<K, T, V, F, BH = RandomState> {
+    map: FrozenMap<K, (T, OnceLock<V>), BH>,
+    init: F,
+}
+
+impl<K, T, V, F, BH> LazyFrozenMap<K, T, V, F, BH>
+where
+    K: Hash + Eq,
+    F: Fn(&K, &T) -> V,
+    BH: BuildHasher,
+{
+    /// Creates a lazy frozen map which will use the given hash builder to hash keys.
+    ///
+    /// The payload associates every key with a seed which is later given to `init`
+    /// in order to produce the key's value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::LazyFrozenMap;
+    /// use std::hash::RandomState;
+    ///
+    /// let map = LazyFrozenMap::from_vec_with_hasher(vec![(1, 2), (3, 4)], |k: &i32, s: &i32| k + s, RandomState::new());
+    /// assert_eq!(map.get(&3), Some(&7));
+    /// ```
+    #[must_use]
+    pub fn from_vec_with_hasher(payload: Vec<(K, T)>, init: F, bh: BH) -> Self {
+        Self {
+            map: FrozenMap::from_iter_with_hasher(
+                payload
+                    .into_iter()
+                    .map(|(k, seed)| (k, (seed, OnceLock::new()))),
+                bh,
+            ),
+            init,
+        }
+    }
+
+    /// Returns a reference to the value corresponding to the key, computing it if needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::LazyFrozenMap;
+    ///
+    /// let map = LazyFrozenMap::from_vec(vec![(1, 2)], |k: &i32, s: &i32| k * s);
+    /// assert_eq!(map.get(&1), Some(&2));
+    /// assert_eq!(map.get(&2), None);
+    /// ```
+    #[inline]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let (k, (seed, cell)) = self.map.get_key_value(key)?;
+        Some(cell.get_or_init(|| (self.init)(k, seed)))
+    }
+
+    /// Returns the value corresponding to the key, only if it has already been computed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::LazyFrozenMap;
+    ///
+    /// let map = LazyFrozenMap::from_vec(vec![(1, 2)], |k: &i32, s: &i32| k * s);
+    /// assert_eq!(map.get_if_initialized(&1), None);
+    /// _ = map.get(&1);
+    /// assert_eq!(map.get_if_initialized(&1), Some(&2));
+    /// ```
+    #[inline]
+    pub fn get_if_initialized<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.map.get(key)?.1.get()
+    }
+
+    /// Returns the seed associated with the key.
+    #[inline]
+    pub fn get_seed<Q>(&self, key: &Q) -> Option<&T>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        Some(&self.map.get(key)?.0)
+    }
+
+    /// Returns `true` if the value for the key has already been computed.
+    #[inline]
+    pub fn is_initialized<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.get_if_initialized(key).is_some()
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// This never causes the key's value to be computed.
+    #[inline]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// An iterator visiting all keys in arbitrary order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.map.keys()
+    }
+}
+
+impl<K, T, V, F> LazyFrozenMap<K, T, V, F, RandomState>
+where
+    K: Hash + Eq,
+    F: Fn(&K, &T) -> V,
+{
+    /// Creates a lazy frozen map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::LazyFrozenMap;
+    ///
+    /// let map = LazyFrozenMap::from_vec(vec![(1, 2)], |k: &i32, s: &i32| k + s);
+    /// ```
+    #[must_use]
+    pub fn from_vec(payload: Vec<(K, T)>, init: F) -> Self {
+        Self::from_vec_with_hasher(payload, init, RandomState::new())
+    }
+}
+
+impl<K, T, V, F, BH> Len for LazyFrozenMap<K, T, V, F, BH>
+where
+    K: Hash + Eq,
+    BH: BuildHasher,
+{
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+}
+
+impl<K, T, V, F, BH> Debug for LazyFrozenMap<K, T, V, F, BH>
+where
+    K: Hash + Eq + Debug,
+    V: Debug,
+    BH: BuildHasher,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_map()
+            .entries(self.map.iter().map(|(k, (_, cell))| (k, cell.get())))
+            .finish()
+    }
+}
@@ -0,0 +1,40 @@
+use std::cell::Cell;
+
+use crate::facades::LazyFrozenMap;
+use crate::traits::len::Len;
+
+#[test]
+fn test_values_computed_once() {
+    let calls = Cell::new(0);
+    let payload: Vec<_> = (0..10).map(|i| (format!("K{i}"), i)).collect();
+    let m = LazyFrozenMap::from_vec(payload, |k: &String, seed: &i32| {
+        calls.set(calls.get() + 1);
+        format!("{k}:{seed}")
+    });
+
+    assert_eq!(10, m.len());
+    assert_eq!(0, calls.get());
+
+    for _ in 0..3 {
+        assert_eq!(Some(&"K3:3".to_string()), m.get("K3"));
+    }
+
+    assert_eq!(1, calls.get());
+    assert!(m.is_initialized(&"K3".to_string()));
+    assert!(!m.is_initialized(&"K4".to_string()));
+    assert_eq!(Some(&4), m.get_seed(&"K4".to_string()));
+
+    assert!(m.contains_key(&"K9".to_string()));
+    assert_eq!(1, calls.get());
+
+    assert_eq!(None, m.get(&"K10".to_string()));
+    assert_eq!(1, calls.get());
+}
+
+#[test]
+fn test_debug() {
+    let m = LazyFrozenMap::from_vec(vec![(1, 10)], |k: &i32, seed: &i32| k + seed);
+    assert_eq!("{1: None}", format!("{m:?}"));
+    _ = m.get(&1);
+    assert_eq!("{1: Some(11)}", format!("{m:?}"));
+}
@@ -0,0 +1,120 @@
+use std::collections::VecDeque;
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::{BuildHasher, Hash};
+use std::iter::{Fuse, FusedIterator};
+use std::ops::Range;
+
+use crate::facades::FrozenMap;
+use crate::traits::equivalent::Equivalent;
+
+/// The number of lookups kept in flight, which is enough for the entries of the oldest one to
+/// have arrived from memory by the time it's finished, without evicting them again.
+const LOOKAHEAD: usize = 8;
+
+/// Where a lookup which was started, but not finished, will find its key.
+///
+/// This stays private to the crate, as only [`LookupStream`] is re-exported.
+pub enum Probe {
+    /// The entries of a hash table which may hold the key, along with the key's hash code.
+    Table { hash_code: u64, range: Range<usize> },
+
+    /// The only slot of a perfect hash map which may hold the key, if any.
+    Slot(Option<usize>),
+
+    /// The key is looked up from scratch, as the map is small or doesn't hash keys.
+    Direct,
+}
+
+/// An iterator looking up a stream of keys in a frozen map, created with
+/// [`FrozenMap::lookup_stream`].
+///
+/// Each key is hashed and the memory holding its entries is prefetched as soon as the key is
+/// read, while its result is only produced once a few more keys were read. The memory accesses
+/// of consecutive lookups thus overlap, rather than each lookup waiting on its own.
+///
+/// Results are produced in the order of the keys, one per key.
+pub struct LookupStream<'a, 'q, K, V, Q, I, BH>
+where
+    Q: ?Sized,
+{
+    map: &'a FrozenMap<K, V, BH>,
+    keys: Fuse<I>,
+    pending: VecDeque<(&'q Q, Probe)>,
+}
+
+impl<'a, 'q, K, V, Q, I, BH> LookupStream<'a, 'q, K, V, Q, I, BH>
+where
+    Q: ?Sized,
+{
+    pub(crate) fn new(map: &'a FrozenMap<K, V, BH>, keys: I) -> Self
+    where
+        I: Iterator<Item = &'q Q>,
+    {
+        Self {
+            map,
+            keys: keys.fuse(),
+            pending: VecDeque::with_capacity(LOOKAHEAD),
+        }
+    }
+}
+
+impl<'a, 'q, K, V, Q, I, BH> Iterator for LookupStream<'a, 'q, K, V, Q, I, BH>
+where
+    K: Hash + Eq,
+    Q: ?Sized + Hash + Equivalent<K> + 'q,
+    I: Iterator<Item = &'q Q>,
+    BH: BuildHasher,
+{
+    type Item = Option<&'a V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.len() < LOOKAHEAD {
+            let Some(key) = self.keys.next() else {
+                break;
+            };
+
+            self.pending.push_back((key, self.map.locate(key)));
+        }
+
+        let (key, probe) = self.pending.pop_front()?;
+        Some(self.map.find_located(probe, key))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.keys.size_hint();
+        let pending = self.pending.len();
+        (
+            lower.saturating_add(pending),
+            upper.and_then(|upper| upper.checked_add(pending)),
+        )
+    }
+}
+
+impl<'q, K, V, Q, I, BH> ExactSizeIterator for LookupStream<'_, 'q, K, V, Q, I, BH>
+where
+    K: Hash + Eq,
+    Q: ?Sized + Hash + Equivalent<K> + 'q,
+    I: ExactSizeIterator<Item = &'q Q>,
+    BH: BuildHasher,
+{
+}
+
+impl<'q, K, V, Q, I, BH> FusedIterator for LookupStream<'_, 'q, K, V, Q, I, BH>
+where
+    K: Hash + Eq,
+    Q: ?Sized + Hash + Equivalent<K> + 'q,
+    I: Iterator<Item = &'q Q>,
+    BH: BuildHasher,
+{
+}
+
+impl<K, V, Q, I, BH> Debug for LookupStream<'_, '_, K, V, Q, I, BH>
+where
+    Q: ?Sized,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("LookupStream")
+            .field("pending", &self.pending.len())
+            .finish_non_exhaustive()
+    }
+}
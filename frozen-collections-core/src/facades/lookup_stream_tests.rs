@@ -0,0 +1,124 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use crate::facades::{AnalysisOptions, FrozenMap, MapStrategy};
+use crate::hashers::SeededState;
+use crate::traits::equivalent::Equivalent;
+
+fn check<K, Q>(map: &FrozenMap<K, usize, SeededState>, keys: &[&Q])
+where
+    K: Hash + Eq + Debug,
+    Q: ?Sized + Hash + Equivalent<K>,
+{
+    let expected: Vec<_> = keys.iter().map(|k| map.get(*k)).collect();
+    let stream = map.lookup_stream(keys.iter().copied());
+    assert_eq!(keys.len(), stream.len());
+    assert_eq!(expected, stream.collect::<Vec<_>>());
+}
+
+#[test]
+fn matches_get_for_every_strategy() {
+    let string_payloads: Vec<Vec<String>> = vec![
+        (0..3).map(|i| format!("k{i}")).collect(),
+        (0..100).map(|i| format!("{i:03}-suffix")).collect(),
+        (0..100).map(|i| format!("prefix-{i:03}")).collect(),
+        (1..=300).map(|i| "x".repeat(i)).collect(),
+        (0..2000).map(|i| i.to_string()).collect(),
+        (0..5000).map(|i| format!("user-{i}")).collect(),
+    ];
+
+    let mut strategies = Vec::new();
+    for keys in string_payloads {
+        let map = FrozenMap::from_vec_with_hasher(
+            keys.iter().cloned().zip(0..).collect(),
+            SeededState::new(3),
+        );
+        strategies.push(map.strategy());
+
+        let mut probes: Vec<_> = keys.iter().map(String::as_str).collect();
+        let misses: Vec<_> = keys.iter().map(|k| format!("{k}!")).collect();
+        probes.extend(misses.iter().map(String::as_str));
+        probes.push("");
+        check(&map, &probes);
+    }
+
+    let int_payloads: Vec<Vec<u64>> = vec![
+        (0..10).collect(),
+        (5..15).collect(),
+        (0..300).map(|i| i * 2).collect(),
+        (0..20).map(|i| i << 40).collect(),
+        (0..2000).map(|i| i * 7919).collect(),
+    ];
+
+    for keys in int_payloads {
+        let map = FrozenMap::from_vec_with_hasher(
+            keys.iter().copied().zip(0..).collect(),
+            SeededState::new(3),
+        );
+        strategies.push(map.strategy());
+
+        let misses: Vec<_> = keys.iter().map(|k| k + 1).collect();
+        let probes: Vec<_> = keys.iter().chain(&misses).collect();
+        check(&map, &probes);
+    }
+
+    let keys: Vec<_> = (0..5000).map(|i| format!("user-{i}")).collect();
+    for options in [
+        AnalysisOptions::new().prefilter(true),
+        AnalysisOptions::new()
+            .slice_maps(false)
+            .perfect_hash_maps(false),
+    ] {
+        let map = FrozenMap::from_vec_with_options(
+            keys.iter().cloned().zip(0..).collect(),
+            SeededState::new(3),
+            options,
+        );
+        strategies.push(map.strategy());
+
+        let mut probes: Vec<_> = keys.iter().map(String::as_str).collect();
+        probes.extend(keys.iter().map(|k| &k[1..]));
+        check(&map, &probes);
+    }
+
+    for strategy in [
+        MapStrategy::Common,
+        MapStrategy::Scanning,
+        MapStrategy::PerfectHash,
+        MapStrategy::Integer,
+        MapStrategy::IntegerRange,
+        MapStrategy::DenseInteger,
+        MapStrategy::Length,
+    ] {
+        assert!(
+            strategies.contains(&strategy),
+            "{strategy:?} in {strategies:?}"
+        );
+    }
+}
+
+#[test]
+fn empty_streams_and_maps() {
+    let map = FrozenMap::from_vec_with_hasher(vec![(1u32, 1usize)], SeededState::new(3));
+    assert_eq!(0, map.lookup_stream(std::iter::empty::<&u32>()).count());
+
+    let map = FrozenMap::<u32, usize, _>::from_vec_with_hasher(Vec::new(), SeededState::new(3));
+    assert_eq!(
+        vec![None, None],
+        map.lookup_stream(&[1, 2]).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn results_follow_the_keys() {
+    let map: FrozenMap<String, usize> = (0..1000).map(|i| (i.to_string(), i)).collect();
+    let keys: Vec<_> = (0..3000).rev().map(|i| i.to_string()).collect();
+
+    let mut stream = map.lookup_stream(keys.iter().map(String::as_str));
+    for key in &keys {
+        assert_eq!(map.get(key.as_str()), stream.next().unwrap());
+    }
+
+    assert_eq!(None, stream.next());
+    assert_eq!(None, stream.next());
+}
@@ -0,0 +1,64 @@
+use std::ops::Range;
+
+/// The implementation a [`FrozenMap`](crate::facades::FrozenMap) picked for its payload, as
+/// reported by [`FrozenMap::strategy`](crate::facades::FrozenMap::strategy).
+///
+/// This makes it possible to check, in tests or at startup, that real keys end up on the
+/// fast path they're expected to.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::{FrozenMap, MapStrategy};
+///
+/// let map = FrozenMap::from([(10, "a"), (11, "b"), (12, "c"), (13, "d")]);
+/// assert_eq!(map.strategy(), MapStrategy::IntegerRange);
+///
+/// let map = FrozenMap::from([("alpha", 1), ("bravo", 2), ("gamma", 3), ("delta", 4)]);
+/// assert_eq!(map.strategy(), MapStrategy::LeftSlice { range: 0..1 });
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum MapStrategy {
+    /// Keys are compared one after the other, which is fastest for a handful of entries.
+    Scanning,
+
+    /// Keys are hashed in full into a hash table.
+    Common,
+
+    /// Keys are hashed in full and placed in their own slot by a perfect hash function.
+    PerfectHash,
+
+    /// Integer keys are used as their own hash codes in a hash table.
+    Integer,
+
+    /// Integer keys cover a contiguous range, so the position of an entry is computed
+    /// from its key.
+    IntegerRange,
+
+    /// Integer keys fill most of a range, which is indexed directly.
+    DenseInteger,
+
+    /// A handful of integer keys are compared one after the other.
+    IntegerScanning,
+
+    /// Keys are hashed by the bytes in `range`, counted from their start.
+    LeftSlice { range: Range<usize> },
+
+    /// Keys are hashed by the bytes in `range`, counted back from their end.
+    RightSlice { range: Range<usize> },
+
+    /// Keys are hashed by the bytes in `left`, counted from their start, together with the
+    /// bytes in `right`, counted back from their end.
+    SplitSlice {
+        left: Range<usize>,
+        right: Range<usize>,
+    },
+
+    /// Keys are hashed by the bytes in `range`, counted from their start, together with their
+    /// length.
+    LengthSlice { range: Range<usize> },
+
+    /// Keys are told apart by their length, which is used as their hash code.
+    Length,
+}
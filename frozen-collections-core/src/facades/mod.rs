@@ -1,11 +1,165 @@
+pub use analysis_options::*;
+pub use arena_frozen_map::*;
+pub use case_insensitive_frozen_map::*;
+pub use compact_frozen_map::*;
+pub use compact_frozen_set::*;
+pub use deferred_frozen_map::*;
+pub use duplicate_policy::*;
+pub use front_coded_frozen_map::*;
+pub use frozen_enum_map::*;
+pub use frozen_index_map::*;
+pub use frozen_interner::*;
+pub use frozen_keyed_set::*;
 pub use frozen_map::*;
+pub use frozen_map_builder::*;
+#[cfg(feature = "arc-swap")]
+pub use frozen_map_handle::*;
+pub use frozen_ordered_map::*;
+pub use frozen_prefix_map::*;
 pub use frozen_set::*;
+pub use frozen_string_map::*;
+pub use frozen_table::*;
+pub use frozen_type_map::*;
+pub use indirect_frozen_map::*;
+pub use key_suggester::*;
+pub use lazy_frozen_map::*;
+pub use lookup_stream::LookupStream;
+pub use map_strategy::*;
+pub use normalized_frozen_map::*;
+pub use packed_frozen_map::*;
+pub use secondary_index::*;
+pub use sharded_frozen_map::*;
+pub use streaming_frozen_map_builder::*;
+pub use tiered_map::*;
+#[cfg(feature = "zeroize")]
+pub use zeroizing_frozen_map::*;
+#[cfg(feature = "zeroize")]
+pub use zeroizing_frozen_set::*;
 
+mod analysis_options;
+mod arena_frozen_map;
+mod case_insensitive_frozen_map;
+mod compact_frozen_map;
+mod compact_frozen_set;
+mod deferred_frozen_map;
+mod duplicate_policy;
+mod front_coded_frozen_map;
+mod frozen_enum_map;
+mod frozen_index_map;
+mod frozen_interner;
+mod frozen_keyed_set;
 mod frozen_map;
+mod frozen_map_builder;
+#[cfg(feature = "arc-swap")]
+mod frozen_map_handle;
+mod frozen_ordered_map;
+mod frozen_prefix_map;
 mod frozen_set;
+mod frozen_string_map;
+mod frozen_table;
+mod frozen_type_map;
+mod indirect_frozen_map;
+mod key_suggester;
+mod lazy_frozen_map;
+mod lookup_stream;
+mod map_strategy;
+mod normalized_frozen_map;
+mod packed_frozen_map;
+mod secondary_index;
+mod sharded_frozen_map;
+mod streaming_frozen_map_builder;
+mod tiered_map;
+#[cfg(feature = "zeroize")]
+mod zeroizing_frozen_map;
+#[cfg(feature = "zeroize")]
+mod zeroizing_frozen_set;
+
+#[cfg(test)]
+mod arena_frozen_map_tests;
+
+#[cfg(test)]
+mod case_insensitive_frozen_map_tests;
+
+#[cfg(test)]
+mod compact_frozen_map_tests;
+
+#[cfg(test)]
+mod compact_frozen_set_tests;
+
+#[cfg(test)]
+mod deferred_frozen_map_tests;
+
+#[cfg(test)]
+mod front_coded_frozen_map_tests;
+
+#[cfg(test)]
+mod frozen_enum_map_tests;
+
+#[cfg(test)]
+mod frozen_index_map_tests;
+
+#[cfg(test)]
+mod frozen_interner_tests;
+
+#[cfg(test)]
+mod frozen_keyed_set_tests;
+
+#[cfg(all(test, feature = "arc-swap"))]
+mod frozen_map_handle_tests;
 
 #[cfg(test)]
 mod frozen_map_tests;
 
+#[cfg(test)]
+mod frozen_ordered_map_tests;
+
+#[cfg(test)]
+mod frozen_prefix_map_tests;
+
 #[cfg(test)]
 mod frozen_set_tests;
+
+#[cfg(test)]
+mod frozen_string_map_tests;
+
+#[cfg(test)]
+mod frozen_table_tests;
+
+#[cfg(test)]
+mod frozen_type_map_tests;
+
+#[cfg(test)]
+mod indirect_frozen_map_tests;
+
+#[cfg(test)]
+mod key_suggester_tests;
+
+#[cfg(test)]
+mod lazy_frozen_map_tests;
+
+#[cfg(test)]
+mod lookup_stream_tests;
+
+#[cfg(test)]
+mod normalized_frozen_map_tests;
+
+#[cfg(test)]
+mod packed_frozen_map_tests;
+
+#[cfg(test)]
+mod secondary_index_tests;
+
+#[cfg(test)]
+mod sharded_frozen_map_tests;
+
+#[cfg(test)]
+mod streaming_frozen_map_builder_tests;
+
+#[cfg(test)]
+mod tiered_map_tests;
+
+#[cfg(all(test, feature = "zeroize"))]
+mod zeroizing_frozen_map_tests;
+
+#[cfg(all(test, feature = "zeroize"))]
+mod zeroizing_frozen_set_tests;
@@ -0,0 +1,198 @@
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::RandomState;
+use std::hash::{BuildHasher, Hash};
+
+use crate::facades::FrozenMap;
+use crate::specialized_maps::{Iter, Keys, Values};
+use crate::traits::len::Len;
+
+/// A frozen map which normalizes keys before storing them and before looking them up.
+///
+/// The normalization function is applied to every key when the map is created and to every
+/// query, so lookups can be made insensitive to case, surrounding whitespace, punctuation,
+/// or any other difference which shouldn't matter, without callers having to remember to
+/// normalize their queries.
+///
+/// Keys are stored in their normalized form. Keys which normalize to the same value are
+/// duplicates, which is a logic error just like duplicate keys in a [`FrozenMap`].
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::NormalizedFrozenMap;
+///
+/// let map = NormalizedFrozenMap::from_vec(
+///     vec![("Content-Type".to_string(), 1), ("Accept".to_string(), 2)],
+///     |k: &String| k.trim().to_lowercase(),
+/// );
+///
+/// assert_eq!(map.get(&"content-type".to_string()), Some(&1));
+/// assert_eq!(map.get(&"  ACCEPT ".to_string()), Some(&2));
+/// assert_eq!(map.get(&"Host".to_string()), None);
+/// ```
+#[derive(Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct NormalizedFrozenMap<K, V, N, BH = RandomState> {
+    map: FrozenMap<K, V, BH>,
+    normalize: N,
+}
+
+impl<K, V, N, BH> NormalizedFrozenMap<K, V, N, BH>
+where
+    K: Hash + Eq,
+    N: Fn(&K) -> K,
+    BH: BuildHasher,
+{
+    /// Creates a normalized frozen map which will use the given hash builder to hash keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::NormalizedFrozenMap;
+    /// use std::hash::RandomState;
+    ///
+    /// let map = NormalizedFrozenMap::from_vec_with_hasher(vec![(-1, 2)], |k: &i32| k.abs(), RandomState::new());
+    /// assert_eq!(map.get(&1), Some(&2));
+    /// ```
+    #[must_use]
+    pub fn from_vec_with_hasher(payload: Vec<(K, V)>, normalize: N, bh: BH) -> Self {
+        let payload = payload
+            .into_iter()
+            .map(|(k, v)| (normalize(&k), v))
+            .collect();
+
+        Self {
+            map: FrozenMap::from_vec_with_hasher(payload, bh),
+            normalize,
+        }
+    }
+
+    /// Returns a reference to the value corresponding to the normalized key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::NormalizedFrozenMap;
+    ///
+    /// let map = NormalizedFrozenMap::from_vec(vec![('a', 1)], |k: &char| k.to_ascii_lowercase());
+    /// assert_eq!(map.get(&'A'), Some(&1));
+    /// assert_eq!(map.get(&'b'), None);
+    /// ```
+    #[inline]
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.map.get(&(self.normalize)(key))
+    }
+
+    /// Returns the stored key-value pair corresponding to the normalized key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::NormalizedFrozenMap;
+    ///
+    /// let map = NormalizedFrozenMap::from_vec(vec![('A', 1)], |k: &char| k.to_ascii_lowercase());
+    /// assert_eq!(map.get_key_value(&'A'), Some((&'a', &1)));
+    /// ```
+    #[inline]
+    pub fn get_key_value(&self, key: &K) -> Option<(&K, &V)> {
+        self.map.get_key_value(&(self.normalize)(key))
+    }
+
+    /// Returns a mutable reference to the value corresponding to the normalized key.
+    #[inline]
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let key = (self.normalize)(key);
+        self.map.get_mut(&key)
+    }
+
+    /// Returns `true` if the map contains a value for the normalized key.
+    #[inline]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.map.contains_key(&(self.normalize)(key))
+    }
+
+    /// Applies the map's normalization function to a key.
+    #[must_use]
+    pub fn normalize(&self, key: &K) -> K {
+        (self.normalize)(key)
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// An iterator visiting all key-value pairs in arbitrary order, with keys in their normalized form.
+    pub const fn iter(&self) -> Iter<'_, K, V> {
+        self.map.iter()
+    }
+
+    /// An iterator visiting all normalized keys in arbitrary order.
+    pub const fn keys(&self) -> Keys<'_, K, V> {
+        self.map.keys()
+    }
+
+    /// An iterator visiting all values in arbitrary order.
+    pub const fn values(&self) -> Values<'_, K, V> {
+        self.map.values()
+    }
+}
+
+impl<K, V, N> NormalizedFrozenMap<K, V, N, RandomState>
+where
+    K: Hash + Eq,
+    N: Fn(&K) -> K,
+{
+    /// Creates a normalized frozen map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::NormalizedFrozenMap;
+    ///
+    /// let map = NormalizedFrozenMap::from_vec(vec![(1, 2)], |k: &i32| *k);
+    /// ```
+    #[must_use]
+    pub fn from_vec(payload: Vec<(K, V)>, normalize: N) -> Self {
+        Self::from_vec_with_hasher(payload, normalize, RandomState::new())
+    }
+}
+
+impl<K, V, N, BH> Len for NormalizedFrozenMap<K, V, N, BH>
+where
+    K: Hash + Eq,
+    BH: BuildHasher,
+{
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+}
+
+impl<K, V, N, BH> Debug for NormalizedFrozenMap<K, V, N, BH>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.map.fmt(f)
+    }
+}
+
+impl<'a, K, V, N, BH> IntoIterator for &'a NormalizedFrozenMap<K, V, N, BH>
+where
+    K: Hash + Eq,
+    N: Fn(&K) -> K,
+    BH: BuildHasher,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
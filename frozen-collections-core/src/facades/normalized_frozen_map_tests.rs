@@ -0,0 +1,41 @@
+use crate::facades::{FrozenMapBuilder, NormalizedFrozenMap};
+
+#[allow(clippy::ptr_arg)]
+fn normalize(key: &String) -> String {
+    key.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[test]
+fn test_get() {
+    let payload: Vec<_> = (0..300).map(|i| (format!("Key-{i:03}"), i)).collect();
+    let mut m = NormalizedFrozenMap::from_vec(payload, normalize);
+
+    assert_eq!(300, m.len());
+    assert_eq!(Some(&42), m.get(&"KEY 042".to_string()));
+    assert_eq!(Some(&42), m.get(&"key042".to_string()));
+    assert_eq!(
+        Some((&"key007".to_string(), &7)),
+        m.get_key_value(&"-Key-007-".to_string())
+    );
+    assert!(m.contains_key(&"k.e.y.299".to_string()));
+    assert!(!m.contains_key(&"key300".to_string()));
+
+    *m.get_mut(&"KEY_001".to_string()).unwrap() = 100;
+    assert_eq!(Some(&100), m.get(&"key001".to_string()));
+
+    assert!(m.keys().all(|k| *k == normalize(k)));
+}
+
+#[test]
+fn test_builder() {
+    let mut builder = FrozenMapBuilder::new();
+    builder.extend([("  a ".to_string(), 1), ("B".to_string(), 2)]);
+
+    let m = builder.build_normalized(|k: &String| k.trim().to_lowercase());
+    assert_eq!(Some(&1), m.get(&"A".to_string()));
+    assert_eq!(Some(&2), m.get(&"b  ".to_string()));
+    assert_eq!("c", m.normalize(&" C ".to_string()));
+}
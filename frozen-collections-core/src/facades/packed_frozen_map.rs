@@ -0,0 +1,248 @@
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+
+use crate::facades::FrozenMap;
+use crate::specialized_maps::Keys;
+use crate::traits::len::Len;
+use crate::traits::packed_value::PackedValue;
+
+/// A frozen map whose values are bit-packed into a dense array, separately from the keys.
+///
+/// Every value is stored using the same number of bits, which by default is the smallest
+/// width able to represent all the values in the map. A map of millions of keys to 3-bit
+/// enums therefore spends under half a byte per value, instead of the 8 bytes or more
+/// needed to keep values inline next to their keys.
+///
+/// Since values are not stored as-is, lookups return values rather than references to them.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::PackedFrozenMap;
+///
+/// let map = PackedFrozenMap::from_vec(vec![("a", 1u8), ("b", 5), ("c", 3), ("d", 0)]);
+///
+/// assert_eq!(map.value_bits(), 3);
+/// assert_eq!(map.get(&"b"), Some(5));
+/// assert_eq!(map.get(&"e"), None);
+/// ```
+#[derive(Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct PackedFrozenMap<K, V, BH = RandomState> {
+    keys: FrozenMap<K, (), BH>,
+    bits: u32,
+    words: Box<[u64]>,
+    _values: PhantomData<V>,
+}
+
+impl<K, V, BH> PackedFrozenMap<K, V, BH>
+where
+    K: Hash + Eq,
+    V: PackedValue,
+    BH: BuildHasher,
+{
+    /// Creates a packed frozen map which will use the given hash builder to hash keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::PackedFrozenMap;
+    /// use std::hash::RandomState;
+    ///
+    /// let map = PackedFrozenMap::from_vec_with_hasher(vec![(1, true), (2, false)], RandomState::new());
+    /// assert_eq!(map.get(&1), Some(true));
+    /// ```
+    #[must_use]
+    pub fn from_vec_with_hasher(payload: Vec<(K, V)>, bh: BH) -> Self {
+        Self::new(payload, bh, None)
+    }
+
+    /// Creates a packed frozen map, optionally forcing the number of bits used per value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is larger than 64 or too small to represent one of the values.
+    pub(crate) fn new(payload: Vec<(K, V)>, bh: BH, bits: Option<u32>) -> Self {
+        let max = payload
+            .iter()
+            .map(|entry| entry.1.to_bits())
+            .max()
+            .unwrap_or(0);
+        let needed = u64::BITS - max.leading_zeros();
+        let bits = bits.map_or(needed, |bits| {
+            assert!(bits <= u64::BITS, "values can't use more than 64 bits");
+            assert!(bits >= needed, "a value doesn't fit in {bits} bits");
+            bits
+        });
+
+        let mut words = vec![0; (payload.len() * bits as usize).div_ceil(64)];
+        let mut index = 0;
+        let keys = FrozenMap::from_vec_with_hasher(payload, bh).map_values(|v| {
+            write_bits(&mut words, bits, index, v.to_bits());
+            index += 1;
+        });
+
+        Self {
+            keys,
+            bits,
+            words: words.into_boxed_slice(),
+            _values: PhantomData,
+        }
+    }
+
+    /// Returns the value corresponding to the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::PackedFrozenMap;
+    ///
+    /// let map = PackedFrozenMap::from_vec(vec![(1, 2u16)]);
+    /// assert_eq!(map.get(&1), Some(2));
+    /// assert_eq!(map.get(&2), None);
+    /// ```
+    #[inline]
+    pub fn get(&self, key: &K) -> Option<V> {
+        let index = self.keys.entry_index(key)?;
+        Some(V::from_bits(read_bits(&self.words, self.bits, index)))
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::PackedFrozenMap;
+    ///
+    /// let map = PackedFrozenMap::from_vec(vec![(1, 2u16)]);
+    /// assert!(map.contains_key(&1));
+    /// assert!(!map.contains_key(&2));
+    /// ```
+    #[inline]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.keys.contains_key(key)
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the number of bits used to store each value.
+    #[must_use]
+    pub const fn value_bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// An iterator visiting all keys in arbitrary order.
+    pub const fn keys(&self) -> Keys<'_, K, ()> {
+        self.keys.keys()
+    }
+
+    /// An iterator visiting all key-value pairs in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::PackedFrozenMap;
+    ///
+    /// let map = PackedFrozenMap::from_vec(vec![(1, 2u8), (3, 4)]);
+    /// let mut pairs: Vec<_> = map.iter().collect();
+    /// pairs.sort();
+    /// assert_eq!(pairs, vec![(&1, 2), (&3, 4)]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&K, V)> {
+        self.keys
+            .keys()
+            .enumerate()
+            .map(|(index, k)| (k, V::from_bits(read_bits(&self.words, self.bits, index))))
+    }
+}
+
+impl<K, V> PackedFrozenMap<K, V, RandomState>
+where
+    K: Hash + Eq,
+    V: PackedValue,
+{
+    /// Creates a packed frozen map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::PackedFrozenMap;
+    ///
+    /// let map = PackedFrozenMap::from_vec(vec![(1, 2u8)]);
+    /// ```
+    #[must_use]
+    pub fn from_vec(payload: Vec<(K, V)>) -> Self {
+        Self::from_vec_with_hasher(payload, RandomState::new())
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for PackedFrozenMap<K, V, RandomState>
+where
+    K: Hash + Eq,
+    V: PackedValue,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self::from_vec(Vec::from_iter(iter))
+    }
+}
+
+impl<K, V, BH> Len for PackedFrozenMap<K, V, BH>
+where
+    K: Hash + Eq,
+    V: PackedValue,
+    BH: BuildHasher,
+{
+    fn len(&self) -> usize {
+        self.keys.len()
+    }
+}
+
+impl<K, V, BH> Debug for PackedFrozenMap<K, V, BH>
+where
+    K: Hash + Eq + Debug,
+    V: PackedValue + Debug,
+    BH: BuildHasher,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+fn write_bits(words: &mut [u64], bits: u32, index: usize, value: u64) {
+    if bits == 0 {
+        return;
+    }
+
+    let pos = index * bits as usize;
+    let (word, shift) = (pos / 64, pos % 64);
+    words[word] |= value << shift;
+    if shift + bits as usize > 64 {
+        words[word + 1] |= value >> (64 - shift);
+    }
+}
+
+#[inline]
+fn read_bits(words: &[u64], bits: u32, index: usize) -> u64 {
+    if bits == 0 {
+        return 0;
+    }
+
+    let pos = index * bits as usize;
+    let (word, shift) = (pos / 64, pos % 64);
+    let mut value = words[word] >> shift;
+    if shift + bits as usize > 64 {
+        value |= words[word + 1] << (64 - shift);
+    }
+
+    value & (u64::MAX >> (64 - bits))
+}
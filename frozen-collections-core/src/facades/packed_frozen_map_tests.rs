@@ -0,0 +1,117 @@
+use crate::facades::{FrozenMapBuilder, PackedFrozenMap};
+use crate::traits::packed_value::PackedValue;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Color {
+    Red,
+    Green,
+    Blue,
+    Cyan,
+    Magenta,
+}
+
+impl PackedValue for Color {
+    fn to_bits(self) -> u64 {
+        self as u64
+    }
+
+    fn from_bits(bits: u64) -> Self {
+        [
+            Self::Red,
+            Self::Green,
+            Self::Blue,
+            Self::Cyan,
+            Self::Magenta,
+        ][usize::try_from(bits).unwrap()]
+    }
+}
+
+const COLORS: [Color; 5] = [
+    Color::Red,
+    Color::Green,
+    Color::Blue,
+    Color::Cyan,
+    Color::Magenta,
+];
+
+#[test]
+fn test_get() {
+    for len in [0, 1, 3, 10, 255, 256, 1000] {
+        let payload: Vec<_> = (0..len).map(|i| (i, COLORS[i % 5])).collect();
+        let m = PackedFrozenMap::from_vec(payload.clone());
+
+        assert_eq!(len, m.len());
+        if len > 4 {
+            assert_eq!(3, m.value_bits());
+        }
+
+        for (k, v) in &payload {
+            assert_eq!(Some(*v), m.get(k));
+        }
+
+        assert_eq!(None, m.get(&len));
+        assert!(!m.contains_key(&len));
+    }
+}
+
+#[test]
+fn test_string_keys() {
+    let payload: Vec<_> = (0..500)
+        .map(|i| (format!("KEY-{i:04}"), i64::from(i) - 250))
+        .collect();
+    let m = PackedFrozenMap::from_vec(payload.clone());
+
+    for (k, v) in &payload {
+        assert_eq!(Some(*v), m.get(k));
+    }
+
+    let mut pairs: Vec<_> = m.iter().map(|(k, v)| (k.clone(), v)).collect();
+    pairs.sort();
+    assert_eq!(payload, pairs);
+}
+
+#[test]
+fn test_wide_values() {
+    let payload = vec![(1u32, u64::MAX), (2, 0), (3, 1 << 63), (4, 12345), (5, 7)];
+    let m = PackedFrozenMap::from_vec(payload.clone());
+
+    assert_eq!(64, m.value_bits());
+    for (k, v) in &payload {
+        assert_eq!(Some(*v), m.get(k));
+    }
+}
+
+#[test]
+fn test_zero_bits() {
+    let m = PackedFrozenMap::from_vec(vec![(1, false), (2, false), (3, false), (4, false)]);
+
+    assert_eq!(0, m.value_bits());
+    assert_eq!(Some(false), m.get(&3));
+    assert_eq!(None, m.get(&5));
+}
+
+#[test]
+fn test_builder() {
+    let mut builder = FrozenMapBuilder::new();
+    builder.extend((0..100).map(|i| (i, i % 2 == 0)));
+    builder.value_bits(7);
+
+    let m = builder.build_packed();
+    assert_eq!(7, m.value_bits());
+    assert_eq!(Some(true), m.get(&42));
+    assert_eq!(Some(false), m.get(&43));
+}
+
+#[test]
+#[should_panic(expected = "doesn't fit")]
+fn test_builder_bits_too_small() {
+    let mut builder = FrozenMapBuilder::new();
+    builder.push(1, 8u8).value_bits(3);
+    _ = builder.build_packed();
+}
+
+#[test]
+fn test_debug() {
+    let m = PackedFrozenMap::from_vec(vec![(1, Color::Blue)]);
+    assert_eq!("{1: Blue}", format!("{m:?}"));
+}
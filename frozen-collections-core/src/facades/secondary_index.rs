@@ -0,0 +1,142 @@
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::RandomState;
+use std::hash::{BuildHasher, Hash};
+
+use crate::facades::FrozenMap;
+use crate::traits::equivalent::Equivalent;
+use crate::traits::len::Len;
+
+/// An index over the values of a [`FrozenMap`] or the records of a
+/// [`FrozenTable`](crate::facades::FrozenTable).
+///
+/// The index maps keys derived from the values to the positions of the entries holding them,
+/// so it offers another way into the map without duplicating its values. A position refers to
+/// an entry of the map or table the index was built over, and means nothing for any other.
+/// Indexes are created with [`FrozenMap::index_by`](crate::facades::FrozenMap::index_by) or
+/// [`FrozenTable::index_by`](crate::facades::FrozenTable::index_by).
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::FrozenMap;
+///
+/// struct Server {
+///     region: String,
+///     port: u16,
+/// }
+///
+/// let servers = FrozenMap::from([
+///     ("alpha", Server { region: "us".to_string(), port: 80 }),
+///     ("bravo", Server { region: "eu".to_string(), port: 443 }),
+///     ("charlie", Server { region: "us".to_string(), port: 8080 }),
+/// ]);
+///
+/// let by_region = servers.index_by(|s| s.region.clone());
+///
+/// assert_eq!(by_region.get("us").len(), 2);
+/// assert_eq!(by_region.get("asia"), &[]);
+///
+/// let (name, server) = servers.entry_at(by_region.get("eu")[0]).unwrap();
+/// assert_eq!((*name, server.port), ("bravo", 443));
+/// ```
+#[derive(Clone)]
+pub struct SecondaryIndex<K, BH = RandomState> {
+    positions: FrozenMap<K, Box<[usize]>, BH>,
+}
+
+impl<K, BH> SecondaryIndex<K, BH>
+where
+    K: Hash + Eq,
+    BH: BuildHasher,
+{
+    /// Creates an index from the keys derived from each entry of a map, in entry order.
+    pub(crate) fn new<I>(keys: I, bh: BH) -> Self
+    where
+        I: Iterator<Item = K>,
+    {
+        Self {
+            positions: FrozenMap::group_from_iter_with_hasher(keys.zip(0..), bh),
+        }
+    }
+
+    /// Returns the positions of the entries whose value has the given key, in increasing order.
+    ///
+    /// The slice is empty when no value has the key.
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> &[usize]
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.positions.get(key).map_or(&[], |p| p)
+    }
+
+    /// Returns `true` if some value has the given key.
+    #[must_use]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.positions.contains_key(key)
+    }
+
+    /// An iterator visiting the entries of `map` whose value has the given key.
+    ///
+    /// `map` must be the map the index was built over.
+    ///
+    /// # Panics
+    ///
+    /// May panic if `map` is smaller than the map the index was built over.
+    pub fn entries<'m, Q, MK, MV, MBH>(
+        &self,
+        key: &Q,
+        map: &'m FrozenMap<MK, MV, MBH>,
+    ) -> impl Iterator<Item = (&'m MK, &'m MV)> + use<'_, 'm, Q, K, BH, MK, MV, MBH>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.get(key).iter().map(|&index| {
+            map.entry_at(index)
+                .expect("The index must be used with the map it was built over")
+        })
+    }
+}
+
+impl<K, BH> SecondaryIndex<K, BH> {
+    /// Returns the number of distinct keys in the index.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns `true` if the index holds no keys.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// An iterator visiting all keys in arbitrary order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.positions.keys()
+    }
+
+    /// An iterator visiting all keys and the positions of the entries with them, in arbitrary
+    /// order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &[usize])> {
+        self.positions.iter().map(|(k, p)| (k, &**p))
+    }
+}
+
+impl<K, BH> Len for SecondaryIndex<K, BH> {
+    fn len(&self) -> usize {
+        self.positions.len()
+    }
+}
+
+impl<K, BH> Debug for SecondaryIndex<K, BH>
+where
+    K: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
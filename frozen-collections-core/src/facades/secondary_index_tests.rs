@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use crate::facades::FrozenMap;
+use crate::traits::len::Len;
+
+#[derive(Debug, PartialEq)]
+struct Server {
+    region: String,
+    port: u16,
+}
+
+fn servers(count: u16) -> FrozenMap<String, Server> {
+    (0..count)
+        .map(|i| {
+            let server = Server {
+                region: format!("region-{}", i % 7),
+                port: i,
+            };
+
+            (format!("server-{i:04}"), server)
+        })
+        .collect()
+}
+
+#[test]
+fn index_by_value_field() {
+    for count in [0, 1, 3, 10, 300, 2000] {
+        let map = servers(count);
+        let index = map.index_by(|s| s.region.clone());
+        assert_eq!(usize::from(count).min(7), index.len());
+        assert_eq!(index.len(), Len::len(&index));
+        assert_eq!(count == 0, index.is_empty());
+
+        let mut expected: HashMap<_, Vec<_>> = HashMap::new();
+        for (name, server) in &map {
+            expected
+                .entry(server.region.clone())
+                .or_default()
+                .push(name);
+        }
+
+        for (region, names) in expected {
+            assert!(index.contains_key(region.as_str()));
+            let positions = index.get(region.as_str());
+            assert!(positions.is_sorted());
+            assert_eq!(names.len(), positions.len());
+
+            let found: Vec<_> = index
+                .entries(region.as_str(), &map)
+                .map(|(k, _)| k)
+                .collect();
+            assert_eq!(names, found);
+            assert!(index
+                .entries(region.as_str(), &map)
+                .all(|(_, s)| s.region == region));
+        }
+
+        assert!(index.get("region-7").is_empty());
+        assert!(!index.contains_key("region-7"));
+        assert_eq!(0, index.entries("region-7", &map).count());
+        assert_eq!(
+            usize::from(count),
+            index.iter().map(|(_, p)| p.len()).sum::<usize>()
+        );
+    }
+}
+
+#[test]
+fn unique_values() {
+    let map = servers(100);
+    let by_port = map.index_by(|s| s.port);
+    assert_eq!(100, by_port.len());
+
+    let position = by_port.get(&42)[0];
+    let (name, server) = map.entry_at(position).unwrap();
+    assert_eq!("server-0042", name);
+    assert_eq!(42, server.port);
+    assert_eq!(None, map.entry_at(100));
+    assert_eq!(100, by_port.keys().count());
+}
+
+#[test]
+fn debug() {
+    let map = FrozenMap::from([(1, 'a')]);
+    assert_eq!("{'a': [0]}", format!("{:?}", map.index_by(|c| *c)));
+}
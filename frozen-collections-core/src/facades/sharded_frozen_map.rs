@@ -0,0 +1,417 @@
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::num::NonZeroUsize;
+use std::panic::resume_unwind;
+use std::thread;
+
+use crate::analyzers::hash_code_analyzer::analyze_hash_codes;
+use crate::specialized_maps::hash_table::HashTable;
+use crate::specialized_maps::MapStats;
+use crate::traits::equivalent::Equivalent;
+use crate::traits::len::Len;
+
+/// The number of entries from which a map is split into several shards by default.
+pub const MIN_SHARDED_LEN: usize = 1024 * 1024;
+
+/// The largest number of shards a map is split into.
+pub const MAX_SHARDS: usize = 256;
+
+/// Controls how a [`ShardedFrozenMap`] splits its entries and where it builds its shards.
+///
+/// # Examples
+///
+/// ```
+/// use std::hash::RandomState;
+///
+/// use frozen_collections_core::facades::{ShardOptions, ShardedFrozenMap};
+///
+/// let options = ShardOptions::new().shards(4);
+/// let payload: Vec<_> = (0..1000).map(|i| (i, i * 2)).collect();
+/// let map = ShardedFrozenMap::from_vec_with_options(payload, RandomState::new(), options);
+///
+/// assert_eq!(map.shard_count(), 4);
+/// assert_eq!(map.get(&500), Some(&1000));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ShardOptions {
+    shards: Option<usize>,
+    placement: Option<fn(usize)>,
+}
+
+impl ShardOptions {
+    /// Creates options which shard maps of at least [`MIN_SHARDED_LEN`] entries across the
+    /// available cores, and leave shards wherever the system allocator puts them.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            shards: None,
+            placement: None,
+        }
+    }
+
+    /// Splits maps into this many shards, whatever their size.
+    ///
+    /// The count is rounded up to a power of two, and capped at [`MAX_SHARDS`].
+    #[must_use]
+    pub const fn shards(mut self, count: usize) -> Self {
+        self.shards = Some(count);
+        self
+    }
+
+    /// Calls `place` with the index of each shard, on the thread building the shard, before
+    /// the shard allocates any memory.
+    ///
+    /// Operating systems back memory with pages from the NUMA node of the thread which first
+    /// writes to it, so binding the thread to a node from `place`, for example with
+    /// `sched_setaffinity`, keeps the whole shard local to that node. Shards are then copied
+    /// into memory allocated by their own thread, rather than left where the payload was.
+    #[must_use]
+    pub const fn placement(mut self, place: fn(usize)) -> Self {
+        self.placement = Some(place);
+        self
+    }
+
+    /// Returns the number of shards a payload of `len` entries is split into.
+    fn shard_count(&self, len: usize) -> usize {
+        let count = self.shards.unwrap_or_else(|| {
+            if len < MIN_SHARDED_LEN {
+                1
+            } else {
+                thread::available_parallelism().map_or(1, NonZeroUsize::get)
+            }
+        });
+
+        count.clamp(1, MAX_SHARDS).next_power_of_two()
+    }
+}
+
+/// A frozen map split into independent hash tables, for payloads too large to be frozen on a
+/// single core.
+///
+/// Each entry goes to the shard picked by the high bits of its hash code, and each shard is
+/// a hash table of its own, analyzed and built on its own thread. The keys are hashed once,
+/// split across the same number of threads, and each shard is built from the hash codes of
+/// its entries. Lookups hash the key once, and find the key within its shard from the
+/// remainder of the whole hash code over the shard's number of slots, so they cost about the
+/// same as in a single table. Shards can also be placed on distinct NUMA nodes, see
+/// [`ShardOptions::placement`].
+///
+/// Payloads smaller than [`MIN_SHARDED_LEN`] are held in a single shard, unless a number of
+/// shards is given explicitly. As with a [`FrozenMap`](crate::facades::FrozenMap), the keys
+/// of the payload are expected to be distinct.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::ShardedFrozenMap;
+///
+/// let map = ShardedFrozenMap::from_vec((0..10_000).map(|i| (i.to_string(), i)).collect());
+///
+/// assert_eq!(map.len(), 10_000);
+/// assert_eq!(map.get("42"), Some(&42));
+/// assert_eq!(map.get("-1"), None);
+/// ```
+#[derive(Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct ShardedFrozenMap<K, V, BH = RandomState> {
+    shards: Box<[HashTable<K, V>]>,
+
+    /// The number of bits a hash code is shifted right by to get its shard, which is 64 for a
+    /// single shard.
+    shift: u32,
+    bh: BH,
+}
+
+impl<K, V, BH> ShardedFrozenMap<K, V, BH>
+where
+    K: Hash + Send,
+    V: Send,
+    BH: BuildHasher + Sync,
+{
+    /// Creates a map which will use the given hash builder to hash keys, sharded if the payload
+    /// holds at least [`MIN_SHARDED_LEN`] entries.
+    #[must_use]
+    pub fn from_vec_with_hasher(payload: Vec<(K, V)>, bh: BH) -> Self {
+        Self::from_vec_with_options(payload, bh, ShardOptions::new())
+    }
+
+    /// Creates a map which will use the given hash builder to hash keys, sharded as the options
+    /// specify.
+    ///
+    /// # Panics
+    ///
+    /// Panics if hashing a key panics, and resumes the panic of the thread building a shard, if
+    /// any panicked.
+    #[must_use]
+    pub fn from_vec_with_options(mut payload: Vec<(K, V)>, bh: BH, options: ShardOptions) -> Self {
+        let count = options.shard_count(payload.len());
+        let shift = u64::BITS - count.trailing_zeros();
+        let hash_codes = hash_keys(&mut payload, &bh, count);
+        let parts = partition(payload, hash_codes, shift, count);
+
+        let shards = if count == 1 && options.placement.is_none() {
+            parts
+                .into_iter()
+                .map(|(entries, hash_codes)| build_shard(entries, hash_codes))
+                .collect()
+        } else {
+            thread::scope(|scope| {
+                // every worker is spawned before any is joined, so the shards build concurrently
+                #[allow(clippy::needless_collect)]
+                let workers: Vec<_> = parts
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, (entries, hash_codes))| {
+                        scope.spawn(move || match options.placement {
+                            Some(place) => {
+                                place(index);
+
+                                // move the entries to memory first written by this thread
+                                let mut local = Vec::with_capacity(entries.len());
+                                local.extend(entries);
+                                build_shard(local, hash_codes)
+                            }
+                            None => build_shard(entries, hash_codes),
+                        })
+                    })
+                    .collect();
+
+                workers
+                    .into_iter()
+                    .map(|worker| worker.join().unwrap_or_else(|panic| resume_unwind(panic)))
+                    .collect()
+            })
+        };
+
+        Self { shards, shift, bh }
+    }
+}
+
+impl<K, V> ShardedFrozenMap<K, V, RandomState>
+where
+    K: Hash + Send,
+    V: Send,
+{
+    /// Creates a map, sharded if the payload holds at least [`MIN_SHARDED_LEN`] entries.
+    #[must_use]
+    pub fn from_vec(payload: Vec<(K, V)>) -> Self {
+        Self::from_vec_with_hasher(payload, RandomState::new())
+    }
+}
+
+impl<K, V, BH> ShardedFrozenMap<K, V, BH>
+where
+    BH: BuildHasher,
+{
+    /// Returns a reference to the value corresponding to the key.
+    #[inline]
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let (_, v) = self.get_key_value(key)?;
+        Some(v)
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    #[inline]
+    #[must_use]
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let hash_code = self.bh.hash_one(key);
+        let (k, v) = self.shards[self.shard_index(hash_code)].find(hash_code, key)?;
+        Some((k, v))
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    #[inline]
+    #[must_use]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let hash_code = self.bh.hash_one(key);
+        let index = self.shard_index(hash_code);
+        let (_, v) = self.shards[index].find_mut(hash_code, key)?;
+        Some(v)
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    #[inline]
+    #[must_use]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Returns the shard holding the entries whose keys have the given hash code.
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    const fn shard_index(&self, hash_code: u64) -> usize {
+        // the shift leaves fewer bits than there are shards, which always fits a usize
+        match hash_code.checked_shr(self.shift) {
+            Some(index) => index as usize,
+            None => 0,
+        }
+    }
+}
+
+impl<K, V, BH> ShardedFrozenMap<K, V, BH> {
+    /// Returns the number of elements in the map.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(HashTable::len).sum()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of hash tables the entries are split into.
+    #[must_use]
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Returns the hash builder used to hash keys.
+    #[must_use]
+    pub const fn hasher(&self) -> &BH {
+        &self.bh
+    }
+
+    /// An iterator visiting all key-value pairs, one shard after the other.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.shards
+            .iter()
+            .flat_map(|shard| shard.entries.iter().map(|(k, v)| (k, v)))
+    }
+
+    /// An iterator visiting all keys, one shard after the other.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(k, _)| k)
+    }
+
+    /// An iterator visiting all values, one shard after the other.
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, v)| v)
+    }
+
+    /// Describes the layout of the map, as the sum of its shards.
+    ///
+    /// The longest probe chain is the longest of any shard.
+    #[must_use]
+    pub fn stats(&self) -> MapStats {
+        let shards: Vec<_> = self.shards.iter().map(HashTable::stats).collect();
+        MapStats::new::<K, V>(
+            shards.iter().map(|s| s.len).sum(),
+            shards.iter().map(|s| s.num_slots).sum(),
+            shards
+                .iter()
+                .map(|s| s.longest_probe_chain)
+                .max()
+                .unwrap_or(0),
+            shards.iter().map(|s| s.slot_bytes).sum(),
+        )
+    }
+}
+
+/// The entries of a shard, along with the hash codes of their keys.
+type Part<K, V> = (Vec<(K, V)>, Vec<u64>);
+
+/// Computes the hash code of the key of each entry, splitting the payload across one thread
+/// per shard.
+fn hash_keys<K, V, BH>(payload: &mut [(K, V)], bh: &BH, count: usize) -> Vec<u64>
+where
+    K: Hash + Send,
+    V: Send,
+    BH: BuildHasher + Sync,
+{
+    let hash = |entry: &(K, V)| bh.hash_one(&entry.0);
+    if count == 1 {
+        return payload.iter().map(hash).collect();
+    }
+
+    // the threads only read the entries, but they get them through mutable chunks, since
+    // entries can be sent to another thread without being shareable
+    let mut hash_codes = vec![0; payload.len()];
+    let chunk_len = payload.len().div_ceil(count).max(1);
+    thread::scope(|scope| {
+        for (entries, codes) in payload
+            .chunks_mut(chunk_len)
+            .zip(hash_codes.chunks_mut(chunk_len))
+        {
+            scope.spawn(move || {
+                for (code, entry) in codes.iter_mut().zip(entries.iter()) {
+                    *code = hash(entry);
+                }
+            });
+        }
+    });
+
+    hash_codes
+}
+
+/// Splits a payload into the entries of each shard, along with their hash codes, keeping
+/// their relative order.
+fn partition<K, V>(
+    payload: Vec<(K, V)>,
+    hash_codes: Vec<u64>,
+    shift: u32,
+    count: usize,
+) -> Vec<Part<K, V>> {
+    if count == 1 {
+        return vec![(payload, hash_codes)];
+    }
+
+    // the shift leaves fewer bits than there are shards, which always fits a usize
+    #[allow(clippy::cast_possible_truncation)]
+    let shard_index = |hash_code: u64| (hash_code >> shift) as usize;
+
+    let mut lens = vec![0; count];
+    for &hash_code in &hash_codes {
+        lens[shard_index(hash_code)] += 1;
+    }
+
+    let mut parts: Vec<_> = lens
+        .into_iter()
+        .map(|len| (Vec::with_capacity(len), Vec::with_capacity(len)))
+        .collect();
+
+    for (entry, hash_code) in payload.into_iter().zip(hash_codes) {
+        let (entries, codes) = &mut parts[shard_index(hash_code)];
+        entries.push(entry);
+        codes.push(hash_code);
+    }
+
+    parts
+}
+
+/// Analyzes the hash codes of the entries of a shard and builds its table.
+fn build_shard<K, V>(entries: Vec<(K, V)>, hash_codes: Vec<u64>) -> HashTable<K, V> {
+    let code_analysis = analyze_hash_codes(hash_codes.iter().copied());
+    HashTable::from_hash_codes(entries, hash_codes, code_analysis, false)
+}
+
+impl<K, V, BH> Len for ShardedFrozenMap<K, V, BH> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V, BH> Debug for ShardedFrozenMap<K, V, BH>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
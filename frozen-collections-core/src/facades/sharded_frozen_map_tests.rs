@@ -0,0 +1,95 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+
+use crate::facades::{ShardOptions, ShardedFrozenMap, MAX_SHARDS};
+use crate::hashers::SeededState;
+use crate::traits::len::Len;
+
+#[test]
+fn lookups_match_a_hash_map() {
+    let payload: Vec<_> = (0..20_000).map(|i| (format!("key-{i}"), i)).collect();
+    let expected: HashMap<_, _> = payload.iter().cloned().collect();
+
+    for shards in [1, 2, 3, 8] {
+        let mut map = ShardedFrozenMap::from_vec_with_options(
+            payload.clone(),
+            SeededState::new(7),
+            ShardOptions::new().shards(shards),
+        );
+
+        assert_eq!(shards.next_power_of_two(), map.shard_count());
+        assert_eq!(expected.len(), map.len());
+        assert_eq!(expected.len(), Len::len(&map));
+        assert_eq!(expected.len(), map.stats().len);
+
+        for (k, v) in &expected {
+            assert_eq!(Some(v), map.get(k.as_str()));
+            assert_eq!(Some((k, v)), map.get_key_value(k.as_str()));
+        }
+
+        assert!(!map.contains_key("key-20000"));
+        assert!(!map.contains_key(""));
+
+        *map.get_mut("key-42").unwrap() = -1;
+        assert_eq!(Some(&-1), map.get("key-42"));
+
+        let visited: HashMap<_, _> = map.iter().map(|(k, v)| (k.clone(), *v)).collect();
+        assert_eq!(expected.len(), visited.len());
+        assert_eq!(expected.len(), map.keys().count());
+        assert_eq!(expected.len(), map.values().count());
+    }
+}
+
+#[test]
+fn shard_counts() {
+    let small = ShardedFrozenMap::from_vec((0..100).map(|i| (i, i)).collect());
+    assert_eq!(1, small.shard_count());
+
+    let capped = ShardedFrozenMap::from_vec_with_options(
+        vec![(1, 1)],
+        SeededState::new(1),
+        ShardOptions::new().shards(MAX_SHARDS * 4),
+    );
+    assert_eq!(MAX_SHARDS, capped.shard_count());
+    assert_eq!(Some(&1), capped.get(&1));
+}
+
+#[test]
+fn placement_runs_on_each_shard_thread() {
+    thread_local! {
+        static PLACED: Cell<Option<usize>> = const { Cell::new(None) };
+    }
+
+    fn place(index: usize) {
+        // every shard is built on a thread of its own
+        PLACED.with(|placed| assert_eq!(None, placed.replace(Some(index))));
+    }
+
+    let payload: Vec<_> = (0..5000).map(|i| (i.to_string(), i)).collect();
+    let map = ShardedFrozenMap::from_vec_with_options(
+        payload,
+        SeededState::new(3),
+        ShardOptions::new().shards(4).placement(place),
+    );
+
+    assert_eq!(None, PLACED.with(Cell::get));
+    assert_eq!(5000, map.len());
+    assert_eq!(Some(&4999), map.get("4999"));
+}
+
+#[test]
+fn empty() {
+    let map = ShardedFrozenMap::<String, i32>::from_vec(Vec::new());
+    assert!(map.is_empty());
+    assert_eq!(None, map.get("a"));
+    assert_eq!(0, map.iter().count());
+    assert_eq!("{}", format!("{map:?}"));
+
+    let map = ShardedFrozenMap::<String, i32, _>::from_vec_with_options(
+        Vec::new(),
+        SeededState::new(3),
+        ShardOptions::new().shards(4),
+    );
+    assert!(map.is_empty());
+    assert_eq!(None, map.get("a"));
+}
@@ -0,0 +1,135 @@
+use std::hash::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::mem::take;
+use std::panic::resume_unwind;
+use std::sync::mpsc::{channel, Sender};
+use std::thread::{spawn, JoinHandle};
+
+use crate::facades::FrozenMap;
+
+/// The number of entries accumulated before they are handed over to the background thread.
+const BATCH_SIZE: usize = 1024;
+
+/// Builds a frozen map on a background thread while entries are still being produced.
+///
+/// Entries pushed into the builder are forwarded in batches to a dedicated thread which
+/// accumulates them and, once [`finish`](Self::finish) is called, analyzes the keys and lays
+/// out the map. The producing thread only pays for moving entries around, so it can keep
+/// doing I/O, such as parsing a large input file, while the background thread works.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::StreamingFrozenMapBuilder;
+///
+/// let mut builder = StreamingFrozenMapBuilder::new();
+/// for line in "a=1\nb=2\nc=3".lines() {
+///     let (k, v) = line.split_once('=').unwrap();
+///     builder.push(k.to_string(), v.parse::<i32>().unwrap());
+/// }
+///
+/// let map = builder.finish();
+/// assert_eq!(map.get(&"b".to_string()), Some(&2));
+/// ```
+#[allow(clippy::module_name_repetitions)]
+pub struct StreamingFrozenMapBuilder<K, V, BH = RandomState> {
+    batch: Vec<(K, V)>,
+    sender: Sender<Vec<(K, V)>>,
+    worker: JoinHandle<FrozenMap<K, V, BH>>,
+}
+
+impl<K, V, BH> StreamingFrozenMapBuilder<K, V, BH>
+where
+    K: Hash + Eq + Send + 'static,
+    V: Send + 'static,
+    BH: BuildHasher + Send + 'static,
+{
+    /// Creates a builder for a map which will use the given hash builder to hash keys.
+    ///
+    /// This starts the background thread.
+    #[must_use]
+    pub fn with_hasher(bh: BH) -> Self {
+        let (sender, receiver) = channel::<Vec<(K, V)>>();
+        let worker = spawn(move || {
+            let mut payload = Vec::new();
+            for batch in receiver {
+                payload.extend(batch);
+            }
+
+            FrozenMap::from_vec_with_hasher(payload, bh)
+        });
+
+        Self {
+            batch: Vec::with_capacity(BATCH_SIZE),
+            sender,
+            worker,
+        }
+    }
+
+    /// Adds an entry to the map being built.
+    pub fn push(&mut self, key: K, value: V) {
+        self.batch.push((key, value));
+        if self.batch.len() >= BATCH_SIZE {
+            self.flush();
+        }
+    }
+
+    /// Waits for the background thread to finish building the map, and returns it.
+    ///
+    /// # Panics
+    ///
+    /// Propagates any panic which occurred on the background thread.
+    #[must_use]
+    pub fn finish(mut self) -> FrozenMap<K, V, BH> {
+        self.flush();
+
+        let Self { sender, worker, .. } = self;
+        drop(sender);
+
+        worker.join().unwrap_or_else(|e| resume_unwind(e))
+    }
+
+    fn flush(&mut self) {
+        if !self.batch.is_empty() {
+            // if sending fails the background thread has panicked, which is reported by `finish`
+            _ = self.sender.send(take(&mut self.batch));
+        }
+    }
+}
+
+impl<K, V> StreamingFrozenMapBuilder<K, V, RandomState>
+where
+    K: Hash + Eq + Send + 'static,
+    V: Send + 'static,
+{
+    /// Creates a builder.
+    ///
+    /// This starts the background thread.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<K, V> Default for StreamingFrozenMapBuilder<K, V, RandomState>
+where
+    K: Hash + Eq + Send + 'static,
+    V: Send + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, BH> Extend<(K, V)> for StreamingFrozenMapBuilder<K, V, BH>
+where
+    K: Hash + Eq + Send + 'static,
+    V: Send + 'static,
+    BH: BuildHasher + Send + 'static,
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (k, v) in iter {
+            self.push(k, v);
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use crate::facades::StreamingFrozenMapBuilder;
+
+#[test]
+fn test_streaming() {
+    for len in [0, 1, 10, 1023, 1024, 1025, 5000] {
+        let mut builder = StreamingFrozenMapBuilder::new();
+        for i in 0..len {
+            builder.push(format!("K{i:05}"), i);
+        }
+
+        let m = builder.finish();
+        assert_eq!(len, m.len());
+        for i in 0..len {
+            assert_eq!(Some(&i), m.get(&format!("K{i:05}")));
+        }
+    }
+}
+
+#[test]
+fn test_extend() {
+    let mut builder = StreamingFrozenMapBuilder::default();
+    builder.extend((0..2000u32).map(|i| (i, i * 2)));
+
+    let m = builder.finish();
+    assert_eq!(Some(&3998), m.get(&1999));
+}
+
+#[test]
+fn test_abandoned_builder() {
+    let mut builder = StreamingFrozenMapBuilder::new();
+    builder.extend((0..3000).map(|i| (i, i)));
+    drop(builder);
+}
@@ -0,0 +1,232 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::RandomState;
+use std::hash::{BuildHasher, Hash};
+
+use crate::facades::FrozenMap;
+use crate::traits::len::Len;
+
+/// A frozen map with a small mutable overlay, for data which is mostly static.
+///
+/// Lookups check the overlay first and fall back to the frozen base. Inserting a key puts it in
+/// the overlay, shadowing any value the base holds for it, while removing a key held by the
+/// base puts a tombstone in the overlay, which hides it. The base is never modified, so updates
+/// are cheap, but every lookup pays for probing the overlay too. Calling
+/// [`refreeze`](Self::refreeze) once the overlay has grown merges it into a new frozen base.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::TieredMap;
+///
+/// let mut map = TieredMap::from_vec(vec![("a", 1), ("b", 2), ("c", 3)]);
+///
+/// map.insert("b", 20);
+/// map.insert("d", 4);
+/// assert!(map.remove("c"));
+///
+/// assert_eq!(map.get("b"), Some(&20));
+/// assert_eq!(map.get("c"), None);
+/// assert_eq!(map.len(), 3);
+/// assert_eq!(map.overlay_len(), 3);
+///
+/// map.refreeze();
+/// assert_eq!(map.get("d"), Some(&4));
+/// assert_eq!(map.overlay_len(), 0);
+/// ```
+#[derive(Clone)]
+pub struct TieredMap<K, V, BH = RandomState> {
+    base: FrozenMap<K, V, BH>,
+
+    /// Values which replace those of the base, with `None` hiding a key held by the base.
+    overlay: HashMap<K, Option<V>, BH>,
+    bh: BH,
+}
+
+impl<K, V, BH> TieredMap<K, V, BH>
+where
+    K: Hash + Eq,
+    BH: BuildHasher + Clone,
+{
+    /// Creates a tiered map with an empty overlay, which will use the given hash builder to hash
+    /// keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::facades::TieredMap;
+    /// use std::hash::RandomState;
+    ///
+    /// let map = TieredMap::from_vec_with_hasher(vec![(1, 2)], RandomState::new());
+    /// assert_eq!(map.get(&1), Some(&2));
+    /// ```
+    #[must_use]
+    pub fn from_vec_with_hasher(payload: Vec<(K, V)>, bh: BH) -> Self {
+        Self {
+            base: FrozenMap::from_vec_with_hasher(payload, bh.clone()),
+            overlay: HashMap::with_hasher(bh.clone()),
+            bh,
+        }
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.overlay
+            .get(key)
+            .map_or_else(|| self.base.get(key), Option::as_ref)
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    #[must_use]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Sets the value of a key in the overlay, shadowing any value the base holds for it.
+    pub fn insert(&mut self, key: K, value: V) {
+        _ = self.overlay.insert(key, Some(value));
+    }
+
+    /// Removes a key from the map, returning `true` if the map held it.
+    ///
+    /// Keys held by the base are hidden by a tombstone in the overlay.
+    pub fn remove<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q> + Clone,
+        Q: ?Sized + Hash + Eq,
+    {
+        let present = self.contains_key(key);
+        match self.base.get_key_value(key) {
+            Some((k, _)) => _ = self.overlay.insert(k.clone(), None),
+            None => _ = self.overlay.remove(key),
+        }
+
+        present
+    }
+
+    /// Returns the number of elements in the map.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        let mut len = self.base.len();
+        for (k, v) in &self.overlay {
+            match (self.base.contains_key(k), v.is_some()) {
+                (false, true) => len += 1,
+                (true, false) => len -= 1,
+                _ => {}
+            }
+        }
+
+        len
+    }
+
+    /// Returns `true` if the map contains no elements.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of keys inserted or removed since the base was last frozen.
+    #[must_use]
+    pub fn overlay_len(&self) -> usize {
+        self.overlay.len()
+    }
+
+    /// Returns the frozen base, which ignores the changes held by the overlay.
+    #[must_use]
+    pub const fn base(&self) -> &FrozenMap<K, V, BH> {
+        &self.base
+    }
+
+    /// An iterator visiting all key-value pairs in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        let overlay = self
+            .overlay
+            .iter()
+            .filter_map(|(k, v)| v.as_ref().map(|v| (k, v)));
+        let base = self
+            .base
+            .iter()
+            .filter(|(k, _)| !self.overlay.contains_key(*k));
+
+        overlay.chain(base)
+    }
+
+    /// Merges the overlay into a new frozen base, leaving the overlay empty.
+    ///
+    /// This runs the full analysis of the keys, so it's meant to be called periodically rather
+    /// than after every change.
+    pub fn refreeze(&mut self) {
+        if self.overlay.is_empty() {
+            return;
+        }
+
+        let base = std::mem::take(&mut self.base);
+        let mut payload: Vec<_> = base
+            .into_entries()
+            .into_vec()
+            .into_iter()
+            .filter(|(k, _)| !self.overlay.contains_key(k))
+            .collect();
+        payload.extend(self.overlay.drain().filter_map(|(k, v)| Some((k, v?))));
+
+        self.base = FrozenMap::from_vec_with_hasher(payload, self.bh.clone());
+    }
+
+    /// Merges the overlay into the base and returns the resulting frozen map.
+    #[must_use]
+    pub fn into_frozen(mut self) -> FrozenMap<K, V, BH> {
+        self.refreeze();
+        self.base
+    }
+}
+
+impl<K, V> TieredMap<K, V, RandomState>
+where
+    K: Hash + Eq,
+{
+    /// Creates a tiered map with an empty overlay.
+    #[must_use]
+    pub fn from_vec(payload: Vec<(K, V)>) -> Self {
+        Self::from_vec_with_hasher(payload, RandomState::new())
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for TieredMap<K, V, RandomState>
+where
+    K: Hash + Eq,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self::from_vec(Vec::from_iter(iter))
+    }
+}
+
+impl<K, V, BH> Len for TieredMap<K, V, BH>
+where
+    K: Hash + Eq,
+    BH: BuildHasher + Clone,
+{
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V, BH> Debug for TieredMap<K, V, BH>
+where
+    K: Hash + Eq + Debug,
+    V: Debug,
+    BH: BuildHasher + Clone,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+
+use crate::facades::TieredMap;
+use crate::traits::len::Len;
+
+#[test]
+fn overlay_shadows_base() {
+    let mut map: TieredMap<_, _> = (0..300).map(|i| (format!("Key-{i:03}"), i)).collect();
+    let mut expected: HashMap<_, _> = map.iter().map(|(k, v)| (k.clone(), *v)).collect();
+    assert_eq!(300, map.len());
+
+    for i in (0..400).step_by(3) {
+        let key = format!("Key-{i:03}");
+        map.insert(key.clone(), i * 10);
+        _ = expected.insert(key, i * 10);
+    }
+
+    for i in (0..400).step_by(5) {
+        let key = format!("Key-{i:03}");
+        assert_eq!(expected.remove(&key).is_some(), map.remove(key.as_str()));
+        assert!(!map.remove(key.as_str()));
+    }
+
+    let check = |map: &TieredMap<String, i32>| {
+        assert_eq!(expected.len(), map.len());
+        assert_eq!(expected.len(), Len::len(map));
+        for i in 0..400 {
+            let key = format!("Key-{i:03}");
+            assert_eq!(expected.get(&key), map.get(key.as_str()));
+            assert_eq!(expected.contains_key(&key), map.contains_key(&key));
+        }
+
+        let merged: HashMap<_, _> = map.iter().map(|(k, v)| (k.clone(), *v)).collect();
+        assert_eq!(expected, merged);
+    };
+
+    check(&map);
+    assert!(map.overlay_len() > 0);
+    assert_eq!(300, map.base().len());
+
+    map.refreeze();
+    assert_eq!(0, map.overlay_len());
+    assert_eq!(expected.len(), map.base().len());
+    check(&map);
+
+    let frozen = map.into_frozen();
+    assert_eq!(expected.len(), frozen.len());
+}
+
+#[test]
+fn removing_overlay_keys() {
+    let mut map = TieredMap::from_vec(vec![(1, 'a')]);
+    map.insert(2, 'b');
+    assert!(map.remove(&2));
+    assert_eq!(0, map.overlay_len());
+
+    assert!(map.remove(&1));
+    assert!(map.is_empty());
+    assert_eq!("{}", format!("{map:?}"));
+
+    map.insert(1, 'c');
+    assert_eq!("{1: 'c'}", format!("{map:?}"));
+}
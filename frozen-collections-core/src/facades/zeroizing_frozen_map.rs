@@ -0,0 +1,108 @@
+use std::hash::Hash;
+use std::hash::RandomState;
+use std::ops::Deref;
+
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::facades::FrozenMap;
+
+/// A frozen map which wipes its keys, values and hash table metadata when it's dropped.
+///
+/// Maps holding secrets, such as allowlists of API tokens, shouldn't leave them behind in freed
+/// memory. This wraps a [`FrozenMap`], derefs to it for lookups, and zeroizes it on drop. The
+/// keys and values must implement [`Zeroize`], which is why this is a separate type rather than
+/// a property of every frozen map.
+///
+/// This type is only available with the `zeroize` feature.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::ZeroizingFrozenMap;
+///
+/// let tokens: ZeroizingFrozenMap<_, _> =
+///     [("token-1".to_string(), 1), ("token-2".to_string(), 2)].into_iter().collect();
+///
+/// assert_eq!(tokens.get(&"token-1".to_string()), Some(&1));
+/// assert_eq!(tokens.get(&"token-3".to_string()), None);
+/// ```
+#[derive(Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct ZeroizingFrozenMap<K, V, BH = RandomState>
+where
+    K: Zeroize,
+    V: Zeroize,
+{
+    map: FrozenMap<K, V, BH>,
+}
+
+impl<K, V, BH> ZeroizingFrozenMap<K, V, BH>
+where
+    K: Zeroize,
+    V: Zeroize,
+{
+    /// Takes ownership of a map, wiping it when it's dropped.
+    #[must_use]
+    pub const fn new(map: FrozenMap<K, V, BH>) -> Self {
+        Self { map }
+    }
+}
+
+impl<K, V, BH> From<FrozenMap<K, V, BH>> for ZeroizingFrozenMap<K, V, BH>
+where
+    K: Zeroize,
+    V: Zeroize,
+{
+    fn from(map: FrozenMap<K, V, BH>) -> Self {
+        Self::new(map)
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for ZeroizingFrozenMap<K, V, RandomState>
+where
+    K: Hash + Eq + Zeroize,
+    V: Zeroize,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self::new(FrozenMap::from_iter(iter))
+    }
+}
+
+impl<K, V, BH> Deref for ZeroizingFrozenMap<K, V, BH>
+where
+    K: Zeroize,
+    V: Zeroize,
+{
+    type Target = FrozenMap<K, V, BH>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.map
+    }
+}
+
+impl<K, V, BH> Zeroize for ZeroizingFrozenMap<K, V, BH>
+where
+    K: Zeroize,
+    V: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.map.zeroize();
+    }
+}
+
+impl<K, V, BH> Drop for ZeroizingFrozenMap<K, V, BH>
+where
+    K: Zeroize,
+    V: Zeroize,
+{
+    fn drop(&mut self) {
+        self.map.zeroize();
+    }
+}
+
+impl<K, V, BH> ZeroizeOnDrop for ZeroizingFrozenMap<K, V, BH>
+where
+    K: Zeroize,
+    V: Zeroize,
+{
+}
@@ -0,0 +1,45 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use zeroize::Zeroize;
+
+use crate::facades::{FrozenMap, ZeroizingFrozenMap};
+
+static WIPED: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+struct Secret(String);
+
+impl Zeroize for Secret {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+        WIPED.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn lookups() {
+    let m: ZeroizingFrozenMap<_, _> = (0..300).map(|i| (format!("token-{i:03}"), i)).collect();
+    assert_eq!(300, m.len());
+    assert_eq!(Some(&42), m.get(&"token-042".to_string()));
+    assert_eq!(None, m.get(&"token-300".to_string()));
+
+    let mut m = ZeroizingFrozenMap::from(FrozenMap::from([(1u64, 10u64), (2, 20)]));
+    assert_eq!(Some(&20), m.get(&2));
+    m.zeroize();
+    assert!(m.is_empty());
+}
+
+#[test]
+fn wiped_on_drop() {
+    let m: ZeroizingFrozenMap<_, _> = (0..10)
+        .map(|i| (Secret(format!("key-{i}")), Secret(format!("value-{i}"))))
+        .collect();
+    assert_eq!(
+        Some(&Secret("value-3".to_string())),
+        m.get(&Secret("key-3".to_string()))
+    );
+
+    let before = WIPED.load(Ordering::Relaxed);
+    drop(m);
+    assert_eq!(before + 20, WIPED.load(Ordering::Relaxed));
+}
@@ -0,0 +1,94 @@
+use std::hash::Hash;
+use std::hash::RandomState;
+use std::ops::Deref;
+
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::facades::FrozenSet;
+
+/// A frozen set which wipes its values and hash table metadata when it's dropped.
+///
+/// This wraps a [`FrozenSet`], derefs to it for lookups, and zeroizes it on drop, in the same
+/// way as [`ZeroizingFrozenMap`](crate::facades::ZeroizingFrozenMap) does for maps.
+///
+/// This type is only available with the `zeroize` feature.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::ZeroizingFrozenSet;
+///
+/// let allowlist: ZeroizingFrozenSet<_> =
+///     ["token-1".to_string(), "token-2".to_string()].into_iter().collect();
+///
+/// assert!(allowlist.contains(&"token-1".to_string()));
+/// assert!(!allowlist.contains(&"token-3".to_string()));
+/// ```
+#[derive(Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct ZeroizingFrozenSet<T, BH = RandomState>
+where
+    T: Zeroize,
+{
+    set: FrozenSet<T, BH>,
+}
+
+impl<T, BH> ZeroizingFrozenSet<T, BH>
+where
+    T: Zeroize,
+{
+    /// Takes ownership of a set, wiping it when it's dropped.
+    #[must_use]
+    pub const fn new(set: FrozenSet<T, BH>) -> Self {
+        Self { set }
+    }
+}
+
+impl<T, BH> From<FrozenSet<T, BH>> for ZeroizingFrozenSet<T, BH>
+where
+    T: Zeroize,
+{
+    fn from(set: FrozenSet<T, BH>) -> Self {
+        Self::new(set)
+    }
+}
+
+impl<T> FromIterator<T> for ZeroizingFrozenSet<T, RandomState>
+where
+    T: Hash + Eq + Zeroize,
+{
+    fn from_iter<U: IntoIterator<Item = T>>(iter: U) -> Self {
+        Self::new(FrozenSet::from_iter(iter))
+    }
+}
+
+impl<T, BH> Deref for ZeroizingFrozenSet<T, BH>
+where
+    T: Zeroize,
+{
+    type Target = FrozenSet<T, BH>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.set
+    }
+}
+
+impl<T, BH> Zeroize for ZeroizingFrozenSet<T, BH>
+where
+    T: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.set.zeroize();
+    }
+}
+
+impl<T, BH> Drop for ZeroizingFrozenSet<T, BH>
+where
+    T: Zeroize,
+{
+    fn drop(&mut self) {
+        self.set.zeroize();
+    }
+}
+
+impl<T, BH> ZeroizeOnDrop for ZeroizingFrozenSet<T, BH> where T: Zeroize {}
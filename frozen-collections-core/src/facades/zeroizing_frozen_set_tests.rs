@@ -0,0 +1,41 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use zeroize::Zeroize;
+
+use crate::facades::{FrozenSet, ZeroizingFrozenSet};
+use crate::traits::len::Len;
+
+static WIPED: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+struct Secret(String);
+
+impl Zeroize for Secret {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+        WIPED.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn lookups() {
+    let s: ZeroizingFrozenSet<_> = (0..300).map(|i| format!("token-{i:03}")).collect();
+    assert_eq!(300, s.len());
+    assert!(s.contains(&"token-042".to_string()));
+    assert!(!s.contains(&"token-300".to_string()));
+
+    let mut s = ZeroizingFrozenSet::from(FrozenSet::from([1u64, 3, 5]));
+    assert!(s.contains(&3));
+    s.zeroize();
+    assert!(s.is_empty());
+}
+
+#[test]
+fn wiped_on_drop() {
+    let s: ZeroizingFrozenSet<_> = (0..10).map(|i| Secret(format!("token-{i}"))).collect();
+    assert!(s.contains(&Secret("token-3".to_string())));
+
+    let before = WIPED.load(Ordering::Relaxed);
+    drop(s);
+    assert_eq!(before + 10, WIPED.load(Ordering::Relaxed));
+}
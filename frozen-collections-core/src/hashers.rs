@@ -0,0 +1,360 @@
+//! Hashers for use with the frozen collections.
+//!
+//! Collections default to [`RandomState`](std::hash::RandomState), whose `SipHash` is resistant
+//! to collision attacks but dominates the cost of looking up keys with no exploitable pattern.
+//! Faster hash builders are available behind the `ahash`, `foldhash`, and `fxhash` features,
+//! for keys which don't come from untrusted sources, and [`FastHashBuilder`] names the fastest
+//! of those enabled.
+
+use std::hash::{BuildHasher, BuildHasherDefault, DefaultHasher, Hash, Hasher};
+
+/// Creates hashers keyed with a fixed seed, so collections built from the same payload get the
+/// same layout, and iterate in the same order, every time the program runs.
+///
+/// Keys are hashed with the same algorithm as [`RandomState`](std::hash::RandomState), keyed
+/// with the seed instead of random keys. The algorithm may change between Rust releases, so
+/// layouts are only reproducible across runs of the same build. As the hash codes are
+/// predictable, collections using this hash builder shouldn't hold keys from untrusted sources.
+///
+/// Layouts are also only reproducible when the analysis of the keys runs to completion. An
+/// analysis bounded with [`AnalysisOptions::max_duration`](crate::facades::AnalysisOptions::max_duration)
+/// stops at a point which depends on the speed and load of the machine, and may settle on a
+/// different layout each time.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::FrozenMap;
+/// use frozen_collections_core::hashers::SeededState;
+///
+/// let payload: Vec<_> = (0..100).map(|i| (format!("key{i}"), i)).collect();
+/// let a = FrozenMap::from_vec_with_hasher(payload.clone(), SeededState::new(42));
+/// let b = FrozenMap::from_vec_with_hasher(payload, SeededState::new(42));
+///
+/// assert!(a.keys().eq(b.keys()));
+/// ```
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct SeededState {
+    seed: u64,
+}
+
+impl SeededState {
+    /// Creates a hash builder keyed with the seed.
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    /// Returns the seed the hashers are keyed with.
+    #[must_use]
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl BuildHasher for SeededState {
+    type Hasher = DefaultHasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        let mut h = DefaultHasher::new();
+        h.write_u64(self.seed);
+        h
+    }
+}
+
+/// Creates `aHash` hashers, which use AES instructions where available.
+#[cfg(feature = "ahash")]
+pub type AHashBuilder = ahash::RandomState;
+
+/// Creates `foldhash` hashers, the fastest of the available hashers with good quality.
+#[cfg(feature = "foldhash")]
+pub type FoldHashBuilder = foldhash::fast::RandomState;
+
+/// Creates `FxHash` hashers, which are the fastest for small integer keys but are easily
+/// driven into collisions, and aren't seeded.
+#[cfg(feature = "fxhash")]
+pub type FxHashBuilder = BuildHasherDefault<fxhash::FxHasher>;
+
+/// The fastest hash builder enabled through features, picking `foldhash`, then `aHash`,
+/// then `FxHash`, and falling back to [`RandomState`](std::hash::RandomState).
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::FrozenMap;
+/// use frozen_collections_core::hashers::FastHashBuilder;
+///
+/// let map = FrozenMap::from_vec_with_hasher(vec![("a", 1), ("b", 2)], FastHashBuilder::default());
+/// assert_eq!(map.get("b"), Some(&2));
+/// ```
+#[cfg(feature = "foldhash")]
+pub type FastHashBuilder = FoldHashBuilder;
+
+/// The fastest hash builder enabled through features, picking `foldhash`, then `aHash`,
+/// then `FxHash`, and falling back to [`RandomState`](std::hash::RandomState).
+#[cfg(all(feature = "ahash", not(feature = "foldhash")))]
+pub type FastHashBuilder = AHashBuilder;
+
+/// The fastest hash builder enabled through features, picking `foldhash`, then `aHash`,
+/// then `FxHash`, and falling back to [`RandomState`](std::hash::RandomState).
+#[cfg(all(feature = "fxhash", not(any(feature = "foldhash", feature = "ahash"))))]
+pub type FastHashBuilder = FxHashBuilder;
+
+/// The fastest hash builder enabled through features, picking `foldhash`, then `aHash`,
+/// then `FxHash`, and falling back to [`RandomState`](std::hash::RandomState).
+#[cfg(not(any(feature = "foldhash", feature = "ahash", feature = "fxhash")))]
+pub type FastHashBuilder = std::hash::RandomState;
+
+/// A hasher which passes the bits of keys through rather than hashing them.
+///
+/// Keys which are already high-quality hashes, such as content digests, gain nothing from
+/// being hashed again. Like the hasher of the `nohash-hasher` crate, this hasher uses the
+/// integer a key writes as its hash code. Keys which write several integers or runs of bytes,
+/// such as `[u8; 32]` digests, get a hash code folding together everything they write, in
+/// 64-bit words.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct NoHasher {
+    code: u64,
+}
+
+#[allow(clippy::cast_sign_loss)]
+#[allow(clippy::cast_possible_truncation)]
+impl Hasher for NoHasher {
+    fn finish(&self) -> u64 {
+        self.code
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut buf = [0; 8];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            self.code ^= u64::from_le_bytes(buf);
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.code ^= u64::from(i);
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.code ^= u64::from(i);
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.code ^= u64::from(i);
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.code ^= i;
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.code ^= i as u64 ^ (i >> 64) as u64;
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.code ^= i as u64;
+    }
+
+    fn write_i8(&mut self, i: i8) {
+        self.code ^= i as u64;
+    }
+
+    fn write_i16(&mut self, i: i16) {
+        self.code ^= i as u64;
+    }
+
+    fn write_i32(&mut self, i: i32) {
+        self.code ^= i as u64;
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.code ^= i as u64;
+    }
+
+    fn write_i128(&mut self, i: i128) {
+        self.write_u128(i as u128);
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.code ^= i as u64;
+    }
+}
+
+/// Creates [`NoHasher`] instances, for collections of keys which are already hashes.
+///
+/// This hash builder implements [`PassThroughHasher`], so frozen maps and sets built with it
+/// through their `from_vec_with_pass_through_hasher` constructors don't analyze their keys for
+/// subslices or lengths which tell them apart, since those would have to be hashed again. Keys
+/// which write a single integer use it directly, as they do with any hash builder, and other
+/// keys go straight into a hash table indexed by the bits they write.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::FrozenMap;
+/// use frozen_collections_core::hashers::NoHashBuilder;
+///
+/// let digests: Vec<[u8; 16]> = (0..10u128)
+///     .map(|i| (i * 0x9e37_79b9_7f4a_7c15).to_le_bytes())
+///     .collect();
+///
+/// let payload = digests.iter().copied().zip(0..).collect();
+/// let map = FrozenMap::from_vec_with_pass_through_hasher(payload, NoHashBuilder);
+///
+/// assert_eq!(map.get(&digests[3]), Some(&3));
+/// assert_eq!(map.get(&[0xFF; 16]), None);
+/// ```
+#[derive(Clone, Copy, Default, Debug)]
+pub struct NoHashBuilder;
+
+impl BuildHasher for NoHashBuilder {
+    type Hasher = NoHasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        NoHasher::default()
+    }
+}
+
+/// Marks hash builders whose hashers pass the bits of keys through rather than hashing them.
+///
+/// Frozen maps and sets created with such a hash builder through their
+/// `from_vec_with_pass_through_hasher` constructors skip looking for subslices or lengths
+/// which tell their keys apart, and hash the keys in full, since anything else would be
+/// slower than using the bits the keys write directly.
+///
+/// The trait is implemented for [`NoHashBuilder`], and for the hash builders of the
+/// `nohash-hasher` crate when the `nohash-hasher` feature is enabled. Other hash builders opt
+/// in by implementing it.
+pub trait PassThroughHasher: BuildHasher {}
+
+impl PassThroughHasher for NoHashBuilder {}
+
+impl PassThroughHasher for BuildHasherDefault<NoHasher> {}
+
+#[cfg(feature = "nohash-hasher")]
+impl<T: nohash_hasher::IsEnabled> PassThroughHasher for nohash_hasher::BuildNoHashHasher<T> {}
+
+/// Feeds the items of a collection to a hasher regardless of their order, so collections which
+/// compare equal hash the same however their items are laid out.
+///
+/// Each item is hashed on its own with a fixed-key hasher, and the number of items is written
+/// along with the sum of those hashes.
+pub(crate) fn hash_unordered<I, H>(items: I, state: &mut H)
+where
+    I: IntoIterator,
+    I::Item: Hash,
+    H: Hasher,
+{
+    let mut count = 0usize;
+    let mut sum = 0u64;
+    for item in items {
+        let mut h = DefaultHasher::new();
+        item.hash(&mut h);
+        sum = sum.wrapping_add(h.finish());
+        count += 1;
+    }
+
+    state.write_usize(count);
+    state.write_u64(sum);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::facades::FrozenMap;
+
+    #[test]
+    fn passes_integers_through() {
+        assert_eq!(42, NoHashBuilder.hash_one(42u8));
+        assert_eq!(u64::MAX, NoHashBuilder.hash_one(-1i32));
+        assert_eq!(7, NoHashBuilder.hash_one(7u128));
+        assert_eq!(0x1234, NoHashBuilder.hash_one(0x1234u64));
+    }
+
+    #[test]
+    fn folds_bytes() {
+        let digest = [1u8, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0];
+
+        // the length prefix of the array is folded in along with its bytes
+        assert_eq!(16 ^ 1 ^ 2, NoHashBuilder.hash_one(digest));
+        assert_ne!(NoHashBuilder.hash_one("ab"), NoHashBuilder.hash_one("ba"));
+    }
+
+    #[test]
+    fn fast_hash_builders() {
+        fn check<BH: BuildHasher + Default>() {
+            let keys: Vec<String> = (0..300).map(|i| format!("key{i}")).collect();
+            let map = FrozenMap::from_vec_with_hasher(
+                keys.iter().cloned().zip(0..).collect(),
+                BH::default(),
+            );
+
+            for (i, key) in keys.iter().enumerate() {
+                assert_eq!(Some(&i), map.get(key));
+            }
+            assert_eq!(None, map.get("key300"));
+        }
+
+        check::<FastHashBuilder>();
+
+        #[cfg(feature = "ahash")]
+        check::<AHashBuilder>();
+
+        #[cfg(feature = "foldhash")]
+        check::<FoldHashBuilder>();
+
+        #[cfg(feature = "fxhash")]
+        check::<FxHashBuilder>();
+    }
+
+    #[test]
+    fn seeded_state() {
+        assert_eq!(
+            SeededState::new(1).hash_one("key"),
+            SeededState::new(1).hash_one("key")
+        );
+        assert_ne!(
+            SeededState::new(1).hash_one("key"),
+            SeededState::new(2).hash_one("key")
+        );
+        assert_eq!(7, SeededState::new(7).seed());
+    }
+
+    #[test]
+    fn hashes_unordered() {
+        use crate::specialized_sets::{CommonSet, ScanningSet};
+
+        let hash = |values: &[u32]| {
+            let mut h = DefaultHasher::new();
+            hash_unordered(values, &mut h);
+            h.finish()
+        };
+
+        assert_eq!(hash(&[1, 2, 3]), hash(&[3, 1, 2]));
+        assert_ne!(hash(&[1, 2, 3]), hash(&[1, 2, 4]));
+        assert_ne!(hash(&[]), hash(&[0]));
+
+        // sets which compare equal hash the same, whatever their implementation
+        let scanning = ScanningSet::from_vec(vec![3, 1, 2]);
+        let common = CommonSet::from_vec(vec![1, 2, 3]);
+        assert!(scanning == common);
+        assert_eq!(
+            SeededState::new(0).hash_one(&scanning),
+            SeededState::new(0).hash_one(&common)
+        );
+    }
+
+    #[test]
+    fn pass_through_hashers() {
+        fn check<BH: PassThroughHasher + Default>() {
+            assert_eq!(42, BH::default().hash_one(42u64));
+        }
+
+        check::<NoHashBuilder>();
+        check::<BuildHasherDefault<NoHasher>>();
+
+        #[cfg(feature = "nohash-hasher")]
+        check::<nohash_hasher::BuildNoHashHasher<u64>>();
+    }
+}
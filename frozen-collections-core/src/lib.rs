@@ -1,5 +1,8 @@
 mod analyzers;
+pub mod codegen;
+pub mod errors;
 pub mod facades;
+pub mod hashers;
 #[doc(hidden)]
 pub mod macros;
 pub mod specialized_maps;
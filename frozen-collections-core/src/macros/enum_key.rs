@@ -0,0 +1,192 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{parse2, Data, DeriveInput, Error, Fields};
+
+#[doc(hidden)]
+#[must_use]
+pub fn enum_key_derive(input: TokenStream) -> TokenStream {
+    let input = match parse2::<DeriveInput>(input) {
+        Ok(syntax_tree) => syntax_tree,
+        Err(error) => return error.to_compile_error(),
+    };
+
+    let Data::Enum(data) = &input.data else {
+        return Error::new(Span::call_site(), "EnumKey can only be derived for enums")
+            .to_compile_error();
+    };
+
+    if data.variants.is_empty() {
+        return Error::new(
+            Span::call_site(),
+            "EnumKey cannot be derived for empty enums",
+        )
+        .to_compile_error();
+    }
+
+    if !input.generics.params.is_empty() {
+        return Error::new(
+            Span::call_site(),
+            "EnumKey cannot be derived for generic enums",
+        )
+        .to_compile_error();
+    }
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let map_name = format_ident!("__{}EnumKeyMap", name);
+
+    let mut field_decls = Vec::new();
+    let mut locals = Vec::new();
+    let mut inits = Vec::new();
+    let mut pushes = Vec::new();
+    let mut gets = Vec::new();
+    let mut get_muts = Vec::new();
+    let mut lens = Vec::new();
+
+    for (i, variant) in data.variants.iter().enumerate() {
+        let field = format_ident!("v{}", i);
+        let ident = &variant.ident;
+
+        match &variant.fields {
+            Fields::Unit => {
+                field_decls.push(quote!(#field: ::std::option::Option<V>));
+                locals.push(quote!(let mut #field = ::std::option::Option::None;));
+                inits.push(quote!(#field));
+                pushes.push(quote!(#name::#ident => #field = ::std::option::Option::Some(v),));
+                gets.push(quote!(#name::#ident => self.#field.as_ref(),));
+                get_muts.push(quote!(#name::#ident => self.#field.as_mut(),));
+                lens.push(quote!(usize::from(self.#field.is_some())));
+                continue;
+            }
+
+            Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => {
+                let ty = &unnamed.unnamed[0].ty;
+                field_decls.push(quote!(#field: ::frozen_collections::FrozenMap<#ty, V>));
+                pushes.push(quote!(#name::#ident(p) => #field.push((p, v)),));
+                gets.push(quote!(#name::#ident(p) => self.#field.get(p),));
+                get_muts.push(quote!(#name::#ident(p) => self.#field.get_mut(p),));
+            }
+
+            Fields::Named(named) if named.named.len() == 1 => {
+                let ty = &named.named[0].ty;
+                let f = &named.named[0].ident;
+                field_decls.push(quote!(#field: ::frozen_collections::FrozenMap<#ty, V>));
+                pushes.push(quote!(#name::#ident { #f: p } => #field.push((p, v)),));
+                gets.push(quote!(#name::#ident { #f: p } => self.#field.get(p),));
+                get_muts.push(quote!(#name::#ident { #f: p } => self.#field.get_mut(p),));
+            }
+
+            // variants with several fields are keyed by the whole enum
+            Fields::Unnamed(_) => {
+                field_decls.push(quote!(#field: ::frozen_collections::FrozenMap<#name, V>));
+                pushes.push(quote!(k @ #name::#ident(..) => #field.push((k, v)),));
+                gets.push(quote!(#name::#ident(..) => self.#field.get(key),));
+                get_muts.push(quote!(#name::#ident(..) => self.#field.get_mut(key),));
+            }
+
+            Fields::Named(_) => {
+                field_decls.push(quote!(#field: ::frozen_collections::FrozenMap<#name, V>));
+                pushes.push(quote!(k @ #name::#ident { .. } => #field.push((k, v)),));
+                gets.push(quote!(#name::#ident { .. } => self.#field.get(key),));
+                get_muts.push(quote!(#name::#ident { .. } => self.#field.get_mut(key),));
+            }
+        }
+
+        locals.push(quote!(let mut #field = ::std::vec::Vec::new();));
+        inits.push(quote!(#field: ::frozen_collections::FrozenMap::from_vec(#field)));
+        lens.push(quote!(self.#field.len()));
+    }
+
+    quote!(
+        #[doc(hidden)]
+        #vis struct #map_name<V> {
+            #(#field_decls,)*
+        }
+
+        impl<V> ::frozen_collections::traits::enum_key::EnumKeyMap<#name, V> for #map_name<V> {
+            #[allow(unused_variables)]
+            fn from_vec(payload: ::std::vec::Vec<(#name, V)>) -> Self {
+                #(#locals)*
+
+                for (k, v) in payload {
+                    match k {
+                        #(#pushes)*
+                    }
+                }
+
+                Self {
+                    #(#inits,)*
+                }
+            }
+
+            fn get(&self, key: &#name) -> ::std::option::Option<&V> {
+                match key {
+                    #(#gets)*
+                }
+            }
+
+            fn get_mut(&mut self, key: &#name) -> ::std::option::Option<&mut V> {
+                match key {
+                    #(#get_muts)*
+                }
+            }
+
+            fn len(&self) -> usize {
+                0 #(+ #lens)*
+            }
+        }
+
+        impl ::frozen_collections::traits::enum_key::EnumKey for #name {
+            type Map<V> = #map_name<V>;
+        }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use proc_macro2::TokenStream;
+
+    use crate::macros::enum_key::enum_key_derive;
+
+    #[test]
+    fn basic() {
+        let ts = TokenStream::from_str(
+            "
+            enum Command {
+                Get(u32),
+                Put { name: String },
+                Ping,
+                Move(i32, i32),
+            }
+        ",
+        )
+        .unwrap();
+
+        let ts2 = enum_key_derive(ts).to_string();
+        assert!(
+            ts2.contains("impl :: frozen_collections :: traits :: enum_key :: EnumKey for Command")
+        );
+        assert!(ts2.contains("v0 : :: frozen_collections :: FrozenMap < u32 , V >"));
+        assert!(ts2.contains("v1 : :: frozen_collections :: FrozenMap < String , V >"));
+        assert!(ts2.contains("v2 : :: std :: option :: Option < V >"));
+        assert!(ts2.contains("v3 : :: frozen_collections :: FrozenMap < Command , V >"));
+    }
+
+    #[test]
+    fn structs_are_rejected() {
+        let ts = TokenStream::from_str("struct Pair(u32, String);").unwrap();
+
+        let ts2 = enum_key_derive(ts).to_string();
+        assert!(ts2.contains("compile_error"));
+    }
+
+    #[test]
+    fn generic_enums_are_rejected() {
+        let ts = TokenStream::from_str("enum Either<L, R> { Left(L), Right(R) }").unwrap();
+
+        let ts2 = enum_key_derive(ts).to_string();
+        assert!(ts2.contains("compile_error"));
+    }
+}
@@ -0,0 +1,105 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{parse2, Data, DeriveInput, Error, Fields};
+
+#[doc(hidden)]
+#[must_use]
+pub fn frozen_key_derive(input: TokenStream) -> TokenStream {
+    let input = match parse2::<DeriveInput>(input) {
+        Ok(syntax_tree) => syntax_tree,
+        Err(error) => return error.to_compile_error(),
+    };
+
+    let Data::Enum(data) = &input.data else {
+        return Error::new(Span::call_site(), "FrozenKey can only be derived for enums")
+            .to_compile_error();
+    };
+
+    if let Some(variant) = data
+        .variants
+        .iter()
+        .find(|v| !matches!(v.fields, Fields::Unit))
+    {
+        return Error::new_spanned(
+            variant,
+            "FrozenKey can only be derived for enums whose variants have no fields",
+        )
+        .to_compile_error();
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let count = data.variants.len();
+    let arms = data.variants.iter().enumerate().map(|(i, variant)| {
+        let ident = &variant.ident;
+        quote!(Self::#ident => #i,)
+    });
+
+    quote!(
+        impl #impl_generics ::frozen_collections::traits::frozen_key::FrozenKey for #name #ty_generics #where_clause {
+            const COUNT: usize = #count;
+
+            fn index(&self) -> usize {
+                match *self {
+                    #(#arms)*
+                }
+            }
+        }
+
+        impl #impl_generics ::std::hash::Hash for #name #ty_generics #where_clause {
+            fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+                state.write_usize(::frozen_collections::traits::frozen_key::FrozenKey::index(self));
+            }
+        }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use proc_macro2::TokenStream;
+
+    use crate::macros::frozen_key::frozen_key_derive;
+
+    #[test]
+    fn basic() {
+        let ts = TokenStream::from_str(
+            "
+            enum Status {
+                Ok = 200,
+                NotFound = 404,
+                Teapot = 418,
+            }
+        ",
+        )
+        .unwrap();
+
+        let ts2 = frozen_key_derive(ts).to_string();
+        assert!(ts2.contains(
+            "impl :: frozen_collections :: traits :: frozen_key :: FrozenKey for Status"
+        ));
+        assert!(ts2.contains("const COUNT : usize = 3usize"));
+        assert!(ts2.contains(
+            "Self :: Ok => 0usize , Self :: NotFound => 1usize , Self :: Teapot => 2usize ,"
+        ));
+        assert!(ts2.contains("impl :: std :: hash :: Hash for Status"));
+    }
+
+    #[test]
+    fn variants_with_fields_are_rejected() {
+        let ts = TokenStream::from_str("enum Shape { Dot, Circle(f64) }").unwrap();
+
+        let ts2 = frozen_key_derive(ts).to_string();
+        assert!(ts2.contains("compile_error"));
+        assert!(ts2.contains("whose variants have no fields"));
+    }
+
+    #[test]
+    fn structs_are_rejected() {
+        let ts = TokenStream::from_str("struct Id(u32);").unwrap();
+
+        let ts2 = frozen_key_derive(ts).to_string();
+        assert!(ts2.contains("compile_error"));
+    }
+}
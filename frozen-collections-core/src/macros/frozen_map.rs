@@ -1,22 +1,34 @@
 use std::cmp::PartialEq;
 use std::fmt::Display;
-use std::hash::RandomState;
+use std::hash::Hash;
 use std::str::FromStr;
 
-use bitvec::macros::internal::funty::Fundamental;
 use num_traits::PrimInt;
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote, ToTokens};
 use syn::parse::{Parse, ParseStream};
-use syn::{parse2, Expr, LitInt, LitStr, Token, Type};
+use syn::{
+    parse2, parse_quote, Attribute, Expr, ExprLit, ExprUnary, Ident, Lit, LitInt, LitStr, Token,
+    Type, UnOp, Visibility,
+};
 
-use crate::analyzers::int_key_analyzer::{analyze_int_keys, IntKeyAnalysisResult};
+use crate::analyzers::int_key_analyzer::{
+    analyze_int_keys, IntKeyAnalysisResult, DEFAULT_MIN_KEY_DENSITY_PERCENT,
+    MAX_INTEGER_SCANNING_KEYS,
+};
 use crate::analyzers::slice_key_analyzer::{analyze_slice_keys, SliceKeyAnalysisResult};
+use crate::errors::FrozenError;
+use crate::hashers::SeededState;
+use crate::specialized_maps::static_map::{static_table, StaticKeyCode};
 
 struct Entry(Expr, Expr);
 
 struct Map {
-    ty: Type,
+    /// The subslice `&str` keys are hashed by, when pinned with a `#[hash]` attribute.
+    hash: Option<SliceKeyAnalysisResult>,
+
+    /// The key type, when given ahead of the entries.
+    ty: Option<Type>,
     entries: Vec<Entry>,
 }
 
@@ -31,24 +43,199 @@ impl ToTokens for Entry {
 
 impl Parse for Map {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let mut entries = Vec::<Entry>::new();
+        let hash = parse_hash_attr(input)?;
 
-        let ty = input.parse::<Type>()?;
+        // the key type is optional, and only present when followed by a comma
+        let fork = input.fork();
+        let ty = if fork.parse::<Type>().is_ok() && fork.peek(Token![,]) {
+            let ty = input.parse::<Type>()?;
+            input.parse::<Token![,]>()?;
+            Some(ty)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            hash,
+            ty,
+            entries: parse_entries(input)?,
+        })
+    }
+}
+
+fn parse_entries(input: ParseStream) -> syn::Result<Vec<Entry>> {
+    let mut entries = Vec::<Entry>::new();
+
+    while !input.is_empty() {
+        let key = input.parse::<Expr>()?;
+        input.parse::<Token![:]>()?;
+        let value = input.parse::<Expr>()?;
+
+        entries.push(Entry(key, value));
+
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Parses the optional `#[hash(left, a..b)]` or `#[hash(right, a..b)]` attribute ahead of the
+/// contents of a collection.
+pub fn parse_hash_attr(input: ParseStream) -> syn::Result<Option<SliceKeyAnalysisResult>> {
+    let mut attrs = input.call(Attribute::parse_outer)?;
+    let hash = take_hash_attr(&mut attrs)?;
+    if let Some(attr) = attrs.first() {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "unsupported attribute, expected `#[hash(left, a..b)]` or `#[hash(right, a..b)]`",
+        ));
+    }
+
+    Ok(hash)
+}
+
+/// Removes the `#[hash]` attribute from `attrs`, returning the subslice it pins.
+///
+/// The attribute names the side the subslice is measured from, and its range, as in
+/// `#[hash(right, 0..4)]` for the last four bytes of keys.
+fn take_hash_attr(attrs: &mut Vec<Attribute>) -> syn::Result<Option<SliceKeyAnalysisResult>> {
+    let Some(index) = attrs.iter().position(|attr| attr.path().is_ident("hash")) else {
+        return Ok(None);
+    };
+
+    let attr = attrs.remove(index);
+    if let Some(other) = attrs.iter().find(|attr| attr.path().is_ident("hash")) {
+        return Err(syn::Error::new_spanned(
+            other,
+            "duplicate `#[hash]` attribute",
+        ));
+    }
+
+    attr.parse_args_with(|input: ParseStream| {
+        let side = input.parse::<Ident>()?;
         input.parse::<Token![,]>()?;
+        let start = input.parse::<LitInt>()?.base10_parse::<usize>()?;
+        input.parse::<Token![..]>()?;
+        let end_lit = input.parse::<LitInt>()?;
+        let end = end_lit.base10_parse::<usize>()?;
+
+        if end <= start {
+            return Err(syn::Error::new_spanned(
+                end_lit,
+                "the range must not be empty",
+            ));
+        }
+
+        let (subslice_index, subslice_len) = (start, end - start);
+        match side.to_string().as_str() {
+            "left" => Ok(SliceKeyAnalysisResult::LeftHandSubslice {
+                subslice_index,
+                subslice_len,
+            }),
+            "right" => Ok(SliceKeyAnalysisResult::RightHandSubslice {
+                subslice_index,
+                subslice_len,
+            }),
+            _ => Err(syn::Error::new_spanned(side, "expected `left` or `right`")),
+        }
+    })
+    .map(Some)
+}
+
+/// The declaration of a `static` holding a lazily created collection, up to its type.
+pub struct StaticItem {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    name: Ident,
+}
+
+impl Parse for StaticItem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse::<Visibility>()?;
+        input.parse::<Token![static]>()?;
+        let name = input.parse::<Ident>()?;
+        input.parse::<Token![:]>()?;
 
-        while !input.is_empty() {
-            let key = input.parse::<Expr>()?;
-            input.parse::<Token![:]>()?;
-            let value = input.parse::<Expr>()?;
+        Ok(Self { attrs, vis, name })
+    }
+}
+
+impl StaticItem {
+    /// Removes the `#[hash]` attribute from those of the `static`, returning the subslice it
+    /// pins.
+    pub fn take_hash(&mut self) -> syn::Result<Option<SliceKeyAnalysisResult>> {
+        take_hash_attr(&mut self.attrs)
+    }
 
-            entries.push(Entry(key, value));
+    /// Declares the `static`, creating the collection on first use.
+    pub fn declare(self, collection: Collection) -> TokenStream {
+        let Self { attrs, vis, name } = self;
+        let Collection { ty, ctor } = collection;
 
-            if input.peek(Token![,]) {
-                input.parse::<Token![,]>()?;
+        quote!(
+            #(#attrs)*
+            #vis static #name: ::std::sync::LazyLock<#ty> = ::std::sync::LazyLock::new(|| #ctor);
+        )
+    }
+}
+
+/// A map declared as a `static`, as in `pub static NAME: &str => u32, "a": 1, "b": 2`.
+struct StaticMap {
+    item: StaticItem,
+    value_ty: Type,
+    map: Map,
+}
+
+impl Parse for StaticMap {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut item = input.parse::<StaticItem>()?;
+        let hash = item.take_hash()?;
+        let ty = input.parse::<Type>()?;
+        input.parse::<Token![=>]>()?;
+        let value_ty = input.parse::<Type>()?;
+
+        let entries = if input.is_empty() {
+            Vec::new()
+        } else {
+            input.parse::<Token![,]>()?;
+            parse_entries(input)?
+        };
+
+        Ok(Self {
+            item,
+            value_ty,
+            map: Map {
+                hash,
+                ty: Some(ty),
+                entries,
+            },
+        })
+    }
+}
+
+/// The kinds of literals the key type can be inferred from.
+#[derive(PartialEq)]
+enum LiteralKind {
+    String,
+    Char,
+
+    /// An integer, along with its type suffix, if any.
+    Integer(String),
+}
+
+impl Display for LiteralKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::String => write!(f, "a string literal"),
+            Self::Char => write!(f, "a char literal"),
+            Self::Integer(suffix) if suffix.is_empty() => {
+                write!(f, "an unsuffixed integer literal")
             }
+            Self::Integer(suffix) => write!(f, "a `{suffix}` integer literal"),
         }
-
-        Ok(Self { ty, entries })
     }
 }
 
@@ -69,66 +256,153 @@ pub fn frozen_map_macro(args: TokenStream) -> TokenStream {
         Err(error) => return error.to_compile_error(),
     };
 
+    match map_collection(input, &parse_quote!(_)) {
+        Ok(collection) => collection.into_block(),
+        Err(error) => error.to_compile_error(),
+    }
+}
+
+#[doc(hidden)]
+#[must_use]
+pub fn static_frozen_map_macro(args: TokenStream) -> TokenStream {
+    let input = match parse2::<StaticMap>(args) {
+        Ok(syntax_tree) => syntax_tree,
+        Err(error) => return error.to_compile_error(),
+    };
+
+    match map_collection(input.map, &input.value_ty) {
+        Ok(collection) => input.item.declare(collection),
+        Err(error) => error.to_compile_error(),
+    }
+}
+
+/// The type of a collection created by a macro, along with the expression creating it.
+pub struct Collection {
+    pub ty: TokenStream,
+    pub ctor: TokenStream,
+}
+
+impl Collection {
+    /// Produces a block creating the collection, ascribed its type.
+    pub fn into_block(self) -> TokenStream {
+        let Self { ty, ctor } = self;
+        quote!({
+            let c: #ty = #ctor;
+            c
+        })
+    }
+}
+
+/// Picks the map best suited to the entries, holding values of the given type.
+fn map_collection(input: Map, value_ty: &Type) -> syn::Result<Collection> {
     let mut kv_pairs = input.entries;
+    let mut hash = input.hash;
+
+    // keys which aren't all literals can't be analyzed here, so the map analyzes them at runtime
+    let Some(kinds) = kv_pairs
+        .iter()
+        .map(|kv| literal_kind(&kv.0))
+        .collect::<Option<Vec<_>>>()
+    else {
+        return Ok(runtime_map(input.ty, value_ty, kv_pairs, hash.as_ref()));
+    };
+
+    let mut ty = input
+        .ty
+        .map_or_else(|| infer_key_type(&kv_pairs, kinds), Ok)?;
 
     if kv_pairs.len() < 3 {
-        return quote!({
-            let m = ::frozen_collections::specialized_maps::ScanningMap::from_vec(vec![
-            #(
-                (#kv_pairs),
-            )*
-            ]);
-            m
+        return Ok(Collection {
+            ty: quote!(::frozen_collections::specialized_maps::ScanningMap<#ty, #value_ty>),
+            ctor: quote!(
+                ::frozen_collections::specialized_maps::ScanningMap::from_vec(vec![
+                #(
+                    (#kv_pairs),
+                )*
+                ])
+            ),
         });
     }
 
-    let mut ty = input.ty;
-    let type_name = format!("{}", ty.to_token_stream());
+    let borrowed = is_borrowed_str(&ty);
+    let type_name = if borrowed {
+        "& str".to_string()
+    } else {
+        format!("{}", ty.to_token_stream())
+    };
 
     let mut variety = KeyVariety::Integer;
-    let mut int_analysis = IntKeyAnalysisResult::Normal;
     let mut slice_analysis = SliceKeyAnalysisResult::Normal;
 
-    // TODO: fix the unwrap usage
-    match type_name.as_str() {
-        "u8" => int_analysis = process_int_keys::<u8>(&kv_pairs).unwrap(),
-        "i8" => int_analysis = process_int_keys::<i8>(&kv_pairs).unwrap(),
-        "u16" => int_analysis = process_int_keys::<u16>(&kv_pairs).unwrap(),
-        "i16" => int_analysis = process_int_keys::<i16>(&kv_pairs).unwrap(),
-        "u32" => int_analysis = process_int_keys::<u32>(&kv_pairs).unwrap(),
-        "i32" => int_analysis = process_int_keys::<i32>(&kv_pairs).unwrap(),
-        "u64" => int_analysis = process_int_keys::<u64>(&kv_pairs).unwrap(),
-        "i64" => int_analysis = process_int_keys::<i64>(&kv_pairs).unwrap(),
-        "u128" => int_analysis = process_int_keys::<u128>(&kv_pairs).unwrap(),
-        "i128" => int_analysis = process_int_keys::<i128>(&kv_pairs).unwrap(),
+    let int_analysis = match type_name.as_str() {
+        "u8" => process_int_keys::<u8>(&kv_pairs),
+        "i8" => process_int_keys::<i8>(&kv_pairs),
+        "u16" => process_int_keys::<u16>(&kv_pairs),
+        "i16" => process_int_keys::<i16>(&kv_pairs),
+        "u32" => process_int_keys::<u32>(&kv_pairs),
+        "i32" => process_int_keys::<i32>(&kv_pairs),
+        "u64" => process_int_keys::<u64>(&kv_pairs),
+        "i64" => process_int_keys::<i64>(&kv_pairs),
+        "u128" => process_int_keys::<u128>(&kv_pairs),
+        "i128" => process_int_keys::<i128>(&kv_pairs),
 
         "& str" => {
             variety = KeyVariety::String;
-            slice_analysis =
-                process_string_keys(kv_pairs.iter().map(|x| x.0.to_token_stream())).unwrap();
-
-            let mut copy = Vec::with_capacity(kv_pairs.len());
-            for kv in kv_pairs {
-                let original = kv.0.to_token_stream();
-                let modified = quote!(String::from(#original));
-                copy.push(Entry(parse2::<Expr>(modified).unwrap(), kv.1));
+            match hash.take() {
+                Some(pinned) => {
+                    slice_analysis = pinned;
+                    Ok(IntKeyAnalysisResult::Normal)
+                }
+
+                None => process_string_keys(kv_pairs.iter().map(|x| x.0.to_token_stream())).map(
+                    |result| {
+                        slice_analysis = result;
+                        IntKeyAnalysisResult::Normal
+                    },
+                ),
             }
+        }
 
-            kv_pairs = copy;
-            ty = parse2::<Type>(quote!(String)).unwrap();
+        _ => {
+            variety = KeyVariety::Common;
+            Ok(IntKeyAnalysisResult::Normal)
         }
+    }?;
 
-        _ => variety = KeyVariety::Common,
+    if hash.is_some() {
+        return Err(syn::Error::new_spanned(
+            &ty,
+            "the `#[hash]` attribute only applies to `&str` keys",
+        ));
+    }
+
+    if variety == KeyVariety::String && !borrowed {
+        kv_pairs = string_keys(kv_pairs);
+        ty = parse_quote!(String);
+    }
+
+    if variety == KeyVariety::Integer
+        && int_analysis != IntKeyAnalysisResult::Range
+        && kv_pairs.len() <= MAX_INTEGER_SCANNING_KEYS
+    {
+        return Ok(Collection {
+            ty: quote!(::frozen_collections::specialized_maps::IntegerScanningMap<#ty, #value_ty>),
+            ctor: quote!(
+                ::frozen_collections::specialized_maps::IntegerScanningMap::from_vec(vec![
+                #(
+                    (#kv_pairs),
+                )*
+                ])
+            ),
+        });
     }
 
     let map_type = match variety {
-        KeyVariety::Integer => {
-            if int_analysis == IntKeyAnalysisResult::Range {
-                format_ident!("{}", "IntegerRangeMap")
-            } else {
-                format_ident!("{}", "IntegerMap")
-            }
-        }
+        KeyVariety::Integer => match int_analysis {
+            IntKeyAnalysisResult::Range => format_ident!("{}", "IntegerRangeMap"),
+            IntKeyAnalysisResult::Dense => format_ident!("{}", "DenseIntegerMap"),
+            IntKeyAnalysisResult::Normal => format_ident!("{}", "IntegerMap"),
+        },
 
         KeyVariety::String => match slice_analysis {
             SliceKeyAnalysisResult::Normal => format_ident!("{}", "CommonMap"),
@@ -143,58 +417,348 @@ pub fn frozen_map_macro(args: TokenStream) -> TokenStream {
                 subslice_index: _,
                 subslice_len: _,
             } => format_ident!("{}", "RightSliceMap"),
+
+            SliceKeyAnalysisResult::SplitSubslice { .. } => format_ident!("{}", "SplitSliceMap"),
+
+            SliceKeyAnalysisResult::LengthAndSubslice { .. } => {
+                format_ident!("{}", "LengthSliceMap")
+            }
         },
 
         KeyVariety::Common => format_ident!("{}", "CommonMap"),
     };
 
-    let payload_size = format_ident!(
-        "{}",
-        if kv_pairs.len() <= u8::MAX.as_usize() {
-            "u8"
-        } else if kv_pairs.len() <= u16::MAX.as_usize() {
-            "u16"
-        } else {
-            "usize"
-        }
-    );
-
-    match slice_analysis {
+    let collection = match slice_analysis {
         SliceKeyAnalysisResult::LeftHandSubslice {
             subslice_index,
             subslice_len,
-        } => quote!(
-        {
-            let m: ::frozen_collections::specialized_maps::#map_type<#ty, _, #payload_size, ::std::hash::RandomState> = ::frozen_collections::specialized_maps::#map_type::from_vec(vec![
+        }
+        | SliceKeyAnalysisResult::RightHandSubslice {
+            subslice_index,
+            subslice_len,
+        }
+        | SliceKeyAnalysisResult::LengthAndSubslice {
+            subslice_index,
+            subslice_len,
+        } => Collection {
+            ty: quote!(::frozen_collections::specialized_maps::#map_type<#ty, #value_ty, ::std::hash::RandomState>),
+            ctor: quote!(::frozen_collections::specialized_maps::#map_type::from_vec(vec![
             #(
                 (#kv_pairs),
             )*
-            ], #subslice_index..#subslice_index + #subslice_len);
-            m
-        }),
+            ], #subslice_index..#subslice_index + #subslice_len)),
+        },
 
-        SliceKeyAnalysisResult::RightHandSubslice {
-            subslice_index,
-            subslice_len,
-        } => quote!(
-        {
-            let m: ::frozen_collections::specialized_maps::#map_type<#ty, _, #payload_size, ::std::hash::RandomState> = ::frozen_collections::specialized_maps::#map_type::from_vec(vec![
+        SliceKeyAnalysisResult::SplitSubslice {
+            left_index,
+            left_len,
+            right_index,
+            right_len,
+        } => Collection {
+            ty: quote!(::frozen_collections::specialized_maps::#map_type<#ty, #value_ty, ::std::hash::RandomState>),
+            ctor: quote!(::frozen_collections::specialized_maps::#map_type::from_vec(vec![
             #(
                 (#kv_pairs),
             )*
-            ], #subslice_index..#subslice_index + #subslice_len);
-            m
-        }),
+            ], #left_index..#left_index + #left_len, #right_index..#right_index + #right_len)),
+        },
+
+        _ => Collection {
+            ty: quote!(::frozen_collections::specialized_maps::#map_type<#ty, #value_ty>),
+            ctor: quote!(::frozen_collections::specialized_maps::#map_type::from_vec(vec![
+            #(
+                (#kv_pairs),
+            )*
+            ])),
+        },
+    };
+
+    Ok(collection)
+}
+
+#[doc(hidden)]
+#[must_use]
+pub fn frozen_map_static_macro(args: TokenStream) -> TokenStream {
+    let input = match parse2::<Map>(args) {
+        Ok(syntax_tree) => syntax_tree,
+        Err(error) => return error.to_compile_error(),
+    };
+
+    match static_map(input) {
+        Ok(ts) => ts,
+        Err(error) => error.to_compile_error(),
+    }
+}
+
+/// Lays out the entries of a map in hash slots, producing a constant expression which creates
+/// a [`StaticMap`](crate::specialized_maps::StaticMap) holding them.
+fn static_map(input: Map) -> syn::Result<TokenStream> {
+    let kv_pairs = input.entries;
+
+    if input.hash.is_some() {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "the `#[hash]` attribute isn't supported by static maps",
+        ));
+    }
+
+    let mut kinds = Vec::with_capacity(kv_pairs.len());
+    for kv in &kv_pairs {
+        kinds.push(literal_kind(&kv.0).ok_or_else(|| {
+            syn::Error::new_spanned(
+                &kv.0,
+                "static maps are laid out at compile time, so their keys must be literals",
+            )
+        })?);
+    }
+
+    let ty = match input.ty {
+        Some(ty) => ty,
+        None => infer_key_type(&kv_pairs, kinds)?,
+    };
+
+    let type_name = if is_borrowed_str(&ty) {
+        "& str".to_string()
+    } else {
+        ty.to_token_stream().to_string()
+    };
+
+    let layout = match type_name.as_str() {
+        "u8" => static_layout(&kv_pairs, parse_int_keys::<u8>(&kv_pairs)?),
+        "i8" => static_layout(&kv_pairs, parse_int_keys::<i8>(&kv_pairs)?),
+        "u16" => static_layout(&kv_pairs, parse_int_keys::<u16>(&kv_pairs)?),
+        "i16" => static_layout(&kv_pairs, parse_int_keys::<i16>(&kv_pairs)?),
+        "u32" => static_layout(&kv_pairs, parse_int_keys::<u32>(&kv_pairs)?),
+        "i32" => static_layout(&kv_pairs, parse_int_keys::<i32>(&kv_pairs)?),
+        "u64" => static_layout(&kv_pairs, parse_int_keys::<u64>(&kv_pairs)?),
+        "i64" => static_layout(&kv_pairs, parse_int_keys::<i64>(&kv_pairs)?),
+        "u128" => static_layout(&kv_pairs, parse_int_keys::<u128>(&kv_pairs)?),
+        "i128" => static_layout(&kv_pairs, parse_int_keys::<i128>(&kv_pairs)?),
+
+        "& str" => {
+            let mut keys = Vec::with_capacity(kv_pairs.len());
+            for kv in &kv_pairs {
+                keys.push(parse2::<LitStr>(kv.0.to_token_stream())?.value());
+            }
+
+            static_layout(&kv_pairs, keys)
+        }
+
+        _ => Err(syn::Error::new_spanned(
+            &ty,
+            "static maps can only be laid out at compile time for integer and `&str` keys",
+        )),
+    }?;
+
+    let key_code = match layout.key_code {
+        StaticKeyCode::Integer => quote!(Integer),
+        StaticKeyCode::Length => quote!(Length),
+        StaticKeyCode::Hashed => quote!(Hashed),
+        StaticKeyCode::LeftSlice(range) => {
+            let (start, end) = (range.start, range.end);
+            quote!(LeftSlice(#start..#end))
+        }
+        StaticKeyCode::RightSlice(range) => {
+            let (start, end) = (range.start, range.end);
+            quote!(RightSlice(#start..#end))
+        }
+        StaticKeyCode::SplitSlice(left, right) => {
+            let (left_start, left_end) = (left.start, left.end);
+            let (right_start, right_end) = (right.start, right.end);
+            quote!(SplitSlice(#left_start..#left_end, #right_start..#right_end))
+        }
+        StaticKeyCode::LengthSlice(range) => {
+            let (start, end) = (range.start, range.end);
+            quote!(LengthSlice(#start..#end))
+        }
+    };
+
+    let slots = layout
+        .slots
+        .into_iter()
+        .map(|(start, end)| quote!((#start, #end)));
+    let entries = layout.order.into_iter().map(|index| &kv_pairs[index]);
+
+    Ok(quote!(
+        ::frozen_collections::specialized_maps::StaticMap::<#ty, _>::from_raw_parts(
+            ::frozen_collections::specialized_maps::StaticKeyCode::#key_code,
+            &[#(#slots),*],
+            &[
+            #(
+                (#entries),
+            )*
+            ],
+        )
+    ))
+}
+
+/// Where the entries of a static map go.
+struct StaticLayout {
+    key_code: StaticKeyCode,
+
+    /// The range of entries in each hash slot.
+    slots: Vec<(u32, u32)>,
+
+    /// The index in the macro input of each entry, in the order they are laid out.
+    order: Vec<usize>,
+}
+
+/// Distributes keys over hash slots the way a [`StaticMap`](crate::specialized_maps::StaticMap)
+/// looks them up.
+fn static_layout<K>(kv_pairs: &[Entry], keys: Vec<K>) -> syn::Result<StaticLayout>
+where
+    K: Hash + Eq,
+{
+    let payload: Vec<_> = keys.into_iter().zip(0..).collect();
+    let (key_code, table) = static_table(payload).map_err(|error| match error {
+        FrozenError::DuplicateKey { index } => {
+            syn::Error::new_spanned(&kv_pairs[index].0, "duplicate key")
+        }
+        error => syn::Error::new(Span::call_site(), error),
+    })?;
 
-        _ => quote!(
-        {
-            let m: ::frozen_collections::specialized_maps::#map_type<#ty, _, #payload_size> = ::frozen_collections::specialized_maps::#map_type::from_vec(vec![
+    // the table holds no more than u32::MAX entries, as static_table checks
+    #[allow(clippy::cast_possible_truncation)]
+    let slots = table
+        .slot_ranges()
+        .map(|range| (range.start as u32, range.end as u32))
+        .collect();
+
+    let order = table.entries.iter().map(|entry| entry.1).collect();
+    Ok(StaticLayout {
+        key_code,
+        slots,
+        order,
+    })
+}
+
+/// Creates a map which analyzes its keys at runtime, for keys which aren't all literals.
+///
+/// A subslice pinned with a `#[hash]` attribute is used as is, without analyzing the keys.
+fn runtime_map(
+    ty: Option<Type>,
+    value_ty: &Type,
+    kv_pairs: Vec<Entry>,
+    hash: Option<&SliceKeyAnalysisResult>,
+) -> Collection {
+    let (ty, kv_pairs): (Type, _) = match ty {
+        Some(ty) if ty.to_token_stream().to_string() == "& str" => {
+            (parse_quote!(String), string_keys(kv_pairs))
+        }
+        Some(ty) => (ty, kv_pairs),
+        None => (parse_quote!(_), kv_pairs),
+    };
+
+    let (strategy, start, len) = match hash {
+        Some(SliceKeyAnalysisResult::LeftHandSubslice {
+            subslice_index,
+            subslice_len,
+        }) => (quote!(LeftSlice), *subslice_index, *subslice_len),
+        Some(SliceKeyAnalysisResult::RightHandSubslice {
+            subslice_index,
+            subslice_len,
+        }) => (quote!(RightSlice), *subslice_index, *subslice_len),
+        _ => {
+            return Collection {
+                ty: quote!(::frozen_collections::FrozenMap<#ty, #value_ty>),
+                ctor: quote!(::frozen_collections::FrozenMap::from_vec(vec![
+                #(
+                    (#kv_pairs),
+                )*
+                ])),
+            };
+        }
+    };
+
+    let end = start + len;
+    Collection {
+        ty: quote!(::frozen_collections::FrozenMap<#ty, #value_ty>),
+        ctor: quote!(::frozen_collections::FrozenMap::try_from_vec_with_strategy(
+            vec![
             #(
                 (#kv_pairs),
             )*
-            ]);
-            m
-        }),
+            ],
+            ::frozen_collections::MapStrategy::#strategy { range: #start..#end },
+            ::std::hash::RandomState::new(),
+        ).expect("the keys must be distinct and write their bytes to a hasher")),
+    }
+}
+
+/// Returns `true` for a string slice with a spelled out lifetime, as in `&'static str`.
+///
+/// Keys of such a type are borrowed from the string literals they are written as, while `&str`
+/// keys are turned into `String` keys, which keeps the lifetime out of the collection's type.
+pub fn is_borrowed_str(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(r) => {
+            r.lifetime.is_some()
+                && r.mutability.is_none()
+                && r.elem.to_token_stream().to_string() == "str"
+        }
+        _ => false,
+    }
+}
+
+/// Turns `&str` keys into `String` keys.
+fn string_keys(kv_pairs: Vec<Entry>) -> Vec<Entry> {
+    kv_pairs
+        .into_iter()
+        .map(|kv| {
+            let key = kv.0;
+            Entry(parse_quote!(String::from(#key)), kv.1)
+        })
+        .collect()
+}
+
+/// Infers the key type from the kind of literal the first key is written as, requiring the
+/// other keys to be written as the same kind of literal.
+fn infer_key_type(kv_pairs: &[Entry], kinds: Vec<LiteralKind>) -> syn::Result<Type> {
+    let mut kinds = kinds.into_iter();
+    let Some(kind) = kinds.next() else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "the key type of an empty map can't be inferred, specify it as in `frozen_map!(u32,)`",
+        ));
+    };
+
+    for (kv, other) in kv_pairs[1..].iter().zip(kinds) {
+        if other != kind {
+            return Err(syn::Error::new_spanned(
+                &kv.0,
+                format!("mixed key literals: expected {kind} like the first key, found {other}"),
+            ));
+        }
+    }
+
+    Ok(match kind {
+        LiteralKind::String => parse_quote!(&str),
+        LiteralKind::Char => parse_quote!(char),
+        LiteralKind::Integer(suffix) if suffix.is_empty() => parse_quote!(i32),
+        LiteralKind::Integer(suffix) => parse2(suffix.parse()?)?,
+    })
+}
+
+/// Returns the kind of literal a key is written as, treating negated integers as integers.
+fn literal_kind(key: &Expr) -> Option<LiteralKind> {
+    match key {
+        Expr::Lit(ExprLit { lit, .. }) => match lit {
+            Lit::Str(_) => Some(LiteralKind::String),
+            Lit::Char(_) => Some(LiteralKind::Char),
+            Lit::Int(li) => Some(LiteralKind::Integer(li.suffix().to_string())),
+            _ => None,
+        },
+
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => match literal_kind(expr) {
+            Some(LiteralKind::Integer(suffix)) => Some(LiteralKind::Integer(suffix)),
+            _ => None,
+        },
+
+        _ => None,
     }
 }
 
@@ -203,15 +767,39 @@ where
     K: PrimInt + FromStr,
     K::Err: Display,
 {
-    let keys = kv_pairs.iter().map(|x| x.0.to_token_stream());
+    Ok(analyze_int_keys(
+        parse_int_keys::<K>(kv_pairs)?.into_iter(),
+        DEFAULT_MIN_KEY_DENSITY_PERCENT,
+    ))
+}
+
+/// Parses keys written as integer literals, which may be negated.
+fn parse_int_keys<K>(kv_pairs: &[Entry]) -> syn::Result<Vec<K>>
+where
+    K: FromStr,
+    K::Err: Display,
+{
     let mut parsed = Vec::new();
-    for key in keys {
-        let li = parse2::<LitInt>(key)?;
-        let v = li.base10_parse::<K>()?;
+    for kv in kv_pairs {
+        let v = match &kv.0 {
+            Expr::Unary(ExprUnary {
+                op: UnOp::Neg(_),
+                expr,
+                ..
+            }) => {
+                let li = parse2::<LitInt>(expr.to_token_stream())?;
+                format!("-{}", li.base10_digits())
+                    .parse::<K>()
+                    .map_err(|error| syn::Error::new_spanned(&kv.0, error))?
+            }
+
+            key => parse2::<LitInt>(key.to_token_stream())?.base10_parse::<K>()?,
+        };
+
         parsed.push(v);
     }
 
-    Ok(analyze_int_keys(parsed.into_iter()))
+    Ok(parsed)
 }
 
 fn process_string_keys<I>(keys: I) -> syn::Result<SliceKeyAnalysisResult>
@@ -224,7 +812,8 @@ where
         parsed.push(ls.value());
     }
 
-    let bh = RandomState::new();
+    // a fixed seed keeps expansions reproducible from one build to the next
+    let bh = SeededState::new(0);
     Ok(analyze_slice_keys(parsed.iter().map(String::as_bytes), &bh))
 }
 
@@ -234,7 +823,9 @@ mod tests {
 
     use proc_macro2::TokenStream;
 
-    use crate::macros::frozen_map::frozen_map_macro;
+    use crate::macros::frozen_map::{
+        frozen_map_macro, frozen_map_static_macro, static_frozen_map_macro,
+    };
 
     #[test]
     fn basic() {
@@ -254,4 +845,258 @@ mod tests {
 
         println!("{ts2}");
     }
+
+    #[test]
+    fn inferred_key_types() {
+        let expand = |s: &str| frozen_map_macro(TokenStream::from_str(s).unwrap()).to_string();
+
+        let ts2 = expand("\"a\": 1, \"bb\": 2, \"ccc\": 3, \"dddd\": 4");
+        assert!(ts2.contains("LengthMap < String , _ >"));
+        assert!(ts2.contains("String :: from (\"bb\")"));
+
+        let ts2 = expand("1: 'a', 2: 'b', 3: 'c', 4: 'd'");
+        assert!(ts2.contains("IntegerRangeMap < i32 , _ >"));
+
+        let ts2 = expand("-1i8: 'a', 2i8: 'b', 30i8: 'c'");
+        assert!(ts2.contains("IntegerScanningMap < i8 , _ >"));
+
+        let ts2 = expand("'a': 1, 'b': 2, 'c': 3");
+        assert!(ts2.contains("CommonMap < char , _ >"));
+
+        // an explicit type still takes precedence
+        let ts2 = expand("u64, 1: 'a', 2: 'b', 3: 'c'");
+        assert!(ts2.contains("IntegerRangeMap < u64 , _ >"));
+    }
+
+    #[test]
+    fn wide_keys() {
+        let expand = |s: &str| frozen_map_macro(TokenStream::from_str(s).unwrap()).to_string();
+
+        let ts2 = expand("u128, 1: 'a', 2: 'b', 3: 'c'");
+        assert!(ts2.contains("IntegerRangeMap < u128 , _ >"));
+
+        // the codes of keys past 64 bits don't follow one another
+        let ts2 = expand(
+            "u128, 18446744073709551615: 'a', 18446744073709551616: 'b', 18446744073709551617: 'c'",
+        );
+        assert!(ts2.contains("IntegerScanningMap < u128 , _ >"));
+    }
+
+    #[test]
+    fn non_literal_keys() {
+        let expand = |s: &str| frozen_map_macro(TokenStream::from_str(s).unwrap()).to_string();
+
+        let ts2 = expand("Color::Red: 1, Color::Green: 2, Color::Blue: 3, Color::Black: 4");
+        assert!(
+            ts2.contains("FrozenMap < _ , _ > = :: frozen_collections :: FrozenMap :: from_vec")
+        );
+        assert!(ts2.contains("(Color :: Green , 2)"));
+
+        // a single non-literal key is enough to fall back, even when the type is given
+        let ts2 = expand("u32, 1: 'a', 2: 'b', BASE + 3: 'c', 4: 'd'");
+        assert!(ts2.contains("FrozenMap < u32 , _ >"));
+
+        let ts2 = expand("&str, \"a\": 1, concat!(\"b\", \"c\"): 2, \"d\": 3");
+        assert!(ts2.contains("FrozenMap < String , _ >"));
+        assert!(ts2.contains("String :: from (concat ! (\"b\" , \"c\"))"));
+    }
+
+    #[test]
+    fn mixed_key_literals() {
+        let expand = |s: &str| frozen_map_macro(TokenStream::from_str(s).unwrap()).to_string();
+
+        let ts2 = expand("\"a\": 1, 2: 2, \"c\": 3");
+        assert!(ts2.contains("compile_error"));
+        assert!(ts2.contains(
+            "expected a string literal like the first key, found an unsuffixed integer literal"
+        ));
+
+        let ts2 = expand("1u8: 1, 2u16: 2, 3u8: 3");
+        assert!(ts2.contains(
+            "expected a `u8` integer literal like the first key, found a `u16` integer literal"
+        ));
+
+        let ts2 = expand("");
+        assert!(ts2.contains("key type of an empty map"));
+
+        let ts2 = expand("u8, 1: 1, 2: 2, 300: 3");
+        assert!(ts2.contains("compile_error"));
+
+        let ts2 = expand("&str, \"a\": 1, 2: 2, \"c\": 3");
+        assert!(ts2.contains("compile_error"));
+    }
+
+    #[test]
+    fn pinned_subslices() {
+        let expand = |s: &str| frozen_map_macro(TokenStream::from_str(s).unwrap()).to_string();
+
+        let ts2 = expand(
+            "#[hash(right, 0..4)] &str, \"alpha-0001\": 1, \"bravo-0002\": 2, \"charlie-0003\": 3",
+        );
+        assert!(ts2.contains("RightSliceMap < String , _ , :: std :: hash :: RandomState >"));
+        assert!(ts2.contains("0usize .. 0usize + 4usize"));
+
+        let ts2 = expand("#[hash(left, 1..3)] \"xab\": 1, \"ycd\": 2, \"zef\": 3");
+        assert!(ts2.contains("LeftSliceMap < String , _ , :: std :: hash :: RandomState >"));
+        assert!(ts2.contains("1usize .. 1usize + 2usize"));
+
+        // keys which aren't all literals are placed at runtime, still with the pinned subslice
+        let ts2 = expand("#[hash(right, 0..2)] &str, \"a\": 1, concat!(\"b\", \"c\"): 2, \"d\": 3");
+        assert!(ts2.contains("try_from_vec_with_strategy"));
+        assert!(ts2.contains("MapStrategy :: RightSlice { range : 0usize .. 2usize }"));
+
+        let ts2 = expand("#[hash(up, 0..2)] \"a\": 1, \"b\": 2, \"c\": 3");
+        assert!(ts2.contains("expected `left` or `right`"));
+
+        let ts2 = expand("#[hash(left, 2..2)] \"a\": 1, \"b\": 2, \"c\": 3");
+        assert!(ts2.contains("the range must not be empty"));
+
+        let ts2 = expand("#[hash(left, 0..2)] 1: 1, 2: 2, 3: 3");
+        assert!(ts2.contains("only applies to `&str` keys"));
+
+        let ts2 = expand("#[inline] \"a\": 1, \"b\": 2, \"c\": 3");
+        assert!(ts2.contains("unsupported attribute"));
+
+        let ts2 = frozen_map_static_macro(
+            TokenStream::from_str("#[hash(left, 0..1)] \"a\": 1, \"b\": 2, \"c\": 3").unwrap(),
+        )
+        .to_string();
+        assert!(ts2.contains("isn't supported by static maps"));
+
+        let ts2 = static_frozen_map_macro(
+            TokenStream::from_str(
+                "#[hash(left, 0..1)] static CODES: &str => u32, \"a1\": 1, \"b2\": 2, \"c3\": 3",
+            )
+            .unwrap(),
+        )
+        .to_string();
+        assert!(ts2.starts_with("static CODES"));
+        assert!(ts2.contains("LeftSliceMap < String , u32 , :: std :: hash :: RandomState >"));
+    }
+
+    #[test]
+    fn split_subslices() {
+        let entries: Vec<_> = ["us", "eu", "ap", "ca"]
+            .iter()
+            .flat_map(|region| (0..50).map(move |i| format!("{region}-1/instances/{i:04}")))
+            .zip(0..)
+            .map(|(key, value)| format!("\"{key}\": {value}"))
+            .collect();
+        let entries = entries.join(", ");
+
+        let ts2 = frozen_map_macro(TokenStream::from_str(&entries).unwrap()).to_string();
+        assert!(ts2.contains("SplitSliceMap < String , _ , :: std :: hash :: RandomState >"));
+        assert!(ts2.contains("0usize .. 0usize + 1usize , 0usize .. 0usize + 2usize"));
+
+        let ts2 = frozen_map_static_macro(TokenStream::from_str(&entries).unwrap()).to_string();
+        assert!(ts2.contains("StaticKeyCode :: SplitSlice (0usize .. 1usize , 0usize .. 2usize)"));
+    }
+
+    #[test]
+    fn length_and_subslice() {
+        let entries: Vec<_> = ('a'..='t')
+            .flat_map(|c| (0..10).map(move |n| format!("{c}{}", "-".repeat(n + 5))))
+            .zip(0..)
+            .map(|(key, value)| format!("\"{key}\": {value}"))
+            .collect();
+        let entries = entries.join(", ");
+
+        let ts2 = frozen_map_macro(TokenStream::from_str(&entries).unwrap()).to_string();
+        assert!(ts2.contains("LengthSliceMap < String , _ , :: std :: hash :: RandomState >"));
+        assert!(ts2.contains("0usize .. 0usize + 1usize"));
+
+        let ts2 = frozen_map_static_macro(TokenStream::from_str(&entries).unwrap()).to_string();
+        assert!(ts2.contains("StaticKeyCode :: LengthSlice (0usize .. 1usize)"));
+    }
+
+    #[test]
+    fn static_maps() {
+        let expand =
+            |s: &str| frozen_map_static_macro(TokenStream::from_str(s).unwrap()).to_string();
+
+        let ts2 = expand("\"red\": 1, \"blue\": 2, \"green\": 3, \"purple\": 4");
+        assert!(ts2.contains("StaticMap :: < & str , _ > :: from_raw_parts"));
+        assert!(ts2.contains("StaticKeyCode :: Length"));
+        assert!(ts2
+            .contains("& [(\"blue\" , 2) , (\"green\" , 3) , (\"purple\" , 4) , (\"red\" , 1) ,]"));
+
+        let ts2 = expand("u16, 3: 'c', 1: 'a', 2: 'b'");
+        assert!(ts2.contains("StaticMap :: < u16 , _ >"));
+        assert!(ts2.contains("StaticKeyCode :: Integer"));
+    }
+
+    #[test]
+    fn borrowed_str_keys() {
+        let expand = |s: &str| frozen_map_macro(TokenStream::from_str(s).unwrap()).to_string();
+
+        let ts2 = expand("&'static str, \"a\": 1, \"bb\": 2, \"ccc\": 3, \"dddd\": 4");
+        assert!(ts2.contains("LengthMap < & 'static str , _ >"));
+        assert!(ts2.contains("(\"bb\" , 2)"));
+        assert!(!ts2.contains("String"));
+
+        let ts2 = expand("#[hash(right, 0..1)] &'a str, \"x1\": 1, \"y2\": 2, \"z3\": 3");
+        assert!(ts2.contains("RightSliceMap < & 'a str , _ , :: std :: hash :: RandomState >"));
+
+        let ts2 = expand("&'static str, \"a\": 1, concat!(\"b\", \"c\"): 2, \"d\": 3");
+        assert!(ts2.contains("FrozenMap < & 'static str , _ >"));
+        assert!(ts2.contains("(concat ! (\"b\" , \"c\") , 2)"));
+
+        let ts2 = frozen_map_static_macro(
+            TokenStream::from_str("&'static str, \"a\": 1, \"bb\": 2, \"ccc\": 3").unwrap(),
+        )
+        .to_string();
+        assert!(ts2.contains("StaticMap :: < & 'static str , _ >"));
+        assert!(ts2.contains("StaticKeyCode :: Length"));
+
+        let ts2 = static_frozen_map_macro(
+            TokenStream::from_str(
+                "static CODES: &'static str => u32, \"a\": 1, \"bb\": 2, \"ccc\": 3",
+            )
+            .unwrap(),
+        )
+        .to_string();
+        assert!(ts2.contains("LazyLock < :: frozen_collections :: specialized_maps :: LengthMap < & 'static str , u32 > >"));
+    }
+
+    #[test]
+    fn bad_static_maps() {
+        let expand =
+            |s: &str| frozen_map_static_macro(TokenStream::from_str(s).unwrap()).to_string();
+
+        let ts2 = expand("BASE: 1, 2: 2");
+        assert!(ts2.contains("their keys must be literals"));
+
+        let ts2 = expand("'a': 1, 'b': 2");
+        assert!(ts2.contains("only be laid out at compile time for integer and `&str` keys"));
+
+        let ts2 = expand("1: 1, 2: 2, 1: 3");
+        assert!(ts2.contains("duplicate key"));
+
+        let ts2 = expand("u8, 1: 1, 256: 2");
+        assert!(ts2.contains("compile_error"));
+    }
+
+    #[test]
+    fn lazy_static_maps() {
+        let expand =
+            |s: &str| static_frozen_map_macro(TokenStream::from_str(s).unwrap()).to_string();
+
+        let ts2 = expand(
+            "#[allow(dead_code)] pub static COLORS: &str => u32, \"red\": 1, \"blue\": 2, \"green\": 3",
+        );
+        assert!(ts2.starts_with("# [allow (dead_code)] pub static COLORS : :: std :: sync :: LazyLock < :: frozen_collections :: specialized_maps :: LengthMap < String , u32 > >"));
+        assert!(ts2.contains(":: std :: sync :: LazyLock :: new (|| :: frozen_collections :: specialized_maps :: LengthMap :: from_vec"));
+
+        let ts2 = expand("static NAMES: u8 => &'static str, 1: \"one\", 2: \"two\"");
+        assert!(ts2.contains("LazyLock < :: frozen_collections :: specialized_maps :: ScanningMap < u8 , & 'static str > >"));
+
+        let ts2 = expand("static EMPTY: u8 => u8");
+        assert!(ts2.contains("ScanningMap < u8 , u8 >"));
+
+        let ts2 = expand("static BAD: u8 => u8, 1: 1, 2: 2, 300: 3");
+        assert!(ts2.contains("compile_error"));
+
+        let ts2 = expand("let BAD: u8 => u8, 1: 1");
+        assert!(ts2.contains("compile_error"));
+    }
 }
@@ -1,232 +1,310 @@
 use std::cmp::PartialEq;
 use std::fmt::Display;
-use std::hash::RandomState;
 use std::str::FromStr;
 
-use bitvec::macros::internal::funty::Fundamental;
 use num_traits::PrimInt;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote, ToTokens};
 use syn::parse::{Parse, ParseStream};
-use syn::{parse2, Expr, LitInt, LitStr, Token, Type};
+use syn::{parse2, parse_quote, Expr, LitInt, LitStr, Token, Type};
 
-use crate::analyzers::int_key_analyzer::{analyze_int_keys, IntKeyAnalysisResult};
+use crate::analyzers::int_key_analyzer::{
+    analyze_int_keys, IntKeyAnalysisResult, MIN_BIT_SET_DENSITY_PERCENT,
+};
 use crate::analyzers::slice_key_analyzer::{analyze_slice_keys, SliceKeyAnalysisResult};
+use crate::hashers::SeededState;
+use crate::macros::frozen_map::{is_borrowed_str, parse_hash_attr, Collection, StaticItem};
 
-struct Entry(Expr, Expr);
+struct Set {
+    /// The subslice `&str` values are hashed by, when pinned with a `#[hash]` attribute.
+    hash: Option<SliceKeyAnalysisResult>,
 
-struct Map {
     ty: Type,
-    entries: Vec<Entry>,
+    values: Vec<Expr>,
 }
 
-impl ToTokens for Entry {
-    fn to_tokens(&self, tokens: &mut TokenStream) {
-        let key = self.0.clone();
-        let value = self.1.clone();
-
-        tokens.extend(quote!(#key, #value));
-    }
-}
-
-impl Parse for Map {
+impl Parse for Set {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let mut entries = Vec::<Entry>::new();
+        let mut values = Vec::<Expr>::new();
 
+        let hash = parse_hash_attr(input)?;
         let ty = input.parse::<Type>()?;
         input.parse::<Token![,]>()?;
 
         while !input.is_empty() {
-            let key = input.parse::<Expr>()?;
-            input.parse::<Token![:]>()?;
-            let value = input.parse::<Expr>()?;
-
-            entries.push(Entry(key, value));
+            values.push(input.parse::<Expr>()?);
 
             if input.peek(Token![,]) {
                 input.parse::<Token![,]>()?;
             }
         }
 
-        Ok(Self { ty, entries })
+        Ok(Self { hash, ty, values })
+    }
+}
+
+/// A set declared as a `static`, as in `pub static NAME: &str, "a", "b"`.
+struct StaticSet {
+    item: StaticItem,
+    set: Set,
+}
+
+impl Parse for StaticSet {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut item = input.parse::<StaticItem>()?;
+        let hash = item.take_hash()?;
+        let mut set = input.parse::<Set>()?;
+        set.hash = hash.or(set.hash);
+
+        Ok(Self { item, set })
     }
 }
 
 #[derive(PartialEq)]
-enum KeyVariety {
+enum ValueVariety {
     Common,
     Integer,
     String,
 }
 
-// TODO: make this work!
-
 #[doc(hidden)]
 #[must_use]
 #[allow(clippy::module_name_repetitions)]
 pub fn frozen_set_macro(args: TokenStream) -> TokenStream {
-    // proc_marco2 version of "parse_macro_input!(input as ParsedMap)"
-    let input = match parse2::<Map>(args) {
+    // proc_marco2 version of "parse_macro_input!(input as Set)"
+    let input = match parse2::<Set>(args) {
         Ok(syntax_tree) => syntax_tree,
         Err(error) => return error.to_compile_error(),
     };
 
-    let mut kv_pairs = input.entries;
+    match set_collection(input) {
+        Ok(collection) => collection.into_block(),
+        Err(error) => error.to_compile_error(),
+    }
+}
 
-    if kv_pairs.len() < 3 {
-        return quote!({
-            let m = ::frozen_collections::specialized_maps::ScanningMap::from_vec(vec![
-            #(
-                (#kv_pairs),
-            )*
-            ]);
-            m
-        });
+#[doc(hidden)]
+#[must_use]
+pub fn static_frozen_set_macro(args: TokenStream) -> TokenStream {
+    let input = match parse2::<StaticSet>(args) {
+        Ok(syntax_tree) => syntax_tree,
+        Err(error) => return error.to_compile_error(),
+    };
+
+    match set_collection(input.set) {
+        Ok(collection) => input.item.declare(collection),
+        Err(error) => error.to_compile_error(),
     }
+}
 
+/// Picks the set best suited to the values.
+fn set_collection(input: Set) -> syn::Result<Collection> {
+    let mut values = input.values;
     let mut ty = input.ty;
-    let type_name = format!("{}", ty.to_token_stream());
+    let mut hash = input.hash;
+
+    if values.len() < 3 {
+        return Ok(Collection {
+            ty: quote!(::frozen_collections::specialized_sets::ScanningSet<#ty>),
+            ctor: quote!(
+                ::frozen_collections::specialized_sets::ScanningSet::from_vec(vec![
+                #(
+                    #values,
+                )*
+                ])
+            ),
+        });
+    }
 
-    let mut variety = KeyVariety::Integer;
-    let mut int_analysis = IntKeyAnalysisResult::Normal;
+    let borrowed = is_borrowed_str(&ty);
+    let type_name = if borrowed {
+        "& str".to_string()
+    } else {
+        format!("{}", ty.to_token_stream())
+    };
+
+    let mut variety = ValueVariety::Integer;
     let mut slice_analysis = SliceKeyAnalysisResult::Normal;
 
-    // TODO: fix the unwrap usage
-    match type_name.as_str() {
-        "u8" => int_analysis = process_int_keys::<u8>(&kv_pairs).unwrap(),
-        "i8" => int_analysis = process_int_keys::<i8>(&kv_pairs).unwrap(),
-        "u16" => int_analysis = process_int_keys::<u16>(&kv_pairs).unwrap(),
-        "i16" => int_analysis = process_int_keys::<i16>(&kv_pairs).unwrap(),
-        "u32" => int_analysis = process_int_keys::<u32>(&kv_pairs).unwrap(),
-        "i32" => int_analysis = process_int_keys::<i32>(&kv_pairs).unwrap(),
-        "u64" => int_analysis = process_int_keys::<u64>(&kv_pairs).unwrap(),
-        "i64" => int_analysis = process_int_keys::<i64>(&kv_pairs).unwrap(),
-        "u128" => int_analysis = process_int_keys::<u128>(&kv_pairs).unwrap(),
-        "i128" => int_analysis = process_int_keys::<i128>(&kv_pairs).unwrap(),
+    let int_analysis = match type_name.as_str() {
+        "u8" => process_int_values::<u8>(&values),
+        "i8" => process_int_values::<i8>(&values),
+        "u16" => process_int_values::<u16>(&values),
+        "i16" => process_int_values::<i16>(&values),
+        "u32" => process_int_values::<u32>(&values),
+        "i32" => process_int_values::<i32>(&values),
+        "u64" => process_int_values::<u64>(&values),
+        "i64" => process_int_values::<i64>(&values),
+        "u128" => process_int_values::<u128>(&values),
+        "i128" => process_int_values::<i128>(&values),
 
         "& str" => {
-            variety = KeyVariety::String;
-            slice_analysis =
-                process_string_keys(kv_pairs.iter().map(|x| x.0.to_token_stream())).unwrap();
-
-            let mut copy = Vec::with_capacity(kv_pairs.len());
-            for kv in kv_pairs {
-                let original = kv.0.to_token_stream();
-                let modified = quote!(String::from(#original));
-                copy.push(Entry(parse2::<Expr>(modified).unwrap(), kv.1));
+            variety = ValueVariety::String;
+            match hash.take() {
+                Some(pinned) => {
+                    slice_analysis = pinned;
+                    Ok(IntKeyAnalysisResult::Normal)
+                }
+
+                None => process_string_values(&values).map(|result| {
+                    slice_analysis = result;
+                    IntKeyAnalysisResult::Normal
+                }),
             }
+        }
 
-            kv_pairs = copy;
-            ty = parse2::<Type>(quote!(String)).unwrap();
+        _ => {
+            variety = ValueVariety::Common;
+            Ok(IntKeyAnalysisResult::Normal)
         }
+    }?;
 
-        _ => variety = KeyVariety::Common,
+    if hash.is_some() {
+        return Err(syn::Error::new_spanned(
+            &ty,
+            "the `#[hash]` attribute only applies to `&str` values",
+        ));
     }
 
-    let map_type = match variety {
-        KeyVariety::Integer => {
+    if variety == ValueVariety::String && !borrowed {
+        values = values
+            .into_iter()
+            .map(|v| parse_quote!(::std::string::String::from(#v)))
+            .collect();
+        ty = parse_quote!(::std::string::String);
+    }
+
+    let set_type = match variety {
+        ValueVariety::Integer => {
             if int_analysis == IntKeyAnalysisResult::Range {
-                format_ident!("{}", "IntegerRangeMap")
-            } else {
-                format_ident!("{}", "IntegerMap")
+                return Ok(Collection {
+                    ty: quote!(::frozen_collections::specialized_sets::IntegerRangeSet<#ty>),
+                    ctor: quote!(
+                        ::frozen_collections::specialized_sets::IntegerRangeSet::from_vec(vec![
+                        #(
+                            #values,
+                        )*
+                        ])
+                    ),
+                });
+            }
+
+            // bit sets only check the codes of values, which no longer identify 128-bit values
+            if int_analysis == IntKeyAnalysisResult::Dense && !type_name.ends_with("128") {
+                return Ok(Collection {
+                    ty: quote!(::frozen_collections::specialized_sets::BitSet<#ty>),
+                    ctor: quote!(::frozen_collections::specialized_sets::BitSet::from_vec(
+                        vec![
+                        #(
+                            #values,
+                        )*
+                        ]
+                    )),
+                });
             }
+
+            format_ident!("{}", "IntegerSet")
         }
 
-        KeyVariety::String => match slice_analysis {
-            SliceKeyAnalysisResult::Normal => format_ident!("{}", "CommonMap"),
-            SliceKeyAnalysisResult::Length => format_ident!("{}", "LengthMap"),
+        ValueVariety::String => match slice_analysis {
+            SliceKeyAnalysisResult::Normal => format_ident!("{}", "CommonSet"),
+            SliceKeyAnalysisResult::Length => format_ident!("{}", "LengthSet"),
 
             SliceKeyAnalysisResult::LeftHandSubslice {
                 subslice_index: _,
                 subslice_len: _,
-            } => format_ident!("{}", "LeftSliceMap"),
+            } => format_ident!("{}", "LeftSliceSet"),
 
             SliceKeyAnalysisResult::RightHandSubslice {
                 subslice_index: _,
                 subslice_len: _,
-            } => format_ident!("{}", "RightSliceMap"),
+            } => format_ident!("{}", "RightSliceSet"),
+
+            SliceKeyAnalysisResult::SplitSubslice { .. } => format_ident!("{}", "SplitSliceSet"),
+
+            SliceKeyAnalysisResult::LengthAndSubslice { .. } => {
+                format_ident!("{}", "LengthSliceSet")
+            }
         },
 
-        KeyVariety::Common => format_ident!("{}", "CommonMap"),
+        ValueVariety::Common => format_ident!("{}", "CommonSet"),
     };
 
-    let payload_size = format_ident!(
-        "{}",
-        if kv_pairs.len() <= u8::MAX.as_usize() {
-            "u8"
-        } else if kv_pairs.len() <= u16::MAX.as_usize() {
-            "u16"
-        } else {
-            "usize"
-        }
-    );
-
-    match slice_analysis {
+    let collection = match slice_analysis {
         SliceKeyAnalysisResult::LeftHandSubslice {
             subslice_index,
             subslice_len,
-        } => quote!(
-        {
-            let m: ::frozen_collections::specialized_maps::#map_type<#ty, _, #payload_size, ::std::hash::RandomState> = ::frozen_collections::specialized_maps::#map_type::from_vec(vec![
+        }
+        | SliceKeyAnalysisResult::RightHandSubslice {
+            subslice_index,
+            subslice_len,
+        }
+        | SliceKeyAnalysisResult::LengthAndSubslice {
+            subslice_index,
+            subslice_len,
+        } => Collection {
+            ty: quote!(::frozen_collections::specialized_sets::#set_type<#ty, ::std::hash::RandomState>),
+            ctor: quote!(::frozen_collections::specialized_sets::#set_type::from_vec(vec![
             #(
-                (#kv_pairs),
+                #values,
             )*
-            ], #subslice_index..#subslice_index + #subslice_len);
-            m
-        }),
+            ], #subslice_index..#subslice_index + #subslice_len)),
+        },
 
-        SliceKeyAnalysisResult::RightHandSubslice {
-            subslice_index,
-            subslice_len,
-        } => quote!(
-        {
-            let m: ::frozen_collections::specialized_maps::#map_type<#ty, _, #payload_size, ::std::hash::RandomState> = ::frozen_collections::specialized_maps::#map_type::from_vec(vec![
+        SliceKeyAnalysisResult::SplitSubslice {
+            left_index,
+            left_len,
+            right_index,
+            right_len,
+        } => Collection {
+            ty: quote!(::frozen_collections::specialized_sets::#set_type<#ty, ::std::hash::RandomState>),
+            ctor: quote!(::frozen_collections::specialized_sets::#set_type::from_vec(vec![
             #(
-                (#kv_pairs),
+                #values,
             )*
-            ], #subslice_index..#subslice_index + #subslice_len);
-            m
-        }),
+            ], #left_index..#left_index + #left_len, #right_index..#right_index + #right_len)),
+        },
 
-        _ => quote!(
-        {
-            let m: ::frozen_collections::specialized_maps::#map_type<#ty, _, #payload_size> = ::frozen_collections::specialized_maps::#map_type::from_vec(vec![
+        _ => Collection {
+            ty: quote!(::frozen_collections::specialized_sets::#set_type<#ty>),
+            ctor: quote!(::frozen_collections::specialized_sets::#set_type::from_vec(vec![
             #(
-                (#kv_pairs),
+                #values,
             )*
-            ]);
-            m
-        }),
-    }
+            ])),
+        },
+    };
+
+    Ok(collection)
 }
 
-fn process_int_keys<K>(kv_pairs: &[Entry]) -> syn::Result<IntKeyAnalysisResult>
+fn process_int_values<T>(values: &[Expr]) -> syn::Result<IntKeyAnalysisResult>
 where
-    K: PrimInt + FromStr,
-    K::Err: Display,
+    T: PrimInt + FromStr,
+    T::Err: Display,
 {
-    let keys = kv_pairs.iter().map(|x| x.0.to_token_stream());
     let mut parsed = Vec::new();
-    for key in keys {
-        let li = parse2::<LitInt>(key)?;
-        let v = li.base10_parse::<K>()?;
+    for value in values {
+        let li = parse2::<LitInt>(value.to_token_stream())?;
+        let v = li.base10_parse::<T>()?;
         parsed.push(v);
     }
 
-    Ok(analyze_int_keys(parsed.into_iter()))
+    Ok(analyze_int_keys(
+        parsed.into_iter(),
+        MIN_BIT_SET_DENSITY_PERCENT,
+    ))
 }
 
-fn process_string_keys<I>(keys: I) -> syn::Result<SliceKeyAnalysisResult>
-where
-    I: Iterator<Item = TokenStream>,
-{
+fn process_string_values(values: &[Expr]) -> syn::Result<SliceKeyAnalysisResult> {
     let mut parsed = Vec::new();
-    for key in keys {
-        let ls = parse2::<LitStr>(key)?;
+    for value in values {
+        let ls = parse2::<LitStr>(value.to_token_stream())?;
         parsed.push(ls.value());
     }
 
-    let bh = RandomState::new();
+    // a fixed seed keeps expansions reproducible from one build to the next
+    let bh = SeededState::new(0);
     Ok(analyze_slice_keys(parsed.iter().map(String::as_bytes), &bh))
 }
 
@@ -236,24 +314,126 @@ mod tests {
 
     use proc_macro2::TokenStream;
 
-    use crate::macros::frozen_set::frozen_set_macro;
+    use crate::macros::frozen_set::{frozen_set_macro, static_frozen_set_macro};
+
+    #[test]
+    fn few_values_use_scanning() {
+        let ts = TokenStream::from_str("u32, 1, 2").unwrap();
+
+        let ts2 = frozen_set_macro(ts).to_string();
+        assert!(ts2.contains("ScanningSet :: from_vec"));
+    }
+
+    #[test]
+    fn integer_range() {
+        let ts = TokenStream::from_str("u32, 3, 4, 5, 6").unwrap();
+
+        let ts2 = frozen_set_macro(ts).to_string();
+        assert!(ts2.contains("IntegerRangeSet < u32 >"));
+    }
+
+    #[test]
+    fn integer() {
+        let ts = TokenStream::from_str("i64, 1, 10, 100, 1000").unwrap();
+
+        let ts2 = frozen_set_macro(ts).to_string();
+        assert!(ts2.contains("IntegerSet < i64 >"));
+    }
 
     #[test]
-    fn basic() {
+    fn strings() {
         let ts = TokenStream::from_str(
             "
             &str,
-            \"first_key\": (1, \"first_value\"),
-            \"second_key\": (2, \"second_value\"),
-            \"third_key\": (3, \"third_value\"),
-            \"fourth_key\": (4, \"fourth_value\"),
-            \"fifth_key\": (5, \"fifth_value\"),
+            \"first_value\",
+            \"second_value\",
+            \"third_value\",
+            \"fourth_value\",
+            \"fifth_value\",
         ",
         )
         .unwrap();
 
-        let ts2 = frozen_set_macro(ts);
+        let ts2 = frozen_set_macro(ts).to_string();
+        assert!(ts2.contains("specialized_sets :: LengthSet < :: std :: string :: String >"));
+        assert!(ts2.contains(":: std :: string :: String :: from (\"fifth_value\")"));
+
+        let ts = TokenStream::from_str("&'static str, \"a\", \"bb\", \"ccc\", \"dddd\"").unwrap();
+        let ts2 = frozen_set_macro(ts).to_string();
+        assert!(ts2.contains("specialized_sets :: LengthSet < & 'static str >"));
+        assert!(!ts2.contains("String"));
+    }
+
+    #[test]
+    fn pinned_subslices() {
+        let ts =
+            TokenStream::from_str("#[hash(right, 0..3)] &str, \"a-001\", \"bb-002\", \"c-003\"")
+                .unwrap();
+
+        let ts2 = frozen_set_macro(ts).to_string();
+        assert!(ts2.contains("RightSliceSet"));
+        assert!(ts2.contains("0usize .. 0usize + 3usize"));
+
+        let ts = TokenStream::from_str("#[hash(left, 0..1)] u32, 1, 2, 3").unwrap();
+        let ts2 = frozen_set_macro(ts).to_string();
+        assert!(ts2.contains("only applies to `&str` values"));
+
+        let ts =
+            TokenStream::from_str("#[hash(left, 0..1)] static CODES: &str, \"a\", \"b\", \"c\"")
+                .unwrap();
+        let ts2 = static_frozen_set_macro(ts).to_string();
+        assert!(ts2.starts_with("static CODES"));
+        assert!(ts2.contains("LeftSliceSet"));
+    }
+
+    #[test]
+    fn split_subslices() {
+        let values: Vec<_> = ["us", "eu", "ap", "ca"]
+            .iter()
+            .flat_map(|region| (0..50).map(move |i| format!("\"{region}-1/instances/{i:04}\"")))
+            .collect();
+        let ts = TokenStream::from_str(&format!("&str, {}", values.join(", "))).unwrap();
+
+        let ts2 = frozen_set_macro(ts).to_string();
+        assert!(ts2.contains(
+            "SplitSliceSet < :: std :: string :: String , :: std :: hash :: RandomState >"
+        ));
+    }
+
+    #[test]
+    fn length_and_subslice() {
+        let values: Vec<_> = ('a'..='t')
+            .flat_map(|c| (0..10).map(move |n| format!("\"{c}{}\"", "-".repeat(n + 5))))
+            .collect();
+        let ts = TokenStream::from_str(&format!("&str, {}", values.join(", "))).unwrap();
+
+        let ts2 = frozen_set_macro(ts).to_string();
+        assert!(ts2.contains(
+            "LengthSliceSet < :: std :: string :: String , :: std :: hash :: RandomState >"
+        ));
+    }
+
+    #[test]
+    fn common() {
+        let ts = TokenStream::from_str("char, 'a', 'b', 'c'").unwrap();
+
+        let ts2 = frozen_set_macro(ts).to_string();
+        assert!(ts2.contains("CommonSet < char >"));
+    }
 
-        println!("{ts2}");
+    #[test]
+    fn bad_values() {
+        let ts = TokenStream::from_str("u8, 1, 2, 300").unwrap();
+
+        let ts2 = frozen_set_macro(ts).to_string();
+        assert!(ts2.contains("compile_error"));
+    }
+
+    #[test]
+    fn lazy_static_sets() {
+        let ts = TokenStream::from_str("pub(crate) static PRIMES: u32, 2, 3, 5, 7, 11").unwrap();
+        let ts2 = static_frozen_set_macro(ts).to_string();
+        assert!(ts2.starts_with("pub (crate) static PRIMES : :: std :: sync :: LazyLock < :: frozen_collections :: specialized_sets :: BitSet < u32 > >"));
+        assert!(ts2.contains(":: std :: sync :: LazyLock :: new (|| :: frozen_collections :: specialized_sets :: BitSet :: from_vec"));
     }
 }
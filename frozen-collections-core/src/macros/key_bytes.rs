@@ -0,0 +1,118 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{parse2, Data, DeriveInput, Error, Fields, Index};
+
+#[doc(hidden)]
+#[must_use]
+pub fn key_bytes_derive(input: TokenStream) -> TokenStream {
+    let input = match parse2::<DeriveInput>(input) {
+        Ok(syntax_tree) => syntax_tree,
+        Err(error) => return error.to_compile_error(),
+    };
+
+    let fields = match &input.data {
+        Data::Struct(s) => match &s.fields {
+            Fields::Named(named) => named
+                .named
+                .iter()
+                .map(|f| f.ident.to_token_stream())
+                .collect(),
+            Fields::Unnamed(unnamed) => (0..unnamed.unnamed.len())
+                .map(|i| Index::from(i).to_token_stream())
+                .collect(),
+            Fields::Unit => Vec::new(),
+        },
+        _ => {
+            return Error::new(
+                Span::call_site(),
+                "KeyBytes can only be derived for structs",
+            )
+            .to_compile_error()
+        }
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let separators = fields.len().saturating_sub(1);
+
+    let writes = fields.iter().enumerate().map(|(i, f)| {
+        if i == 0 {
+            quote!(::frozen_collections::traits::key_bytes::KeyBytes::write_key_bytes(&self.#f, out);)
+        } else {
+            quote!(
+                out.push(0xFF);
+                ::frozen_collections::traits::key_bytes::KeyBytes::write_key_bytes(&self.#f, out);
+            )
+        }
+    });
+
+    quote!(
+        impl #impl_generics ::frozen_collections::traits::key_bytes::KeyBytes for #name #ty_generics #where_clause {
+            fn write_key_bytes(&self, out: &mut ::std::vec::Vec<u8>) {
+                #(#writes)*
+            }
+
+            fn key_bytes_len(&self) -> usize {
+                #separators #(+ ::frozen_collections::traits::key_bytes::KeyBytes::key_bytes_len(&self.#fields))*
+            }
+        }
+
+        impl #impl_generics ::frozen_collections::traits::slice_hash::SliceHash for #name #ty_generics #where_clause {
+            fn hash<BH: ::std::hash::BuildHasher>(&self, bh: &BH, range: ::std::ops::Range<usize>) -> u64 {
+                let bytes = ::frozen_collections::traits::key_bytes::KeyBytes::key_bytes(self);
+                ::frozen_collections::traits::slice_hash::SliceHash::hash(bytes.as_slice(), bh, range)
+            }
+        }
+
+        impl #impl_generics ::frozen_collections::traits::len::Len for #name #ty_generics #where_clause {
+            fn len(&self) -> usize {
+                ::frozen_collections::traits::key_bytes::KeyBytes::key_bytes_len(self)
+            }
+        }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use proc_macro2::TokenStream;
+
+    use crate::macros::key_bytes::key_bytes_derive;
+
+    #[test]
+    fn basic() {
+        let ts = TokenStream::from_str(
+            "
+            struct Route {
+                region: String,
+                service: String,
+                endpoint: String,
+            }
+        ",
+        )
+        .unwrap();
+
+        let ts2 = key_bytes_derive(ts).to_string();
+        assert!(
+            ts2.contains("impl :: frozen_collections :: traits :: key_bytes :: KeyBytes for Route")
+        );
+        assert!(ts2.contains("2usize +"));
+    }
+
+    #[test]
+    fn tuple_struct() {
+        let ts = TokenStream::from_str("struct Pair(u32, String);").unwrap();
+
+        let ts2 = key_bytes_derive(ts).to_string();
+        assert!(ts2.contains("self . 1"));
+    }
+
+    #[test]
+    fn enums_are_rejected() {
+        let ts = TokenStream::from_str("enum Color { Red, Green }").unwrap();
+
+        let ts2 = key_bytes_derive(ts).to_string();
+        assert!(ts2.contains("compile_error"));
+    }
+}
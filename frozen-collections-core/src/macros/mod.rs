@@ -1,8 +1,20 @@
 #[doc(hidden)]
-pub use frozen_map::frozen_map_macro;
+pub use enum_key::enum_key_derive;
 
 #[doc(hidden)]
-pub use frozen_set::frozen_set_macro;
+pub use frozen_key::frozen_key_derive;
 
+#[doc(hidden)]
+pub use frozen_map::{frozen_map_macro, frozen_map_static_macro, static_frozen_map_macro};
+
+#[doc(hidden)]
+pub use frozen_set::{frozen_set_macro, static_frozen_set_macro};
+
+#[doc(hidden)]
+pub use key_bytes::key_bytes_derive;
+
+mod enum_key;
+mod frozen_key;
 mod frozen_map;
 mod frozen_set;
+mod key_bytes;
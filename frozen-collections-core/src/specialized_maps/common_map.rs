@@ -1,30 +1,35 @@
-use std::borrow::Borrow;
 use std::fmt::{Debug, Formatter, Result};
 use std::hash::RandomState;
-use std::hash::{BuildHasher, Hash};
-use std::intrinsics::transmute;
-use std::mem::MaybeUninit;
-use std::ops::Range;
+use std::hash::{BuildHasher, Hash, Hasher};
 use std::ops::{Index, IndexMut};
 
-use num_traits::{PrimInt, Unsigned};
-
-use crate::analyzers::hash_code_analyzer::analyze_hash_codes;
-use crate::specialized_maps::hash_table::HashTable;
-use crate::specialized_maps::{Iter, Keys, Values};
+use crate::analyzers::hash_code_analyzer::{
+    analyze_hash_codes, analyze_hash_codes_with, HashCodeAnalysisOptions,
+};
+use crate::errors::FrozenError;
+use crate::hashers::hash_unordered;
+use crate::specialized_maps::hash_table::{disjoint_values_mut, HashTable};
+use crate::specialized_maps::{
+    IntoIter, IntoKeys, IntoValues, Iter, IterMut, Keys, MapStats, Values, ValuesMut,
+};
+use crate::traits::equivalent::Equivalent;
+use crate::traits::heap_size::{entries_heap_bytes, HeapSize};
 use crate::traits::len::Len;
+use crate::traits::map::Map;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
 
 /// A general purpose map.
 #[derive(Clone)]
-pub struct CommonMap<K, V, S = u8, BH = RandomState> {
-    pub(crate) table: HashTable<K, V, S>,
+pub struct CommonMap<K, V, BH = RandomState> {
+    pub(crate) table: HashTable<K, V>,
     bh: BH,
 }
 
-impl<K, V, S, BH> CommonMap<K, V, S, BH>
+impl<K, V, BH> CommonMap<K, V, BH>
 where
     K: Hash,
-    S: PrimInt + Unsigned,
     BH: BuildHasher,
 {
     #[must_use]
@@ -32,7 +37,24 @@ where
         let code_analysis = analyze_hash_codes(payload.iter().map(|entry| bh.hash_one(&entry.0)));
 
         Self {
-            table: HashTable::new(payload, code_analysis.num_hash_slots, |k| bh.hash_one(k)),
+            table: HashTable::from_analysis(payload, code_analysis, false, |k| bh.hash_one(k)),
+            bh,
+        }
+    }
+
+    /// Creates a map whose hash table is sized within the given limits.
+    pub(crate) fn from_vec_with_sizing(
+        payload: Vec<(K, V)>,
+        bh: BH,
+        sizing: &HashCodeAnalysisOptions,
+    ) -> Self {
+        let codes = payload.iter().map(|entry| bh.hash_one(&entry.0));
+        let code_analysis = analyze_hash_codes_with(codes, sizing);
+
+        Self {
+            table: HashTable::from_analysis(payload, code_analysis, sizing.prefilter, |k| {
+                bh.hash_one(k)
+            }),
             bh,
         }
     }
@@ -42,120 +64,150 @@ where
         Self::from_vec_with_hasher(Vec::from_iter(iter), bh)
     }
 
+    /// Creates a map, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys.
+    pub fn try_from_vec_with_hasher(
+        payload: Vec<(K, V)>,
+        bh: BH,
+    ) -> std::result::Result<Self, FrozenError>
+    where
+        K: Eq,
+    {
+        let code_analysis = analyze_hash_codes(payload.iter().map(|entry| bh.hash_one(&entry.0)));
+
+        Ok(Self {
+            table: HashTable::try_from_analysis(payload, code_analysis, |k| bh.hash_one(k))?,
+            bh,
+        })
+    }
+
     #[must_use]
     pub fn with_hasher<const N: usize>(payload: [(K, V); N], bh: BH) -> Self {
         Self::from_vec_with_hasher(Vec::from_iter(payload), bh)
     }
 }
 
-impl<K, V, S, BH> CommonMap<K, V, S, BH>
+impl<K, V, BH> CommonMap<K, V, BH>
 where
-    S: PrimInt + Unsigned,
     BH: BuildHasher,
 {
     #[inline]
     #[must_use]
-    fn get_hash_info<Q>(&self, key: &Q) -> Range<usize>
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
     where
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<K> + ?Sized,
     {
-        let hash_code = self.bh.hash_one(key.borrow());
-        self.table.get_hash_info(hash_code)
+        let entry = self.table.find(self.bh.hash_one(key), key)?;
+        Some(&entry.1)
     }
 
     #[inline]
     #[must_use]
-    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
     where
-        K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<K> + ?Sized,
     {
-        let range = self.get_hash_info(key);
-        let entries = unsafe { self.table.entries.get_unchecked(range) };
-        for entry in entries {
-            if key.eq(entry.0.borrow()) {
-                return Some(&entry.1);
-            }
-        }
-
-        None
+        let entry = self.table.find(self.bh.hash_one(key), key)?;
+        Some((&entry.0, &entry.1))
     }
 
     #[inline]
     #[must_use]
-    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
     where
-        K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<K> + ?Sized,
     {
-        let range = self.get_hash_info(key);
-        let entries = unsafe { self.table.entries.get_unchecked(range) };
-        for entry in entries {
-            if key.eq(entry.0.borrow()) {
-                return Some((&entry.0, &entry.1));
-            }
-        }
+        let entry = self.table.find_mut(self.bh.hash_one(key), key)?;
+        Some(&mut entry.1)
+    }
 
-        None
+    /// Returns a reference to the value corresponding to the key, given the key's hash code.
+    ///
+    /// The hash code must be the one the map's hasher produces for the key, as returned by
+    /// `self.hasher().hash_one(key)`, or the lookup fails. Maps built with clones of the same
+    /// hash builder produce the same codes, so a key can be hashed once and looked up in all
+    /// of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::hashers::SeededState;
+    /// use frozen_collections_core::specialized_maps::CommonMap;
+    /// use std::hash::BuildHasher;
+    ///
+    /// let bh = SeededState::new(42);
+    /// let names = CommonMap::from_vec_with_hasher(vec![("fr", "France")], bh);
+    /// let codes = CommonMap::from_vec_with_hasher(vec![("fr", 250)], bh);
+    ///
+    /// let hash = bh.hash_one("fr");
+    /// assert_eq!(names.get_with_hash(hash, "fr"), Some(&"France"));
+    /// assert_eq!(codes.get_with_hash(hash, "fr"), Some(&250));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn get_with_hash<Q>(&self, hash_code: u64, key: &Q) -> Option<&V>
+    where
+        Q: Equivalent<K> + ?Sized,
+    {
+        let entry = self.table.find(hash_code, key)?;
+        Some(&entry.1)
     }
 
+    /// Returns the key-value pair corresponding to the key, given the key's hash code.
+    ///
+    /// The hash code must be the one the map's hasher produces for the key, as described for
+    /// [`get_with_hash`](Self::get_with_hash).
     #[inline]
     #[must_use]
-    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    pub fn get_key_value_with_hash<Q>(&self, hash_code: u64, key: &Q) -> Option<(&K, &V)>
     where
-        K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Equivalent<K> + ?Sized,
     {
-        let range = self.get_hash_info(key);
-        let entries = unsafe { self.table.entries.get_unchecked_mut(range) };
-        for entry in entries {
-            if key.eq(entry.0.borrow()) {
-                return Some(&mut entry.1);
-            }
-        }
+        let entry = self.table.find(hash_code, key)?;
+        Some((&entry.0, &entry.1))
+    }
 
-        None
+    /// Returns references to the values corresponding to several keys at once.
+    ///
+    /// The hash codes of all the keys are computed first and their slots prefetched, so the
+    /// memory latency of the lookups overlaps.
+    #[inline]
+    #[must_use]
+    pub fn get_many<Q, const N: usize>(&self, keys: [&Q; N]) -> [Option<&V>; N]
+    where
+        Q: Hash + Equivalent<K> + ?Sized,
+    {
+        let hash_codes = keys.map(|key| self.bh.hash_one(key));
+        self.table
+            .find_many(hash_codes, keys)
+            .map(|entry| entry.map(|(_, v)| v))
     }
 
-    #[allow(mutable_transmutes)]
+    /// Returns mutable references to the values of several keys at once.
+    ///
+    /// Returns `None` if any key is missing, or if two keys match the same entry.
     pub fn get_many_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> Option<[&mut V; N]>
     where
-        K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<K> + ?Sized,
     {
-        // ensure key uniqueness (assumes "keys" is a relatively small array)
-        for i in 0..keys.len() {
-            for j in 0..i {
-                if keys[j].eq(keys[i]) {
-                    return None;
-                }
-            }
-        }
-
-        unsafe {
-            let mut result: MaybeUninit<[&mut V; N]> = MaybeUninit::uninit();
-            let p = result.as_mut_ptr();
-
-            for (i, key) in keys.iter().enumerate() {
-                *(*p).get_unchecked_mut(i) = transmute(self.get(key)?);
-            }
-
-            Some(result.assume_init())
-        }
+        let indices = keys.map(|key| self.table.find_index(self.bh.hash_one(key), key));
+        disjoint_values_mut(&mut self.table.entries, indices)
     }
 
     #[inline]
     #[must_use]
     pub fn contains_key<Q>(&self, key: &Q) -> bool
     where
-        K: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<K> + ?Sized,
     {
         self.get(key).is_some()
     }
 }
 
-impl<K, V, S, BH> CommonMap<K, V, S, BH> {
+impl<K, V, BH> CommonMap<K, V, BH> {
     #[must_use]
     pub const fn iter(&self) -> Iter<K, V> {
         Iter::new(&self.table.entries)
@@ -171,30 +223,156 @@ impl<K, V, S, BH> CommonMap<K, V, S, BH> {
         Values::new(&self.table.entries)
     }
 
+    #[must_use]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut::new(&mut self.table.entries)
+    }
+
+    #[must_use]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut::new(&mut self.table.entries)
+    }
+
+    #[must_use]
+    pub fn into_keys(self) -> IntoKeys<K, V> {
+        IntoKeys::new(self.table.entries)
+    }
+
+    #[must_use]
+    pub fn into_values(self) -> IntoValues<K, V> {
+        IntoValues::new(self.table.entries)
+    }
+
+    pub(crate) fn map_values<V2, F>(self, f: F) -> CommonMap<K, V2, BH>
+    where
+        F: FnMut(V) -> V2,
+    {
+        CommonMap {
+            table: self.table.map_values(f),
+            bh: self.bh,
+        }
+    }
+
     #[must_use]
     pub const fn hasher(&self) -> &BH {
         &self.bh
     }
 }
 
-impl<K, V, S> CommonMap<K, V, S, RandomState>
+impl<K, V> CommonMap<K, V, RandomState>
 where
     K: Hash,
-    S: PrimInt + Unsigned,
 {
     #[must_use]
     pub fn from_vec(payload: Vec<(K, V)>) -> Self {
         Self::from_vec_with_hasher(payload, RandomState::new())
     }
+
+    /// Creates a map, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys.
+    pub fn try_from_vec(payload: Vec<(K, V)>) -> std::result::Result<Self, FrozenError>
+    where
+        K: Eq,
+    {
+        Self::try_from_vec_with_hasher(payload, RandomState::new())
+    }
 }
 
-impl<K, V, S, BH> Len for CommonMap<K, V, S, BH> {
+impl<K, V, BH> CommonMap<K, V, BH> {
+    /// Describes the layout of the map.
+    #[must_use]
+    pub fn stats(&self) -> MapStats {
+        self.table.stats()
+    }
+}
+
+impl<K, V, BH> CommonMap<K, V, BH> {
+    /// Creates an empty map which will use the given hash builder, without allocating.
+    #[must_use]
+    pub fn empty_with_hasher(bh: BH) -> Self {
+        Self {
+            table: HashTable::empty(),
+            bh,
+        }
+    }
+}
+
+impl<K, V, BH> Default for CommonMap<K, V, BH>
+where
+    BH: Default,
+{
+    fn default() -> Self {
+        Self::empty_with_hasher(BH::default())
+    }
+}
+
+impl<K, V, BH> Len for CommonMap<K, V, BH> {
     fn len(&self) -> usize {
         self.table.len()
     }
 }
 
-impl<K, V, S, BH> Debug for CommonMap<K, V, S, BH>
+impl<K, V, BH> HeapSize for CommonMap<K, V, BH>
+where
+    K: HeapSize,
+    V: HeapSize,
+{
+    fn heap_bytes(&self) -> usize {
+        self.stats().heap_bytes() + entries_heap_bytes(self.iter())
+    }
+}
+
+impl<K, V, BH> Map<K, V> for CommonMap<K, V, BH>
+where
+    K: Hash + Eq,
+    BH: BuildHasher,
+{
+    type Iterator<'a>
+        = Iter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+        BH: 'a;
+
+    type KeyIterator<'a>
+        = Keys<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+        BH: 'a;
+
+    type ValueIterator<'a>
+        = Values<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+        BH: 'a;
+
+    fn iter(&self) -> Iter<'_, K, V> {
+        self.iter()
+    }
+
+    fn keys(&self) -> Keys<'_, K, V> {
+        self.keys()
+    }
+
+    fn values(&self) -> Values<'_, K, V> {
+        self.values()
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+}
+
+impl<K, V, BH> Debug for CommonMap<K, V, BH>
 where
     K: Debug,
     V: Debug,
@@ -204,11 +382,9 @@ where
     }
 }
 
-impl<Q, K, V, S, BH> Index<Q> for CommonMap<K, V, S, BH>
+impl<Q, K, V, BH> Index<Q> for CommonMap<K, V, BH>
 where
-    K: Borrow<Q>,
-    Q: Hash + Eq,
-    S: PrimInt + Unsigned,
+    Q: Hash + Equivalent<K>,
     BH: BuildHasher,
 {
     type Output = V;
@@ -218,11 +394,9 @@ where
     }
 }
 
-impl<Q, K, V, S, BH> IndexMut<Q> for CommonMap<K, V, S, BH>
+impl<Q, K, V, BH> IndexMut<Q> for CommonMap<K, V, BH>
 where
-    K: Borrow<Q>,
-    Q: Hash + Eq,
-    S: PrimInt + Unsigned,
+    Q: Hash + Equivalent<K>,
     BH: BuildHasher,
 {
     fn index_mut(&mut self, index: Q) -> &mut V {
@@ -230,7 +404,16 @@ where
     }
 }
 
-impl<'a, K, V, S, BH> IntoIterator for &'a CommonMap<K, V, S, BH> {
+impl<K, V, BH> IntoIterator for CommonMap<K, V, BH> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.table.entries)
+    }
+}
+
+impl<'a, K, V, BH> IntoIterator for &'a CommonMap<K, V, BH> {
     type Item = (&'a K, &'a V);
     type IntoIter = Iter<'a, K, V>;
 
@@ -239,11 +422,19 @@ impl<'a, K, V, S, BH> IntoIterator for &'a CommonMap<K, V, S, BH> {
     }
 }
 
-impl<K, V, S, BH> PartialEq<Self> for CommonMap<K, V, S, BH>
+impl<'a, K, V, BH> IntoIterator for &'a mut CommonMap<K, V, BH> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K, V, BH> PartialEq<Self> for CommonMap<K, V, BH>
 where
     K: Hash + Eq,
     V: PartialEq,
-    S: PrimInt + Unsigned,
     BH: BuildHasher,
 {
     fn eq(&self, other: &Self) -> bool {
@@ -256,35 +447,53 @@ where
     }
 }
 
-impl<K, V, S, BH> Eq for CommonMap<K, V, S, BH>
+impl<K, V, BH> Eq for CommonMap<K, V, BH>
 where
     K: Hash + Eq,
     V: Eq,
-    S: PrimInt + Unsigned,
     BH: BuildHasher,
 {
 }
 
-impl<K, V, S, const N: usize> From<[(K, V); N]> for CommonMap<K, V, S, RandomState>
+impl<K, V, BH> Hash for CommonMap<K, V, BH>
+where
+    K: Hash,
+    V: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self, state);
+    }
+}
+
+impl<K, V, const N: usize> From<[(K, V); N]> for CommonMap<K, V, RandomState>
 where
     K: Hash,
-    S: PrimInt + Unsigned,
 {
     fn from(payload: [(K, V); N]) -> Self {
         Self::from_vec(Vec::from_iter(payload))
     }
 }
 
-impl<K, V, S> FromIterator<(K, V)> for CommonMap<K, V, S, RandomState>
+impl<K, V> FromIterator<(K, V)> for CommonMap<K, V, RandomState>
 where
     K: Hash,
-    S: PrimInt + Unsigned,
 {
     fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
         Self::from_vec(Vec::from_iter(iter))
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl<K, V, BH> Zeroize for CommonMap<K, V, BH>
+where
+    K: Zeroize,
+    V: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.table.zeroize();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::hash::RandomState;
@@ -294,23 +503,77 @@ mod tests {
     #[test]
     fn test_from_iter_empty() {
         let pairs: Vec<(i32, i32)> = vec![];
-        let map: CommonMap<i32, i32, u32, RandomState> = pairs.into_iter().collect();
+        let map: CommonMap<i32, i32, RandomState> = pairs.into_iter().collect();
         assert!(map.is_empty());
     }
 
     #[test]
     fn test_from_iter_single() {
         let pairs = vec![(1, 2)];
-        let map: CommonMap<i32, i32, u32, RandomState> = pairs.into_iter().collect();
+        let map: CommonMap<i32, i32, RandomState> = pairs.into_iter().collect();
         assert_eq!(map.get(&1), Some(&2));
     }
 
     #[test]
     fn test_from_iter_multiple() {
         let pairs = vec![(1, 2), (3, 4), (5, 6)];
-        let map: CommonMap<i32, i32, u32, RandomState> = pairs.into_iter().collect();
+        let map: CommonMap<i32, i32, RandomState> = pairs.into_iter().collect();
         assert_eq!(map.get(&1), Some(&2));
         assert_eq!(map.get(&3), Some(&4));
         assert_eq!(map.get(&5), Some(&6));
     }
+
+    #[test]
+    fn test_empty() {
+        let map = CommonMap::<i32, i32, RandomState>::default();
+        assert!(map.is_empty());
+        assert_eq!(map.get(&0), None);
+        assert_eq!(map.iter().count(), 0);
+
+        let map = CommonMap::<String, i32, _>::empty_with_hasher(RandomState::new());
+        assert!(!map.contains_key(&String::new()));
+    }
+
+    #[test]
+    fn test_payloads_past_index_widths() {
+        for len in [255, 256, 65_535, 65_536] {
+            let payload: Vec<(u32, u32)> = (0..len).map(|i| (i, i * 2)).collect();
+            let map = CommonMap::<_, _>::try_from_vec(payload.clone()).unwrap();
+            assert_eq!(map.len(), len as usize);
+            assert!(payload.iter().all(|(k, v)| map.get(k) == Some(v)));
+            assert_eq!(map.get(&len), None);
+
+            let map = CommonMap::<_, _>::from_vec(payload);
+            assert_eq!(map.get(&(len - 1)), Some(&((len - 1) * 2)));
+        }
+    }
+
+    #[test]
+    fn test_get_many_mut_rejects_keys_matching_one_entry() {
+        // distinct queries which are equivalent to the same key
+        #[derive(PartialEq, Eq)]
+        struct Query(u32, u32);
+
+        impl Hash for Query {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.0.hash(state);
+            }
+        }
+
+        impl Equivalent<u32> for Query {
+            fn equivalent(&self, key: &u32) -> bool {
+                self.0 == *key
+            }
+        }
+
+        let mut map = CommonMap::<u32, u32>::from_vec(vec![(1, 10), (2, 20)]);
+        assert!(map.get_many_mut([&Query(1, 0), &Query(1, 1)]).is_none());
+        assert!(map.get_many_mut([&Query(1, 0), &Query(3, 0)]).is_none());
+
+        let [a, b] = map.get_many_mut([&Query(2, 0), &Query(1, 1)]).unwrap();
+        *a += 1;
+        *b += 1;
+        assert_eq!(Some(&21), map.get(&2));
+        assert_eq!(Some(&11), map.get(&1));
+    }
 }
@@ -0,0 +1,594 @@
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::{Hash, Hasher};
+use std::ops::{Index, IndexMut};
+
+use crate::analyzers::duplicate_key_analyzer::find_duplicate_keys;
+use crate::analyzers::int_key_analyzer::{find_dense_code_range, DEFAULT_MIN_KEY_DENSITY_PERCENT};
+use crate::errors::FrozenError;
+use crate::hashers::hash_unordered;
+use crate::specialized_maps::hash_table::{disjoint_values_mut, map_entries, scatter};
+use crate::specialized_maps::key_codes::int_code;
+use crate::specialized_maps::{
+    IntoIter, IntoKeys, IntoValues, Iter, IterMut, Keys, MapStats, Values, ValuesMut,
+};
+use crate::traits::equivalent::Equivalent;
+use crate::traits::heap_size::{entries_heap_bytes, HeapSize};
+use crate::traits::len::Len;
+use crate::traits::map::Map;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// A map whose keys are integers spread over a bounded range, with gaps.
+///
+/// Each integer of the range has a slot holding the index of its entry, so a lookup is a
+/// subtraction and two array reads, without hashing. The slots take four bytes for every
+/// integer of the range, so this is only worthwhile when the keys occupy a good share of it.
+///
+/// As with [`IntegerMap`](crate::specialized_maps::IntegerMap), any key whose [`Hash`]
+/// implementation writes a single integer is treated as that integer.
+#[derive(Clone)]
+pub struct DenseIntegerMap<K, V> {
+    base: u64,
+
+    /// For each integer of the range, one more than the index of its entry, or zero.
+    slots: Box<[u32]>,
+
+    pub(crate) entries: Box<[(K, V)]>,
+}
+
+impl<K, V> DenseIntegerMap<K, V>
+where
+    K: Hash + Eq,
+{
+    /// # Panics
+    ///
+    /// Panics if the keys occupy less than a quarter of the range they cover.
+    #[must_use]
+    pub fn from_vec(payload: Vec<(K, V)>) -> Self {
+        Self::from_vec_with_density(payload, DEFAULT_MIN_KEY_DENSITY_PERCENT)
+    }
+
+    /// Creates a map whose keys must occupy at least `min_density_percent` percent of the range
+    /// they cover.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the keys are too sparse.
+    #[must_use]
+    pub fn from_vec_with_density(payload: Vec<(K, V)>, min_density_percent: u8) -> Self {
+        Self::try_from_vec_with_density(payload, min_density_percent)
+            .expect("DenseIntegerMap requires that the map keys densely cover their range")
+    }
+
+    /// Creates a map, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys, or if the keys occupy less than a quarter of
+    /// the range they cover.
+    pub fn try_from_vec(payload: Vec<(K, V)>) -> std::result::Result<Self, FrozenError> {
+        Self::try_from_vec_with_density(payload, DEFAULT_MIN_KEY_DENSITY_PERCENT)
+    }
+
+    /// Creates a map whose keys must occupy at least `min_density_percent` percent of the range
+    /// they cover, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys or more entries than fit in a `u32`, or if the
+    /// keys are too sparse.
+    pub fn try_from_vec_with_density(
+        mut payload: Vec<(K, V)>,
+        min_density_percent: u8,
+    ) -> std::result::Result<Self, FrozenError> {
+        if payload.is_empty() {
+            return Ok(Self::empty());
+        }
+
+        if let Some(group) = find_duplicate_keys(&payload, int_code).first() {
+            return Err(FrozenError::DuplicateKey { index: group[1] });
+        }
+
+        let max = u32::MAX.try_into().unwrap_or(usize::MAX);
+        if payload.len() > max {
+            return Err(FrozenError::CapacityExceeded {
+                len: payload.len(),
+                max,
+            });
+        }
+
+        let mut codes: Vec<_> = payload.iter().map(|x| int_code(&x.0)).collect();
+        let (base, span) = find_dense_code_range(&mut codes, min_density_percent)
+            .ok_or(FrozenError::SparseRange)?;
+        let span = usize::try_from(span).map_err(|_| FrozenError::SparseRange)?;
+
+        // every offset from the base is within the span
+        #[allow(clippy::cast_possible_truncation)]
+        let mut positions: Vec<_> = payload
+            .iter()
+            .map(|x| int_code(&x.0).wrapping_sub(base) as usize)
+            .collect();
+
+        // number the occupied slots in key order, which is the order entries are stored in
+        let mut slots = vec![0; span].into_boxed_slice();
+        for &offset in &positions {
+            slots[offset] = 1;
+        }
+
+        let mut index = 0;
+        for slot in &mut slots {
+            index += *slot;
+            if *slot != 0 {
+                *slot = index;
+            }
+        }
+
+        for position in &mut positions {
+            *position = slots[*position] as usize - 1;
+        }
+
+        scatter(&mut payload, &mut positions);
+
+        Ok(Self {
+            base,
+            slots,
+            entries: payload.into_boxed_slice(),
+        })
+    }
+}
+
+impl<K, V> DenseIntegerMap<K, V> {
+    /// Returns the index of the entry holding the key.
+    #[inline]
+    fn find<Q>(&self, key: &Q) -> Option<usize>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let offset = usize::try_from(int_code(key).wrapping_sub(self.base)).ok()?;
+        let index = (*self.slots.get(offset)? as usize).checked_sub(1)?;
+        if key.equivalent(&self.entries[index].0) {
+            Some(index)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let index = self.find(key)?;
+        Some(&self.entries[index].1)
+    }
+
+    /// Returns a reference to the value corresponding to a key known to be in the map.
+    ///
+    /// The key's entry is found from the key's value alone, without comparing keys.
+    ///
+    /// # Safety
+    ///
+    /// Calling this method with a key which isn't in the map is undefined behavior.
+    #[inline]
+    #[must_use]
+    pub unsafe fn get_unchecked<Q>(&self, key: &Q) -> &V
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        // a present key's offset is within the slots, and its slot holds its position plus one
+        #[allow(clippy::cast_possible_truncation)]
+        let offset = int_code(key).wrapping_sub(self.base) as usize;
+        unsafe {
+            let index = *self.slots.get_unchecked(offset) as usize - 1;
+            &self.entries.get_unchecked(index).1
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let index = self.find(key)?;
+        Some(&mut self.entries[index].1)
+    }
+
+    /// Returns references to the values corresponding to several keys at once.
+    #[inline]
+    #[must_use]
+    pub fn get_many<Q, const N: usize>(&self, keys: [&Q; N]) -> [Option<&V>; N]
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        keys.map(|key| self.get(key))
+    }
+
+    /// Returns mutable references to the values of several keys at once.
+    ///
+    /// Returns `None` if any key is missing, or if two keys match the same entry.
+    pub fn get_many_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> Option<[&mut V; N]>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let indices = keys.map(|key| self.find(key));
+        disjoint_values_mut(&mut self.entries, indices)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let index = self.find(key)?;
+        Some((&self.entries[index].0, &self.entries[index].1))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.find(key).is_some()
+    }
+
+    #[must_use]
+    pub const fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(&self.entries)
+    }
+
+    #[must_use]
+    pub const fn keys(&self) -> Keys<'_, K, V> {
+        Keys::new(&self.entries)
+    }
+
+    #[must_use]
+    pub const fn values(&self) -> Values<'_, K, V> {
+        Values::new(&self.entries)
+    }
+
+    #[must_use]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut::new(&mut self.entries)
+    }
+
+    #[must_use]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut::new(&mut self.entries)
+    }
+
+    #[must_use]
+    pub fn into_keys(self) -> IntoKeys<K, V> {
+        IntoKeys::new(self.entries)
+    }
+
+    #[must_use]
+    pub fn into_values(self) -> IntoValues<K, V> {
+        IntoValues::new(self.entries)
+    }
+
+    pub(crate) fn map_values<V2, F>(self, f: F) -> DenseIntegerMap<K, V2>
+    where
+        F: FnMut(V) -> V2,
+    {
+        DenseIntegerMap {
+            base: self.base,
+            slots: self.slots,
+            entries: map_entries(self.entries, f),
+        }
+    }
+}
+
+impl<K, V> DenseIntegerMap<K, V> {
+    /// Describes the layout of the map.
+    #[must_use]
+    pub fn stats(&self) -> MapStats {
+        MapStats::direct::<K, V>(
+            self.entries.len(),
+            self.slots.len(),
+            size_of_val(&*self.slots),
+        )
+    }
+}
+
+impl<K, V> DenseIntegerMap<K, V> {
+    /// Creates an empty map without allocating.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            base: 0,
+            slots: Box::default(),
+            entries: Box::default(),
+        }
+    }
+}
+
+impl<K, V> Default for DenseIntegerMap<K, V> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<K, V> Len for DenseIntegerMap<K, V> {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl<K, V> HeapSize for DenseIntegerMap<K, V>
+where
+    K: HeapSize,
+    V: HeapSize,
+{
+    fn heap_bytes(&self) -> usize {
+        self.stats().heap_bytes() + entries_heap_bytes(self.iter())
+    }
+}
+
+impl<K, V> Map<K, V> for DenseIntegerMap<K, V>
+where
+    K: Hash + Eq,
+{
+    type Iterator<'a>
+        = Iter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    type KeyIterator<'a>
+        = Keys<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    type ValueIterator<'a>
+        = Values<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    fn iter(&self) -> Iter<'_, K, V> {
+        self.iter()
+    }
+
+    fn keys(&self) -> Keys<'_, K, V> {
+        self.keys()
+    }
+
+    fn values(&self) -> Values<'_, K, V> {
+        self.values()
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    unsafe fn get_unchecked(&self, key: &K) -> &V {
+        unsafe { self.get_unchecked(key) }
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+}
+
+impl<K, V> Debug for DenseIntegerMap<K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let pairs = self.entries.iter().map(|x| (&x.0, &x.1));
+        f.debug_map().entries(pairs).finish()
+    }
+}
+
+impl<Q, K, V> Index<Q> for DenseIntegerMap<K, V>
+where
+    Q: Hash + Equivalent<K>,
+{
+    type Output = V;
+
+    fn index(&self, index: Q) -> &Self::Output {
+        self.get(&index).unwrap()
+    }
+}
+
+impl<Q, K, V> IndexMut<Q> for DenseIntegerMap<K, V>
+where
+    Q: Hash + Equivalent<K>,
+{
+    fn index_mut(&mut self, index: Q) -> &mut V {
+        self.get_mut(&index).unwrap()
+    }
+}
+
+impl<K, V> IntoIterator for DenseIntegerMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.entries)
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a DenseIntegerMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut DenseIntegerMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K, V> PartialEq<Self> for DenseIntegerMap<K, V>
+where
+    K: Hash + Eq,
+    V: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+
+        self.iter()
+            .all(|(key, value)| other.get(key).is_some_and(|v| *value == *v))
+    }
+}
+
+impl<K, V> Eq for DenseIntegerMap<K, V>
+where
+    K: Hash + Eq,
+    V: Eq,
+{
+}
+
+impl<K, V> Hash for DenseIntegerMap<K, V>
+where
+    K: Hash,
+    V: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self, state);
+    }
+}
+
+impl<K, V, const N: usize> From<[(K, V); N]> for DenseIntegerMap<K, V>
+where
+    K: Hash + Eq,
+{
+    fn from(payload: [(K, V); N]) -> Self {
+        Self::from_vec(Vec::from_iter(payload))
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for DenseIntegerMap<K, V>
+where
+    K: Hash + Eq,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self::from_vec(Vec::from_iter(iter))
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<K, V> Zeroize for DenseIntegerMap<K, V>
+where
+    K: Zeroize,
+    V: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.entries.zeroize();
+        self.slots.zeroize();
+        self.base.zeroize();
+        *self = Self::empty();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::errors::FrozenError;
+    use crate::traits::len::Len;
+
+    use super::DenseIntegerMap;
+
+    #[test]
+    fn dense_map_test() {
+        const MIN: [i32; 4] = [-20, -7, 0, 100];
+
+        for min in MIN {
+            let v: Vec<_> = (0..10).map(|i| (min + i * 3, i)).collect();
+            let mut m = DenseIntegerMap::<i32, i32>::from_vec(v);
+
+            assert_eq!(10, m.len());
+            for i in 0..10 {
+                let key = min + i * 3;
+                assert_eq!(i, *m.get(&key).unwrap());
+                assert_eq!(i, *m.get_mut(&key).unwrap());
+                assert_eq!((&key, &i), m.get_key_value(&key).unwrap());
+
+                assert_eq!(None, m.get(&(key + 1)));
+                assert_eq!(None, m.get_mut(&(key + 2)));
+            }
+
+            assert_eq!(None, m.get(&(min - 3)));
+            assert_eq!(None, m.get(&(min + 30)));
+
+            if min == -7 {
+                assert_eq!(
+                    "{-7: 0, -4: 1, -1: 2, 2: 3, 5: 4, 8: 5, 11: 6, 14: 7, 17: 8, 20: 9}",
+                    format!("{m:?}")
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn newtype_keys() {
+        #[derive(Debug, Hash, PartialEq, Eq)]
+        struct Id(u64);
+
+        let m = DenseIntegerMap::from_vec((0..5).map(|i| (Id(u64::MAX - i * 2), i)).collect());
+        assert_eq!(5, m.len());
+        assert_eq!(Some(&0), m.get(&Id(u64::MAX)));
+        assert_eq!(Some(&4), m.get(&Id(u64::MAX - 8)));
+        assert_eq!(None, m.get(&Id(u64::MAX - 1)));
+        assert_eq!(None, m.get(&Id(0)));
+    }
+
+    #[test]
+    fn try_from_vec_reports_bad_keys() {
+        assert_eq!(
+            Some(FrozenError::SparseRange),
+            DenseIntegerMap::try_from_vec(vec![(1, 1), (2, 2), (299, 4)]).err()
+        );
+        assert_eq!(
+            Some(FrozenError::DuplicateKey { index: 2 }),
+            DenseIntegerMap::try_from_vec(vec![(1, 1), (3, 2), (1, 3)]).err()
+        );
+        assert_eq!(
+            3,
+            DenseIntegerMap::try_from_vec_with_density(vec![(1, 1), (2, 2), (299, 4)], 1)
+                .unwrap()
+                .len()
+        );
+    }
+
+    #[test]
+    fn empty_map_matches_no_key() {
+        for m in [
+            DenseIntegerMap::<i32, i32>::empty(),
+            DenseIntegerMap::default(),
+            DenseIntegerMap::from_vec(Vec::new()),
+        ] {
+            assert_eq!(0, m.len());
+            assert_eq!(None, m.get(&0));
+            assert_eq!(None, m.get(&1));
+        }
+    }
+
+    #[test]
+    fn get_many_mut_returns_distinct_values() {
+        let mut m = DenseIntegerMap::from_vec(vec![(1, 10), (2, 20), (4, 40)]);
+        let [a, b] = m.get_many_mut([&4, &1]).unwrap();
+        *a += 1;
+        *b += 1;
+        assert_eq!(Some(&41), m.get(&4));
+        assert_eq!(Some(&11), m.get(&1));
+        assert!(m.get_many_mut([&2, &2]).is_none());
+        assert!(m.get_many_mut([&2, &3]).is_none());
+    }
+}
@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::fmt::{Debug, Formatter, Result};
 use std::num::{NonZeroU64, NonZeroUsize};
 use std::ops::Range;
@@ -5,117 +6,803 @@ use std::ops::Range;
 use bitvec::macros::internal::funty::Fundamental;
 use num_traits::{PrimInt, Unsigned};
 
+use crate::analyzers::displacement_analyzer::displaced_slot_index;
+use crate::analyzers::duplicate_key_analyzer::find_duplicate_keys;
+use crate::analyzers::hash_code_analyzer::HashCodeAnalysisResult;
+#[cfg(feature = "rayon")]
+use crate::analyzers::MIN_PARALLEL_LEN;
+use crate::errors::FrozenError;
+use crate::specialized_maps::prefilter::Prefilter;
+use crate::specialized_maps::MapStats;
+use crate::traits::equivalent::Equivalent;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
 #[derive(Clone)]
-pub struct HashTable<K, V, S> {
+pub struct HashTable<K, V> {
     num_slots: NonZeroU64,
-    slots: Box<[HashTableSlot<S>]>,
+    slots: Slots,
     pub entries: Box<[(K, V)]>,
+
+    /// A tag derived from the hash code of each entry, checked before comparing keys so that
+    /// entries which merely share a slot are skipped cheaply.
+    tags: Box<[u32]>,
+
+    /// A control byte derived from the hash code of each entry, padded so a whole group can be
+    /// loaded from any entry, or nothing for tables too small to benefit from them.
+    ///
+    /// The control bytes of a slot are matched a group at a time, so most misses are settled
+    /// without reading any of the tags or entries.
+    controls: Box<[u8]>,
+
+    /// A filter over the hash codes of the entries, checked before the slots so that most
+    /// misses are settled with a single cache line read, or an empty filter which rules
+    /// nothing out.
+    prefilter: Prefilter,
+
+    /// Seeds which pick the slot of each hash code, for hash codes too skewed to be spread by
+    /// taking them modulo the number of slots, or nothing when the modulo is used.
+    displacements: Box<[u32]>,
 }
 
+/// The number of entries from which a table keeps control bytes.
+pub const MIN_CONTROL_BYTES_LEN: usize = 1024;
+
+/// The number of control bytes matched at once.
+const GROUP_WIDTH: usize = 16;
+
+/// The number of bytes in a cache line.
+const CACHE_LINE_BYTES: usize = 64;
+
+/// A cache line's worth of slot bounds.
+#[derive(Clone, Copy)]
+#[repr(align(64))]
+struct BoundLine<S, const N: usize>([S; N]);
+
+/// The bounds of the entries held by each slot, packed so both bounds of a slot are in the
+/// same cache line.
+///
+/// Slots hold contiguous runs of entries in slot order, so slot `i` holds the entries from
+/// its bound to the bound of slot `i + 1`. Each line holds `N` bounds which cover `N - 1`
+/// slots, the last bound of a line being repeated as the first bound of the next one. Storing
+/// one bound per slot rather than a pair halves the size of the slots, and since the lines
+/// are aligned, locating the entries of a slot never reads more than one cache line.
 #[derive(Clone)]
-struct HashTableSlot<S> {
-    min_index: S,
-    max_index: S,
+struct PackedBounds<S, const N: usize> {
+    lines: Box<[BoundLine<S, N>]>,
+    len: usize,
+}
+
+impl<S: PrimInt + Unsigned, const N: usize> PackedBounds<S, N> {
+    /// The number of slots covered by each line.
+    const SLOTS_PER_LINE: usize = N - 1;
+
+    /// Records the range of entries held by each slot, given the number of entries in each
+    /// slot.
+    fn new(counts: &[usize]) -> Self {
+        let num_hash_slots = counts.len();
+
+        // the bound of a slot is the number of entries in the slots before it
+        let mut bounds = Vec::with_capacity(num_hash_slots + 1);
+        let mut entry_index: usize = 0;
+        bounds.push(S::zero());
+        for &count in counts {
+            entry_index += count;
+            bounds.push(to_slot_bound(entry_index));
+        }
+
+        let lines: Vec<_> = (0..num_hash_slots.div_ceil(Self::SLOTS_PER_LINE))
+            .map(|line| {
+                // the last line is padded with the final bound, leaving the slots past the end
+                // empty
+                let first = line * Self::SLOTS_PER_LINE;
+                BoundLine(std::array::from_fn(|i| {
+                    bounds[(first + i).min(num_hash_slots)]
+                }))
+            })
+            .collect();
+
+        Self {
+            lines: lines.into_boxed_slice(),
+            len: num_hash_slots,
+        }
+    }
+
+    #[inline]
+    fn get(&self, index: usize) -> Option<Range<usize>> {
+        if index >= self.len {
+            return None;
+        }
+
+        let line = &self.lines.get(index / Self::SLOTS_PER_LINE)?.0;
+        let offset = index % Self::SLOTS_PER_LINE;
+        Some(from_slot_bound(line[offset])..from_slot_bound(line[offset + 1]))
+    }
+
+    fn size_in_bytes(&self) -> usize {
+        size_of_val(&*self.lines)
+    }
 }
 
-struct PrepItem<K, V> {
-    hash_slot_index: usize,
-    entry: (K, V), // TODO: Try to use a different approach so we don't copy around so much data
+impl<S, const N: usize> Default for PackedBounds<S, N> {
+    fn default() -> Self {
+        Self {
+            lines: Box::default(),
+            len: 0,
+        }
+    }
 }
 
-impl<K, V, S> HashTable<K, V, S>
-where
-    S: PrimInt + Unsigned,
-{
-    pub fn new<F>(payload: Vec<(K, V)>, num_hash_slots: usize, hash: F) -> Self
+/// The bounds of the entries held by each slot, stored in the narrowest integer type able to
+/// index every entry of the table.
+#[derive(Clone)]
+enum Slots {
+    Small(PackedBounds<u8, CACHE_LINE_BYTES>),
+    Medium(PackedBounds<u16, { CACHE_LINE_BYTES / size_of::<u16>() }>),
+    Large(PackedBounds<usize, { CACHE_LINE_BYTES / size_of::<usize>() }>),
+}
+
+impl Slots {
+    /// Records the range of entries held by each slot, given the number of entries in each
+    /// slot and in total.
+    fn new(counts: &[usize], len: usize) -> Self {
+        if len <= u8::MAX.as_usize() {
+            Self::Small(PackedBounds::new(counts))
+        } else if len <= u16::MAX.as_usize() {
+            Self::Medium(PackedBounds::new(counts))
+        } else {
+            Self::Large(PackedBounds::new(counts))
+        }
+    }
+
+    #[inline]
+    fn get(&self, index: usize) -> Option<Range<usize>> {
+        match self {
+            Self::Small(slots) => slots.get(index),
+            Self::Medium(slots) => slots.get(index),
+            Self::Large(slots) => slots.get(index),
+        }
+    }
+
+    const fn len(&self) -> usize {
+        match self {
+            Self::Small(slots) => slots.len,
+            Self::Medium(slots) => slots.len,
+            Self::Large(slots) => slots.len,
+        }
+    }
+
+    fn size_in_bytes(&self) -> usize {
+        match self {
+            Self::Small(slots) => slots.size_in_bytes(),
+            Self::Medium(slots) => slots.size_in_bytes(),
+            Self::Large(slots) => slots.size_in_bytes(),
+        }
+    }
+}
+
+impl<K, V> HashTable<K, V> {
+    /// Creates a table which distributes the payload over `num_hash_slots` slots.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_hash_slots` is zero while the payload isn't empty.
+    pub fn new<F>(mut payload: Vec<(K, V)>, num_hash_slots: usize, hash: F) -> Self
     where
         F: Fn(&K) -> u64,
     {
+        let hash_codes = hash_entries(&mut payload, hash);
+        Self::build(payload, hash_codes, num_hash_slots, None, false)
+    }
+
+    /// Creates a table laid out as recommended by an analysis of the hash codes of its keys,
+    /// which also holds a filter over the hash codes when `prefilter` is set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the analysis recommends no slots while the payload isn't empty.
+    pub fn from_analysis<F>(
+        mut payload: Vec<(K, V)>,
+        analysis: HashCodeAnalysisResult,
+        prefilter: bool,
+        hash: F,
+    ) -> Self
+    where
+        F: Fn(&K) -> u64,
+    {
+        let hash_codes = hash_entries(&mut payload, hash);
+        Self::from_hash_codes(payload, hash_codes, analysis, prefilter)
+    }
+
+    /// Creates a table like [`Self::from_analysis`], from the hash codes of the keys of the
+    /// payload, given in the same order as the payload, for callers which already computed
+    /// them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the analysis recommends no slots while the payload isn't empty, or if there
+    /// isn't one hash code per entry.
+    pub fn from_hash_codes(
+        payload: Vec<(K, V)>,
+        hash_codes: Vec<u64>,
+        analysis: HashCodeAnalysisResult,
+        prefilter: bool,
+    ) -> Self {
+        assert_eq!(
+            payload.len(),
+            hash_codes.len(),
+            "One hash code is needed per entry"
+        );
+        Self::build(
+            payload,
+            hash_codes,
+            analysis.num_hash_slots,
+            analysis.displacements,
+            prefilter,
+        )
+    }
+
+    fn build(
+        mut payload: Vec<(K, V)>,
+        hash_codes: Vec<u64>,
+        num_hash_slots: usize,
+        displacements: Option<Box<[u32]>>,
+        prefilter: bool,
+    ) -> Self {
         if payload.is_empty() {
-            return Self {
-                num_slots: NonZeroU64::try_from(1).unwrap(),
-                slots: Box::new([HashTableSlot {
-                    min_index: S::zero(),
-                    max_index: S::zero(),
-                }]),
-                entries: Box::new([]),
-            };
-        } else if payload.len() > S::max_value().to_usize().unwrap() {
-            panic!("Too many payload entries for the map size S")
+            return Self::empty();
         }
 
-        let mut prep_items = Vec::new();
-        for entry in payload {
-            let hash_code = hash(&entry.0);
-            let hash_slot_index = (hash_code % num_hash_slots as u64).as_usize();
+        let num_slots = NonZeroUsize::new(num_hash_slots)
+            .and_then(|n| NonZeroU64::try_from(n).ok())
+            .expect("A hash table needs at least one slot");
+
+        let len = payload.len();
+        let displacements = displacements.unwrap_or_default();
+        let prefilter = if prefilter {
+            Prefilter::new(&hash_codes)
+        } else {
+            Prefilter::default()
+        };
 
-            prep_items.push(PrepItem {
-                hash_slot_index,
-                entry,
-            });
+        // a first pass counts the entries of each slot, which gives the slot bounds
+        let mut positions = pick_slots(&hash_codes, num_slots, &displacements);
+        let mut cursors = vec![0; num_hash_slots];
+        for &slot in &positions {
+            cursors[slot] += 1;
         }
 
-        // sort items so hash collisions are contiguous
-        prep_items.sort_unstable_by(|x, y| x.hash_slot_index.cmp(&y.hash_slot_index));
+        let slots = Slots::new(&cursors, len);
 
-        let mut entry_index = 0;
-        let mut slots = Vec::with_capacity(num_hash_slots);
-        let mut entries = Vec::with_capacity(prep_items.len());
+        let mut next = 0;
+        for cursor in &mut cursors {
+            let count = *cursor;
+            *cursor = next;
+            next += count;
+        }
 
-        slots.resize_with(num_hash_slots, || HashTableSlot {
-            min_index: S::zero(),
-            max_index: S::zero(),
-        });
+        // a second pass places each entry after the entries of its slot which came before it
+        // in the payload, replacing its slot with its position once its tag and control byte
+        // are recorded, which reuses the buffer of the slots
+        let mut tags = vec![0; len];
+        let mut controls = if len >= MIN_CONTROL_BYTES_LEN {
+            vec![0; len + GROUP_WIDTH - 1]
+        } else {
+            Vec::new()
+        };
 
-        while let Some(mut item) = prep_items.pop() {
-            let hash_slot_index = item.hash_slot_index;
-            let mut num_entries = 0;
+        for (slot, hash_code) in positions.iter_mut().zip(hash_codes) {
+            let cursor = &mut cursors[*slot];
+            let position = *cursor;
+            *cursor += 1;
 
-            loop {
-                entries.push(item.entry);
-                num_entries += 1;
+            tags[position] = tag(hash_code);
+            if let Some(c) = controls.get_mut(position) {
+                *c = control(hash_code);
+            }
 
-                if prep_items.is_empty()
-                    || prep_items.last().unwrap().hash_slot_index != hash_slot_index
-                {
-                    break;
-                }
+            *slot = position;
+        }
 
-                item = prep_items.pop().unwrap();
-            }
+        drop(cursors);
+        scatter(&mut payload, &mut positions);
 
-            slots[hash_slot_index] = HashTableSlot {
-                min_index: S::from(entry_index).unwrap(),
-                max_index: S::from(entry_index).unwrap() + S::from(num_entries).unwrap(),
-            };
+        Self {
+            num_slots,
+            slots,
+            entries: payload.into_boxed_slice(),
+            tags: tags.into_boxed_slice(),
+            controls: controls.into_boxed_slice(),
+            prefilter,
+            displacements,
+        }
+    }
 
-            entry_index += num_entries;
+    /// Creates a table like [`Self::new`], but reports a payload which holds duplicate keys
+    /// instead of accepting it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_hash_slots` is zero while the payload isn't empty.
+    pub fn try_new<F>(
+        payload: Vec<(K, V)>,
+        num_hash_slots: usize,
+        hash: F,
+    ) -> std::result::Result<Self, FrozenError>
+    where
+        K: Eq,
+        F: Fn(&K) -> u64,
+    {
+        if let Some(group) = find_duplicate_keys(&payload, &hash).first() {
+            return Err(FrozenError::DuplicateKey { index: group[1] });
         }
 
-        Self {
-            num_slots: NonZeroU64::try_from(NonZeroUsize::try_from(slots.len()).unwrap()).unwrap(),
-            slots: slots.into_boxed_slice(),
-            entries: entries.into_boxed_slice(),
+        Ok(Self::new(payload, num_hash_slots, hash))
+    }
+
+    /// Creates a table like [`Self::from_analysis`], without a filter, but reports a payload
+    /// which holds duplicate keys instead of accepting it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the analysis recommends no slots while the payload isn't empty.
+    pub fn try_from_analysis<F>(
+        payload: Vec<(K, V)>,
+        analysis: HashCodeAnalysisResult,
+        hash: F,
+    ) -> std::result::Result<Self, FrozenError>
+    where
+        K: Eq,
+        F: Fn(&K) -> u64,
+    {
+        if let Some(group) = find_duplicate_keys(&payload, &hash).first() {
+            return Err(FrozenError::DuplicateKey { index: group[1] });
         }
+
+        Ok(Self::from_analysis(payload, analysis, false, hash))
     }
 
     #[inline]
     pub fn get_hash_info(&self, hash_code: u64) -> Range<usize> {
-        let hash_slot_index = (hash_code % self.num_slots).as_usize();
-        let hash_slot = unsafe { self.slots.get_unchecked(hash_slot_index) };
+        if !self.prefilter.may_contain(hash_code) {
+            return 0..0;
+        }
+
+        let hash_slot_index = pick_slot(hash_code, self.num_slots, &self.displacements);
+
+        // only an empty table has no slots
+        let Some(range) = self.slots.get(hash_slot_index) else {
+            return 0..0;
+        };
+
+        debug_assert!(range.start <= range.end && range.end <= self.entries.len());
+        range
+    }
+
+    /// Returns the range of entries held by each slot, in slot order.
+    pub fn slot_ranges(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        (0..self.slots.len()).filter_map(|index| self.slots.get(index))
+    }
+
+    /// Returns the largest number of entries sharing a slot.
+    pub fn max_collisions(&self) -> usize {
+        self.slot_ranges().map(|r| r.len()).max().unwrap_or(0)
+    }
+
+    /// Describes the layout of the table.
+    pub fn stats(&self) -> MapStats {
+        MapStats::new::<K, V>(
+            self.entries.len(),
+            self.slots.len(),
+            self.max_collisions(),
+            self.slots.size_in_bytes()
+                + size_of_val(&*self.displacements)
+                + size_of_val(&*self.tags)
+                + size_of_val(&*self.controls)
+                + self.prefilter.size_in_bytes(),
+        )
+    }
+
+    /// Finds the index of the entry holding a key whose hash code is already known.
+    ///
+    /// Keys are only compared for entries whose tag matches that of the hash code.
+    #[inline]
+    pub fn find_index<Q>(&self, hash_code: u64, key: &Q) -> Option<usize>
+    where
+        Q: ?Sized + Equivalent<K>,
+    {
+        self.find_in_range(self.get_hash_info(hash_code), hash_code, key)
+    }
+
+    /// Finds the index of the entry holding a key among the entries of a range returned by
+    /// [`get_hash_info`](Self::get_hash_info), comparing every key of the range.
+    #[inline]
+    pub fn find_index_in<Q>(&self, range: Range<usize>, key: &Q) -> Option<usize>
+    where
+        Q: ?Sized + Equivalent<K>,
+    {
+        range
+            .into_iter()
+            .find(|&index| key.equivalent(&self.entries[index].0))
+    }
+
+    /// Finds the index of the entry holding a key among the entries of the key's slot.
+    #[inline]
+    fn find_in_range<Q>(&self, range: Range<usize>, hash_code: u64, key: &Q) -> Option<usize>
+    where
+        Q: ?Sized + Equivalent<K>,
+    {
+        let tag = tag(hash_code);
+
+        // get_hash_info only returns ranges within the entries, which match the tags
+        let is_match = |index: usize| unsafe {
+            *self.tags.get_unchecked(index) == tag
+                && key.equivalent(&self.entries.get_unchecked(index).0)
+        };
+
+        if self.controls.is_empty() {
+            return range.into_iter().find(|&index| is_match(index));
+        }
+
+        let control = control(hash_code);
+        let mut start = range.start;
+        while start < range.end {
+            let mut matches =
+                match_group(&self.controls, start, control) & group_mask(range.end - start);
+
+            while matches != 0 {
+                let index = start + matches.trailing_zeros() as usize;
+                if is_match(index) {
+                    return Some(index);
+                }
+
+                matches &= matches - 1;
+            }
+
+            start += GROUP_WIDTH;
+        }
+
+        None
+    }
+
+    /// Finds the entry holding a key whose hash code is already known.
+    #[inline]
+    pub fn find<Q>(&self, hash_code: u64, key: &Q) -> Option<&(K, V)>
+    where
+        Q: ?Sized + Equivalent<K>,
+    {
+        let index = self.find_index(hash_code, key)?;
+        Some(unsafe { self.entries.get_unchecked(index) })
+    }
+
+    /// Finds the entry holding a key whose hash code is already known.
+    #[inline]
+    pub fn find_mut<Q>(&mut self, hash_code: u64, key: &Q) -> Option<&mut (K, V)>
+    where
+        Q: ?Sized + Equivalent<K>,
+    {
+        let index = self.find_index(hash_code, key)?;
+        Some(unsafe { self.entries.get_unchecked_mut(index) })
+    }
+
+    /// Finds the entries holding several keys whose hash codes are already known.
+    ///
+    /// Every slot is located and prefetched before any key is compared, so the cache misses
+    /// of the individual lookups overlap instead of adding up.
+    #[inline]
+    pub fn find_many<Q, const N: usize>(
+        &self,
+        hash_codes: [u64; N],
+        keys: [&Q; N],
+    ) -> [Option<&(K, V)>; N]
+    where
+        Q: ?Sized + Equivalent<K>,
+    {
+        let ranges = hash_codes.map(|hash_code| self.locate(hash_code));
+        std::array::from_fn(|i| self.find_located(ranges[i].clone(), hash_codes[i], keys[i]))
+    }
+
+    /// Locates the slot of a hash code and starts loading its first entry into the cache.
+    ///
+    /// The lookup is finished by [`Self::find_located`], once enough other work was done for
+    /// the entry to have arrived.
+    #[inline]
+    pub fn locate(&self, hash_code: u64) -> Range<usize> {
+        let range = self.get_hash_info(hash_code);
+        if let Some(entry) = self.entries.get(range.start) {
+            prefetch(entry);
+        }
+
+        range
+    }
+
+    /// Finds the entry holding a key among the entries returned by [`Self::locate`].
+    #[inline]
+    pub fn find_located<Q>(&self, range: Range<usize>, hash_code: u64, key: &Q) -> Option<&(K, V)>
+    where
+        Q: ?Sized + Equivalent<K>,
+    {
+        let index = self.find_in_range(range, hash_code, key)?;
+        Some(&self.entries[index])
+    }
+}
+
+/// Derives the control byte stored for an entry from its hash code.
+///
+/// The top byte is used, since the low bits mostly pick the slot and the tag folds in the
+/// lower bytes of each half.
+#[inline]
+#[allow(clippy::cast_possible_truncation)]
+const fn control(hash_code: u64) -> u8 {
+    (hash_code >> 56) as u8
+}
+
+/// Returns a mask of the bits for the first `len` entries of a group.
+#[inline]
+const fn group_mask(len: usize) -> u16 {
+    if len >= GROUP_WIDTH {
+        u16::MAX
+    } else {
+        (1u16 << len) - 1
+    }
+}
+
+/// Returns a mask with a bit set for each of the control bytes in the group starting at `start`
+/// which equals `control`.
+///
+/// `controls` must hold a whole group from `start` onward.
+#[inline]
+fn match_group(controls: &[u8], start: usize, control: u8) -> u16 {
+    debug_assert!(start + GROUP_WIDTH <= controls.len());
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        use std::arch::x86_64::{
+            __m128i, _mm_cmpeq_epi8, _mm_loadu_si128, _mm_movemask_epi8, _mm_set1_epi8,
+        };
+
+        // SAFETY: SSE2 is part of the x86_64 baseline, the caller guarantees the group is in
+        // bounds, and the load doesn't need to be aligned
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_possible_wrap,
+            clippy::cast_sign_loss,
+            clippy::cast_ptr_alignment
+        )]
+        unsafe {
+            let group = _mm_loadu_si128(controls.as_ptr().add(start).cast::<__m128i>());
+            let matches = _mm_cmpeq_epi8(group, _mm_set1_epi8(control as i8));
+            _mm_movemask_epi8(matches) as u16
+        }
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        controls[start..start + GROUP_WIDTH]
+            .iter()
+            .enumerate()
+            .fold(0, |mask, (i, &c)| mask | (u16::from(c == control) << i))
+    }
+}
+
+/// Derives the tag stored for an entry from its hash code.
+///
+/// Both halves of the hash code are folded in, since hash codes which are small numbers,
+/// like lengths, only differ in their low bits, while entries sharing a slot tend to agree
+/// on them.
+#[inline]
+#[allow(clippy::cast_possible_truncation)]
+const fn tag(hash_code: u64) -> u32 {
+    (hash_code ^ (hash_code >> 32)) as u32
+}
+
+/// Hints the processor to start loading the given value into the cache.
+///
+/// This is only a hint, which compiles to nothing on targets without a prefetch instruction.
+#[inline]
+pub fn prefetch<T>(value: &T) {
+    #[cfg(target_arch = "x86_64")]
+    {
+        use std::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+
+        // SAFETY: prefetching has no observable effect, even on invalid addresses
+        unsafe { _mm_prefetch::<_MM_HINT_T0>(std::ptr::from_ref(value).cast()) };
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    let _ = value;
+}
+
+/// Maps a hash code to a slot.
+///
+/// The arithmetic is done in 64 bits so every bit of the hash code contributes, whatever the
+/// width of `usize`. The result is less than `num_slots`, which was itself converted from a
+/// `usize`, so narrowing it back can't truncate.
+#[inline]
+#[allow(clippy::cast_possible_truncation)]
+pub fn slot_index(hash_code: u64, num_slots: NonZeroU64) -> usize {
+    (hash_code % num_slots) as usize
+}
+
+/// Maps a hash code to a slot, through the seeds found for skewed hash codes if there are any.
+#[inline]
+/// Computes the hash code of each entry of the payload.
+///
+/// The entries stay in the payload's buffer, so its spare capacity is dropped before the hash
+/// codes are allocated, rather than when the entries are boxed.
+fn hash_entries<K, V, F>(payload: &mut Vec<(K, V)>, hash: F) -> Vec<u64>
+where
+    F: Fn(&K) -> u64,
+{
+    payload.shrink_to_fit();
+    payload.iter().map(|entry| hash(&entry.0)).collect()
+}
+
+/// Picks the slot of each hash code, spreading the hash codes across threads when there are
+/// many of them and the `rayon` feature is enabled.
+fn pick_slots(hash_codes: &[u64], num_slots: NonZeroU64, displacements: &[u32]) -> Vec<usize> {
+    #[cfg(feature = "rayon")]
+    if hash_codes.len() >= MIN_PARALLEL_LEN {
+        return hash_codes
+            .par_iter()
+            .map(|&hash_code| pick_slot(hash_code, num_slots, displacements))
+            .collect();
+    }
+
+    hash_codes
+        .iter()
+        .map(|&hash_code| pick_slot(hash_code, num_slots, displacements))
+        .collect()
+}
+
+fn pick_slot(hash_code: u64, num_slots: NonZeroU64, displacements: &[u32]) -> usize {
+    if displacements.is_empty() {
+        return slot_index(hash_code, num_slots);
+    }
+
+    // the number of slots was converted from a usize
+    #[allow(clippy::cast_possible_truncation)]
+    displaced_slot_index(hash_code, displacements, num_slots.get() as usize)
+}
+
+/// Returns mutable references to the values of the entries at several indices.
+///
+/// Returns `None` if any index is missing, or if two of them are the same, which is how lookups
+/// of keys matching the same entry are rejected.
+pub fn disjoint_values_mut<K, V, const N: usize>(
+    entries: &mut [(K, V)],
+    indices: [Option<usize>; N],
+) -> Option<[&mut V; N]> {
+    let mut found = [0; N];
+    for (index, entry) in found.iter_mut().zip(indices) {
+        *index = entry?;
+    }
+
+    let entries = entries.get_disjoint_mut(found).ok()?;
+    Some(entries.map(|entry| &mut entry.1))
+}
+
+/// Reorders items in place so the item which was at position `i` ends up at `positions[i]`.
+///
+/// `positions` must be a permutation of the item positions, and is left holding each position
+/// at its own index.
+pub fn scatter<T>(items: &mut [T], positions: &mut [usize]) {
+    debug_assert_eq!(items.len(), positions.len());
+
+    for start in 0..items.len() {
+        // swap the item at start into place until the one which belongs there arrives
+        loop {
+            let position = positions[start];
+            if position == start {
+                break;
+            }
+
+            items.swap(start, position);
+            positions.swap(start, position);
+        }
+    }
+}
+
+/// Sorts items in place, keeping items which compare equal in their original order.
+///
+/// Large items are sorted through their positions, and then moved into place once, rather
+/// than being moved at every step of the sort.
+pub fn sort_entries_by<T, F>(items: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    // moving a small item costs about as much as moving its position
+    if size_of::<T>() <= 2 * size_of::<usize>() {
+        items.sort_by(compare);
+        return;
+    }
 
-        hash_slot.min_index.to_usize().unwrap()..hash_slot.max_index.to_usize().unwrap()
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    order.sort_by(|&x, &y| compare(&items[x], &items[y]));
+
+    let mut positions = vec![0; items.len()];
+    for (position, &index) in order.iter().enumerate() {
+        positions[index] = position;
     }
+
+    drop(order);
+    scatter(items, &mut positions);
 }
 
-impl<K, V, S> HashTable<K, V, S> {
+#[inline]
+fn to_slot_bound<S: PrimInt + Unsigned>(index: usize) -> S {
+    // the slot width is picked to fit the number of entries
+    S::from(index).unwrap()
+}
+
+#[inline]
+fn from_slot_bound<S: PrimInt + Unsigned>(bound: S) -> usize {
+    // slot bounds never exceed the number of entries, which is a usize
+    bound.to_usize().unwrap()
+}
+
+impl<K, V> HashTable<K, V> {
+    /// Creates a table with no entries and no slots, without allocating.
+    pub fn empty() -> Self {
+        Self {
+            num_slots: NonZeroU64::MIN,
+            slots: Slots::Small(PackedBounds::default()),
+            entries: Box::default(),
+            tags: Box::default(),
+            controls: Box::default(),
+            prefilter: Prefilter::default(),
+            displacements: Box::default(),
+        }
+    }
+
     #[inline]
     pub const fn len(&self) -> usize {
         self.entries.len()
     }
+
+    /// Returns the number of slots entries are distributed over.
+    pub const fn num_slots(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns `true` if the table holds a filter over the hash codes of its entries.
+    pub fn has_prefilter(&self) -> bool {
+        !self.prefilter.is_empty()
+    }
+
+    /// Transforms every value while keeping entries, and therefore slots, in place.
+    pub fn map_values<V2, F>(self, f: F) -> HashTable<K, V2>
+    where
+        F: FnMut(V) -> V2,
+    {
+        HashTable {
+            num_slots: self.num_slots,
+            slots: self.slots,
+            entries: map_entries(self.entries, f),
+            tags: self.tags,
+            controls: self.controls,
+            prefilter: self.prefilter,
+            displacements: self.displacements,
+        }
+    }
 }
 
-impl<K, V, S> Debug for HashTable<K, V, S>
+/// Transforms the values of a boxed entry slice, calling `f` in entry order.
+pub fn map_entries<K, V, V2, F>(entries: Box<[(K, V)]>, mut f: F) -> Box<[(K, V2)]>
+where
+    F: FnMut(V) -> V2,
+{
+    entries
+        .into_vec()
+        .into_iter()
+        .map(|(k, v)| (k, f(v)))
+        .collect()
+}
+
+impl<K, V> Debug for HashTable<K, V>
 where
     K: Debug,
     V: Debug,
@@ -125,3 +812,337 @@ where
         f.debug_map().entries(pairs).finish()
     }
 }
+
+#[cfg(feature = "zeroize")]
+impl<K, V> Zeroize for HashTable<K, V>
+where
+    K: Zeroize,
+    V: Zeroize,
+{
+    /// Wipes the entries and the slot metadata, leaving a table in which every slot is empty.
+    fn zeroize(&mut self) {
+        self.entries.zeroize();
+        self.entries = Box::default();
+        self.tags.zeroize();
+        self.tags = Box::default();
+        self.controls.zeroize();
+        self.controls = Box::default();
+        self.prefilter.zeroize();
+        self.displacements.zeroize();
+
+        // the number of slots is kept, so slot indices remain in bounds
+        match &mut self.slots {
+            Slots::Small(slots) => zeroize_slots(slots),
+            Slots::Medium(slots) => zeroize_slots(slots),
+            Slots::Large(slots) => zeroize_slots(slots),
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+fn zeroize_slots<S: Zeroize, const N: usize>(slots: &mut PackedBounds<S, N>) {
+    for line in &mut slots.lines {
+        line.0.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use super::{
+        group_mask, match_group, scatter, slot_index, sort_entries_by, HashTable, Slots,
+        CACHE_LINE_BYTES, MIN_CONTROL_BYTES_LEN,
+    };
+    use crate::errors::FrozenError;
+
+    #[test]
+    fn entries_are_found_in_their_slot() {
+        let payload: Vec<_> = (0..1000u64).map(|i| (i * 7919, i)).collect();
+        let table = HashTable::new(payload, 313, |k| *k);
+
+        assert_eq!(1000, table.len());
+        for i in 0..1000u64 {
+            let range = table.get_hash_info(i * 7919);
+            assert!(range.end <= table.len());
+            assert!(table.entries[range].iter().any(|x| x.1 == i));
+        }
+    }
+
+    #[test]
+    fn find_many_matches_find() {
+        let payload: Vec<_> = (0..1000u64).map(|i| (i * 7919, i)).collect();
+        let table = HashTable::new(payload, 313, |k| *k);
+
+        let keys = [0, 7919 * 5, 1, 7919 * 999];
+        let found = table.find_many(keys, [&keys[0], &keys[1], &keys[2], &keys[3]]);
+        assert_eq!(keys.map(|k| table.find(k, &k)), found);
+        assert_eq!(
+            [Some(0), Some(5), None, Some(999)],
+            found.map(|e| e.map(|x| x.1))
+        );
+    }
+
+    #[test]
+    fn keys_are_only_compared_on_matching_tags() {
+        use std::cell::Cell;
+
+        struct Key<'a>(u64, &'a Cell<usize>);
+
+        impl PartialEq for Key<'_> {
+            fn eq(&self, other: &Self) -> bool {
+                self.1.set(self.1.get() + 1);
+                self.0 == other.0
+            }
+        }
+
+        impl Eq for Key<'_> {}
+
+        // a single slot, so every lookup scans every entry
+        let comparisons = Cell::new(0);
+        let payload: Vec<_> = (0..100u64).map(|i| (Key(i, &comparisons), i)).collect();
+        let table = HashTable::new(payload, 1, |k| k.0);
+
+        assert_eq!(
+            Some(42),
+            table.find(42, &Key(42, &comparisons)).map(|x| x.1)
+        );
+        assert_eq!(1, comparisons.get());
+        assert!(table.find(1000, &Key(1000, &comparisons)).is_none());
+        assert_eq!(1, comparisons.get());
+    }
+
+    #[test]
+    fn control_bytes() {
+        use std::hash::{BuildHasher, RandomState};
+
+        let bh = RandomState::new();
+        for len in [MIN_CONTROL_BYTES_LEN - 1, MIN_CONTROL_BYTES_LEN, 5000] {
+            for num_slots in [1, 7, 313] {
+                let payload: Vec<_> = (0..len as u64).map(|i| (i, i)).collect();
+                let table = HashTable::new(payload, num_slots, |k| bh.hash_one(k));
+                assert_eq!(len >= MIN_CONTROL_BYTES_LEN, !table.controls.is_empty());
+
+                for i in 0..len as u64 + 100 {
+                    let expected = (i < len as u64).then_some(i);
+                    assert_eq!(expected, table.find(bh.hash_one(i), &i).map(|x| x.1));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn group_matching() {
+        let mut controls = vec![0u8; 40];
+        controls[3] = 7;
+        controls[17] = 7;
+        controls[18] = 7;
+
+        assert_eq!(1 << 3, match_group(&controls, 0, 7));
+        assert_eq!(0b1100, match_group(&controls, 15, 7));
+        assert_eq!(!0b1100, match_group(&controls, 15, 0));
+        assert_eq!(0, match_group(&controls, 20, 7));
+        assert_eq!(0b111, group_mask(3));
+        assert_eq!(u16::MAX, group_mask(40));
+    }
+
+    #[test]
+    fn large_tables() {
+        // big enough for the widest slot bounds, and to pick slots in parallel when the rayon
+        // feature is enabled
+        let payload: Vec<_> = (0..200_000u64).map(|i| (i * 7919, i)).collect();
+        let table = HashTable::new(payload, 150_001, |k| *k);
+
+        for i in (0..200_000u64).step_by(7) {
+            assert_eq!(Some(&(i * 7919, i)), table.find(i * 7919, &(i * 7919)));
+        }
+    }
+
+    #[test]
+    fn scatter_follows_positions() {
+        let mut items = ['a', 'b', 'c', 'd', 'e', 'f'];
+        let mut positions = [1, 2, 3, 0, 5, 4];
+        scatter(&mut items, &mut positions);
+        assert_eq!(['d', 'a', 'b', 'c', 'f', 'e'], items);
+        assert_eq!([0, 1, 2, 3, 4, 5], positions);
+    }
+
+    #[test]
+    fn sort_entries_is_stable() {
+        let mut small: Vec<_> = (0..1000u32).map(|i| (i % 7, i)).collect();
+        let mut large: Vec<_> = (0..1000u32).map(|i| (i % 7, [i; 16])).collect();
+
+        let mut expected = small.clone();
+        expected.sort_by_key(|x| x.0);
+
+        sort_entries_by(&mut small, |x, y| x.0.cmp(&y.0));
+        sort_entries_by(&mut large, |x, y| x.0.cmp(&y.0));
+        assert_eq!(expected, small);
+        assert_eq!(
+            expected,
+            large.iter().map(|x| (x.0, x.1[0])).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn entries_stay_in_the_payload_buffer() {
+        let payload: Vec<_> = (0..5000u64).map(|i| (i * 7919, i)).collect();
+        let buffer = payload.as_ptr();
+        let table = HashTable::new(payload, 1000, |k| *k);
+
+        assert_eq!(buffer, table.entries.as_ptr());
+        for i in 0..5000u64 {
+            assert_eq!(Some(i), table.find(i * 7919, &(i * 7919)).map(|x| x.1));
+        }
+    }
+
+    #[test]
+    fn slot_widths() {
+        for (len, expected) in [
+            (255, "Small"),
+            (256, "Medium"),
+            (65_535, "Medium"),
+            (65_536, "Large"),
+        ] {
+            let payload: Vec<_> = (0..len as u64).map(|i| (i, i)).collect();
+            let table = HashTable::new(payload, len / 15 + 1, |k| *k);
+
+            let width = match table.slots {
+                Slots::Small(_) => "Small",
+                Slots::Medium(_) => "Medium",
+                Slots::Large(_) => "Large",
+            };
+            assert_eq!(expected, width, "{len}");
+
+            let max_end = (0..len as u64).map(|i| table.get_hash_info(i).end).max();
+            assert_eq!(Some(len), max_end);
+            assert!((0..len as u64)
+                .step_by(97)
+                .all(|i| table.entries[table.get_hash_info(i)]
+                    .iter()
+                    .any(|x| x.0 == i)));
+        }
+    }
+
+    #[test]
+    fn packed_bounds_match_slots() {
+        for (len, num_slots) in [
+            (10, 1),
+            (10, 63),
+            (200, 63),
+            (200, 64),
+            (255, 127),
+            (1000, 31),
+            (1000, 32),
+            (1000, 33),
+            (70_000, 7),
+            (70_000, 8),
+            (70_000, 50_000),
+        ] {
+            let mut sorted_slots: Vec<_> =
+                (0..len).map(|i: usize| (i * 7919) % num_slots).collect();
+            sorted_slots.sort_unstable();
+
+            let mut counts = vec![0; num_slots];
+            for &slot in &sorted_slots {
+                counts[slot] += 1;
+            }
+
+            let slots = Slots::new(&counts, len);
+            assert_eq!(num_slots, slots.len());
+            assert_eq!(None, slots.get(num_slots));
+
+            let mut start = 0;
+            for slot in 0..num_slots {
+                let count = sorted_slots[start..]
+                    .iter()
+                    .take_while(|&&s| s == slot)
+                    .count();
+                assert_eq!(
+                    Some(start..start + count),
+                    slots.get(slot),
+                    "{len} {num_slots}"
+                );
+                start += count;
+            }
+
+            let (lines, line_bytes) = match &slots {
+                Slots::Small(s) => (s.lines.as_ptr().addr(), size_of_val(&s.lines[0])),
+                Slots::Medium(s) => (s.lines.as_ptr().addr(), size_of_val(&s.lines[0])),
+                Slots::Large(s) => (s.lines.as_ptr().addr(), size_of_val(&s.lines[0])),
+            };
+            assert_eq!(0, lines % CACHE_LINE_BYTES);
+            assert_eq!(CACHE_LINE_BYTES, line_bytes);
+        }
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn slot_bounds_beyond_u32() {
+        let big = u32::MAX as usize + 1;
+        let counts = [big, 0, big + 2, 5];
+        let len = counts.iter().sum();
+
+        let slots = Slots::new(&counts, len);
+        assert!(matches!(slots, Slots::Large(_)));
+        assert_eq!(Some(0..big), slots.get(0));
+        assert_eq!(Some(big..big), slots.get(1));
+        assert_eq!(Some(big..2 * big + 2), slots.get(2));
+        assert_eq!(Some(2 * big + 2..len), slots.get(3));
+        assert_eq!(None, slots.get(4));
+    }
+
+    // this needs about 100 GB of memory, run it with `cargo test --release -- --ignored`
+    #[test]
+    #[ignore = "builds a table of more than u32::MAX entries"]
+    #[cfg(target_pointer_width = "64")]
+    fn tables_beyond_u32() {
+        let len = u32::MAX as usize + 2;
+
+        // zero-sized entries keep the payload itself free, so only the metadata is allocated
+        let table = HashTable::new(vec![((), ()); len], 2, |()| 1);
+        assert!(matches!(table.slots, Slots::Large(_)));
+        assert_eq!(len, table.len());
+        assert_eq!(0..0, table.get_hash_info(0));
+        assert_eq!(0..len, table.get_hash_info(1));
+        assert_eq!(Some(&((), ())), table.find(1, &()));
+        assert_eq!(None, table.find(0, &()));
+    }
+
+    #[test]
+    fn try_new_reports_bad_payloads() {
+        let payload = vec![(1u64, 1), (2, 2), (1, 3)];
+        assert_eq!(
+            Some(FrozenError::DuplicateKey { index: 2 }),
+            HashTable::try_new(payload, 2, |k| *k).err()
+        );
+
+        let payload: Vec<_> = (0..300u64).map(|i| (i, i)).collect();
+        let table = HashTable::try_new(payload, 17, |k| *k).unwrap();
+        assert_eq!(300, table.len());
+    }
+
+    #[test]
+    #[should_panic(expected = "A hash table needs at least one slot")]
+    fn zero_slots() {
+        _ = HashTable::new(vec![(1, 1)], 0, |k| *k);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn slot_index_uses_all_64_bits() {
+        let num_slots = NonZeroU64::new(5_000_000_011).unwrap();
+
+        for hash_code in [0, u64::from(u32::MAX) + 1, 1 << 40, u64::MAX - 1, u64::MAX] {
+            let index = slot_index(hash_code, num_slots);
+            assert_eq!(hash_code % 5_000_000_011, index as u64);
+            assert!((index as u64) < num_slots.get());
+        }
+
+        // hash codes which only differ above bit 32 must not collapse into the same slot
+        assert_ne!(
+            slot_index(1 << 33, num_slots),
+            slot_index(1 << 34, num_slots)
+        );
+    }
+}
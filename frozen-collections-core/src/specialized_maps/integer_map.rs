@@ -1,48 +1,81 @@
-use std::borrow::Borrow;
 use std::fmt::{Debug, Formatter, Result};
-use std::intrinsics::transmute;
-use std::mem::MaybeUninit;
+use std::hash::{Hash, Hasher};
 use std::ops::Range;
 use std::ops::{Index, IndexMut};
 
-use num_traits::{AsPrimitive, PrimInt, Unsigned};
-
-use crate::analyzers::hash_code_analyzer::analyze_hash_codes;
-use crate::specialized_maps::hash_table::HashTable;
-use crate::specialized_maps::{Iter, Keys, Values};
+use crate::analyzers::hash_code_analyzer::{
+    analyze_hash_codes, analyze_hash_codes_with, HashCodeAnalysisOptions,
+};
+use crate::errors::FrozenError;
+use crate::hashers::hash_unordered;
+use crate::specialized_maps::hash_table::{disjoint_values_mut, HashTable};
+use crate::specialized_maps::key_codes::int_code;
+use crate::specialized_maps::{
+    IntoIter, IntoKeys, IntoValues, Iter, IterMut, Keys, MapStats, Values, ValuesMut,
+};
+use crate::traits::equivalent::Equivalent;
+use crate::traits::heap_size::{entries_heap_bytes, HeapSize};
 use crate::traits::len::Len;
+use crate::traits::map::Map;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
 
 /// A map whose keys are integers, and which uses those key values as hash codes to avoid the overhead of hashing.
+///
+/// Keys don't need to be primitive integers: any key whose [`Hash`] implementation writes a
+/// single integer, such as a newtype around an integer or a fieldless enum, is treated as that
+/// integer.
 #[derive(Clone)]
-pub struct IntegerMap<K, V, S = u8> {
-    pub(crate) table: HashTable<K, V, S>,
+pub struct IntegerMap<K, V> {
+    pub(crate) table: HashTable<K, V>,
 }
 
-impl<K, V, S> IntegerMap<K, V, S>
+impl<K, V> IntegerMap<K, V>
 where
-    K: PrimInt + AsPrimitive<u64>,
-    S: PrimInt + Unsigned,
+    K: Hash + Eq,
 {
     #[must_use]
     pub fn from_vec(payload: Vec<(K, V)>) -> Self {
-        let code_analysis = analyze_hash_codes(payload.iter().map(|entry| entry.0.as_()));
+        let code_analysis = analyze_hash_codes(payload.iter().map(|entry| int_code(&entry.0)));
         Self {
-            table: HashTable::new(payload, code_analysis.num_hash_slots, |k| k.as_()),
+            table: HashTable::from_analysis(payload, code_analysis, false, int_code),
         }
     }
+
+    /// Creates a map whose hash table is sized within the given limits.
+    pub(crate) fn from_vec_with_sizing(
+        payload: Vec<(K, V)>,
+        sizing: &HashCodeAnalysisOptions,
+    ) -> Self {
+        let codes = payload.iter().map(|entry| int_code(&entry.0));
+        let code_analysis = analyze_hash_codes_with(codes, sizing);
+        Self {
+            table: HashTable::from_analysis(payload, code_analysis, sizing.prefilter, int_code),
+        }
+    }
+
+    /// Creates a map, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys.
+    pub fn try_from_vec(payload: Vec<(K, V)>) -> std::result::Result<Self, FrozenError> {
+        let code_analysis = analyze_hash_codes(payload.iter().map(|entry| int_code(&entry.0)));
+        Ok(Self {
+            table: HashTable::try_from_analysis(payload, code_analysis, int_code)?,
+        })
+    }
 }
 
-impl<K, V, S> IntegerMap<K, V, S>
-where
-    S: PrimInt + Unsigned,
-{
+impl<K, V> IntegerMap<K, V> {
     #[inline]
     #[must_use]
     fn get_hash_info<Q>(&self, key: &Q) -> Range<usize>
     where
-        Q: PrimInt + AsPrimitive<u64>,
+        Q: ?Sized + Hash,
     {
-        let hash_code = key.as_();
+        let hash_code = int_code(key);
         self.table.get_hash_info(hash_code)
     }
 
@@ -50,13 +83,12 @@ where
     #[must_use]
     pub fn get<Q>(&self, key: &Q) -> Option<&V>
     where
-        K: Borrow<Q>,
-        Q: PrimInt + AsPrimitive<u64>,
+        Q: ?Sized + Hash + Equivalent<K>,
     {
         let range = self.get_hash_info(key);
         let entries = unsafe { self.table.entries.get_unchecked(range) };
         for entry in entries {
-            if key.eq(entry.0.borrow()) {
+            if key.equivalent(&entry.0) {
                 return Some(&entry.1);
             }
         }
@@ -68,13 +100,12 @@ where
     #[must_use]
     pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
     where
-        K: Borrow<Q>,
-        Q: PrimInt + AsPrimitive<u64>,
+        Q: ?Sized + Hash + Equivalent<K>,
     {
         let range = self.get_hash_info(key);
         let entries = unsafe { self.table.entries.get_unchecked_mut(range) };
         for entry in entries {
-            if key.eq(entry.0.borrow()) {
+            if key.equivalent(&entry.0) {
                 return Some(&mut entry.1);
             }
         }
@@ -82,44 +113,82 @@ where
         None
     }
 
-    #[allow(mutable_transmutes)]
-    pub fn get_many_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> Option<[&mut V; N]>
+    /// Returns a reference to the value corresponding to the key, given the key's hash code.
+    ///
+    /// Integer maps use the integer written by a key as its hash code, which
+    /// [`raw::int_hash`](crate::specialized_maps::raw::int_hash) returns. Any other code makes
+    /// the lookup fail.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::specialized_maps::raw::int_hash;
+    /// use frozen_collections_core::specialized_maps::IntegerMap;
+    ///
+    /// let map = IntegerMap::from_vec(vec![(10u32, "a"), (20, "b")]);
+    /// assert_eq!(map.get_with_hash(int_hash(&20u32), &20), Some(&"b"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn get_with_hash<Q>(&self, hash_code: u64, key: &Q) -> Option<&V>
     where
-        K: Borrow<Q>,
-        Q: PrimInt + AsPrimitive<u64>,
+        Q: ?Sized + Equivalent<K>,
     {
-        // ensure key uniqueness (assumes "keys" is a relatively small array)
-        for i in 0..keys.len() {
-            for j in 0..i {
-                if keys[j].eq(keys[i]) {
-                    return None;
-                }
-            }
-        }
+        let entry = self.table.find(hash_code, key)?;
+        Some(&entry.1)
+    }
 
-        unsafe {
-            let mut result: MaybeUninit<[&mut V; N]> = MaybeUninit::uninit();
-            let p = result.as_mut_ptr();
+    /// Returns the key-value pair corresponding to the key, given the key's hash code.
+    ///
+    /// The hash code must be the integer written by the key, as described for
+    /// [`get_with_hash`](Self::get_with_hash).
+    #[inline]
+    #[must_use]
+    pub fn get_key_value_with_hash<Q>(&self, hash_code: u64, key: &Q) -> Option<(&K, &V)>
+    where
+        Q: ?Sized + Equivalent<K>,
+    {
+        let entry = self.table.find(hash_code, key)?;
+        Some((&entry.0, &entry.1))
+    }
 
-            for (i, key) in keys.iter().enumerate() {
-                *(*p).get_unchecked_mut(i) = transmute(self.get(key)?);
-            }
+    /// Returns references to the values corresponding to several keys at once.
+    ///
+    /// The hash codes of all the keys are computed first and their slots prefetched, so the
+    /// memory latency of the lookups overlaps.
+    #[inline]
+    #[must_use]
+    pub fn get_many<Q, const N: usize>(&self, keys: [&Q; N]) -> [Option<&V>; N]
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let hash_codes = keys.map(|key| int_code(key));
+        self.table
+            .find_many(hash_codes, keys)
+            .map(|entry| entry.map(|(_, v)| v))
+    }
 
-            Some(result.assume_init())
-        }
+    /// Returns mutable references to the values of several keys at once.
+    ///
+    /// Returns `None` if any key is missing, or if two keys match the same entry.
+    pub fn get_many_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> Option<[&mut V; N]>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let indices = keys.map(|key| self.table.find_index_in(self.get_hash_info(key), key));
+        disjoint_values_mut(&mut self.table.entries, indices)
     }
 
     #[inline]
     #[must_use]
     pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
     where
-        K: Borrow<Q>,
-        Q: PrimInt + AsPrimitive<u64>,
+        Q: ?Sized + Hash + Equivalent<K>,
     {
         let range = self.get_hash_info(key);
         let entries = unsafe { self.table.entries.get_unchecked(range) };
         for entry in entries {
-            if key.eq(entry.0.borrow()) {
+            if key.equivalent(&entry.0) {
                 return Some((&entry.0, &entry.1));
             }
         }
@@ -131,14 +200,13 @@ where
     #[must_use]
     pub fn contains_key<Q>(&self, key: &Q) -> bool
     where
-        K: Borrow<Q>,
-        Q: PrimInt + AsPrimitive<u64>,
+        Q: ?Sized + Hash + Equivalent<K>,
     {
         self.get(key).is_some()
     }
 }
 
-impl<K, V, S> IntegerMap<K, V, S> {
+impl<K, V> IntegerMap<K, V> {
     #[must_use]
     pub const fn iter(&self) -> Iter<K, V> {
         Iter::new(&self.table.entries)
@@ -153,15 +221,121 @@ impl<K, V, S> IntegerMap<K, V, S> {
     pub const fn values(&self) -> Values<K, V> {
         Values::new(&self.table.entries)
     }
+
+    #[must_use]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut::new(&mut self.table.entries)
+    }
+
+    #[must_use]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut::new(&mut self.table.entries)
+    }
+
+    #[must_use]
+    pub fn into_keys(self) -> IntoKeys<K, V> {
+        IntoKeys::new(self.table.entries)
+    }
+
+    #[must_use]
+    pub fn into_values(self) -> IntoValues<K, V> {
+        IntoValues::new(self.table.entries)
+    }
+
+    pub(crate) fn map_values<V2, F>(self, f: F) -> IntegerMap<K, V2>
+    where
+        F: FnMut(V) -> V2,
+    {
+        IntegerMap {
+            table: self.table.map_values(f),
+        }
+    }
+}
+
+impl<K, V> IntegerMap<K, V> {
+    /// Describes the layout of the map.
+    #[must_use]
+    pub fn stats(&self) -> MapStats {
+        self.table.stats()
+    }
+}
+
+impl<K, V> IntegerMap<K, V> {
+    /// Creates an empty map without allocating.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            table: HashTable::empty(),
+        }
+    }
+}
+
+impl<K, V> Default for IntegerMap<K, V> {
+    fn default() -> Self {
+        Self::empty()
+    }
 }
 
-impl<K, V, S> Len for IntegerMap<K, V, S> {
+impl<K, V> Len for IntegerMap<K, V> {
     fn len(&self) -> usize {
         self.table.len()
     }
 }
 
-impl<K, V, S> Debug for IntegerMap<K, V, S>
+impl<K, V> HeapSize for IntegerMap<K, V>
+where
+    K: HeapSize,
+    V: HeapSize,
+{
+    fn heap_bytes(&self) -> usize {
+        self.stats().heap_bytes() + entries_heap_bytes(self.iter())
+    }
+}
+
+impl<K, V> Map<K, V> for IntegerMap<K, V>
+where
+    K: Hash + Eq,
+{
+    type Iterator<'a>
+        = Iter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    type KeyIterator<'a>
+        = Keys<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    type ValueIterator<'a>
+        = Values<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    fn iter(&self) -> Iter<'_, K, V> {
+        self.iter()
+    }
+
+    fn keys(&self) -> Keys<'_, K, V> {
+        self.keys()
+    }
+
+    fn values(&self) -> Values<'_, K, V> {
+        self.values()
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+}
+
+impl<K, V> Debug for IntegerMap<K, V>
 where
     K: Debug,
     V: Debug,
@@ -171,11 +345,9 @@ where
     }
 }
 
-impl<Q, K, V, S> Index<Q> for IntegerMap<K, V, S>
+impl<Q, K, V> Index<Q> for IntegerMap<K, V>
 where
-    K: Borrow<Q>,
-    Q: PrimInt + AsPrimitive<u64>,
-    S: PrimInt + Unsigned,
+    Q: Hash + Equivalent<K>,
 {
     type Output = V;
 
@@ -184,18 +356,25 @@ where
     }
 }
 
-impl<Q, K, V, S> IndexMut<Q> for IntegerMap<K, V, S>
+impl<Q, K, V> IndexMut<Q> for IntegerMap<K, V>
 where
-    K: Borrow<Q>,
-    Q: PrimInt + AsPrimitive<u64>,
-    S: PrimInt + Unsigned,
+    Q: Hash + Equivalent<K>,
 {
     fn index_mut(&mut self, index: Q) -> &mut V {
         self.get_mut(&index).unwrap()
     }
 }
 
-impl<'a, K, V, S> IntoIterator for &'a IntegerMap<K, V, S> {
+impl<K, V> IntoIterator for IntegerMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.table.entries)
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a IntegerMap<K, V> {
     type Item = (&'a K, &'a V);
     type IntoIter = Iter<'a, K, V>;
 
@@ -204,11 +383,19 @@ impl<'a, K, V, S> IntoIterator for &'a IntegerMap<K, V, S> {
     }
 }
 
-impl<K, V, S> PartialEq<Self> for IntegerMap<K, V, S>
+impl<'a, K, V> IntoIterator for &'a mut IntegerMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K, V> PartialEq<Self> for IntegerMap<K, V>
 where
-    K: PrimInt + AsPrimitive<u64>,
+    K: Hash + Eq,
     V: PartialEq,
-    S: PrimInt + Unsigned,
 {
     fn eq(&self, other: &Self) -> bool {
         if self.len() != other.len() {
@@ -220,34 +407,52 @@ where
     }
 }
 
-impl<K, V, S> Eq for IntegerMap<K, V, S>
+impl<K, V> Eq for IntegerMap<K, V>
 where
-    K: PrimInt + AsPrimitive<u64>,
+    K: Hash + Eq,
     V: Eq,
-    S: PrimInt + Unsigned,
 {
 }
 
-impl<K, V, S, const N: usize> From<[(K, V); N]> for IntegerMap<K, V, S>
+impl<K, V> Hash for IntegerMap<K, V>
 where
-    K: PrimInt + AsPrimitive<u64>,
-    S: PrimInt + Unsigned,
+    K: Hash,
+    V: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self, state);
+    }
+}
+
+impl<K, V, const N: usize> From<[(K, V); N]> for IntegerMap<K, V>
+where
+    K: Hash + Eq,
 {
     fn from(payload: [(K, V); N]) -> Self {
         Self::from_vec(Vec::from_iter(payload))
     }
 }
 
-impl<K, V, S> FromIterator<(K, V)> for IntegerMap<K, V, S>
+impl<K, V> FromIterator<(K, V)> for IntegerMap<K, V>
 where
-    K: PrimInt + AsPrimitive<u64>,
-    S: PrimInt + Unsigned,
+    K: Hash + Eq,
 {
     fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
         Self::from_vec(Vec::from_iter(iter))
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl<K, V> Zeroize for IntegerMap<K, V>
+where
+    K: Zeroize,
+    V: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.table.zeroize();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -255,21 +460,21 @@ mod tests {
     #[test]
     fn test_from_iter_empty() {
         let pairs: Vec<(u32, u32)> = vec![];
-        let map: IntegerMap<u32, u32, u32> = pairs.into_iter().collect();
+        let map: IntegerMap<u32, u32> = pairs.into_iter().collect();
         assert!(map.is_empty());
     }
 
     #[test]
     fn test_from_iter_single() {
         let pairs = vec![(1, 2)];
-        let map: IntegerMap<u32, u32, u32> = pairs.into_iter().collect();
+        let map: IntegerMap<u32, u32> = pairs.into_iter().collect();
         assert_eq!(map.get(&1), Some(&2));
     }
 
     #[test]
     fn test_from_iter_multiple() {
         let pairs = vec![(1, 2), (3, 4), (5, 6)];
-        let map: IntegerMap<u32, u32, u32> = pairs.into_iter().collect();
+        let map: IntegerMap<u32, u32> = pairs.into_iter().collect();
         assert_eq!(map.get(&1), Some(&2));
         assert_eq!(map.get(&3), Some(&4));
         assert_eq!(map.get(&5), Some(&6));
@@ -1,69 +1,91 @@
-use std::borrow::Borrow;
 use std::fmt::{Debug, Formatter, Result};
-use std::intrinsics::transmute;
-use std::mem::MaybeUninit;
+use std::hash::{Hash, Hasher};
 use std::ops::{Index, IndexMut};
 
-use num_traits::PrimInt;
-
-use crate::specialized_maps::{Iter, Keys, Values};
+use crate::analyzers::duplicate_key_analyzer::find_duplicate_keys;
+use crate::analyzers::int_key_analyzer::find_code_range;
+use crate::errors::FrozenError;
+use crate::hashers::hash_unordered;
+use crate::specialized_maps::hash_table::{disjoint_values_mut, map_entries, scatter};
+use crate::specialized_maps::key_codes::int_code;
+use crate::specialized_maps::{
+    IntoIter, IntoKeys, IntoValues, Iter, IterMut, Keys, MapStats, Values, ValuesMut,
+};
+use crate::traits::equivalent::Equivalent;
+use crate::traits::heap_size::{entries_heap_bytes, HeapSize};
 use crate::traits::len::Len;
+use crate::traits::map::Map;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
 
 /// A map whose keys are a continuous range of integers.
+///
+/// As with [`IntegerMap`](crate::specialized_maps::IntegerMap), any key whose [`Hash`]
+/// implementation writes a single integer is treated as that integer.
 #[derive(Clone)]
 pub struct IntegerRangeMap<K, V> {
-    min: K,
-    max: K,
+    base: u64,
     pub(crate) entries: Box<[(K, V)]>,
 }
 
 impl<K, V> IntegerRangeMap<K, V>
 where
-    K: PrimInt,
+    K: Hash + Eq,
 {
     /// # Panics
     ///
     /// Panics if the keys aren't all in a contiguous range.
     #[must_use]
-    pub fn from_vec(mut payload: Vec<(K, V)>) -> Self {
+    pub fn from_vec(payload: Vec<(K, V)>) -> Self {
+        Self::try_from_vec(payload)
+            .expect("IntegerRangeMap requires that the map keys be in a continuous range")
+    }
+
+    /// Creates a map, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys, or if the keys aren't all in a contiguous range.
+    pub fn try_from_vec(mut payload: Vec<(K, V)>) -> std::result::Result<Self, FrozenError> {
         if payload.is_empty() {
-            return Self {
-                min: K::zero(),
-                max: K::zero(),
-                entries: Box::new([]),
-            };
+            return Ok(Self::empty());
         }
 
-        payload.sort_by_key(|x| x.0);
+        if let Some(group) = find_duplicate_keys(&payload, int_code).first() {
+            return Err(FrozenError::DuplicateKey { index: group[1] });
+        }
 
-        let min = payload[0].0;
-        let max = payload[payload.len() - 1].0;
+        let mut codes: Vec<_> = payload.iter().map(|x| int_code(&x.0)).collect();
+        let base = find_code_range(&mut codes).ok_or(FrozenError::NonContiguousRange)?;
 
-        assert_eq!(
-            max.sub(min).to_usize().unwrap(),
-            payload.len() - 1,
-            "IntegerRangeMap requires that the map keys be in a continuous range"
-        );
+        // the keys are distinct and fill the range, so the offset of each key from the base is
+        // the position of its entry, which is less than the number of entries
+        #[allow(clippy::cast_possible_truncation)]
+        let mut positions: Vec<_> = payload
+            .iter()
+            .map(|x| int_code(&x.0).wrapping_sub(base) as usize)
+            .collect();
+        scatter(&mut payload, &mut positions);
 
-        Self {
-            min,
-            max,
+        Ok(Self {
+            base,
             entries: payload.into_boxed_slice(),
-        }
+        })
     }
 }
 
 impl<K, V> IntegerRangeMap<K, V> {
+    /// Returns the index of the entry holding the key.
     #[inline]
-    #[must_use]
-    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    fn find<Q>(&self, key: &Q) -> Option<usize>
     where
-        K: Borrow<Q>,
-        Q: PrimInt,
+        Q: ?Sized + Hash + Equivalent<K>,
     {
-        if *key >= *self.min.borrow() && *key <= *self.max.borrow() {
-            let index = (*key - *self.min.borrow()).to_usize()?;
-            Some(&self.entries[index].1)
+        let index = usize::try_from(int_code(key).wrapping_sub(self.base)).ok()?;
+        let entry = self.entries.get(index)?;
+        if key.equivalent(&entry.0) {
+            Some(index)
         } else {
             None
         }
@@ -71,67 +93,79 @@ impl<K, V> IntegerRangeMap<K, V> {
 
     #[inline]
     #[must_use]
-    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
     where
-        K: Borrow<Q>,
-        Q: PrimInt,
+        Q: ?Sized + Hash + Equivalent<K>,
     {
-        if *key >= *self.min.borrow() && *key <= *self.max.borrow() {
-            let index = (*key - *self.min.borrow()).to_usize()?;
-            Some(&mut self.entries[index].1)
-        } else {
-            None
-        }
+        let index = self.find(key)?;
+        Some(&self.entries[index].1)
     }
 
-    #[allow(mutable_transmutes)]
-    pub fn get_many_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> Option<[&mut V; N]>
+    /// Returns a reference to the value corresponding to a key known to be in the map.
+    ///
+    /// The key's entry is found from the key's value alone, without comparing keys.
+    ///
+    /// # Safety
+    ///
+    /// Calling this method with a key which isn't in the map is undefined behavior.
+    #[inline]
+    #[must_use]
+    pub unsafe fn get_unchecked<Q>(&self, key: &Q) -> &V
     where
-        K: Borrow<Q>,
-        Q: PrimInt,
+        Q: ?Sized + Hash + Equivalent<K>,
     {
-        // ensure key uniqueness (assumes "keys" is a relatively small array)
-        for i in 0..keys.len() {
-            for j in 0..i {
-                if keys[j].eq(keys[i]) {
-                    return None;
-                }
-            }
-        }
+        // the keys of the map cover a range, so a present key's offset is within the entries
+        #[allow(clippy::cast_possible_truncation)]
+        let index = int_code(key).wrapping_sub(self.base) as usize;
+        unsafe { &self.entries.get_unchecked(index).1 }
+    }
 
-        unsafe {
-            let mut result: MaybeUninit<[&mut V; N]> = MaybeUninit::uninit();
-            let p = result.as_mut_ptr();
+    #[inline]
+    #[must_use]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let index = self.find(key)?;
+        Some(&mut self.entries[index].1)
+    }
 
-            for (i, key) in keys.iter().enumerate() {
-                *(*p).get_unchecked_mut(i) = transmute(self.get(key)?);
-            }
+    /// Returns references to the values corresponding to several keys at once.
+    #[inline]
+    #[must_use]
+    pub fn get_many<Q, const N: usize>(&self, keys: [&Q; N]) -> [Option<&V>; N]
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        keys.map(|key| self.get(key))
+    }
 
-            Some(result.assume_init())
-        }
+    /// Returns mutable references to the values of several keys at once.
+    ///
+    /// Returns `None` if any key is missing, or if two keys match the same entry.
+    pub fn get_many_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> Option<[&mut V; N]>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let indices = keys.map(|key| self.find(key));
+        disjoint_values_mut(&mut self.entries, indices)
     }
 
     #[inline]
     #[must_use]
     pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
     where
-        K: Borrow<Q>,
-        Q: PrimInt,
+        Q: ?Sized + Hash + Equivalent<K>,
     {
-        if *key >= *self.min.borrow() && *key <= *self.max.borrow() {
-            let index = (*key - *self.min.borrow()).to_usize()?;
-            Some((&self.entries[index].0, &self.entries[index].1))
-        } else {
-            None
-        }
+        let index = self.find(key)?;
+        Some((&self.entries[index].0, &self.entries[index].1))
     }
 
     #[inline]
     #[must_use]
     pub fn contains_key<Q>(&self, key: &Q) -> bool
     where
-        K: Borrow<Q>,
-        Q: PrimInt,
+        Q: ?Sized + Hash + Equivalent<K>,
     {
         self.get(key).is_some()
     }
@@ -150,6 +184,61 @@ impl<K, V> IntegerRangeMap<K, V> {
     pub const fn values(&self) -> Values<K, V> {
         Values::new(&self.entries)
     }
+
+    #[must_use]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut::new(&mut self.entries)
+    }
+
+    #[must_use]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut::new(&mut self.entries)
+    }
+
+    #[must_use]
+    pub fn into_keys(self) -> IntoKeys<K, V> {
+        IntoKeys::new(self.entries)
+    }
+
+    #[must_use]
+    pub fn into_values(self) -> IntoValues<K, V> {
+        IntoValues::new(self.entries)
+    }
+
+    pub(crate) fn map_values<V2, F>(self, f: F) -> IntegerRangeMap<K, V2>
+    where
+        F: FnMut(V) -> V2,
+    {
+        IntegerRangeMap {
+            base: self.base,
+            entries: map_entries(self.entries, f),
+        }
+    }
+}
+
+impl<K, V> IntegerRangeMap<K, V> {
+    /// Describes the layout of the map.
+    #[must_use]
+    pub fn stats(&self) -> MapStats {
+        MapStats::direct::<K, V>(self.entries.len(), self.entries.len(), 0)
+    }
+}
+
+impl<K, V> IntegerRangeMap<K, V> {
+    /// Creates an empty map without allocating.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            base: 0,
+            entries: Box::default(),
+        }
+    }
+}
+
+impl<K, V> Default for IntegerRangeMap<K, V> {
+    fn default() -> Self {
+        Self::empty()
+    }
 }
 
 impl<K, V> Len for IntegerRangeMap<K, V> {
@@ -158,6 +247,63 @@ impl<K, V> Len for IntegerRangeMap<K, V> {
     }
 }
 
+impl<K, V> HeapSize for IntegerRangeMap<K, V>
+where
+    K: HeapSize,
+    V: HeapSize,
+{
+    fn heap_bytes(&self) -> usize {
+        self.stats().heap_bytes() + entries_heap_bytes(self.iter())
+    }
+}
+
+impl<K, V> Map<K, V> for IntegerRangeMap<K, V>
+where
+    K: Hash + Eq,
+{
+    type Iterator<'a>
+        = Iter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    type KeyIterator<'a>
+        = Keys<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    type ValueIterator<'a>
+        = Values<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    fn iter(&self) -> Iter<'_, K, V> {
+        self.iter()
+    }
+
+    fn keys(&self) -> Keys<'_, K, V> {
+        self.keys()
+    }
+
+    fn values(&self) -> Values<'_, K, V> {
+        self.values()
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    unsafe fn get_unchecked(&self, key: &K) -> &V {
+        unsafe { self.get_unchecked(key) }
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+}
+
 impl<K, V> Debug for IntegerRangeMap<K, V>
 where
     K: Debug,
@@ -171,8 +317,7 @@ where
 
 impl<Q, K, V> Index<Q> for IntegerRangeMap<K, V>
 where
-    K: Borrow<Q>,
-    Q: PrimInt,
+    Q: Hash + Equivalent<K>,
 {
     type Output = V;
 
@@ -183,14 +328,22 @@ where
 
 impl<Q, K, V> IndexMut<Q> for IntegerRangeMap<K, V>
 where
-    K: Borrow<Q>,
-    Q: PrimInt,
+    Q: Hash + Equivalent<K>,
 {
     fn index_mut(&mut self, index: Q) -> &mut V {
         self.get_mut(&index).unwrap()
     }
 }
 
+impl<K, V> IntoIterator for IntegerRangeMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.entries)
+    }
+}
+
 impl<'a, K, V> IntoIterator for &'a IntegerRangeMap<K, V> {
     type Item = (&'a K, &'a V);
     type IntoIter = Iter<'a, K, V>;
@@ -200,9 +353,18 @@ impl<'a, K, V> IntoIterator for &'a IntegerRangeMap<K, V> {
     }
 }
 
+impl<'a, K, V> IntoIterator for &'a mut IntegerRangeMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
 impl<K, V> PartialEq<Self> for IntegerRangeMap<K, V>
 where
-    K: PrimInt,
+    K: Hash + Eq,
     V: PartialEq,
 {
     fn eq(&self, other: &Self) -> bool {
@@ -217,14 +379,24 @@ where
 
 impl<K, V> Eq for IntegerRangeMap<K, V>
 where
-    K: PrimInt,
+    K: Hash + Eq,
     V: Eq,
 {
 }
 
+impl<K, V> Hash for IntegerRangeMap<K, V>
+where
+    K: Hash,
+    V: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self, state);
+    }
+}
+
 impl<K, V, const N: usize> From<[(K, V); N]> for IntegerRangeMap<K, V>
 where
-    K: PrimInt,
+    K: Hash + Eq,
 {
     fn from(payload: [(K, V); N]) -> Self {
         Self::from_vec(Vec::from_iter(payload))
@@ -233,15 +405,29 @@ where
 
 impl<K, V> FromIterator<(K, V)> for IntegerRangeMap<K, V>
 where
-    K: PrimInt,
+    K: Hash + Eq,
 {
     fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
         Self::from_vec(Vec::from_iter(iter))
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl<K, V> Zeroize for IntegerRangeMap<K, V>
+where
+    K: Zeroize,
+    V: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.entries.zeroize();
+        self.base.zeroize();
+        *self = Self::empty();
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use crate::errors::FrozenError;
     use crate::traits::len::Len;
 
     use super::IntegerRangeMap;
@@ -286,4 +472,48 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn newtype_keys() {
+        #[derive(Debug, Hash, PartialEq, Eq)]
+        struct Id(u64);
+
+        let m = IntegerRangeMap::from_vec((0..5).map(|i| (Id(u64::MAX - i), i)).collect());
+        assert_eq!(5, m.len());
+        assert_eq!(Some(&0), m.get(&Id(u64::MAX)));
+        assert_eq!(Some(&4), m.get(&Id(u64::MAX - 4)));
+        assert_eq!(None, m.get(&Id(u64::MAX - 5)));
+        assert_eq!(None, m.get(&Id(0)));
+    }
+
+    #[test]
+    fn try_from_vec_reports_bad_keys() {
+        assert_eq!(
+            Some(FrozenError::NonContiguousRange),
+            IntegerRangeMap::try_from_vec(vec![(1, 1), (2, 2), (4, 4)]).err()
+        );
+        assert_eq!(
+            Some(FrozenError::DuplicateKey { index: 2 }),
+            IntegerRangeMap::try_from_vec(vec![(1, 1), (2, 2), (1, 3)]).err()
+        );
+        assert_eq!(
+            3,
+            IntegerRangeMap::try_from_vec(vec![(-1, 1), (0, 2), (1, 3)])
+                .unwrap()
+                .len()
+        );
+    }
+
+    #[test]
+    fn empty_map_matches_no_key() {
+        for m in [
+            IntegerRangeMap::<i32, i32>::empty(),
+            IntegerRangeMap::default(),
+            IntegerRangeMap::from_vec(Vec::new()),
+        ] {
+            assert_eq!(0, m.len());
+            assert_eq!(None, m.get(&0));
+            assert_eq!(None, m.get(&1));
+        }
+    }
 }
@@ -0,0 +1,481 @@
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::{Hash, Hasher};
+use std::ops::{Index, IndexMut};
+
+use crate::analyzers::duplicate_key_analyzer::find_duplicate_keys;
+use crate::errors::FrozenError;
+use crate::hashers::hash_unordered;
+use crate::specialized_maps::hash_table::{disjoint_values_mut, map_entries};
+use crate::specialized_maps::key_codes::int_code;
+use crate::specialized_maps::{
+    IntoIter, IntoKeys, IntoValues, Iter, IterMut, Keys, MapStats, Values, ValuesMut,
+};
+use crate::traits::equivalent::Equivalent;
+use crate::traits::heap_size::{entries_heap_bytes, HeapSize};
+use crate::traits::len::Len;
+use crate::traits::map::Map;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// A map that scans the integer codes of its keys upon lookup, designed for very small
+/// payloads of integer keys.
+///
+/// Unlike [`ScanningMap`](crate::specialized_maps::ScanningMap), the codes are kept in their
+/// own contiguous array and compared without branching, which the compiler turns into a few
+/// vector instructions, and only the matching entry is touched afterwards.
+///
+/// As with [`IntegerMap`](crate::specialized_maps::IntegerMap), any key whose [`Hash`]
+/// implementation writes a single integer is treated as that integer.
+#[derive(Clone)]
+pub struct IntegerScanningMap<K, V> {
+    /// The integer code of the key of each entry.
+    codes: Box<[u64]>,
+
+    pub(crate) entries: Box<[(K, V)]>,
+}
+
+impl<K, V> IntegerScanningMap<K, V>
+where
+    K: Hash + Eq,
+{
+    #[must_use]
+    pub fn from_vec(payload: Vec<(K, V)>) -> Self {
+        Self {
+            codes: payload.iter().map(|x| int_code(&x.0)).collect(),
+            entries: payload.into_boxed_slice(),
+        }
+    }
+
+    /// Creates a map, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys.
+    pub fn try_from_vec(payload: Vec<(K, V)>) -> std::result::Result<Self, FrozenError> {
+        if let Some(group) = find_duplicate_keys(&payload, int_code).first() {
+            return Err(FrozenError::DuplicateKey { index: group[1] });
+        }
+
+        Ok(Self::from_vec(payload))
+    }
+}
+
+impl<K, V> IntegerScanningMap<K, V> {
+    /// Returns the index of the entry holding the key.
+    #[inline]
+    fn find<Q>(&self, key: &Q) -> Option<usize>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let code = int_code(key);
+
+        // visit every code rather than stopping at a match, so the loop has no branch
+        let mut found = usize::MAX;
+        for (index, &c) in self.codes.iter().enumerate() {
+            found = if c == code { index } else { found };
+        }
+
+        let entry = self.entries.get(found)?;
+        if key.equivalent(&entry.0) {
+            return Some(found);
+        }
+
+        // only keys wider than 64 bits can share a code without being equal
+        self.entries
+            .iter()
+            .position(|entry| key.equivalent(&entry.0))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let index = self.find(key)?;
+        Some(&self.entries[index].1)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let index = self.find(key)?;
+        Some(&mut self.entries[index].1)
+    }
+
+    /// Returns references to the values corresponding to several keys at once.
+    #[inline]
+    #[must_use]
+    pub fn get_many<Q, const N: usize>(&self, keys: [&Q; N]) -> [Option<&V>; N]
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        keys.map(|key| self.get(key))
+    }
+
+    /// Returns mutable references to the values of several keys at once.
+    ///
+    /// Returns `None` if any key is missing, or if two keys match the same entry.
+    pub fn get_many_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> Option<[&mut V; N]>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let indices = keys.map(|key| self.find(key));
+        disjoint_values_mut(&mut self.entries, indices)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let index = self.find(key)?;
+        Some((&self.entries[index].0, &self.entries[index].1))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.find(key).is_some()
+    }
+
+    #[must_use]
+    pub const fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(&self.entries)
+    }
+
+    #[must_use]
+    pub const fn keys(&self) -> Keys<'_, K, V> {
+        Keys::new(&self.entries)
+    }
+
+    #[must_use]
+    pub const fn values(&self) -> Values<'_, K, V> {
+        Values::new(&self.entries)
+    }
+
+    #[must_use]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut::new(&mut self.entries)
+    }
+
+    #[must_use]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut::new(&mut self.entries)
+    }
+
+    #[must_use]
+    pub fn into_keys(self) -> IntoKeys<K, V> {
+        IntoKeys::new(self.entries)
+    }
+
+    #[must_use]
+    pub fn into_values(self) -> IntoValues<K, V> {
+        IntoValues::new(self.entries)
+    }
+
+    pub(crate) fn map_values<V2, F>(self, f: F) -> IntegerScanningMap<K, V2>
+    where
+        F: FnMut(V) -> V2,
+    {
+        IntegerScanningMap {
+            codes: self.codes,
+            entries: map_entries(self.entries, f),
+        }
+    }
+}
+
+impl<K, V> IntegerScanningMap<K, V> {
+    /// Describes the layout of the map.
+    #[must_use]
+    pub fn stats(&self) -> MapStats {
+        MapStats::scanning::<K, V>(self.entries.len(), size_of_val(&*self.codes))
+    }
+}
+
+impl<K, V> IntegerScanningMap<K, V> {
+    /// Creates an empty map without allocating.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            codes: Box::default(),
+            entries: Box::default(),
+        }
+    }
+}
+
+impl<K, V> Default for IntegerScanningMap<K, V> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<K, V> Len for IntegerScanningMap<K, V> {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl<K, V> HeapSize for IntegerScanningMap<K, V>
+where
+    K: HeapSize,
+    V: HeapSize,
+{
+    fn heap_bytes(&self) -> usize {
+        self.stats().heap_bytes() + entries_heap_bytes(self.iter())
+    }
+}
+
+impl<K, V> Map<K, V> for IntegerScanningMap<K, V>
+where
+    K: Hash + Eq,
+{
+    type Iterator<'a>
+        = Iter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    type KeyIterator<'a>
+        = Keys<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    type ValueIterator<'a>
+        = Values<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    fn iter(&self) -> Iter<'_, K, V> {
+        self.iter()
+    }
+
+    fn keys(&self) -> Keys<'_, K, V> {
+        self.keys()
+    }
+
+    fn values(&self) -> Values<'_, K, V> {
+        self.values()
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+}
+
+impl<K, V> Debug for IntegerScanningMap<K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let pairs = self.entries.iter().map(|x| (&x.0, &x.1));
+        f.debug_map().entries(pairs).finish()
+    }
+}
+
+impl<Q, K, V> Index<Q> for IntegerScanningMap<K, V>
+where
+    Q: Hash + Equivalent<K>,
+{
+    type Output = V;
+
+    fn index(&self, index: Q) -> &Self::Output {
+        self.get(&index).unwrap()
+    }
+}
+
+impl<Q, K, V> IndexMut<Q> for IntegerScanningMap<K, V>
+where
+    Q: Hash + Equivalent<K>,
+{
+    fn index_mut(&mut self, index: Q) -> &mut V {
+        self.get_mut(&index).unwrap()
+    }
+}
+
+impl<K, V> IntoIterator for IntegerScanningMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.entries)
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a IntegerScanningMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut IntegerScanningMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K, V> PartialEq<Self> for IntegerScanningMap<K, V>
+where
+    K: Hash + Eq,
+    V: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+
+        self.iter()
+            .all(|(key, value)| other.get(key).is_some_and(|v| *value == *v))
+    }
+}
+
+impl<K, V> Eq for IntegerScanningMap<K, V>
+where
+    K: Hash + Eq,
+    V: Eq,
+{
+}
+
+impl<K, V> Hash for IntegerScanningMap<K, V>
+where
+    K: Hash,
+    V: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self, state);
+    }
+}
+
+impl<K, V, const N: usize> From<[(K, V); N]> for IntegerScanningMap<K, V>
+where
+    K: Hash + Eq,
+{
+    fn from(payload: [(K, V); N]) -> Self {
+        Self::from_vec(Vec::from_iter(payload))
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for IntegerScanningMap<K, V>
+where
+    K: Hash + Eq,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self::from_vec(Vec::from_iter(iter))
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<K, V> Zeroize for IntegerScanningMap<K, V>
+where
+    K: Zeroize,
+    V: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.entries.zeroize();
+        self.codes.zeroize();
+        *self = Self::empty();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::errors::FrozenError;
+    use crate::traits::len::Len;
+
+    use super::{int_code, IntegerScanningMap};
+
+    #[test]
+    fn integer_scanning_map_test() {
+        for len in [0, 1, 3, 7, 16, 17] {
+            let v: Vec<_> = (0..len).map(|i| (i * 7 - 20, i)).collect();
+            let count = v.len();
+            let mut m = IntegerScanningMap::<i32, i32>::from_vec(v);
+
+            assert_eq!(count, m.len());
+            for i in 0..len {
+                let key = i * 7 - 20;
+                assert_eq!(i, *m.get(&key).unwrap());
+                assert_eq!(i, *m.get_mut(&key).unwrap());
+                assert_eq!((&key, &i), m.get_key_value(&key).unwrap());
+                assert_eq!(None, m.get(&(key + 1)));
+            }
+
+            assert_eq!(None, m.get(&-27));
+            assert_eq!(None, m.get(&i32::MAX));
+        }
+
+        let m = IntegerScanningMap::from_vec(vec![(1u8, 'a'), (2, 'b')]);
+        assert_eq!("{1: 'a', 2: 'b'}", format!("{m:?}"));
+    }
+
+    #[test]
+    fn wide_keys_sharing_codes() {
+        // folding the high half of the second key into its low half produces a code of 1
+        let wide = (1u128 << 64) | 0x9e37_79b9_7f4a_7c14;
+        assert_eq!(int_code(&1u128), int_code(&wide));
+
+        let m = IntegerScanningMap::from_vec(vec![(1u128, 1), (wide, 2)]);
+        assert_eq!(Some(&1), m.get(&1));
+        assert_eq!(Some(&2), m.get(&wide));
+        assert_eq!(None, m.get(&((2u128 << 64) | 1)));
+    }
+
+    #[test]
+    fn try_from_vec_reports_duplicates() {
+        assert_eq!(
+            Some(FrozenError::DuplicateKey { index: 2 }),
+            IntegerScanningMap::try_from_vec(vec![(1, 1), (3, 2), (1, 3)]).err()
+        );
+        assert_eq!(
+            2,
+            IntegerScanningMap::try_from_vec(vec![(1, 1), (3, 2)])
+                .unwrap()
+                .len()
+        );
+    }
+
+    #[test]
+    fn empty_map_matches_no_key() {
+        for m in [
+            IntegerScanningMap::<i32, i32>::empty(),
+            IntegerScanningMap::default(),
+            IntegerScanningMap::from_vec(Vec::new()),
+        ] {
+            assert_eq!(0, m.len());
+            assert_eq!(None, m.get(&0));
+        }
+    }
+
+    #[test]
+    fn get_many_mut_returns_distinct_values() {
+        let mut m = IntegerScanningMap::from_vec(vec![(7, 70), (300, 3), (-5, 5)]);
+        let [a, b] = m.get_many_mut([&-5, &7]).unwrap();
+        *a += 1;
+        *b += 1;
+        assert_eq!(Some(&6), m.get(&-5));
+        assert_eq!(Some(&71), m.get(&7));
+        assert!(m.get_many_mut([&300, &300]).is_none());
+        assert!(m.get_many_mut([&300, &8]).is_none());
+    }
+}
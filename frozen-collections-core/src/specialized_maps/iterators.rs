@@ -5,12 +5,74 @@ use std::iter::FusedIterator;
 pub struct Iter<'a, K, V> {
     entries: &'a [(K, V)],
     index: usize,
+    end: usize,
 }
 
 impl<'a, K, V> Iter<'a, K, V> {
     #[must_use]
     pub const fn new(entries: &'a [(K, V)]) -> Self {
-        Self { entries, index: 0 }
+        Self {
+            entries,
+            index: 0,
+            end: entries.len(),
+        }
+    }
+
+    pub(crate) const fn entries(&self) -> &'a [(K, V)] {
+        self.entries
+    }
+
+    /// Creates an iterator which starts at the given position, or is exhausted if the position
+    /// is past the end of the entries.
+    #[must_use]
+    pub const fn at(entries: &'a [(K, V)], cursor: Cursor) -> Self {
+        let index = if cursor.0 < entries.len() {
+            cursor.0
+        } else {
+            entries.len()
+        };
+
+        Self {
+            entries,
+            index,
+            end: entries.len(),
+        }
+    }
+
+    /// Returns the entries not yet produced from either end.
+    fn remaining(&self) -> &'a [(K, V)] {
+        &self.entries[self.index..self.end]
+    }
+
+    /// Returns the position of the next entry this iterator will produce.
+    ///
+    /// Iteration can later be resumed from that point with the `iter_at` method of the
+    /// map the iterator came from.
+    #[must_use]
+    pub const fn cursor(&self) -> Cursor {
+        Cursor(self.index)
+    }
+}
+
+/// A position within the iteration order of a map.
+///
+/// Cursors are plain indices, so they can be stored or sent elsewhere, for example as a
+/// pagination token, and later used to resume iterating. A cursor is only meaningful for the
+/// map which produced it, as different maps can order the same entries differently.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cursor(usize);
+
+impl Cursor {
+    /// Creates a cursor from a position previously obtained with [`Cursor::position`].
+    #[must_use]
+    pub const fn new(position: usize) -> Self {
+        Self(position)
+    }
+
+    /// Returns the position represented by this cursor.
+    #[must_use]
+    pub const fn position(self) -> usize {
+        self.0
     }
 }
 
@@ -19,6 +81,7 @@ impl<'a, K, V> Clone for Iter<'a, K, V> {
         Self {
             entries: self.entries,
             index: self.index,
+            end: self.end,
         }
     }
 }
@@ -27,7 +90,7 @@ impl<'a, K, V> Iterator for Iter<'a, K, V> {
     type Item = (&'a K, &'a V);
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.index < self.entries.len() {
+        if self.index < self.end {
             let entry = &self.entries[self.index];
             self.index += 1;
             Some((&entry.0, &entry.1))
@@ -46,11 +109,57 @@ impl<'a, K, V> Iterator for Iter<'a, K, V> {
     {
         self.len()
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.index = self.index.saturating_add(n).min(self.end);
+        self.next()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.remaining()
+            .iter()
+            .fold(init, |acc, entry| f(acc, (&entry.0, &entry.1)))
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Iter<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index < self.end {
+            self.end -= 1;
+            let entry = &self.entries[self.end];
+            Some((&entry.0, &entry.1))
+        } else {
+            None
+        }
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.end = self.end.saturating_sub(n).max(self.index);
+        self.next_back()
+    }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.remaining()
+            .iter()
+            .rfold(init, |acc, entry| f(acc, (&entry.0, &entry.1)))
+    }
 }
 
 impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {
     fn len(&self) -> usize {
-        self.entries.len() - self.index
+        self.end - self.index
     }
 }
 
@@ -110,6 +219,32 @@ impl<'a, K, V> Iterator for Keys<'a, K, V> {
     {
         self.inner.fold(init, |acc, (k, _)| f(acc, k))
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner.nth(n).map(|x| x.0)
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Keys<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|x| x.0)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner.nth_back(n).map(|x| x.0)
+    }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.inner.rfold(init, |acc, (k, _)| f(acc, k))
+    }
 }
 
 impl<'a, K, V> ExactSizeIterator for Keys<'a, K, V> {
@@ -174,6 +309,32 @@ impl<'a, K, V> Iterator for Values<'a, K, V> {
     {
         self.inner.fold(init, |acc, (_, v)| f(acc, v))
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner.nth(n).map(|x| x.1)
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Values<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|x| x.1)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner.nth_back(n).map(|x| x.1)
+    }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.inner.rfold(init, |acc, (_, v)| f(acc, v))
+    }
 }
 
 impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> {
@@ -193,3 +354,415 @@ where
         f.debug_list().entries(self.clone()).finish()
     }
 }
+
+/// An owning iterator over the entries of a map.
+pub struct IntoIter<K, V> {
+    iter: std::vec::IntoIter<(K, V)>,
+}
+
+impl<K, V> IntoIter<K, V> {
+    pub(crate) fn new(entries: Box<[(K, V)]>) -> Self {
+        Self {
+            iter: entries.into_vec().into_iter(),
+        }
+    }
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth(n)
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    fn fold<B, F>(self, init: B, f: F) -> B
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.iter.fold(init, f)
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IntoIter<K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth_back(n)
+    }
+
+    fn rfold<B, F>(self, init: B, f: F) -> B
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.iter.rfold(init, f)
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<K, V> FusedIterator for IntoIter<K, V> {}
+
+impl<K, V> Debug for IntoIter<K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list()
+            .entries(self.iter.as_slice().iter().map(|x| (&x.0, &x.1)))
+            .finish()
+    }
+}
+
+/// An owning iterator over the keys of a map.
+pub struct IntoKeys<K, V> {
+    inner: IntoIter<K, V>,
+}
+
+impl<K, V> IntoKeys<K, V> {
+    pub(crate) fn new(entries: Box<[(K, V)]>) -> Self {
+        Self {
+            inner: IntoIter::new(entries),
+        }
+    }
+}
+
+impl<K, V> Iterator for IntoKeys<K, V> {
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|x| x.0)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.inner.count()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner.nth(n).map(|x| x.0)
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.inner.fold(init, |acc, (k, _)| f(acc, k))
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IntoKeys<K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|x| x.0)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner.nth_back(n).map(|x| x.0)
+    }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.inner.rfold(init, |acc, (k, _)| f(acc, k))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoKeys<K, V> {
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<K, V> FusedIterator for IntoKeys<K, V> {}
+
+impl<K, V> Debug for IntoKeys<K, V>
+where
+    K: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list()
+            .entries(self.inner.iter.as_slice().iter().map(|x| &x.0))
+            .finish()
+    }
+}
+
+/// An owning iterator over the values of a map.
+pub struct IntoValues<K, V> {
+    inner: IntoIter<K, V>,
+}
+
+impl<K, V> IntoValues<K, V> {
+    pub(crate) fn new(entries: Box<[(K, V)]>) -> Self {
+        Self {
+            inner: IntoIter::new(entries),
+        }
+    }
+}
+
+impl<K, V> Iterator for IntoValues<K, V> {
+    type Item = V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|x| x.1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.inner.count()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner.nth(n).map(|x| x.1)
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.inner.fold(init, |acc, (_, v)| f(acc, v))
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IntoValues<K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|x| x.1)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner.nth_back(n).map(|x| x.1)
+    }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.inner.rfold(init, |acc, (_, v)| f(acc, v))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoValues<K, V> {
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<K, V> FusedIterator for IntoValues<K, V> {}
+
+impl<K, V> Debug for IntoValues<K, V>
+where
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list()
+            .entries(self.inner.iter.as_slice().iter().map(|x| &x.1))
+            .finish()
+    }
+}
+
+/// A mutable iterator over the entries of a map.
+pub struct IterMut<'a, K, V> {
+    iter: std::slice::IterMut<'a, (K, V)>,
+}
+
+impl<'a, K, V> IterMut<'a, K, V> {
+    pub(crate) fn new(entries: &'a mut [(K, V)]) -> Self {
+        Self {
+            iter: entries.iter_mut(),
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|x| (&x.0, &mut x.1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth(n).map(|x| (&x.0, &mut x.1))
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.iter.fold(init, |acc, x| f(acc, (&x.0, &mut x.1)))
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IterMut<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|x| (&x.0, &mut x.1))
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth_back(n).map(|x| (&x.0, &mut x.1))
+    }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.iter.rfold(init, |acc, x| f(acc, (&x.0, &mut x.1)))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IterMut<'_, K, V> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<K, V> FusedIterator for IterMut<'_, K, V> {}
+
+impl<K, V> Debug for IterMut<'_, K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list()
+            .entries(self.iter.as_slice().iter().map(|x| (&x.0, &x.1)))
+            .finish()
+    }
+}
+
+/// A mutable iterator over the values of a map.
+pub struct ValuesMut<'a, K, V> {
+    inner: IterMut<'a, K, V>,
+}
+
+impl<'a, K, V> ValuesMut<'a, K, V> {
+    pub(crate) fn new(entries: &'a mut [(K, V)]) -> Self {
+        Self {
+            inner: IterMut::new(entries),
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for ValuesMut<'a, K, V> {
+    type Item = &'a mut V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|x| x.1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.inner.count()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner.nth(n).map(|x| x.1)
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.inner.fold(init, |acc, (_, v)| f(acc, v))
+    }
+}
+
+impl<K, V> DoubleEndedIterator for ValuesMut<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|x| x.1)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner.nth_back(n).map(|x| x.1)
+    }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.inner.rfold(init, |acc, (_, v)| f(acc, v))
+    }
+}
+
+impl<K, V> ExactSizeIterator for ValuesMut<'_, K, V> {
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<K, V> FusedIterator for ValuesMut<'_, K, V> {}
+
+impl<K, V> Debug for ValuesMut<'_, K, V>
+where
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list()
+            .entries(self.inner.iter.as_slice().iter().map(|x| &x.1))
+            .finish()
+    }
+}
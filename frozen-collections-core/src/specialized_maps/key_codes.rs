@@ -0,0 +1,416 @@
+//! Hash codes derived from what keys feed into a [`Hasher`].
+//!
+//! A key's [`Hash`] implementation describes the key as a sequence of integers and runs of
+//! bytes. Rather than requiring keys to be primitive integers or strings, the integer and
+//! string specialized maps compute their hash codes from that sequence, which lets them work
+//! with any key whose implementation has the right shape: newtypes and fieldless enums write
+//! a single integer, while `str`, `String`, `[u8]` and friends write a single run of bytes.
+//!
+//! Since a key and any borrowed form of it, or any type [`Equivalent`] to it, must hash
+//! identically, looking a key up through one of them produces the same codes as the key itself.
+//!
+//! [`Equivalent`]: crate::traits::equivalent::Equivalent
+
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::ops::Range;
+
+/// Returns the integer a key writes to a hasher, for keys which write a single integer.
+///
+/// Signed integers are sign-extended, so the code matches what casting the key to a `u64`
+/// produces. 128-bit integers which don't fit in 64 bits have their high half folded into their
+/// low half instead, so keys which only differ in their high half, such as UUIDs or digests
+/// sharing a suffix, still get distinct codes. Keys which write anything else get a code which
+/// mixes all of the data they write.
+#[inline]
+pub fn int_code<Q>(key: &Q) -> u64
+where
+    Q: ?Sized + Hash,
+{
+    let mut h = IntHasher::default();
+    key.hash(&mut h);
+    h.code
+}
+
+/// Returns the code of a key which writes a single integer to a hasher, or `None` for keys
+/// which write anything else.
+pub fn probe_int<Q>(key: &Q) -> Option<u64>
+where
+    Q: ?Sized + Hash,
+{
+    let mut h = IntHasher::default();
+    key.hash(&mut h);
+
+    if h.ints == 1 && h.runs == 0 {
+        Some(h.code)
+    } else {
+        None
+    }
+}
+
+/// Appends the first run of bytes a key writes to a hasher to `out`.
+///
+/// Returns `false` when the key doesn't write any bytes.
+pub fn capture_bytes<Q>(key: &Q, out: &mut Vec<u8>) -> bool
+where
+    Q: ?Sized + Hash,
+{
+    let mut captured = false;
+    bytes_code(key, |bytes| {
+        out.extend_from_slice(bytes);
+        captured = true;
+        0
+    });
+
+    captured
+}
+
+/// Hashes a left-aligned subslice of the first run of bytes a key writes to a hasher.
+///
+/// Keys too short to contain the subslice get a code of zero.
+#[inline]
+pub fn left_slice_code<Q, BH>(key: &Q, bh: &BH, range: &Range<usize>) -> u64
+where
+    Q: ?Sized + Hash,
+    BH: BuildHasher,
+{
+    bytes_code(key, |bytes| {
+        bytes
+            .get(range.clone())
+            .map_or(0, |sub| hash_bytes(bh, sub))
+    })
+}
+
+/// Hashes a right-aligned subslice of the first run of bytes a key writes to a hasher.
+///
+/// The range is measured from the end of the bytes. Keys too short to contain the subslice
+/// get a code of zero.
+#[inline]
+pub fn right_slice_code<Q, BH>(key: &Q, bh: &BH, range: &Range<usize>) -> u64
+where
+    Q: ?Sized + Hash,
+    BH: BuildHasher,
+{
+    bytes_code(key, |bytes| {
+        let len = bytes.len();
+        if len >= range.end {
+            hash_bytes(bh, &bytes[len - range.end..len - range.start])
+        } else {
+            0
+        }
+    })
+}
+
+/// Hashes a left-aligned subslice together with a right-aligned subslice of the first run of
+/// bytes a key writes to a hasher.
+///
+/// The right range is measured from the end of the bytes. Keys too short to contain both
+/// subslices get a code of zero.
+#[inline]
+pub fn split_slice_code<Q, BH>(key: &Q, bh: &BH, left: &Range<usize>, right: &Range<usize>) -> u64
+where
+    Q: ?Sized + Hash,
+    BH: BuildHasher,
+{
+    bytes_code(key, |bytes| {
+        let len = bytes.len();
+        if len >= left.end && len >= right.end {
+            combine_codes(
+                hash_bytes(bh, &bytes[left.clone()]),
+                hash_bytes(bh, &bytes[len - right.end..len - right.start]),
+            )
+        } else {
+            0
+        }
+    })
+}
+
+/// Hashes a left-aligned subslice of the first run of bytes a key writes to a hasher, together
+/// with the length of the bytes.
+///
+/// Keys too short to contain the subslice are told apart by their length alone.
+#[inline]
+pub fn length_slice_code<Q, BH>(key: &Q, bh: &BH, range: &Range<usize>) -> u64
+where
+    Q: ?Sized + Hash,
+    BH: BuildHasher,
+{
+    bytes_code(key, |bytes| {
+        let code = bytes
+            .get(range.clone())
+            .map_or(0, |sub| hash_bytes(bh, sub));
+        combine_codes(code, bytes.len() as u64)
+    })
+}
+
+/// Combines the hash codes of two parts of a key into a single code.
+#[inline]
+#[must_use]
+pub const fn combine_codes(left: u64, right: u64) -> u64 {
+    left.rotate_left(32) ^ right
+}
+
+/// Returns the length of the first run of bytes a key writes to a hasher.
+#[inline]
+pub fn length_code<Q>(key: &Q) -> u64
+where
+    Q: ?Sized + Hash,
+{
+    bytes_code(key, |bytes| bytes.len() as u64)
+}
+
+fn hash_bytes<BH: BuildHasher>(bh: &BH, bytes: &[u8]) -> u64 {
+    let mut h = bh.build_hasher();
+    h.write(bytes);
+    h.finish()
+}
+
+fn bytes_code<Q, F>(key: &Q, f: F) -> u64
+where
+    Q: ?Sized + Hash,
+    F: FnMut(&[u8]) -> u64,
+{
+    let mut h = BytesHasher { code: None, f };
+    key.hash(&mut h);
+    h.code.unwrap_or(0)
+}
+
+/// Mixes the high half of 128-bit integers into their codes.
+const FOLD_MULTIPLIER: u64 = 0x9e_37_79_b9_7f_4a_7c_15;
+
+/// Folds the high half of a 128-bit integer into its low half.
+///
+/// `extension` is the high half the integer would have if it fit in its low half, which leaves
+/// the low half as the code of such integers.
+#[inline]
+#[allow(clippy::cast_possible_truncation)]
+const fn fold_halves(i: u128, extension: u64) -> u64 {
+    let low = i as u64;
+    let high = (i >> 64) as u64;
+    low ^ (high ^ extension).wrapping_mul(FOLD_MULTIPLIER)
+}
+
+/// Records the integers written to it, folding them together when there is more than one.
+#[derive(Default)]
+struct IntHasher {
+    code: u64,
+    ints: usize,
+    runs: usize,
+}
+
+impl IntHasher {
+    #[inline]
+    const fn push(&mut self, value: u64) {
+        self.code = if self.ints == 0 && self.runs == 0 {
+            value
+        } else {
+            // same mixing step as FxHash
+            (self.code.rotate_left(5) ^ value).wrapping_mul(0x51_7c_c1_b7_27_22_0a_95)
+        };
+
+        self.ints += 1;
+    }
+}
+
+#[allow(clippy::cast_sign_loss)]
+#[allow(clippy::cast_possible_truncation)]
+impl Hasher for IntHasher {
+    fn finish(&self) -> u64 {
+        self.code
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        let ints = self.ints;
+        for chunk in bytes.chunks(8) {
+            let mut buf = [0; 8];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            self.push(u64::from_le_bytes(buf));
+        }
+
+        self.ints = ints;
+        self.runs += 1;
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.push(u64::from(i));
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.push(u64::from(i));
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.push(u64::from(i));
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.push(i);
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.push(fold_halves(i, 0));
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.push(i as u64);
+    }
+
+    fn write_i8(&mut self, i: i8) {
+        self.push(i as u64);
+    }
+
+    fn write_i16(&mut self, i: i16) {
+        self.push(i as u64);
+    }
+
+    fn write_i32(&mut self, i: i32) {
+        self.push(i as u64);
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.push(i as u64);
+    }
+
+    fn write_i128(&mut self, i: i128) {
+        let extension = (i as i64 >> 63) as u64;
+        self.push(fold_halves(i as u128, extension));
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.push(i as u64);
+    }
+}
+
+/// Computes a code from the first run of bytes written to it, ignoring everything else.
+struct BytesHasher<F> {
+    code: Option<u64>,
+    f: F,
+}
+
+impl<F> Hasher for BytesHasher<F>
+where
+    F: FnMut(&[u8]) -> u64,
+{
+    fn finish(&self) -> u64 {
+        self.code.unwrap_or(0)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        if self.code.is_none() {
+            self.code = Some((self.f)(bytes));
+        }
+    }
+
+    // integers, such as the length prefix of slices or the terminator of strings, are
+    // deliberately left out of the codes
+
+    fn write_u8(&mut self, _: u8) {}
+    fn write_u16(&mut self, _: u16) {}
+    fn write_u32(&mut self, _: u32) {}
+    fn write_u64(&mut self, _: u64) {}
+    fn write_u128(&mut self, _: u128) {}
+    fn write_usize(&mut self, _: usize) {}
+    fn write_i8(&mut self, _: i8) {}
+    fn write_i16(&mut self, _: i16) {}
+    fn write_i32(&mut self, _: i32) {}
+    fn write_i64(&mut self, _: i64) {}
+    fn write_i128(&mut self, _: i128) {}
+    fn write_isize(&mut self, _: isize) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::RandomState;
+
+    use super::*;
+
+    #[derive(Hash)]
+    struct UserId(u32);
+
+    #[derive(Hash)]
+    enum Color {
+        Red,
+        Green,
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(Some(42), probe_int(&42u8));
+        assert_eq!(Some(u64::MAX), probe_int(&-1i32));
+        assert_eq!(Some(7), probe_int(&7u128));
+        assert_eq!(Some(u64::from('a')), probe_int(&'a'));
+        assert_eq!(Some(3), probe_int(&UserId(3)));
+        assert_eq!(Some(1), probe_int(&Color::Green));
+        assert_ne!(probe_int(&Color::Red), probe_int(&Color::Green));
+
+        assert_eq!(None, probe_int(&(1, 2)));
+        assert_eq!(None, probe_int("a"));
+        assert_eq!(None, probe_int(&()));
+
+        assert_eq!(u64::MAX - 4, int_code(&-5i64));
+        assert_eq!(u64::MAX - 4, int_code(&-5i128));
+        assert_eq!(u64::MAX, int_code(&u128::from(u64::MAX)));
+        assert_ne!(int_code(&(1u128 << 64)), int_code(&(2u128 << 64)));
+        assert_ne!(int_code(&(1u128 << 64)), int_code(&0u128));
+        assert_ne!(int_code(&(1i128 << 64)), int_code(&(-1i128 << 64)));
+        assert_ne!(int_code(&i128::MIN), int_code(&0i128));
+        assert_eq!(int_code(&(1, 2)), int_code(&(1, 2)));
+        assert_ne!(int_code(&(1, 2)), int_code(&(2, 1)));
+    }
+
+    #[test]
+    fn bytes() {
+        let mut out = Vec::new();
+        assert!(capture_bytes("abc", &mut out));
+        assert!(capture_bytes(&b"de".to_vec(), &mut out));
+        assert!(!capture_bytes(&1, &mut out));
+        assert_eq!(b"abcde", out.as_slice());
+
+        assert_eq!(3, length_code("abc"));
+        assert_eq!(3, length_code(&"abc".to_string()));
+        assert_eq!(2, length_code(&b"ab"[..]));
+    }
+
+    #[test]
+    fn slices() {
+        let bh = RandomState::new();
+        let key = "Key-123".to_string();
+
+        assert_eq!(
+            left_slice_code(&key, &bh, &(4..6)),
+            left_slice_code("Key-129", &bh, &(4..6))
+        );
+        assert_eq!(
+            right_slice_code(&key, &bh, &(0..3)),
+            right_slice_code("X-123", &bh, &(0..3))
+        );
+        assert_eq!(hash_bytes(&bh, b"12"), left_slice_code(&key, &bh, &(4..6)));
+        assert_eq!(hash_bytes(&bh, b"12"), right_slice_code(&key, &bh, &(1..3)));
+
+        assert_eq!(0, left_slice_code("Key", &bh, &(4..6)));
+        assert_eq!(0, right_slice_code("Ke", &bh, &(0..3)));
+
+        assert_eq!(
+            split_slice_code(&key, &bh, &(0..1), &(0..2)),
+            split_slice_code("K-0023", &bh, &(0..1), &(0..2))
+        );
+        assert_eq!(
+            combine_codes(hash_bytes(&bh, b"K"), hash_bytes(&bh, b"23")),
+            split_slice_code(&key, &bh, &(0..1), &(0..2))
+        );
+        assert_ne!(
+            split_slice_code(&key, &bh, &(0..1), &(0..2)),
+            split_slice_code("X-123", &bh, &(0..1), &(0..2))
+        );
+        assert_eq!(0, split_slice_code("Ke", &bh, &(0..1), &(0..3)));
+
+        assert_eq!(
+            combine_codes(hash_bytes(&bh, b"ey"), 7),
+            length_slice_code(&key, &bh, &(1..3))
+        );
+        assert_ne!(
+            length_slice_code(&key, &bh, &(1..3)),
+            length_slice_code("Key-12", &bh, &(1..3))
+        );
+        assert_eq!(combine_codes(0, 2), length_slice_code("Ke", &bh, &(1..3)));
+    }
+}
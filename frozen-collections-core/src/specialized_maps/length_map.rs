@@ -1,48 +1,129 @@
-use std::borrow::Borrow;
 use std::fmt::{Debug, Formatter, Result};
-use std::intrinsics::transmute;
-use std::mem::MaybeUninit;
+use std::hash::{Hash, Hasher};
 use std::ops::Range;
 use std::ops::{Index, IndexMut};
 
 use bitvec::macros::internal::funty::Fundamental;
-use num_traits::{PrimInt, Unsigned};
 
-use crate::analyzers::hash_code_analyzer::analyze_hash_codes;
-use crate::specialized_maps::hash_table::HashTable;
-use crate::specialized_maps::{Iter, Keys, Values};
+use crate::analyzers::hash_code_analyzer::{
+    analyze_hash_codes, analyze_hash_codes_with, HashCodeAnalysisOptions,
+};
+use crate::errors::FrozenError;
+use crate::hashers::hash_unordered;
+use crate::specialized_maps::hash_table::{disjoint_values_mut, HashTable};
+use crate::specialized_maps::key_codes::length_code;
+use crate::specialized_maps::{
+    IntoIter, IntoKeys, IntoValues, Iter, IterMut, Keys, MapStats, Values, ValuesMut,
+};
+use crate::traits::equivalent::Equivalent;
+use crate::traits::heap_size::{entries_heap_bytes, HeapSize};
 use crate::traits::len::Len;
+use crate::traits::map::Map;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
 
 /// A map that uses key lengths as hash codes, in order to avoid hashing overhead.
 #[derive(Clone)]
-pub struct LengthMap<K, V, S = u8> {
-    pub(crate) table: HashTable<K, V, S>,
+pub struct LengthMap<K, V> {
+    pub(crate) table: HashTable<K, V>,
 }
 
-impl<K, V, S> LengthMap<K, V, S>
+impl<K, V> LengthMap<K, V>
 where
     K: Len + Eq,
-    S: PrimInt + Unsigned,
 {
     #[must_use]
     pub fn from_vec(payload: Vec<(K, V)>) -> Self {
         let code_analysis = analyze_hash_codes(payload.iter().map(|entry| entry.0.len().as_u64()));
 
         Self {
-            table: HashTable::new(payload, code_analysis.num_hash_slots, |k| k.len() as u64),
+            table: HashTable::from_analysis(payload, code_analysis, false, |k| k.len() as u64),
         }
     }
+
+    /// Creates a map, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys.
+    pub fn try_from_vec(payload: Vec<(K, V)>) -> std::result::Result<Self, FrozenError> {
+        let code_analysis = analyze_hash_codes(payload.iter().map(|entry| entry.0.len().as_u64()));
+
+        Ok(Self {
+            table: HashTable::try_from_analysis(payload, code_analysis, |k| k.len() as u64)?,
+        })
+    }
 }
 
-impl<K, V, S> LengthMap<K, V, S>
+impl<K, V> LengthMap<K, V>
 where
-    S: PrimInt + Unsigned,
+    K: Hash + Eq,
 {
+    /// Creates a map which uses the length of the bytes keys write to a
+    /// [`Hasher`](std::hash::Hasher) as hash codes, instead of going through [`Len`].
+    ///
+    /// The map must then be probed with [`Self::find`], which computes hash codes the same
+    /// way. For `String` keys, both approaches produce the same hash codes. The hash table is
+    /// sized within the given limits.
+    pub(crate) fn from_hashed_vec(payload: Vec<(K, V)>, sizing: &HashCodeAnalysisOptions) -> Self {
+        let codes = payload.iter().map(|entry| length_code(&entry.0));
+        let code_analysis = analyze_hash_codes_with(codes, sizing);
+
+        Self {
+            table: HashTable::from_analysis(payload, code_analysis, sizing.prefilter, length_code),
+        }
+    }
+
+    /// Finds the entry holding a key, in a map created with [`Self::from_hashed_vec`].
+    #[inline]
+    pub(crate) fn find<Q>(&self, key: &Q) -> Option<&(K, V)>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.table.find(length_code(key), key)
+    }
+
+    /// Finds the entries holding several keys, in a map created with [`Self::from_hashed_vec`].
+    #[inline]
+    pub(crate) fn find_many<Q, const N: usize>(&self, keys: [&Q; N]) -> [Option<&(K, V)>; N]
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let hash_codes = keys.map(|key| length_code(key));
+        self.table.find_many(hash_codes, keys)
+    }
+
+    /// Locates the entries which may hold a key and starts loading them into the cache, in a
+    /// map created with [`Self::from_hashed_vec`].
+    ///
+    /// Returns the key's hash code along with the entries, which together finish the lookup
+    /// through [`HashTable::find_located`].
+    #[inline]
+    pub(crate) fn locate<Q>(&self, key: &Q) -> (u64, Range<usize>)
+    where
+        Q: ?Sized + Hash,
+    {
+        let hash_code = length_code(key);
+        (hash_code, self.table.locate(hash_code))
+    }
+
+    /// Finds the entry holding a key, in a map created with [`Self::from_hashed_vec`].
+    #[inline]
+    pub(crate) fn find_mut<Q>(&mut self, key: &Q) -> Option<&mut (K, V)>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.table.find_mut(length_code(key), key)
+    }
+}
+
+impl<K, V> LengthMap<K, V> {
     #[inline]
     #[must_use]
     fn get_hash_info<Q>(&self, key: &Q) -> Range<usize>
     where
-        Q: Len,
+        Q: Len + ?Sized,
     {
         let hash_code = key.len().as_u64();
         self.table.get_hash_info(hash_code)
@@ -52,13 +133,12 @@ where
     #[must_use]
     pub fn get<Q>(&self, key: &Q) -> Option<&V>
     where
-        K: Borrow<Q>,
-        Q: Len + Eq,
+        Q: Len + Equivalent<K> + ?Sized,
     {
         let range = self.get_hash_info(key);
         let entries = unsafe { self.table.entries.get_unchecked(range) };
         for entry in entries {
-            if key.eq(entry.0.borrow()) {
+            if key.equivalent(&entry.0) {
                 return Some(&entry.1);
             }
         }
@@ -70,13 +150,12 @@ where
     #[must_use]
     pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
     where
-        K: Borrow<Q>,
-        Q: Len + Eq,
+        Q: Len + Equivalent<K> + ?Sized,
     {
         let range = self.get_hash_info(key);
         let entries = unsafe { self.table.entries.get_unchecked(range) };
         for entry in entries {
-            if key.eq(entry.0.borrow()) {
+            if key.equivalent(&entry.0) {
                 return Some((&entry.0, &entry.1));
             }
         }
@@ -88,13 +167,12 @@ where
     #[must_use]
     pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
     where
-        K: Borrow<Q>,
-        Q: Len + Eq,
+        Q: Len + Equivalent<K> + ?Sized,
     {
         let range = self.get_hash_info(key);
         let entries = unsafe { self.table.entries.get_unchecked_mut(range) };
         for entry in entries {
-            if key.eq(entry.0.borrow()) {
+            if key.equivalent(&entry.0) {
                 return Some(&mut entry.1);
             }
         }
@@ -102,45 +180,44 @@ where
         None
     }
 
-    #[allow(mutable_transmutes)]
-    pub fn get_many_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> Option<[&mut V; N]>
+    /// Returns references to the values corresponding to several keys at once.
+    ///
+    /// The hash codes of all the keys are computed first and their slots prefetched, so the
+    /// memory latency of the lookups overlaps.
+    #[inline]
+    #[must_use]
+    pub fn get_many<Q, const N: usize>(&self, keys: [&Q; N]) -> [Option<&V>; N]
     where
-        K: Borrow<Q>,
-        Q: Len + Eq,
+        Q: Len + Equivalent<K> + ?Sized,
     {
-        // ensure key uniqueness (assumes "keys" is a relatively small array)
-        for i in 0..keys.len() {
-            for j in 0..i {
-                if keys[j].eq(keys[i]) {
-                    return None;
-                }
-            }
-        }
-
-        unsafe {
-            let mut result: MaybeUninit<[&mut V; N]> = MaybeUninit::uninit();
-            let p = result.as_mut_ptr();
-
-            for (i, key) in keys.iter().enumerate() {
-                *(*p).get_unchecked_mut(i) = transmute(self.get(key)?);
-            }
+        let hash_codes = keys.map(|key| key.len().as_u64());
+        self.table
+            .find_many(hash_codes, keys)
+            .map(|entry| entry.map(|(_, v)| v))
+    }
 
-            Some(result.assume_init())
-        }
+    /// Returns mutable references to the values of several keys at once.
+    ///
+    /// Returns `None` if any key is missing, or if two keys match the same entry.
+    pub fn get_many_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> Option<[&mut V; N]>
+    where
+        Q: Len + Equivalent<K> + ?Sized,
+    {
+        let indices = keys.map(|key| self.table.find_index_in(self.get_hash_info(key), key));
+        disjoint_values_mut(&mut self.table.entries, indices)
     }
 
     #[inline]
     #[must_use]
     pub fn contains_key<Q>(&self, key: &Q) -> bool
     where
-        K: Borrow<Q>,
-        Q: Len + Eq,
+        Q: Len + Equivalent<K> + ?Sized,
     {
         self.get(key).is_some()
     }
 }
 
-impl<K, V, S> LengthMap<K, V, S> {
+impl<K, V> LengthMap<K, V> {
     #[must_use]
     pub const fn iter(&self) -> Iter<K, V> {
         Iter::new(&self.table.entries)
@@ -155,15 +232,121 @@ impl<K, V, S> LengthMap<K, V, S> {
     pub const fn values(&self) -> Values<K, V> {
         Values::new(&self.table.entries)
     }
+
+    #[must_use]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut::new(&mut self.table.entries)
+    }
+
+    #[must_use]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut::new(&mut self.table.entries)
+    }
+
+    #[must_use]
+    pub fn into_keys(self) -> IntoKeys<K, V> {
+        IntoKeys::new(self.table.entries)
+    }
+
+    #[must_use]
+    pub fn into_values(self) -> IntoValues<K, V> {
+        IntoValues::new(self.table.entries)
+    }
+
+    pub(crate) fn map_values<V2, F>(self, f: F) -> LengthMap<K, V2>
+    where
+        F: FnMut(V) -> V2,
+    {
+        LengthMap {
+            table: self.table.map_values(f),
+        }
+    }
+}
+
+impl<K, V> LengthMap<K, V> {
+    /// Describes the layout of the map.
+    #[must_use]
+    pub fn stats(&self) -> MapStats {
+        self.table.stats()
+    }
 }
 
-impl<K, V, S> Len for LengthMap<K, V, S> {
+impl<K, V> LengthMap<K, V> {
+    /// Creates an empty map without allocating.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            table: HashTable::empty(),
+        }
+    }
+}
+
+impl<K, V> Default for LengthMap<K, V> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<K, V> Len for LengthMap<K, V> {
     fn len(&self) -> usize {
         self.table.len()
     }
 }
 
-impl<K, V, S> Debug for LengthMap<K, V, S>
+impl<K, V> HeapSize for LengthMap<K, V>
+where
+    K: HeapSize,
+    V: HeapSize,
+{
+    fn heap_bytes(&self) -> usize {
+        self.stats().heap_bytes() + entries_heap_bytes(self.iter())
+    }
+}
+
+impl<K, V> Map<K, V> for LengthMap<K, V>
+where
+    K: Len + Eq,
+{
+    type Iterator<'a>
+        = Iter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    type KeyIterator<'a>
+        = Keys<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    type ValueIterator<'a>
+        = Values<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    fn iter(&self) -> Iter<'_, K, V> {
+        self.iter()
+    }
+
+    fn keys(&self) -> Keys<'_, K, V> {
+        self.keys()
+    }
+
+    fn values(&self) -> Values<'_, K, V> {
+        self.values()
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+}
+
+impl<K, V> Debug for LengthMap<K, V>
 where
     K: Debug,
     V: Debug,
@@ -173,11 +356,9 @@ where
     }
 }
 
-impl<Q, K, V, S> Index<Q> for LengthMap<K, V, S>
+impl<Q, K, V> Index<Q> for LengthMap<K, V>
 where
-    K: Borrow<Q>,
-    Q: Len + Eq,
-    S: PrimInt + Unsigned,
+    Q: Len + Equivalent<K>,
 {
     type Output = V;
 
@@ -186,18 +367,25 @@ where
     }
 }
 
-impl<Q, K, V, S> IndexMut<Q> for LengthMap<K, V, S>
+impl<Q, K, V> IndexMut<Q> for LengthMap<K, V>
 where
-    K: Borrow<Q>,
-    Q: Len + Eq,
-    S: PrimInt + Unsigned,
+    Q: Len + Equivalent<K>,
 {
     fn index_mut(&mut self, index: Q) -> &mut V {
         self.get_mut(&index).unwrap()
     }
 }
 
-impl<'a, K, V, S> IntoIterator for &'a LengthMap<K, V, S> {
+impl<K, V> IntoIterator for LengthMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.table.entries)
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a LengthMap<K, V> {
     type Item = (&'a K, &'a V);
     type IntoIter = Iter<'a, K, V>;
 
@@ -206,11 +394,19 @@ impl<'a, K, V, S> IntoIterator for &'a LengthMap<K, V, S> {
     }
 }
 
-impl<K, V, S> PartialEq<Self> for LengthMap<K, V, S>
+impl<'a, K, V> IntoIterator for &'a mut LengthMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K, V> PartialEq<Self> for LengthMap<K, V>
 where
     K: Len + Eq,
     V: PartialEq,
-    S: PrimInt + Unsigned,
 {
     fn eq(&self, other: &Self) -> bool {
         if self.len() != other.len() {
@@ -222,14 +418,23 @@ where
     }
 }
 
-impl<K, V, S> Eq for LengthMap<K, V, S>
+impl<K, V> Eq for LengthMap<K, V>
 where
     K: Len + Eq,
     V: Eq,
-    S: PrimInt + Unsigned,
 {
 }
 
+impl<K, V> Hash for LengthMap<K, V>
+where
+    K: Hash,
+    V: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self, state);
+    }
+}
+
 impl<K, V, const N: usize> From<[(K, V); N]> for LengthMap<K, V>
 where
     K: Len + Eq,
@@ -248,6 +453,17 @@ where
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl<K, V> Zeroize for LengthMap<K, V>
+where
+    K: Zeroize,
+    V: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.table.zeroize();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,88 @@
+use std::mem::size_of;
+
+/// Describes the layout of a map, to help tune key formats and keep track of memory use.
+///
+/// The byte counts cover the arrays a map allocates, so memory owned by the keys and values
+/// themselves, such as the contents of strings, isn't included.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::FrozenMap;
+///
+/// let map = FrozenMap::from_iter((0..100u64).map(|i| (i * 1000, i)));
+/// let stats = map.stats();
+///
+/// assert_eq!(stats.len, 100);
+/// assert!(stats.load_factor() <= 1.0);
+/// assert!(stats.longest_probe_chain >= 1);
+/// assert_eq!(stats.key_bytes, 800);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct MapStats {
+    /// The number of entries in the map.
+    pub len: usize,
+
+    /// The number of slots keys are distributed over, or zero for maps which scan or search
+    /// their entries instead.
+    pub num_slots: usize,
+
+    /// The largest number of entries a lookup may have to examine.
+    pub longest_probe_chain: usize,
+
+    /// The number of bytes taken by the keys.
+    pub key_bytes: usize,
+
+    /// The number of bytes taken by the values.
+    pub value_bytes: usize,
+
+    /// The number of bytes taken by slots and any other metadata used to find entries.
+    pub slot_bytes: usize,
+}
+
+impl MapStats {
+    pub(crate) const fn new<K, V>(
+        len: usize,
+        num_slots: usize,
+        longest_probe_chain: usize,
+        slot_bytes: usize,
+    ) -> Self {
+        Self {
+            len,
+            num_slots,
+            longest_probe_chain,
+            key_bytes: len * size_of::<K>(),
+            value_bytes: len * size_of::<V>(),
+            slot_bytes,
+        }
+    }
+
+    /// Describes a map which examines its entries one after the other.
+    pub(crate) const fn scanning<K, V>(len: usize, slot_bytes: usize) -> Self {
+        Self::new::<K, V>(len, 0, len, slot_bytes)
+    }
+
+    /// Describes a map which finds an entry straight from its key, through one of `num_slots`
+    /// slots.
+    pub(crate) const fn direct<K, V>(len: usize, num_slots: usize, slot_bytes: usize) -> Self {
+        Self::new::<K, V>(len, num_slots, if len == 0 { 0 } else { 1 }, slot_bytes)
+    }
+
+    /// Returns the number of entries per slot, or zero when there are no slots.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn load_factor(&self) -> f64 {
+        if self.num_slots == 0 {
+            0.0
+        } else {
+            self.len as f64 / self.num_slots as f64
+        }
+    }
+
+    /// Returns the total number of bytes taken by keys, values, and slots.
+    #[must_use]
+    pub const fn heap_bytes(&self) -> usize {
+        self.key_bytes + self.value_bytes + self.slot_bytes
+    }
+}
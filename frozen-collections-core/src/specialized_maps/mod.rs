@@ -5,20 +5,45 @@
 //! [`FrozenMap`](crate::FrozenMap) type when the items are only known at runtime.
 
 pub use common_map::CommonMap;
+pub use dense_integer_map::DenseIntegerMap;
 pub use integer_map::IntegerMap;
 pub use integer_range_map::IntegerRangeMap;
+pub use integer_scanning_map::IntegerScanningMap;
 pub use iterators::*;
 pub use left_slice_map::LeftSliceMap;
 pub use length_map::LengthMap;
+pub use length_slice_map::LengthSliceMap;
+pub use map_stats::MapStats;
+pub use ordered_map::OrderedMap;
+pub use perfect_hash_map::PerfectHashMap;
 pub use right_slice_map::RightSliceMap;
 pub use scanning_map::ScanningMap;
+pub use split_slice_map::SplitSliceMap;
+#[cfg(feature = "rkyv")]
+pub use static_map::ArchivedStaticKeyCode;
+pub use static_map::{StaticKeyCode, StaticMap};
+#[cfg(feature = "rkyv")]
+pub use zero_copy_map::{ArchivedZeroCopyMap, ZeroCopyMap};
 
 mod common_map;
-mod hash_table;
+mod dense_integer_map;
+pub(crate) mod hash_table;
 mod integer_map;
 mod integer_range_map;
+mod integer_scanning_map;
 mod iterators;
+pub(crate) mod key_codes;
 mod left_slice_map;
 mod length_map;
+mod length_slice_map;
+mod map_stats;
+mod ordered_map;
+mod perfect_hash_map;
+pub(crate) mod prefilter;
+pub mod raw;
 mod right_slice_map;
 mod scanning_map;
+mod split_slice_map;
+pub(crate) mod static_map;
+#[cfg(feature = "rkyv")]
+mod zero_copy_map;
@@ -0,0 +1,674 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::{Hash, Hasher};
+use std::ops::{Index, IndexMut, Range};
+
+use crate::errors::FrozenError;
+use crate::hashers::hash_unordered;
+use crate::specialized_maps::hash_table::{disjoint_values_mut, sort_entries_by};
+use crate::specialized_maps::{
+    Cursor, IntoIter, IntoKeys, IntoValues, Iter, IterMut, Keys, MapStats, Values, ValuesMut,
+};
+use crate::traits::equivalent::Comparable;
+use crate::traits::heap_size::{entries_heap_bytes, HeapSize};
+use crate::traits::len::Len;
+use crate::traits::map::Map;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// A map whose entries are sorted by key, and which uses binary search upon lookup.
+///
+/// Since entries are ordered, this map supports range queries such as
+/// [`keys_with_prefix`](Self::keys_with_prefix), and iterates in key order.
+#[derive(Clone)]
+pub struct OrderedMap<K, V> {
+    pub(crate) entries: Box<[(K, V)]>,
+}
+
+impl<K, V> OrderedMap<K, V>
+where
+    K: Ord,
+{
+    #[must_use]
+    pub fn from_vec(payload: Vec<(K, V)>) -> Self {
+        let mut payload = payload;
+        sort_entries_by(&mut payload, |x, y| x.0.cmp(&y.0));
+
+        Self {
+            entries: payload.into_boxed_slice(),
+        }
+    }
+
+    /// Creates a map, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys.
+    pub fn try_from_vec(payload: Vec<(K, V)>) -> std::result::Result<Self, FrozenError> {
+        // a stable sort keeps the entries holding the same key in payload order
+        let mut order: Vec<_> = (0..payload.len()).collect();
+        order.sort_by(|&x, &y| payload[x].0.cmp(&payload[y].0));
+
+        let repeated = order
+            .windows(2)
+            .filter(|pair| payload[pair[0]].0 == payload[pair[1]].0)
+            .map(|pair| pair[1])
+            .min();
+
+        if let Some(index) = repeated {
+            return Err(FrozenError::DuplicateKey { index });
+        }
+
+        Ok(Self::from_vec(payload))
+    }
+}
+
+impl<K, V> OrderedMap<K, V> {
+    #[inline]
+    fn find<Q>(&self, key: &Q) -> Option<usize>
+    where
+        Q: Comparable<K> + ?Sized,
+    {
+        self.entries
+            .binary_search_by(|entry| key.compare(&entry.0).reverse())
+            .ok()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: Comparable<K> + ?Sized,
+    {
+        let index = self.find(key)?;
+        Some(&self.entries[index].1)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        Q: Comparable<K> + ?Sized,
+    {
+        let index = self.find(key)?;
+        Some(&mut self.entries[index].1)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        Q: Comparable<K> + ?Sized,
+    {
+        let index = self.find(key)?;
+        let entry = &self.entries[index];
+        Some((&entry.0, &entry.1))
+    }
+
+    /// Returns references to the values corresponding to several keys at once.
+    #[inline]
+    #[must_use]
+    pub fn get_many<Q, const N: usize>(&self, keys: [&Q; N]) -> [Option<&V>; N]
+    where
+        Q: Comparable<K> + ?Sized,
+    {
+        keys.map(|key| self.get(key))
+    }
+
+    /// Returns mutable references to the values of several keys at once.
+    ///
+    /// Returns `None` if any key is missing, or if two keys match the same entry.
+    pub fn get_many_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> Option<[&mut V; N]>
+    where
+        Q: Comparable<K> + ?Sized,
+    {
+        let indices = keys.map(|key| self.find(key));
+        disjoint_values_mut(&mut self.entries, indices)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: Comparable<K> + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Returns `true` if the map holds every one of the keys.
+    ///
+    /// The entries are searched in step with the keys, like two sorted lists being merged, so
+    /// keys given in ascending order are each looked for only past the previous one. Keys
+    /// given in any other order are still found, with a search over all the entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::specialized_maps::OrderedMap;
+    ///
+    /// let map = OrderedMap::from_vec(vec![(10, 'a'), (20, 'b'), (30, 'c')]);
+    /// assert!(map.contains_all_keys(&[10, 30]));
+    /// assert!(!map.contains_all_keys(&[10, 25]));
+    /// ```
+    pub fn contains_all_keys<'q, Q, I>(&self, keys: I) -> bool
+    where
+        Q: Comparable<K> + ?Sized + 'q,
+        I: IntoIterator<Item = &'q Q>,
+    {
+        let mut cursor = 0;
+        keys.into_iter().all(|key| self.seek(key, &mut cursor))
+    }
+
+    /// Returns `true` if the map holds at least one of the keys.
+    ///
+    /// Like [`Self::contains_all_keys`], keys given in ascending order are looked for in a
+    /// single pass over the entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::specialized_maps::OrderedMap;
+    ///
+    /// let map = OrderedMap::from_vec(vec![(10, 'a'), (20, 'b'), (30, 'c')]);
+    /// assert!(map.contains_any_key(&[5, 20]));
+    /// assert!(!map.contains_any_key(&[5, 25]));
+    /// ```
+    pub fn contains_any_key<'q, Q, I>(&self, keys: I) -> bool
+    where
+        Q: Comparable<K> + ?Sized + 'q,
+        I: IntoIterator<Item = &'q Q>,
+    {
+        let mut cursor = 0;
+        keys.into_iter().any(|key| self.seek(key, &mut cursor))
+    }
+
+    /// Looks for a key among the entries from the cursor onward, then moves the cursor past
+    /// where the key is or would be. A key lower than the one before it restarts the search
+    /// from the first entry.
+    fn seek<Q>(&self, key: &Q, cursor: &mut usize) -> bool
+    where
+        Q: Comparable<K> + ?Sized,
+    {
+        let start = match cursor.checked_sub(1) {
+            Some(prev) if key.compare(&self.entries[prev].0) != Ordering::Greater => 0,
+            _ => *cursor,
+        };
+
+        // widen the window until its last entry isn't below the key, then search within it
+        let rest = &self.entries[start..];
+        let mut end = 1;
+        while end < rest.len() && key.compare(&rest[end - 1].0) == Ordering::Greater {
+            end *= 2;
+        }
+
+        let found = rest[..end.min(rest.len())]
+            .binary_search_by(|entry| key.compare(&entry.0).reverse());
+        *cursor = start + found.map_or_else(|index| index, |index| index + 1);
+        found.is_ok()
+    }
+
+    #[must_use]
+    pub const fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(&self.entries)
+    }
+
+    #[must_use]
+    pub const fn keys(&self) -> Keys<'_, K, V> {
+        Keys::new(&self.entries)
+    }
+
+    #[must_use]
+    pub const fn values(&self) -> Values<'_, K, V> {
+        Values::new(&self.entries)
+    }
+
+    #[must_use]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut::new(&mut self.entries)
+    }
+
+    #[must_use]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut::new(&mut self.entries)
+    }
+
+    #[must_use]
+    pub fn into_keys(self) -> IntoKeys<K, V> {
+        IntoKeys::new(self.entries)
+    }
+
+    #[must_use]
+    pub fn into_values(self) -> IntoValues<K, V> {
+        IntoValues::new(self.entries)
+    }
+}
+
+impl<K, V> OrderedMap<K, V> {
+    /// An iterator visiting, in order, the entries whose key is greater than or equal to `key`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::specialized_maps::OrderedMap;
+    ///
+    /// let map = OrderedMap::from_vec(vec![(10, 'a'), (20, 'b'), (30, 'c')]);
+    /// let rest: Vec<_> = map.iter_from(&15).collect();
+    ///
+    /// assert_eq!(rest, vec![(&20, &'b'), (&30, &'c')]);
+    /// ```
+    #[must_use]
+    pub fn iter_from<Q>(&self, key: &Q) -> Iter<'_, K, V>
+    where
+        Q: Comparable<K> + ?Sized,
+    {
+        let start = self
+            .entries
+            .partition_point(|entry| key.compare(&entry.0).is_gt());
+        Iter::at(&self.entries, Cursor::new(start))
+    }
+
+    /// An iterator visiting, in order, the entries starting at a position obtained from
+    /// [`Iter::cursor`].
+    #[must_use]
+    pub const fn iter_at(&self, cursor: Cursor) -> Iter<'_, K, V> {
+        Iter::at(&self.entries, cursor)
+    }
+}
+
+impl<K, V> OrderedMap<K, V>
+where
+    K: Borrow<str>,
+{
+    /// Returns the range of entries whose key starts with `prefix`.
+    pub(crate) fn prefix_range(&self, prefix: &str) -> Range<usize> {
+        let start = self
+            .entries
+            .partition_point(|entry| entry.0.borrow() < prefix);
+        let len =
+            self.entries[start..].partition_point(|entry| entry.0.borrow().starts_with(prefix));
+
+        start..start + len
+    }
+
+    /// An iterator visiting, in order, the keys which start with `prefix`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::specialized_maps::OrderedMap;
+    ///
+    /// let map = OrderedMap::from_vec(vec![("apple", 1), ("apricot", 2), ("banana", 3), ("app", 4)]);
+    /// let keys: Vec<_> = map.keys_with_prefix("app").collect();
+    ///
+    /// assert_eq!(keys, vec![&"app", &"apple"]);
+    /// ```
+    #[must_use]
+    pub fn keys_with_prefix(&self, prefix: &str) -> Keys<'_, K, V> {
+        Keys::new(&self.entries[self.prefix_range(prefix)])
+    }
+
+    /// An iterator visiting, in order, the entries whose key starts with `prefix`.
+    #[must_use]
+    pub fn iter_with_prefix(&self, prefix: &str) -> Iter<'_, K, V> {
+        Iter::new(&self.entries[self.prefix_range(prefix)])
+    }
+}
+
+impl<K, V> OrderedMap<K, V> {
+    /// Describes the layout of the map.
+    ///
+    /// The probe chain is the number of entries a binary search examines.
+    #[must_use]
+    pub fn stats(&self) -> MapStats {
+        let len = self.entries.len();
+        let probes = (usize::BITS - len.leading_zeros()) as usize;
+        MapStats::new::<K, V>(len, 0, probes, 0)
+    }
+}
+
+impl<K, V> OrderedMap<K, V> {
+    /// Creates an empty map without allocating.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            entries: Box::default(),
+        }
+    }
+}
+
+impl<K, V> Default for OrderedMap<K, V> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<K, V> Len for OrderedMap<K, V> {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl<K, V> HeapSize for OrderedMap<K, V>
+where
+    K: HeapSize,
+    V: HeapSize,
+{
+    fn heap_bytes(&self) -> usize {
+        self.stats().heap_bytes() + entries_heap_bytes(self.iter())
+    }
+}
+
+impl<K, V> Map<K, V> for OrderedMap<K, V>
+where
+    K: Ord,
+{
+    type Iterator<'a>
+        = Iter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    type KeyIterator<'a>
+        = Keys<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    type ValueIterator<'a>
+        = Values<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    fn iter(&self) -> Iter<'_, K, V> {
+        self.iter()
+    }
+
+    fn keys(&self) -> Keys<'_, K, V> {
+        self.keys()
+    }
+
+    fn values(&self) -> Values<'_, K, V> {
+        self.values()
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+}
+
+impl<K, V> Debug for OrderedMap<K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let pairs = self.entries.iter().map(|x| (&x.0, &x.1));
+        f.debug_map().entries(pairs).finish()
+    }
+}
+
+impl<Q, K, V> Index<Q> for OrderedMap<K, V>
+where
+    Q: Comparable<K>,
+{
+    type Output = V;
+
+    fn index(&self, index: Q) -> &Self::Output {
+        self.get(&index).unwrap()
+    }
+}
+
+impl<Q, K, V> IndexMut<Q> for OrderedMap<K, V>
+where
+    Q: Comparable<K>,
+{
+    fn index_mut(&mut self, index: Q) -> &mut V {
+        self.get_mut(&index).unwrap()
+    }
+}
+
+impl<K, V> IntoIterator for OrderedMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.entries)
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a OrderedMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut OrderedMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K, V> PartialEq<Self> for OrderedMap<K, V>
+where
+    K: Ord,
+    V: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.entries
+            .iter()
+            .map(|x| (&x.0, &x.1))
+            .eq(other.entries.iter().map(|x| (&x.0, &x.1)))
+    }
+}
+
+impl<K, V> Eq for OrderedMap<K, V>
+where
+    K: Ord,
+    V: Eq,
+{
+}
+
+impl<K, V> Hash for OrderedMap<K, V>
+where
+    K: Hash,
+    V: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self, state);
+    }
+}
+
+impl<K, V, const N: usize> From<[(K, V); N]> for OrderedMap<K, V>
+where
+    K: Ord,
+{
+    fn from(payload: [(K, V); N]) -> Self {
+        Self::from_vec(Vec::from_iter(payload))
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for OrderedMap<K, V>
+where
+    K: Ord,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self::from_vec(Vec::from_iter(iter))
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<K, V> Zeroize for OrderedMap<K, V>
+where
+    K: Zeroize,
+    V: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.entries.zeroize();
+        self.entries = Box::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::specialized_maps::Cursor;
+    use crate::traits::len::Len;
+
+    use super::OrderedMap;
+
+    #[test]
+    fn stats_count_binary_search_probes() {
+        let map = OrderedMap::from_vec((0..7).map(|i| (i, i)).collect());
+        assert_eq!(3, map.stats().longest_probe_chain);
+        assert_eq!(0, map.stats().num_slots);
+
+        let map = OrderedMap::from_vec((0..8).map(|i| (i, i)).collect());
+        assert_eq!(4, map.stats().longest_probe_chain);
+    }
+
+    #[test]
+    fn get_returns_values_for_existing_keys() {
+        let map = OrderedMap::from_vec(vec![(50, 60), (10, 20), (30, 40)]);
+        assert_eq!(3, map.len());
+        assert_eq!(Some(&20), map.get(&10));
+        assert_eq!(Some(&60), map.get(&50));
+        assert_eq!(None, map.get(&0));
+        assert_eq!(None, map.get(&40));
+    }
+
+    #[test]
+    fn get_accepts_comparable_keys() {
+        use std::cmp::Ordering;
+
+        use crate::traits::equivalent::{Comparable, Equivalent};
+
+        struct Query<'a>(&'a str, u32);
+
+        impl Equivalent<(String, u32)> for Query<'_> {
+            fn equivalent(&self, key: &(String, u32)) -> bool {
+                self.0 == key.0 && self.1 == key.1
+            }
+        }
+
+        impl Comparable<(String, u32)> for Query<'_> {
+            fn compare(&self, key: &(String, u32)) -> Ordering {
+                self.0.cmp(&key.0).then(self.1.cmp(&key.1))
+            }
+        }
+
+        let map = OrderedMap::from_vec(vec![
+            (("b".to_string(), 1), 10),
+            (("a".to_string(), 2), 20),
+            (("a".to_string(), 1), 30),
+        ]);
+        assert_eq!(Some(&30), map.get(&Query("a", 1)));
+        assert_eq!(Some(&20), map.get(&Query("a", 2)));
+        assert_eq!(None, map.get(&Query("b", 2)));
+        assert_eq!(
+            vec![&20, &10],
+            map.iter_from(&Query("a", 2))
+                .map(|x| x.1)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn iterates_in_key_order() {
+        let map = OrderedMap::from_vec(vec![(3, 'c'), (1, 'a'), (2, 'b')]);
+        assert_eq!(vec![&1, &2, &3], map.keys().collect::<Vec<_>>());
+        assert_eq!("{1: 'a', 2: 'b', 3: 'c'}", format!("{map:?}"));
+    }
+
+    #[test]
+    fn consuming_iterators_move_entries_out() {
+        let map = OrderedMap::from_vec(vec![(3, "c".to_string()), (1, "a".to_string())]);
+        let mut iter = map.clone().into_iter();
+        assert_eq!(2, iter.len());
+        assert_eq!(r#"[(1, "a"), (3, "c")]"#, format!("{iter:?}"));
+        assert_eq!(Some((1, "a".to_string())), iter.next());
+        assert_eq!(1, iter.len());
+
+        assert_eq!(vec![1, 3], map.clone().into_keys().collect::<Vec<_>>());
+        assert_eq!(vec!["a", "c"], map.into_values().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn get_many_mut_returns_distinct_values() {
+        let mut map = OrderedMap::from_vec(vec![(1, 10), (2, 20), (3, 30)]);
+        let [a, b] = map.get_many_mut([&1, &3]).unwrap();
+        *a += 1;
+        *b += 1;
+        assert_eq!(Some(&11), map.get(&1));
+        assert_eq!(Some(&31), map.get(&3));
+        assert!(map.get_many_mut([&1, &1]).is_none());
+    }
+
+    #[test]
+    fn contains_all_and_any_keys() {
+        let map: OrderedMap<u32, u32> = (0..1000).map(|i| (i * 3, i)).collect();
+        let evens: Vec<u32> = (0..500).map(|i| i * 6).collect();
+        let shuffled = [2997, 0, 1500, 3];
+
+        assert!(map.contains_all_keys(&evens));
+        assert!(map.contains_all_keys(&shuffled));
+        assert!(map.contains_all_keys(&[6, 6, 9]));
+        assert!(map.contains_all_keys(&[0u32; 0]));
+        assert!(!map.contains_all_keys(&[0, 3, 4]));
+        assert!(!map.contains_all_keys(&[2997, 3000]));
+
+        assert!(map.contains_any_key(&[1, 2, 2997]));
+        assert!(map.contains_any_key(&[3000, 1, 0]));
+        assert!(!map.contains_any_key(&[1, 2, 4, 3000]));
+        assert!(!map.contains_any_key(&[0u32; 0]));
+
+        let map = OrderedMap::from_vec(vec![("b".to_string(), 1), ("d".to_string(), 2)]);
+        assert!(map.contains_all_keys(["b", "d"]));
+        assert!(map.contains_any_key(["a", "c", "d"]));
+        assert!(!OrderedMap::<u32, u32>::empty().contains_any_key(&[1]));
+    }
+
+    #[test]
+    fn keys_with_prefix() {
+        let words = ["car", "card", "care", "cart", "cat", "dog", "ca", "c"];
+        let map: OrderedMap<String, usize> =
+            words.iter().map(|w| (w.to_string(), w.len())).collect();
+
+        let keys: Vec<_> = map.keys_with_prefix("car").map(String::as_str).collect();
+        assert_eq!(vec!["car", "card", "care", "cart"], keys);
+
+        assert_eq!(7, map.keys_with_prefix("c").count());
+        assert_eq!(8, map.keys_with_prefix("").count());
+        assert_eq!(0, map.keys_with_prefix("cb").count());
+        assert_eq!(0, map.keys_with_prefix("zebra").count());
+
+        let entries: Vec<_> = map.iter_with_prefix("do").collect();
+        assert_eq!(vec![(&"dog".to_string(), &3)], entries);
+    }
+
+    #[test]
+    fn iter_from_and_cursor_resume_in_order() {
+        let map: OrderedMap<u32, u32> = (0..100).map(|i| (i * 2, i)).collect();
+
+        let mut iter = map.iter_from(&51);
+        assert_eq!(Some((&52, &26)), iter.next());
+        assert_eq!(Some((&54, &27)), iter.next());
+
+        let resumed: Vec<_> = map.iter_at(iter.cursor()).map(|x| *x.0).take(2).collect();
+        assert_eq!(vec![56, 58], resumed);
+
+        assert_eq!(0, map.iter_from(&1000).count());
+        assert_eq!(0, map.iter_at(Cursor::new(1000)).count());
+    }
+}
@@ -0,0 +1,635 @@
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::RandomState;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::ops::{Index, IndexMut};
+
+use crate::analyzers::duplicate_key_analyzer::find_duplicate_keys;
+use crate::analyzers::perfect_hash_analyzer::{
+    analyze_perfect_hash, bucket_index, slot_index, PerfectHashLayout,
+};
+use crate::errors::FrozenError;
+use crate::hashers::hash_unordered;
+use crate::specialized_maps::hash_table::{disjoint_values_mut, map_entries, prefetch, scatter};
+use crate::specialized_maps::prefilter::Prefilter;
+use crate::specialized_maps::{
+    IntoIter, IntoKeys, IntoValues, Iter, IterMut, Keys, MapStats, Values, ValuesMut,
+};
+use crate::traits::equivalent::Equivalent;
+use crate::traits::heap_size::{entries_heap_bytes, HeapSize};
+use crate::traits::len::Len;
+use crate::traits::map::Map;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// A map which places every key in its own slot using a minimal perfect hash function,
+/// designed for large payloads.
+///
+/// Creating this map is slower than creating a [`CommonMap`](crate::specialized_maps::CommonMap),
+/// but lookups never compare more than one key.
+#[derive(Clone)]
+pub struct PerfectHashMap<K, V, BH = RandomState> {
+    seeds: Box<[u32]>,
+    pub(crate) entries: Box<[(K, V)]>,
+    prefilter: Prefilter,
+    bh: BH,
+}
+
+impl<K, V, BH> PerfectHashMap<K, V, BH>
+where
+    K: Hash,
+    BH: BuildHasher,
+{
+    /// # Panics
+    ///
+    /// Panics if two keys have the same hash code, as duplicate keys do, since no perfect hash
+    /// function can tell them apart. For distinct hash codes, a function is all but certain to
+    /// be found.
+    #[must_use]
+    pub fn from_vec_with_hasher(payload: Vec<(K, V)>, bh: BH) -> Self {
+        let codes: Vec<_> = payload.iter().map(|entry| bh.hash_one(&entry.0)).collect();
+        let layout = analyze_perfect_hash(&codes)
+            .expect("A perfect hash function must exist for the payload's keys");
+
+        Self::from_layout(payload, layout, bh)
+    }
+
+    #[must_use]
+    pub fn from_iter_with_hasher<T: IntoIterator<Item = (K, V)>>(iter: T, bh: BH) -> Self {
+        Self::from_vec_with_hasher(Vec::from_iter(iter), bh)
+    }
+
+    /// Creates a map, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys, or if no perfect hash function can be found
+    /// for its keys.
+    pub fn try_from_vec_with_hasher(
+        payload: Vec<(K, V)>,
+        bh: BH,
+    ) -> std::result::Result<Self, FrozenError>
+    where
+        K: Eq,
+    {
+        if let Some(group) = find_duplicate_keys(&payload, |k| bh.hash_one(k)).first() {
+            return Err(FrozenError::DuplicateKey { index: group[1] });
+        }
+
+        let codes: Vec<_> = payload.iter().map(|entry| bh.hash_one(&entry.0)).collect();
+        let layout = analyze_perfect_hash(&codes).ok_or(FrozenError::NoPerfectHash)?;
+
+        Ok(Self::from_layout(payload, layout, bh))
+    }
+
+    #[must_use]
+    pub fn with_hasher<const N: usize>(payload: [(K, V); N], bh: BH) -> Self {
+        Self::from_vec_with_hasher(Vec::from_iter(payload), bh)
+    }
+
+    /// Moves each entry of the payload to the slot picked for it by the layout.
+    pub(crate) fn from_layout(
+        mut payload: Vec<(K, V)>,
+        mut layout: PerfectHashLayout,
+        bh: BH,
+    ) -> Self {
+        // every slot holds exactly one entry, so the slots are the positions of the entries
+        scatter(&mut payload, &mut layout.slots);
+
+        Self {
+            seeds: layout.seeds,
+            entries: payload.into_boxed_slice(),
+            prefilter: Prefilter::default(),
+            bh,
+        }
+    }
+
+    /// Adds a filter over the hash codes of the keys, in any order, which rules out most absent
+    /// keys before their slot is read.
+    #[must_use]
+    pub(crate) fn with_prefilter(mut self, hash_codes: &[u64]) -> Self {
+        self.prefilter = Prefilter::new(hash_codes);
+        self
+    }
+}
+
+impl<K, V, BH> PerfectHashMap<K, V, BH>
+where
+    BH: BuildHasher,
+{
+    /// Returns the only slot which can hold the key.
+    #[inline]
+    fn find_slot<Q>(&self, key: &Q) -> Option<usize>
+    where
+        Q: Hash + ?Sized,
+    {
+        if self.entries.is_empty() {
+            return None;
+        }
+
+        let hash_code = self.bh.hash_one(key);
+        if !self.prefilter.may_contain(hash_code) {
+            return None;
+        }
+
+        let seed = self.seeds[bucket_index(hash_code, self.seeds.len())];
+        Some(slot_index(hash_code, seed, self.entries.len()))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: Hash + Equivalent<K> + ?Sized,
+    {
+        let entry = &self.entries[self.find_slot(key)?];
+        if key.equivalent(&entry.0) {
+            return Some(&entry.1);
+        }
+
+        None
+    }
+
+    /// Returns a reference to the value corresponding to a key known to be in the map.
+    ///
+    /// A present key is the only key of its slot, so it's found without comparing keys.
+    ///
+    /// # Safety
+    ///
+    /// Calling this method with a key which isn't in the map is undefined behavior.
+    #[inline]
+    #[must_use]
+    pub unsafe fn get_unchecked<Q>(&self, key: &Q) -> &V
+    where
+        Q: Hash + Equivalent<K> + ?Sized,
+    {
+        unsafe {
+            let slot = self.find_slot(key).unwrap_unchecked();
+            &self.entries.get_unchecked(slot).1
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        Q: Hash + Equivalent<K> + ?Sized,
+    {
+        let entry = &self.entries[self.find_slot(key)?];
+        if key.equivalent(&entry.0) {
+            return Some((&entry.0, &entry.1));
+        }
+
+        None
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        Q: Hash + Equivalent<K> + ?Sized,
+    {
+        let slot = self.find_slot(key)?;
+        let entry = &mut self.entries[slot];
+        if key.equivalent(&entry.0) {
+            return Some(&mut entry.1);
+        }
+
+        None
+    }
+
+    /// Returns references to the values corresponding to several keys at once.
+    ///
+    /// The slots of all the keys are computed first and prefetched, so the memory latency of
+    /// the lookups overlaps.
+    #[inline]
+    #[must_use]
+    pub fn get_many<Q, const N: usize>(&self, keys: [&Q; N]) -> [Option<&V>; N]
+    where
+        Q: Hash + Equivalent<K> + ?Sized,
+    {
+        let slots = keys.map(|key| self.locate(key));
+        std::array::from_fn(|i| Some(&self.find_located(slots[i], keys[i])?.1))
+    }
+
+    /// Computes the only slot which can hold a key and starts loading it into the cache.
+    ///
+    /// The lookup is finished by [`Self::find_located`], once enough other work was done for
+    /// the slot to have arrived.
+    #[inline]
+    pub(crate) fn locate<Q>(&self, key: &Q) -> Option<usize>
+    where
+        Q: Hash + ?Sized,
+    {
+        let slot = self.find_slot(key)?;
+        prefetch(&self.entries[slot]);
+        Some(slot)
+    }
+
+    /// Finds the entry holding a key in the slot returned by [`Self::locate`].
+    #[inline]
+    pub(crate) fn find_located<Q>(&self, slot: Option<usize>, key: &Q) -> Option<&(K, V)>
+    where
+        Q: Equivalent<K> + ?Sized,
+    {
+        let entry = &self.entries[slot?];
+        key.equivalent(&entry.0).then_some(entry)
+    }
+
+    /// Returns mutable references to the values of several keys at once.
+    ///
+    /// Returns `None` if any key is missing, or if two keys match the same entry.
+    pub fn get_many_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> Option<[&mut V; N]>
+    where
+        Q: Hash + Equivalent<K> + ?Sized,
+    {
+        let indices = keys.map(|key| {
+            self.find_slot(key)
+                .filter(|&index| key.equivalent(&self.entries[index].0))
+        });
+        disjoint_values_mut(&mut self.entries, indices)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: Hash + Equivalent<K> + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+}
+
+impl<K, V, BH> PerfectHashMap<K, V, BH> {
+    #[must_use]
+    pub const fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(&self.entries)
+    }
+
+    #[must_use]
+    pub const fn keys(&self) -> Keys<'_, K, V> {
+        Keys::new(&self.entries)
+    }
+
+    #[must_use]
+    pub const fn values(&self) -> Values<'_, K, V> {
+        Values::new(&self.entries)
+    }
+
+    #[must_use]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut::new(&mut self.entries)
+    }
+
+    #[must_use]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut::new(&mut self.entries)
+    }
+
+    #[must_use]
+    pub fn into_keys(self) -> IntoKeys<K, V> {
+        IntoKeys::new(self.entries)
+    }
+
+    #[must_use]
+    pub fn into_values(self) -> IntoValues<K, V> {
+        IntoValues::new(self.entries)
+    }
+
+    pub(crate) fn map_values<V2, F>(self, f: F) -> PerfectHashMap<K, V2, BH>
+    where
+        F: FnMut(V) -> V2,
+    {
+        PerfectHashMap {
+            seeds: self.seeds,
+            entries: map_entries(self.entries, f),
+            prefilter: self.prefilter,
+            bh: self.bh,
+        }
+    }
+
+    #[must_use]
+    pub const fn hasher(&self) -> &BH {
+        &self.bh
+    }
+}
+
+impl<K, V> PerfectHashMap<K, V, RandomState>
+where
+    K: Hash,
+{
+    /// # Panics
+    ///
+    /// Panics if two keys have the same hash code, as duplicate keys do, since no perfect hash
+    /// function can tell them apart. For distinct hash codes, a function is all but certain to
+    /// be found.
+    #[must_use]
+    pub fn from_vec(payload: Vec<(K, V)>) -> Self {
+        Self::from_vec_with_hasher(payload, RandomState::new())
+    }
+
+    /// Creates a map, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys, or if no perfect hash function can be found
+    /// for its keys.
+    pub fn try_from_vec(payload: Vec<(K, V)>) -> std::result::Result<Self, FrozenError>
+    where
+        K: Eq,
+    {
+        Self::try_from_vec_with_hasher(payload, RandomState::new())
+    }
+}
+
+impl<K, V, BH> PerfectHashMap<K, V, BH> {
+    /// Describes the layout of the map.
+    #[must_use]
+    pub fn stats(&self) -> MapStats {
+        MapStats::direct::<K, V>(
+            self.entries.len(),
+            self.entries.len(),
+            size_of_val(&*self.seeds) + self.prefilter.size_in_bytes(),
+        )
+    }
+
+    /// Returns `true` if the map was built with a filter ruling out absent keys.
+    #[must_use]
+    pub(crate) fn has_prefilter(&self) -> bool {
+        !self.prefilter.is_empty()
+    }
+}
+
+impl<K, V, BH> PerfectHashMap<K, V, BH> {
+    /// Creates an empty map which will use the given hash builder, without allocating.
+    #[must_use]
+    pub fn empty_with_hasher(bh: BH) -> Self {
+        Self {
+            seeds: Box::default(),
+            entries: Box::default(),
+            prefilter: Prefilter::default(),
+            bh,
+        }
+    }
+}
+
+impl<K, V, BH> Default for PerfectHashMap<K, V, BH>
+where
+    BH: Default,
+{
+    fn default() -> Self {
+        Self::empty_with_hasher(BH::default())
+    }
+}
+
+impl<K, V, BH> Len for PerfectHashMap<K, V, BH> {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl<K, V, BH> HeapSize for PerfectHashMap<K, V, BH>
+where
+    K: HeapSize,
+    V: HeapSize,
+{
+    fn heap_bytes(&self) -> usize {
+        self.stats().heap_bytes() + entries_heap_bytes(self.iter())
+    }
+}
+
+impl<K, V, BH> Map<K, V> for PerfectHashMap<K, V, BH>
+where
+    K: Hash + Eq,
+    BH: BuildHasher,
+{
+    type Iterator<'a>
+        = Iter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+        BH: 'a;
+
+    type KeyIterator<'a>
+        = Keys<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+        BH: 'a;
+
+    type ValueIterator<'a>
+        = Values<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+        BH: 'a;
+
+    fn iter(&self) -> Iter<'_, K, V> {
+        self.iter()
+    }
+
+    fn keys(&self) -> Keys<'_, K, V> {
+        self.keys()
+    }
+
+    fn values(&self) -> Values<'_, K, V> {
+        self.values()
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    unsafe fn get_unchecked(&self, key: &K) -> &V {
+        unsafe { self.get_unchecked(key) }
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+}
+
+impl<K, V, BH> Debug for PerfectHashMap<K, V, BH>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let pairs = self.entries.iter().map(|x| (&x.0, &x.1));
+        f.debug_map().entries(pairs).finish()
+    }
+}
+
+impl<Q, K, V, BH> Index<Q> for PerfectHashMap<K, V, BH>
+where
+    Q: Hash + Equivalent<K>,
+    BH: BuildHasher,
+{
+    type Output = V;
+
+    fn index(&self, index: Q) -> &Self::Output {
+        self.get(&index).unwrap()
+    }
+}
+
+impl<Q, K, V, BH> IndexMut<Q> for PerfectHashMap<K, V, BH>
+where
+    Q: Hash + Equivalent<K>,
+    BH: BuildHasher,
+{
+    fn index_mut(&mut self, index: Q) -> &mut V {
+        self.get_mut(&index).unwrap()
+    }
+}
+
+impl<K, V, BH> IntoIterator for PerfectHashMap<K, V, BH> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.entries)
+    }
+}
+
+impl<'a, K, V, BH> IntoIterator for &'a PerfectHashMap<K, V, BH> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, BH> IntoIterator for &'a mut PerfectHashMap<K, V, BH> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K, V, BH> PartialEq<Self> for PerfectHashMap<K, V, BH>
+where
+    K: Hash + Eq,
+    V: PartialEq,
+    BH: BuildHasher,
+{
+    fn eq(&self, other: &Self) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+
+        self.iter()
+            .all(|(key, value)| other.get(key).is_some_and(|v| *value == *v))
+    }
+}
+
+impl<K, V, BH> Eq for PerfectHashMap<K, V, BH>
+where
+    K: Hash + Eq,
+    V: Eq,
+    BH: BuildHasher,
+{
+}
+
+impl<K, V, BH> Hash for PerfectHashMap<K, V, BH>
+where
+    K: Hash,
+    V: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self, state);
+    }
+}
+
+impl<K, V, const N: usize> From<[(K, V); N]> for PerfectHashMap<K, V, RandomState>
+where
+    K: Hash,
+{
+    fn from(payload: [(K, V); N]) -> Self {
+        Self::from_vec(Vec::from_iter(payload))
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for PerfectHashMap<K, V, RandomState>
+where
+    K: Hash,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self::from_vec(Vec::from_iter(iter))
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<K, V, BH> Zeroize for PerfectHashMap<K, V, BH>
+where
+    K: Zeroize,
+    V: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.entries.zeroize();
+        self.entries = Box::default();
+        self.seeds.zeroize();
+        self.seeds = Box::default();
+        self.prefilter.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::RandomState;
+
+    use super::*;
+
+    #[test]
+    fn test_from_iter() {
+        let map: PerfectHashMap<String, usize> =
+            (0..5000).map(|i| (format!("key{i}"), i)).collect();
+        assert_eq!(map.len(), 5000);
+
+        for i in 0..5000 {
+            assert_eq!(map.get(format!("key{i}").as_str()), Some(&i));
+        }
+
+        assert_eq!(map.get("key5000"), None);
+        assert_eq!(map.get(""), None);
+    }
+
+    #[test]
+    fn test_try_from_vec() {
+        let map = PerfectHashMap::try_from_vec(vec![("a", 1), ("b", 2), ("c", 3)]).unwrap();
+        assert_eq!(map.get("b"), Some(&2));
+
+        assert_eq!(
+            PerfectHashMap::try_from_vec(vec![("a", 1), ("b", 2), ("a", 3)]).err(),
+            Some(FrozenError::DuplicateKey { index: 2 })
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        let map = PerfectHashMap::<i32, i32, RandomState>::default();
+        assert!(map.is_empty());
+        assert_eq!(map.get(&0), None);
+        assert_eq!(map.iter().count(), 0);
+
+        let map = PerfectHashMap::<String, i32>::from_vec(vec![]);
+        assert!(!map.contains_key(""));
+    }
+
+    #[test]
+    fn test_get_many_mut() {
+        let mut map: PerfectHashMap<String, usize> =
+            (0..100).map(|i| (format!("key{i}"), i)).collect();
+
+        let [a, b] = map.get_many_mut(["key1", "key99"]).unwrap();
+        *a += 100;
+        *b += 100;
+        assert_eq!(map.get("key1"), Some(&101));
+        assert_eq!(map.get("key99"), Some(&199));
+
+        assert!(map.get_many_mut(["key1", "key1"]).is_none());
+        assert!(map.get_many_mut(["key1", "key100"]).is_none());
+    }
+}
@@ -0,0 +1,167 @@
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// The number of filter bits spent on each key, which keeps false positives around one percent.
+const BITS_PER_KEY: usize = 10;
+
+/// The number of bits each key sets within its block.
+const PROBES: u32 = 6;
+
+/// The number of bits in a block, which fills a cache line.
+const BLOCK_BITS: usize = 512;
+
+/// The bits of the filter which a range of keys sets, laid out to fill a cache line.
+#[derive(Clone, Copy, Default)]
+#[repr(align(64))]
+struct Block([u64; BLOCK_BITS / 64]);
+
+/// A blocked Bloom filter over the hash codes of a table's keys.
+///
+/// Each hash code picks a block, and sets a few bits within it. Checking a hash code reads a
+/// single block, so most hash codes which no key has are ruled out with one cache line read,
+/// before the slots or the entries of the table are touched. Hash codes of keys are never
+/// ruled out.
+///
+/// A filter with no blocks rules nothing out, which is what tables built without a filter
+/// hold.
+#[derive(Clone, Default)]
+pub struct Prefilter {
+    blocks: Box<[Block]>,
+}
+
+impl Prefilter {
+    /// Creates a filter holding the given hash codes.
+    pub fn new(hash_codes: &[u64]) -> Self {
+        if hash_codes.is_empty() {
+            return Self::default();
+        }
+
+        let num_blocks = (hash_codes.len() * BITS_PER_KEY).div_ceil(BLOCK_BITS);
+        let mut blocks = vec![Block::default(); num_blocks];
+        for &hash_code in hash_codes {
+            let mixed = mix(hash_code);
+            let block = &mut blocks[block_index(mixed, num_blocks)];
+            for bit in probe_bits(mixed) {
+                block.0[bit / 64] |= 1 << (bit % 64);
+            }
+        }
+
+        Self {
+            blocks: blocks.into_boxed_slice(),
+        }
+    }
+
+    /// Returns `false` if no key has the hash code, and `true` if one may have it.
+    #[inline]
+    pub fn may_contain(&self, hash_code: u64) -> bool {
+        if self.blocks.is_empty() {
+            return true;
+        }
+
+        let mixed = mix(hash_code);
+
+        // block_index is always less than the number of blocks
+        let block = unsafe {
+            self.blocks
+                .get_unchecked(block_index(mixed, self.blocks.len()))
+        };
+        probe_bits(mixed).all(|bit| block.0[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Returns `true` if the filter has no blocks, and so rules nothing out.
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Returns the number of bytes taken by the filter.
+    pub fn size_in_bytes(&self) -> usize {
+        size_of_val(&*self.blocks)
+    }
+}
+
+/// Spreads every bit of a hash code over the whole code, since hash codes like integer keys
+/// and lengths only differ in their low bits.
+#[inline]
+const fn mix(hash_code: u64) -> u64 {
+    let mut h = hash_code;
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    h ^ (h >> 33)
+}
+
+/// Picks the block of a mixed hash code from its high half.
+#[inline]
+#[allow(clippy::cast_possible_truncation)]
+const fn block_index(mixed: u64, num_blocks: usize) -> usize {
+    // the product of a 32-bit value and the number of blocks, shifted down by 32 bits, is less
+    // than the number of blocks
+    (((mixed >> 32) * num_blocks as u64) >> 32) as usize
+}
+
+/// Picks the bits of a mixed hash code within its block from its low half, rehashed so they
+/// cover more bits than the half holds.
+#[inline]
+#[allow(clippy::cast_possible_truncation)]
+fn probe_bits(mixed: u64) -> impl Iterator<Item = usize> {
+    let bits = (mixed & 0xffff_ffff).wrapping_mul(0x9e37_79b9_7f4a_7c15) ^ mixed;
+    (0..PROBES).map(move |i| ((bits >> (i * 9)) as usize) % BLOCK_BITS)
+}
+
+#[cfg(feature = "zeroize")]
+impl Zeroize for Prefilter {
+    fn zeroize(&mut self) {
+        for block in &mut self.blocks {
+            block.0.zeroize();
+        }
+
+        self.blocks = Box::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::{BuildHasher, RandomState};
+
+    use super::*;
+
+    #[test]
+    fn keys_are_never_ruled_out() {
+        let bh = RandomState::new();
+        for len in [1, 10, 1000, 100_000] {
+            let codes: Vec<_> = (0..len).map(|i| bh.hash_one(i)).collect();
+            let filter = Prefilter::new(&codes);
+            assert!(codes.iter().all(|&code| filter.may_contain(code)));
+        }
+
+        // small integer codes only differ in their low bits
+        let codes: Vec<u64> = (0..10_000).collect();
+        let filter = Prefilter::new(&codes);
+        assert!(codes.iter().all(|&code| filter.may_contain(code)));
+    }
+
+    #[test]
+    fn most_misses_are_ruled_out() {
+        let codes: Vec<u64> = (0..100_000).map(|i| i * 2).collect();
+        let filter = Prefilter::new(&codes);
+        assert_eq!(
+            (100_000 * BITS_PER_KEY).div_ceil(BLOCK_BITS) * 64,
+            filter.size_in_bytes()
+        );
+
+        let false_positives = (0..100_000u64)
+            .filter(|i| filter.may_contain(i * 2 + 1))
+            .count();
+        assert!(false_positives < 3000, "{false_positives} false positives");
+    }
+
+    #[test]
+    fn empty_filter() {
+        let filter = Prefilter::new(&[]);
+        assert!(filter.is_empty());
+        assert_eq!(0, filter.size_in_bytes());
+        assert!(filter.may_contain(0));
+        assert!(filter.may_contain(u64::MAX));
+    }
+}
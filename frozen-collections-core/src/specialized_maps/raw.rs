@@ -0,0 +1,154 @@
+//! Low-level access to the hash tables of maps.
+//!
+//! Maps normally hash a key for every lookup. The functions and traits in this module expose
+//! the hash codes and the layout of the tables, so code looking the same key up in several
+//! maps can hash it once, or walk the entries of a bucket directly.
+
+use std::hash::{BuildHasher, Hash};
+use std::ops::Range;
+
+use crate::specialized_maps::key_codes::int_code;
+use crate::specialized_maps::{CommonMap, IntegerMap};
+
+/// Returns the hash code an [`IntegerMap`] uses for a key, which is the integer the key writes
+/// to a hasher.
+#[inline]
+#[must_use]
+pub fn int_hash<Q>(key: &Q) -> u64
+where
+    Q: ?Sized + Hash,
+{
+    int_code(key)
+}
+
+/// A map whose entries are laid out in buckets selected by hash codes.
+///
+/// The entries of a bucket are contiguous, so a bucket is described by a range of positions
+/// within [`entries`](Self::entries).
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::specialized_maps::raw::RawTable;
+/// use frozen_collections_core::specialized_maps::CommonMap;
+///
+/// let map = CommonMap::<_, _>::from_vec(vec![("a", 1), ("b", 2), ("c", 3)]);
+/// let hash = map.hash_key("b");
+///
+/// let bucket = map.bucket(hash);
+/// assert!(bucket.iter().any(|(k, v)| *k == "b" && *v == 2));
+/// assert_eq!(&map.entries()[map.bucket_range(hash)], bucket);
+/// ```
+pub trait RawTable<K, V> {
+    /// Returns the hash code the map uses for a key.
+    fn hash_key<Q>(&self, key: &Q) -> u64
+    where
+        Q: ?Sized + Hash;
+
+    /// Returns the positions of the entries in the bucket selected by a hash code.
+    ///
+    /// Every entry whose key has this hash code is in the bucket, possibly along with entries
+    /// whose keys have other hash codes.
+    fn bucket_range(&self, hash_code: u64) -> Range<usize>;
+
+    /// Returns all the entries of the map, grouped by bucket.
+    fn entries(&self) -> &[(K, V)];
+
+    /// Returns the entries of the bucket selected by a hash code.
+    fn bucket(&self, hash_code: u64) -> &[(K, V)] {
+        &self.entries()[self.bucket_range(hash_code)]
+    }
+}
+
+impl<K, V, BH> RawTable<K, V> for CommonMap<K, V, BH>
+where
+    BH: BuildHasher,
+{
+    #[inline]
+    fn hash_key<Q>(&self, key: &Q) -> u64
+    where
+        Q: ?Sized + Hash,
+    {
+        self.hasher().hash_one(key)
+    }
+
+    #[inline]
+    fn bucket_range(&self, hash_code: u64) -> Range<usize> {
+        self.table.get_hash_info(hash_code)
+    }
+
+    fn entries(&self) -> &[(K, V)] {
+        &self.table.entries
+    }
+}
+
+impl<K, V> RawTable<K, V> for IntegerMap<K, V> {
+    #[inline]
+    fn hash_key<Q>(&self, key: &Q) -> u64
+    where
+        Q: ?Sized + Hash,
+    {
+        int_code(key)
+    }
+
+    #[inline]
+    fn bucket_range(&self, hash_code: u64) -> Range<usize> {
+        self.table.get_hash_info(hash_code)
+    }
+
+    fn entries(&self) -> &[(K, V)] {
+        &self.table.entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::RandomState;
+
+    use super::*;
+    use crate::hashers::SeededState;
+
+    #[test]
+    fn common_map_buckets() {
+        let payload: Vec<_> = (0..500).map(|i| (format!("key{i}"), i)).collect();
+        let map = CommonMap::from_vec_with_hasher(payload.clone(), RandomState::new());
+
+        for (k, v) in &payload {
+            let hash = map.hash_key(k.as_str());
+            assert_eq!(Some(v), map.get_with_hash(hash, k.as_str()));
+            assert_eq!(Some((k, v)), map.get_key_value_with_hash(hash, k.as_str()));
+            assert!(map.bucket(hash).iter().any(|entry| entry.0 == *k));
+            assert!(map.bucket_range(hash).end <= map.entries().len());
+        }
+
+        assert_eq!(None, map.get_with_hash(map.hash_key("key500"), "key500"));
+    }
+
+    #[test]
+    fn shared_hash_codes() {
+        let bh = SeededState::new(7);
+        let a = CommonMap::from_vec_with_hasher(vec![("x", 1), ("y", 2)], bh);
+        let b = CommonMap::from_vec_with_hasher(vec![("y", 20), ("z", 30)], bh);
+
+        let hash = bh.hash_one("y");
+        assert_eq!(Some(&2), a.get_with_hash(hash, "y"));
+        assert_eq!(Some(&20), b.get_with_hash(hash, "y"));
+        assert_eq!(None, a.get_with_hash(hash.wrapping_add(1), "y"));
+    }
+
+    #[test]
+    fn integer_map_buckets() {
+        let map = IntegerMap::from_vec((0..100u64).map(|i| (i * 1_000_003, i)).collect());
+        for i in 0..100u64 {
+            let key = i * 1_000_003;
+            assert_eq!(int_hash(&key), map.hash_key(&key));
+            assert_eq!(Some(&i), map.get_with_hash(int_hash(&key), &key));
+            assert!(map.bucket(int_hash(&key)).iter().any(|e| e.0 == key));
+        }
+
+        assert_eq!(None, map.get_with_hash(int_hash(&1u64), &1));
+        assert!(IntegerMap::<u64, u64>::from_vec(vec![])
+            .bucket(0)
+            .is_empty());
+    }
+}
@@ -1,55 +1,81 @@
-use std::borrow::Borrow;
 use std::fmt::{Debug, Formatter, Result};
-use std::hash::BuildHasher;
 use std::hash::RandomState;
-use std::intrinsics::transmute;
-use std::mem::MaybeUninit;
+use std::hash::{BuildHasher, Hash, Hasher};
 use std::ops::Range;
 use std::ops::{Index, IndexMut};
 
-use num_traits::{PrimInt, Unsigned};
-
-use crate::analyzers::hash_code_analyzer::analyze_hash_codes;
-use crate::specialized_maps::hash_table::HashTable;
-use crate::specialized_maps::{Iter, Keys, Values};
+use crate::analyzers::hash_code_analyzer::{
+    analyze_hash_codes, analyze_hash_codes_with, HashCodeAnalysisOptions,
+};
+use crate::errors::FrozenError;
+use crate::hashers::hash_unordered;
+use crate::specialized_maps::hash_table::{disjoint_values_mut, HashTable};
+use crate::specialized_maps::key_codes::right_slice_code;
+use crate::specialized_maps::{
+    IntoIter, IntoKeys, IntoValues, Iter, IterMut, Keys, MapStats, Values, ValuesMut,
+};
+use crate::traits::equivalent::Equivalent;
+use crate::traits::heap_size::{entries_heap_bytes, HeapSize};
 use crate::traits::len::Len;
+use crate::traits::map::Map;
 use crate::traits::slice_hash::SliceHash;
 
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
 /// A map that hashes right-aligned slices of its keys.
 #[derive(Clone)]
-pub struct RightSliceMap<K, V, S = u8, BH = RandomState> {
-    pub(crate) table: HashTable<K, V, S>,
+pub struct RightSliceMap<K, V, BH = RandomState> {
+    pub(crate) table: HashTable<K, V>,
     bh: BH,
-    range: Range<usize>,
+    pub(crate) range: Range<usize>,
 }
 
-impl<K, V, S, BH> RightSliceMap<K, V, S, BH>
+impl<K, V, BH> RightSliceMap<K, V, BH>
 where
     K: SliceHash + Len + Eq,
-    S: PrimInt + Unsigned,
     BH: BuildHasher,
 {
     #[must_use]
     pub fn from_vec_with_hasher(payload: Vec<(K, V)>, range: Range<usize>, bh: BH) -> Self {
-        let codes = payload.iter().map(|entry| {
-            let key = &entry.0;
-            if key.len() >= range.end {
-                key.hash(&bh, key.len() - range.start..key.len() - range.end)
-            } else {
-                0
-            }
-        });
+        let codes = payload
+            .iter()
+            .map(|entry| right_slice_hash(&entry.0, &bh, &range));
 
         let code_analysis = analyze_hash_codes(codes);
         Self {
-            table: HashTable::new(payload, code_analysis.num_hash_slots, |k| {
-                k.hash(&bh, k.len() - range.start..k.len() - range.end)
+            table: HashTable::from_analysis(payload, code_analysis, false, |k| {
+                right_slice_hash(k, &bh, &range)
             }),
             bh,
             range,
         }
     }
 
+    /// Creates a map, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys.
+    pub fn try_from_vec_with_hasher(
+        payload: Vec<(K, V)>,
+        range: Range<usize>,
+        bh: BH,
+    ) -> std::result::Result<Self, FrozenError> {
+        let codes = payload
+            .iter()
+            .map(|entry| right_slice_hash(&entry.0, &bh, &range));
+
+        let code_analysis = analyze_hash_codes(codes);
+        Ok(Self {
+            table: HashTable::try_from_analysis(payload, code_analysis, |k| {
+                right_slice_hash(k, &bh, &range)
+            })?,
+            bh,
+            range,
+        })
+    }
+
     #[must_use]
     pub fn from_iter_with_hasher<T: IntoIterator<Item = (K, V)>>(
         iter: T,
@@ -65,40 +91,106 @@ where
     }
 }
 
-impl<K, V, S, BH> RightSliceMap<K, V, S, BH>
+impl<K, V, BH> RightSliceMap<K, V, BH>
+where
+    K: Hash + Eq,
+    BH: BuildHasher,
+{
+    /// Creates a map which hashes the bytes keys write to a [`Hasher`](std::hash::Hasher)
+    /// instead of going through [`SliceHash`].
+    ///
+    /// The map must then be probed with [`Self::find`], which hashes keys the same way. For
+    /// `String` keys, both approaches produce the same hash codes. The hash table is sized
+    /// within the given limits.
+    pub(crate) fn from_hashed_vec(
+        payload: Vec<(K, V)>,
+        range: Range<usize>,
+        bh: BH,
+        sizing: &HashCodeAnalysisOptions,
+    ) -> Self {
+        let codes = payload
+            .iter()
+            .map(|entry| right_slice_code(&entry.0, &bh, &range));
+        let code_analysis = analyze_hash_codes_with(codes, sizing);
+
+        Self {
+            table: HashTable::from_analysis(payload, code_analysis, sizing.prefilter, |k| {
+                right_slice_code(k, &bh, &range)
+            }),
+            bh,
+            range,
+        }
+    }
+
+    /// Finds the entry holding a key, in a map created with [`Self::from_hashed_vec`].
+    #[inline]
+    pub(crate) fn find<Q>(&self, key: &Q) -> Option<&(K, V)>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.table
+            .find(right_slice_code(key, &self.bh, &self.range), key)
+    }
+
+    /// Finds the entries holding several keys, in a map created with [`Self::from_hashed_vec`].
+    #[inline]
+    pub(crate) fn find_many<Q, const N: usize>(&self, keys: [&Q; N]) -> [Option<&(K, V)>; N]
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let hash_codes = keys.map(|key| right_slice_code(key, &self.bh, &self.range));
+        self.table.find_many(hash_codes, keys)
+    }
+
+    /// Locates the entries which may hold a key and starts loading them into the cache, in a
+    /// map created with [`Self::from_hashed_vec`].
+    ///
+    /// Returns the key's hash code along with the entries, which together finish the lookup
+    /// through [`HashTable::find_located`].
+    #[inline]
+    pub(crate) fn locate<Q>(&self, key: &Q) -> (u64, Range<usize>)
+    where
+        Q: ?Sized + Hash,
+    {
+        let hash_code = right_slice_code(key, &self.bh, &self.range);
+        (hash_code, self.table.locate(hash_code))
+    }
+
+    /// Finds the entry holding a key, in a map created with [`Self::from_hashed_vec`].
+    #[inline]
+    pub(crate) fn find_mut<Q>(&mut self, key: &Q) -> Option<&mut (K, V)>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.table
+            .find_mut(right_slice_code(key, &self.bh, &self.range), key)
+    }
+}
+
+impl<K, V, BH> RightSliceMap<K, V, BH>
 where
-    S: PrimInt + Unsigned,
     BH: BuildHasher,
 {
     #[inline]
     #[must_use]
     fn get_hash_info<Q>(&self, key: &Q) -> Range<usize>
     where
-        Q: SliceHash + Len,
+        Q: SliceHash + Len + ?Sized,
     {
-        let hash_code = if key.len() >= self.range.start {
-            key.hash(
-                &self.bh,
-                key.len() - self.range.start..key.len() - self.range.end,
-            )
-        } else {
-            0
-        };
-
-        self.table.get_hash_info(hash_code)
+        self.table
+            .get_hash_info(right_slice_hash(key, &self.bh, &self.range))
     }
 
     #[inline]
     #[must_use]
     pub fn get<Q>(&self, key: &Q) -> Option<&V>
     where
-        K: Borrow<Q>,
-        Q: SliceHash + Len + Eq,
+        Q: SliceHash + Len + Equivalent<K> + ?Sized,
     {
         let range = self.get_hash_info(key);
         let entries = unsafe { self.table.entries.get_unchecked(range) };
         for entry in entries {
-            if key.eq(entry.0.borrow()) {
+            if key.equivalent(&entry.0) {
                 return Some(&entry.1);
             }
         }
@@ -110,13 +202,12 @@ where
     #[must_use]
     pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
     where
-        K: Borrow<Q>,
-        Q: SliceHash + Len + Eq,
+        Q: SliceHash + Len + Equivalent<K> + ?Sized,
     {
         let range = self.get_hash_info(key);
         let entries = unsafe { self.table.entries.get_unchecked(range) };
         for entry in entries {
-            if key.eq(entry.0.borrow()) {
+            if key.equivalent(&entry.0) {
                 return Some((&entry.0, &entry.1));
             }
         }
@@ -128,13 +219,12 @@ where
     #[must_use]
     pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
     where
-        K: Borrow<Q>,
-        Q: SliceHash + Len + Eq,
+        Q: SliceHash + Len + Equivalent<K> + ?Sized,
     {
         let range = self.get_hash_info(key);
         let entries = unsafe { self.table.entries.get_unchecked_mut(range) };
         for entry in entries {
-            if key.eq(entry.0.borrow()) {
+            if key.equivalent(&entry.0) {
                 return Some(&mut entry.1);
             }
         }
@@ -142,45 +232,44 @@ where
         None
     }
 
-    #[allow(mutable_transmutes)]
-    pub fn get_many_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> Option<[&mut V; N]>
+    /// Returns references to the values corresponding to several keys at once.
+    ///
+    /// The hash codes of all the keys are computed first and their slots prefetched, so the
+    /// memory latency of the lookups overlaps.
+    #[inline]
+    #[must_use]
+    pub fn get_many<Q, const N: usize>(&self, keys: [&Q; N]) -> [Option<&V>; N]
     where
-        K: Borrow<Q>,
-        Q: SliceHash + Len + Eq,
+        Q: SliceHash + Len + Equivalent<K> + ?Sized,
     {
-        // ensure key uniqueness (assumes "keys" is a relatively small array)
-        for i in 0..keys.len() {
-            for j in 0..i {
-                if keys[j].eq(keys[i]) {
-                    return None;
-                }
-            }
-        }
-
-        unsafe {
-            let mut result: MaybeUninit<[&mut V; N]> = MaybeUninit::uninit();
-            let p = result.as_mut_ptr();
-
-            for (i, key) in keys.iter().enumerate() {
-                *(*p).get_unchecked_mut(i) = transmute(self.get(key)?);
-            }
+        let hash_codes = keys.map(|key| right_slice_hash(key, &self.bh, &self.range));
+        self.table
+            .find_many(hash_codes, keys)
+            .map(|entry| entry.map(|(_, v)| v))
+    }
 
-            Some(result.assume_init())
-        }
+    /// Returns mutable references to the values of several keys at once.
+    ///
+    /// Returns `None` if any key is missing, or if two keys match the same entry.
+    pub fn get_many_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> Option<[&mut V; N]>
+    where
+        Q: SliceHash + Len + Equivalent<K> + ?Sized,
+    {
+        let indices = keys.map(|key| self.table.find_index_in(self.get_hash_info(key), key));
+        disjoint_values_mut(&mut self.table.entries, indices)
     }
 
     #[inline]
     #[must_use]
     pub fn contains_key<Q>(&self, key: &Q) -> bool
     where
-        K: Borrow<Q>,
-        Q: SliceHash + Len + Eq,
+        Q: SliceHash + Len + Equivalent<K> + ?Sized,
     {
         self.get(key).is_some()
     }
 }
 
-impl<K, V, S, BH> RightSliceMap<K, V, S, BH> {
+impl<K, V, BH> RightSliceMap<K, V, BH> {
     #[must_use]
     pub const fn iter(&self) -> Iter<K, V> {
         Iter::new(&self.table.entries)
@@ -196,22 +285,64 @@ impl<K, V, S, BH> RightSliceMap<K, V, S, BH> {
         Values::new(&self.table.entries)
     }
 
+    #[must_use]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut::new(&mut self.table.entries)
+    }
+
+    #[must_use]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut::new(&mut self.table.entries)
+    }
+
+    #[must_use]
+    pub fn into_keys(self) -> IntoKeys<K, V> {
+        IntoKeys::new(self.table.entries)
+    }
+
+    #[must_use]
+    pub fn into_values(self) -> IntoValues<K, V> {
+        IntoValues::new(self.table.entries)
+    }
+
+    pub(crate) fn map_values<V2, F>(self, f: F) -> RightSliceMap<K, V2, BH>
+    where
+        F: FnMut(V) -> V2,
+    {
+        RightSliceMap {
+            table: self.table.map_values(f),
+            bh: self.bh,
+            range: self.range,
+        }
+    }
+
     #[must_use]
     pub const fn hasher(&self) -> &BH {
         &self.bh
     }
 }
 
-impl<K, V, S> RightSliceMap<K, V, S, RandomState>
+impl<K, V> RightSliceMap<K, V, RandomState>
 where
     K: SliceHash + Len + Eq,
-    S: PrimInt + Unsigned,
 {
     #[must_use]
     pub fn from_vec(payload: Vec<(K, V)>, range: Range<usize>) -> Self {
         Self::from_vec_with_hasher(payload, range, RandomState::new())
     }
 
+    /// Creates a map, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys.
+    pub fn try_from_vec(
+        payload: Vec<(K, V)>,
+        range: Range<usize>,
+    ) -> std::result::Result<Self, FrozenError> {
+        Self::try_from_vec_with_hasher(payload, range, RandomState::new())
+    }
+
     #[must_use]
     pub fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T, range: Range<usize>) -> Self {
         Self::from_iter_with_hasher(iter, range, RandomState::new())
@@ -223,13 +354,99 @@ where
     }
 }
 
-impl<K, V, S, BH> Len for RightSliceMap<K, V, S, BH> {
+impl<K, V, BH> RightSliceMap<K, V, BH> {
+    /// Describes the layout of the map.
+    #[must_use]
+    pub fn stats(&self) -> MapStats {
+        self.table.stats()
+    }
+}
+
+impl<K, V, BH> RightSliceMap<K, V, BH> {
+    /// Creates an empty map which will use the given hash builder, without allocating.
+    #[must_use]
+    pub fn empty_with_hasher(bh: BH) -> Self {
+        Self {
+            table: HashTable::empty(),
+            bh,
+            range: 0..0,
+        }
+    }
+}
+
+impl<K, V, BH> Default for RightSliceMap<K, V, BH>
+where
+    BH: Default,
+{
+    fn default() -> Self {
+        Self::empty_with_hasher(BH::default())
+    }
+}
+
+impl<K, V, BH> Len for RightSliceMap<K, V, BH> {
     fn len(&self) -> usize {
         self.table.len()
     }
 }
 
-impl<K, V, S, BH> Debug for RightSliceMap<K, V, S, BH>
+impl<K, V, BH> HeapSize for RightSliceMap<K, V, BH>
+where
+    K: HeapSize,
+    V: HeapSize,
+{
+    fn heap_bytes(&self) -> usize {
+        self.stats().heap_bytes() + entries_heap_bytes(self.iter())
+    }
+}
+
+impl<K, V, BH> Map<K, V> for RightSliceMap<K, V, BH>
+where
+    K: SliceHash + Len + Eq,
+    BH: BuildHasher,
+{
+    type Iterator<'a>
+        = Iter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+        BH: 'a;
+
+    type KeyIterator<'a>
+        = Keys<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+        BH: 'a;
+
+    type ValueIterator<'a>
+        = Values<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+        BH: 'a;
+
+    fn iter(&self) -> Iter<'_, K, V> {
+        self.iter()
+    }
+
+    fn keys(&self) -> Keys<'_, K, V> {
+        self.keys()
+    }
+
+    fn values(&self) -> Values<'_, K, V> {
+        self.values()
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+}
+
+impl<K, V, BH> Debug for RightSliceMap<K, V, BH>
 where
     K: Debug,
     V: Debug,
@@ -239,11 +456,9 @@ where
     }
 }
 
-impl<Q, K, V, S, BH> Index<Q> for RightSliceMap<K, V, S, BH>
+impl<Q, K, V, BH> Index<Q> for RightSliceMap<K, V, BH>
 where
-    K: Borrow<Q>,
-    Q: SliceHash + Len + Eq,
-    S: PrimInt + Unsigned,
+    Q: SliceHash + Len + Equivalent<K>,
     BH: BuildHasher,
 {
     type Output = V;
@@ -253,11 +468,9 @@ where
     }
 }
 
-impl<Q, K, V, S, BH> IndexMut<Q> for RightSliceMap<K, V, S, BH>
+impl<Q, K, V, BH> IndexMut<Q> for RightSliceMap<K, V, BH>
 where
-    K: Borrow<Q>,
-    Q: SliceHash + Len + Eq,
-    S: PrimInt + Unsigned,
+    Q: SliceHash + Len + Equivalent<K>,
     BH: BuildHasher,
 {
     fn index_mut(&mut self, index: Q) -> &mut V {
@@ -265,7 +478,16 @@ where
     }
 }
 
-impl<'a, K, V, S, BH> IntoIterator for &'a RightSliceMap<K, V, S, BH> {
+impl<K, V, BH> IntoIterator for RightSliceMap<K, V, BH> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.table.entries)
+    }
+}
+
+impl<'a, K, V, BH> IntoIterator for &'a RightSliceMap<K, V, BH> {
     type Item = (&'a K, &'a V);
     type IntoIter = Iter<'a, K, V>;
 
@@ -274,11 +496,19 @@ impl<'a, K, V, S, BH> IntoIterator for &'a RightSliceMap<K, V, S, BH> {
     }
 }
 
-impl<K, V, S, BH> PartialEq<Self> for RightSliceMap<K, V, S, BH>
+impl<'a, K, V, BH> IntoIterator for &'a mut RightSliceMap<K, V, BH> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K, V, BH> PartialEq<Self> for RightSliceMap<K, V, BH>
 where
     K: SliceHash + Len + Eq,
     V: PartialEq,
-    S: PrimInt + Unsigned,
     BH: BuildHasher,
 {
     fn eq(&self, other: &Self) -> bool {
@@ -291,11 +521,48 @@ where
     }
 }
 
-impl<K, V, S, BH> Eq for RightSliceMap<K, V, S, BH>
+impl<K, V, BH> Eq for RightSliceMap<K, V, BH>
 where
     K: SliceHash + Len + Eq,
     V: Eq,
-    S: PrimInt + Unsigned,
     BH: BuildHasher,
 {
 }
+
+impl<K, V, BH> Hash for RightSliceMap<K, V, BH>
+where
+    K: Hash,
+    V: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self, state);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<K, V, BH> Zeroize for RightSliceMap<K, V, BH>
+where
+    K: Zeroize,
+    V: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.table.zeroize();
+    }
+}
+
+/// Hashes the subslice of a key selected by a range measured from the end of the key.
+///
+/// Keys too short to contain the subslice get a code of zero.
+#[inline]
+fn right_slice_hash<Q, BH>(key: &Q, bh: &BH, range: &Range<usize>) -> u64
+where
+    Q: SliceHash + Len + ?Sized,
+    BH: BuildHasher,
+{
+    let len = key.len();
+    if len >= range.end {
+        key.hash(bh, len - range.end..len - range.start)
+    } else {
+        0
+    }
+}
@@ -1,11 +1,20 @@
-use std::borrow::Borrow;
 use std::fmt::{Debug, Formatter, Result};
-use std::intrinsics::transmute;
-use std::mem::MaybeUninit;
+use std::hash::{Hash, Hasher};
 use std::ops::{Index, IndexMut};
 
-use crate::specialized_maps::{Iter, Keys, Values};
+use crate::errors::FrozenError;
+use crate::hashers::hash_unordered;
+use crate::specialized_maps::hash_table::{disjoint_values_mut, map_entries};
+use crate::specialized_maps::{
+    IntoIter, IntoKeys, IntoValues, Iter, IterMut, Keys, MapStats, Values, ValuesMut,
+};
+use crate::traits::equivalent::Equivalent;
+use crate::traits::heap_size::{entries_heap_bytes, HeapSize};
 use crate::traits::len::Len;
+use crate::traits::map::Map;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
 
 /// A map that does a linear scan of its entries upon lookup, designed for very small payloads.
 #[derive(Clone)]
@@ -23,6 +32,21 @@ where
             entries: payload.into_boxed_slice(),
         }
     }
+
+    /// Creates a map, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys.
+    pub fn try_from_vec(payload: Vec<(K, V)>) -> std::result::Result<Self, FrozenError> {
+        for (index, x) in payload.iter().enumerate() {
+            if payload[..index].iter().any(|y| x.0 == y.0) {
+                return Err(FrozenError::DuplicateKey { index });
+            }
+        }
+
+        Ok(Self::from_vec(payload))
+    }
 }
 
 impl<K, V> ScanningMap<K, V> {
@@ -30,11 +54,10 @@ impl<K, V> ScanningMap<K, V> {
     #[must_use]
     pub fn get<Q>(&self, key: &Q) -> Option<&V>
     where
-        K: Borrow<Q>,
-        Q: Eq,
+        Q: Equivalent<K> + ?Sized,
     {
         for entry in self.entries.iter() {
-            if key.eq(entry.0.borrow()) {
+            if key.equivalent(&entry.0) {
                 return Some(&entry.1);
             }
         }
@@ -46,11 +69,10 @@ impl<K, V> ScanningMap<K, V> {
     #[must_use]
     pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
     where
-        K: Borrow<Q>,
-        Q: Eq,
+        Q: Equivalent<K> + ?Sized,
     {
         for entry in self.entries.iter_mut() {
-            if key.eq(entry.0.borrow()) {
+            if key.equivalent(&entry.0) {
                 return Some(&mut entry.1);
             }
         }
@@ -62,11 +84,10 @@ impl<K, V> ScanningMap<K, V> {
     #[must_use]
     pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
     where
-        K: Borrow<Q>,
-        Q: Eq,
+        Q: Equivalent<K> + ?Sized,
     {
         for entry in self.entries.iter() {
-            if key.eq(entry.0.borrow()) {
+            if key.equivalent(&entry.0) {
                 return Some((&entry.0, &entry.1));
             }
         }
@@ -74,39 +95,36 @@ impl<K, V> ScanningMap<K, V> {
         None
     }
 
-    #[allow(mutable_transmutes)]
-    pub fn get_many_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> Option<[&mut V; N]>
+    /// Returns references to the values corresponding to several keys at once.
+    #[inline]
+    #[must_use]
+    pub fn get_many<Q, const N: usize>(&self, keys: [&Q; N]) -> [Option<&V>; N]
     where
-        K: Borrow<Q>,
-        Q: Eq,
+        Q: Equivalent<K> + ?Sized,
     {
-        // ensure key uniqueness (assumes "keys" is a relatively small array)
-        for i in 0..keys.len() {
-            for j in 0..i {
-                if keys[j].eq(keys[i]) {
-                    return None;
-                }
-            }
-        }
-
-        unsafe {
-            let mut result: MaybeUninit<[&mut V; N]> = MaybeUninit::uninit();
-            let p = result.as_mut_ptr();
-
-            for (i, key) in keys.iter().enumerate() {
-                *(*p).get_unchecked_mut(i) = transmute(self.get(key)?);
-            }
+        keys.map(|key| self.get(key))
+    }
 
-            Some(result.assume_init())
-        }
+    /// Returns mutable references to the values of several keys at once.
+    ///
+    /// Returns `None` if any key is missing, or if two keys match the same entry.
+    pub fn get_many_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> Option<[&mut V; N]>
+    where
+        Q: Equivalent<K> + ?Sized,
+    {
+        let indices = keys.map(|key| {
+            self.entries
+                .iter()
+                .position(|entry| key.equivalent(&entry.0))
+        });
+        disjoint_values_mut(&mut self.entries, indices)
     }
 
     #[inline]
     #[must_use]
     pub fn contains_key<Q>(&self, key: &Q) -> bool
     where
-        K: Borrow<Q>,
-        Q: Eq,
+        Q: Equivalent<K> + ?Sized,
     {
         self.get(key).is_some()
     }
@@ -125,6 +143,59 @@ impl<K, V> ScanningMap<K, V> {
     pub const fn values(&self) -> Values<K, V> {
         Values::new(&self.entries)
     }
+
+    #[must_use]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut::new(&mut self.entries)
+    }
+
+    #[must_use]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut::new(&mut self.entries)
+    }
+
+    #[must_use]
+    pub fn into_keys(self) -> IntoKeys<K, V> {
+        IntoKeys::new(self.entries)
+    }
+
+    #[must_use]
+    pub fn into_values(self) -> IntoValues<K, V> {
+        IntoValues::new(self.entries)
+    }
+
+    pub(crate) fn map_values<V2, F>(self, f: F) -> ScanningMap<K, V2>
+    where
+        F: FnMut(V) -> V2,
+    {
+        ScanningMap {
+            entries: map_entries(self.entries, f),
+        }
+    }
+}
+
+impl<K, V> ScanningMap<K, V> {
+    /// Describes the layout of the map.
+    #[must_use]
+    pub fn stats(&self) -> MapStats {
+        MapStats::scanning::<K, V>(self.entries.len(), 0)
+    }
+}
+
+impl<K, V> ScanningMap<K, V> {
+    /// Creates an empty map without allocating.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            entries: Box::default(),
+        }
+    }
+}
+
+impl<K, V> Default for ScanningMap<K, V> {
+    fn default() -> Self {
+        Self::empty()
+    }
 }
 
 impl<K, V> Len for ScanningMap<K, V> {
@@ -133,6 +204,59 @@ impl<K, V> Len for ScanningMap<K, V> {
     }
 }
 
+impl<K, V> HeapSize for ScanningMap<K, V>
+where
+    K: HeapSize,
+    V: HeapSize,
+{
+    fn heap_bytes(&self) -> usize {
+        self.stats().heap_bytes() + entries_heap_bytes(self.iter())
+    }
+}
+
+impl<K, V> Map<K, V> for ScanningMap<K, V>
+where
+    K: Eq,
+{
+    type Iterator<'a>
+        = Iter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    type KeyIterator<'a>
+        = Keys<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    type ValueIterator<'a>
+        = Values<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    fn iter(&self) -> Iter<'_, K, V> {
+        self.iter()
+    }
+
+    fn keys(&self) -> Keys<'_, K, V> {
+        self.keys()
+    }
+
+    fn values(&self) -> Values<'_, K, V> {
+        self.values()
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+}
+
 impl<K, V> Debug for ScanningMap<K, V>
 where
     K: Debug,
@@ -146,8 +270,7 @@ where
 
 impl<Q, K, V> Index<Q> for ScanningMap<K, V>
 where
-    K: Borrow<Q>,
-    Q: Eq,
+    Q: Equivalent<K>,
 {
     type Output = V;
 
@@ -158,14 +281,22 @@ where
 
 impl<Q, K, V> IndexMut<Q> for ScanningMap<K, V>
 where
-    K: Borrow<Q>,
-    Q: Eq,
+    Q: Equivalent<K>,
 {
     fn index_mut(&mut self, index: Q) -> &mut V {
         self.get_mut(&index).unwrap()
     }
 }
 
+impl<K, V> IntoIterator for ScanningMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.entries)
+    }
+}
+
 impl<'a, K, V> IntoIterator for &'a ScanningMap<K, V> {
     type Item = (&'a K, &'a V);
     type IntoIter = Iter<'a, K, V>;
@@ -175,6 +306,15 @@ impl<'a, K, V> IntoIterator for &'a ScanningMap<K, V> {
     }
 }
 
+impl<'a, K, V> IntoIterator for &'a mut ScanningMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
 impl<K, V> PartialEq<Self> for ScanningMap<K, V>
 where
     K: Eq,
@@ -197,6 +337,16 @@ where
 {
 }
 
+impl<K, V> Hash for ScanningMap<K, V>
+where
+    K: Hash,
+    V: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self, state);
+    }
+}
+
 impl<K, V, const N: usize> From<[(K, V); N]> for ScanningMap<K, V>
 where
     K: Eq,
@@ -215,6 +365,18 @@ where
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl<K, V> Zeroize for ScanningMap<K, V>
+where
+    K: Zeroize,
+    V: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.entries.zeroize();
+        self.entries = Box::default();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::traits::len::Len;
@@ -228,6 +390,24 @@ mod tests {
         assert_eq!(payload.len(), map.len());
     }
 
+    #[test]
+    fn mutable_iterators_update_values_in_place() {
+        let mut map = ScanningMap::<i32, i32>::from_vec(vec![(10, 20), (30, 40), (50, 60)]);
+        for (k, v) in &mut map {
+            *v += k;
+        }
+
+        assert_eq!(3, map.values_mut().len());
+        assert_eq!("[30, 70, 110]", format!("{:?}", map.values_mut()));
+
+        for v in map.values_mut() {
+            *v /= 10;
+        }
+
+        assert_eq!(Some(&3), map.get(&10));
+        assert_eq!(Some(&11), map.get(&50));
+    }
+
     #[test]
     fn get_returns_some_for_existing_keys() {
         let payload = vec![(10, 20), (30, 40), (50, 60)];
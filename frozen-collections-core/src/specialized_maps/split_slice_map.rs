@@ -0,0 +1,607 @@
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::RandomState;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::ops::Range;
+use std::ops::{Index, IndexMut};
+
+use crate::analyzers::hash_code_analyzer::{
+    analyze_hash_codes, analyze_hash_codes_with, HashCodeAnalysisOptions,
+};
+use crate::errors::FrozenError;
+use crate::hashers::hash_unordered;
+use crate::specialized_maps::hash_table::{disjoint_values_mut, HashTable};
+use crate::specialized_maps::key_codes::{combine_codes, split_slice_code};
+use crate::specialized_maps::{
+    IntoIter, IntoKeys, IntoValues, Iter, IterMut, Keys, MapStats, Values, ValuesMut,
+};
+use crate::traits::equivalent::Equivalent;
+use crate::traits::heap_size::{entries_heap_bytes, HeapSize};
+use crate::traits::len::Len;
+use crate::traits::map::Map;
+use crate::traits::slice_hash::SliceHash;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// A map that hashes a left-aligned slice of its keys together with a right-aligned slice.
+///
+/// This suits keys which vary at both ends, like `us-east-1-i-0abc123`, where neither end alone
+/// tells the keys apart.
+#[derive(Clone)]
+pub struct SplitSliceMap<K, V, BH = RandomState> {
+    pub(crate) table: HashTable<K, V>,
+    bh: BH,
+    pub(crate) left: Range<usize>,
+    pub(crate) right: Range<usize>,
+}
+
+impl<K, V, BH> SplitSliceMap<K, V, BH>
+where
+    K: SliceHash + Len + Eq,
+    BH: BuildHasher,
+{
+    #[must_use]
+    pub fn from_vec_with_hasher(
+        payload: Vec<(K, V)>,
+        left: Range<usize>,
+        right: Range<usize>,
+        bh: BH,
+    ) -> Self {
+        let codes = payload
+            .iter()
+            .map(|entry| split_slice_hash(&entry.0, &bh, &left, &right));
+
+        let code_analysis = analyze_hash_codes(codes);
+        Self {
+            table: HashTable::from_analysis(payload, code_analysis, false, |k| {
+                split_slice_hash(k, &bh, &left, &right)
+            }),
+            bh,
+            left,
+            right,
+        }
+    }
+
+    /// Creates a map, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys.
+    pub fn try_from_vec_with_hasher(
+        payload: Vec<(K, V)>,
+        left: Range<usize>,
+        right: Range<usize>,
+        bh: BH,
+    ) -> std::result::Result<Self, FrozenError> {
+        let codes = payload
+            .iter()
+            .map(|entry| split_slice_hash(&entry.0, &bh, &left, &right));
+
+        let code_analysis = analyze_hash_codes(codes);
+        Ok(Self {
+            table: HashTable::try_from_analysis(payload, code_analysis, |k| {
+                split_slice_hash(k, &bh, &left, &right)
+            })?,
+            bh,
+            left,
+            right,
+        })
+    }
+
+    #[must_use]
+    pub fn from_iter_with_hasher<T: IntoIterator<Item = (K, V)>>(
+        iter: T,
+        left: Range<usize>,
+        right: Range<usize>,
+        bh: BH,
+    ) -> Self {
+        Self::from_vec_with_hasher(Vec::from_iter(iter), left, right, bh)
+    }
+
+    #[must_use]
+    pub fn with_hasher<const N: usize>(
+        payload: [(K, V); N],
+        left: Range<usize>,
+        right: Range<usize>,
+        bh: BH,
+    ) -> Self {
+        Self::from_vec_with_hasher(Vec::from_iter(payload), left, right, bh)
+    }
+}
+
+impl<K, V, BH> SplitSliceMap<K, V, BH>
+where
+    K: Hash + Eq,
+    BH: BuildHasher,
+{
+    /// Creates a map which hashes the bytes keys write to a [`Hasher`](std::hash::Hasher)
+    /// instead of going through [`SliceHash`].
+    ///
+    /// The map must then be probed with [`Self::find`], which hashes keys the same way. For
+    /// `String` keys, both approaches produce the same hash codes. The hash table is sized
+    /// within the given limits.
+    pub(crate) fn from_hashed_vec(
+        payload: Vec<(K, V)>,
+        left: Range<usize>,
+        right: Range<usize>,
+        bh: BH,
+        sizing: &HashCodeAnalysisOptions,
+    ) -> Self {
+        let codes = payload
+            .iter()
+            .map(|entry| split_slice_code(&entry.0, &bh, &left, &right));
+        let code_analysis = analyze_hash_codes_with(codes, sizing);
+
+        Self {
+            table: HashTable::from_analysis(payload, code_analysis, sizing.prefilter, |k| {
+                split_slice_code(k, &bh, &left, &right)
+            }),
+            bh,
+            left,
+            right,
+        }
+    }
+
+    /// Finds the entry holding a key, in a map created with [`Self::from_hashed_vec`].
+    #[inline]
+    pub(crate) fn find<Q>(&self, key: &Q) -> Option<&(K, V)>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.table.find(
+            split_slice_code(key, &self.bh, &self.left, &self.right),
+            key,
+        )
+    }
+
+    /// Finds the entries holding several keys, in a map created with [`Self::from_hashed_vec`].
+    #[inline]
+    pub(crate) fn find_many<Q, const N: usize>(&self, keys: [&Q; N]) -> [Option<&(K, V)>; N]
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let hash_codes = keys.map(|key| split_slice_code(key, &self.bh, &self.left, &self.right));
+        self.table.find_many(hash_codes, keys)
+    }
+
+    /// Locates the entries which may hold a key and starts loading them into the cache, in a
+    /// map created with [`Self::from_hashed_vec`].
+    ///
+    /// Returns the key's hash code along with the entries, which together finish the lookup
+    /// through [`HashTable::find_located`].
+    #[inline]
+    pub(crate) fn locate<Q>(&self, key: &Q) -> (u64, Range<usize>)
+    where
+        Q: ?Sized + Hash,
+    {
+        let hash_code = split_slice_code(key, &self.bh, &self.left, &self.right);
+        (hash_code, self.table.locate(hash_code))
+    }
+
+    /// Finds the entry holding a key, in a map created with [`Self::from_hashed_vec`].
+    #[inline]
+    pub(crate) fn find_mut<Q>(&mut self, key: &Q) -> Option<&mut (K, V)>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.table.find_mut(
+            split_slice_code(key, &self.bh, &self.left, &self.right),
+            key,
+        )
+    }
+}
+
+impl<K, V, BH> SplitSliceMap<K, V, BH>
+where
+    BH: BuildHasher,
+{
+    #[inline]
+    #[must_use]
+    fn get_hash_info<Q>(&self, key: &Q) -> Range<usize>
+    where
+        Q: SliceHash + Len + ?Sized,
+    {
+        self.table
+            .get_hash_info(split_slice_hash(key, &self.bh, &self.left, &self.right))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: SliceHash + Len + Equivalent<K> + ?Sized,
+    {
+        let range = self.get_hash_info(key);
+        let entries = unsafe { self.table.entries.get_unchecked(range) };
+        for entry in entries {
+            if key.equivalent(&entry.0) {
+                return Some(&entry.1);
+            }
+        }
+
+        None
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        Q: SliceHash + Len + Equivalent<K> + ?Sized,
+    {
+        let range = self.get_hash_info(key);
+        let entries = unsafe { self.table.entries.get_unchecked(range) };
+        for entry in entries {
+            if key.equivalent(&entry.0) {
+                return Some((&entry.0, &entry.1));
+            }
+        }
+
+        None
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        Q: SliceHash + Len + Equivalent<K> + ?Sized,
+    {
+        let range = self.get_hash_info(key);
+        let entries = unsafe { self.table.entries.get_unchecked_mut(range) };
+        for entry in entries {
+            if key.equivalent(&entry.0) {
+                return Some(&mut entry.1);
+            }
+        }
+
+        None
+    }
+
+    /// Returns references to the values corresponding to several keys at once.
+    ///
+    /// The hash codes of all the keys are computed first and their slots prefetched, so the
+    /// memory latency of the lookups overlaps.
+    #[inline]
+    #[must_use]
+    pub fn get_many<Q, const N: usize>(&self, keys: [&Q; N]) -> [Option<&V>; N]
+    where
+        Q: SliceHash + Len + Equivalent<K> + ?Sized,
+    {
+        let hash_codes = keys.map(|key| split_slice_hash(key, &self.bh, &self.left, &self.right));
+        self.table
+            .find_many(hash_codes, keys)
+            .map(|entry| entry.map(|(_, v)| v))
+    }
+
+    /// Returns mutable references to the values of several keys at once.
+    ///
+    /// Returns `None` if any key is missing, or if two keys match the same entry.
+    pub fn get_many_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> Option<[&mut V; N]>
+    where
+        Q: SliceHash + Len + Equivalent<K> + ?Sized,
+    {
+        let indices = keys.map(|key| self.table.find_index_in(self.get_hash_info(key), key));
+        disjoint_values_mut(&mut self.table.entries, indices)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: SliceHash + Len + Equivalent<K> + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+}
+
+impl<K, V, BH> SplitSliceMap<K, V, BH> {
+    #[must_use]
+    pub const fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(&self.table.entries)
+    }
+
+    #[must_use]
+    pub const fn keys(&self) -> Keys<'_, K, V> {
+        Keys::new(&self.table.entries)
+    }
+
+    #[must_use]
+    pub const fn values(&self) -> Values<'_, K, V> {
+        Values::new(&self.table.entries)
+    }
+
+    #[must_use]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut::new(&mut self.table.entries)
+    }
+
+    #[must_use]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut::new(&mut self.table.entries)
+    }
+
+    #[must_use]
+    pub fn into_keys(self) -> IntoKeys<K, V> {
+        IntoKeys::new(self.table.entries)
+    }
+
+    #[must_use]
+    pub fn into_values(self) -> IntoValues<K, V> {
+        IntoValues::new(self.table.entries)
+    }
+
+    pub(crate) fn map_values<V2, F>(self, f: F) -> SplitSliceMap<K, V2, BH>
+    where
+        F: FnMut(V) -> V2,
+    {
+        SplitSliceMap {
+            table: self.table.map_values(f),
+            bh: self.bh,
+            left: self.left,
+            right: self.right,
+        }
+    }
+
+    #[must_use]
+    pub const fn hasher(&self) -> &BH {
+        &self.bh
+    }
+}
+
+impl<K, V> SplitSliceMap<K, V, RandomState>
+where
+    K: SliceHash + Len + Eq,
+{
+    #[must_use]
+    pub fn from_vec(payload: Vec<(K, V)>, left: Range<usize>, right: Range<usize>) -> Self {
+        Self::from_vec_with_hasher(payload, left, right, RandomState::new())
+    }
+
+    /// Creates a map, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys.
+    pub fn try_from_vec(
+        payload: Vec<(K, V)>,
+        left: Range<usize>,
+        right: Range<usize>,
+    ) -> std::result::Result<Self, FrozenError> {
+        Self::try_from_vec_with_hasher(payload, left, right, RandomState::new())
+    }
+
+    #[must_use]
+    pub fn from_iter<T: IntoIterator<Item = (K, V)>>(
+        iter: T,
+        left: Range<usize>,
+        right: Range<usize>,
+    ) -> Self {
+        Self::from_iter_with_hasher(iter, left, right, RandomState::new())
+    }
+
+    #[must_use]
+    pub fn from<const N: usize>(
+        payload: [(K, V); N],
+        left: Range<usize>,
+        right: Range<usize>,
+    ) -> Self {
+        Self::with_hasher(payload, left, right, RandomState::new())
+    }
+}
+
+impl<K, V, BH> SplitSliceMap<K, V, BH> {
+    /// Describes the layout of the map.
+    #[must_use]
+    pub fn stats(&self) -> MapStats {
+        self.table.stats()
+    }
+}
+
+impl<K, V, BH> SplitSliceMap<K, V, BH> {
+    /// Creates an empty map which will use the given hash builder, without allocating.
+    #[must_use]
+    pub fn empty_with_hasher(bh: BH) -> Self {
+        Self {
+            table: HashTable::empty(),
+            bh,
+            left: 0..0,
+            right: 0..0,
+        }
+    }
+}
+
+impl<K, V, BH> Default for SplitSliceMap<K, V, BH>
+where
+    BH: Default,
+{
+    fn default() -> Self {
+        Self::empty_with_hasher(BH::default())
+    }
+}
+
+impl<K, V, BH> Len for SplitSliceMap<K, V, BH> {
+    fn len(&self) -> usize {
+        self.table.len()
+    }
+}
+
+impl<K, V, BH> HeapSize for SplitSliceMap<K, V, BH>
+where
+    K: HeapSize,
+    V: HeapSize,
+{
+    fn heap_bytes(&self) -> usize {
+        self.stats().heap_bytes() + entries_heap_bytes(self.iter())
+    }
+}
+
+impl<K, V, BH> Map<K, V> for SplitSliceMap<K, V, BH>
+where
+    K: SliceHash + Len + Eq,
+    BH: BuildHasher,
+{
+    type Iterator<'a>
+        = Iter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+        BH: 'a;
+
+    type KeyIterator<'a>
+        = Keys<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+        BH: 'a;
+
+    type ValueIterator<'a>
+        = Values<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+        BH: 'a;
+
+    fn iter(&self) -> Iter<'_, K, V> {
+        self.iter()
+    }
+
+    fn keys(&self) -> Keys<'_, K, V> {
+        self.keys()
+    }
+
+    fn values(&self) -> Values<'_, K, V> {
+        self.values()
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+}
+
+impl<K, V, BH> Debug for SplitSliceMap<K, V, BH>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.table.fmt(f)
+    }
+}
+
+impl<Q, K, V, BH> Index<Q> for SplitSliceMap<K, V, BH>
+where
+    Q: SliceHash + Len + Equivalent<K>,
+    BH: BuildHasher,
+{
+    type Output = V;
+
+    fn index(&self, index: Q) -> &Self::Output {
+        self.get(&index).unwrap()
+    }
+}
+
+impl<Q, K, V, BH> IndexMut<Q> for SplitSliceMap<K, V, BH>
+where
+    Q: SliceHash + Len + Equivalent<K>,
+    BH: BuildHasher,
+{
+    fn index_mut(&mut self, index: Q) -> &mut V {
+        self.get_mut(&index).unwrap()
+    }
+}
+
+impl<K, V, BH> IntoIterator for SplitSliceMap<K, V, BH> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.table.entries)
+    }
+}
+
+impl<'a, K, V, BH> IntoIterator for &'a SplitSliceMap<K, V, BH> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, BH> IntoIterator for &'a mut SplitSliceMap<K, V, BH> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K, V, BH> PartialEq<Self> for SplitSliceMap<K, V, BH>
+where
+    K: SliceHash + Len + Eq,
+    V: PartialEq,
+    BH: BuildHasher,
+{
+    fn eq(&self, other: &Self) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+
+        self.iter()
+            .all(|(key, value)| other.get(key).is_some_and(|v| *value == *v))
+    }
+}
+
+impl<K, V, BH> Eq for SplitSliceMap<K, V, BH>
+where
+    K: SliceHash + Len + Eq,
+    V: Eq,
+    BH: BuildHasher,
+{
+}
+
+impl<K, V, BH> Hash for SplitSliceMap<K, V, BH>
+where
+    K: Hash,
+    V: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self, state);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<K, V, BH> Zeroize for SplitSliceMap<K, V, BH>
+where
+    K: Zeroize,
+    V: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.table.zeroize();
+    }
+}
+
+/// Hashes the subslices of a key selected by a range measured from the start of the key and a
+/// range measured from its end.
+///
+/// Keys too short to contain both subslices get a code of zero.
+#[inline]
+fn split_slice_hash<Q, BH>(key: &Q, bh: &BH, left: &Range<usize>, right: &Range<usize>) -> u64
+where
+    Q: SliceHash + Len + ?Sized,
+    BH: BuildHasher,
+{
+    let len = key.len();
+    if len >= left.end && len >= right.end {
+        combine_codes(
+            key.hash(bh, left.clone()),
+            key.hash(bh, len - right.end..len - right.start),
+        )
+    } else {
+        0
+    }
+}
@@ -0,0 +1,576 @@
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::{BuildHasher, BuildHasherDefault, Hash, Hasher};
+use std::num::NonZeroU64;
+use std::ops::{Index, Range};
+
+use bitvec::macros::internal::funty::Fundamental;
+
+use crate::analyzers::hash_code_analyzer::analyze_hash_codes;
+use crate::analyzers::slice_key_analyzer::{analyze_slice_keys, SliceKeyAnalysisResult};
+use crate::errors::FrozenError;
+use crate::specialized_maps::hash_table::{slot_index, HashTable};
+use crate::specialized_maps::key_codes::{
+    capture_bytes, int_code, left_slice_code, length_code, length_slice_code, probe_int,
+    right_slice_code, split_slice_code,
+};
+use crate::specialized_maps::{Iter, Keys, Values};
+use crate::traits::equivalent::Equivalent;
+use crate::traits::len::Len;
+use crate::traits::map::Map;
+
+/// The hasher used by static maps and zero-copy maps.
+///
+/// The hash codes of a static map are computed by a build script, and those of a zero-copy map
+/// by whichever program wrote its archive, so they must come out the same in the program which
+/// looks keys up. The algorithm is owned by this crate rather than taken from the standard
+/// library, which may change its default algorithm between releases, and integers are written
+/// in little-endian order so the codes agree across platforms. Slices of integers other than
+/// bytes are the exception, as the standard library hashes them from their native-endian bytes.
+///
+/// The bytes are hashed with FNV-1a, and the result goes through a final mix which spreads
+/// its bits, since slots are picked from the remainder of the hash code.
+#[derive(Clone, Copy, Debug)]
+struct StaticHasher {
+    state: u64,
+}
+
+impl Default for StaticHasher {
+    fn default() -> Self {
+        Self {
+            state: 0xcbf2_9ce4_8422_2325,
+        }
+    }
+}
+
+macro_rules! write_le {
+    ($($name:ident: $t:ty),*) => {
+        $(
+            fn $name(&mut self, i: $t) {
+                self.write(&i.to_le_bytes());
+            }
+        )*
+    };
+}
+
+impl Hasher for StaticHasher {
+    fn finish(&self) -> u64 {
+        let mut h = self.state;
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xff51_afd7_ed55_8ccd);
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+        h ^ (h >> 33)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.state ^= u64::from(b);
+            self.state = self.state.wrapping_mul(0x100_0000_01b3);
+        }
+    }
+
+    write_le!(
+        write_u16: u16,
+        write_u32: u32,
+        write_u64: u64,
+        write_u128: u128,
+        write_i16: i16,
+        write_i32: i32,
+        write_i64: i64,
+        write_i128: i128
+    );
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_i64(i as i64);
+    }
+}
+
+type StaticHashBuilder = BuildHasherDefault<StaticHasher>;
+
+/// Returns the hash code of a fixed value, which is stored in zero-copy archives so an archive
+/// written with a different hashing algorithm is rejected rather than silently missing keys.
+#[cfg(any(feature = "rkyv", test))]
+pub fn probe_code() -> u64 {
+    StaticHashBuilder::default().hash_one(("frozen-collections", 0x0123_4567_89ab_cdef_u64))
+}
+
+/// How a static map derives hash codes from its keys.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub enum StaticKeyCode {
+    /// Use the integer written by each key as its hash code.
+    Integer,
+
+    /// Use the length of the bytes written by each key as its hash code.
+    Length,
+
+    /// Hash a left-aligned subslice of the bytes written by each key.
+    LeftSlice(Range<usize>),
+
+    /// Hash a right-aligned subslice of the bytes written by each key, with the range measured
+    /// from the end of the bytes.
+    RightSlice(Range<usize>),
+
+    /// Hash a left-aligned subslice together with a right-aligned subslice of the bytes written
+    /// by each key, with the second range measured from the end of the bytes.
+    SplitSlice(Range<usize>, Range<usize>),
+
+    /// Hash a left-aligned subslice of the bytes written by each key together with the length
+    /// of the bytes.
+    LengthSlice(Range<usize>),
+
+    /// Hash each key in full.
+    Hashed,
+}
+
+impl StaticKeyCode {
+    #[inline]
+    pub(crate) fn code<Q>(&self, key: &Q) -> u64
+    where
+        Q: ?Sized + Hash,
+    {
+        let bh = StaticHashBuilder::default();
+        match self {
+            Self::Integer => int_code(key),
+            Self::Length => length_code(key),
+            Self::LeftSlice(range) => left_slice_code(key, &bh, range),
+            Self::RightSlice(range) => right_slice_code(key, &bh, range),
+            Self::SplitSlice(left, right) => split_slice_code(key, &bh, left, right),
+            Self::LengthSlice(range) => length_slice_code(key, &bh, range),
+            Self::Hashed => bh.hash_one(key),
+        }
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl ArchivedStaticKeyCode {
+    /// Reads the archived key code back, which only copies a couple of integers.
+    pub(crate) fn to_native(&self) -> StaticKeyCode {
+        let range = |r: &rkyv::ops::ArchivedRange<rkyv::Archived<usize>>| {
+            r.start.to_native() as usize..r.end.to_native() as usize
+        };
+
+        match self {
+            Self::Integer => StaticKeyCode::Integer,
+            Self::Length => StaticKeyCode::Length,
+            Self::LeftSlice(r) => StaticKeyCode::LeftSlice(range(r)),
+            Self::RightSlice(r) => StaticKeyCode::RightSlice(range(r)),
+            Self::SplitSlice(l, r) => StaticKeyCode::SplitSlice(range(l), range(r)),
+            Self::LengthSlice(r) => StaticKeyCode::LengthSlice(range(r)),
+            Self::Hashed => StaticKeyCode::Hashed,
+        }
+    }
+}
+
+/// Picks how a static map derives hash codes from the keys of the payload, and how many hash
+/// slots to distribute the keys over.
+pub fn analyze_static_layout<K, V>(payload: &[(K, V)]) -> (StaticKeyCode, usize)
+where
+    K: Hash,
+{
+    let key_code = analyze_key_code(payload);
+    let codes = payload.iter().map(|entry| key_code.code(&entry.0));
+    let num_hash_slots = analyze_hash_codes(codes).num_hash_slots;
+    (key_code, num_hash_slots)
+}
+
+/// Lays out the payload of a static map in a hash table, whose slot bounds are then stored as
+/// `u32` values.
+///
+/// # Errors
+///
+/// Fails if the payload holds duplicate keys, or more entries than fit in a `u32`.
+pub fn static_table<K, V>(
+    payload: Vec<(K, V)>,
+) -> std::result::Result<(StaticKeyCode, HashTable<K, V>), FrozenError>
+where
+    K: Hash + Eq,
+{
+    if u32::try_from(payload.len()).is_err() {
+        return Err(FrozenError::CapacityExceeded {
+            len: payload.len(),
+            max: u32::MAX.as_usize(),
+        });
+    }
+
+    let (key_code, num_hash_slots) = analyze_static_layout(&payload);
+    let table = HashTable::try_new(payload, num_hash_slots, |k| key_code.code(k))?;
+    Ok((key_code, table))
+}
+
+/// Lays out the payload of a static map in a hash table, deriving hash codes from the keys as
+/// `key_code` specifies.
+pub fn static_table_with<K, V>(payload: Vec<(K, V)>, key_code: &StaticKeyCode) -> HashTable<K, V>
+where
+    K: Hash,
+{
+    let codes = payload.iter().map(|entry| key_code.code(&entry.0));
+    let num_hash_slots = analyze_hash_codes(codes).num_hash_slots;
+    HashTable::new(payload, num_hash_slots, |k| key_code.code(k))
+}
+
+/// Picks how a static map derives hash codes from the keys of the payload.
+fn analyze_key_code<K, V>(payload: &[(K, V)]) -> StaticKeyCode
+where
+    K: Hash,
+{
+    if payload.iter().all(|entry| probe_int(&entry.0).is_some()) {
+        return StaticKeyCode::Integer;
+    }
+
+    let mut bytes = Vec::new();
+    let mut bounds = Vec::with_capacity(payload.len());
+    for entry in payload {
+        let start = bytes.len();
+        if !capture_bytes(&entry.0, &mut bytes) {
+            return StaticKeyCode::Hashed;
+        }
+
+        bounds.push(start..bytes.len());
+    }
+
+    let bh = StaticHashBuilder::default();
+    match analyze_slice_keys(bounds.into_iter().map(|r| &bytes[r]), &bh) {
+        SliceKeyAnalysisResult::Normal => StaticKeyCode::Hashed,
+        SliceKeyAnalysisResult::Length => StaticKeyCode::Length,
+        SliceKeyAnalysisResult::LeftHandSubslice {
+            subslice_index,
+            subslice_len,
+        } => StaticKeyCode::LeftSlice(subslice_index..subslice_index + subslice_len),
+        SliceKeyAnalysisResult::RightHandSubslice {
+            subslice_index,
+            subslice_len,
+        } => StaticKeyCode::RightSlice(subslice_index..subslice_index + subslice_len),
+        SliceKeyAnalysisResult::SplitSubslice {
+            left_index,
+            left_len,
+            right_index,
+            right_len,
+        } => StaticKeyCode::SplitSlice(
+            left_index..left_index + left_len,
+            right_index..right_index + right_len,
+        ),
+        SliceKeyAnalysisResult::LengthAndSubslice {
+            subslice_index,
+            subslice_len,
+        } => StaticKeyCode::LengthSlice(subslice_index..subslice_index + subslice_len),
+    }
+}
+
+/// A map whose layout was computed ahead of time, which can be stored in a `static`.
+///
+/// These maps are normally declared by source code emitted by
+/// [`emit_static_map`](crate::codegen::emit_static_map) from a build script, so the work of
+/// analyzing keys and distributing them over hash slots is done when compiling rather than
+/// when the program starts. Lookups are as cheap as those of the other hashed maps.
+///
+/// A layout which doesn't match its entries makes lookups miss, but never causes undefined
+/// behavior.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::specialized_maps::{StaticKeyCode, StaticMap};
+///
+/// static MAP: StaticMap<u32, &str> = StaticMap::from_raw_parts(
+///     StaticKeyCode::Integer,
+///     &[(0, 1), (1, 2), (2, 3)],
+///     &[(3, "three"), (1, "one"), (2, "two")],
+/// );
+///
+/// assert_eq!(MAP.get(&1), Some(&"one"));
+/// assert_eq!(MAP.get(&4), None);
+/// ```
+pub struct StaticMap<K: 'static, V: 'static> {
+    key_code: StaticKeyCode,
+    slots: &'static [(u32, u32)],
+    entries: &'static [(K, V)],
+}
+
+impl<K, V> StaticMap<K, V> {
+    /// Creates a map from a precomputed layout.
+    ///
+    /// Each slot holds the range of `entries` whose keys have a hash code which lands in that
+    /// slot, and the hash code of a key is the one given by `key_code`. This is meant to be
+    /// called by code emitted by [`emit_static_map`](crate::codegen::emit_static_map), which
+    /// computes a consistent layout.
+    #[must_use]
+    pub const fn from_raw_parts(
+        key_code: StaticKeyCode,
+        slots: &'static [(u32, u32)],
+        entries: &'static [(K, V)],
+    ) -> Self {
+        Self {
+            key_code,
+            slots,
+            entries,
+        }
+    }
+
+    /// Creates an empty map.
+    ///
+    /// Unlike the other maps, this can be used to initialize a `static` or `const` item,
+    /// since the map borrows its layout rather than owning it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::specialized_maps::StaticMap;
+    ///
+    /// static MAP: StaticMap<&str, u32> = StaticMap::empty();
+    ///
+    /// assert!(MAP.is_empty());
+    /// assert_eq!(MAP.get("a"), None);
+    /// ```
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self::from_raw_parts(StaticKeyCode::Length, &[], &[])
+    }
+
+    #[inline]
+    fn find<Q>(&self, key: &Q) -> Option<&'static (K, V)>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let num_slots = NonZeroU64::new(self.slots.len() as u64)?;
+        let hash_code = self.key_code.code(key);
+        let &(start, end) = self.slots.get(slot_index(hash_code, num_slots))?;
+        let entries = self.entries.get(start as usize..end as usize)?;
+        entries.iter().find(|entry| key.equivalent(&entry.0))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.find(key).map(|entry| &entry.1)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.find(key).map(|entry| (&entry.0, &entry.1))
+    }
+
+    /// Returns references to the values corresponding to several keys at once.
+    #[inline]
+    #[must_use]
+    pub fn get_many<Q, const N: usize>(&self, keys: [&Q; N]) -> [Option<&V>; N]
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        keys.map(|key| self.get(key))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.find(key).is_some()
+    }
+
+    #[must_use]
+    pub const fn iter(&self) -> Iter<'static, K, V> {
+        Iter::new(self.entries)
+    }
+
+    #[must_use]
+    pub const fn keys(&self) -> Keys<'static, K, V> {
+        Keys::new(self.entries)
+    }
+
+    #[must_use]
+    pub const fn values(&self) -> Values<'static, K, V> {
+        Values::new(self.entries)
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<K, V> Len for StaticMap<K, V> {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl<K, V> Debug for StaticMap<K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let pairs = self.entries.iter().map(|x| (&x.0, &x.1));
+        f.debug_map().entries(pairs).finish()
+    }
+}
+
+impl<Q, K, V> Index<&Q> for StaticMap<K, V>
+where
+    Q: ?Sized + Hash + Equivalent<K>,
+{
+    type Output = V;
+
+    fn index(&self, index: &Q) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<K, V> IntoIterator for &StaticMap<K, V> {
+    type Item = (&'static K, &'static V);
+    type IntoIter = Iter<'static, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V> Map<K, V> for StaticMap<K, V>
+where
+    K: Hash + Eq,
+{
+    type Iterator<'a>
+        = Iter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    type KeyIterator<'a>
+        = Keys<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    type ValueIterator<'a>
+        = Values<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    fn iter(&self) -> Iter<'_, K, V> {
+        self.iter()
+    }
+
+    fn keys(&self) -> Keys<'_, K, V> {
+        self.keys()
+    }
+
+    fn values(&self) -> Values<'_, K, V> {
+        self.values()
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // emitted by emit_static_map("COLORS", "&str", "u32", ...)
+    static COLORS: StaticMap<&str, u32> = StaticMap::from_raw_parts(
+        StaticKeyCode::Length,
+        &[(0, 1), (1, 2), (2, 3), (3, 4)],
+        &[("blue", 2), ("green", 3), ("purple", 4), ("red", 1)],
+    );
+
+    #[test]
+    fn lookups() {
+        assert_eq!(COLORS.len(), 4);
+        assert_eq!(COLORS.get("red"), Some(&1));
+        assert_eq!(COLORS.get("purple"), Some(&4));
+        assert_eq!(COLORS["green"], 3);
+        assert_eq!(COLORS.get_key_value("blue"), Some((&"blue", &2)));
+        assert_eq!(COLORS.get("black"), None);
+        assert_eq!(COLORS.get("orange"), None);
+        assert!(!COLORS.contains_key("blues"));
+    }
+
+    #[test]
+    fn empty() {
+        const EMPTY: StaticMap<String, u32> = StaticMap::empty();
+
+        assert!(EMPTY.is_empty());
+        assert_eq!(EMPTY.get("a"), None);
+        assert_eq!(EMPTY.iter().count(), 0);
+    }
+
+    #[test]
+    fn byte_slice_keys() {
+        static BYTES: StaticMap<&[u8], char> = StaticMap::from_raw_parts(
+            StaticKeyCode::Length,
+            &[(0, 1), (1, 2)],
+            &[(&[1, 2], 'a'), (&[3], 'b')],
+        );
+
+        assert_eq!(BYTES.get(&[1u8, 2][..]), Some(&'a'));
+        assert_eq!(BYTES.get(&[3u8][..]), Some(&'b'));
+        assert_eq!(BYTES.get(&[4u8][..]), None);
+    }
+
+    #[test]
+    fn bad_layouts_miss() {
+        static MAP: StaticMap<u32, u32> =
+            StaticMap::from_raw_parts(StaticKeyCode::Integer, &[(0, 9), (2, 1)], &[(0, 0)]);
+
+        static EMPTY: StaticMap<u32, u32> =
+            StaticMap::from_raw_parts(StaticKeyCode::Integer, &[], &[]);
+
+        assert_eq!(MAP.get(&0), None);
+        assert_eq!(MAP.get(&1), None);
+        assert!(EMPTY.is_empty());
+        assert_eq!(EMPTY.get(&0), None);
+    }
+
+    #[test]
+    fn key_codes() {
+        assert_eq!(
+            analyze_key_code(&[(1u8, ()), (2, ())]),
+            StaticKeyCode::Integer
+        );
+        assert_eq!(
+            analyze_key_code(&[((1, 2), ()), ((3, 4), ())]),
+            StaticKeyCode::Hashed
+        );
+
+        let keys: Vec<_> = (0..100).map(|i| (format!("prefix-{i:03}"), ())).collect();
+        assert_eq!(analyze_key_code(&keys), StaticKeyCode::LeftSlice(8..10));
+    }
+
+    #[test]
+    fn stable_hash_codes() {
+        // these codes are baked into build script output and archives, so they must never
+        // change without also changing the probe stored in archives
+        let bh = StaticHashBuilder::default();
+        assert_eq!(0xef15_5762_c4cb_2c57, bh.hash_one("key"));
+        assert_eq!(0xa624_5a5d_cf27_8758, bh.hash_one(42u64));
+        assert_eq!(bh.hash_one(42usize), bh.hash_one(42u64));
+        assert_eq!(0xd0e9_c66a_fb15_be5d, probe_code());
+    }
+}
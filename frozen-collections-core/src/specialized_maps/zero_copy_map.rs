@@ -0,0 +1,339 @@
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter, Result};
+use std::hash::Hash;
+use std::num::NonZeroU64;
+
+use rkyv::bytecheck::Verify;
+use rkyv::rancor::{fail, Fallible, Source};
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::errors::FrozenError;
+use crate::specialized_maps::hash_table::{slot_index, HashTable};
+use crate::specialized_maps::static_map::{analyze_static_layout, probe_code, static_table};
+use crate::specialized_maps::{Iter, Keys, StaticKeyCode, Values};
+use crate::traits::equivalent::Equivalent;
+use crate::traits::len::Len;
+
+/// A map which can be archived with [`rkyv`] and used straight from the archive.
+///
+/// The map is analyzed and laid out like a [`StaticMap`](crate::specialized_maps::StaticMap)
+/// when created. Serializing it writes that layout to a byte buffer, and accessing the buffer
+/// later, for instance after memory-mapping the file it was saved to, validates it and gives an
+/// [`ArchivedZeroCopyMap`] which looks keys up in place, with no deserialization pass and
+/// without analyzing the keys again. This is only available when the `rkyv` feature is enabled.
+///
+/// Hash codes come from a hashing algorithm owned by this crate, so an archive can be read by
+/// programs built with other toolchains, or for other platforms, than the program which wrote
+/// it. The archive also records the hash code of a fixed probe value, and accessing an archive
+/// written with a different algorithm fails validation instead of making lookups miss.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::specialized_maps::{ArchivedZeroCopyMap, ZeroCopyMap};
+///
+/// let map = ZeroCopyMap::from_vec(vec![("red".to_string(), 1), ("green".to_string(), 2)]);
+/// let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&map).unwrap();
+///
+/// let archived =
+///     rkyv::access::<ArchivedZeroCopyMap<String, i32>, rkyv::rancor::Error>(&bytes).unwrap();
+///
+/// assert_eq!(*archived.get("green").unwrap(), 2);
+/// assert_eq!(archived.get("blue"), None);
+/// ```
+#[derive(Clone, Archive, Serialize, Deserialize)]
+#[rkyv(bytecheck(verify))]
+pub struct ZeroCopyMap<K, V> {
+    probe: u64,
+    key_code: StaticKeyCode,
+    slots: Box<[(u32, u32)]>,
+    entries: Box<[(K, V)]>,
+}
+
+impl<K, V> ZeroCopyMap<K, V>
+where
+    K: Hash + Eq,
+{
+    /// # Panics
+    ///
+    /// Panics if the payload holds more entries than fit in a `u32`.
+    #[must_use]
+    pub fn from_vec(payload: Vec<(K, V)>) -> Self {
+        assert!(
+            u32::try_from(payload.len()).is_ok(),
+            "Too many payload entries for a zero-copy map"
+        );
+
+        let (key_code, num_hash_slots) = analyze_static_layout(&payload);
+        let table = HashTable::new(payload, num_hash_slots, |k| key_code.code(k));
+        Self::from_table(key_code, table)
+    }
+
+    /// Creates a map, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate keys, or more entries than fit in a `u32`.
+    pub fn try_from_vec(payload: Vec<(K, V)>) -> std::result::Result<Self, FrozenError> {
+        let (key_code, table) = static_table(payload)?;
+        Ok(Self::from_table(key_code, table))
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn from_table(key_code: StaticKeyCode, table: HashTable<K, V>) -> Self {
+        // the table holds at most u32::MAX entries, so its slot bounds fit in a u32
+        Self {
+            probe: probe_code(),
+            key_code,
+            slots: table
+                .slot_ranges()
+                .map(|r| (r.start as u32, r.end as u32))
+                .collect(),
+            entries: table.entries,
+        }
+    }
+}
+
+impl<K, V> ZeroCopyMap<K, V> {
+    #[inline]
+    fn find<Q>(&self, key: &Q) -> Option<&(K, V)>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let num_slots = NonZeroU64::new(self.slots.len() as u64)?;
+        let hash_code = self.key_code.code(key);
+        let &(start, end) = self.slots.get(slot_index(hash_code, num_slots))?;
+        let entries = self.entries.get(start as usize..end as usize)?;
+        entries.iter().find(|entry| key.equivalent(&entry.0))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.find(key).map(|entry| &entry.1)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        self.find(key).is_some()
+    }
+
+    #[must_use]
+    pub const fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(&self.entries)
+    }
+
+    #[must_use]
+    pub const fn keys(&self) -> Keys<'_, K, V> {
+        Keys::new(&self.entries)
+    }
+
+    #[must_use]
+    pub const fn values(&self) -> Values<'_, K, V> {
+        Values::new(&self.entries)
+    }
+}
+
+impl<K, V> Len for ZeroCopyMap<K, V> {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a ZeroCopyMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V> Debug for ZeroCopyMap<K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let pairs = self.entries.iter().map(|x| (&x.0, &x.1));
+        f.debug_map().entries(pairs).finish()
+    }
+}
+
+/// The error reported when an archive was written with a different hashing algorithm.
+#[derive(Debug)]
+struct ProbeMismatch;
+
+impl Display for ProbeMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "the map was archived with a different hashing algorithm")
+    }
+}
+
+impl Error for ProbeMismatch {}
+
+// SAFETY: the probe is the only invariant added on top of the checks of the fields, and an
+// archive with the wrong probe is rejected
+unsafe impl<K, V, C> Verify<C> for ArchivedZeroCopyMap<K, V>
+where
+    K: Archive,
+    V: Archive,
+    C: Fallible + ?Sized,
+    C::Error: Source,
+{
+    fn verify(&self, _context: &mut C) -> std::result::Result<(), C::Error> {
+        if self.probe.to_native() != probe_code() {
+            fail!(ProbeMismatch);
+        }
+
+        Ok(())
+    }
+}
+
+impl<K, V> ArchivedZeroCopyMap<K, V>
+where
+    K: Archive,
+    V: Archive,
+{
+    #[inline]
+    fn find<Q>(&self, key: &Q) -> Option<(&K::Archived, &V::Archived)>
+    where
+        K::Archived: PartialEq<Q>,
+        Q: ?Sized + Hash,
+    {
+        // the layout was validated as well-formed data, but not as consistent, so every index is
+        // checked and an inconsistent layout makes lookups miss
+        let num_slots = NonZeroU64::new(self.slots.len() as u64)?;
+        let hash_code = self.key_code.to_native().code(key);
+        let slot = (*self.slots).get(slot_index(hash_code, num_slots))?;
+        let range = slot.0.to_native() as usize..slot.1.to_native() as usize;
+        let entries = (*self.entries).get(range)?;
+        entries
+            .iter()
+            .find(|entry| entry.0 == *key)
+            .map(|entry| (&entry.0, &entry.1))
+    }
+
+    /// Returns a reference to the archived value corresponding to the key.
+    ///
+    /// The key is given in its regular form, such as a `str` for archived strings or an integer
+    /// for archived integers, so it hashes just like the keys the map was created from.
+    #[inline]
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V::Archived>
+    where
+        K::Archived: PartialEq<Q>,
+        Q: ?Sized + Hash,
+    {
+        self.find(key).map(|(_, v)| v)
+    }
+
+    /// Returns the archived key-value pair corresponding to the key.
+    #[inline]
+    #[must_use]
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K::Archived, &V::Archived)>
+    where
+        K::Archived: PartialEq<Q>,
+        Q: ?Sized + Hash,
+    {
+        self.find(key)
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    #[inline]
+    #[must_use]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K::Archived: PartialEq<Q>,
+        Q: ?Sized + Hash,
+    {
+        self.find(key).is_some()
+    }
+
+    /// An iterator visiting all archived entries in arbitrary order.
+    #[must_use]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&K::Archived, &V::Archived)> {
+        self.entries.iter().map(|entry| (&entry.0, &entry.1))
+    }
+
+    /// Returns the number of elements in the map.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rkyv::rancor::Error;
+
+    use super::*;
+
+    #[test]
+    fn archived_lookups() {
+        let payload: Vec<_> = (0..1000).map(|i| (format!("key{i}"), i)).collect();
+        let map = ZeroCopyMap::from_vec(payload);
+        let bytes = rkyv::to_bytes::<Error>(&map).unwrap();
+        let archived = rkyv::access::<ArchivedZeroCopyMap<String, u32>, Error>(&bytes).unwrap();
+
+        assert_eq!(archived.len(), 1000);
+        for i in 0..1000 {
+            let key = format!("key{i}");
+            assert_eq!(map.get(key.as_str()), Some(&i));
+            assert_eq!(archived.get(key.as_str()).map(|v| v.to_native()), Some(i));
+        }
+
+        assert!(!archived.contains_key("key1000"));
+
+        let copy: ZeroCopyMap<String, u32> = rkyv::deserialize::<_, Error>(archived).unwrap();
+        assert_eq!(copy.get("key42"), Some(&42));
+    }
+
+    #[test]
+    fn archived_integer_keys() {
+        let map = ZeroCopyMap::from_vec(vec![(10u64, 1u8), (20, 2), (30, 3), (40, 4)]);
+        let bytes = rkyv::to_bytes::<Error>(&map).unwrap();
+        let archived = rkyv::access::<ArchivedZeroCopyMap<u64, u8>, Error>(&bytes).unwrap();
+
+        assert_eq!(archived.get(&30u64), Some(&3));
+        assert_eq!(archived.get(&50u64), None);
+    }
+
+    #[test]
+    fn corrupt_archives_are_rejected() {
+        let map = ZeroCopyMap::from_vec(vec![(1u32, 1u32), (2, 2)]);
+        let bytes = rkyv::to_bytes::<Error>(&map).unwrap();
+
+        assert!(rkyv::access::<ArchivedZeroCopyMap<u32, u32>, Error>(&bytes[1..]).is_err());
+    }
+
+    #[test]
+    fn archives_from_other_hashers_are_rejected() {
+        let mut map = ZeroCopyMap::from_vec(vec![("a".to_string(), 1u32), ("b".to_string(), 2)]);
+        map.probe ^= 1;
+        let bytes = rkyv::to_bytes::<Error>(&map).unwrap();
+
+        assert!(rkyv::access::<ArchivedZeroCopyMap<String, u32>, Error>(&bytes).is_err());
+    }
+
+    #[test]
+    fn duplicate_keys() {
+        assert_eq!(
+            ZeroCopyMap::try_from_vec(vec![(1, 1), (1, 2)]).err(),
+            Some(FrozenError::DuplicateKey { index: 1 })
+        );
+    }
+}
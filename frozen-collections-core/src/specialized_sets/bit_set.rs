@@ -0,0 +1,740 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::{Hash, Hasher};
+use std::mem::size_of_val;
+use std::ops::{BitAnd, BitOr, BitXor, Range, Sub};
+
+use bitvec::prelude::*;
+use num_traits::{NumCast, PrimInt};
+
+use crate::analyzers::duplicate_key_analyzer::find_duplicate_keys;
+use crate::analyzers::int_key_analyzer::{
+    find_dense_code_range, is_dense, MIN_BIT_SET_DENSITY_PERCENT,
+};
+use crate::errors::FrozenError;
+use crate::hashers::hash_unordered;
+use crate::specialized_maps::key_codes::int_code;
+use crate::specialized_sets::{IntoIter, Iter, Set};
+use crate::traits::equivalent::Equivalent;
+use crate::traits::heap_size::HeapSize;
+use crate::traits::len::Len;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// The number of bits in a word of the bit vector.
+const WORD_BITS: u64 = usize::BITS as u64;
+
+/// A set whose values are integers clustered in a bounded range, stored as a bit vector.
+///
+/// Each integer of the range has a bit telling whether it's in the set, so `contains` is a
+/// subtraction followed by a shift and a mask. Union, intersection, symmetric difference and
+/// difference between two bit sets combine the bit vectors a word at a time.
+///
+/// Values are identified by the integer their [`Hash`] implementation writes, so two values
+/// which write the same integer are considered equal. This holds for primitive integers up to
+/// 64 bits, and for newtypes and fieldless enums over them.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::specialized_sets::BitSet;
+///
+/// let a = BitSet::from_vec(vec![1u32, 3, 5, 7]);
+/// let b = BitSet::from_vec(vec![3u32, 4, 5, 6]);
+///
+/// assert!(a.contains(&5));
+/// assert!(!a.contains(&4));
+/// assert_eq!((&a & &b).iter().copied().collect::<Vec<_>>(), vec![3, 5]);
+/// ```
+#[derive(Clone)]
+pub struct BitSet<T> {
+    /// The code of the value held by the first bit, a multiple of the word size.
+    base: u64,
+
+    bits: BitBox<usize, Lsb0>,
+
+    /// The values, by increasing offset from the base.
+    entries: Box<[(T, ())]>,
+}
+
+impl<T> BitSet<T>
+where
+    T: Hash + Eq,
+{
+    /// # Panics
+    ///
+    /// Panics if the values occupy less than a tenth of the range they cover.
+    #[must_use]
+    pub fn from_vec(payload: Vec<T>) -> Self {
+        Self::try_from_vec(payload)
+            .expect("BitSet requires that the set values densely cover their range")
+    }
+
+    /// Creates a set, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate values, or if the values occupy less than a tenth
+    /// of the range they cover.
+    pub fn try_from_vec(payload: Vec<T>) -> std::result::Result<Self, FrozenError> {
+        if payload.is_empty() {
+            return Ok(Self::empty());
+        }
+
+        let mut entries: Vec<_> = payload.into_iter().map(|v| (v, ())).collect();
+        if let Some(group) = find_duplicate_keys(&entries, int_code).first() {
+            return Err(FrozenError::DuplicateKey { index: group[1] });
+        }
+
+        let mut codes: Vec<_> = entries.iter().map(|x| int_code(&x.0)).collect();
+        let (start, span) = find_dense_code_range(&mut codes, MIN_BIT_SET_DENSITY_PERCENT)
+            .ok_or(FrozenError::SparseRange)?;
+
+        let base = start & !(WORD_BITS - 1);
+        let len = (start - base)
+            .checked_add(span)
+            .and_then(|len| usize::try_from(len).ok())
+            .ok_or(FrozenError::SparseRange)?;
+
+        entries.sort_unstable_by_key(|x| int_code(&x.0).wrapping_sub(base));
+
+        let mut bits = bitbox![usize, Lsb0; 0; len];
+        for entry in &entries {
+            // the codes were sorted relative to the base, so every offset is within the range
+            #[allow(clippy::cast_possible_truncation)]
+            bits.set(int_code(&entry.0).wrapping_sub(base) as usize, true);
+        }
+
+        Ok(Self {
+            base,
+            bits,
+            entries: entries.into_boxed_slice(),
+        })
+    }
+}
+
+impl<T> BitSet<T> {
+    #[inline]
+    fn offset<Q>(&self, value: &Q) -> Option<usize>
+    where
+        Q: ?Sized + Hash,
+    {
+        usize::try_from(int_code(value).wrapping_sub(self.base)).ok()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Hash,
+        Q: ?Sized + Hash + Equivalent<T>,
+    {
+        if !self.contains(value) {
+            return None;
+        }
+
+        let code = int_code(value);
+        let index = self
+            .entries
+            .binary_search_by_key(&code.wrapping_sub(self.base), |x| {
+                int_code(&x.0).wrapping_sub(self.base)
+            })
+            .ok()?;
+
+        Some(&self.entries[index].0)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        Q: ?Sized + Hash + Equivalent<T>,
+    {
+        self.offset(value)
+            .and_then(|offset| self.bits.get(offset))
+            .is_some_and(|bit| *bit)
+    }
+
+    /// An iterator visiting all values in increasing order of the integers they write.
+    #[must_use]
+    pub const fn iter(&self) -> Iter<'_, T> {
+        Iter::new(&self.entries)
+    }
+
+    pub(crate) const fn entries(&self) -> &[(T, ())] {
+        &self.entries
+    }
+
+    /// Returns the number of bytes taken by the bit vector and the values.
+    pub(crate) fn table_bytes(&self) -> usize {
+        size_of_val(self.bits.as_raw_slice()) + size_of_val(&*self.entries)
+    }
+
+    /// Creates an empty set without allocating.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            base: 0,
+            bits: BitBox::default(),
+            entries: Box::default(),
+        }
+    }
+
+    /// Returns the distance between the codes of the smallest and largest values.
+    pub(crate) fn span(&self) -> u64 {
+        match (self.bits.first_one(), self.bits.last_one()) {
+            (Some(first), Some(last)) => (last - first) as u64,
+            _ => 0,
+        }
+    }
+
+    /// Returns whether no value is in both sets.
+    pub(crate) fn is_disjoint_with(&self, other: &Self) -> bool {
+        let (a, b) = self.windows(other);
+        self.word_pairs(other, a.start.max(b.start)..a.end.min(b.end))
+            .all(|(x, y)| x & y == 0)
+    }
+
+    /// Returns whether every value of `self` is in `other`.
+    pub(crate) fn is_subset_of(&self, other: &Self) -> bool {
+        let (a, _) = self.windows(other);
+        self.word_pairs(other, a).all(|(x, y)| x & !y == 0)
+    }
+
+    /// Returns the positions of the words of both sets, relative to the words of `self`.
+    #[allow(clippy::cast_possible_wrap)]
+    fn windows(&self, other: &Self) -> (Range<i64>, Range<i64>) {
+        let shift = self.shift(other);
+        let a = 0..self.bits.as_raw_slice().len() as i64;
+        let b = shift..shift + other.bits.as_raw_slice().len() as i64;
+        (a, b)
+    }
+
+    /// Returns how many words the first word of `other` lies past the first word of `self`.
+    #[allow(clippy::cast_possible_wrap)]
+    const fn shift(&self, other: &Self) -> i64 {
+        // bases are word-aligned, so the words of both sets line up
+        other.base.wrapping_sub(self.base) as i64 / WORD_BITS as i64
+    }
+
+    /// Pairs up the words of both sets over a window of words, given relative to the words of
+    /// `self`. Words past the end of either set read as zero.
+    fn word_pairs<'a>(
+        &'a self,
+        other: &'a Self,
+        window: Range<i64>,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let shift = self.shift(other);
+        let word = |words: &[usize], index: i64| {
+            usize::try_from(index)
+                .ok()
+                .and_then(|i| words.get(i).copied())
+                .unwrap_or(0)
+        };
+
+        let (a, b) = (self.bits.as_raw_slice(), other.bits.as_raw_slice());
+        window.map(move |w| (word(a, w), word(b, w - shift)))
+    }
+
+    /// Returns the code of the value held by the first bit of a window of words.
+    #[allow(clippy::cast_sign_loss)]
+    const fn window_base(&self, window: &Range<i64>) -> u64 {
+        self.base
+            .wrapping_add((window.start as u64).wrapping_mul(WORD_BITS))
+    }
+}
+
+/// Drops the words holding no values from both ends of a bit vector, moving its base past the
+/// leading ones.
+fn trim_words(mut base: u64, mut words: Vec<usize>) -> (u64, Vec<usize>) {
+    while words.last() == Some(&0) {
+        words.pop();
+    }
+
+    let leading = words.iter().take_while(|&&w| w == 0).count();
+    words.drain(..leading);
+    base = base.wrapping_add(leading as u64 * WORD_BITS);
+    (base, words)
+}
+
+impl<T> BitSet<T>
+where
+    T: Hash + Clone,
+{
+    /// Combines two sets a word at a time, like the operators do, but takes the values of the
+    /// result from the entries of both sets rather than converting codes back into values, so
+    /// any type whose values write integers to their hasher can be combined.
+    ///
+    /// Returns `None` when the sets lie too far apart for their combination to be stored
+    /// densely.
+    pub(crate) fn merge<F>(&self, other: &Self, op: F) -> Option<Self>
+    where
+        F: Fn(usize, usize) -> usize,
+    {
+        let (a, b) = self.windows(other);
+        let window = match (self.entries.is_empty(), other.entries.is_empty()) {
+            (true, true) => return Some(Self::empty()),
+            (false, true) => a,
+            (true, false) => b,
+            (false, false) => a.start.min(b.start)..a.end.max(b.end),
+        };
+
+        // there's a bit for every code of the window
+        #[allow(clippy::cast_sign_loss)]
+        let num_bits = ((window.end - window.start) as u64).saturating_mul(WORD_BITS);
+        let max_len = self.entries.len() + other.entries.len();
+        if !is_dense(max_len, num_bits - 1, MIN_BIT_SET_DENSITY_PERCENT) {
+            return None;
+        }
+
+        let base = self.window_base(&window);
+        let words: Vec<_> = self
+            .word_pairs(other, window)
+            .map(|(x, y)| op(x, y))
+            .collect();
+
+        // the entries of both sets are sorted by their offset within the window, so merging them
+        // visits the values of the result in order, once each
+        let offset = |entry: &(T, ())| int_code(&entry.0).wrapping_sub(base);
+        #[allow(clippy::cast_possible_truncation)]
+        let is_set =
+            |offset: u64| (words[(offset / WORD_BITS) as usize] >> (offset % WORD_BITS)) & 1 == 1;
+
+        let (mut a, mut b) = (
+            self.entries.iter().peekable(),
+            other.entries.iter().peekable(),
+        );
+        let mut entries = Vec::new();
+        loop {
+            let entry = match (a.peek(), b.peek()) {
+                (Some(x), Some(y)) => match offset(x).cmp(&offset(y)) {
+                    Ordering::Less => a.next(),
+                    Ordering::Greater => b.next(),
+                    Ordering::Equal => {
+                        b.next();
+                        a.next()
+                    }
+                },
+                (Some(_), None) => a.next(),
+                (None, _) => b.next(),
+            };
+
+            let Some(entry) = entry else {
+                break;
+            };
+
+            if is_set(offset(entry)) {
+                entries.push(entry.clone());
+            }
+        }
+
+        if entries.is_empty() {
+            return Some(Self::empty());
+        }
+
+        let (base, words) = trim_words(base, words);
+        Some(Self {
+            base,
+            bits: BitVec::<usize, Lsb0>::from_vec(words).into_boxed_bitslice(),
+            entries: entries.into_boxed_slice(),
+        })
+    }
+}
+
+impl<T> BitSet<T>
+where
+    T: PrimInt,
+{
+    /// Combines the words of two sets over a window of words, given relative to the words of
+    /// `self`.
+    fn combine<F>(&self, other: &Self, window: Range<i64>, op: F) -> Self
+    where
+        F: Fn(usize, usize) -> usize,
+    {
+        let base = self.window_base(&window);
+        let words = self
+            .word_pairs(other, window)
+            .map(|(x, y)| op(x, y))
+            .collect();
+
+        Self::from_words(base, words)
+    }
+
+    fn from_words(base: u64, words: Vec<usize>) -> Self {
+        let (base, words) = trim_words(base, words);
+        if words.is_empty() {
+            return Self::empty();
+        }
+
+        let bits = BitVec::<usize, Lsb0>::from_vec(words).into_boxed_bitslice();
+        let entries = bits
+            .iter_ones()
+            .map(|offset| (value_of(base.wrapping_add(offset as u64)), ()))
+            .collect();
+
+        Self {
+            base,
+            bits,
+            entries,
+        }
+    }
+}
+
+/// Returns the value which writes the given integer code.
+fn value_of<T: PrimInt>(code: u64) -> T {
+    // signed values write sign-extended codes
+    #[allow(clippy::cast_possible_wrap)]
+    let value = if T::min_value() < T::zero() {
+        <T as NumCast>::from(code as i64)
+    } else {
+        <T as NumCast>::from(code)
+    };
+
+    value.expect("the code was written by a value of the set")
+}
+
+impl<T> Default for BitSet<T> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<T> Len for BitSet<T> {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl<T> HeapSize for BitSet<T>
+where
+    T: HeapSize,
+{
+    fn heap_bytes(&self) -> usize {
+        self.table_bytes() + self.iter().map(HeapSize::heap_bytes).sum::<usize>()
+    }
+}
+
+impl<T> Debug for BitSet<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T> IntoIterator for BitSet<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.entries)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a BitSet<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for BitSet<T>
+where
+    T: Hash + Eq,
+{
+    fn from(payload: [T; N]) -> Self {
+        Self::from_vec(Vec::from_iter(payload))
+    }
+}
+
+impl<T> FromIterator<T> for BitSet<T>
+where
+    T: Hash + Eq,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_vec(Vec::from_iter(iter))
+    }
+}
+
+impl<T> Set<T> for BitSet<T>
+where
+    T: Hash + Eq,
+{
+    type Iterator<'a> = Iter<'a, T>
+    where
+        T: 'a;
+
+    fn iter(&self) -> Iter<'_, T> {
+        self.iter()
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.contains(value)
+    }
+
+    fn as_bit_set(&self) -> Option<&Self> {
+        Some(self)
+    }
+}
+
+impl<T> BitOr<&BitSet<T>> for &BitSet<T>
+where
+    T: PrimInt,
+{
+    type Output = BitSet<T>;
+
+    fn bitor(self, rhs: &BitSet<T>) -> Self::Output {
+        if self.entries.is_empty() || rhs.entries.is_empty() {
+            return if self.entries.is_empty() { rhs } else { self }.clone();
+        }
+
+        let (a, b) = self.windows(rhs);
+        self.combine(rhs, a.start.min(b.start)..a.end.max(b.end), |x, y| x | y)
+    }
+}
+
+impl<T> BitAnd<&BitSet<T>> for &BitSet<T>
+where
+    T: PrimInt,
+{
+    type Output = BitSet<T>;
+
+    fn bitand(self, rhs: &BitSet<T>) -> Self::Output {
+        let (a, b) = self.windows(rhs);
+        self.combine(rhs, a.start.max(b.start)..a.end.min(b.end), |x, y| x & y)
+    }
+}
+
+impl<T> BitXor<&BitSet<T>> for &BitSet<T>
+where
+    T: PrimInt,
+{
+    type Output = BitSet<T>;
+
+    fn bitxor(self, rhs: &BitSet<T>) -> Self::Output {
+        if self.entries.is_empty() || rhs.entries.is_empty() {
+            return if self.entries.is_empty() { rhs } else { self }.clone();
+        }
+
+        let (a, b) = self.windows(rhs);
+        self.combine(rhs, a.start.min(b.start)..a.end.max(b.end), |x, y| x ^ y)
+    }
+}
+
+impl<T> Sub<&BitSet<T>> for &BitSet<T>
+where
+    T: PrimInt,
+{
+    type Output = BitSet<T>;
+
+    fn sub(self, rhs: &BitSet<T>) -> Self::Output {
+        let (a, _) = self.windows(rhs);
+        self.combine(rhs, a, |x, y| x & !y)
+    }
+}
+
+impl<T, ST> PartialEq<ST> for BitSet<T>
+where
+    T: Hash + Eq,
+    ST: Set<T>,
+{
+    fn eq(&self, other: &ST) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+
+        self.iter().all(|value| other.contains(value))
+    }
+}
+
+impl<T> Eq for BitSet<T> where T: Hash + Eq {}
+
+impl<T> Hash for BitSet<T>
+where
+    T: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self, state);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T> Zeroize for BitSet<T>
+where
+    T: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.entries.zeroize();
+        self.bits.fill(false);
+        self.base.zeroize();
+        *self = Self::empty();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::errors::FrozenError;
+    use crate::traits::len::Len;
+
+    use super::BitSet;
+
+    fn values<T: Copy>(s: &BitSet<T>) -> Vec<T> {
+        s.iter().copied().collect()
+    }
+
+    #[test]
+    fn bit_set_test() {
+        for min in [-200i32, -70, -3, 0, 1000] {
+            let s = BitSet::from_vec((0..50).map(|i| min + i * 3).collect());
+
+            assert_eq!(50, s.len());
+            for i in 0..50 {
+                let value = min + i * 3;
+                assert!(s.contains(&value));
+                assert_eq!(Some(&value), s.get(&value));
+                assert!(!s.contains(&(value + 1)));
+                assert_eq!(None, s.get(&(value + 2)));
+            }
+
+            assert!(!s.contains(&(min - 3)));
+            assert!(!s.contains(&(min + 150)));
+            assert_eq!((0..50).map(|i| min + i * 3).collect::<Vec<_>>(), values(&s));
+        }
+    }
+
+    #[test]
+    fn set_ops() {
+        let a = BitSet::from_vec((-100..100i64).step_by(2).collect());
+        let b = BitSet::from_vec((0..300i64).step_by(3).collect());
+
+        let expected = |f: fn(bool, bool) -> bool| -> Vec<i64> {
+            (-100..300)
+                .filter(|&v| f(v < 100 && v % 2 == 0, v >= 0 && v % 3 == 0))
+                .collect()
+        };
+
+        assert_eq!(expected(|x, y| x || y), values(&(&a | &b)));
+        assert_eq!(expected(|x, y| x && y), values(&(&a & &b)));
+        assert_eq!(expected(|x, y| x != y), values(&(&a ^ &b)));
+        assert_eq!(expected(|x, y| x && !y), values(&(&a - &b)));
+        assert_eq!(expected(|x, y| y && !x), values(&(&b - &a)));
+
+        let c = &a & &b;
+        assert!(c.contains(&0));
+        assert!(c.contains(&96));
+        assert!(!c.contains(&3));
+
+        let empty = BitSet::<i64>::empty();
+        assert_eq!(values(&a), values(&(&a | &empty)));
+        assert_eq!(values(&b), values(&(&empty ^ &b)));
+        assert_eq!(0, (&a & &empty).len());
+        assert_eq!(0, (&empty - &a).len());
+        assert_eq!(values(&a), values(&(&a - &empty)));
+
+        let far = BitSet::from_vec(vec![1_000_000i64, 1_000_001]);
+        assert_eq!(0, (&a & &far).len());
+        assert_eq!(102, (&a | &far).len());
+    }
+
+    #[test]
+    fn merge() {
+        /// An operation on the words of two bit vectors, with the set operation it stands for.
+        type Op = (
+            fn(usize, usize) -> usize,
+            fn(&BitSet<i64>, &BitSet<i64>) -> BitSet<i64>,
+        );
+
+        // values which aren't primitive integers are taken from the operands
+        #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+        struct Port(u16);
+
+        let a = BitSet::from_vec((-100..100i64).step_by(2).collect());
+        let b = BitSet::from_vec((0..300i64).step_by(3).collect());
+        let empty = BitSet::<i64>::empty();
+
+        let ops: [Op; 4] = [
+            (|x, y| x | y, |x, y| x | y),
+            (|x, y| x & y, |x, y| x & y),
+            (|x, y| x ^ y, |x, y| x ^ y),
+            (|x, y| x & !y, |x, y| x - y),
+        ];
+
+        for (word_op, set_op) in ops {
+            for (x, y) in [
+                (&a, &b),
+                (&b, &a),
+                (&a, &empty),
+                (&empty, &b),
+                (&empty, &empty),
+            ] {
+                let merged = x.merge(y, word_op).unwrap();
+                assert_eq!(values(&set_op(x, y)), values(&merged));
+                assert!(values(&merged).iter().all(|v| merged.contains(v)));
+            }
+        }
+
+        // too far apart to share a bit vector
+        let far = BitSet::from_vec(vec![1_000_000i64, 1_000_001]);
+        assert!(a.merge(&far, |x, y| x | y).is_none());
+
+        let c = BitSet::from_vec((0..50).map(|i| Port(i * 2)).collect());
+        let d = BitSet::from_vec((0..50).map(|i| Port(i * 3)).collect());
+        let merged = c.merge(&d, |x, y| x & y).unwrap();
+        assert_eq!(
+            (0..17).map(|i| Port(i * 6)).collect::<Vec<_>>(),
+            values(&merged)
+        );
+        assert_eq!(96, merged.span());
+    }
+
+    #[test]
+    fn subsets_and_disjointness() {
+        let a = BitSet::from_vec((0..100u32).step_by(2).collect());
+        let b = BitSet::from_vec((0..50u32).step_by(4).collect());
+        let c = BitSet::from_vec((1..400u32).step_by(2).collect());
+        let empty = BitSet::<u32>::empty();
+
+        assert!(b.is_subset_of(&a));
+        assert!(!a.is_subset_of(&b));
+        assert!(!c.is_subset_of(&a));
+        assert!(empty.is_subset_of(&a));
+        assert!(a.is_subset_of(&a));
+
+        assert!(a.is_disjoint_with(&c));
+        assert!(!a.is_disjoint_with(&b));
+        assert!(empty.is_disjoint_with(&a));
+        assert!(a.is_disjoint_with(&empty));
+    }
+
+    #[test]
+    fn unsigned_extremes() {
+        let s = BitSet::from_vec(vec![u64::MAX, u64::MAX - 2, 0, 1]);
+        assert!(s.contains(&u64::MAX));
+        assert!(s.contains(&1));
+        assert!(!s.contains(&2));
+        assert!(!s.contains(&(u64::MAX - 1)));
+
+        let t = BitSet::from_vec(vec![u64::MAX - 1, 0, 2]);
+        assert_eq!(
+            vec![u64::MAX - 2, u64::MAX - 1, u64::MAX, 1, 2],
+            values(&(&s ^ &t))
+        );
+    }
+
+    #[test]
+    fn try_from_vec_reports_bad_values() {
+        assert_eq!(
+            Some(FrozenError::SparseRange),
+            BitSet::try_from_vec(vec![1, 2, 1000]).err()
+        );
+        assert_eq!(
+            Some(FrozenError::DuplicateKey { index: 2 }),
+            BitSet::try_from_vec(vec![1, 2, 1]).err()
+        );
+        assert_eq!(0, BitSet::<u8>::try_from_vec(Vec::new()).unwrap().len());
+    }
+}
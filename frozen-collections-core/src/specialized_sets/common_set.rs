@@ -1,33 +1,32 @@
-use std::borrow::Borrow;
 use std::collections::HashSet;
 use std::fmt::{Debug, Formatter, Result};
-use std::hash::{BuildHasher, Hash};
 use std::hash::RandomState;
+use std::hash::{BuildHasher, Hash, Hasher};
 use std::ops::{BitAnd, BitOr, BitXor, Sub};
 
-use num_traits::{PrimInt, Unsigned};
-
+use crate::errors::FrozenError;
+use crate::hashers::hash_unordered;
 use crate::specialized_maps::CommonMap;
 use crate::specialized_sets::{IntoIter, Iter, Set};
+use crate::traits::equivalent::Equivalent;
+use crate::traits::heap_size::HeapSize;
 use crate::traits::len::Len;
 
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
 /// A general-purpose optimized read-only set.
 ///
-/// # Capacity Constraints
-///
-/// The `S` generic argument controls the maximum capacity
-/// of the set. A `u8` will allow up to 255 elements, `u16`
-/// will allow up to 65,535 elements, and `usize` will allow
-/// up to `usize::MAX` elements.
+/// The set indexes its hash table with the narrowest integer type able to address all of its
+/// values, so small sets don't pay for wide indices.
 #[derive(Clone)]
-pub struct CommonSet<T, S = u8, BH = RandomState> {
-    map: CommonMap<T, (), S, BH>,
+pub struct CommonSet<T, BH = RandomState> {
+    map: CommonMap<T, (), BH>,
 }
 
-impl<T, S, BH> CommonSet<T, S, BH>
+impl<T, BH> CommonSet<T, BH>
 where
     T: Hash,
-    S: PrimInt + Unsigned,
     BH: BuildHasher,
 {
     /// Creates a new set which will use the given hasher to hash values.
@@ -39,16 +38,11 @@ where
     /// use std::hash::RandomState;
     /// use frozen_collections_core::traits::len::Len;
     ///
-    /// let set = CommonSet::<_, u8, _>::from_vec_with_hasher(vec![1, 2, 3], RandomState::new());
+    /// let set = CommonSet::<_, _>::from_vec_with_hasher(vec![1, 2, 3], RandomState::new());
     ///
     /// assert_eq!(set.len(), 3);
     /// assert!(set.contains(&1));
     /// ```
-    ///
-    /// # Panics
-    ///
-    /// If the payload contains more items than the set's capacity
-    /// allows. The capacity is determined by the `S` generic argument.
     #[must_use]
     pub fn from_vec_with_hasher(payload: Vec<T>, bh: BH) -> Self {
         Self {
@@ -56,6 +50,41 @@ where
         }
     }
 
+    /// Creates a new set which will use the given hasher to hash values, returning an error
+    /// instead of panicking when the payload is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::errors::FrozenError;
+    /// use frozen_collections_core::specialized_sets::CommonSet;
+    /// use std::hash::RandomState;
+    ///
+    /// let set = CommonSet::<_, _>::try_from_vec_with_hasher(vec![1, 2, 3], RandomState::new());
+    /// assert!(set.is_ok());
+    ///
+    /// let set = CommonSet::<_, _>::try_from_vec_with_hasher(vec![1, 2, 1], RandomState::new());
+    /// assert_eq!(set.err(), Some(FrozenError::DuplicateKey { index: 2 }));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate values.
+    pub fn try_from_vec_with_hasher(
+        payload: Vec<T>,
+        bh: BH,
+    ) -> std::result::Result<Self, FrozenError>
+    where
+        T: Eq,
+    {
+        Ok(Self {
+            map: CommonMap::try_from_vec_with_hasher(
+                payload.into_iter().map(|x| (x, ())).collect(),
+                bh,
+            )?,
+        })
+    }
+
     /// Creates a new set which will use the given hasher to hash
     /// keys.
     ///
@@ -67,16 +96,11 @@ where
     /// use std::hash::RandomState;
     ///
     /// let vec = vec![1, 2, 3];
-    /// let set = CommonSet::<_, u8, _>::from_iter_with_hasher(vec.iter(), RandomState::new());
+    /// let set = CommonSet::<_, _>::from_iter_with_hasher(vec.iter(), RandomState::new());
     ///
     /// assert_eq!(set.len(), 3);
     /// assert!(set.contains(&1));
     /// ```
-    ///
-    /// # Panics
-    ///
-    /// If the payload contains more items than the set's capacity
-    /// allows. The capacity is determined by the `S` generic argument.
     #[must_use]
     pub fn from_iter_with_hasher<I: IntoIterator<Item = T>>(iter: I, bh: BH) -> Self {
         Self {
@@ -94,16 +118,11 @@ where
     /// use frozen_collections_core::traits::len::Len;
     /// use std::hash::RandomState;
     ///
-    /// let set = CommonSet::<_, u8, _>::with_hasher([1, 2, 3], RandomState::new());
+    /// let set = CommonSet::<_, _>::with_hasher([1, 2, 3], RandomState::new());
     ///
     /// assert_eq!(set.len(), 3);
     /// assert!(set.contains(&1));
     /// ```
-    ///
-    /// # Panics
-    ///
-    /// If the payload contains more items than the set's capacity
-    /// allows. The capacity is determined by the `S` generic argument.
     #[must_use]
     pub fn with_hasher<const N: usize>(payload: [T; N], bh: BH) -> Self {
         Self {
@@ -112,9 +131,8 @@ where
     }
 }
 
-impl<T, S, BH> CommonSet<T, S, BH>
+impl<T, BH> CommonSet<T, BH>
 where
-    S: PrimInt + Unsigned,
     BH: BuildHasher,
 {
     /// Returns a reference to the value in the set, if any, that is equal to the given value.
@@ -124,7 +142,7 @@ where
     /// ```
     /// use frozen_collections_core::specialized_sets::CommonSet;
     ///
-    /// let set = CommonSet::<_, u8, _>::from([1, 2, 3]);
+    /// let set = CommonSet::<_, _>::from([1, 2, 3]);
     ///
     /// assert_eq!(set.get(&2), Some(&2));
     /// assert_eq!(set.get(&4), None);
@@ -133,8 +151,7 @@ where
     #[must_use]
     pub fn get<Q>(&self, value: &Q) -> Option<&T>
     where
-        T: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<T>,
     {
         Some(self.map.get_key_value(value)?.0)
     }
@@ -146,7 +163,7 @@ where
     /// ```
     /// use frozen_collections_core::specialized_sets::CommonSet;
     ///
-    /// let set = CommonSet::<_, u8, _>::from([1, 2, 3]);
+    /// let set = CommonSet::<_, _>::from([1, 2, 3]);
     ///
     /// assert!(set.contains(&1));
     /// assert!(!set.contains(&4));
@@ -155,14 +172,19 @@ where
     #[must_use]
     pub fn contains<Q>(&self, value: &Q) -> bool
     where
-        T: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Equivalent<T>,
     {
         self.get(value).is_some()
     }
 }
 
-impl<T, S, BH> CommonSet<T, S, BH> {
+impl<T, BH> CommonSet<T, BH> {
+    /// An iterator visiting all values in arbitrary order.
+    #[must_use]
+    pub const fn iter(&self) -> Iter<'_, T> {
+        Iter::new(&self.map.table.entries)
+    }
+
     /// Returns the hasher for this set.
     #[must_use]
     pub const fn hasher(&self) -> &BH {
@@ -170,10 +192,9 @@ impl<T, S, BH> CommonSet<T, S, BH> {
     }
 }
 
-impl<T, S> CommonSet<T, S, RandomState>
+impl<T> CommonSet<T, RandomState>
 where
     T: Hash,
-    S: PrimInt + Unsigned,
 {
     /// Creates a new set using the default hasher to hash values.
     ///
@@ -182,24 +203,74 @@ where
     /// ```
     /// use frozen_collections_core::specialized_sets::CommonSet;
     ///
-    /// let set = CommonSet::<_, u8, _>::from_vec(vec![1, 2, 3]);
+    /// let set = CommonSet::<_, _>::from_vec(vec![1, 2, 3]);
     /// ```
     #[must_use]
     pub fn from_vec(payload: Vec<T>) -> Self {
         Self::from_vec_with_hasher(payload, RandomState::new())
     }
+
+    /// Creates a new set using the default hasher to hash values, returning an error instead of
+    /// panicking when the payload is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::errors::FrozenError;
+    /// use frozen_collections_core::specialized_sets::CommonSet;
+    ///
+    /// let set = CommonSet::<_, _>::try_from_vec(vec![1, 2, 1]);
+    /// assert_eq!(set.err(), Some(FrozenError::DuplicateKey { index: 2 }));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate values.
+    pub fn try_from_vec(payload: Vec<T>) -> std::result::Result<Self, FrozenError>
+    where
+        T: Eq,
+    {
+        Self::try_from_vec_with_hasher(payload, RandomState::new())
+    }
+}
+
+impl<T, BH> CommonSet<T, BH> {
+    /// Creates an empty set which will use the given hash builder, without allocating.
+    #[must_use]
+    pub fn empty_with_hasher(bh: BH) -> Self {
+        Self {
+            map: CommonMap::empty_with_hasher(bh),
+        }
+    }
 }
 
-impl<T, S, BH> Len for CommonSet<T, S, BH> {
+impl<T, BH> Default for CommonSet<T, BH>
+where
+    BH: Default,
+{
+    fn default() -> Self {
+        Self::empty_with_hasher(BH::default())
+    }
+}
+
+impl<T, BH> Len for CommonSet<T, BH> {
     fn len(&self) -> usize {
         self.map.len()
     }
 }
 
-impl<T, S, BH> Debug for CommonSet<T, S, BH>
+impl<T, BH> HeapSize for CommonSet<T, BH>
+where
+    T: HeapSize,
+{
+    fn heap_bytes(&self) -> usize {
+        self.map.heap_bytes()
+    }
+}
+
+impl<T, BH> Debug for CommonSet<T, BH>
 where
     T: Hash + Eq + Debug,
-    S: PrimInt + Unsigned,
     BH: BuildHasher,
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
@@ -207,10 +278,9 @@ where
     }
 }
 
-impl<T, S, BH> IntoIterator for CommonSet<T, S, BH>
+impl<T, BH> IntoIterator for CommonSet<T, BH>
 where
     T: Hash + Eq,
-    S: PrimInt + Unsigned,
     BH: BuildHasher,
 {
     type Item = T;
@@ -221,12 +291,7 @@ where
     }
 }
 
-impl<'a, T, S, BH> IntoIterator for &'a CommonSet<T, S, BH>
-where
-    T: Hash + Eq,
-    S: PrimInt + Unsigned,
-    BH: BuildHasher,
-{
+impl<'a, T, BH> IntoIterator for &'a CommonSet<T, BH> {
     type Item = &'a T;
     type IntoIter = Iter<'a, T>;
 
@@ -235,10 +300,9 @@ where
     }
 }
 
-impl<T, S, const N: usize> From<[T; N]> for CommonSet<T, S, RandomState>
+impl<T, const N: usize> From<[T; N]> for CommonSet<T, RandomState>
 where
     T: Hash,
-    S: PrimInt + Unsigned,
 {
     fn from(payload: [T; N]) -> Self {
         Self {
@@ -250,10 +314,9 @@ where
     }
 }
 
-impl<T, S> FromIterator<T> for CommonSet<T, S, RandomState>
+impl<T> FromIterator<T> for CommonSet<T, RandomState>
 where
     T: Hash,
-    S: PrimInt + Unsigned,
 {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         Self {
@@ -265,20 +328,19 @@ where
     }
 }
 
-impl<T, S, BH> Set<T> for CommonSet<T, S, BH>
+impl<T, BH> Set<T> for CommonSet<T, BH>
 where
     T: Hash + Eq,
-    S: PrimInt + Unsigned,
     BH: BuildHasher,
 {
-    type Iterator<'a> = Iter<'a, T>
+    type Iterator<'a>
+        = Iter<'a, T>
     where
         T: 'a,
-        S: 'a,
         BH: 'a;
 
     fn iter(&self) -> Iter<'_, T> {
-        Iter::new(&self.map.table.entries)
+        self.iter()
     }
 
     fn contains(&self, value: &T) -> bool {
@@ -286,10 +348,9 @@ where
     }
 }
 
-impl<T, S, ST, BH> BitOr<&ST> for &CommonSet<T, S, BH>
+impl<T, ST, BH> BitOr<&ST> for &CommonSet<T, BH>
 where
     T: Hash + Eq + Clone,
-    S: PrimInt + Unsigned,
     ST: Set<T>,
     BH: BuildHasher + Default,
 {
@@ -300,10 +361,9 @@ where
     }
 }
 
-impl<T, S, ST, BH> BitAnd<&ST> for &CommonSet<T, S, BH>
+impl<T, ST, BH> BitAnd<&ST> for &CommonSet<T, BH>
 where
     T: Hash + Eq + Clone,
-    S: PrimInt + Unsigned,
     ST: Set<T>,
     BH: BuildHasher + Default,
 {
@@ -314,10 +374,9 @@ where
     }
 }
 
-impl<T, S, ST, BH> BitXor<&ST> for &CommonSet<T, S, BH>
+impl<T, ST, BH> BitXor<&ST> for &CommonSet<T, BH>
 where
     T: Hash + Eq + Clone,
-    S: PrimInt + Unsigned,
     ST: Set<T>,
     BH: BuildHasher + Default,
 {
@@ -328,10 +387,9 @@ where
     }
 }
 
-impl<T, S, ST, BH> Sub<&ST> for &CommonSet<T, S, BH>
+impl<T, ST, BH> Sub<&ST> for &CommonSet<T, BH>
 where
     T: Hash + Eq + Clone,
-    S: PrimInt + Unsigned,
     ST: Set<T>,
     BH: BuildHasher + Default,
 {
@@ -342,10 +400,9 @@ where
     }
 }
 
-impl<T, S, ST, BH> PartialEq<ST> for CommonSet<T, S, BH>
+impl<T, ST, BH> PartialEq<ST> for CommonSet<T, BH>
 where
     T: Hash + Eq,
-    S: PrimInt + Unsigned,
     ST: Set<T>,
     BH: BuildHasher + Default,
 {
@@ -358,10 +415,28 @@ where
     }
 }
 
-impl<T, S, BH> Eq for CommonSet<T, S, BH>
+impl<T, BH> Eq for CommonSet<T, BH>
 where
     T: Hash + Eq,
-    S: PrimInt + Unsigned,
     BH: BuildHasher + Default,
 {
 }
+
+impl<T, BH> Hash for CommonSet<T, BH>
+where
+    T: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(Iter::new(&self.map.table.entries), state);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T, BH> Zeroize for CommonSet<T, BH>
+where
+    T: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.map.zeroize();
+    }
+}
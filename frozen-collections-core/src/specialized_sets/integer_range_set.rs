@@ -1,15 +1,21 @@
-use std::borrow::Borrow;
 use std::collections::HashSet;
 use std::fmt::{Debug, Formatter, Result};
-use std::hash::{Hash, RandomState};
+use std::hash::{Hash, Hasher, RandomState};
 use std::ops::{BitAnd, BitOr, BitXor, Sub};
 
 use num_traits::PrimInt;
 
+use crate::errors::FrozenError;
+use crate::hashers::hash_unordered;
 use crate::specialized_maps::IntegerRangeMap;
 use crate::specialized_sets::{IntoIter, Iter, Set};
+use crate::traits::equivalent::Equivalent;
+use crate::traits::heap_size::HeapSize;
 use crate::traits::len::Len;
 
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
 /// A map whose values are a continuous range of integers.
 #[derive(Clone)]
 pub struct IntegerRangeSet<T> {
@@ -18,7 +24,7 @@ pub struct IntegerRangeSet<T> {
 
 impl<T> IntegerRangeSet<T>
 where
-    T: PrimInt,
+    T: Hash + Eq,
 {
     #[must_use]
     pub fn from_vec(payload: Vec<T>) -> Self {
@@ -27,12 +33,23 @@ where
         }
     }
 
+    /// Creates a set, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate values, or if the values aren't all in a contiguous
+    /// range.
+    pub fn try_from_vec(payload: Vec<T>) -> std::result::Result<Self, FrozenError> {
+        Ok(Self {
+            map: IntegerRangeMap::try_from_vec(payload.into_iter().map(|x| (x, ())).collect())?,
+        })
+    }
+
     #[inline]
     #[must_use]
     pub fn get<Q>(&self, value: &Q) -> Option<&T>
     where
-        T: Borrow<Q>,
-        Q: PrimInt,
+        Q: ?Sized + Hash + Equivalent<T>,
     {
         Some(self.map.get_key_value(value)?.0)
     }
@@ -41,8 +58,7 @@ where
     #[must_use]
     pub fn contains<Q>(&self, value: &Q) -> bool
     where
-        T: Borrow<Q>,
-        Q: PrimInt,
+        Q: ?Sized + Hash + Equivalent<T>,
     {
         self.get(value).is_some()
     }
@@ -55,12 +71,37 @@ impl<T> IntegerRangeSet<T> {
     }
 }
 
+impl<T> IntegerRangeSet<T> {
+    /// Creates an empty set without allocating.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            map: IntegerRangeMap::empty(),
+        }
+    }
+}
+
+impl<T> Default for IntegerRangeSet<T> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
 impl<T> Len for IntegerRangeSet<T> {
     fn len(&self) -> usize {
         self.map.len()
     }
 }
 
+impl<T> HeapSize for IntegerRangeSet<T>
+where
+    T: HeapSize,
+{
+    fn heap_bytes(&self) -> usize {
+        self.map.heap_bytes()
+    }
+}
+
 impl<T> Debug for IntegerRangeSet<T>
 where
     T: Debug,
@@ -90,7 +131,7 @@ impl<'a, T> IntoIterator for &'a IntegerRangeSet<T> {
 
 impl<T, const N: usize> From<[T; N]> for IntegerRangeSet<T>
 where
-    T: PrimInt,
+    T: Hash + Eq,
 {
     fn from(payload: [T; N]) -> Self {
         Self::from_vec(Vec::from_iter(payload))
@@ -99,7 +140,7 @@ where
 
 impl<T> FromIterator<T> for IntegerRangeSet<T>
 where
-    T: PrimInt,
+    T: Hash + Eq,
 {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         Self::from_vec(Vec::from_iter(iter))
@@ -108,7 +149,7 @@ where
 
 impl<T> Set<T> for IntegerRangeSet<T>
 where
-    T: PrimInt,
+    T: Hash + Eq,
 {
     type Iterator<'a> = Iter<'a, T>
     where
@@ -173,7 +214,7 @@ where
 
 impl<T, ST> PartialEq<ST> for IntegerRangeSet<T>
 where
-    T: PrimInt,
+    T: Hash + Eq,
     ST: Set<T>,
 {
     fn eq(&self, other: &ST) -> bool {
@@ -185,4 +226,23 @@ where
     }
 }
 
-impl<T> Eq for IntegerRangeSet<T> where T: PrimInt {}
+impl<T> Eq for IntegerRangeSet<T> where T: Hash + Eq {}
+
+impl<T> Hash for IntegerRangeSet<T>
+where
+    T: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self, state);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T> Zeroize for IntegerRangeSet<T>
+where
+    T: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.map.zeroize();
+    }
+}
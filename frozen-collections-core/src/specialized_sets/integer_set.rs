@@ -1,25 +1,30 @@
-use std::borrow::Borrow;
 use std::collections::HashSet;
 use std::fmt::{Debug, Formatter, Result};
-use std::hash::Hash;
+use std::hash::{Hash, Hasher};
 use std::ops::{BitAnd, BitOr, BitXor, Sub};
 
-use num_traits::{AsPrimitive, PrimInt, Unsigned};
+use num_traits::{AsPrimitive, PrimInt};
 
+use crate::errors::FrozenError;
+use crate::hashers::hash_unordered;
 use crate::specialized_maps::IntegerMap;
 use crate::specialized_sets::{IntoIter, Iter, Set};
+use crate::traits::equivalent::Equivalent;
+use crate::traits::heap_size::HeapSize;
 use crate::traits::len::Len;
 
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
 /// A set specialized for integer values.
 #[derive(Clone)]
-pub struct IntegerSet<T, S = u8> {
-    map: IntegerMap<T, (), S>,
+pub struct IntegerSet<T> {
+    map: IntegerMap<T, ()>,
 }
 
-impl<T, S> IntegerSet<T, S>
+impl<T> IntegerSet<T>
 where
-    T: PrimInt + AsPrimitive<u64>,
-    S: PrimInt + Unsigned,
+    T: Hash + Eq,
 {
     #[must_use]
     pub fn from_vec(payload: Vec<T>) -> Self {
@@ -28,12 +33,22 @@ where
         }
     }
 
+    /// Creates a set, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate values.
+    pub fn try_from_vec(payload: Vec<T>) -> std::result::Result<Self, FrozenError> {
+        Ok(Self {
+            map: IntegerMap::try_from_vec(payload.into_iter().map(|x| (x, ())).collect())?,
+        })
+    }
+
     #[inline]
     #[must_use]
     pub fn get<Q>(&self, value: &Q) -> Option<&T>
     where
-        T: Borrow<Q>,
-        Q: PrimInt + AsPrimitive<u64>,
+        Q: ?Sized + Hash + Equivalent<T>,
     {
         Some(self.map.get_key_value(value)?.0)
     }
@@ -42,27 +57,51 @@ where
     #[must_use]
     pub fn contains<Q>(&self, value: &Q) -> bool
     where
-        T: Borrow<Q>,
-        Q: PrimInt + AsPrimitive<u64>,
+        Q: ?Sized + Hash + Equivalent<T>,
     {
         self.get(value).is_some()
     }
 }
 
-impl<T, S> IntegerSet<T, S> {
+impl<T> IntegerSet<T> {
     #[must_use]
     pub const fn iter(&self) -> Iter<T> {
         Iter::new(&self.map.table.entries)
     }
 }
 
-impl<T, S> Len for IntegerSet<T, S> {
+impl<T> IntegerSet<T> {
+    /// Creates an empty set without allocating.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            map: IntegerMap::empty(),
+        }
+    }
+}
+
+impl<T> Default for IntegerSet<T> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<T> Len for IntegerSet<T> {
     fn len(&self) -> usize {
         self.map.len()
     }
 }
 
-impl<T, S> Debug for IntegerSet<T, S>
+impl<T> HeapSize for IntegerSet<T>
+where
+    T: HeapSize,
+{
+    fn heap_bytes(&self) -> usize {
+        self.map.heap_bytes()
+    }
+}
+
+impl<T> Debug for IntegerSet<T>
 where
     T: Debug,
 {
@@ -71,7 +110,7 @@ where
     }
 }
 
-impl<T, S> IntoIterator for IntegerSet<T, S> {
+impl<T> IntoIterator for IntegerSet<T> {
     type Item = T;
     type IntoIter = IntoIter<T>;
 
@@ -80,7 +119,7 @@ impl<T, S> IntoIterator for IntegerSet<T, S> {
     }
 }
 
-impl<'a, T, S> IntoIterator for &'a IntegerSet<T, S> {
+impl<'a, T> IntoIterator for &'a IntegerSet<T> {
     type Item = &'a T;
     type IntoIter = Iter<'a, T>;
 
@@ -89,35 +128,32 @@ impl<'a, T, S> IntoIterator for &'a IntegerSet<T, S> {
     }
 }
 
-impl<T, S, const N: usize> From<[T; N]> for IntegerSet<T, S>
+impl<T, const N: usize> From<[T; N]> for IntegerSet<T>
 where
-    T: PrimInt + AsPrimitive<u64>,
-    S: PrimInt + Unsigned,
+    T: Hash + Eq,
 {
     fn from(payload: [T; N]) -> Self {
         Self::from_vec(Vec::from_iter(payload))
     }
 }
 
-impl<T, S> FromIterator<T> for IntegerSet<T, S>
+impl<T> FromIterator<T> for IntegerSet<T>
 where
-    T: PrimInt + AsPrimitive<u64>,
-    S: PrimInt + Unsigned,
+    T: Hash + Eq,
 {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         Self::from_vec(Vec::from_iter(iter))
     }
 }
 
-impl<T, S> Set<T> for IntegerSet<T, S>
+impl<T> Set<T> for IntegerSet<T>
 where
-    T: PrimInt + AsPrimitive<u64>,
-    S: PrimInt + Unsigned,
+    T: Hash + Eq,
 {
-    type Iterator<'a> = Iter<'a, T>
+    type Iterator<'a>
+        = Iter<'a, T>
     where
-        T: 'a,
-        S: 'a;
+        T: 'a;
 
     fn iter(&self) -> Iter<'_, T> {
         self.iter()
@@ -128,10 +164,9 @@ where
     }
 }
 
-impl<T, S, ST> BitOr<&ST> for &IntegerSet<T, S>
+impl<T, ST> BitOr<&ST> for &IntegerSet<T>
 where
     T: PrimInt + AsPrimitive<u64> + Clone + Hash,
-    S: PrimInt + Unsigned,
     ST: Set<T>,
 {
     type Output = HashSet<T>;
@@ -141,10 +176,9 @@ where
     }
 }
 
-impl<T, S, ST> BitAnd<&ST> for &IntegerSet<T, S>
+impl<T, ST> BitAnd<&ST> for &IntegerSet<T>
 where
     T: PrimInt + AsPrimitive<u64> + Clone + Hash,
-    S: PrimInt + Unsigned,
     ST: Set<T>,
 {
     type Output = HashSet<T>;
@@ -154,10 +188,9 @@ where
     }
 }
 
-impl<T, S, ST> BitXor<&ST> for &IntegerSet<T, S>
+impl<T, ST> BitXor<&ST> for &IntegerSet<T>
 where
     T: PrimInt + AsPrimitive<u64> + Clone + Hash,
-    S: PrimInt + Unsigned,
     ST: Set<T>,
 {
     type Output = HashSet<T>;
@@ -167,10 +200,9 @@ where
     }
 }
 
-impl<T, S, ST> Sub<&ST> for &IntegerSet<T, S>
+impl<T, ST> Sub<&ST> for &IntegerSet<T>
 where
     T: PrimInt + AsPrimitive<u64> + Clone + Hash,
-    S: PrimInt + Unsigned,
     ST: Set<T>,
 {
     type Output = HashSet<T>;
@@ -180,10 +212,9 @@ where
     }
 }
 
-impl<T, S, ST> PartialEq<ST> for IntegerSet<T, S>
+impl<T, ST> PartialEq<ST> for IntegerSet<T>
 where
     T: PrimInt + AsPrimitive<u64> + Hash,
-    S: PrimInt + Unsigned,
     ST: Set<T>,
 {
     fn eq(&self, other: &ST) -> bool {
@@ -195,9 +226,23 @@ where
     }
 }
 
-impl<T, S> Eq for IntegerSet<T, S>
+impl<T> Eq for IntegerSet<T> where T: PrimInt + AsPrimitive<u64> + Hash {}
+
+impl<T> Hash for IntegerSet<T>
 where
-    T: PrimInt + AsPrimitive<u64> + Hash,
-    S: PrimInt + Unsigned,
+    T: Hash,
 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self, state);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T> Zeroize for IntegerSet<T>
+where
+    T: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.map.zeroize();
+    }
 }
@@ -8,11 +8,21 @@ use crate::specialized_sets::Set;
 pub struct Iter<'a, T> {
     entries: &'a [(T, ())],
     index: usize,
+    end: usize,
 }
 
 impl<'a, T> Iter<'a, T> {
     pub(crate) const fn new(entries: &'a [(T, ())]) -> Self {
-        Self { entries, index: 0 }
+        Self {
+            entries,
+            index: 0,
+            end: entries.len(),
+        }
+    }
+
+    /// Returns the items not yet produced from either end.
+    fn remaining(&self) -> &'a [(T, ())] {
+        &self.entries[self.index..self.end]
     }
 }
 
@@ -20,7 +30,7 @@ impl<'a, T> Iterator for Iter<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.index < self.entries.len() {
+        if self.index < self.end {
             let entry = &self.entries[self.index];
             self.index += 1;
             Some(&entry.0)
@@ -37,7 +47,52 @@ impl<'a, T> Iterator for Iter<'a, T> {
     where
         Self: Sized,
     {
-        self.len() - self.index
+        self.len()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.index = self.index.saturating_add(n).min(self.end);
+        self.next()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.remaining()
+            .iter()
+            .fold(init, |acc, entry| f(acc, &entry.0))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index < self.end {
+            self.end -= 1;
+            Some(&self.entries[self.end].0)
+        } else {
+            None
+        }
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.end = self.end.saturating_sub(n).max(self.index);
+        self.next_back()
+    }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.remaining()
+            .iter()
+            .rfold(init, |acc, entry| f(acc, &entry.0))
     }
 }
 
@@ -46,13 +101,14 @@ impl<'a, T> Clone for Iter<'a, T> {
         Self {
             entries: self.entries,
             index: self.index,
+            end: self.end,
         }
     }
 }
 
 impl<'a, T> ExactSizeIterator for Iter<'a, T> {
     fn len(&self) -> usize {
-        self.entries.len() - self.index
+        self.end - self.index
     }
 }
 
@@ -99,6 +155,40 @@ impl<T> Iterator for IntoIter<T> {
     {
         self.iter.count()
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth(n).map(|x| x.0)
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.iter.fold(init, |acc, (x, ())| f(acc, x))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|x| x.0)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth_back(n).map(|x| x.0)
+    }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.iter.rfold(init, |acc, (x, ())| f(acc, x))
+    }
 }
 
 impl<T> ExactSizeIterator for IntoIter<T> {
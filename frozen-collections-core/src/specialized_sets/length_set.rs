@@ -1,25 +1,28 @@
-use std::borrow::Borrow;
 use std::collections::HashSet;
 use std::fmt::{Debug, Formatter, Result};
-use std::hash::{Hash, RandomState};
+use std::hash::{Hash, Hasher, RandomState};
 use std::ops::{BitAnd, BitOr, BitXor, Sub};
 
-use num_traits::{PrimInt, Unsigned};
-
+use crate::errors::FrozenError;
+use crate::hashers::hash_unordered;
 use crate::specialized_maps::LengthMap;
 use crate::specialized_sets::{IntoIter, Iter, Set};
+use crate::traits::equivalent::Equivalent;
+use crate::traits::heap_size::HeapSize;
 use crate::traits::len::Len;
 
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
 /// A set specialized for integer values.
 #[derive(Clone)]
-pub struct LengthSet<T, S = u8> {
-    map: LengthMap<T, (), S>,
+pub struct LengthSet<T> {
+    map: LengthMap<T, ()>,
 }
 
-impl<T, S> LengthSet<T, S>
+impl<T> LengthSet<T>
 where
     T: Len + Eq,
-    S: PrimInt + Unsigned,
 {
     #[must_use]
     pub fn from_vec(payload: Vec<T>) -> Self {
@@ -27,18 +30,25 @@ where
             map: LengthMap::from_vec(payload.into_iter().map(|x| (x, ())).collect()),
         }
     }
+
+    /// Creates a set, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate values.
+    pub fn try_from_vec(payload: Vec<T>) -> std::result::Result<Self, FrozenError> {
+        Ok(Self {
+            map: LengthMap::try_from_vec(payload.into_iter().map(|x| (x, ())).collect())?,
+        })
+    }
 }
 
-impl<T, S> LengthSet<T, S>
-where
-    S: PrimInt + Unsigned,
-{
+impl<T> LengthSet<T> {
     #[inline]
     #[must_use]
     pub fn get<Q>(&self, value: &Q) -> Option<&T>
     where
-        T: Borrow<Q>,
-        Q: Len + Eq,
+        Q: Len + Equivalent<T>,
     {
         Some(self.map.get_key_value(value)?.0)
     }
@@ -47,27 +57,51 @@ where
     #[must_use]
     pub fn contains<Q>(&self, value: &Q) -> bool
     where
-        T: Borrow<Q>,
-        Q: Len + Eq,
+        Q: Len + Equivalent<T>,
     {
         self.get(value).is_some()
     }
 }
 
-impl<T, S> LengthSet<T, S> {
+impl<T> LengthSet<T> {
     #[must_use]
     pub const fn iter(&self) -> Iter<T> {
         Iter::new(&self.map.table.entries)
     }
 }
 
-impl<T, S> Len for LengthSet<T, S> {
+impl<T> LengthSet<T> {
+    /// Creates an empty set without allocating.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            map: LengthMap::empty(),
+        }
+    }
+}
+
+impl<T> Default for LengthSet<T> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<T> Len for LengthSet<T> {
     fn len(&self) -> usize {
         self.map.len()
     }
 }
 
-impl<T, S> Debug for LengthSet<T, S>
+impl<T> HeapSize for LengthSet<T>
+where
+    T: HeapSize,
+{
+    fn heap_bytes(&self) -> usize {
+        self.map.heap_bytes()
+    }
+}
+
+impl<T> Debug for LengthSet<T>
 where
     T: Debug,
 {
@@ -76,7 +110,7 @@ where
     }
 }
 
-impl<T, S> IntoIterator for LengthSet<T, S> {
+impl<T> IntoIterator for LengthSet<T> {
     type Item = T;
     type IntoIter = IntoIter<T>;
 
@@ -85,7 +119,7 @@ impl<T, S> IntoIterator for LengthSet<T, S> {
     }
 }
 
-impl<'a, T, S> IntoIterator for &'a LengthSet<T, S> {
+impl<'a, T> IntoIterator for &'a LengthSet<T> {
     type Item = &'a T;
     type IntoIter = Iter<'a, T>;
 
@@ -94,35 +128,32 @@ impl<'a, T, S> IntoIterator for &'a LengthSet<T, S> {
     }
 }
 
-impl<T, S, const N: usize> From<[T; N]> for LengthSet<T, S>
+impl<T, const N: usize> From<[T; N]> for LengthSet<T>
 where
     T: Len + Eq,
-    S: PrimInt + Unsigned,
 {
     fn from(payload: [T; N]) -> Self {
         Self::from_vec(Vec::from_iter(payload))
     }
 }
 
-impl<T, S> FromIterator<T> for LengthSet<T, S>
+impl<T> FromIterator<T> for LengthSet<T>
 where
     T: Len + Eq,
-    S: PrimInt + Unsigned,
 {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         Self::from_vec(Vec::from_iter(iter))
     }
 }
 
-impl<T, S> Set<T> for LengthSet<T, S>
+impl<T> Set<T> for LengthSet<T>
 where
     T: Len + Eq,
-    S: PrimInt + Unsigned,
 {
-    type Iterator<'a> = Iter<'a, T>
+    type Iterator<'a>
+        = Iter<'a, T>
     where
-        T: 'a,
-        S: 'a;
+        T: 'a;
 
     fn iter(&self) -> Iter<'_, T> {
         self.iter()
@@ -133,10 +164,9 @@ where
     }
 }
 
-impl<T, S, ST> BitOr<&ST> for &LengthSet<T, S>
+impl<T, ST> BitOr<&ST> for &LengthSet<T>
 where
     T: Hash + Eq + Len + Clone,
-    S: PrimInt + Unsigned,
     ST: Set<T>,
 {
     type Output = HashSet<T, RandomState>;
@@ -146,10 +176,9 @@ where
     }
 }
 
-impl<T, S, ST> BitAnd<&ST> for &LengthSet<T, S>
+impl<T, ST> BitAnd<&ST> for &LengthSet<T>
 where
     T: Hash + Eq + Len + Clone,
-    S: PrimInt + Unsigned,
     ST: Set<T>,
 {
     type Output = HashSet<T, RandomState>;
@@ -159,10 +188,9 @@ where
     }
 }
 
-impl<T, S, ST> BitXor<&ST> for &LengthSet<T, S>
+impl<T, ST> BitXor<&ST> for &LengthSet<T>
 where
     T: Hash + Eq + Len + Clone,
-    S: PrimInt + Unsigned,
     ST: Set<T>,
 {
     type Output = HashSet<T, RandomState>;
@@ -172,10 +200,9 @@ where
     }
 }
 
-impl<T, S, ST> Sub<&ST> for &LengthSet<T, S>
+impl<T, ST> Sub<&ST> for &LengthSet<T>
 where
     T: Hash + Eq + Len + Clone,
-    S: PrimInt + Unsigned,
     ST: Set<T>,
 {
     type Output = HashSet<T, RandomState>;
@@ -185,10 +212,9 @@ where
     }
 }
 
-impl<T, S, ST> PartialEq<ST> for LengthSet<T, S>
+impl<T, ST> PartialEq<ST> for LengthSet<T>
 where
     T: Hash + Eq + Len,
-    S: PrimInt + Unsigned,
     ST: Set<T>,
 {
     fn eq(&self, other: &ST) -> bool {
@@ -200,9 +226,23 @@ where
     }
 }
 
-impl<T, S> Eq for LengthSet<T, S>
+impl<T> Eq for LengthSet<T> where T: Hash + Eq + Len {}
+
+impl<T> Hash for LengthSet<T>
+where
+    T: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self, state);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T> Zeroize for LengthSet<T>
 where
-    T: Hash + Eq + Len,
-    S: PrimInt + Unsigned,
+    T: Zeroize,
 {
+    fn zeroize(&mut self) {
+        self.map.zeroize();
+    }
 }
@@ -0,0 +1,317 @@
+use std::collections::HashSet;
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::{BuildHasher, Hash, Hasher, RandomState};
+use std::ops::{BitAnd, BitOr, BitXor, Range, Sub};
+
+use crate::errors::FrozenError;
+use crate::hashers::hash_unordered;
+use crate::specialized_maps::LengthSliceMap;
+use crate::specialized_sets::{IntoIter, Iter, Set};
+use crate::traits::equivalent::Equivalent;
+use crate::traits::heap_size::HeapSize;
+use crate::traits::len::Len;
+use crate::traits::slice_hash::SliceHash;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// A set that hashes left-aligned slices of its values together with their lengths.
+#[derive(Clone)]
+pub struct LengthSliceSet<T, BH = RandomState> {
+    map: LengthSliceMap<T, (), BH>,
+}
+
+impl<T, BH> LengthSliceSet<T, BH>
+where
+    T: SliceHash + Len + Eq,
+    BH: BuildHasher,
+{
+    #[must_use]
+    pub fn from_vec_with_hasher(payload: Vec<T>, range: Range<usize>, bh: BH) -> Self {
+        Self {
+            map: LengthSliceMap::from_iter_with_hasher(
+                payload.into_iter().map(|x| (x, ())),
+                range,
+                bh,
+            ),
+        }
+    }
+
+    /// Creates a set, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate values.
+    pub fn try_from_vec_with_hasher(
+        payload: Vec<T>,
+        range: Range<usize>,
+        bh: BH,
+    ) -> std::result::Result<Self, FrozenError> {
+        Ok(Self {
+            map: LengthSliceMap::try_from_vec_with_hasher(
+                payload.into_iter().map(|x| (x, ())).collect(),
+                range,
+                bh,
+            )?,
+        })
+    }
+
+    #[must_use]
+    pub fn from_iter_with_hasher<I: IntoIterator<Item = T>>(
+        iter: I,
+        range: Range<usize>,
+        bh: BH,
+    ) -> Self {
+        Self {
+            map: LengthSliceMap::from_iter_with_hasher(
+                iter.into_iter().map(|x| (x, ())),
+                range,
+                bh,
+            ),
+        }
+    }
+
+    #[must_use]
+    pub fn with_hasher<const N: usize>(payload: [T; N], range: Range<usize>, bh: BH) -> Self {
+        Self::from_vec_with_hasher(Vec::from_iter(payload), range, bh)
+    }
+}
+
+impl<T, BH> LengthSliceSet<T, BH>
+where
+    BH: BuildHasher,
+{
+    #[inline]
+    #[must_use]
+    pub fn get<Q>(&self, value: &Q) -> Option<&T>
+    where
+        Q: SliceHash + Len + Equivalent<T>,
+    {
+        Some(self.map.get_key_value(value)?.0)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        Q: SliceHash + Len + Equivalent<T>,
+    {
+        self.get(value).is_some()
+    }
+}
+
+impl<T, BH> LengthSliceSet<T, BH> {
+    #[must_use]
+    pub const fn iter(&self) -> Iter<'_, T> {
+        Iter::new(&self.map.table.entries)
+    }
+
+    #[must_use]
+    pub const fn hasher(&self) -> &BH {
+        self.map.hasher()
+    }
+}
+
+impl<T> LengthSliceSet<T, RandomState>
+where
+    T: SliceHash + Len + Eq,
+{
+    #[must_use]
+    pub fn from_vec(payload: Vec<T>, range: Range<usize>) -> Self {
+        Self::from_vec_with_hasher(payload, range, RandomState::new())
+    }
+
+    /// Creates a set, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate values.
+    pub fn try_from_vec(
+        payload: Vec<T>,
+        range: Range<usize>,
+    ) -> std::result::Result<Self, FrozenError> {
+        Self::try_from_vec_with_hasher(payload, range, RandomState::new())
+    }
+
+    pub fn from_iter<I: IntoIterator<Item = T>>(iter: I, range: Range<usize>) -> Self {
+        Self::from_iter_with_hasher(iter, range, RandomState::new())
+    }
+
+    pub fn from<const N: usize>(payload: [T; N], range: Range<usize>) -> Self {
+        Self::with_hasher(payload, range, RandomState::new())
+    }
+}
+
+impl<T, BH> LengthSliceSet<T, BH> {
+    /// Creates an empty set which will use the given hash builder, without allocating.
+    #[must_use]
+    pub fn empty_with_hasher(bh: BH) -> Self {
+        Self {
+            map: LengthSliceMap::empty_with_hasher(bh),
+        }
+    }
+}
+
+impl<T, BH> Default for LengthSliceSet<T, BH>
+where
+    BH: Default,
+{
+    fn default() -> Self {
+        Self::empty_with_hasher(BH::default())
+    }
+}
+
+impl<T, BH> Len for LengthSliceSet<T, BH> {
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+}
+
+impl<T, BH> HeapSize for LengthSliceSet<T, BH>
+where
+    T: HeapSize,
+{
+    fn heap_bytes(&self) -> usize {
+        self.map.heap_bytes()
+    }
+}
+
+impl<T, BH> Debug for LengthSliceSet<T, BH>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.map.fmt(f) // TODO: can we do better here?
+    }
+}
+
+impl<T, BH> IntoIterator for LengthSliceSet<T, BH> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.map.table.entries)
+    }
+}
+
+impl<'a, T, BH> IntoIterator for &'a LengthSliceSet<T, BH> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, BH> Set<T> for LengthSliceSet<T, BH>
+where
+    T: SliceHash + Len + Eq,
+    BH: BuildHasher,
+{
+    type Iterator<'a>
+        = Iter<'a, T>
+    where
+        T: 'a,
+        BH: 'a;
+
+    fn iter(&self) -> Iter<'_, T> {
+        self.iter()
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.contains(value)
+    }
+}
+
+impl<T, ST, BH> BitOr<&ST> for &LengthSliceSet<T, BH>
+where
+    T: SliceHash + Hash + Len + Eq + Clone,
+    ST: Set<T>,
+    BH: BuildHasher + Default,
+{
+    type Output = HashSet<T, BH>;
+
+    fn bitor(self, rhs: &ST) -> Self::Output {
+        self.union(rhs).cloned().collect()
+    }
+}
+
+impl<T, ST, BH> BitAnd<&ST> for &LengthSliceSet<T, BH>
+where
+    T: SliceHash + Hash + Len + Eq + Clone,
+    ST: Set<T>,
+    BH: BuildHasher + Default,
+{
+    type Output = HashSet<T, BH>;
+
+    fn bitand(self, rhs: &ST) -> Self::Output {
+        self.intersection(rhs).cloned().collect()
+    }
+}
+
+impl<T, ST, BH> BitXor<&ST> for &LengthSliceSet<T, BH>
+where
+    T: SliceHash + Hash + Len + Eq + Clone,
+    ST: Set<T>,
+    BH: BuildHasher + Default,
+{
+    type Output = HashSet<T, BH>;
+
+    fn bitxor(self, rhs: &ST) -> Self::Output {
+        self.symmetric_difference(rhs).cloned().collect()
+    }
+}
+
+impl<T, ST, BH> Sub<&ST> for &LengthSliceSet<T, BH>
+where
+    T: SliceHash + Hash + Len + Eq + Clone,
+    ST: Set<T>,
+    BH: BuildHasher + Default,
+{
+    type Output = HashSet<T, BH>;
+
+    fn sub(self, rhs: &ST) -> Self::Output {
+        self.difference(rhs).cloned().collect()
+    }
+}
+
+impl<T, ST, BH> PartialEq<ST> for LengthSliceSet<T, BH>
+where
+    T: SliceHash + Len + Eq,
+    ST: Set<T>,
+    BH: BuildHasher + Default,
+{
+    fn eq(&self, other: &ST) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+
+        self.iter().all(|value| other.contains(value))
+    }
+}
+
+impl<T, BH> Eq for LengthSliceSet<T, BH>
+where
+    T: SliceHash + Len + Eq,
+    BH: BuildHasher + Default,
+{
+}
+
+impl<T, BH> Hash for LengthSliceSet<T, BH>
+where
+    T: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self, state);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T, BH> Zeroize for LengthSliceSet<T, BH>
+where
+    T: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.map.zeroize();
+    }
+}
@@ -0,0 +1,326 @@
+use std::borrow::Borrow;
+use std::collections::BTreeSet;
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::{Hash, Hasher};
+use std::ops::{BitAnd, BitOr, BitXor, Sub};
+
+use crate::errors::FrozenError;
+use crate::hashers::hash_unordered;
+use crate::specialized_maps::OrderedMap;
+use crate::specialized_sets::{IntoIter, Iter, Set};
+use crate::traits::equivalent::Comparable;
+use crate::traits::heap_size::HeapSize;
+use crate::traits::len::Len;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// A set whose values are sorted, and which uses binary search upon lookup.
+#[derive(Clone)]
+pub struct OrderedSet<T> {
+    map: OrderedMap<T, ()>,
+}
+
+impl<T> OrderedSet<T>
+where
+    T: Ord,
+{
+    #[must_use]
+    pub fn from_vec(payload: Vec<T>) -> Self {
+        Self {
+            map: payload.into_iter().map(|x| (x, ())).collect(),
+        }
+    }
+
+    /// Creates a set, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate values.
+    pub fn try_from_vec(payload: Vec<T>) -> std::result::Result<Self, FrozenError> {
+        Ok(Self {
+            map: OrderedMap::try_from_vec(payload.into_iter().map(|x| (x, ())).collect())?,
+        })
+    }
+}
+
+impl<T> OrderedSet<T> {
+    #[inline]
+    #[must_use]
+    pub fn get<Q>(&self, value: &Q) -> Option<&T>
+    where
+        Q: Comparable<T> + ?Sized,
+    {
+        Some(self.map.get_key_value(value)?.0)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        Q: Comparable<T> + ?Sized,
+    {
+        self.get(value).is_some()
+    }
+
+    /// Returns `true` if the set holds every one of the values.
+    ///
+    /// Values given in ascending order are found in a single pass over the set, as with
+    /// [`OrderedMap::contains_all_keys`].
+    pub fn contains_all<'q, Q, I>(&self, values: I) -> bool
+    where
+        Q: Comparable<T> + ?Sized + 'q,
+        I: IntoIterator<Item = &'q Q>,
+    {
+        self.map.contains_all_keys(values)
+    }
+
+    /// Returns `true` if the set holds at least one of the values.
+    pub fn contains_any<'q, Q, I>(&self, values: I) -> bool
+    where
+        Q: Comparable<T> + ?Sized + 'q,
+        I: IntoIterator<Item = &'q Q>,
+    {
+        self.map.contains_any_key(values)
+    }
+
+    #[must_use]
+    pub const fn iter(&self) -> Iter<'_, T> {
+        Iter::new(&self.map.entries)
+    }
+}
+
+impl<T> OrderedSet<T>
+where
+    T: Borrow<str>,
+{
+    /// An iterator visiting, in order, the values which start with `prefix`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frozen_collections_core::specialized_sets::OrderedSet;
+    ///
+    /// let set = OrderedSet::from_vec(vec!["tomato", "potato", "tomatillo", "tom"]);
+    /// let values: Vec<_> = set.keys_with_prefix("toma").collect();
+    ///
+    /// assert_eq!(values, vec![&"tomatillo", &"tomato"]);
+    /// ```
+    #[must_use]
+    pub fn keys_with_prefix(&self, prefix: &str) -> Iter<'_, T> {
+        Iter::new(&self.map.entries[self.map.prefix_range(prefix)])
+    }
+}
+
+impl<T> OrderedSet<T> {
+    /// Creates an empty set without allocating.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            map: OrderedMap::empty(),
+        }
+    }
+}
+
+impl<T> Default for OrderedSet<T> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<T> Len for OrderedSet<T> {
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+}
+
+impl<T> HeapSize for OrderedSet<T>
+where
+    T: HeapSize,
+{
+    fn heap_bytes(&self) -> usize {
+        self.map.heap_bytes()
+    }
+}
+
+impl<T> Debug for OrderedSet<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T> IntoIterator for OrderedSet<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.map.entries)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a OrderedSet<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for OrderedSet<T>
+where
+    T: Ord,
+{
+    fn from(payload: [T; N]) -> Self {
+        Self::from_vec(Vec::from_iter(payload))
+    }
+}
+
+impl<T> FromIterator<T> for OrderedSet<T>
+where
+    T: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_vec(Vec::from_iter(iter))
+    }
+}
+
+impl<T> Set<T> for OrderedSet<T>
+where
+    T: Ord,
+{
+    type Iterator<'a> = Iter<'a, T>
+    where
+        T: 'a;
+
+    fn iter(&self) -> Iter<'_, T> {
+        self.iter()
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.contains(value)
+    }
+}
+
+impl<T, ST> BitOr<&ST> for &OrderedSet<T>
+where
+    T: Ord + Clone,
+    ST: Set<T>,
+{
+    type Output = BTreeSet<T>;
+
+    fn bitor(self, rhs: &ST) -> Self::Output {
+        self.union(rhs).cloned().collect()
+    }
+}
+
+impl<T, ST> BitAnd<&ST> for &OrderedSet<T>
+where
+    T: Ord + Clone,
+    ST: Set<T>,
+{
+    type Output = BTreeSet<T>;
+
+    fn bitand(self, rhs: &ST) -> Self::Output {
+        self.intersection(rhs).cloned().collect()
+    }
+}
+
+impl<T, ST> BitXor<&ST> for &OrderedSet<T>
+where
+    T: Ord + Clone,
+    ST: Set<T>,
+{
+    type Output = BTreeSet<T>;
+
+    fn bitxor(self, rhs: &ST) -> Self::Output {
+        self.symmetric_difference(rhs).cloned().collect()
+    }
+}
+
+impl<T, ST> Sub<&ST> for &OrderedSet<T>
+where
+    T: Ord + Clone,
+    ST: Set<T>,
+{
+    type Output = BTreeSet<T>;
+
+    fn sub(self, rhs: &ST) -> Self::Output {
+        self.difference(rhs).cloned().collect()
+    }
+}
+
+impl<T, ST> PartialEq<ST> for OrderedSet<T>
+where
+    T: Ord,
+    ST: Set<T>,
+{
+    fn eq(&self, other: &ST) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+
+        self.iter().all(|value| other.contains(value))
+    }
+}
+
+impl<T> Eq for OrderedSet<T> where T: Ord {}
+
+impl<T> Hash for OrderedSet<T>
+where
+    T: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self, state);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T> Zeroize for OrderedSet<T>
+where
+    T: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.map.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contains() {
+        let set = OrderedSet::from([5, 1, 3]);
+        assert!(set.contains(&1));
+        assert!(!set.contains(&2));
+        assert_eq!(vec![&1, &3, &5], set.iter().collect::<Vec<_>>());
+        assert_eq!("{1, 3, 5}", format!("{set:?}"));
+    }
+
+    #[test]
+    fn test_contains_all_and_any() {
+        let set = OrderedSet::from([5, 1, 3]);
+        assert!(set.contains_all(&[1, 5]));
+        assert!(set.contains_all(&[5, 1]));
+        assert!(!set.contains_all(&[1, 2]));
+        assert!(set.contains_any(&[0, 3]));
+        assert!(!set.contains_any(&[0, 2, 4]));
+    }
+
+    #[test]
+    fn test_keys_with_prefix() {
+        let set: OrderedSet<String> = ["alpha", "beta", "alpine", "al", "gamma"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        let values: Vec<_> = set.keys_with_prefix("alp").collect();
+        assert_eq!(vec!["alpha", "alpine"], values);
+        assert!(set.contains("beta"));
+        assert_eq!(0, set.keys_with_prefix("delta").count());
+    }
+}
@@ -1,26 +1,29 @@
-use std::borrow::Borrow;
 use std::collections::HashSet;
 use std::fmt::{Debug, Formatter, Result};
-use std::hash::{BuildHasher, Hash, RandomState};
+use std::hash::{BuildHasher, Hash, Hasher, RandomState};
 use std::ops::{BitAnd, BitOr, BitXor, Range, Sub};
 
-use num_traits::{PrimInt, Unsigned};
-
+use crate::errors::FrozenError;
+use crate::hashers::hash_unordered;
 use crate::specialized_maps::RightSliceMap;
 use crate::specialized_sets::{IntoIter, Iter, Set};
+use crate::traits::equivalent::Equivalent;
+use crate::traits::heap_size::HeapSize;
 use crate::traits::len::Len;
 use crate::traits::slice_hash::SliceHash;
 
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
 /// A set that hashes right-aligned slices of its values.
 #[derive(Clone)]
-pub struct RightSliceSet<T, S = u8, BH = RandomState> {
-    map: RightSliceMap<T, (), S, BH>,
+pub struct RightSliceSet<T, BH = RandomState> {
+    map: RightSliceMap<T, (), BH>,
 }
 
-impl<T, S, BH> RightSliceSet<T, S, BH>
+impl<T, BH> RightSliceSet<T, BH>
 where
     T: SliceHash + Len + Eq,
-    S: PrimInt + Unsigned,
     BH: BuildHasher,
 {
     #[must_use]
@@ -34,6 +37,25 @@ where
         }
     }
 
+    /// Creates a set, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate values.
+    pub fn try_from_vec_with_hasher(
+        payload: Vec<T>,
+        range: Range<usize>,
+        bh: BH,
+    ) -> std::result::Result<Self, FrozenError> {
+        Ok(Self {
+            map: RightSliceMap::try_from_vec_with_hasher(
+                payload.into_iter().map(|x| (x, ())).collect(),
+                range,
+                bh,
+            )?,
+        })
+    }
+
     #[must_use]
     pub fn from_iter_with_hasher<I: IntoIterator<Item = T>>(
         iter: I,
@@ -51,17 +73,15 @@ where
     }
 }
 
-impl<T, S, BH> RightSliceSet<T, S, BH>
+impl<T, BH> RightSliceSet<T, BH>
 where
-    S: PrimInt + Unsigned,
     BH: BuildHasher,
 {
     #[inline]
     #[must_use]
     pub fn get<Q>(&self, value: &Q) -> Option<&T>
     where
-        T: Borrow<Q>,
-        Q: SliceHash + Len + Eq,
+        Q: SliceHash + Len + Equivalent<T>,
     {
         Some(self.map.get_key_value(value)?.0)
     }
@@ -70,16 +90,15 @@ where
     #[must_use]
     pub fn contains<Q>(&self, value: &Q) -> bool
     where
-        T: Borrow<Q>,
-        Q: SliceHash + Len + Eq,
+        Q: SliceHash + Len + Equivalent<T>,
     {
         self.get(value).is_some()
     }
 }
 
-impl<T, S, BH> RightSliceSet<T, S, BH> {
+impl<T, BH> RightSliceSet<T, BH> {
     #[must_use]
-    pub const fn iter(&self) -> Iter<T> {
+    pub const fn iter(&self) -> Iter<'_, T> {
         Iter::new(&self.map.table.entries)
     }
 
@@ -89,16 +108,27 @@ impl<T, S, BH> RightSliceSet<T, S, BH> {
     }
 }
 
-impl<T, S> RightSliceSet<T, S, RandomState>
+impl<T> RightSliceSet<T, RandomState>
 where
     T: SliceHash + Len + Eq,
-    S: PrimInt + Unsigned,
 {
     #[must_use]
     pub fn from_vec(payload: Vec<T>, range: Range<usize>) -> Self {
         Self::from_vec_with_hasher(payload, range, RandomState::new())
     }
 
+    /// Creates a set, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate values.
+    pub fn try_from_vec(
+        payload: Vec<T>,
+        range: Range<usize>,
+    ) -> std::result::Result<Self, FrozenError> {
+        Self::try_from_vec_with_hasher(payload, range, RandomState::new())
+    }
+
     pub fn from_iter<I: IntoIterator<Item = T>>(iter: I, range: Range<usize>) -> Self {
         Self::from_iter_with_hasher(iter, range, RandomState::new())
     }
@@ -108,13 +138,41 @@ where
     }
 }
 
-impl<T, S, BH> Len for RightSliceSet<T, S, BH> {
+impl<T, BH> RightSliceSet<T, BH> {
+    /// Creates an empty set which will use the given hash builder, without allocating.
+    #[must_use]
+    pub fn empty_with_hasher(bh: BH) -> Self {
+        Self {
+            map: RightSliceMap::empty_with_hasher(bh),
+        }
+    }
+}
+
+impl<T, BH> Default for RightSliceSet<T, BH>
+where
+    BH: Default,
+{
+    fn default() -> Self {
+        Self::empty_with_hasher(BH::default())
+    }
+}
+
+impl<T, BH> Len for RightSliceSet<T, BH> {
     fn len(&self) -> usize {
         self.map.len()
     }
 }
 
-impl<T, S, BH> Debug for RightSliceSet<T, S, BH>
+impl<T, BH> HeapSize for RightSliceSet<T, BH>
+where
+    T: HeapSize,
+{
+    fn heap_bytes(&self) -> usize {
+        self.map.heap_bytes()
+    }
+}
+
+impl<T, BH> Debug for RightSliceSet<T, BH>
 where
     T: Debug,
 {
@@ -123,7 +181,7 @@ where
     }
 }
 
-impl<T, S, BH> IntoIterator for RightSliceSet<T, S, BH> {
+impl<T, BH> IntoIterator for RightSliceSet<T, BH> {
     type Item = T;
     type IntoIter = IntoIter<T>;
 
@@ -132,7 +190,7 @@ impl<T, S, BH> IntoIterator for RightSliceSet<T, S, BH> {
     }
 }
 
-impl<'a, T, S, BH> IntoIterator for &'a RightSliceSet<T, S, BH> {
+impl<'a, T, BH> IntoIterator for &'a RightSliceSet<T, BH> {
     type Item = &'a T;
     type IntoIter = Iter<'a, T>;
 
@@ -141,16 +199,15 @@ impl<'a, T, S, BH> IntoIterator for &'a RightSliceSet<T, S, BH> {
     }
 }
 
-impl<T, S, BH> Set<T> for RightSliceSet<T, S, BH>
+impl<T, BH> Set<T> for RightSliceSet<T, BH>
 where
     T: SliceHash + Len + Eq,
-    S: PrimInt + Unsigned,
     BH: BuildHasher,
 {
-    type Iterator<'a> = Iter<'a, T>
+    type Iterator<'a>
+        = Iter<'a, T>
     where
         T: 'a,
-        S: 'a,
         BH: 'a;
 
     fn iter(&self) -> Iter<'_, T> {
@@ -162,10 +219,9 @@ where
     }
 }
 
-impl<T, S, ST, BH> BitOr<&ST> for &RightSliceSet<T, S, BH>
+impl<T, ST, BH> BitOr<&ST> for &RightSliceSet<T, BH>
 where
     T: SliceHash + Hash + Len + Eq + Clone,
-    S: PrimInt + Unsigned,
     ST: Set<T>,
     BH: BuildHasher + Default,
 {
@@ -176,10 +232,9 @@ where
     }
 }
 
-impl<T, S, ST, BH> BitAnd<&ST> for &RightSliceSet<T, S, BH>
+impl<T, ST, BH> BitAnd<&ST> for &RightSliceSet<T, BH>
 where
     T: SliceHash + Hash + Len + Eq + Clone,
-    S: PrimInt + Unsigned,
     ST: Set<T>,
     BH: BuildHasher + Default,
 {
@@ -190,10 +245,9 @@ where
     }
 }
 
-impl<T, S, ST, BH> BitXor<&ST> for &RightSliceSet<T, S, BH>
+impl<T, ST, BH> BitXor<&ST> for &RightSliceSet<T, BH>
 where
     T: SliceHash + Hash + Len + Eq + Clone,
-    S: PrimInt + Unsigned,
     ST: Set<T>,
     BH: BuildHasher + Default,
 {
@@ -204,10 +258,9 @@ where
     }
 }
 
-impl<T, S, ST, BH> Sub<&ST> for &RightSliceSet<T, S, BH>
+impl<T, ST, BH> Sub<&ST> for &RightSliceSet<T, BH>
 where
     T: SliceHash + Hash + Len + Eq + Clone,
-    S: PrimInt + Unsigned,
     ST: Set<T>,
     BH: BuildHasher + Default,
 {
@@ -218,10 +271,9 @@ where
     }
 }
 
-impl<T, S, ST, BH> PartialEq<ST> for RightSliceSet<T, S, BH>
+impl<T, ST, BH> PartialEq<ST> for RightSliceSet<T, BH>
 where
     T: SliceHash + Len + Eq,
-    S: PrimInt + Unsigned,
     ST: Set<T>,
     BH: BuildHasher + Default,
 {
@@ -234,10 +286,28 @@ where
     }
 }
 
-impl<T, S, BH> Eq for RightSliceSet<T, S, BH>
+impl<T, BH> Eq for RightSliceSet<T, BH>
 where
     T: SliceHash + Len + Eq,
-    S: PrimInt + Unsigned,
     BH: BuildHasher + Default,
 {
 }
+
+impl<T, BH> Hash for RightSliceSet<T, BH>
+where
+    T: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self, state);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T, BH> Zeroize for RightSliceSet<T, BH>
+where
+    T: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.map.zeroize();
+    }
+}
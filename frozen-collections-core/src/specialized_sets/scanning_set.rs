@@ -1,13 +1,19 @@
-use std::borrow::Borrow;
 use std::collections::HashSet;
 use std::fmt::{Debug, Formatter, Result};
-use std::hash::{Hash, RandomState};
+use std::hash::{Hash, Hasher, RandomState};
 use std::ops::{BitAnd, BitOr, BitXor, Sub};
 
+use crate::errors::FrozenError;
+use crate::hashers::hash_unordered;
 use crate::specialized_maps::ScanningMap;
 use crate::specialized_sets::{IntoIter, Iter, Set};
+use crate::traits::equivalent::Equivalent;
+use crate::traits::heap_size::HeapSize;
 use crate::traits::len::Len;
 
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
 // TODO: Implement PartialEq + Eq
 
 /// A set that does a linear scan of its values upon lookup, designed for very small payloads.
@@ -26,6 +32,17 @@ where
             map: payload.into_iter().map(|x| (x, ())).collect(),
         }
     }
+
+    /// Creates a set, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate values.
+    pub fn try_from_vec(payload: Vec<T>) -> std::result::Result<Self, FrozenError> {
+        Ok(Self {
+            map: ScanningMap::try_from_vec(payload.into_iter().map(|x| (x, ())).collect())?,
+        })
+    }
 }
 
 impl<T> ScanningSet<T> {
@@ -33,8 +50,7 @@ impl<T> ScanningSet<T> {
     #[must_use]
     pub fn get<Q>(&self, value: &Q) -> Option<&T>
     where
-        T: Borrow<Q>,
-        Q: Eq,
+        Q: Equivalent<T>,
     {
         Some(self.map.get_key_value(value)?.0)
     }
@@ -43,8 +59,7 @@ impl<T> ScanningSet<T> {
     #[must_use]
     pub fn contains<Q>(&self, value: &Q) -> bool
     where
-        T: Borrow<Q>,
-        Q: Eq,
+        Q: Equivalent<T>,
     {
         self.get(value).is_some()
     }
@@ -55,12 +70,37 @@ impl<T> ScanningSet<T> {
     }
 }
 
+impl<T> ScanningSet<T> {
+    /// Creates an empty set without allocating.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            map: ScanningMap::empty(),
+        }
+    }
+}
+
+impl<T> Default for ScanningSet<T> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
 impl<T> Len for ScanningSet<T> {
     fn len(&self) -> usize {
         self.map.len()
     }
 }
 
+impl<T> HeapSize for ScanningSet<T>
+where
+    T: HeapSize,
+{
+    fn heap_bytes(&self) -> usize {
+        self.map.heap_bytes()
+    }
+}
+
 impl<T> Debug for ScanningSet<T>
 where
     T: Debug,
@@ -186,3 +226,22 @@ where
 }
 
 impl<T> Eq for ScanningSet<T> where T: Hash + Eq {}
+
+impl<T> Hash for ScanningSet<T>
+where
+    T: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self, state);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T> Zeroize for ScanningSet<T>
+where
+    T: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.map.zeroize();
+    }
+}
@@ -3,7 +3,7 @@ use std::collections::{BTreeSet, HashSet};
 use std::hash::{BuildHasher, Hash};
 
 use crate::specialized_sets::set_ops::{is_disjoint, is_subset, is_superset};
-use crate::specialized_sets::{Difference, Intersection, SymmetricDifference, Union};
+use crate::specialized_sets::{BitSet, Difference, Intersection, SymmetricDifference, Union};
 use crate::traits::len::Len;
 
 pub trait Set<T>: Len {
@@ -30,6 +30,14 @@ pub trait Set<T>: Len {
     fn iter(&self) -> Self::Iterator<'_>;
     fn contains(&self, value: &T) -> bool;
 
+    /// Returns the bit vector holding the set's values, if the set is stored as one.
+    ///
+    /// Operations between two sets which both return a bit vector combine them a word at a
+    /// time, rather than probing one set for every value of the other.
+    fn as_bit_set(&self) -> Option<&BitSet<T>> {
+        None
+    }
+
     /// Visits the values representing the union,
     /// i.e., all the values in `self` or `other`, without duplicates.
     ///
@@ -49,7 +57,7 @@ pub trait Set<T>: Len {
     /// }
     ///
     /// let union: HashSet<_> = a.union(&b).collect();
-    /// assert_eq!(union, [1, 2, 3, 4].iter().collect());
+    /// assert_eq!(union, [1, 2, 3, 4].iter().collect::<HashSet<_>>());
     /// ```
     fn union<'a, ST>(&'a self, other: &'a ST) -> Union<'a, Self, ST, T>
     where
@@ -104,7 +112,7 @@ pub trait Set<T>: Len {
     /// }
     ///
     /// let diff: HashSet<_> = a.difference(&b).collect();
-    /// assert_eq!(diff, [1].iter().collect());
+    /// assert_eq!(diff, [1].iter().collect::<HashSet<_>>());
     /// ```
     fn difference<'a, ST>(&'a self, other: &'a ST) -> Difference<'a, Self, ST, T>
     where
@@ -139,7 +147,7 @@ pub trait Set<T>: Len {
     /// }
     ///
     /// let intersection: HashSet<_> = a.intersection(&b).collect();
-    /// assert_eq!(intersection, [2, 3].iter().collect());
+    /// assert_eq!(intersection, [2, 3].iter().collect::<HashSet<_>>());
     /// ```
     fn intersection<'a, ST>(&'a self, other: &'a ST) -> Intersection<'a, Self, ST, T>
     where
@@ -6,7 +6,11 @@ where
     S2: Set<T>,
     T: 'a,
 {
-    if s1.len() <= s1.len() {
+    if let (Some(b1), Some(b2)) = (s1.as_bit_set(), s2.as_bit_set()) {
+        return b1.is_disjoint_with(b2);
+    }
+
+    if s1.len() <= s2.len() {
         s1.iter().all(|v| !s2.contains(v))
     } else {
         s2.iter().all(|v| !s1.contains(v))
@@ -19,6 +23,10 @@ where
     S2: Set<T>,
     T: 'a,
 {
+    if let (Some(b1), Some(b2)) = (s1.as_bit_set(), s2.as_bit_set()) {
+        return b1.is_subset_of(b2);
+    }
+
     if s1.len() <= s2.len() {
         s1.iter().all(|v| s2.contains(v))
     } else {
@@ -32,6 +40,10 @@ where
     S2: Set<T>,
     T: 'a,
 {
+    if let (Some(b1), Some(b2)) = (s1.as_bit_set(), s2.as_bit_set()) {
+        return b2.is_subset_of(b1);
+    }
+
     if s2.len() <= s1.len() {
         s2.iter().all(|v| s1.contains(v))
     } else {
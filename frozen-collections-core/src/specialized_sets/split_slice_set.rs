@@ -0,0 +1,337 @@
+use std::collections::HashSet;
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::{BuildHasher, Hash, Hasher, RandomState};
+use std::ops::{BitAnd, BitOr, BitXor, Range, Sub};
+
+use crate::errors::FrozenError;
+use crate::hashers::hash_unordered;
+use crate::specialized_maps::SplitSliceMap;
+use crate::specialized_sets::{IntoIter, Iter, Set};
+use crate::traits::equivalent::Equivalent;
+use crate::traits::heap_size::HeapSize;
+use crate::traits::len::Len;
+use crate::traits::slice_hash::SliceHash;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// A set that hashes a left-aligned slice of its values together with a right-aligned slice.
+#[derive(Clone)]
+pub struct SplitSliceSet<T, BH = RandomState> {
+    map: SplitSliceMap<T, (), BH>,
+}
+
+impl<T, BH> SplitSliceSet<T, BH>
+where
+    T: SliceHash + Len + Eq,
+    BH: BuildHasher,
+{
+    #[must_use]
+    pub fn from_vec_with_hasher(
+        payload: Vec<T>,
+        left: Range<usize>,
+        right: Range<usize>,
+        bh: BH,
+    ) -> Self {
+        Self {
+            map: SplitSliceMap::from_iter_with_hasher(
+                payload.into_iter().map(|x| (x, ())),
+                left,
+                right,
+                bh,
+            ),
+        }
+    }
+
+    /// Creates a set, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate values.
+    pub fn try_from_vec_with_hasher(
+        payload: Vec<T>,
+        left: Range<usize>,
+        right: Range<usize>,
+        bh: BH,
+    ) -> std::result::Result<Self, FrozenError> {
+        Ok(Self {
+            map: SplitSliceMap::try_from_vec_with_hasher(
+                payload.into_iter().map(|x| (x, ())).collect(),
+                left,
+                right,
+                bh,
+            )?,
+        })
+    }
+
+    #[must_use]
+    pub fn from_iter_with_hasher<I: IntoIterator<Item = T>>(
+        iter: I,
+        left: Range<usize>,
+        right: Range<usize>,
+        bh: BH,
+    ) -> Self {
+        Self {
+            map: SplitSliceMap::from_iter_with_hasher(
+                iter.into_iter().map(|x| (x, ())),
+                left,
+                right,
+                bh,
+            ),
+        }
+    }
+
+    #[must_use]
+    pub fn with_hasher<const N: usize>(
+        payload: [T; N],
+        left: Range<usize>,
+        right: Range<usize>,
+        bh: BH,
+    ) -> Self {
+        Self::from_vec_with_hasher(Vec::from_iter(payload), left, right, bh)
+    }
+}
+
+impl<T, BH> SplitSliceSet<T, BH>
+where
+    BH: BuildHasher,
+{
+    #[inline]
+    #[must_use]
+    pub fn get<Q>(&self, value: &Q) -> Option<&T>
+    where
+        Q: SliceHash + Len + Equivalent<T>,
+    {
+        Some(self.map.get_key_value(value)?.0)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        Q: SliceHash + Len + Equivalent<T>,
+    {
+        self.get(value).is_some()
+    }
+}
+
+impl<T, BH> SplitSliceSet<T, BH> {
+    #[must_use]
+    pub const fn iter(&self) -> Iter<'_, T> {
+        Iter::new(&self.map.table.entries)
+    }
+
+    #[must_use]
+    pub const fn hasher(&self) -> &BH {
+        self.map.hasher()
+    }
+}
+
+impl<T> SplitSliceSet<T, RandomState>
+where
+    T: SliceHash + Len + Eq,
+{
+    #[must_use]
+    pub fn from_vec(payload: Vec<T>, left: Range<usize>, right: Range<usize>) -> Self {
+        Self::from_vec_with_hasher(payload, left, right, RandomState::new())
+    }
+
+    /// Creates a set, returning an error instead of panicking when the payload is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the payload holds duplicate values.
+    pub fn try_from_vec(
+        payload: Vec<T>,
+        left: Range<usize>,
+        right: Range<usize>,
+    ) -> std::result::Result<Self, FrozenError> {
+        Self::try_from_vec_with_hasher(payload, left, right, RandomState::new())
+    }
+
+    pub fn from_iter<I: IntoIterator<Item = T>>(
+        iter: I,
+        left: Range<usize>,
+        right: Range<usize>,
+    ) -> Self {
+        Self::from_iter_with_hasher(iter, left, right, RandomState::new())
+    }
+
+    pub fn from<const N: usize>(payload: [T; N], left: Range<usize>, right: Range<usize>) -> Self {
+        Self::with_hasher(payload, left, right, RandomState::new())
+    }
+}
+
+impl<T, BH> SplitSliceSet<T, BH> {
+    /// Creates an empty set which will use the given hash builder, without allocating.
+    #[must_use]
+    pub fn empty_with_hasher(bh: BH) -> Self {
+        Self {
+            map: SplitSliceMap::empty_with_hasher(bh),
+        }
+    }
+}
+
+impl<T, BH> Default for SplitSliceSet<T, BH>
+where
+    BH: Default,
+{
+    fn default() -> Self {
+        Self::empty_with_hasher(BH::default())
+    }
+}
+
+impl<T, BH> Len for SplitSliceSet<T, BH> {
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+}
+
+impl<T, BH> HeapSize for SplitSliceSet<T, BH>
+where
+    T: HeapSize,
+{
+    fn heap_bytes(&self) -> usize {
+        self.map.heap_bytes()
+    }
+}
+
+impl<T, BH> Debug for SplitSliceSet<T, BH>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.map.fmt(f) // TODO: can we do better here?
+    }
+}
+
+impl<T, BH> IntoIterator for SplitSliceSet<T, BH> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.map.table.entries)
+    }
+}
+
+impl<'a, T, BH> IntoIterator for &'a SplitSliceSet<T, BH> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, BH> Set<T> for SplitSliceSet<T, BH>
+where
+    T: SliceHash + Len + Eq,
+    BH: BuildHasher,
+{
+    type Iterator<'a>
+        = Iter<'a, T>
+    where
+        T: 'a,
+        BH: 'a;
+
+    fn iter(&self) -> Iter<'_, T> {
+        self.iter()
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.contains(value)
+    }
+}
+
+impl<T, ST, BH> BitOr<&ST> for &SplitSliceSet<T, BH>
+where
+    T: SliceHash + Hash + Len + Eq + Clone,
+    ST: Set<T>,
+    BH: BuildHasher + Default,
+{
+    type Output = HashSet<T, BH>;
+
+    fn bitor(self, rhs: &ST) -> Self::Output {
+        self.union(rhs).cloned().collect()
+    }
+}
+
+impl<T, ST, BH> BitAnd<&ST> for &SplitSliceSet<T, BH>
+where
+    T: SliceHash + Hash + Len + Eq + Clone,
+    ST: Set<T>,
+    BH: BuildHasher + Default,
+{
+    type Output = HashSet<T, BH>;
+
+    fn bitand(self, rhs: &ST) -> Self::Output {
+        self.intersection(rhs).cloned().collect()
+    }
+}
+
+impl<T, ST, BH> BitXor<&ST> for &SplitSliceSet<T, BH>
+where
+    T: SliceHash + Hash + Len + Eq + Clone,
+    ST: Set<T>,
+    BH: BuildHasher + Default,
+{
+    type Output = HashSet<T, BH>;
+
+    fn bitxor(self, rhs: &ST) -> Self::Output {
+        self.symmetric_difference(rhs).cloned().collect()
+    }
+}
+
+impl<T, ST, BH> Sub<&ST> for &SplitSliceSet<T, BH>
+where
+    T: SliceHash + Hash + Len + Eq + Clone,
+    ST: Set<T>,
+    BH: BuildHasher + Default,
+{
+    type Output = HashSet<T, BH>;
+
+    fn sub(self, rhs: &ST) -> Self::Output {
+        self.difference(rhs).cloned().collect()
+    }
+}
+
+impl<T, ST, BH> PartialEq<ST> for SplitSliceSet<T, BH>
+where
+    T: SliceHash + Len + Eq,
+    ST: Set<T>,
+    BH: BuildHasher + Default,
+{
+    fn eq(&self, other: &ST) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+
+        self.iter().all(|value| other.contains(value))
+    }
+}
+
+impl<T, BH> Eq for SplitSliceSet<T, BH>
+where
+    T: SliceHash + Len + Eq,
+    BH: BuildHasher + Default,
+{
+}
+
+impl<T, BH> Hash for SplitSliceSet<T, BH>
+where
+    T: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self, state);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T, BH> Zeroize for SplitSliceSet<T, BH>
+where
+    T: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.map.zeroize();
+    }
+}
@@ -0,0 +1,38 @@
+/// An enum whose variants carry data, and which can be used as the key of a
+/// [`FrozenEnumMap`](crate::facades::FrozenEnumMap).
+///
+/// Hashing a whole enum key forces maps onto their fully generic implementation, even when
+/// every variant wraps a single integer or string. Instead, an enum key has a companion map
+/// type which dispatches on the variant first and then looks up the variant's payload in a
+/// map dedicated to that variant, so each payload type gets its own specialized layout.
+///
+/// This trait is normally implemented with `#[derive(EnumKey)]`. The derived map uses:
+///
+/// - A [`FrozenMap`](crate::facades::FrozenMap) keyed by the payload for variants with a single field.
+/// - A single optional value for variants without fields.
+/// - A [`FrozenMap`](crate::facades::FrozenMap) keyed by the whole enum for variants with several
+///   fields, which requires the enum to implement [`Hash`](std::hash::Hash) and [`Eq`].
+pub trait EnumKey: Sized {
+    /// The map type used to hold values keyed by this enum.
+    type Map<V>: EnumKeyMap<Self, V>;
+}
+
+/// A map keyed by an [`EnumKey`].
+pub trait EnumKeyMap<K, V> {
+    /// Creates the map from a set of entries.
+    fn from_vec(payload: Vec<(K, V)>) -> Self;
+
+    /// Returns a reference to the value corresponding to the key.
+    fn get(&self, key: &K) -> Option<&V>;
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    fn get_mut(&mut self, key: &K) -> Option<&mut V>;
+
+    /// Returns the number of entries in the map.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if the map contains no entries.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
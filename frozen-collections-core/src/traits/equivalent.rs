@@ -0,0 +1,68 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+
+/// A trait for comparing a lookup value with the keys of a map or the values of a set.
+///
+/// Lookups normally take any type the keys can be borrowed as, but [`Borrow`] can only hand
+/// out a reference to something stored inside the key. This rules out probing a map of
+/// `(String, u32)` keys with a `(&str, u32)`, for example. Implementing this trait on a type
+/// lets it be used for lookups anyway, provided it hashes exactly like the keys it is
+/// equivalent to.
+///
+/// The blanket implementation covers every type the key can be borrowed as, so existing
+/// lookups keep working.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::FrozenMap;
+/// use frozen_collections_core::traits::equivalent::Equivalent;
+///
+/// #[derive(Hash)]
+/// struct Query<'a>(&'a str, u32);
+///
+/// impl Equivalent<(String, u32)> for Query<'_> {
+///     fn equivalent(&self, key: &(String, u32)) -> bool {
+///         self.0 == key.0 && self.1 == key.1
+///     }
+/// }
+///
+/// let map = FrozenMap::from([(("a".to_string(), 1), 10), (("b".to_string(), 2), 20)]);
+/// assert_eq!(map.get(&Query("b", 2)), Some(&20));
+/// assert_eq!(map.get(&Query("b", 1)), None);
+/// ```
+pub trait Equivalent<K: ?Sized> {
+    /// Returns whether the value is equal to the key.
+    fn equivalent(&self, key: &K) -> bool;
+}
+
+impl<Q, K> Equivalent<K> for Q
+where
+    Q: ?Sized + Eq,
+    K: ?Sized + Borrow<Q>,
+{
+    #[inline]
+    fn equivalent(&self, key: &K) -> bool {
+        self == key.borrow()
+    }
+}
+
+/// A trait for ordering a lookup value against the keys of a sorted map or set.
+///
+/// This is the counterpart of [`Equivalent`] for collections which binary search their keys,
+/// and must order values the same way the keys order themselves.
+pub trait Comparable<K: ?Sized>: Equivalent<K> {
+    /// Compares the value with the key.
+    fn compare(&self, key: &K) -> Ordering;
+}
+
+impl<Q, K> Comparable<K> for Q
+where
+    Q: ?Sized + Ord,
+    K: ?Sized + Borrow<Q>,
+{
+    #[inline]
+    fn compare(&self, key: &K) -> Ordering {
+        self.cmp(key.borrow())
+    }
+}
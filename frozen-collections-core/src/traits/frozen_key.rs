@@ -0,0 +1,18 @@
+/// A fieldless enum whose variants are numbered from zero, in declaration order.
+///
+/// Maps pick their layout from what a key's [`Hash`](std::hash::Hash) implementation writes,
+/// and the derived implementation for an enum writes its discriminant. Enums with explicit,
+/// sparse discriminants, such as `NotFound = 404`, therefore end up in a hashed integer map.
+/// Keys implementing this trait hash their variant's index instead, so a map holding every
+/// variant covers a dense integer range, and looking a key up is a single array index.
+///
+/// This trait is normally implemented with `#[derive(FrozenKey)]`, which also implements
+/// [`Hash`](std::hash::Hash) in terms of [`FrozenKey::index`], and so takes the place of
+/// `#[derive(Hash)]`.
+pub trait FrozenKey {
+    /// The number of variants of the enum.
+    const COUNT: usize;
+
+    /// Returns the position of the variant among those of the enum, starting at zero.
+    fn index(&self) -> usize;
+}
@@ -0,0 +1,168 @@
+use std::borrow::Cow;
+use std::mem::{size_of, size_of_val};
+
+/// A trait for measuring the memory a value owns on the heap.
+///
+/// Frozen collections tend to live for as long as the program does, so they are worth
+/// accounting for when keeping track of memory use. The collections implement this trait
+/// whenever their keys and values do, adding the memory owned by the keys and values, such
+/// as the contents of strings, to the arrays the collections allocate.
+///
+/// Values shared through [`Rc`](std::rc::Rc) or [`Arc`](std::sync::Arc) don't implement
+/// the trait, since which of their owners should account for them is up to the program.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::facades::FrozenMap;
+/// use frozen_collections_core::traits::heap_size::HeapSize;
+///
+/// let map = FrozenMap::from_iter((0..100).map(|i| (format!("key{i:03}"), i)));
+///
+/// assert!(map.heap_bytes() >= map.stats().heap_bytes() + 100 * 6);
+/// assert_eq!(map.deep_size_of(), size_of_val(&map) + map.heap_bytes());
+/// ```
+pub trait HeapSize {
+    /// Returns the number of bytes the value owns on the heap.
+    ///
+    /// This doesn't include the bytes of the value itself, nor any overhead of the allocator.
+    fn heap_bytes(&self) -> usize;
+
+    /// Returns the number of bytes taken by the value, including those it owns on the heap.
+    fn deep_size_of(&self) -> usize {
+        size_of_val(self) + self.heap_bytes()
+    }
+}
+
+macro_rules! inline_heap_size {
+    ($($t:ty),*) => {
+        $(
+            impl HeapSize for $t {
+                fn heap_bytes(&self) -> usize {
+                    0
+                }
+            }
+        )*
+    };
+}
+
+inline_heap_size!(
+    (),
+    bool,
+    char,
+    str,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64
+);
+
+impl<T: ?Sized> HeapSize for &T {
+    fn heap_bytes(&self) -> usize {
+        0
+    }
+}
+
+impl HeapSize for String {
+    fn heap_bytes(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl<T: HeapSize> HeapSize for [T] {
+    fn heap_bytes(&self) -> usize {
+        self.iter().map(HeapSize::heap_bytes).sum()
+    }
+}
+
+impl<T: HeapSize, const N: usize> HeapSize for [T; N] {
+    fn heap_bytes(&self) -> usize {
+        self.as_slice().heap_bytes()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Vec<T> {
+    fn heap_bytes(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.as_slice().heap_bytes()
+    }
+}
+
+impl<T: ?Sized + HeapSize> HeapSize for Box<T> {
+    fn heap_bytes(&self) -> usize {
+        T::deep_size_of(self)
+    }
+}
+
+impl<T: HeapSize> HeapSize for Option<T> {
+    fn heap_bytes(&self) -> usize {
+        self.as_ref().map_or(0, HeapSize::heap_bytes)
+    }
+}
+
+impl<T> HeapSize for Cow<'_, T>
+where
+    T: ?Sized + ToOwned,
+    T::Owned: HeapSize,
+{
+    fn heap_bytes(&self) -> usize {
+        match self {
+            Cow::Borrowed(_) => 0,
+            Cow::Owned(owned) => owned.heap_bytes(),
+        }
+    }
+}
+
+impl<A: HeapSize, B: HeapSize> HeapSize for (A, B) {
+    fn heap_bytes(&self) -> usize {
+        self.0.heap_bytes() + self.1.heap_bytes()
+    }
+}
+
+impl<A: HeapSize, B: HeapSize, C: HeapSize> HeapSize for (A, B, C) {
+    fn heap_bytes(&self) -> usize {
+        self.0.heap_bytes() + self.1.heap_bytes() + self.2.heap_bytes()
+    }
+}
+
+/// Returns the number of bytes the keys and values of a map own on the heap.
+pub(crate) fn entries_heap_bytes<'a, K, V, I>(entries: I) -> usize
+where
+    K: HeapSize + 'a,
+    V: HeapSize + 'a,
+    I: Iterator<Item = (&'a K, &'a V)>,
+{
+    entries.map(|(k, v)| k.heap_bytes() + v.heap_bytes()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_owned_bytes() {
+        assert_eq!(0, 42u64.heap_bytes());
+        assert_eq!(0, "abc".heap_bytes());
+        assert_eq!(16, String::with_capacity(16).heap_bytes());
+
+        let v = vec![String::from("abc"), String::from("defg")];
+        assert_eq!(v.capacity() * size_of::<String>() + 7, v.heap_bytes());
+
+        let b: Box<str> = "abcde".into();
+        assert_eq!(5, b.heap_bytes());
+        assert_eq!(size_of::<Box<str>>() + 5, b.deep_size_of());
+
+        assert_eq!(3, Some(String::from("abc")).heap_bytes());
+        assert_eq!(0, Cow::Borrowed("abc").heap_bytes());
+        assert_eq!(3, (1u8, String::from("abc")).heap_bytes());
+    }
+}
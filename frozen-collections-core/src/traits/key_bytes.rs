@@ -0,0 +1,253 @@
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// Produces a stable byte encoding of a key.
+///
+/// Frozen collections analyze the bytes of string-like keys in order to find short subslices or
+/// lengths that are unique enough to be used in place of hashing whole keys. Implementing this
+/// trait lets composite keys, such as a struct made up of a region, service, and endpoint, take
+/// part in that analysis and in subslice hashing. Equality is still determined by the key's [`Eq`]
+/// implementation, the encoding is only used to compute hash codes.
+///
+/// This trait is normally implemented with `#[derive(KeyBytes)]`, which also implements
+/// [`SliceHash`](crate::traits::slice_hash::SliceHash) and [`Len`](crate::traits::len::Len) in terms
+/// of the encoding. The derived encoding concatenates the encoding of each field in declaration order,
+/// separated by a `0xFF` byte, which never appears in UTF-8 text.
+pub trait KeyBytes {
+    /// Appends the encoding of the key to the given buffer.
+    fn write_key_bytes(&self, out: &mut Vec<u8>);
+
+    /// Returns the number of bytes in the encoding of the key.
+    fn key_bytes_len(&self) -> usize {
+        self.key_bytes().len()
+    }
+
+    /// Returns the encoding of the key.
+    #[must_use]
+    fn key_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.write_key_bytes(&mut out);
+        out
+    }
+}
+
+impl KeyBytes for str {
+    fn write_key_bytes(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.as_bytes());
+    }
+
+    fn key_bytes_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl KeyBytes for String {
+    fn write_key_bytes(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.as_bytes());
+    }
+
+    fn key_bytes_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl KeyBytes for [u8] {
+    fn write_key_bytes(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self);
+    }
+
+    fn key_bytes_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl KeyBytes for Vec<u8> {
+    fn write_key_bytes(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self);
+    }
+
+    fn key_bytes_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl KeyBytes for bool {
+    fn write_key_bytes(&self, out: &mut Vec<u8>) {
+        out.push(u8::from(*self));
+    }
+
+    fn key_bytes_len(&self) -> usize {
+        1
+    }
+}
+
+impl KeyBytes for char {
+    fn write_key_bytes(&self, out: &mut Vec<u8>) {
+        let mut buf = [0; 4];
+        out.extend_from_slice(self.encode_utf8(&mut buf).as_bytes());
+    }
+
+    fn key_bytes_len(&self) -> usize {
+        self.len_utf8()
+    }
+}
+
+impl<T: ?Sized + KeyBytes> KeyBytes for &T {
+    fn write_key_bytes(&self, out: &mut Vec<u8>) {
+        T::write_key_bytes(self, out);
+    }
+
+    fn key_bytes_len(&self) -> usize {
+        T::key_bytes_len(self)
+    }
+}
+
+impl<T: ?Sized + KeyBytes> KeyBytes for Box<T> {
+    fn write_key_bytes(&self, out: &mut Vec<u8>) {
+        T::write_key_bytes(self, out);
+    }
+
+    fn key_bytes_len(&self) -> usize {
+        T::key_bytes_len(self)
+    }
+}
+
+impl<T: ?Sized + KeyBytes> KeyBytes for Rc<T> {
+    fn write_key_bytes(&self, out: &mut Vec<u8>) {
+        T::write_key_bytes(self, out);
+    }
+
+    fn key_bytes_len(&self) -> usize {
+        T::key_bytes_len(self)
+    }
+}
+
+impl<T: ?Sized + KeyBytes> KeyBytes for Arc<T> {
+    fn write_key_bytes(&self, out: &mut Vec<u8>) {
+        T::write_key_bytes(self, out);
+    }
+
+    fn key_bytes_len(&self) -> usize {
+        T::key_bytes_len(self)
+    }
+}
+
+impl<T: ?Sized + KeyBytes + ToOwned> KeyBytes for Cow<'_, T> {
+    fn write_key_bytes(&self, out: &mut Vec<u8>) {
+        T::write_key_bytes(self, out);
+    }
+
+    fn key_bytes_len(&self) -> usize {
+        T::key_bytes_len(self)
+    }
+}
+
+macro_rules! int_key_bytes {
+    ($($t:ty),*) => {
+        $(
+            impl KeyBytes for $t {
+                fn write_key_bytes(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_be_bytes());
+                }
+
+                fn key_bytes_len(&self) -> usize {
+                    size_of::<$t>()
+                }
+            }
+        )*
+    };
+}
+
+int_key_bytes!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize);
+
+#[cfg(test)]
+mod tests {
+    use std::hash::RandomState;
+    use std::ops::Range;
+
+    use crate::specialized_maps::{LeftSliceMap, LengthMap};
+    use crate::traits::len::Len;
+    use crate::traits::slice_hash::SliceHash;
+
+    use super::*;
+
+    // What `#[derive(KeyBytes)]` produces for this struct.
+    #[derive(PartialEq, Eq, Debug)]
+    struct Endpoint {
+        region: String,
+        port: u16,
+    }
+
+    impl KeyBytes for Endpoint {
+        fn write_key_bytes(&self, out: &mut Vec<u8>) {
+            KeyBytes::write_key_bytes(&self.region, out);
+            out.push(0xFF);
+            KeyBytes::write_key_bytes(&self.port, out);
+        }
+
+        fn key_bytes_len(&self) -> usize {
+            KeyBytes::key_bytes_len(&self.region) + 1 + KeyBytes::key_bytes_len(&self.port)
+        }
+    }
+
+    impl SliceHash for Endpoint {
+        fn hash<BH: std::hash::BuildHasher>(&self, bh: &BH, range: Range<usize>) -> u64 {
+            SliceHash::hash(KeyBytes::key_bytes(self).as_slice(), bh, range)
+        }
+    }
+
+    impl Len for Endpoint {
+        fn len(&self) -> usize {
+            KeyBytes::key_bytes_len(self)
+        }
+    }
+
+    fn endpoint(region: &str, port: u16) -> Endpoint {
+        Endpoint {
+            region: region.to_string(),
+            port,
+        }
+    }
+
+    #[test]
+    fn test_encoding() {
+        assert_eq!(vec![b'a', b'b'], "ab".key_bytes());
+        assert_eq!(vec![0, 0, 1, 2], 0x0102u32.key_bytes());
+        assert_eq!(vec![1], true.key_bytes());
+        assert_eq!("é".len(), 'é'.key_bytes_len());
+
+        let e = endpoint("us", 0x0304);
+        assert_eq!(vec![b'u', b's', 0xFF, 3, 4], e.key_bytes());
+        assert_eq!(5, e.key_bytes_len());
+        assert_eq!(5, Len::len(&e));
+    }
+
+    #[test]
+    fn test_left_slice_map() {
+        let payload = vec![
+            (endpoint("us-east", 80), 1),
+            (endpoint("us-west", 80), 2),
+            (endpoint("eu-north", 443), 3),
+            (endpoint("ap-south", 8080), 4),
+        ];
+
+        let m = LeftSliceMap::<_, _, _>::from_vec_with_hasher(payload, 3..4, RandomState::new());
+        assert_eq!(Some(&1), m.get(&endpoint("us-east", 80)));
+        assert_eq!(Some(&4), m.get(&endpoint("ap-south", 8080)));
+        assert_eq!(None, m.get(&endpoint("us-east", 81)));
+    }
+
+    #[test]
+    fn test_length_map() {
+        let payload = vec![
+            (endpoint("a", 1), 1),
+            (endpoint("bb", 2), 2),
+            (endpoint("ccc", 3), 3),
+        ];
+
+        let m = LengthMap::<_, _>::from_vec(payload);
+        assert_eq!(Some(&2), m.get(&endpoint("bb", 2)));
+        assert_eq!(None, m.get(&endpoint("bb", 3)));
+    }
+}
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
 use std::ffi::{CStr, CString, OsStr, OsString};
 use std::rc::Rc;
@@ -62,6 +63,18 @@ impl<T> Len for [T] {
     }
 }
 
+impl<T: ?Sized + Len> Len for &T {
+    fn len(&self) -> usize {
+        T::len(self)
+    }
+}
+
+impl<T: ?Sized + Len> Len for &mut T {
+    fn len(&self) -> usize {
+        T::len(self)
+    }
+}
+
 impl<T: ?Sized + Len> Len for Box<T> {
     fn len(&self) -> usize {
         T::len(self)
@@ -80,6 +93,12 @@ impl<T: ?Sized + Len> Len for Arc<T> {
     }
 }
 
+impl<T: ?Sized + Len + ToOwned> Len for Cow<'_, T> {
+    fn len(&self) -> usize {
+        T::len(self)
+    }
+}
+
 impl<K, V> Len for BTreeMap<K, V> {
     fn len(&self) -> usize {
         self.len()
@@ -0,0 +1,161 @@
+use std::collections::{btree_map, hash_map};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+
+use crate::traits::len::Len;
+
+/// A read-only view of a map, which lets generic code accept any frozen map as well as the
+/// standard library's maps.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use frozen_collections_core::facades::FrozenMap;
+/// use frozen_collections_core::traits::map::Map;
+///
+/// fn total<M: Map<&'static str, i32>>(map: &M) -> i32 {
+///     map.values().sum()
+/// }
+///
+/// let frozen = FrozenMap::from([("a", 1), ("b", 2)]);
+/// let hashed = HashMap::from([("a", 1), ("b", 2)]);
+///
+/// assert_eq!(total(&frozen), 3);
+/// assert_eq!(total(&hashed), 3);
+/// ```
+pub trait Map<K, V>: Len {
+    type Iterator<'a>: Iterator<Item = (&'a K, &'a V)>
+    where
+        Self: 'a,
+        K: 'a,
+        V: 'a;
+
+    type KeyIterator<'a>: Iterator<Item = &'a K>
+    where
+        Self: 'a,
+        K: 'a,
+        V: 'a;
+
+    type ValueIterator<'a>: Iterator<Item = &'a V>
+    where
+        Self: 'a,
+        K: 'a,
+        V: 'a;
+
+    /// An iterator visiting all entries in arbitrary order.
+    /// The iterator element type is `(&'a K, &'a V)`.
+    fn iter(&self) -> Self::Iterator<'_>;
+
+    /// An iterator visiting all keys in arbitrary order.
+    /// The iterator element type is `&'a K`.
+    fn keys(&self) -> Self::KeyIterator<'_>;
+
+    /// An iterator visiting all values in arbitrary order.
+    /// The iterator element type is `&'a V`.
+    fn values(&self) -> Self::ValueIterator<'_>;
+
+    /// Returns a reference to the value corresponding to the key.
+    fn get(&self, key: &K) -> Option<&V>;
+
+    /// Returns `true` if the map contains a value for the specified key.
+    fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns a reference to the value corresponding to a key known to be in the map.
+    ///
+    /// This skips the check for a missing key, and maps which can reach a key's entry without
+    /// comparing keys skip the comparison as well.
+    ///
+    /// # Safety
+    ///
+    /// Calling this method with a key which isn't in the map is undefined behavior.
+    unsafe fn get_unchecked(&self, key: &K) -> &V {
+        unsafe { self.get(key).unwrap_unchecked() }
+    }
+}
+
+impl<K, V, BH> Map<K, V> for HashMap<K, V, BH>
+where
+    K: Hash + Eq,
+    BH: BuildHasher,
+{
+    type Iterator<'a> = hash_map::Iter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+        BH: 'a;
+
+    type KeyIterator<'a> = hash_map::Keys<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+        BH: 'a;
+
+    type ValueIterator<'a> = hash_map::Values<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+        BH: 'a;
+
+    fn iter(&self) -> Self::Iterator<'_> {
+        Self::iter(self)
+    }
+
+    fn keys(&self) -> Self::KeyIterator<'_> {
+        Self::keys(self)
+    }
+
+    fn values(&self) -> Self::ValueIterator<'_> {
+        Self::values(self)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        Self::get(self, key)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        Self::contains_key(self, key)
+    }
+}
+
+impl<K, V> Map<K, V> for BTreeMap<K, V>
+where
+    K: Ord,
+{
+    type Iterator<'a> = btree_map::Iter<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    type KeyIterator<'a> = btree_map::Keys<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    type ValueIterator<'a> = btree_map::Values<'a, K, V>
+    where
+        K: 'a,
+        V: 'a;
+
+    fn iter(&self) -> Self::Iterator<'_> {
+        Self::iter(self)
+    }
+
+    fn keys(&self) -> Self::KeyIterator<'_> {
+        Self::keys(self)
+    }
+
+    fn values(&self) -> Self::ValueIterator<'_> {
+        Self::values(self)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        Self::get(self, key)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        Self::contains_key(self, key)
+    }
+}
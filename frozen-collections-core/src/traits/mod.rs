@@ -1,2 +1,9 @@
+pub mod enum_key;
+pub mod equivalent;
+pub mod frozen_key;
+pub mod heap_size;
+pub mod key_bytes;
 pub mod len;
+pub mod map;
+pub mod packed_value;
 pub mod slice_hash;
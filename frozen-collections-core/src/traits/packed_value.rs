@@ -0,0 +1,122 @@
+/// A small value which can be stored in a handful of bits.
+///
+/// Maps of keys to small integers, flags, or field-less enums can store their values
+/// bit-packed in a dense array rather than inline next to the keys, which saves a lot of
+/// memory for large maps. The fewer significant bits a value's representation has, the
+/// less space it takes.
+///
+/// # Examples
+///
+/// ```
+/// use frozen_collections_core::traits::packed_value::PackedValue;
+///
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// enum Level {
+///     Low,
+///     Medium,
+///     High,
+/// }
+///
+/// impl PackedValue for Level {
+///     fn to_bits(self) -> u64 {
+///         self as u64
+///     }
+///
+///     fn from_bits(bits: u64) -> Self {
+///         match bits {
+///             0 => Self::Low,
+///             1 => Self::Medium,
+///             _ => Self::High,
+///         }
+///     }
+/// }
+///
+/// assert_eq!(Level::from_bits(Level::High.to_bits()), Level::High);
+/// ```
+pub trait PackedValue: Copy {
+    /// Converts the value to its bit representation.
+    fn to_bits(self) -> u64;
+
+    /// Converts a bit representation produced by [`Self::to_bits`] back to a value.
+    fn from_bits(bits: u64) -> Self;
+}
+
+impl PackedValue for bool {
+    fn to_bits(self) -> u64 {
+        u64::from(self)
+    }
+
+    fn from_bits(bits: u64) -> Self {
+        bits != 0
+    }
+}
+
+macro_rules! unsigned_packed_value {
+    ($($t:ty),*) => {
+        $(
+            impl PackedValue for $t {
+                fn to_bits(self) -> u64 {
+                    u64::from(self)
+                }
+
+                #[allow(clippy::cast_possible_truncation)]
+                fn from_bits(bits: u64) -> Self {
+                    bits as Self
+                }
+            }
+        )*
+    };
+}
+
+// Signed values are zigzag encoded, so that small negative numbers need few bits too.
+macro_rules! signed_packed_value {
+    ($($t:ty),*) => {
+        $(
+            impl PackedValue for $t {
+                #[allow(clippy::cast_sign_loss)]
+                fn to_bits(self) -> u64 {
+                    let v = i64::from(self);
+                    ((v << 1) ^ (v >> 63)) as u64
+                }
+
+                #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+                fn from_bits(bits: u64) -> Self {
+                    ((bits >> 1) as i64 ^ -((bits & 1) as i64)) as Self
+                }
+            }
+        )*
+    };
+}
+
+unsigned_packed_value!(u8, u16, u32, u64);
+signed_packed_value!(i8, i16, i32, i64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for v in [0u8, 1, 7, 255] {
+            assert_eq!(v, u8::from_bits(v.to_bits()));
+        }
+
+        for v in [0i32, 1, -1, 2, -2, i32::MIN, i32::MAX] {
+            assert_eq!(v, i32::from_bits(v.to_bits()));
+        }
+
+        for v in [i64::MIN, -1, 0, i64::MAX] {
+            assert_eq!(v, i64::from_bits(v.to_bits()));
+        }
+
+        assert!(bool::from_bits(true.to_bits()));
+        assert!(!bool::from_bits(false.to_bits()));
+    }
+
+    #[test]
+    fn test_zigzag_is_compact() {
+        assert_eq!(1, (-1i8).to_bits());
+        assert_eq!(2, 1i8.to_bits());
+        assert_eq!(3, (-2i16).to_bits());
+    }
+}
@@ -1,5 +1,8 @@
+use std::borrow::Cow;
 use std::hash::{BuildHasher, Hasher};
 use std::ops::Range;
+use std::rc::Rc;
+use std::sync::Arc;
 
 /// Enables hashing over a slice of an input.
 pub trait SliceHash {
@@ -18,6 +21,13 @@ impl SliceHash for String {
     }
 }
 
+impl SliceHash for str {
+    #[inline]
+    fn hash<BH: BuildHasher>(&self, bh: &BH, range: Range<usize>) -> u64 {
+        self.as_bytes().hash(bh, range)
+    }
+}
+
 impl SliceHash for [u8] {
     #[inline]
     fn hash<BH: BuildHasher>(&self, bh: &BH, range: Range<usize>) -> u64 {
@@ -27,3 +37,45 @@ impl SliceHash for [u8] {
         h.finish()
     }
 }
+
+impl SliceHash for Vec<u8> {
+    #[inline]
+    fn hash<BH: BuildHasher>(&self, bh: &BH, range: Range<usize>) -> u64 {
+        self.as_slice().hash(bh, range)
+    }
+}
+
+impl<T: ?Sized + SliceHash> SliceHash for Box<T> {
+    #[inline]
+    fn hash<BH: BuildHasher>(&self, bh: &BH, range: Range<usize>) -> u64 {
+        T::hash(self, bh, range)
+    }
+}
+
+impl<T: ?Sized + SliceHash> SliceHash for Rc<T> {
+    #[inline]
+    fn hash<BH: BuildHasher>(&self, bh: &BH, range: Range<usize>) -> u64 {
+        T::hash(self, bh, range)
+    }
+}
+
+impl<T: ?Sized + SliceHash> SliceHash for Arc<T> {
+    #[inline]
+    fn hash<BH: BuildHasher>(&self, bh: &BH, range: Range<usize>) -> u64 {
+        T::hash(self, bh, range)
+    }
+}
+
+impl<T: ?Sized + SliceHash> SliceHash for &T {
+    #[inline]
+    fn hash<BH: BuildHasher>(&self, bh: &BH, range: Range<usize>) -> u64 {
+        T::hash(self, bh, range)
+    }
+}
+
+impl<T: ?Sized + SliceHash + ToOwned> SliceHash for Cow<'_, T> {
+    #[inline]
+    fn hash<BH: BuildHasher>(&self, bh: &BH, range: Range<usize>) -> u64 {
+        T::hash(self, bh, range)
+    }
+}
@@ -2,10 +2,122 @@ use proc_macro::TokenStream;
 
 use proc_macro_error::proc_macro_error;
 
-use frozen_collections_core::macros::frozen_map_macro;
+use frozen_collections_core::macros::{
+    enum_key_derive, frozen_key_derive, frozen_map_macro, frozen_map_static_macro,
+    frozen_set_macro, key_bytes_derive, static_frozen_map_macro, static_frozen_set_macro,
+};
 
+/// Creates a frozen map from a list of literal keys and their values, picking the best map
+/// type for the keys at compile time.
+///
+/// The key type may be given ahead of the entries, as in `frozen_map!(u16, 1: "a", 2: "b")`.
+/// Otherwise it is inferred from the first key, which must then be a string, char, or integer
+/// literal, with the other keys written as the same kind of literal.
+///
+/// When some of the keys aren't literals, such as constants, enum variants, or `concat!`
+/// results, they can't be analyzed at compile time, and a `FrozenMap` is created instead, which
+/// analyzes them at runtime.
+///
+/// Keys given as `&str` are stored as `String`. Spelling out a lifetime, as in `&'static str`,
+/// keeps them borrowed from their literals instead.
+///
+/// The subslice `&str` keys are hashed by can be pinned with a leading `#[hash]` attribute,
+/// naming the side of the keys it's measured from and its range, rather than left to the
+/// analysis. Maps with fewer than three entries scan their keys regardless.
+///
+/// ```ignore
+/// let fm = frozen_map!(#[hash(right, 0..4)] &str, "ab-0001": 1, "cde-0002": 2, "f-0003": 3);
+/// ```
 #[proc_macro]
 #[proc_macro_error]
 pub fn frozen_map(item: TokenStream) -> TokenStream {
     frozen_map_macro(item.into()).into()
 }
+
+/// Creates a `StaticMap` from a list of literal keys and their values, as a constant
+/// expression which can initialize a `static` without any work left for runtime.
+///
+/// The keys are laid out in hash slots at compile time, so they must be integer or string
+/// literals. As with [`frozen_map!`], the key type may be given ahead of the entries, and is
+/// otherwise inferred from the literals. The values must be constant expressions.
+///
+/// ```ignore
+/// static ROUTES: StaticMap<&str, u32> = frozen_map_static!("/": 1, "/login": 2, "/logout": 3);
+/// ```
+#[proc_macro]
+#[proc_macro_error]
+pub fn frozen_map_static(item: TokenStream) -> TokenStream {
+    frozen_map_static_macro(item.into()).into()
+}
+
+/// Creates a frozen set from a list of literal values, picking the best set type for the
+/// values at compile time.
+///
+/// As with [`frozen_map!`], a leading `#[hash(left, a..b)]` or `#[hash(right, a..b)]`
+/// attribute pins the subslice `&str` values are hashed by.
+#[proc_macro]
+#[proc_macro_error]
+pub fn frozen_set(item: TokenStream) -> TokenStream {
+    frozen_set_macro(item.into()).into()
+}
+
+/// Declares a `static` frozen map, created by [`frozen_map!`] the first time it's used.
+///
+/// The declaration names the key and value types ahead of the entries, which are given as
+/// for [`frozen_map!`]. The map is wrapped in a `std::sync::LazyLock`, and has the type
+/// [`frozen_map!`] picks for the entries, with `&str` keys stored as `String` and `&'static str` keys kept borrowed.
+///
+/// ```ignore
+/// static_frozen_map!(pub static COLORS: &str => u32, "red": 1, "green": 2, "blue": 3);
+///
+/// assert_eq!(COLORS.get("green"), Some(&2));
+/// ```
+#[proc_macro]
+#[proc_macro_error]
+pub fn static_frozen_map(item: TokenStream) -> TokenStream {
+    static_frozen_map_macro(item.into()).into()
+}
+
+/// Declares a `static` frozen set, created by [`frozen_set!`] the first time it's used.
+///
+/// The declaration names the value type ahead of the values, which are given as for
+/// [`frozen_set!`]. The set is wrapped in a `std::sync::LazyLock`.
+///
+/// ```ignore
+/// static_frozen_set!(static PRIMES: u32, 2, 3, 5, 7, 11, 13);
+///
+/// assert!(PRIMES.contains(&7));
+/// ```
+#[proc_macro]
+#[proc_macro_error]
+pub fn static_frozen_set(item: TokenStream) -> TokenStream {
+    static_frozen_set_macro(item.into()).into()
+}
+
+/// Implements `KeyBytes`, `SliceHash`, and `Len` for a struct so it can be used as a
+/// key of the slice-based frozen maps and sets.
+#[proc_macro_derive(KeyBytes)]
+#[proc_macro_error]
+pub fn key_bytes(item: TokenStream) -> TokenStream {
+    key_bytes_derive(item.into()).into()
+}
+
+/// Implements `EnumKey` for an enum so it can be used as the key of a `FrozenEnumMap`,
+/// which looks up each variant's payload in a map dedicated to that variant.
+#[proc_macro_derive(EnumKey)]
+#[proc_macro_error]
+pub fn enum_key(item: TokenStream) -> TokenStream {
+    enum_key_derive(item.into()).into()
+}
+
+/// Implements `FrozenKey` and `Hash` for a fieldless enum, hashing each variant as its position
+/// among the enum's variants rather than as its discriminant.
+///
+/// A frozen map holding every variant of the enum then covers a dense integer range, and uses
+/// an `IntegerRangeMap` which looks keys up with a single array index, whatever discriminants
+/// the variants are given. This derive takes the place of `#[derive(Hash)]`.
+#[proc_macro_derive(FrozenKey)]
+#[proc_macro_error]
+pub fn frozen_key(item: TokenStream) -> TokenStream {
+    frozen_key_derive(item.into()).into()
+}
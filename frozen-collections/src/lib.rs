@@ -14,6 +14,42 @@
 pub use frozen_collections_core::*;
 #[doc(inline)]
 pub use {
-    frozen_collections_core::facades::FrozenMap, frozen_collections_core::facades::FrozenSet,
+    frozen_collections_core::errors::FrozenError,
+    frozen_collections_core::facades::AnalysisOptions,
+    frozen_collections_core::facades::ArenaFrozenMap,
+    frozen_collections_core::facades::CaseInsensitiveFrozenMap,
+    frozen_collections_core::facades::CompactFrozenMap,
+    frozen_collections_core::facades::CompactFrozenSet,
+    frozen_collections_core::facades::DeferredFrozenMap,
+    frozen_collections_core::facades::DuplicatePolicy,
+    frozen_collections_core::facades::FrontCodedFrozenMap,
+    frozen_collections_core::facades::FrozenEnumMap,
+    frozen_collections_core::facades::FrozenIndexMap,
+    frozen_collections_core::facades::FrozenInterner,
+    frozen_collections_core::facades::FrozenKeyedSet, frozen_collections_core::facades::FrozenMap,
+    frozen_collections_core::facades::FrozenMapBuilder,
+    frozen_collections_core::facades::FrozenOrderedMap,
+    frozen_collections_core::facades::FrozenPrefixMap, frozen_collections_core::facades::FrozenSet,
+    frozen_collections_core::facades::FrozenStringMap,
+    frozen_collections_core::facades::FrozenTable, frozen_collections_core::facades::FrozenTypeMap,
+    frozen_collections_core::facades::IndirectFrozenMap,
+    frozen_collections_core::facades::KeySuggester,
+    frozen_collections_core::facades::LazyFrozenMap,
+    frozen_collections_core::facades::LookupStream, frozen_collections_core::facades::MapStrategy,
+    frozen_collections_core::facades::NormalizedFrozenMap,
+    frozen_collections_core::facades::PackedFrozenMap,
+    frozen_collections_core::facades::SecondaryIndex,
+    frozen_collections_core::facades::ShardOptions,
+    frozen_collections_core::facades::ShardedFrozenMap,
+    frozen_collections_core::facades::StreamingFrozenMapBuilder,
+    frozen_collections_core::facades::Symbol, frozen_collections_core::facades::UniqueIndex,
     frozen_collections_core::traits::*, frozen_collections_macros::*,
 };
+
+#[cfg(feature = "arc-swap")]
+#[doc(inline)]
+pub use frozen_collections_core::facades::FrozenMapHandle;
+
+#[cfg(feature = "zeroize")]
+#[doc(inline)]
+pub use frozen_collections_core::facades::{ZeroizingFrozenMap, ZeroizingFrozenSet};